@@ -1,10 +1,11 @@
-use std::{net::IpAddr, path::PathBuf, sync::Arc};
+use std::{net::IpAddr, num::NonZeroUsize, path::PathBuf, sync::Arc};
 
 use clap::Parser;
 use ream_consensus_misc::checkpoint::Checkpoint;
 use ream_network_manager::config::ManagerConfig;
 use ream_network_spec::{cli::beacon_network_parser, networks::BeaconNetworkSpec};
-use ream_p2p::bootnodes::Bootnodes;
+use ream_p2p::{bootnodes::Bootnodes, config::TransportKind};
+use ream_storage::cache::DEFAULT_SEEN_MESSAGE_CAPACITY;
 use url::Url;
 
 use crate::cli::constants::{
@@ -93,6 +94,40 @@ pub struct BeaconNodeConfig {
         requires = "execution_endpoint"
     )]
     pub execution_jwt_secret: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_delimiter = ',',
+        help = "Comma-delimited validator indices whose attestation inclusion is tracked and logged"
+    )]
+    pub monitored_validators: Vec<u64>,
+
+    #[arg(
+        long,
+        help = "Record the per-epoch reward and penalty components of the monitored validators in the database",
+        requires = "monitored_validators"
+    )]
+    pub validator_income_history: bool,
+
+    #[arg(
+        long,
+        help = "Drop the execution payloads of finalized blocks from the database and fetch them back from the execution endpoint when the blocks are served",
+        requires = "execution_endpoint"
+    )]
+    pub blinded_block_storage: bool,
+
+    #[arg(
+        long,
+        help = "Number of attestations and aggregates remembered to ignore their duplicates on gossip without verifying them again",
+        default_value_t = NonZeroUsize::new(DEFAULT_SEEN_MESSAGE_CAPACITY).expect("Invalid cache size")
+    )]
+    pub seen_message_cache_capacity: NonZeroUsize,
+
+    #[arg(
+        long,
+        help = "Path to a file holding a token which enables the admin endpoints under /ream/v1/admin. Requests to them must send the token as a bearer token"
+    )]
+    pub admin_token_file: Option<PathBuf>,
 }
 
 impl From<BeaconNodeConfig> for ManagerConfig {
@@ -112,6 +147,11 @@ impl From<BeaconNodeConfig> for ManagerConfig {
             purge_db: config.purge_db,
             execution_endpoint: config.execution_endpoint,
             execution_jwt_secret: config.execution_jwt_secret,
+            monitored_validators: config.monitored_validators,
+            validator_income_history: config.validator_income_history,
+            blinded_block_storage: config.blinded_block_storage,
+            seen_message_cache_capacity: config.seen_message_cache_capacity,
+            transport: TransportKind::Tcp,
         }
     }
 }
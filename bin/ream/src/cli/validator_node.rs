@@ -3,6 +3,7 @@ use std::{net::IpAddr, path::PathBuf, sync::Arc, time::Duration};
 use alloy_primitives::Address;
 use clap::Parser;
 use ream_network_spec::{cli::beacon_network_parser, networks::BeaconNetworkSpec};
+use ream_validator_beacon::constants::DEFAULT_GAS_LIMIT;
 use url::Url;
 
 use crate::cli::constants::{
@@ -68,6 +69,27 @@ pub struct ValidatorNodeConfig {
         requires = "enable_builder"
     )]
     pub mev_relay_url: Option<Url>,
+
+    #[arg(
+        long,
+        help = "Path to a YAML file listing the relays to use for external block building, each with its own url, timeout_ms, min_bid and validators. Will only be used if `enable_builder` is passed.",
+        requires = "enable_builder"
+    )]
+    pub relay_config: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "The gas limit to register with the MEV relay",
+        default_value_t = DEFAULT_GAS_LIMIT,
+        requires = "enable_builder"
+    )]
+    pub gas_limit: u64,
+
+    #[arg(
+        long,
+        help = "The directory for storing the validator registrations sent to the MEV relay"
+    )]
+    pub data_dir: Option<PathBuf>,
 }
 
 pub fn duration_parser(duration_string: &str) -> Result<Duration, String> {
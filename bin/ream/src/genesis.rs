@@ -0,0 +1,73 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ream_network_spec::networks::beacon_network_spec;
+use ream_validator_beacon::beacon_api_client::BeaconApiClient;
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+/// How long before genesis the beacon node starts its network, so the gossipsub meshes are formed
+/// by the time the first block is proposed.
+pub const GENESIS_PRESUBSCRIBE_LEAD: Duration = Duration::from_secs(60);
+
+/// How often the time remaining until genesis is logged.
+pub const GENESIS_COUNTDOWN_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Returns the time remaining until `genesis_time`, or `None` if genesis has passed.
+pub fn time_until_genesis(genesis_time: u64) -> Option<Duration> {
+    (UNIX_EPOCH + Duration::from_secs(genesis_time))
+        .duration_since(SystemTime::now())
+        .ok()
+        .filter(|remaining| !remaining.is_zero())
+}
+
+/// Waits until `lead` before `genesis_time`, logging a countdown. Returns immediately when started
+/// after that point.
+pub async fn wait_for_genesis(genesis_time: u64, lead: Duration) {
+    while let Some(remaining) = time_until_genesis(genesis_time) {
+        let Some(wait) = remaining.checked_sub(lead).filter(|wait| !wait.is_zero()) else {
+            return;
+        };
+
+        info!(
+            "Waiting for genesis: {} seconds remaining",
+            remaining.as_secs()
+        );
+        sleep(wait.min(GENESIS_COUNTDOWN_INTERVAL)).await;
+    }
+}
+
+/// Returns the genesis time of the chain served by the beacon node. A beacon node started before
+/// genesis only serves its API from [`GENESIS_PRESUBSCRIBE_LEAD`] before genesis, so until then
+/// the request is retried, logging a countdown to the earliest genesis time of the network.
+pub async fn fetch_genesis_time(beacon_api_client: &BeaconApiClient) -> u64 {
+    loop {
+        match beacon_api_client.get_genesis().await {
+            Ok(genesis) => return genesis.data.genesis_time,
+            Err(err) => match time_until_genesis(beacon_network_spec().min_genesis_time) {
+                Some(remaining) => info!(
+                    "Waiting for the beacon node to serve genesis: at least {} seconds remaining",
+                    remaining.as_secs()
+                ),
+                None => warn!("Failed to get genesis from the beacon node, retrying: {err}"),
+            },
+        }
+        sleep(GENESIS_COUNTDOWN_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_until_genesis() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("correct time")
+            .as_secs();
+
+        assert_eq!(time_until_genesis(now - 10), None);
+        let remaining = time_until_genesis(now + 100).expect("genesis is in the future");
+        assert!(remaining > Duration::from_secs(98) && remaining <= Duration::from_secs(100));
+    }
+}
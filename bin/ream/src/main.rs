@@ -1,18 +1,21 @@
 use std::{
-    env, process,
+    env, fs, process,
     sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use clap::Parser;
-use ream::cli::{
-    Cli, Commands,
-    account_manager::AccountManagerConfig,
-    beacon_node::BeaconNodeConfig,
-    import_keystores::{load_keystore_directory, load_password_from_config, process_password},
-    lean_node::LeanNodeConfig,
-    validator_node::ValidatorNodeConfig,
-    voluntary_exit::VoluntaryExitConfig,
+use ream::{
+    cli::{
+        Cli, Commands,
+        account_manager::AccountManagerConfig,
+        beacon_node::BeaconNodeConfig,
+        import_keystores::{load_keystore_directory, load_password_from_config, process_password},
+        lean_node::LeanNodeConfig,
+        validator_node::ValidatorNodeConfig,
+        voluntary_exit::VoluntaryExitConfig,
+    },
+    genesis::{GENESIS_PRESUBSCRIBE_LEAD, fetch_genesis_time, wait_for_genesis},
 };
 use ream_beacon_api_types::id::{ID, ValidatorID};
 use ream_checkpoint_sync::initialize_db_from_checkpoint;
@@ -24,19 +27,28 @@ use ream_network_spec::networks::{
 };
 use ream_operation_pool::OperationPool;
 use ream_p2p::network::lean::NetworkService as LeanNetworkService;
-use ream_rpc_beacon::{config::RpcServerConfig, start_server};
+use ream_rpc_beacon::{
+    config::{AdminConfig, LogFilterHandle, RpcServerConfig},
+    start_server,
+};
 use ream_storage::{
     db::{ReamDB, reset_db},
     dir::setup_data_dir,
     tables::Table,
 };
 use ream_validator_beacon::{
-    beacon_api_client::BeaconApiClient, validator::ValidatorService,
+    beacon_api_client::BeaconApiClient,
+    builder::{
+        builder_client::BuilderConfig,
+        registration::{VALIDATOR_REGISTRATIONS_FILE_NAME, ValidatorRegistrations},
+        relay::RelayConfig,
+    },
+    validator::ValidatorService,
     voluntary_exit::process_voluntary_exit,
 };
 use ream_validator_lean::service::ValidatorService as LeanValidatorService;
 use tracing::{error, info};
-use tracing_subscriber::EnvFilter;
+use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, reload, util::SubscriberInitExt};
 
 pub const APP_NAME: &str = "ream";
 
@@ -51,7 +63,20 @@ fn main() {
         false => EnvFilter::builder().parse_lossy(rust_log),
     };
 
-    tracing_subscriber::fmt().with_env_filter(env_filter).init();
+    // The filter can be replaced at runtime through the admin endpoints
+    let (env_filter, env_filter_handle) = reload::Layer::new(env_filter);
+    tracing_subscriber::registry()
+        .with(env_filter)
+        .with(fmt::layer())
+        .init();
+    let log_filter = LogFilterHandle::new(move |filter| {
+        let env_filter = EnvFilter::builder()
+            .parse(filter)
+            .map_err(|err| err.to_string())?;
+        env_filter_handle
+            .reload(env_filter)
+            .map_err(|err| err.to_string())
+    });
 
     let cli = Cli::parse();
 
@@ -63,7 +88,8 @@ fn main() {
             executor_clone.spawn(async move { run_lean_node(*config, executor).await });
         }
         Commands::BeaconNode(config) => {
-            executor_clone.spawn(async move { run_beacon_node(*config, executor).await });
+            executor_clone
+                .spawn(async move { run_beacon_node(*config, executor, log_filter).await });
         }
         Commands::ValidatorNode(config) => {
             executor_clone.spawn(async move { run_validator_node(*config, executor).await });
@@ -123,7 +149,14 @@ pub async fn run_lean_node(config: LeanNodeConfig, executor: ReamExecutor) {
 /// At the end of setup, it starts 2 services:
 /// 1. The HTTP server that serves Beacon API, Engine API.
 /// 2. The P2P network that handles peer discovery (discv5), gossiping (gossipsub) and Req/Resp API.
-pub async fn run_beacon_node(config: BeaconNodeConfig, executor: ReamExecutor) {
+///
+/// `log_filter` is used by the admin endpoints, which are only served if an admin token is
+/// configured.
+pub async fn run_beacon_node(
+    config: BeaconNodeConfig,
+    executor: ReamExecutor,
+    log_filter: LogFilterHandle,
+) {
     info!("starting up beacon node...");
 
     set_beacon_network_spec(config.network.clone());
@@ -154,22 +187,31 @@ pub async fn run_beacon_node(config: BeaconNodeConfig, executor: ReamExecutor) {
         .get_oldest_root()
         .expect("Failed to access slot index provider")
         .expect("No oldest root found");
-    set_genesis_validator_root(
-        ream_db
-            .beacon_state_provider()
-            .get(oldest_root)
-            .expect("Failed to access beacon state provider")
-            .expect("No beacon state found")
-            .genesis_validators_root,
-    );
+    let anchor_state = ream_db
+        .beacon_state_provider()
+        .get(oldest_root)
+        .expect("Failed to access beacon state provider")
+        .expect("No beacon state found");
+    set_genesis_validator_root(anchor_state.genesis_validators_root);
+    let genesis_time = anchor_state.genesis_time;
 
     let operation_pool = Arc::new(OperationPool::default());
 
-    let server_config = RpcServerConfig::new(
+    let mut server_config = RpcServerConfig::new(
         config.http_address,
         config.http_port,
         config.http_allow_origin,
     );
+    let admin_token = config.admin_token_file.as_ref().map(|path| {
+        fs::read_to_string(path)
+            .expect("Unable to read admin token file")
+            .trim()
+            .to_string()
+    });
+
+    // When started before genesis, the network is started shortly before genesis so the gossip
+    // topics are subscribed to in time. The slot clock of the manager first ticks at genesis
+    wait_for_genesis(genesis_time, GENESIS_PRESUBSCRIBE_LEAD).await;
 
     let network_manager = NetworkManagerService::new(
         executor.clone(),
@@ -185,6 +227,23 @@ pub async fn run_beacon_node(config: BeaconNodeConfig, executor: ReamExecutor) {
 
     let execution_engine = network_manager.beacon_chain.execution_engine.clone();
 
+    let event_sender = network_manager.beacon_chain.event_sender.clone();
+
+    let beacon_chain = network_manager.beacon_chain.clone();
+
+    let cached_db = network_manager.cached_db.clone();
+
+    let p2p_sender = network_manager.p2p_sender();
+
+    if let Some(token) = admin_token {
+        assert!(!token.is_empty(), "Admin token file must not be empty");
+        server_config = server_config.with_admin(AdminConfig {
+            token,
+            log_filter,
+            queue_dump_sender: network_manager.queue_dump_sender(),
+        });
+    }
+
     let network_future = executor.spawn(async move {
         network_manager.start().await;
     });
@@ -196,6 +255,10 @@ pub async fn run_beacon_node(config: BeaconNodeConfig, executor: ReamExecutor) {
             network_state,
             operation_pool,
             execution_engine,
+            event_sender,
+            beacon_chain,
+            cached_db,
+            p2p_sender,
         )
         .await
     });
@@ -235,16 +298,37 @@ pub async fn run_validator_node(config: ValidatorNodeConfig, executor: ReamExecu
         })
         .collect::<Vec<_>>();
 
+    let mut relays = config
+        .relay_config
+        .map(|path| RelayConfig::load_from_file(path).expect("Failed to load relay configuration"))
+        .unwrap_or_default();
+    relays.extend(config.mev_relay_url.map(RelayConfig::new));
+    let builder_config = BuilderConfig {
+        builder_enabled: config.enable_builder,
+        relays,
+        gas_limit: config.gas_limit,
+    };
+    let validator_registrations = ValidatorRegistrations::load(
+        setup_data_dir(APP_NAME, config.data_dir, false)
+            .expect("Unable to initialize data directory")
+            .join(VALIDATOR_REGISTRATIONS_FILE_NAME),
+    )
+    .expect("Failed to load validator registrations");
+
     let validator_service = ValidatorService::new(
         keystores,
         config.suggested_fee_recipient,
         config.beacon_api_endpoint,
         config.request_timeout,
         executor,
+        Some(builder_config),
+        validator_registrations,
     )
     .expect("Failed to create validator service");
 
-    validator_service.start().await;
+    let genesis_time = fetch_genesis_time(&validator_service.beacon_api_client).await;
+    wait_for_genesis(genesis_time, Duration::ZERO).await;
+    validator_service.start(genesis_time).await;
 }
 
 /// Runs the account manager.
@@ -1,5 +1,8 @@
 use ream_consensus_beacon::{
-    electra::{beacon_block::BeaconBlock, blinded_beacon_block::BlindedBeaconBlock},
+    electra::{
+        beacon_block::{BeaconBlock, SignedBeaconBlock},
+        blinded_beacon_block::BlindedBeaconBlock,
+    },
     execution_engine::rpc_types::get_blobs::Blob,
     polynomial_commitments::kzg_proof::KZGProof,
 };
@@ -48,3 +51,12 @@ pub struct FullBlockData {
     pub kzg_proofs: Vec<KZGProof>,
     pub blobs: Vec<Blob>,
 }
+
+/// A signed block published together with the blobs and proofs of its commitments, from which the
+/// beacon node builds the blob sidecars to gossip.
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct SignedBlockContents {
+    pub signed_block: SignedBeaconBlock,
+    pub kzg_proofs: Vec<KZGProof>,
+    pub blobs: Vec<Blob>,
+}
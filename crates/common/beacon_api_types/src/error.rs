@@ -15,6 +15,9 @@ pub enum ApiError {
     #[error("Internal Server Error: {0}")]
     InternalError(String),
 
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
     #[error("Invalid parameter: {0}")]
     InvalidParameter(String),
 
@@ -39,6 +42,7 @@ impl ResponseError for ApiError {
             ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
             ApiError::NotFound(_) => StatusCode::NOT_FOUND,
             ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
             ApiError::InvalidParameter(_) => StatusCode::BAD_REQUEST,
             ApiError::ValidatorNotFound(_) => StatusCode::NOT_FOUND,
             ApiError::TooManyValidatorsIds => StatusCode::URI_TOO_LONG,
@@ -0,0 +1,41 @@
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+
+pub const CHAIN_REORG_EVENT_TOPIC: &str = "chain_reorg";
+
+/// An event published on the `/eth/v1/events` server-sent events stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BeaconEvent {
+    ChainReorg(ChainReorgEvent),
+}
+
+impl BeaconEvent {
+    /// Returns the topic name of the event, which is used as the SSE event type.
+    pub fn topic(&self) -> &'static str {
+        match self {
+            BeaconEvent::ChainReorg(_) => CHAIN_REORG_EVENT_TOPIC,
+        }
+    }
+
+    /// Returns the JSON encoded data of the event.
+    pub fn data(&self) -> serde_json::Result<String> {
+        match self {
+            BeaconEvent::ChainReorg(event) => serde_json::to_string(event),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainReorgEvent {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub slot: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub depth: u64,
+    pub old_head_block: B256,
+    pub new_head_block: B256,
+    pub old_head_state: B256,
+    pub new_head_state: B256,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub epoch: u64,
+    pub execution_optimistic: bool,
+}
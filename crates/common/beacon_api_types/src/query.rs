@@ -2,13 +2,19 @@ use alloy_primitives::B256;
 use serde::{Deserialize, Serialize};
 
 use super::id::ValidatorID;
-use crate::validator::ValidatorStatus;
+use crate::{block::BroadcastValidation, validator::ValidatorStatus};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EpochQuery {
     pub epoch: Option<u64>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct EpochRangeQuery {
+    pub start_epoch: Option<u64>,
+    pub end_epoch: Option<u64>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct SlotQuery {
     pub slot: Option<u64>,
@@ -44,12 +50,36 @@ pub struct StatusQuery {
     pub status: Option<Vec<ValidatorStatus>>,
 }
 
+#[derive(Default, Debug, Deserialize)]
+pub struct PaginationQuery {
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub struct BroadcastValidationQuery {
+    #[serde(default)]
+    pub broadcast_validation: BroadcastValidation,
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub struct EventTopicQuery {
+    pub topics: Vec<String>,
+}
+
 #[derive(Default, Debug, Deserialize)]
 pub struct AttestationQuery {
     pub slot: u64,
     pub committee_index: u64,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SyncCommitteeContributionQuery {
+    pub slot: u64,
+    pub subcommittee_index: u64,
+    pub beacon_block_root: B256,
+}
+
 impl StatusQuery {
     pub fn has_status(&self) -> bool {
         match &self.status {
@@ -1,14 +1,28 @@
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
-use anyhow::bail;
+use alloy_primitives::B256;
+use anyhow::{anyhow, bail, ensure};
+use arc_swap::ArcSwapOption;
+use ream_beacon_api_types::event::{BeaconEvent, ChainReorgEvent};
 use ream_consensus_beacon::{
-    attestation::Attestation, attester_slashing::AttesterSlashing,
-    electra::beacon_block::SignedBeaconBlock,
+    attestation::Attestation,
+    attester_slashing::AttesterSlashing,
+    electra::{
+        beacon_block::{BeaconBlock, SignedBeaconBlock},
+        beacon_state::BeaconState,
+    },
+    pubkey_cache::PubkeyCache,
+    tree_hash_cache::BeaconStateTreeHashCache,
+    validator_income::ValidatorIncome,
+};
+use ream_consensus_misc::{
+    constants::{EPOCHS_PER_ETH1_VOTING_PERIOD, SLOTS_PER_EPOCH, genesis_validators_root},
+    eth_1_data::Eth1Data,
+    misc::compute_epoch_at_slot,
 };
-use ream_consensus_misc::constants::genesis_validators_root;
 use ream_execution_engine::ExecutionEngine;
 use ream_fork_choice::{
-    handlers::{on_attestation, on_attester_slashing, on_block, on_tick},
+    handlers::{on_attestation, on_attester_slashing, on_block_with_cache, on_tick},
     store::Store,
 };
 use ream_network_spec::networks::beacon_network_spec;
@@ -16,15 +30,56 @@ use ream_operation_pool::OperationPool;
 use ream_p2p::req_resp::messages::status::Status;
 use ream_storage::{
     db::ReamDB,
+    errors::StoreError,
     tables::{Field, Table},
 };
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, broadcast};
 use tracing::warn;
+use tree_hash::TreeHash;
+
+use crate::{
+    canonical_head::CanonicalHead,
+    eth1_cache::Eth1Cache,
+    node_metrics::{QueueDepths, SyncMetrics},
+    reorg::{ReorgMetrics, find_common_ancestor},
+    validator_income::compute_income,
+    validator_monitor::ValidatorMonitor,
+};
+
+/// The number of events buffered for each subscriber of the event stream.
+pub const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// The maximum number of attestations waiting for their slot to pass.
+pub const MAX_QUEUED_ATTESTATIONS: usize = 16384;
+
+/// Attestations from a slot further than this ahead of the local clock are not queued.
+pub const MAX_QUEUED_ATTESTATION_FUTURE_SLOTS: u64 = 1;
+
+/// The number of blocks blinded under one hold of the store lock.
+pub const MAX_BLOCKS_TO_BLIND_PER_BATCH: usize = 64;
 
 /// BeaconChain is the main struct which manages the nodes local beacon chain.
 pub struct BeaconChain {
     pub store: Mutex<Store>,
     pub execution_engine: Option<ExecutionEngine>,
+    pub validator_monitor: Mutex<ValidatorMonitor>,
+    pub canonical_head: ArcSwapOption<CanonicalHead>,
+    pub reorg_metrics: Mutex<ReorgMetrics>,
+    pub event_sender: broadcast::Sender<BeaconEvent>,
+    pub eth1_cache: Mutex<Eth1Cache>,
+    pub queue_depths: Mutex<QueueDepths>,
+    pub sync_metrics: Mutex<SyncMetrics>,
+    /// Whether the payloads of finalized blocks are dropped from storage, to be fetched back from
+    /// the execution engine when the blocks are served.
+    pub blinded_block_storage: bool,
+    /// Attestations received on the wire whose slot is not in the past yet, which can only affect
+    /// fork choice from the next slot on.
+    queued_attestations: Mutex<Vec<Attestation>>,
+    /// The tree hash cache of the last imported state, which the next block usually builds on.
+    tree_hash_cache: Mutex<BeaconStateTreeHashCache>,
+    /// The validator indices by public key of the last imported state, for the deposits of its
+    /// children, along with the root of its block.
+    pubkey_cache: Mutex<(B256, PubkeyCache)>,
 }
 
 impl BeaconChain {
@@ -33,32 +88,179 @@ impl BeaconChain {
         db: ReamDB,
         operation_pool: Arc<OperationPool>,
         execution_engine: Option<ExecutionEngine>,
+        monitored_validators: Vec<u64>,
+        validator_income_history: bool,
+        blinded_block_storage: bool,
     ) -> Self {
         Self {
             store: Mutex::new(Store::new(db, operation_pool)),
             execution_engine,
+            validator_monitor: Mutex::new(
+                ValidatorMonitor::new(monitored_validators)
+                    .with_income_history(validator_income_history),
+            ),
+            canonical_head: ArcSwapOption::empty(),
+            reorg_metrics: Mutex::new(ReorgMetrics::default()),
+            event_sender: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+            eth1_cache: Mutex::new(Eth1Cache::default()),
+            queue_depths: Mutex::new(QueueDepths::default()),
+            sync_metrics: Mutex::new(SyncMetrics::default()),
+            blinded_block_storage,
+            queued_attestations: Mutex::new(vec![]),
+            tree_hash_cache: Mutex::new(BeaconStateTreeHashCache::default()),
+            pubkey_cache: Mutex::new((B256::ZERO, PubkeyCache::default())),
         }
     }
 
     pub async fn process_block(&self, signed_block: SignedBeaconBlock) -> anyhow::Result<()> {
         let mut store = self.store.lock().await;
-        on_block(
-            &mut store,
-            &signed_block,
-            &self.execution_engine,
-            signed_block.message.slot >= beacon_network_spec().slot_n_days_ago(17),
-        )
-        .await?;
+        {
+            let mut pubkey_cache = self.pubkey_cache.lock().await;
+            let (cached_block_root, pubkey_cache) = &mut *pubkey_cache;
+            // The registries of two forks may hold different validators at the same indices, which
+            // the cache cannot tell apart, so it is only reused for a child of the state it indexes
+            if *cached_block_root != signed_block.message.parent_root {
+                *pubkey_cache = PubkeyCache::default();
+            }
+            *cached_block_root = B256::ZERO;
+            on_block_with_cache(
+                &mut store,
+                &signed_block,
+                &self.execution_engine,
+                signed_block.message.slot >= beacon_network_spec().slot_n_days_ago(17),
+                &mut *self.tree_hash_cache.lock().await,
+                pubkey_cache,
+            )
+            .await?;
+            *cached_block_root = signed_block.message.block_root();
+        }
+
+        let mut validator_monitor = self.validator_monitor.lock().await;
+        if validator_monitor.is_enabled()
+            && let Ok(Some(state)) = store
+                .db
+                .beacon_state_provider()
+                .get(signed_block.message.tree_hash_root())
+        {
+            if let Err(err) = validator_monitor.register_block(&state, &signed_block.message) {
+                warn!("Failed to register block in validator monitor: {err}");
+            }
+            if validator_monitor.records_income()
+                && let Err(err) = record_validator_income(
+                    &store.db,
+                    &state,
+                    &signed_block.message,
+                    validator_monitor.monitored_validators(),
+                )
+            {
+                warn!("Failed to record validator income: {err}");
+            }
+        }
+
+        if let Err(err) = self.update_head(&store).await {
+            warn!("Failed to update head: {err}");
+        }
+        drop(validator_monitor);
+        drop(store);
+
+        if self.blinded_block_storage
+            && let Err(err) = self.blind_finalized_blocks().await
+        {
+            warn!("Failed to blind finalized blocks: {err}");
+        }
+
         Ok(())
     }
 
+    /// Drops the execution payloads of the ancestors of the finalized block, walking back along
+    /// their parent roots in batches of [`MAX_BLOCKS_TO_BLIND_PER_BATCH`] blocks which each take
+    /// the store lock. The finalized block itself is kept whole, since fork choice reads it to
+    /// find the checkpoint blocks of new blocks and attestations. Once the walk reaches the blocks
+    /// blinded by the previous walk, the slot of the finalized block is persisted so that the next
+    /// walk, including after a restart, stops there.
+    async fn blind_finalized_blocks(&self) -> anyhow::Result<()> {
+        let (finalized_slot, next_slot_to_blind, mut block_root) = {
+            let store = self.store.lock().await;
+            let finalized_root = store.db.finalized_checkpoint_provider().get()?.root;
+            let Some((finalized_slot, parent_root)) =
+                get_slot_and_parent_root(&store.db, finalized_root)?
+            else {
+                return Ok(());
+            };
+            let next_slot_to_blind = match store.db.next_slot_to_blind_provider().get() {
+                Ok(slot) => slot,
+                Err(StoreError::FieldNotInitilized) => 0,
+                Err(err) => return Err(err.into()),
+            };
+            (finalized_slot, next_slot_to_blind, parent_root)
+        };
+        if finalized_slot <= next_slot_to_blind {
+            return Ok(());
+        }
+
+        loop {
+            let store = self.store.lock().await;
+            for _ in 0..MAX_BLOCKS_TO_BLIND_PER_BATCH {
+                let Some((slot, parent_root)) = get_slot_and_parent_root(&store.db, block_root)?
+                else {
+                    // The walk reached the oldest stored block
+                    return Self::set_next_slot_to_blind(&store.db, finalized_slot);
+                };
+                if slot < next_slot_to_blind {
+                    return Self::set_next_slot_to_blind(&store.db, finalized_slot);
+                }
+                store.db.beacon_block_provider().blind(block_root)?;
+                block_root = parent_root;
+            }
+        }
+    }
+
+    /// Persists `slot` as the first slot whose block may still be stored with its payload, unless
+    /// a concurrent walk from a later finalized block already persisted a later slot.
+    fn set_next_slot_to_blind(db: &ReamDB, slot: u64) -> anyhow::Result<()> {
+        match db.next_slot_to_blind_provider().get() {
+            Ok(next_slot_to_blind) if next_slot_to_blind >= slot => {}
+            Ok(_) | Err(StoreError::FieldNotInitilized) => {
+                db.next_slot_to_blind_provider().insert(slot)?
+            }
+            Err(err) => return Err(err.into()),
+        }
+        Ok(())
+    }
+
+    /// Runs the state transition of a block on a copy of its parent state without importing it,
+    /// verifying the proposer signature and the state root. The execution payload is not sent to
+    /// the execution engine.
+    pub async fn verify_block_consensus(
+        &self,
+        signed_block: &SignedBeaconBlock,
+    ) -> anyhow::Result<()> {
+        let mut state = self
+            .store
+            .lock()
+            .await
+            .db
+            .beacon_state_provider()
+            .get(signed_block.message.parent_root)?
+            .ok_or_else(|| {
+                anyhow!(
+                    "Failed to find parent state {}",
+                    signed_block.message.parent_root
+                )
+            })?;
+        state
+            .state_transition(signed_block, true, &None::<ExecutionEngine>)
+            .await
+    }
+
     pub async fn process_attester_slashing(
         &self,
         attester_slashing: AttesterSlashing,
     ) -> anyhow::Result<()> {
         let mut store = self.store.lock().await;
         on_attester_slashing(&mut store, attester_slashing)?;
-        Ok(())
+        // The weights of the slashed validators are removed from fork choice
+        self.update_head(&store).await
     }
 
     pub async fn process_attestation(
@@ -67,17 +269,192 @@ impl BeaconChain {
         is_from_block: bool,
     ) -> anyhow::Result<()> {
         let mut store = self.store.lock().await;
-        on_attestation(&mut store, attestation, is_from_block)?;
-        Ok(())
+        let current_slot = store.get_current_slot()?;
+
+        // Attestations can only affect the fork choice of subsequent slots, so the ones from the
+        // current slot are queued until it has passed
+        if !is_from_block && attestation.data.slot >= current_slot {
+            drop(store);
+            ensure!(
+                attestation.data.slot <= current_slot + MAX_QUEUED_ATTESTATION_FUTURE_SLOTS,
+                "Attestation slot {} is too far ahead of the current slot {current_slot}",
+                attestation.data.slot
+            );
+            let mut queued_attestations = self.queued_attestations.lock().await;
+            ensure!(
+                queued_attestations.len() < MAX_QUEUED_ATTESTATIONS,
+                "Attestation queue is full"
+            );
+            queued_attestations.push(attestation);
+            return Ok(());
+        }
+
+        // Running fork choice for every gossip attestation is too costly, the head snapshot takes
+        // the new votes at the next tick or block import
+        on_attestation(&mut store, attestation, is_from_block)
+    }
+
+    /// Removes the queued attestations whose slot is before `current_slot` from the queue, and
+    /// returns the ones which are recent enough to be applied to fork choice.
+    async fn take_ready_attestations(&self, current_slot: u64) -> Vec<Attestation> {
+        let previous_epoch = compute_epoch_at_slot(current_slot).saturating_sub(1);
+        self.queued_attestations
+            .lock()
+            .await
+            .extract_if(.., |attestation| attestation.data.slot < current_slot)
+            .filter(|attestation| attestation.data.target.epoch >= previous_epoch)
+            .collect()
     }
 
     pub async fn process_tick(&self, time: u64) -> anyhow::Result<()> {
+        let current_slot = {
+            let mut store = self.store.lock().await;
+            on_tick(&mut store, time)?;
+            store.get_current_slot()?
+        };
+
+        let ready_attestations = self.take_ready_attestations(current_slot).await;
         let mut store = self.store.lock().await;
-        on_tick(&mut store, time)?;
+        for attestation in ready_attestations {
+            if let Err(err) = on_attestation(&mut store, attestation, false) {
+                warn!("Failed to apply queued attestation: {err}");
+            }
+        }
+        self.validator_monitor
+            .lock()
+            .await
+            .process_epoch(compute_epoch_at_slot(store.get_current_slot()?));
+
+        if let Err(err) = self.update_head(&store).await {
+            warn!("Failed to update head: {err}");
+        }
+
+        Ok(())
+    }
+
+    /// Fetches the next eth1 blocks which fell behind the follow distance, at most
+    /// [`MAX_ETH1_BLOCKS_PER_UPDATE`](crate::eth1_cache::MAX_ETH1_BLOCKS_PER_UPDATE) of them, and
+    /// drops the blocks which can no longer be voted for. The cache is not locked while the
+    /// execution client is requested.
+    pub async fn update_eth1_cache(&self, time: u64) -> anyhow::Result<()> {
+        let Some(execution_engine) = &self.execution_engine else {
+            return Ok(());
+        };
+
+        let latest_block_number = execution_engine.get_latest_block_number().await?;
+        let block_range = self
+            .eth1_cache
+            .lock()
+            .await
+            .next_block_range(latest_block_number);
+        let mut blocks = vec![];
+        for block_number in block_range {
+            blocks.push(
+                execution_engine
+                    .get_eth1_block(beacon_network_spec().deposit_contract_address, block_number)
+                    .await?,
+            );
+        }
+
+        let voting_period_duration = EPOCHS_PER_ETH1_VOTING_PERIOD
+            * SLOTS_PER_EPOCH
+            * beacon_network_spec().seconds_per_slot;
+        let mut eth1_cache = self.eth1_cache.lock().await;
+        for block in blocks {
+            eth1_cache.insert(block);
+        }
+        eth1_cache.prune(time.saturating_sub(voting_period_duration));
+        Ok(())
+    }
+
+    /// Returns the eth1 data to vote for in a block built on `state`: the majority vote of the
+    /// current voting period among the cached candidate blocks, else the latest candidate block.
+    pub async fn get_eth1_vote(&self, state: &BeaconState) -> Eth1Data {
+        state.get_eth1_vote(&self.eth1_cache.lock().await.blocks())
+    }
+
+    /// Returns the latest canonical head snapshot without locking the fork choice store.
+    pub fn canonical_head(&self) -> Option<Arc<CanonicalHead>> {
+        self.canonical_head.load_full()
+    }
+
+    /// Returns the canonical head root from the head snapshot, locking the store to run fork
+    /// choice only if no snapshot has been taken yet.
+    pub async fn head_root(&self) -> anyhow::Result<B256> {
+        match self.canonical_head() {
+            Some(canonical_head) => Ok(canonical_head.head_block_root),
+            None => self.store.lock().await.get_head(),
+        }
+    }
+
+    /// Runs fork choice outside of the regular ticks and blocks, and returns the new head root.
+    pub async fn recompute_head(&self) -> anyhow::Result<B256> {
+        self.update_head(&*self.store.lock().await).await?;
+        self.head_root().await
+    }
+
+    /// Swaps in a new canonical head snapshot after fork choice ran. If the new head does not
+    /// descend from the previous head, the reorg is logged, counted and published as a
+    /// `chain_reorg` event.
+    async fn update_head(&self, store: &Store) -> anyhow::Result<()> {
+        let new_head = CanonicalHead::from_store(store, store.get_head()?)?;
+        let Some(old_head) = self.canonical_head.swap(Some(Arc::new(new_head))) else {
+            return Ok(());
+        };
+        if old_head.head_block_root == new_head.head_block_root
+            || store.get_ancestor(new_head.head_block_root, old_head.head_state.slot)?
+                == old_head.head_block_root
+        {
+            return Ok(());
+        }
+
+        let (common_ancestor_root, common_ancestor_slot) = find_common_ancestor(
+            store,
+            (old_head.head_block_root, old_head.head_state.slot),
+            (new_head.head_block_root, new_head.head_state.slot),
+        )?;
+        let depth = old_head.head_state.slot - common_ancestor_slot;
+        self.reorg_metrics.lock().await.observe(depth);
+
+        warn!(
+            depth,
+            old_head_slot = old_head.head_state.slot,
+            old_head_root = %old_head.head_block_root,
+            new_head_slot = new_head.head_state.slot,
+            new_head_root = %new_head.head_block_root,
+            common_ancestor_slot,
+            common_ancestor_root = %common_ancestor_root,
+            "Chain reorg detected"
+        );
+
+        // Sending only fails if there are no subscribers, which is fine
+        let _ = self
+            .event_sender
+            .send(BeaconEvent::ChainReorg(ChainReorgEvent {
+                slot: new_head.head_state.slot,
+                depth,
+                old_head_block: old_head.head_block_root,
+                new_head_block: new_head.head_block_root,
+                old_head_state: old_head.head_state.state_root,
+                new_head_state: new_head.head_state.state_root,
+                epoch: compute_epoch_at_slot(new_head.head_state.slot),
+                execution_optimistic: false,
+            }));
+
         Ok(())
     }
 
     pub async fn build_status_request(&self) -> anyhow::Result<Status> {
+        if let Some(canonical_head) = self.canonical_head() {
+            return Ok(Status {
+                fork_digest: beacon_network_spec().fork_digest(genesis_validators_root()),
+                finalized_root: canonical_head.finalized_checkpoint.root,
+                finalized_epoch: canonical_head.finalized_checkpoint.epoch,
+                head_root: canonical_head.head_block_root,
+                head_slot: canonical_head.head_state.slot,
+            });
+        }
+
         let Ok(finalized_checkpoint) = self
             .store
             .lock()
@@ -120,3 +497,277 @@ impl BeaconChain {
         })
     }
 }
+
+/// Returns the slot and the parent root of the block with `block_root`, whether it is stored
+/// whole or blinded.
+fn get_slot_and_parent_root(db: &ReamDB, block_root: B256) -> anyhow::Result<Option<(u64, B256)>> {
+    if let Some(block) = db.beacon_block_provider().get(block_root)? {
+        return Ok(Some((block.message.slot, block.message.parent_root)));
+    }
+    Ok(db
+        .blinded_beacon_block_provider()
+        .get(block_root)?
+        .map(|block| (block.message.slot, block.message.parent_root)))
+}
+
+/// Records the incomes of the monitored validators from the import of `block`. Blocks are
+/// accounted for when imported, so the blocks of forks which are later abandoned are counted too.
+fn record_validator_income(
+    db: &ReamDB,
+    post_state: &BeaconState,
+    block: &BeaconBlock,
+    validator_indices: &HashSet<u64>,
+) -> anyhow::Result<()> {
+    let parent_state = db
+        .beacon_state_provider()
+        .get(block.parent_root)?
+        .ok_or_else(|| anyhow!("Failed to find parent state {}", block.parent_root))?;
+
+    let records = compute_income(parent_state, post_state, block, validator_indices)?;
+    let validator_income_provider = db.validator_income_provider();
+    validator_income_provider
+        .merge(records.epoch_incomes, ValidatorIncome::set_epoch_components)?;
+    validator_income_provider
+        .merge(records.block_incomes, ValidatorIncome::add_block_components)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use alloy_primitives::B256;
+    use ream_bls::BLSSignature;
+    use ream_consensus_beacon::{
+        electra::{
+            beacon_block::{BeaconBlock, SignedBeaconBlock},
+            beacon_state::BeaconState,
+        },
+        test_utils::{attestation, attester_slot, single_validator_anchor},
+    };
+    use ream_consensus_misc::{
+        checkpoint::Checkpoint, constants::SLOTS_PER_EPOCH, misc::compute_start_slot_at_epoch,
+    };
+    use ream_fork_choice::store::get_forkchoice_store;
+    use ream_network_spec::networks::{beacon_network_spec, initialize_test_network_spec};
+    use ream_operation_pool::OperationPool;
+    use ream_storage::{
+        db::ReamDB,
+        tables::{Field, Table},
+    };
+    use tempdir::TempDir;
+    use tree_hash::TreeHash;
+
+    use super::{BeaconChain, MAX_BLOCKS_TO_BLIND_PER_BATCH};
+
+    /// Returns a beacon chain whose fork choice store is anchored at `state` and `block`, and
+    /// the directory of its database.
+    fn beacon_chain(
+        state: BeaconState,
+        block: BeaconBlock,
+    ) -> anyhow::Result<(BeaconChain, ReamDB, TempDir)> {
+        let data_dir = TempDir::new("ream_beacon_chain_test")?;
+        let db = ReamDB::new(data_dir.path().to_path_buf())?;
+        get_forkchoice_store(state, block, db.clone())?;
+        let beacon_chain = BeaconChain::new(
+            db.clone(),
+            Arc::new(OperationPool::default()),
+            None,
+            vec![],
+            false,
+            false,
+        );
+        Ok((beacon_chain, db, data_dir))
+    }
+
+    #[tokio::test]
+    async fn test_current_slot_attestation_is_applied_once_its_slot_has_passed()
+    -> anyhow::Result<()> {
+        initialize_test_network_spec();
+        let (state, block) = single_validator_anchor()?;
+        let anchor_root = block.tree_hash_root();
+        let (beacon_chain, db, _data_dir) = beacon_chain(state.clone(), block)?;
+        let slot = attester_slot(&state)?;
+        let attestation = attestation(&state, slot, anchor_root, anchor_root)?;
+
+        let seconds_per_slot = beacon_network_spec().seconds_per_slot;
+        beacon_chain
+            .process_tick(state.genesis_time + slot * seconds_per_slot)
+            .await?;
+        beacon_chain.process_attestation(attestation, false).await?;
+        assert_eq!(beacon_chain.queued_attestations.lock().await.len(), 1);
+        assert!(db.latest_messages_provider().get(0)?.is_none());
+
+        beacon_chain
+            .process_tick(state.genesis_time + (slot + 1) * seconds_per_slot)
+            .await?;
+        assert!(beacon_chain.queued_attestations.lock().await.is_empty());
+        let latest_message = db
+            .latest_messages_provider()
+            .get(0)?
+            .expect("The queued attestation was applied");
+        assert_eq!(latest_message.root, anchor_root);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_canonical_head_is_taken_on_tick() -> anyhow::Result<()> {
+        initialize_test_network_spec();
+        let (state, block) = single_validator_anchor()?;
+        let anchor_root = block.tree_hash_root();
+        let (beacon_chain, _db, _data_dir) = beacon_chain(state.clone(), block)?;
+        assert!(beacon_chain.canonical_head().is_none());
+
+        beacon_chain.process_tick(state.genesis_time).await?;
+        let canonical_head = beacon_chain
+            .canonical_head()
+            .expect("The tick took a head snapshot");
+        assert_eq!(canonical_head.head_block_root, anchor_root);
+        assert_eq!(canonical_head.head_state.slot, 0);
+        assert_eq!(canonical_head.finalized_checkpoint.root, anchor_root);
+        assert_eq!(beacon_chain.head_root().await?, anchor_root);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_canonical_head_takes_attestations_on_next_tick() -> anyhow::Result<()> {
+        initialize_test_network_spec();
+        let (state, block) = single_validator_anchor()?;
+        let anchor_root = block.tree_hash_root();
+        let (beacon_chain, db, _data_dir) = beacon_chain(state.clone(), block)?;
+
+        // Two competing children of the anchor, the one with the higher root wins the tie
+        let children = [1, 2].map(|byte| BeaconBlock {
+            slot: 1,
+            parent_root: anchor_root,
+            state_root: B256::with_last_byte(byte),
+            ..Default::default()
+        });
+        for child in &children {
+            let child_root = child.tree_hash_root();
+            db.beacon_block_provider().insert(
+                child_root,
+                SignedBeaconBlock {
+                    message: child.clone(),
+                    signature: BLSSignature::infinity(),
+                },
+            )?;
+            db.unrealized_justifications_provider()
+                .insert(child_root, Checkpoint::default())?;
+        }
+        let mut child_roots = children.map(|child| child.tree_hash_root());
+        child_roots.sort();
+
+        let slot = attester_slot(&state)?;
+        let seconds_per_slot = beacon_network_spec().seconds_per_slot;
+        beacon_chain
+            .process_tick(state.genesis_time + (slot + 1) * seconds_per_slot)
+            .await?;
+        assert_eq!(beacon_chain.head_root().await?, child_roots[1]);
+
+        // The vote only moves the head snapshot at the next tick
+        beacon_chain
+            .process_attestation(
+                attestation(&state, slot, child_roots[0], anchor_root)?,
+                false,
+            )
+            .await?;
+        assert_eq!(beacon_chain.head_root().await?, child_roots[1]);
+        assert_eq!(beacon_chain.store.lock().await.get_head()?, child_roots[0]);
+
+        beacon_chain
+            .process_tick(state.genesis_time + (slot + 2) * seconds_per_slot)
+            .await?;
+        assert_eq!(beacon_chain.head_root().await?, child_roots[0]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_blind_finalized_blocks_blinds_the_ancestors_of_the_finalized_block()
+    -> anyhow::Result<()> {
+        initialize_test_network_spec();
+        let (state, block) = single_validator_anchor()?;
+        let anchor_root = block.tree_hash_root();
+        let (beacon_chain, db, _data_dir) = beacon_chain(state, block)?;
+        let insert_block = |slot, parent_root, proposer_index| -> anyhow::Result<B256> {
+            let mut block = BeaconBlock {
+                slot,
+                proposer_index,
+                parent_root,
+                ..Default::default()
+            };
+            block.body.execution_payload.block_hash = B256::repeat_byte(1);
+            let block_root = block.tree_hash_root();
+            db.beacon_block_provider().insert(
+                block_root,
+                SignedBeaconBlock {
+                    message: block,
+                    signature: BLSSignature::infinity(),
+                },
+            )?;
+            db.slot_index_provider().insert(slot, block_root)?;
+            Ok(block_root)
+        };
+
+        // Enough finalized blocks to take several batches, with the first slot of the finalized
+        // epoch empty so that the finalized block is the one of the slot before
+        let finalized_epoch = MAX_BLOCKS_TO_BLIND_PER_BATCH as u64 / SLOTS_PER_EPOCH + 2;
+        let finalized_slot = compute_start_slot_at_epoch(finalized_epoch) - 1;
+        let mut block_roots = vec![anchor_root];
+        for slot in 1..=finalized_slot {
+            block_roots.push(insert_block(slot, block_roots[slot as usize - 1], 0)?);
+        }
+        // A block of another fork, which the slot index points at last
+        let fork_root = insert_block(3, block_roots[2], 1)?;
+        db.finalized_checkpoint_provider().insert(Checkpoint {
+            epoch: finalized_epoch,
+            root: block_roots[finalized_slot as usize],
+        })?;
+        // A previous walk already blinded the blocks before slot 2
+        db.next_slot_to_blind_provider().insert(2)?;
+
+        beacon_chain.blind_finalized_blocks().await?;
+
+        let is_blinded = |block_root| -> anyhow::Result<bool> {
+            let is_blinded = db
+                .blinded_beacon_block_provider()
+                .get(block_root)?
+                .is_some();
+            assert_ne!(
+                is_blinded,
+                db.beacon_block_provider().get(block_root)?.is_some()
+            );
+            Ok(is_blinded)
+        };
+        assert!(!is_blinded(block_roots[1])?);
+        for slot in 2..finalized_slot {
+            assert!(
+                is_blinded(block_roots[slot as usize])?,
+                "The block at slot {slot} is blinded"
+            );
+        }
+        assert!(!is_blinded(block_roots[finalized_slot as usize])?);
+        assert!(!is_blinded(fork_root)?);
+        assert_eq!(db.next_slot_to_blind_provider().get()?, finalized_slot);
+
+        // Fork choice still finds the finalized block as the checkpoint block of its descendants
+        let child_root = insert_block(finalized_slot + 2, block_roots[finalized_slot as usize], 0)?;
+        assert_eq!(
+            beacon_chain
+                .store
+                .lock()
+                .await
+                .get_checkpoint_block(child_root, finalized_epoch)?,
+            block_roots[finalized_slot as usize]
+        );
+
+        // Nothing is left to blind until the next finalized checkpoint
+        beacon_chain.blind_finalized_blocks().await?;
+        assert_eq!(db.next_slot_to_blind_provider().get()?, finalized_slot);
+
+        Ok(())
+    }
+}
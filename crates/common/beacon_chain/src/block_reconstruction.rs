@@ -0,0 +1,28 @@
+use alloy_primitives::B256;
+use anyhow::anyhow;
+use ream_consensus_beacon::electra::beacon_block::SignedBeaconBlock;
+use ream_execution_engine::ExecutionEngine;
+use ream_storage::{db::ReamDB, tables::Table};
+
+/// Returns the block with `block_root`. If the block was stored blinded, its execution payload is
+/// fetched back from the execution engine.
+pub async fn get_beacon_block(
+    db: &ReamDB,
+    execution_engine: Option<&ExecutionEngine>,
+    block_root: B256,
+) -> anyhow::Result<Option<SignedBeaconBlock>> {
+    if let Some(block) = db.beacon_block_provider().get(block_root)? {
+        return Ok(Some(block));
+    }
+    let Some(blinded_block) = db.blinded_beacon_block_provider().get(block_root)? else {
+        return Ok(None);
+    };
+
+    let execution_engine = execution_engine.ok_or_else(|| {
+        anyhow!("Block {block_root} is stored blinded, an execution engine is required to serve it")
+    })?;
+    Ok(execution_engine
+        .reconstruct_blocks(vec![blinded_block])
+        .await?
+        .pop())
+}
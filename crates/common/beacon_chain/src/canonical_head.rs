@@ -0,0 +1,45 @@
+use alloy_primitives::B256;
+use anyhow::anyhow;
+use ream_consensus_misc::checkpoint::Checkpoint;
+use ream_fork_choice::store::Store;
+use ream_storage::tables::{Field, Table};
+
+/// Summary of the state of the canonical head block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeadStateSummary {
+    pub slot: u64,
+    pub state_root: B256,
+}
+
+/// A consistent snapshot of the canonical head as selected by fork choice.
+///
+/// The snapshot is swapped atomically after fork choice runs, so readers always observe a head
+/// and checkpoints computed together without locking the fork choice store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CanonicalHead {
+    pub head_block_root: B256,
+    pub head_state: HeadStateSummary,
+    pub justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+}
+
+impl CanonicalHead {
+    pub fn from_store(store: &Store, head_block_root: B256) -> anyhow::Result<Self> {
+        let head_block = store
+            .db
+            .beacon_block_provider()
+            .get(head_block_root)?
+            .ok_or_else(|| anyhow!("Failed to find block for head root {head_block_root}"))?
+            .message;
+
+        Ok(Self {
+            head_block_root,
+            head_state: HeadStateSummary {
+                slot: head_block.slot,
+                state_root: head_block.state_root,
+            },
+            justified_checkpoint: store.db.justified_checkpoint_provider().get()?,
+            finalized_checkpoint: store.db.finalized_checkpoint_provider().get()?,
+        })
+    }
+}
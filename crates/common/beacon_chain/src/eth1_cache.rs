@@ -0,0 +1,118 @@
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+use ream_consensus_beacon::eth_1_block::Eth1Block;
+use ream_consensus_misc::constants::{ETH1_FOLLOW_DISTANCE, SECONDS_PER_ETH1_BLOCK};
+
+/// The maximum number of eth1 blocks fetched by an update of the cache, so that filling an empty
+/// cache is spread over several updates.
+pub const MAX_ETH1_BLOCKS_PER_UPDATE: u64 = 256;
+
+/// Recent eth1 blocks and the state of the deposit contract at each of them, from which the eth1
+/// data vote of produced blocks is selected.
+#[derive(Debug, Default)]
+pub struct Eth1Cache {
+    blocks: BTreeMap<u64, Eth1Block>,
+}
+
+impl Eth1Cache {
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    pub fn insert(&mut self, block: Eth1Block) {
+        self.blocks.insert(block.number, block);
+    }
+
+    pub fn highest_block_number(&self) -> Option<u64> {
+        self.blocks.last_key_value().map(|(number, _)| *number)
+    }
+
+    /// Returns the numbers of the blocks to fetch next when the latest eth1 block is
+    /// `latest_block_number`: the blocks following the cached ones up to the follow distance, or
+    /// the candidate blocks of a voting period when the cache is empty, at most
+    /// [`MAX_ETH1_BLOCKS_PER_UPDATE`] of them.
+    pub fn next_block_range(&self, latest_block_number: u64) -> RangeInclusive<u64> {
+        let follow_block_number = latest_block_number.saturating_sub(ETH1_FOLLOW_DISTANCE);
+        let start_block_number = self.highest_block_number().map_or(
+            follow_block_number.saturating_sub(ETH1_FOLLOW_DISTANCE),
+            |number| number + 1,
+        );
+        start_block_number
+            ..=follow_block_number.min(start_block_number + MAX_ETH1_BLOCKS_PER_UPDATE - 1)
+    }
+
+    /// Returns the cached blocks in ascending order of block number.
+    pub fn blocks(&self) -> Vec<&Eth1Block> {
+        self.blocks.values().collect()
+    }
+
+    /// Drops the blocks which are too old to be a candidate block of a voting period starting at
+    /// or after `period_start`.
+    pub fn prune(&mut self, period_start: u64) {
+        self.blocks.retain(|_, block| {
+            block.timestamp + SECONDS_PER_ETH1_BLOCK * ETH1_FOLLOW_DISTANCE * 2 >= period_start
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::B256;
+
+    use super::*;
+
+    fn eth1_block(number: u64) -> Eth1Block {
+        Eth1Block {
+            number,
+            timestamp: number * SECONDS_PER_ETH1_BLOCK,
+            deposit_root: B256::ZERO,
+            deposit_count: 0,
+            block_hash: B256::with_last_byte(number as u8),
+        }
+    }
+
+    #[test]
+    fn test_eth1_cache_prune() {
+        let mut eth1_cache = Eth1Cache::default();
+        for number in [3, 1, 2] {
+            eth1_cache.insert(eth1_block(number));
+        }
+        assert_eq!(eth1_cache.highest_block_number(), Some(3));
+        assert_eq!(
+            eth1_cache
+                .blocks()
+                .iter()
+                .map(|block| block.number)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+
+        eth1_cache.prune(SECONDS_PER_ETH1_BLOCK * (ETH1_FOLLOW_DISTANCE * 2 + 2));
+        assert_eq!(eth1_cache.len(), 2);
+        assert_eq!(eth1_cache.blocks()[0].number, 2);
+    }
+
+    #[test]
+    fn test_eth1_cache_next_block_range() {
+        let mut eth1_cache = Eth1Cache::default();
+        let latest_block_number = ETH1_FOLLOW_DISTANCE * 2 + 10;
+        assert_eq!(
+            eth1_cache.next_block_range(latest_block_number),
+            10..=(ETH1_FOLLOW_DISTANCE + 10).min(10 + MAX_ETH1_BLOCKS_PER_UPDATE - 1)
+        );
+
+        eth1_cache.insert(eth1_block(ETH1_FOLLOW_DISTANCE));
+        assert_eq!(
+            eth1_cache.next_block_range(latest_block_number),
+            ETH1_FOLLOW_DISTANCE + 1..=ETH1_FOLLOW_DISTANCE + 10
+        );
+
+        // Nothing is fetched until a new block falls behind the follow distance
+        eth1_cache.insert(eth1_block(ETH1_FOLLOW_DISTANCE + 10));
+        assert!(eth1_cache.next_block_range(latest_block_number).is_empty());
+    }
+}
@@ -1 +1,8 @@
 pub mod beacon_chain;
+pub mod block_reconstruction;
+pub mod canonical_head;
+pub mod eth1_cache;
+pub mod node_metrics;
+pub mod reorg;
+pub mod validator_income;
+pub mod validator_monitor;
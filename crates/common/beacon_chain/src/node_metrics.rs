@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use alloy_primitives::B256;
+use serde::Serialize;
+use tokio::sync::{mpsc, oneshot};
+
+/// Depths of the queues of the network manager, refreshed after each event it handles.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct QueueDepths {
+    /// Network events waiting to be handled.
+    pub network_events: usize,
+    /// Blocks waiting for an unknown ancestor.
+    pub quarantined_blocks: usize,
+    pub quarantined_blob_sidecars: usize,
+    /// Ancestors of quarantined blocks which are being looked up.
+    pub pending_block_lookups: usize,
+    /// Gossip messages waiting for their block or slot to be re-processed.
+    pub reprocess_queue: usize,
+}
+
+/// A block waiting in the quarantine for an unknown ancestor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QuarantinedBlockEntry {
+    pub slot: u64,
+    pub block_root: B256,
+    pub parent_root: B256,
+    pub blob_sidecars: usize,
+}
+
+/// The number of gossip messages parked in the reprocess queue for a block root or a slot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReprocessEntry<K> {
+    pub key: K,
+    pub messages: usize,
+}
+
+/// The contents of the queues of the network manager.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct QueueDump {
+    pub depths: QueueDepths,
+    pub quarantined_blocks: Vec<QuarantinedBlockEntry>,
+    pub pending_block_lookups: Vec<B256>,
+    pub awaiting_block: Vec<ReprocessEntry<B256>>,
+    pub awaiting_slot: Vec<ReprocessEntry<u64>>,
+}
+
+/// Asks the network manager for a [`QueueDump`], which it answers between two events.
+pub type QueueDumpSender = mpsc::UnboundedSender<oneshot::Sender<QueueDump>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncBatchKind {
+    BlockRange,
+    BlockRoots,
+    BlobIdentifiers,
+}
+
+/// A batch of the range sync which is being downloaded from a peer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SyncBatch {
+    pub kind: SyncBatchKind,
+    pub peer_id: String,
+    /// The first slot of the batch, for block ranges.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_slot: Option<u64>,
+    /// The number of blocks or blob sidecars requested.
+    pub count: u64,
+}
+
+/// What a peer contributed to the range sync.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct PeerSyncContribution {
+    pub blocks: u64,
+    pub blob_sidecars: u64,
+    pub failed_requests: u64,
+}
+
+/// The state of the range sync, published by the syncer after each of its steps.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SyncMetrics {
+    /// The next data the syncer fetches, or `None` when it is not running.
+    pub stage: Option<String>,
+    pub batches: Vec<SyncBatch>,
+    /// Contributions by peer id, since the node started.
+    pub peer_contributions: HashMap<String, PeerSyncContribution>,
+}
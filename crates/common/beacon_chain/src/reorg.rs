@@ -0,0 +1,81 @@
+use alloy_primitives::B256;
+use anyhow::anyhow;
+use ream_fork_choice::store::Store;
+use ream_storage::tables::Table;
+
+/// Upper bounds of the reorg depth buckets, a reorg deeper than the last bound is counted in an
+/// extra overflow bucket.
+pub const REORG_DEPTH_BUCKETS: [u64; 6] = [1, 2, 4, 8, 16, 32];
+
+/// Counts of observed reorgs, bucketed by depth.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReorgMetrics {
+    depth_counts: [u64; REORG_DEPTH_BUCKETS.len() + 1],
+}
+
+impl ReorgMetrics {
+    pub fn observe(&mut self, depth: u64) {
+        let bucket = REORG_DEPTH_BUCKETS
+            .iter()
+            .position(|&bound| depth <= bound)
+            .unwrap_or(REORG_DEPTH_BUCKETS.len());
+        self.depth_counts[bucket] += 1;
+    }
+
+    pub fn depth_counts(&self) -> &[u64] {
+        &self.depth_counts
+    }
+
+    pub fn total(&self) -> u64 {
+        self.depth_counts.iter().sum()
+    }
+}
+
+/// Returns the root and slot of the latest common ancestor of two blocks.
+pub fn find_common_ancestor(
+    store: &Store,
+    mut first: (B256, u64),
+    mut second: (B256, u64),
+) -> anyhow::Result<(B256, u64)> {
+    let get_parent = |root: B256| -> anyhow::Result<(B256, u64)> {
+        let parent_root = store
+            .db
+            .beacon_block_provider()
+            .get(root)?
+            .ok_or_else(|| anyhow!("Failed to find block for root {root}"))?
+            .message
+            .parent_root;
+        let parent_slot = store
+            .db
+            .beacon_block_provider()
+            .get(parent_root)?
+            .ok_or_else(|| anyhow!("Failed to find parent block for root {root}"))?
+            .message
+            .slot;
+        Ok((parent_root, parent_slot))
+    };
+
+    while first.0 != second.0 {
+        if first.1 >= second.1 {
+            first = get_parent(first.0)?;
+        } else {
+            second = get_parent(second.0)?;
+        }
+    }
+    Ok(first)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reorg_metrics_buckets() {
+        let mut reorg_metrics = ReorgMetrics::default();
+        for depth in [1, 2, 3, 4, 5, 32, 33, 100] {
+            reorg_metrics.observe(depth);
+        }
+        assert_eq!(reorg_metrics.depth_counts(), &[1, 1, 2, 1, 0, 1, 2]);
+        assert_eq!(reorg_metrics.total(), 8);
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::{HashMap, HashSet};
+
+use ream_consensus_beacon::{
+    electra::{beacon_block::BeaconBlock, beacon_state::BeaconState},
+    validator_income::ValidatorIncome,
+};
+use ream_consensus_misc::{
+    constants::{
+        GENESIS_EPOCH, MIN_SLASHING_PENALTY_QUOTIENT_ELECTRA, TIMELY_HEAD_FLAG_INDEX,
+        TIMELY_SOURCE_FLAG_INDEX, TIMELY_TARGET_FLAG_INDEX,
+    },
+    misc::{compute_epoch_at_slot, compute_start_slot_at_epoch},
+};
+
+/// The incomes of the monitored validators accounted for by an imported block, keyed by
+/// `(validator_index, epoch)`.
+#[derive(Debug, Default)]
+pub struct IncomeRecords {
+    /// The components applied by the epoch transitions between the parent state and the block.
+    pub epoch_incomes: Vec<((u64, u64), ValidatorIncome)>,
+    /// The components applied by the processing of the block itself.
+    pub block_incomes: Vec<((u64, u64), ValidatorIncome)>,
+}
+
+/// Computes the incomes of `validator_indices` from the import of `block`, by replaying the
+/// epoch transitions on a copy of its parent state.
+///
+/// `post_state` must be the post-state of `block`.
+pub fn compute_income(
+    mut state: BeaconState,
+    post_state: &BeaconState,
+    block: &BeaconBlock,
+    validator_indices: &HashSet<u64>,
+) -> anyhow::Result<IncomeRecords> {
+    let mut records = IncomeRecords::default();
+    let block_epoch = compute_epoch_at_slot(block.slot);
+
+    while state.get_current_epoch() < block_epoch {
+        let epoch = state.get_current_epoch();
+        let last_slot = compute_start_slot_at_epoch(epoch + 1) - 1;
+        if state.slot < last_slot {
+            state.process_slots(last_slot)?;
+        }
+        records.epoch_incomes.extend(
+            compute_epoch_income(&state, validator_indices)?
+                .into_iter()
+                .map(|(index, income)| ((index, epoch), income)),
+        );
+        state.process_slots(last_slot + 1)?;
+    }
+
+    // Balances only change at epoch boundaries between blocks, so the state does not need to be
+    // advanced to the slot of the block
+    records.block_incomes.extend(
+        compute_block_income(&state, post_state, block, validator_indices)?
+            .into_iter()
+            .map(|(index, income)| ((index, block_epoch), income)),
+    );
+
+    Ok(records)
+}
+
+/// Computes the balance changes the epoch processing at the end of the current epoch of `state`
+/// applies to `validator_indices`. `state` must be at the last slot of its epoch.
+pub fn compute_epoch_income(
+    state: &BeaconState,
+    validator_indices: &HashSet<u64>,
+) -> anyhow::Result<HashMap<u64, ValidatorIncome>> {
+    let mut incomes = validator_indices
+        .iter()
+        .filter(|&&index| index < state.validators.len() as u64)
+        .map(|&index| (index, ValidatorIncome::default()))
+        .collect::<HashMap<_, _>>();
+
+    // The deltas depend on the finality and inactivity scores updated earlier in the epoch
+    // processing
+    let mut state = state.clone();
+    state.process_justification_and_finalization()?;
+    state.process_inactivity_updates()?;
+
+    // No rewards are applied at the end of the genesis epoch
+    if state.get_current_epoch() != GENESIS_EPOCH {
+        let (source_rewards, source_penalties) =
+            state.get_flag_index_deltas(TIMELY_SOURCE_FLAG_INDEX)?;
+        let (target_rewards, target_penalties) =
+            state.get_flag_index_deltas(TIMELY_TARGET_FLAG_INDEX)?;
+        let (head_rewards, _) = state.get_flag_index_deltas(TIMELY_HEAD_FLAG_INDEX)?;
+        let (_, inactivity_penalties) = state.get_inactivity_penalty_deltas()?;
+        for (&index, income) in incomes.iter_mut() {
+            let index = index as usize;
+            income.source_reward = source_rewards[index];
+            income.source_penalty = source_penalties[index];
+            income.target_reward = target_rewards[index];
+            income.target_penalty = target_penalties[index];
+            income.head_reward = head_rewards[index];
+            income.inactivity_penalty = inactivity_penalties[index];
+        }
+        state.process_rewards_and_penalties()?;
+    }
+
+    state.process_registry_updates()?;
+    for (index, penalty) in state.get_slashing_penalties()? {
+        if let Some(income) = incomes.get_mut(&index) {
+            income.proportional_slashing_penalty = penalty;
+        }
+    }
+
+    Ok(incomes)
+}
+
+/// Computes the balance changes the processing of `block` applies to `validator_indices`.
+///
+/// `pre_state` must be in the epoch of `block` with the balances from before the block, and
+/// `post_state` must be the post-state of `block`.
+pub fn compute_block_income(
+    pre_state: &BeaconState,
+    post_state: &BeaconState,
+    block: &BeaconBlock,
+    validator_indices: &HashSet<u64>,
+) -> anyhow::Result<HashMap<u64, ValidatorIncome>> {
+    let mut incomes = HashMap::<u64, ValidatorIncome>::new();
+
+    let (participant_reward, _) = pre_state.get_proposer_and_participant_rewards();
+    let committee_indices =
+        pre_state.get_sync_committee_indices(&pre_state.current_sync_committee)?;
+    for (&index, participated) in committee_indices
+        .iter()
+        .zip(block.body.sync_aggregate.sync_committee_bits.iter())
+    {
+        let index = index as u64;
+        if !validator_indices.contains(&index) {
+            continue;
+        }
+        let income = incomes.entry(index).or_default();
+        if participated {
+            income.sync_committee_reward += participant_reward;
+        } else {
+            income.sync_committee_penalty += participant_reward;
+        }
+    }
+
+    for &index in validator_indices {
+        let (Some(pre_validator), Some(post_validator)) = (
+            pre_state.validators.get(index as usize),
+            post_state.validators.get(index as usize),
+        ) else {
+            continue;
+        };
+        if post_validator.slashed && !pre_validator.slashed {
+            incomes.entry(index).or_default().slashing_penalty +=
+                pre_validator.effective_balance / MIN_SLASHING_PENALTY_QUOTIENT_ELECTRA;
+        }
+    }
+
+    // The proposer rewards are what remains of the balance change of the proposer once its
+    // other balance changes during the block are accounted for
+    let proposer_index = block.proposer_index;
+    if validator_indices.contains(&proposer_index)
+        && let (Some(&pre_balance), Some(&post_balance)) = (
+            pre_state.balances.get(proposer_index as usize),
+            post_state.balances.get(proposer_index as usize),
+        )
+    {
+        let withdrawn = block
+            .body
+            .execution_payload
+            .withdrawals
+            .iter()
+            .filter(|withdrawal| withdrawal.validator_index == proposer_index)
+            .map(|withdrawal| withdrawal.amount)
+            .sum::<u64>();
+        let income = incomes.entry(proposer_index).or_default();
+        let proposer_reward = post_balance as i128 - pre_balance as i128 + withdrawn as i128
+            - income.sync_committee_reward as i128
+            + income.sync_committee_penalty as i128
+            + income.slashing_penalty as i128;
+        income.proposer_reward = proposer_reward.max(0) as u64;
+    }
+
+    Ok(incomes)
+}
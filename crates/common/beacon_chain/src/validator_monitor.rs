@@ -0,0 +1,255 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use ream_consensus_beacon::{
+    committee_cache::CommitteeCache,
+    electra::{beacon_block::BeaconBlock, beacon_state::BeaconState},
+};
+use ream_consensus_misc::{
+    constants::SLOTS_PER_EPOCH,
+    misc::{compute_epoch_at_slot, compute_start_slot_at_epoch},
+};
+use tracing::{info, warn};
+
+/// Attestations can be included until the end of the epoch after their target epoch (EIP-7045),
+/// so the inclusion distance is bounded by two epochs.
+pub const MAX_INCLUSION_DISTANCE: u64 = 2 * SLOTS_PER_EPOCH;
+
+/// Histogram of attestation inclusion distances, where bucket `i` counts the attestations which
+/// were included `i` slots after their attestation slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InclusionDistanceHistogram {
+    buckets: Vec<u64>,
+}
+
+impl Default for InclusionDistanceHistogram {
+    fn default() -> Self {
+        Self {
+            buckets: vec![0; MAX_INCLUSION_DISTANCE as usize + 1],
+        }
+    }
+}
+
+impl InclusionDistanceHistogram {
+    pub fn observe(&mut self, inclusion_distance: u64) {
+        self.buckets[inclusion_distance.min(MAX_INCLUSION_DISTANCE) as usize] += 1;
+    }
+
+    pub fn buckets(&self) -> &[u64] {
+        &self.buckets
+    }
+
+    pub fn count(&self) -> u64 {
+        self.buckets.iter().sum()
+    }
+}
+
+/// The attestation duty of a monitored validator and the smallest distance at which its
+/// attestation was included, if it was included at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttestationDuty {
+    pub slot: u64,
+    pub committee_index: u64,
+    pub inclusion_distance: Option<u64>,
+}
+
+/// Tracks whether the attestations of monitored validators were included on chain, by
+/// cross-referencing the attestations of imported blocks with the expected attester duties.
+#[derive(Debug, Default)]
+pub struct ValidatorMonitor {
+    monitored_validators: HashSet<u64>,
+    /// Attestation duties of the monitored validators, keyed by epoch and validator index.
+    duties: BTreeMap<u64, HashMap<u64, AttestationDuty>>,
+    inclusion_distances: InclusionDistanceHistogram,
+    missed_attestations: u64,
+    current_epoch: u64,
+    /// Whether the per-epoch incomes of the monitored validators are recorded.
+    record_income: bool,
+}
+
+impl ValidatorMonitor {
+    pub fn new(monitored_validators: impl IntoIterator<Item = u64>) -> Self {
+        Self {
+            monitored_validators: monitored_validators.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Enables the recording of the per-epoch incomes of the monitored validators.
+    pub fn with_income_history(mut self, record_income: bool) -> Self {
+        self.record_income = record_income;
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.monitored_validators.is_empty()
+    }
+
+    pub fn records_income(&self) -> bool {
+        self.record_income && self.is_enabled()
+    }
+
+    pub fn monitored_validators(&self) -> &HashSet<u64> {
+        &self.monitored_validators
+    }
+
+    pub fn inclusion_distances(&self) -> &InclusionDistanceHistogram {
+        &self.inclusion_distances
+    }
+
+    pub fn missed_attestations(&self) -> u64 {
+        self.missed_attestations
+    }
+
+    /// Records the inclusion of the monitored validators' attestations in an imported block.
+    /// Blocks of epochs which were already summarized, e.g. while syncing, are ignored.
+    ///
+    /// `state` must be the post-state of `block`.
+    pub fn register_block(
+        &mut self,
+        state: &BeaconState,
+        block: &BeaconBlock,
+    ) -> anyhow::Result<()> {
+        if !self.is_enabled() || compute_epoch_at_slot(block.slot) + 1 < self.current_epoch {
+            return Ok(());
+        }
+
+        for epoch in [state.get_previous_epoch(), state.get_current_epoch()] {
+            if epoch + 1 >= self.current_epoch && !self.duties.contains_key(&epoch) {
+                let duties = self.compute_duties(state, epoch)?;
+                self.duties.insert(epoch, duties);
+            }
+        }
+
+        for attestation in block.body.attestations.iter() {
+            let Some(duties) = self
+                .duties
+                .get_mut(&compute_epoch_at_slot(attestation.data.slot))
+            else {
+                continue;
+            };
+
+            let inclusion_distance = block.slot.saturating_sub(attestation.data.slot);
+            for validator_index in state.get_attesting_indices(attestation)? {
+                if let Some(duty) = duties.get_mut(&validator_index)
+                    && duty
+                        .inclusion_distance
+                        .is_none_or(|distance| inclusion_distance < distance)
+                {
+                    duty.inclusion_distance = Some(inclusion_distance);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Summarizes the epochs whose attestations can no longer be included at `current_epoch`,
+    /// updating the inclusion distance histogram and logging missed attestations.
+    pub fn process_epoch(&mut self, current_epoch: u64) {
+        self.current_epoch = current_epoch;
+        while let Some(entry) = self.duties.first_entry() {
+            let epoch = *entry.key();
+            if epoch + 1 >= current_epoch {
+                break;
+            }
+
+            let mut included = 0;
+            for (validator_index, duty) in entry.remove() {
+                match duty.inclusion_distance {
+                    Some(inclusion_distance) => {
+                        self.inclusion_distances.observe(inclusion_distance);
+                        included += 1;
+                    }
+                    None => {
+                        self.missed_attestations += 1;
+                        warn!(
+                            "Attestation of validator {validator_index} was not included: epoch {epoch}, slot {}, committee_index {}",
+                            duty.slot, duty.committee_index
+                        );
+                    }
+                }
+            }
+
+            info!(
+                "Attestation inclusion for epoch {epoch}: included {included}, inclusion distances {:?}, total missed {}",
+                self.inclusion_distances.buckets(),
+                self.missed_attestations
+            );
+        }
+    }
+
+    fn compute_duties(
+        &self,
+        state: &BeaconState,
+        epoch: u64,
+    ) -> anyhow::Result<HashMap<u64, AttestationDuty>> {
+        // Every committee of the epoch is visited, so the validators are shuffled once
+        let committee_cache = CommitteeCache::new(state, epoch)?;
+        let mut duties = HashMap::new();
+        let start_slot = compute_start_slot_at_epoch(epoch);
+        for slot in start_slot..start_slot + SLOTS_PER_EPOCH {
+            for committee_index in 0..committee_cache.committees_per_slot() {
+                for &validator_index in
+                    committee_cache.get_beacon_committee(slot, committee_index)?
+                {
+                    if self.monitored_validators.contains(&validator_index) {
+                        duties.insert(
+                            validator_index,
+                            AttestationDuty {
+                                slot,
+                                committee_index,
+                                inclusion_distance: None,
+                            },
+                        );
+                    }
+                }
+            }
+        }
+        Ok(duties)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_epoch_summarizes_past_epochs() {
+        let mut validator_monitor = ValidatorMonitor::new([1, 2]);
+        for epoch in [3, 4] {
+            validator_monitor.duties.insert(
+                epoch,
+                HashMap::from([
+                    (
+                        1,
+                        AttestationDuty {
+                            slot: epoch * SLOTS_PER_EPOCH,
+                            committee_index: 0,
+                            inclusion_distance: Some(1),
+                        },
+                    ),
+                    (
+                        2,
+                        AttestationDuty {
+                            slot: epoch * SLOTS_PER_EPOCH + 1,
+                            committee_index: 0,
+                            inclusion_distance: None,
+                        },
+                    ),
+                ]),
+            );
+        }
+
+        // Attestations of epoch 4 can still be included in epoch 5
+        validator_monitor.process_epoch(5);
+        assert_eq!(validator_monitor.inclusion_distances().count(), 1);
+        assert_eq!(validator_monitor.inclusion_distances().buckets()[1], 1);
+        assert_eq!(validator_monitor.missed_attestations(), 1);
+        assert!(validator_monitor.duties.contains_key(&4));
+
+        validator_monitor.process_epoch(6);
+        assert_eq!(validator_monitor.inclusion_distances().count(), 2);
+        assert_eq!(validator_monitor.missed_attestations(), 2);
+        assert!(validator_monitor.duties.is_empty());
+    }
+}
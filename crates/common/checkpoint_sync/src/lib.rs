@@ -6,7 +6,10 @@ use anyhow::{anyhow, ensure};
 use checkpoint::get_checkpoint_sync_sources;
 use ream_consensus_beacon::{
     blob_sidecar::{BlobIdentifier, BlobSidecar},
-    electra::{beacon_block::SignedBeaconBlock, beacon_state::BeaconState},
+    electra::{
+        beacon_block::{BeaconBlock, SignedBeaconBlock},
+        beacon_state::BeaconState,
+    },
     execution_engine::rpc_types::get_blobs::BlobAndProofV1,
 };
 use ream_consensus_misc::checkpoint::Checkpoint;
@@ -56,49 +59,75 @@ pub async fn initialize_db_from_checkpoint(
     let checkpoint_sync_url = get_checkpoint_sync_sources(checkpoint_sync_url).remove(0);
     info!("Initiating checkpoint sync");
 
-    info!("Fetching finalized block...");
-    let block = fetch_finalized_block(&checkpoint_sync_url).await?;
+    // The anchor is the block of the trusted checkpoint if there is one, else the finalized block
+    // of the checkpoint sync source
+    let block_id = match &weak_subjectivity_checkpoint {
+        Some(weak_subjectivity_checkpoint) => weak_subjectivity_checkpoint.root.to_string(),
+        None => "finalized".to_string(),
+    };
+    info!("Fetching block {block_id}...");
+    let block = fetch_block(&checkpoint_sync_url, &block_id).await?;
+    let block_root = block.message.block_root();
     info!(
-        "Downloaded block: {} with root: {}. Slot: {}",
-        block.message.body.execution_payload.block_number,
-        block.message.block_root(),
-        block.message.slot
+        "Downloaded block: {} with root: {block_root}. Slot: {}",
+        block.message.body.execution_payload.block_number, block.message.slot
     );
     let slot = block.message.slot;
 
+    info!("Fetching initial state...");
+    let state = get_state(&checkpoint_sync_url, slot).await?;
+    let state_root = match &weak_subjectivity_checkpoint {
+        Some(weak_subjectivity_checkpoint) => {
+            verify_checkpoint_anchor(&block.message, &state, weak_subjectivity_checkpoint)?
+        }
+        None => state.state_root(),
+    };
+    info!("Downloaded state with root: {state_root}. Slot: {slot}");
+
+    ensure!(block.message.slot == state.slot, "Slot mismatch");
+    ensure!(
+        block.message.state_root == state_root,
+        "Block state root {} does not match the state root {state_root}",
+        block.message.state_root
+    );
+
     info!("Fetching blobs...");
-    initialize_blobs_in_db(&checkpoint_sync_url, db.clone(), block.message.block_root()).await?;
+    initialize_blobs_in_db(&checkpoint_sync_url, db.clone(), block_root).await?;
     info!(
         "Downloaded blobs for block: {}",
         block.message.body.execution_payload.block_number
     );
 
-    info!("Fetching initial state...");
-    let state = get_state(&checkpoint_sync_url, slot).await?;
-    info!(
-        "Downloaded state with root: {}. Slot: {}",
-        state.state_root(),
-        slot
-    );
-
-    ensure!(block.message.slot == state.slot, "Slot mismatch");
-
-    ensure!(block.message.state_root == state.state_root());
-    let mut store = get_forkchoice_store(state.clone(), block.message, db)?;
+    let mut store = get_forkchoice_store(state, block.message, db)?;
 
     let time = beacon_network_spec().min_genesis_time
         + beacon_network_spec().seconds_per_slot * (slot + 1);
     on_tick(&mut store, time)?;
     info!("Initial sync complete");
 
-    if let Some(weak_subjectivity_checkpoint) = &weak_subjectivity_checkpoint {
-        if !verify_state_from_weak_subjectivity_checkpoint(&state, weak_subjectivity_checkpoint)? {
-            return Ok(WeakSubjectivityState::CheckpointPendingVerification);
-        }
-    } else {
-        return Ok(WeakSubjectivityState::None);
-    }
-    Ok(WeakSubjectivityState::CheckpointAlreadyVerified)
+    Ok(match weak_subjectivity_checkpoint {
+        Some(_) => WeakSubjectivityState::CheckpointAlreadyVerified,
+        None => WeakSubjectivityState::None,
+    })
+}
+
+/// Verify that the downloaded `block` and its post-state `state` are the anchor of the trusted
+/// `weak_subjectivity_checkpoint`, before they are stored. Returns the state root.
+fn verify_checkpoint_anchor(
+    block: &BeaconBlock,
+    state: &BeaconState,
+    weak_subjectivity_checkpoint: &Checkpoint,
+) -> anyhow::Result<B256> {
+    let block_root = block.block_root();
+    ensure!(
+        block_root == weak_subjectivity_checkpoint.root,
+        "Downloaded block root {block_root} does not match the checkpoint root {}",
+        weak_subjectivity_checkpoint.root
+    );
+    Ok(state.verify_against_checkpoint(
+        weak_subjectivity_checkpoint.root,
+        weak_subjectivity_checkpoint.epoch,
+    )?)
 }
 
 /// Fetch initial state from trusted RPC
@@ -116,11 +145,11 @@ async fn get_state(rpc: &Url, slot: u64) -> anyhow::Result<BeaconState> {
         .map_err(|err| anyhow!("Unable to decode state from ssz bytes: {err:?}"))
 }
 
-/// Fetch initial block from trusted RPC
-async fn fetch_finalized_block(rpc: &Url) -> anyhow::Result<SignedBeaconBlock> {
+/// Fetch the initial block `block_id` from trusted RPC
+async fn fetch_block(rpc: &Url, block_id: &str) -> anyhow::Result<SignedBeaconBlock> {
     let client = reqwest::Client::new();
     let raw_bytes = client
-        .get(format!("{rpc}eth/v2/beacon/blocks/finalized"))
+        .get(format!("{rpc}eth/v2/beacon/blocks/{block_id}"))
         .header(ACCEPT, HeaderValue::from_static("application/octet-stream"))
         .send()
         .await?
@@ -160,3 +189,48 @@ async fn initialize_blobs_in_db(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use ream_consensus_beacon::test_utils::single_validator_state;
+    use tree_hash::TreeHash;
+
+    use super::*;
+
+    #[test]
+    fn test_verify_checkpoint_anchor() -> anyhow::Result<()> {
+        let mut state = single_validator_state()?;
+        let mut block = BeaconBlock::default();
+        state.latest_block_header.body_root = block.body.tree_hash_root();
+        block.state_root = state.state_root();
+        let checkpoint = Checkpoint {
+            epoch: 0,
+            root: block.block_root(),
+        };
+        assert_eq!(
+            verify_checkpoint_anchor(&block, &state, &checkpoint)?,
+            block.state_root
+        );
+
+        // A block of another root with a state of the checkpoint
+        let other_block = BeaconBlock {
+            proposer_index: 1,
+            ..block.clone()
+        };
+        assert!(verify_checkpoint_anchor(&other_block, &state, &checkpoint).is_err());
+
+        // The state of a block of another epoch
+        assert!(
+            verify_checkpoint_anchor(
+                &block,
+                &state,
+                &Checkpoint {
+                    epoch: 1,
+                    ..checkpoint
+                }
+            )
+            .is_err()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use alloy_primitives::B256;
+use ream_consensus_misc::{
+    beacon_block_header::BeaconBlockHeader,
+    checkpoint::Checkpoint,
+    eth_1_data::Eth1Data,
+    fork::Fork,
+    preset::{
+        EpochsPerHistoricalVector, EpochsPerSlashingsVector, SlotsPerEth1VotingPeriod,
+        SlotsPerHistoricalRoot,
+    },
+    validator::Validator,
+};
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{
+    BitVector, FixedVector, VariableList,
+    serde_utils::{quoted_u64_fixed_vec, quoted_u64_var_list},
+    typenum::{U4, U16777216},
+};
+use tree_hash_derive::TreeHash;
+
+use crate::{
+    electra::zkvm_types::ValidatorRegistryLimit, quoted::quoted_u8_var_list,
+    sync_committee::SyncCommittee,
+};
+
+/// The beacon state of the Altair fork, which replaces the pending attestations with
+/// participation flags and adds the sync committees.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct BeaconState {
+    // Versioning
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub genesis_time: u64,
+    pub genesis_validators_root: B256,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub slot: u64,
+    pub fork: Fork,
+
+    // History
+    pub latest_block_header: BeaconBlockHeader,
+    pub block_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
+    pub state_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
+    pub historical_roots: VariableList<B256, U16777216>,
+
+    // Eth1
+    pub eth1_data: Eth1Data,
+    pub eth1_data_votes: VariableList<Eth1Data, SlotsPerEth1VotingPeriod>,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub eth1_deposit_index: u64,
+
+    // Registry
+    pub validators: VariableList<Validator, ValidatorRegistryLimit>,
+    #[serde(with = "quoted_u64_var_list")]
+    pub balances: VariableList<u64, ValidatorRegistryLimit>,
+
+    // Randomness
+    pub randao_mixes: FixedVector<B256, EpochsPerHistoricalVector>,
+
+    // Slashings
+    #[serde(with = "quoted_u64_fixed_vec")]
+    pub slashings: FixedVector<u64, EpochsPerSlashingsVector>,
+
+    // Participation
+    #[serde(with = "quoted_u8_var_list")]
+    pub previous_epoch_participation: VariableList<u8, ValidatorRegistryLimit>,
+    #[serde(with = "quoted_u8_var_list")]
+    pub current_epoch_participation: VariableList<u8, ValidatorRegistryLimit>,
+
+    // Finality
+    pub justification_bits: BitVector<U4>,
+    pub previous_justified_checkpoint: Checkpoint,
+    pub current_justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+
+    // Inactivity
+    #[serde(with = "quoted_u64_var_list")]
+    pub inactivity_scores: VariableList<u64, ValidatorRegistryLimit>,
+
+    // Sync
+    pub current_sync_committee: Arc<SyncCommittee>,
+    pub next_sync_committee: Arc<SyncCommittee>,
+}
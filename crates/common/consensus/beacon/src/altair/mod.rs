@@ -0,0 +1 @@
+pub mod beacon_state;
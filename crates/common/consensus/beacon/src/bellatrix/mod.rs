@@ -0,0 +1,2 @@
+pub mod beacon_state;
+pub mod execution_payload_header;
@@ -4,11 +4,12 @@ use ream_consensus_misc::{
     constants::{
         BLOB_KZG_COMMITMENTS_INDEX, KZG_COMMITMENT_INCLUSION_PROOF_DEPTH, MAX_BLOBS_PER_BLOCK,
     },
+    preset::KzgCommitmentInclusionProofDepth,
 };
 use ream_merkle::{get_root_from_merkle_branch, is_valid_merkle_branch};
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use ssz_types::{FixedVector, typenum::U17};
+use ssz_types::FixedVector;
 use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
 
@@ -25,7 +26,7 @@ pub struct BlobSidecar {
     pub kzg_commitment: KZGCommitment,
     pub kzg_proof: KZGProof,
     pub signed_block_header: SignedBeaconBlockHeader,
-    pub kzg_commitment_inclusion_proof: FixedVector<B256, U17>,
+    pub kzg_commitment_inclusion_proof: FixedVector<B256, KzgCommitmentInclusionProofDepth>,
 }
 
 #[derive(
@@ -33,6 +34,7 @@ pub struct BlobSidecar {
 )]
 pub struct BlobIdentifier {
     pub block_root: B256,
+    #[serde(with = "serde_utils::quoted_u64")]
     pub index: u64,
 }
 
@@ -70,29 +72,24 @@ impl BlobSidecar {
 
 #[cfg(test)]
 mod tests {
-    use std::path::Path;
-
-    use anyhow::anyhow;
     use ream_bls::BLSSignature;
     use ream_consensus_misc::beacon_block_header::{BeaconBlockHeader, SignedBeaconBlockHeader};
-    use snap::raw::Decoder;
-    use ssz::Decode;
-    use ssz_types::{FixedVector, typenum::U17};
+    use ssz_types::{FixedVector, typenum::Unsigned};
 
     use super::*;
 
-    fn read_ssz_snappy_file<T: Decode>(path: &Path) -> anyhow::Result<T> {
-        let ssz_snappy = std::fs::read(path)?;
-        let mut decoder = Decoder::new();
-        let ssz = decoder.decompress_vec(&ssz_snappy)?;
-        T::from_ssz_bytes(&ssz).map_err(|err| anyhow!("Failed to decode SSZ: {err:?}"))
-    }
-
+    // The test asset is encoded with the mainnet preset
+    #[cfg(not(feature = "minimal"))]
     #[test]
     fn verify_blob_sidecar_inclusion_proof_positive() -> anyhow::Result<()> {
+        use snap::raw::Decoder;
+        use ssz::Decode;
+
         let path =
             std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/blob_sidecar.ssz_snappy");
-        let blob_sidecar: BlobSidecar = read_ssz_snappy_file(&path)?;
+        let ssz = Decoder::new().decompress_vec(&std::fs::read(path)?)?;
+        let blob_sidecar = BlobSidecar::from_ssz_bytes(&ssz)
+            .map_err(|err| anyhow::anyhow!("Failed to decode SSZ: {err:?}"))?;
 
         assert!(
             blob_sidecar.verify_blob_sidecar_inclusion_proof(),
@@ -114,10 +111,10 @@ mod tests {
             kzg_commitment: KZGCommitment([0u8; 48]),
             kzg_proof: KZGProof::default(),
             signed_block_header,
-            kzg_commitment_inclusion_proof: FixedVector::<B256, U17>::from(vec![
-                B256::default();
-                17
-            ]),
+            kzg_commitment_inclusion_proof:
+                FixedVector::<B256, KzgCommitmentInclusionProofDepth>::from(
+                    vec![B256::default(); KzgCommitmentInclusionProofDepth::to_usize()],
+                ),
         };
 
         let result = blob_sidecar.verify_blob_sidecar_inclusion_proof();
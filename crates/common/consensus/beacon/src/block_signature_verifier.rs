@@ -0,0 +1,293 @@
+use std::cmp::max;
+
+use anyhow::{anyhow, bail, ensure};
+use ream_bls::{
+    BLSSignature, PublicKey, SignatureSet,
+    traits::{BatchVerifiable, Verifiable},
+};
+use ream_consensus_misc::{
+    constants::{
+        CAPELLA_FORK_VERSION, DOMAIN_BEACON_ATTESTER, DOMAIN_BEACON_PROPOSER, DOMAIN_RANDAO,
+        DOMAIN_SYNC_COMMITTEE, DOMAIN_VOLUNTARY_EXIT,
+    },
+    misc::{compute_domain, compute_epoch_at_slot, compute_signing_root, is_sorted_and_unique},
+};
+
+use crate::{
+    committee_cache::CommitteeCache,
+    electra::{
+        beacon_block::{BeaconBlock, SignedBeaconBlock},
+        beacon_state::BeaconState,
+    },
+};
+
+/// Collects the signatures of a block to verify them as a single batch, which is cheaper than
+/// verifying them one by one while the block is processed.
+///
+/// A batch only tells whether every signature is valid, so when it fails the signatures are
+/// verified one by one to report the invalid one.
+pub struct BlockSignatureVerifier<'a> {
+    state: &'a BeaconState,
+    descriptions: Vec<String>,
+    signature_sets: Vec<SignatureSet<'a>>,
+}
+
+impl<'a> BlockSignatureVerifier<'a> {
+    pub fn new(state: &'a BeaconState) -> Self {
+        Self {
+            state,
+            descriptions: vec![],
+            signature_sets: vec![],
+        }
+    }
+
+    /// Verifies every signature of ``signed_block`` which block processing checks against
+    /// ``state``, which must already be advanced to the slot of the block.
+    pub fn verify_entire_block(
+        state: &'a BeaconState,
+        signed_block: &'a SignedBeaconBlock,
+    ) -> anyhow::Result<()> {
+        let mut verifier = Self::new(state);
+        verifier.include_all_signatures(signed_block)?;
+        verifier.verify()
+    }
+
+    pub fn include_all_signatures(
+        &mut self,
+        signed_block: &'a SignedBeaconBlock,
+    ) -> anyhow::Result<()> {
+        self.include_block_proposal(signed_block)?;
+        self.include_randao_reveal(&signed_block.message)?;
+        self.include_attestations(&signed_block.message)?;
+        self.include_voluntary_exits(&signed_block.message)?;
+        self.include_sync_aggregate(&signed_block.message)
+    }
+
+    pub fn include_block_proposal(
+        &mut self,
+        signed_block: &'a SignedBeaconBlock,
+    ) -> anyhow::Result<()> {
+        let block = &signed_block.message;
+        let signing_root =
+            compute_signing_root(block, self.state.get_domain(DOMAIN_BEACON_PROPOSER, None));
+        self.push(
+            "block proposal".to_string(),
+            SignatureSet::single(
+                &signed_block.signature,
+                self.public_key(block.proposer_index)?,
+                signing_root,
+            ),
+        );
+        Ok(())
+    }
+
+    pub fn include_randao_reveal(&mut self, block: &'a BeaconBlock) -> anyhow::Result<()> {
+        let state = self.state;
+        let epoch = state.get_current_epoch();
+        let Some(proposer) = state
+            .validators
+            .get(state.get_beacon_proposer_index(None)? as usize)
+        else {
+            return Ok(());
+        };
+        let signing_root =
+            compute_signing_root(epoch, state.get_domain(DOMAIN_RANDAO, Some(epoch)));
+        self.push(
+            "RANDAO reveal".to_string(),
+            SignatureSet::single(
+                &block.body.randao_reveal,
+                &proposer.public_key,
+                signing_root,
+            ),
+        );
+        Ok(())
+    }
+
+    pub fn include_attestations(&mut self, block: &'a BeaconBlock) -> anyhow::Result<()> {
+        let state = self.state;
+        let mut committee_caches: Vec<CommitteeCache> = vec![];
+        for (index, attestation) in block.body.attestations.iter().enumerate() {
+            let target_epoch = attestation.data.target.epoch;
+            let position = match committee_caches
+                .iter()
+                .position(|committee_cache| committee_cache.epoch() == target_epoch)
+            {
+                Some(position) => position,
+                None => {
+                    committee_caches.push(CommitteeCache::new(state, target_epoch)?);
+                    committee_caches.len() - 1
+                }
+            };
+            let indexed_attestation = state
+                .get_indexed_attestation_with_cache(attestation, &committee_caches[position])?;
+            let attesting_indices = indexed_attestation
+                .attesting_indices
+                .iter()
+                .map(|&index| index as usize)
+                .collect::<Vec<_>>();
+            ensure!(
+                !attesting_indices.is_empty() && is_sorted_and_unique(&attesting_indices),
+                "Attestation {index} must have sorted and unique attesting indices"
+            );
+
+            let signing_root = compute_signing_root(
+                &attestation.data,
+                state.get_domain(DOMAIN_BEACON_ATTESTER, Some(target_epoch)),
+            );
+            let public_keys = indexed_attestation
+                .attesting_indices
+                .iter()
+                .map(|&index| self.public_key(index))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            self.push(
+                format!("attestation {index}"),
+                SignatureSet::new(&attestation.signature, public_keys, signing_root),
+            );
+        }
+        Ok(())
+    }
+
+    pub fn include_voluntary_exits(&mut self, block: &'a BeaconBlock) -> anyhow::Result<()> {
+        let domain = compute_domain(
+            DOMAIN_VOLUNTARY_EXIT,
+            Some(CAPELLA_FORK_VERSION),
+            Some(self.state.genesis_validators_root),
+        );
+        for (index, signed_voluntary_exit) in block.body.voluntary_exits.iter().enumerate() {
+            let voluntary_exit = &signed_voluntary_exit.message;
+            self.push(
+                format!("voluntary exit {index}"),
+                SignatureSet::single(
+                    &signed_voluntary_exit.signature,
+                    self.public_key(voluntary_exit.validator_index)?,
+                    compute_signing_root(voluntary_exit, domain),
+                ),
+            );
+        }
+        Ok(())
+    }
+
+    pub fn include_sync_aggregate(&mut self, block: &'a BeaconBlock) -> anyhow::Result<()> {
+        let state = self.state;
+        let sync_aggregate = &block.body.sync_aggregate;
+        let public_keys = state
+            .current_sync_committee
+            .public_keys
+            .iter()
+            .zip(sync_aggregate.sync_committee_bits.iter())
+            .filter_map(|(public_key, bit)| bit.then_some(public_key))
+            .collect::<Vec<_>>();
+        // ``eth_fast_aggregate_verify`` accepts an aggregate without participants
+        if public_keys.is_empty()
+            && sync_aggregate.sync_committee_signature == BLSSignature::infinity()
+        {
+            return Ok(());
+        }
+
+        let previous_slot = max(state.slot, 1) - 1;
+        let domain = state.get_domain(
+            DOMAIN_SYNC_COMMITTEE,
+            Some(compute_epoch_at_slot(previous_slot)),
+        );
+        let signing_root =
+            compute_signing_root(state.get_block_root_at_slot(previous_slot)?, domain);
+        self.push(
+            "sync aggregate".to_string(),
+            SignatureSet::new(
+                &sync_aggregate.sync_committee_signature,
+                public_keys,
+                signing_root,
+            ),
+        );
+        Ok(())
+    }
+
+    /// Verifies every signature included so far.
+    pub fn verify(&self) -> anyhow::Result<()> {
+        verify_signature_sets(&self.descriptions, &self.signature_sets)
+    }
+
+    fn push(&mut self, description: String, signature_set: SignatureSet<'a>) {
+        self.descriptions.push(description);
+        self.signature_sets.push(signature_set);
+    }
+
+    fn public_key(&self, index: u64) -> anyhow::Result<&'a PublicKey> {
+        self.state
+            .validators
+            .get(index as usize)
+            .map(|validator| &validator.public_key)
+            .ok_or_else(|| anyhow!("Validator index {index} out of bounds"))
+    }
+}
+
+/// Verifies ``signature_sets`` as a batch, then one by one if the batch fails so that the error
+/// names the invalid set by its description.
+fn verify_signature_sets(
+    descriptions: &[String],
+    signature_sets: &[SignatureSet],
+) -> anyhow::Result<()> {
+    if SignatureSet::verify_batch(signature_sets).unwrap_or(false) {
+        return Ok(());
+    }
+
+    for (description, signature_set) in descriptions.iter().zip(signature_sets) {
+        ensure!(
+            !signature_set.public_keys.is_empty()
+                && signature_set
+                    .signature
+                    .fast_aggregate_verify(
+                        &signature_set.public_keys,
+                        signature_set.message.as_ref()
+                    )
+                    .map_err(|err| anyhow!("Invalid {description} signature: {err}"))?,
+            "Invalid {description} signature"
+        );
+    }
+    bail!("Batch signature verification failed although every signature is valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::B256;
+    use ream_bls::traits::Signable;
+
+    use super::*;
+    use crate::test_utils::{generator_private_key, generator_public_key};
+
+    #[test]
+    fn test_verify_reports_the_invalid_signature_of_a_batch() -> anyhow::Result<()> {
+        let public_key = generator_public_key()?;
+        let private_key = generator_private_key();
+        let messages = [
+            B256::repeat_byte(1),
+            B256::repeat_byte(2),
+            B256::repeat_byte(3),
+        ];
+        let signatures = messages
+            .iter()
+            .map(|message| private_key.sign(message.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let descriptions = (0..messages.len())
+            .map(|index| format!("attestation {index}"))
+            .collect::<Vec<_>>();
+        let mut signature_sets = signatures
+            .iter()
+            .zip(messages)
+            .map(|(signature, message)| SignatureSet::single(signature, &public_key, message))
+            .collect::<Vec<_>>();
+        verify_signature_sets(&descriptions, &signature_sets)?;
+
+        // The signature of attestation 1 is over the message of attestation 0
+        signature_sets[1].signature = &signatures[0];
+        assert!(!SignatureSet::verify_batch(&signature_sets)?);
+        assert_eq!(
+            verify_signature_sets(&descriptions, &signature_sets)
+                .expect_err("The batch has an invalid signature")
+                .to_string(),
+            "Invalid attestation 1 signature"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+use std::{ops::Deref, sync::Arc};
+
+use alloy_primitives::B256;
+use anyhow::{anyhow, ensure};
+use ream_consensus_misc::{
+    beacon_block_header::BeaconBlockHeader,
+    checkpoint::Checkpoint,
+    eth_1_data::Eth1Data,
+    fork::Fork,
+    misc::compute_epoch_at_slot,
+    preset::{
+        EpochsPerHistoricalVector, EpochsPerSlashingsVector, SlotsPerEth1VotingPeriod,
+        SlotsPerHistoricalRoot,
+    },
+    validator::Validator,
+};
+use ream_network_spec::networks::beacon_network_spec;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{
+    BitVector, FixedVector, VariableList,
+    serde_utils::{quoted_u64_fixed_vec, quoted_u64_var_list},
+    typenum::{U4, U16777216},
+};
+use tree_hash_derive::TreeHash;
+
+use super::{
+    execution_payload::ExecutionPayload,
+    execution_payload_header::ExecutionPayloadHeader,
+    withdrawals::{get_expected_withdrawals, get_next_withdrawal_validator_index},
+};
+use crate::{
+    deneb::beacon_state::BeaconState as DenebBeaconState,
+    electra::{
+        execution_payload_header::ExecutionPayloadHeader as DenebExecutionPayloadHeader,
+        zkvm_types::ValidatorRegistryLimit,
+    },
+    historical_summary::HistoricalSummary,
+    quoted::quoted_u8_var_list,
+    sync_committee::SyncCommittee,
+    withdrawal::Withdrawal,
+};
+
+/// The beacon state of the Capella fork, which adds withdrawals and historical summaries.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct BeaconState {
+    // Versioning
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub genesis_time: u64,
+    pub genesis_validators_root: B256,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub slot: u64,
+    pub fork: Fork,
+
+    // History
+    pub latest_block_header: BeaconBlockHeader,
+    pub block_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
+    pub state_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
+    pub historical_roots: VariableList<B256, U16777216>,
+
+    // Eth1
+    pub eth1_data: Eth1Data,
+    pub eth1_data_votes: VariableList<Eth1Data, SlotsPerEth1VotingPeriod>,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub eth1_deposit_index: u64,
+
+    // Registry
+    pub validators: VariableList<Validator, ValidatorRegistryLimit>,
+    #[serde(with = "quoted_u64_var_list")]
+    pub balances: VariableList<u64, ValidatorRegistryLimit>,
+
+    // Randomness
+    pub randao_mixes: FixedVector<B256, EpochsPerHistoricalVector>,
+
+    // Slashings
+    #[serde(with = "quoted_u64_fixed_vec")]
+    pub slashings: FixedVector<u64, EpochsPerSlashingsVector>,
+
+    // Participation
+    #[serde(with = "quoted_u8_var_list")]
+    pub previous_epoch_participation: VariableList<u8, ValidatorRegistryLimit>,
+    #[serde(with = "quoted_u8_var_list")]
+    pub current_epoch_participation: VariableList<u8, ValidatorRegistryLimit>,
+
+    // Finality
+    pub justification_bits: BitVector<U4>,
+    pub previous_justified_checkpoint: Checkpoint,
+    pub current_justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+
+    // Inactivity
+    #[serde(with = "quoted_u64_var_list")]
+    pub inactivity_scores: VariableList<u64, ValidatorRegistryLimit>,
+
+    // Sync
+    pub current_sync_committee: Arc<SyncCommittee>,
+    pub next_sync_committee: Arc<SyncCommittee>,
+
+    // Execution
+    pub latest_execution_payload_header: ExecutionPayloadHeader,
+
+    // Withdrawals
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub next_withdrawal_index: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub next_withdrawal_validator_index: u64,
+
+    // Deep history valid from Capella onwards.
+    pub historical_summaries: VariableList<HistoricalSummary, U16777216>,
+}
+
+impl BeaconState {
+    /// Return the current epoch.
+    pub fn get_current_epoch(&self) -> u64 {
+        compute_epoch_at_slot(self.slot)
+    }
+
+    pub fn get_expected_withdrawals(&self) -> anyhow::Result<Vec<Withdrawal>> {
+        get_expected_withdrawals(
+            &self.validators,
+            &self.balances,
+            self.next_withdrawal_index,
+            self.next_withdrawal_validator_index,
+            self.get_current_epoch(),
+        )
+    }
+
+    pub fn process_withdrawals(&mut self, payload: &ExecutionPayload) -> anyhow::Result<()> {
+        let expected_withdrawals = self.get_expected_withdrawals()?;
+        ensure!(
+            payload.withdrawals.deref() == expected_withdrawals,
+            "Withdrawals do not match expected withdrawals",
+        );
+
+        for withdrawal in &expected_withdrawals {
+            let balance = self
+                .balances
+                .get_mut(withdrawal.validator_index as usize)
+                .ok_or(anyhow!("failed to decrease balance"))?;
+            *balance = balance.saturating_sub(withdrawal.amount);
+        }
+
+        // Update the next withdrawal index if this block contained withdrawals
+        if let Some(latest_withdrawal) = expected_withdrawals.last() {
+            self.next_withdrawal_index = latest_withdrawal.index + 1;
+        }
+
+        // Update the next validator index to start the next withdrawal sweep
+        self.next_withdrawal_validator_index = get_next_withdrawal_validator_index(
+            &expected_withdrawals,
+            self.next_withdrawal_validator_index,
+            self.validators.len(),
+        );
+
+        Ok(())
+    }
+
+    /// Upgrade the state to the Deneb fork at the Deneb fork epoch.
+    pub fn upgrade_to_deneb(self) -> DenebBeaconState {
+        let epoch = self.get_current_epoch();
+        let pre_header = self.latest_execution_payload_header;
+        DenebBeaconState {
+            genesis_time: self.genesis_time,
+            genesis_validators_root: self.genesis_validators_root,
+            slot: self.slot,
+            fork: Fork {
+                previous_version: self.fork.current_version,
+                current_version: beacon_network_spec().deneb_fork_version,
+                epoch,
+            },
+            latest_block_header: self.latest_block_header,
+            block_roots: self.block_roots,
+            state_roots: self.state_roots,
+            historical_roots: self.historical_roots,
+            eth1_data: self.eth1_data,
+            eth1_data_votes: self.eth1_data_votes,
+            eth1_deposit_index: self.eth1_deposit_index,
+            validators: self.validators,
+            balances: self.balances,
+            randao_mixes: self.randao_mixes,
+            slashings: self.slashings,
+            previous_epoch_participation: self.previous_epoch_participation,
+            current_epoch_participation: self.current_epoch_participation,
+            justification_bits: self.justification_bits,
+            previous_justified_checkpoint: self.previous_justified_checkpoint,
+            current_justified_checkpoint: self.current_justified_checkpoint,
+            finalized_checkpoint: self.finalized_checkpoint,
+            inactivity_scores: self.inactivity_scores,
+            current_sync_committee: self.current_sync_committee,
+            next_sync_committee: self.next_sync_committee,
+            latest_execution_payload_header: DenebExecutionPayloadHeader {
+                parent_hash: pre_header.parent_hash,
+                fee_recipient: pre_header.fee_recipient,
+                state_root: pre_header.state_root,
+                receipts_root: pre_header.receipts_root,
+                logs_bloom: pre_header.logs_bloom,
+                prev_randao: pre_header.prev_randao,
+                block_number: pre_header.block_number,
+                gas_limit: pre_header.gas_limit,
+                gas_used: pre_header.gas_used,
+                timestamp: pre_header.timestamp,
+                extra_data: pre_header.extra_data,
+                base_fee_per_gas: pre_header.base_fee_per_gas,
+                block_hash: pre_header.block_hash,
+                transactions_root: pre_header.transactions_root,
+                withdrawals_root: pre_header.withdrawals_root,
+                blob_gas_used: 0,
+                excess_blob_gas: 0,
+            },
+            next_withdrawal_index: self.next_withdrawal_index,
+            next_withdrawal_validator_index: self.next_withdrawal_validator_index,
+            historical_summaries: self.historical_summaries,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use alloy_primitives::{Address, B256, U256};
+use ream_consensus_misc::{misc::checksummed_address, preset::MaxWithdrawalsPerPayload};
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{
+    FixedVector, VariableList,
+    serde_utils::{hex_fixed_vec, hex_var_list, list_of_hex_var_list},
+    typenum::{self, U32},
+};
+use tree_hash_derive::TreeHash;
+
+use crate::{electra::execution_payload::Transactions, withdrawal::Withdrawal};
+
+/// The execution payload of the Capella fork, which adds withdrawals.
+#[derive(
+    Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash,
+)]
+pub struct ExecutionPayload {
+    // Execution block header fields
+    pub parent_hash: B256,
+    #[serde(with = "checksummed_address")]
+    pub fee_recipient: Address,
+    pub state_root: B256,
+    pub receipts_root: B256,
+    #[serde(with = "hex_fixed_vec")]
+    pub logs_bloom: FixedVector<u8, typenum::U256>,
+    pub prev_randao: B256,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub block_number: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub gas_limit: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub gas_used: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub timestamp: u64,
+    #[serde(with = "hex_var_list")]
+    pub extra_data: VariableList<u8, U32>,
+    #[serde(with = "serde_utils::quoted_u256")]
+    pub base_fee_per_gas: U256,
+
+    // Extra payload fields
+    pub block_hash: B256,
+    #[serde(with = "list_of_hex_var_list")]
+    pub transactions: Transactions,
+    pub withdrawals: VariableList<Withdrawal, MaxWithdrawalsPerPayload>,
+}
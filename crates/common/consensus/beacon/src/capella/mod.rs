@@ -0,0 +1,4 @@
+pub mod beacon_state;
+pub mod execution_payload;
+pub mod execution_payload_header;
+pub mod withdrawals;
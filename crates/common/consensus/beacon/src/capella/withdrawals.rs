@@ -0,0 +1,158 @@
+use std::cmp::{max, min};
+
+use alloy_primitives::Address;
+use anyhow::anyhow;
+use ream_consensus_misc::{
+    constants::{
+        MAX_EFFECTIVE_BALANCE, MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP, MAX_WITHDRAWALS_PER_PAYLOAD,
+    },
+    validator::Validator,
+};
+
+use crate::withdrawal::Withdrawal;
+
+/// Check if ``validator`` is fully withdrawable, before Electra added compounding credentials.
+pub fn is_fully_withdrawable_validator(validator: &Validator, balance: u64, epoch: u64) -> bool {
+    validator.has_eth1_withdrawal_credential()
+        && validator.withdrawable_epoch <= epoch
+        && balance > 0
+}
+
+/// Check if ``validator`` is partially withdrawable, before Electra added compounding credentials.
+pub fn is_partially_withdrawable_validator(validator: &Validator, balance: u64) -> bool {
+    validator.has_eth1_withdrawal_credential()
+        && validator.effective_balance == MAX_EFFECTIVE_BALANCE
+        && balance > MAX_EFFECTIVE_BALANCE
+}
+
+/// Return the withdrawals of the next execution payload, sweeping at most
+/// ``MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP`` validators from ``next_withdrawal_validator_index``.
+///
+/// Shared by the Capella and Deneb beacon states, which withdraw alike.
+pub fn get_expected_withdrawals(
+    validators: &[Validator],
+    balances: &[u64],
+    next_withdrawal_index: u64,
+    next_withdrawal_validator_index: u64,
+    epoch: u64,
+) -> anyhow::Result<Vec<Withdrawal>> {
+    let mut withdrawal_index = next_withdrawal_index;
+    let mut validator_index = next_withdrawal_validator_index;
+    let mut withdrawals: Vec<Withdrawal> = vec![];
+    let bound = min(validators.len(), MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP);
+    for _ in 0..bound {
+        let validator = validators
+            .get(validator_index as usize)
+            .ok_or(anyhow!("Validator index out of bounds: {validator_index}"))?;
+        let balance = *balances
+            .get(validator_index as usize)
+            .ok_or(anyhow!("Balance index out of bounds: {validator_index}"))?;
+        let amount = if is_fully_withdrawable_validator(validator, balance, epoch) {
+            Some(balance)
+        } else if is_partially_withdrawable_validator(validator, balance) {
+            Some(balance - MAX_EFFECTIVE_BALANCE)
+        } else {
+            None
+        };
+        if let Some(amount) = amount {
+            withdrawals.push(Withdrawal {
+                index: withdrawal_index,
+                validator_index,
+                address: Address::from_slice(&validator.withdrawal_credentials[12..]),
+                amount,
+            });
+            withdrawal_index += 1;
+        }
+        if withdrawals.len() == MAX_WITHDRAWALS_PER_PAYLOAD as usize {
+            break;
+        }
+        validator_index = (validator_index + 1) % validators.len() as u64;
+    }
+
+    Ok(withdrawals)
+}
+
+/// Return the validator index the withdrawal sweep of the next payload starts from, once
+/// ``expected_withdrawals`` are processed.
+pub fn get_next_withdrawal_validator_index(
+    expected_withdrawals: &[Withdrawal],
+    next_withdrawal_validator_index: u64,
+    validator_count: usize,
+) -> u64 {
+    // An empty registry has nothing to sweep
+    let validator_count = max(validator_count, 1) as u64;
+    match expected_withdrawals.last() {
+        // Next sweep starts after the latest withdrawal's validator index
+        Some(latest_withdrawal)
+            if expected_withdrawals.len() == MAX_WITHDRAWALS_PER_PAYLOAD as usize =>
+        {
+            (latest_withdrawal.validator_index + 1) % validator_count
+        }
+        // Advance sweep by the max length of the sweep if there was not a full set of
+        // withdrawals
+        _ => {
+            (next_withdrawal_validator_index + MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP as u64)
+                % validator_count
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::B256;
+    use ream_consensus_misc::constants::FAR_FUTURE_EPOCH;
+
+    use super::*;
+
+    fn validator(
+        withdrawal_prefix: u8,
+        effective_balance: u64,
+        withdrawable_epoch: u64,
+    ) -> Validator {
+        let mut withdrawal_credentials = B256::repeat_byte(0xaa);
+        withdrawal_credentials[0] = withdrawal_prefix;
+        Validator {
+            public_key: Default::default(),
+            withdrawal_credentials,
+            effective_balance,
+            slashed: false,
+            activation_eligibility_epoch: 0,
+            activation_epoch: 0,
+            exit_epoch: FAR_FUTURE_EPOCH,
+            withdrawable_epoch,
+        }
+    }
+
+    #[test]
+    fn test_get_expected_withdrawals_sweeps_from_the_next_validator() -> anyhow::Result<()> {
+        let validators = vec![
+            // Fully withdrawable
+            validator(0x01, MAX_EFFECTIVE_BALANCE, 5),
+            // BLS credentials are never withdrawn
+            validator(0x00, MAX_EFFECTIVE_BALANCE, 5),
+            // Partially withdrawable
+            validator(0x01, MAX_EFFECTIVE_BALANCE, FAR_FUTURE_EPOCH),
+            // Compounding credentials only withdraw from Electra
+            validator(0x02, MAX_EFFECTIVE_BALANCE, 5),
+        ];
+        let balances = vec![MAX_EFFECTIVE_BALANCE + 1; validators.len()];
+
+        let withdrawals = get_expected_withdrawals(&validators, &balances, 7, 2, 10)?;
+        assert_eq!(
+            withdrawals
+                .iter()
+                .map(|withdrawal| (
+                    withdrawal.index,
+                    withdrawal.validator_index,
+                    withdrawal.amount
+                ))
+                .collect::<Vec<_>>(),
+            vec![(7, 2, 1), (8, 0, MAX_EFFECTIVE_BALANCE + 1)]
+        );
+        assert_eq!(
+            get_next_withdrawal_validator_index(&withdrawals, 2, validators.len()),
+            (2 + MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP as u64) % validators.len() as u64
+        );
+        Ok(())
+    }
+}
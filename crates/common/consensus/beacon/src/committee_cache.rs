@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use alloy_primitives::B256;
+use anyhow::ensure;
+use ream_consensus_misc::{
+    constants::{DOMAIN_BEACON_ATTESTER, SLOTS_PER_EPOCH},
+    misc::{cached_shuffle_list, compute_epoch_at_slot, compute_start_slot_at_epoch},
+};
+
+use crate::electra::beacon_state::BeaconState;
+
+/// The beacon committees of an epoch, shuffled once so that every (slot, index) lookup of the
+/// epoch is a slice of the shuffling instead of a new shuffle of the active validators.
+///
+/// The committees only depend on the active validators and the seed of the epoch, which are both
+/// fixed by the block at the [`ShufflingId`] decision slot, so a cache can be shared by every
+/// state descending from that block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitteeCache {
+    epoch: u64,
+    committees_per_slot: u64,
+    shuffling: Arc<[u64]>,
+}
+
+impl CommitteeCache {
+    pub fn new(state: &BeaconState, epoch: u64) -> anyhow::Result<Self> {
+        let next_epoch = state.get_current_epoch() + 1;
+        ensure!(
+            epoch <= next_epoch,
+            "Requested epoch {epoch} is beyond the allowed maximum (next epoch: {next_epoch})",
+        );
+
+        Ok(Self {
+            epoch,
+            committees_per_slot: state.get_committee_count_per_slot(epoch),
+            shuffling: cached_shuffle_list(
+                &state.get_active_validator_indices(epoch),
+                state.get_seed(epoch, DOMAIN_BEACON_ATTESTER),
+            ),
+        })
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub fn committees_per_slot(&self) -> u64 {
+        self.committees_per_slot
+    }
+
+    /// Return the beacon committee at ``slot`` for ``index``, as `get_beacon_committee` does.
+    pub fn get_beacon_committee(&self, slot: u64, index: u64) -> anyhow::Result<&[u64]> {
+        ensure!(
+            compute_epoch_at_slot(slot) == self.epoch,
+            "Slot {slot} is not in the cached epoch {}",
+            self.epoch
+        );
+        ensure!(
+            index < self.committees_per_slot,
+            "Committee index {index} must be less than {}",
+            self.committees_per_slot
+        );
+
+        let count = self.committees_per_slot * SLOTS_PER_EPOCH;
+        let committee_index = (slot % SLOTS_PER_EPOCH) * self.committees_per_slot + index;
+        let validator_count = self.shuffling.len() as u64;
+        let start = (validator_count * committee_index / count) as usize;
+        let end = (validator_count * (committee_index + 1) / count) as usize;
+        Ok(&self.shuffling[start..end])
+    }
+}
+
+/// Identifies the shuffling of an epoch by the block which decided it: the block at the last
+/// slot of the epoch before the previous one, at which the seed and the active validators of the
+/// epoch were fixed.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct ShufflingId {
+    pub epoch: u64,
+    pub decision_root: B256,
+}
+
+impl ShufflingId {
+    /// Identifies the shuffling of `epoch` for `state`, whose latest block is `block_root`.
+    pub fn new(state: &BeaconState, block_root: B256, epoch: u64) -> anyhow::Result<Self> {
+        Self::with_decision_slot(
+            state,
+            block_root,
+            epoch,
+            compute_start_slot_at_epoch(epoch.saturating_sub(1)).saturating_sub(1),
+        )
+    }
+
+    /// Identifies the proposers of `epoch`, which are sampled by the effective balances updated
+    /// at the start of the epoch, so they are decided by the block at the last slot of the
+    /// previous epoch.
+    pub fn for_proposers(
+        state: &BeaconState,
+        block_root: B256,
+        epoch: u64,
+    ) -> anyhow::Result<Self> {
+        Self::with_decision_slot(
+            state,
+            block_root,
+            epoch,
+            compute_start_slot_at_epoch(epoch).saturating_sub(1),
+        )
+    }
+
+    fn with_decision_slot(
+        state: &BeaconState,
+        block_root: B256,
+        epoch: u64,
+        decision_slot: u64,
+    ) -> anyhow::Result<Self> {
+        // From the slot of the state on, the decision block is the latest block of the state, the
+        // root of `latest_block_header` with its state root filled in. The state root is only
+        // filled in at the next slot, so the root is taken from the caller instead of hashing the
+        // state
+        let decision_root = if decision_slot < state.slot {
+            state.get_block_root_at_slot(decision_slot)?
+        } else {
+            block_root
+        };
+
+        Ok(Self {
+            epoch,
+            decision_root,
+        })
+    }
+}
@@ -0,0 +1,61 @@
+use alloy_primitives::B256;
+use ream_bls::BLSSignature;
+use ream_consensus_misc::beacon_block_header::{BeaconBlockHeader, SignedBeaconBlockHeader};
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use tree_hash::TreeHash;
+use tree_hash_derive::TreeHash;
+
+use super::beacon_block_body::BeaconBlockBody;
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct SignedBeaconBlock {
+    pub message: BeaconBlock,
+    pub signature: BLSSignature,
+}
+
+impl SignedBeaconBlock {
+    /// Returns the root of the block without its signature.
+    pub fn message_root(&self) -> B256 {
+        self.message.block_root()
+    }
+
+    /// Returns the root of the block together with its signature.
+    pub fn signed_root(&self) -> B256 {
+        self.tree_hash_root()
+    }
+
+    pub fn signed_header(&self) -> SignedBeaconBlockHeader {
+        SignedBeaconBlockHeader {
+            message: self.message.block_header(),
+            signature: self.signature.clone(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct BeaconBlock {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub slot: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub proposer_index: u64,
+    pub parent_root: B256,
+    pub state_root: B256,
+    pub body: BeaconBlockBody,
+}
+
+impl BeaconBlock {
+    pub fn block_root(&self) -> B256 {
+        self.tree_hash_root()
+    }
+
+    pub fn block_header(&self) -> BeaconBlockHeader {
+        BeaconBlockHeader {
+            slot: self.slot,
+            proposer_index: self.proposer_index,
+            parent_root: self.parent_root,
+            state_root: self.state_root,
+            body_root: self.body.tree_hash_root(),
+        }
+    }
+}
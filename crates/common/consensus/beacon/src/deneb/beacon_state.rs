@@ -0,0 +1,1439 @@
+use std::{
+    cmp::{max, min},
+    collections::{BTreeSet, HashMap},
+    ops::Deref,
+    sync::Arc,
+};
+
+use alloy_primitives::{B256, aliases::B32};
+use anyhow::{anyhow, ensure};
+use itertools::Itertools;
+use ream_bls::{BLSSignature, PublicKey, traits::Verifiable};
+use ream_consensus_misc::{
+    attestation_data::AttestationData,
+    beacon_block_header::BeaconBlockHeader,
+    checkpoint::Checkpoint,
+    constants::{
+        CAPELLA_FORK_VERSION, DEPOSIT_CONTRACT_TREE_DEPTH, DOMAIN_BEACON_ATTESTER,
+        DOMAIN_BEACON_PROPOSER, DOMAIN_RANDAO, DOMAIN_SYNC_COMMITTEE, DOMAIN_VOLUNTARY_EXIT,
+        EFFECTIVE_BALANCE_INCREMENT, EPOCHS_PER_ETH1_VOTING_PERIOD, EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR, FAR_FUTURE_EPOCH, GENESIS_SLOT, MAX_BLOBS_PER_BLOCK_DENEB,
+        MAX_DEPOSITS, MAX_EFFECTIVE_BALANCE, MIN_ATTESTATION_INCLUSION_DELAY,
+        MIN_SLASHING_PENALTY_QUOTIENT_BELLATRIX, PARTICIPATION_FLAG_WEIGHTS, PROPOSER_WEIGHT,
+        SLOTS_PER_EPOCH, UNSET_DEPOSIT_REQUESTS_START_INDEX, WEIGHT_DENOMINATOR,
+        WHISTLEBLOWER_REWARD_QUOTIENT,
+    },
+    eth_1_data::Eth1Data,
+    fork::Fork,
+    hashing::{hash, hash_fixed},
+    indexed_attestation::IndexedAttestation,
+    misc::{
+        compute_activation_exit_epoch, compute_committee, compute_domain, compute_epoch_at_slot,
+        compute_shuffled_index, compute_signing_root, compute_start_slot_at_epoch,
+    },
+    preset::{
+        EpochsPerHistoricalVector, EpochsPerSlashingsVector, SlotsPerEth1VotingPeriod,
+        SlotsPerHistoricalRoot,
+    },
+    validator::Validator,
+};
+use ream_merkle::is_valid_merkle_branch;
+use ream_network_spec::networks::beacon_network_spec;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{
+    BitVector, FixedVector, VariableList,
+    serde_utils::{quoted_u64_fixed_vec, quoted_u64_var_list},
+    typenum::{U4, U16777216},
+};
+use tree_hash::TreeHash;
+use tree_hash_derive::TreeHash;
+
+use super::{beacon_block::BeaconBlock, beacon_block_body::BeaconBlockBody};
+use crate::{
+    bls_to_execution_change::SignedBLSToExecutionChange,
+    capella::withdrawals::{get_expected_withdrawals, get_next_withdrawal_validator_index},
+    deposit::Deposit,
+    electra::{
+        beacon_state::{
+            BeaconState as ElectraBeaconState, compute_base_reward_per_increment,
+            eth_fast_aggregate_verify, is_valid_deposit_signature,
+        },
+        execution_payload::ExecutionPayload,
+        execution_payload_header::ExecutionPayloadHeader,
+        zkvm_types::ValidatorRegistryLimit,
+    },
+    helpers::{
+        self, add_participation_flags, compute_base_reward, compute_proposer_reward,
+        get_eth1_withdrawal_credentials, xor,
+    },
+    historical_summary::HistoricalSummary,
+    pending_deposit::PendingDeposit,
+    phase0::{attestation::Attestation, attester_slashing::AttesterSlashing},
+    predicates::is_slashable_attestation_data,
+    proposer_slashing::ProposerSlashing,
+    quoted::quoted_u8_var_list,
+    sync_aggregate::SyncAggregate,
+    sync_committee::SyncCommittee,
+    voluntary_exit::SignedVoluntaryExit,
+    withdrawal::Withdrawal,
+};
+
+/// The largest value of the random byte weighting proposer selection by effective balance.
+const MAX_RANDOM_BYTE: u64 = (1 << 8) - 1;
+
+/// The beacon state of the Deneb fork, whose execution payload header tracks blob gas.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct BeaconState {
+    // Versioning
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub genesis_time: u64,
+    pub genesis_validators_root: B256,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub slot: u64,
+    pub fork: Fork,
+
+    // History
+    pub latest_block_header: BeaconBlockHeader,
+    pub block_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
+    pub state_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
+    pub historical_roots: VariableList<B256, U16777216>,
+
+    // Eth1
+    pub eth1_data: Eth1Data,
+    pub eth1_data_votes: VariableList<Eth1Data, SlotsPerEth1VotingPeriod>,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub eth1_deposit_index: u64,
+
+    // Registry
+    pub validators: VariableList<Validator, ValidatorRegistryLimit>,
+    #[serde(with = "quoted_u64_var_list")]
+    pub balances: VariableList<u64, ValidatorRegistryLimit>,
+
+    // Randomness
+    pub randao_mixes: FixedVector<B256, EpochsPerHistoricalVector>,
+
+    // Slashings
+    #[serde(with = "quoted_u64_fixed_vec")]
+    pub slashings: FixedVector<u64, EpochsPerSlashingsVector>,
+
+    // Participation
+    #[serde(with = "quoted_u8_var_list")]
+    pub previous_epoch_participation: VariableList<u8, ValidatorRegistryLimit>,
+    #[serde(with = "quoted_u8_var_list")]
+    pub current_epoch_participation: VariableList<u8, ValidatorRegistryLimit>,
+
+    // Finality
+    pub justification_bits: BitVector<U4>,
+    pub previous_justified_checkpoint: Checkpoint,
+    pub current_justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+
+    // Inactivity
+    #[serde(with = "quoted_u64_var_list")]
+    pub inactivity_scores: VariableList<u64, ValidatorRegistryLimit>,
+
+    // Sync
+    pub current_sync_committee: Arc<SyncCommittee>,
+    pub next_sync_committee: Arc<SyncCommittee>,
+
+    // Execution
+    pub latest_execution_payload_header: ExecutionPayloadHeader,
+
+    // Withdrawals
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub next_withdrawal_index: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub next_withdrawal_validator_index: u64,
+
+    // Deep history valid from Capella onwards.
+    pub historical_summaries: VariableList<HistoricalSummary, U16777216>,
+}
+
+impl BeaconState {
+    /// Return the current epoch.
+    pub fn get_current_epoch(&self) -> u64 {
+        compute_epoch_at_slot(self.slot)
+    }
+
+    /// Return the previous epoch (unless the current epoch is ``GENESIS_EPOCH``).
+    pub fn get_previous_epoch(&self) -> u64 {
+        helpers::get_previous_epoch(self.get_current_epoch())
+    }
+
+    /// Return the block root at the start of a recent ``epoch``.
+    pub fn get_block_root(&self, epoch: u64) -> anyhow::Result<B256> {
+        self.get_block_root_at_slot(compute_start_slot_at_epoch(epoch))
+    }
+
+    /// Return the block root at a recent ``slot``.
+    pub fn get_block_root_at_slot(&self, slot: u64) -> anyhow::Result<B256> {
+        helpers::get_block_root_at_slot(&self.block_roots, self.slot, slot)
+    }
+
+    /// Return the randao mix at a recent ``epoch``.
+    pub fn get_randao_mix(&self, epoch: u64) -> B256 {
+        self.randao_mixes[(epoch % EPOCHS_PER_HISTORICAL_VECTOR) as usize]
+    }
+
+    /// Return the sequence of active validator indices at ``epoch``.
+    pub fn get_active_validator_indices(&self, epoch: u64) -> Vec<u64> {
+        helpers::get_active_validator_indices(&self.validators, epoch)
+    }
+
+    /// Return the validator churn limit for the current epoch.
+    pub fn get_validator_churn_limit(&self) -> u64 {
+        let network_spec = beacon_network_spec();
+        let active_validator_indices = self.get_active_validator_indices(self.get_current_epoch());
+        max(
+            network_spec.min_per_epoch_churn_limit,
+            active_validator_indices.len() as u64 / network_spec.churn_limit_quotient,
+        )
+    }
+
+    /// Return the seed at ``epoch``.
+    pub fn get_seed(&self, epoch: u64, domain_type: B32) -> B256 {
+        helpers::get_seed(&self.randao_mixes, epoch, domain_type)
+    }
+
+    /// Return the number of committees in each slot for the given ``epoch``.
+    pub fn get_committee_count_per_slot(&self, epoch: u64) -> u64 {
+        helpers::get_committee_count_per_slot(self.get_active_validator_indices(epoch).len())
+    }
+
+    /// Return the beacon committee at ``slot`` for ``index``.
+    pub fn get_beacon_committee(&self, slot: u64, index: u64) -> anyhow::Result<Vec<u64>> {
+        let epoch = compute_epoch_at_slot(slot);
+        let committees_per_slot = self.get_committee_count_per_slot(epoch);
+        compute_committee(
+            &self.get_active_validator_indices(epoch),
+            self.get_seed(epoch, DOMAIN_BEACON_ATTESTER),
+            (slot % SLOTS_PER_EPOCH) * committees_per_slot + index,
+            committees_per_slot * SLOTS_PER_EPOCH,
+        )
+    }
+
+    /// Return the beacon proposer index at the current slot.
+    pub fn get_beacon_proposer_index(&self) -> anyhow::Result<u64> {
+        let epoch = self.get_current_epoch();
+        let seed = B256::from(hash_fixed(
+            &[
+                self.get_seed(epoch, DOMAIN_BEACON_PROPOSER).as_slice(),
+                &self.slot.to_le_bytes(),
+            ]
+            .concat(),
+        ));
+        let indices = self.get_active_validator_indices(epoch);
+        ensure!(!indices.is_empty(), "No active validator to propose");
+
+        let total = indices.len();
+        let mut i: usize = 0;
+        loop {
+            let candidate_index = indices[compute_shuffled_index(i % total, total, seed)?];
+            let random_byte =
+                hash_fixed(&[seed.as_slice(), &((i / 32) as u64).to_le_bytes()].concat())[i % 32];
+            let effective_balance = self.validators[candidate_index as usize].effective_balance;
+            if effective_balance * MAX_RANDOM_BYTE >= MAX_EFFECTIVE_BALANCE * random_byte as u64 {
+                return Ok(candidate_index);
+            }
+
+            i += 1;
+        }
+    }
+
+    /// Return the signature domain (fork version concatenated with domain type) of a message.
+    pub fn get_domain(&self, domain_type: B32, epoch: Option<u64>) -> B256 {
+        helpers::get_domain(
+            &self.fork,
+            self.genesis_validators_root,
+            domain_type,
+            epoch.unwrap_or_else(|| self.get_current_epoch()),
+        )
+    }
+
+    /// Return the combined effective balance of the active validators.
+    pub fn get_total_active_balance(&self) -> u64 {
+        max(
+            EFFECTIVE_BALANCE_INCREMENT,
+            self.get_active_validator_indices(self.get_current_epoch())
+                .into_iter()
+                .map(|index| self.validators[index as usize].effective_balance)
+                .sum(),
+        )
+    }
+
+    pub fn get_base_reward_per_increment(&self) -> u64 {
+        compute_base_reward_per_increment(self.get_total_active_balance())
+    }
+
+    /// Increase the validator balance at index ``index`` by ``delta``.
+    pub fn increase_balance(&mut self, index: u64, delta: u64) -> anyhow::Result<()> {
+        if let Some(balance) = self.balances.get_mut(index as usize) {
+            *balance = balance
+                .checked_add(delta)
+                .ok_or_else(|| anyhow!("Balance of validator {index} overflows"))?;
+            Ok(())
+        } else {
+            Err(anyhow!("failed to increase balance"))
+        }
+    }
+
+    /// Decrease the validator balance at index ``index`` by ``delta`` with underflow protection.
+    pub fn decrease_balance(&mut self, index: u64, delta: u64) -> anyhow::Result<()> {
+        if let Some(balance) = self.balances.get_mut(index as usize) {
+            *balance = balance.saturating_sub(delta);
+            Ok(())
+        } else {
+            Err(anyhow!("failed to decrease balance"))
+        }
+    }
+
+    /// Set the validator balance at index ``index`` to ``balance``.
+    pub fn set_balance(&mut self, index: u64, balance: u64) -> anyhow::Result<()> {
+        if let Some(current_balance) = self.balances.get_mut(index as usize) {
+            *current_balance = balance;
+            Ok(())
+        } else {
+            Err(anyhow!("failed to set balance"))
+        }
+    }
+
+    /// Return the set of attesting indices corresponding to ``attestation``.
+    pub fn get_attesting_indices(
+        &self,
+        attestation: &Attestation,
+    ) -> anyhow::Result<BTreeSet<u64>> {
+        let committee = self.get_beacon_committee(attestation.data.slot, attestation.data.index)?;
+        ensure!(
+            attestation.aggregation_bits.len() == committee.len(),
+            "Aggregation bits length {} must match the committee size {}",
+            attestation.aggregation_bits.len(),
+            committee.len()
+        );
+        Ok(committee
+            .into_iter()
+            .zip(attestation.aggregation_bits.iter())
+            .filter_map(|(index, bit)| bit.then_some(index))
+            .collect())
+    }
+
+    /// Return the indexed attestation corresponding to ``attestation``.
+    pub fn get_indexed_attestation(
+        &self,
+        attestation: &Attestation,
+    ) -> anyhow::Result<IndexedAttestation> {
+        Ok(IndexedAttestation {
+            attesting_indices: self
+                .get_attesting_indices(attestation)?
+                .into_iter()
+                .collect::<Vec<_>>()
+                .into(),
+            data: attestation.data.clone(),
+            signature: attestation.signature.clone(),
+        })
+    }
+
+    /// Check if ``indexed_attestation`` is not empty, has sorted and unique indices and has a valid
+    /// aggregate signature.
+    pub fn is_valid_indexed_attestation(
+        &self,
+        indexed_attestation: &IndexedAttestation,
+    ) -> anyhow::Result<bool> {
+        helpers::is_valid_indexed_attestation(
+            indexed_attestation,
+            &self.fork,
+            self.genesis_validators_root,
+            |index| self.validators.get(index),
+        )
+    }
+
+    /// Return the flag indices that are satisfied by an attestation.
+    pub fn get_attestation_participation_flag_indices(
+        &self,
+        data: &AttestationData,
+        inclusion_delay: u64,
+    ) -> anyhow::Result<Vec<u8>> {
+        let justified_checkpoint = if data.target.epoch == self.get_current_epoch() {
+            &self.current_justified_checkpoint
+        } else {
+            &self.previous_justified_checkpoint
+        };
+        helpers::get_attestation_participation_flag_indices(
+            data,
+            inclusion_delay,
+            justified_checkpoint,
+            |slot| self.get_block_root_at_slot(slot),
+        )
+    }
+
+    pub fn process_attestation(&mut self, attestation: &Attestation) -> anyhow::Result<()> {
+        let data = &attestation.data;
+        ensure!(
+            data.target.epoch == self.get_previous_epoch()
+                || data.target.epoch == self.get_current_epoch(),
+            "Target epoch must be the previous or current epoch"
+        );
+        ensure!(
+            data.target.epoch == compute_epoch_at_slot(data.slot),
+            "Target epoch must match the attestation slot"
+        );
+        ensure!(
+            data.slot + MIN_ATTESTATION_INCLUSION_DELAY <= self.slot,
+            "Attestation must be included at least {MIN_ATTESTATION_INCLUSION_DELAY} slot after \
+             its slot"
+        );
+        ensure!(
+            data.index < self.get_committee_count_per_slot(data.target.epoch),
+            "Committee index {} is out of range",
+            data.index
+        );
+
+        // Participation flag indices
+        let participation_flag_indices =
+            self.get_attestation_participation_flag_indices(data, self.slot - data.slot)?;
+
+        // Verify signature
+        let indexed_attestation = self.get_indexed_attestation(attestation)?;
+        ensure!(
+            self.is_valid_indexed_attestation(&indexed_attestation)?,
+            "Attestation signature must be valid"
+        );
+
+        // Update epoch participation flags
+        let base_reward_per_increment = self.get_base_reward_per_increment();
+        let is_current_epoch = data.target.epoch == self.get_current_epoch();
+        let mut proposer_reward_numerator = 0;
+        for &index in indexed_attestation.attesting_indices.iter() {
+            let epoch_participation = if is_current_epoch {
+                &mut self.current_epoch_participation
+            } else {
+                &mut self.previous_epoch_participation
+            };
+            let flags = epoch_participation
+                .get_mut(index as usize)
+                .ok_or(anyhow!("Participation index out of bounds: {index}"))?;
+            for flag_index in add_participation_flags(flags, &participation_flag_indices) {
+                proposer_reward_numerator += compute_base_reward(
+                    self.validators[index as usize].effective_balance,
+                    base_reward_per_increment,
+                ) * PARTICIPATION_FLAG_WEIGHTS[flag_index as usize];
+            }
+        }
+
+        // Reward proposer
+        let proposer_index = self.get_beacon_proposer_index()?;
+        self.increase_balance(
+            proposer_index,
+            compute_proposer_reward(proposer_reward_numerator),
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get_expected_withdrawals(&self) -> anyhow::Result<Vec<Withdrawal>> {
+        get_expected_withdrawals(
+            &self.validators,
+            &self.balances,
+            self.next_withdrawal_index,
+            self.next_withdrawal_validator_index,
+            self.get_current_epoch(),
+        )
+    }
+
+    pub fn process_withdrawals(&mut self, payload: &ExecutionPayload) -> anyhow::Result<()> {
+        let expected_withdrawals = self.get_expected_withdrawals()?;
+        ensure!(
+            payload.withdrawals.deref() == expected_withdrawals,
+            "Withdrawals do not match expected withdrawals",
+        );
+
+        for withdrawal in &expected_withdrawals {
+            self.decrease_balance(withdrawal.validator_index, withdrawal.amount)?;
+        }
+
+        // Update the next withdrawal index if this block contained withdrawals
+        if let Some(latest_withdrawal) = expected_withdrawals.last() {
+            self.next_withdrawal_index = latest_withdrawal.index + 1;
+        }
+
+        // Update the next validator index to start the next withdrawal sweep
+        self.next_withdrawal_validator_index = get_next_withdrawal_validator_index(
+            &expected_withdrawals,
+            self.next_withdrawal_validator_index,
+            self.validators.len(),
+        );
+
+        Ok(())
+    }
+
+    /// Initiate the exit of the validator with index ``index``, behind the exits already queued
+    /// up to the churn limit.
+    pub fn initiate_validator_exit(&mut self, index: u64) -> anyhow::Result<()> {
+        // Return if validator already initiated exit
+        let validator = self
+            .validators
+            .get(index as usize)
+            .ok_or_else(|| anyhow!("Validator at index {index} not found"))?;
+        if validator.exit_epoch != FAR_FUTURE_EPOCH {
+            return Ok(());
+        }
+
+        // Compute exit queue epoch
+        let mut exit_queue_epoch = self
+            .validators
+            .iter()
+            .map(|validator| validator.exit_epoch)
+            .filter(|&exit_epoch| exit_epoch != FAR_FUTURE_EPOCH)
+            .fold(compute_activation_exit_epoch(self.get_current_epoch()), max);
+        let exit_queue_churn = self
+            .validators
+            .iter()
+            .filter(|validator| validator.exit_epoch == exit_queue_epoch)
+            .count() as u64;
+        if exit_queue_churn >= self.get_validator_churn_limit() {
+            exit_queue_epoch += 1;
+        }
+
+        // Set validator exit epoch and withdrawable epoch
+        let validator = &mut self.validators[index as usize];
+        validator.exit_epoch = exit_queue_epoch;
+        validator.withdrawable_epoch = exit_queue_epoch
+            .checked_add(beacon_network_spec().min_validator_withdrawability_delay)
+            .ok_or_else(|| anyhow!("Failed to set withdrawable epoch"))?;
+
+        Ok(())
+    }
+
+    /// Slash the validator with index ``slashed_index``.
+    pub fn slash_validator(
+        &mut self,
+        slashed_index: u64,
+        whistleblower_index: Option<u64>,
+    ) -> anyhow::Result<()> {
+        let epoch = self.get_current_epoch();
+        self.initiate_validator_exit(slashed_index)?;
+
+        let validator = &mut self.validators[slashed_index as usize];
+        validator.slashed = true;
+        validator.withdrawable_epoch = max(
+            validator.withdrawable_epoch,
+            epoch + EPOCHS_PER_SLASHINGS_VECTOR,
+        );
+        let effective_balance = validator.effective_balance;
+        self.slashings[(epoch % EPOCHS_PER_SLASHINGS_VECTOR) as usize] += effective_balance;
+        self.decrease_balance(
+            slashed_index,
+            effective_balance / MIN_SLASHING_PENALTY_QUOTIENT_BELLATRIX,
+        )?;
+
+        // Apply proposer and whistleblower rewards
+        let proposer_index = self.get_beacon_proposer_index()?;
+        let whistleblower_index = whistleblower_index.unwrap_or(proposer_index);
+        let whistleblower_reward = effective_balance / WHISTLEBLOWER_REWARD_QUOTIENT;
+        let proposer_reward = whistleblower_reward * PROPOSER_WEIGHT / WEIGHT_DENOMINATOR;
+        self.increase_balance(proposer_index, proposer_reward)?;
+        self.increase_balance(whistleblower_index, whistleblower_reward - proposer_reward)?;
+
+        Ok(())
+    }
+
+    pub fn compute_timestamp_at_slot(&self, slot: u64) -> u64 {
+        self.genesis_time + (slot - GENESIS_SLOT) * beacon_network_spec().seconds_per_slot
+    }
+
+    /// Apply ``block`` to the state, which must already be advanced to the slot of the block.
+    ///
+    /// The execution engine only serves Electra payloads, so the payload is checked against the
+    /// state but its execution is left to the caller.
+    pub fn process_block(&mut self, block: &BeaconBlock) -> anyhow::Result<()> {
+        self.process_block_header(block)?;
+        self.process_withdrawals(&block.body.execution_payload)?;
+        self.process_execution_payload(&block.body)?;
+        self.process_randao(&block.body)?;
+        self.process_eth1_data(&block.body)?;
+        self.process_operations(&block.body)?;
+        self.process_sync_aggregate(&block.body.sync_aggregate)?;
+
+        Ok(())
+    }
+
+    pub fn process_block_header(&mut self, block: &BeaconBlock) -> anyhow::Result<()> {
+        // Verify that the slots match
+        ensure!(
+            self.slot == block.slot,
+            "State slot must be equal to block slot"
+        );
+        // Verify that the block is newer than latest block header
+        ensure!(
+            block.slot > self.latest_block_header.slot,
+            "Block slot must be greater than latest block header slot of state"
+        );
+        // Verify that proposer index is the correct index
+        ensure!(
+            block.proposer_index == self.get_beacon_proposer_index()?,
+            "Block proposer index must be equal to beacon proposer index"
+        );
+        // Verify that the parent matches
+        ensure!(
+            block.parent_root == self.latest_block_header.tree_hash_root(),
+            "Block Parent Root must be equal root of latest block header"
+        );
+
+        // Cache current block as the new latest block
+        self.latest_block_header = BeaconBlockHeader {
+            slot: block.slot,
+            proposer_index: block.proposer_index,
+            parent_root: block.parent_root,
+            state_root: B256::default(), // Overwritten in the next process_slot call
+            body_root: block.body.tree_hash_root(),
+        };
+
+        // Verify proposer is not slashed
+        let proposer = &self.validators[block.proposer_index as usize];
+        ensure!(!proposer.slashed, "Block proposer must not be slashed");
+
+        Ok(())
+    }
+
+    pub fn process_execution_payload(&mut self, body: &BeaconBlockBody) -> anyhow::Result<()> {
+        let payload = &body.execution_payload;
+
+        // Verify consistency of the parent hash with respect to the previous execution payload
+        // header
+        ensure!(
+            payload.parent_hash == self.latest_execution_payload_header.block_hash,
+            "Execution payload parent hash must be the latest execution block hash"
+        );
+        // Verify prev_randao
+        ensure!(
+            payload.prev_randao == self.get_randao_mix(self.get_current_epoch()),
+            "Execution payload prev_randao must be the current randao mix"
+        );
+        // Verify timestamp
+        ensure!(
+            payload.timestamp == self.compute_timestamp_at_slot(self.slot),
+            "Execution payload timestamp must be the timestamp of the slot"
+        );
+        // Verify commitments are under limit
+        ensure!(
+            body.blob_kzg_commitments.len() as u64 <= MAX_BLOBS_PER_BLOCK_DENEB,
+            "Block must have at most {MAX_BLOBS_PER_BLOCK_DENEB} blob commitments"
+        );
+
+        // Cache execution payload header
+        self.latest_execution_payload_header = payload.to_execution_payload_header();
+
+        Ok(())
+    }
+
+    pub fn process_randao(&mut self, body: &BeaconBlockBody) -> anyhow::Result<()> {
+        let epoch = self.get_current_epoch();
+
+        // Verify RANDAO reveal
+        let proposer = self
+            .validators
+            .get(self.get_beacon_proposer_index()? as usize)
+            .ok_or_else(|| anyhow!("Invalid block proposer index"))?;
+        let signing_root = compute_signing_root(epoch, self.get_domain(DOMAIN_RANDAO, Some(epoch)));
+        ensure!(
+            body.randao_reveal
+                .verify(&proposer.public_key, signing_root.as_ref())?,
+            "BLS Signature verification failed!"
+        );
+
+        // Mix in RANDAO reveal
+        let mix = xor(
+            self.get_randao_mix(epoch).as_slice(),
+            hash(body.randao_reveal.to_slice()).as_slice(),
+        );
+        self.randao_mixes[(epoch % EPOCHS_PER_HISTORICAL_VECTOR) as usize] = mix;
+
+        Ok(())
+    }
+
+    pub fn process_eth1_data(&mut self, body: &BeaconBlockBody) -> anyhow::Result<()> {
+        self.eth1_data_votes
+            .push(body.eth1_data.clone())
+            .map_err(|err| anyhow!("Can't push eth1_data {err:?}"))?;
+
+        let count = self
+            .eth1_data_votes
+            .iter()
+            .filter(|data| **data == body.eth1_data)
+            .count() as u64;
+
+        if count * 2 > EPOCHS_PER_ETH1_VOTING_PERIOD * SLOTS_PER_EPOCH {
+            self.eth1_data = body.eth1_data.clone();
+        }
+
+        Ok(())
+    }
+
+    pub fn process_operations(&mut self, body: &BeaconBlockBody) -> anyhow::Result<()> {
+        // Verify that outstanding deposits are processed up to the maximum number of deposits
+        ensure!(
+            body.deposits.len() as u64
+                == min(
+                    MAX_DEPOSITS,
+                    self.eth1_data
+                        .deposit_count
+                        .saturating_sub(self.eth1_deposit_index)
+                ),
+            "Block must include the outstanding deposits up to {MAX_DEPOSITS}"
+        );
+
+        for proposer_slashing in body.proposer_slashings.iter() {
+            self.process_proposer_slashing(proposer_slashing)?;
+        }
+        for attester_slashing in body.attester_slashings.iter() {
+            self.process_attester_slashing(attester_slashing)?;
+        }
+        for attestation in body.attestations.iter() {
+            self.process_attestation(attestation)?;
+        }
+        for deposit in body.deposits.iter() {
+            self.process_deposit(deposit)?;
+        }
+        for voluntary_exit in body.voluntary_exits.iter() {
+            self.process_voluntary_exit(voluntary_exit)?;
+        }
+        for bls_to_execution_change in body.bls_to_execution_changes.iter() {
+            self.process_bls_to_execution_change(bls_to_execution_change)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn process_proposer_slashing(
+        &mut self,
+        proposer_slashing: &ProposerSlashing,
+    ) -> anyhow::Result<()> {
+        let proposer_index = helpers::validate_proposer_slashing(
+            proposer_slashing,
+            self.get_current_epoch(),
+            &self.fork,
+            self.genesis_validators_root,
+            |index| self.validators.get(index),
+        )?;
+
+        self.slash_validator(proposer_index, None)
+    }
+
+    pub fn process_attester_slashing(
+        &mut self,
+        attester_slashing: &AttesterSlashing,
+    ) -> anyhow::Result<()> {
+        let attestation_1 = &attester_slashing.attestation_1;
+        let attestation_2 = &attester_slashing.attestation_2;
+        ensure!(
+            is_slashable_attestation_data(&attestation_1.data, &attestation_2.data),
+            "Attestations are not slashable"
+        );
+        for attestation in [attestation_1, attestation_2] {
+            let indexed_attestation = IndexedAttestation {
+                attesting_indices: attestation.attesting_indices.to_vec().into(),
+                data: attestation.data.clone(),
+                signature: attestation.signature.clone(),
+            };
+            ensure!(
+                self.is_valid_indexed_attestation(&indexed_attestation)?,
+                "Attestation of the attester slashing is invalid"
+            );
+        }
+
+        let indices_1 = attestation_1
+            .attesting_indices
+            .iter()
+            .copied()
+            .collect::<BTreeSet<_>>();
+        let indices_2 = attestation_2
+            .attesting_indices
+            .iter()
+            .copied()
+            .collect::<BTreeSet<_>>();
+        let current_epoch = self.get_current_epoch();
+        let mut slashed_any = false;
+        for &index in indices_1.intersection(&indices_2) {
+            if self.validators[index as usize].is_slashable_validator(current_epoch) {
+                self.slash_validator(index, None)?;
+                slashed_any = true;
+            }
+        }
+        ensure!(slashed_any, "No validator was slashed");
+
+        Ok(())
+    }
+
+    pub fn add_validator_to_registry(
+        &mut self,
+        public_key: PublicKey,
+        withdrawal_credentials: B256,
+        amount: u64,
+    ) -> anyhow::Result<()> {
+        let validator = Validator {
+            public_key,
+            withdrawal_credentials,
+            effective_balance: min(
+                amount - amount % EFFECTIVE_BALANCE_INCREMENT,
+                MAX_EFFECTIVE_BALANCE,
+            ),
+            slashed: false,
+            activation_eligibility_epoch: FAR_FUTURE_EPOCH,
+            activation_epoch: FAR_FUTURE_EPOCH,
+            exit_epoch: FAR_FUTURE_EPOCH,
+            withdrawable_epoch: FAR_FUTURE_EPOCH,
+        };
+        self.validators
+            .push(validator)
+            .map_err(|err| anyhow!("Couldn't push to validators {err:?}"))?;
+        self.balances
+            .push(amount)
+            .map_err(|err| anyhow!("Couldn't push to balances {err:?}"))?;
+        self.previous_epoch_participation
+            .push(0)
+            .map_err(|err| anyhow!("Couldn't push to previous_epoch_participation {err:?}"))?;
+        self.current_epoch_participation
+            .push(0)
+            .map_err(|err| anyhow!("Couldn't push to current_epoch_participation {err:?}"))?;
+        self.inactivity_scores
+            .push(0)
+            .map_err(|err| anyhow!("Couldn't push to inactivity_scores {err:?}"))?;
+
+        Ok(())
+    }
+
+    /// Credit ``amount`` to the validator with ``public_key``, or add the validator to the
+    /// registry if the deposit signature is valid.
+    pub fn apply_deposit(
+        &mut self,
+        public_key: PublicKey,
+        withdrawal_credentials: B256,
+        amount: u64,
+        signature: BLSSignature,
+    ) -> anyhow::Result<()> {
+        if let Some(index) = self
+            .validators
+            .iter()
+            .position(|validator| validator.public_key == public_key)
+        {
+            return self.increase_balance(index as u64, amount);
+        }
+
+        // Verify the deposit signature (proof of possession) which is not checked by the deposit
+        // contract
+        if let Ok(true) =
+            is_valid_deposit_signature(&public_key, withdrawal_credentials, amount, &signature)
+        {
+            self.add_validator_to_registry(public_key, withdrawal_credentials, amount)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn process_deposit(&mut self, deposit: &Deposit) -> anyhow::Result<()> {
+        // Verify the Merkle branch
+        ensure!(
+            is_valid_merkle_branch(
+                deposit.data.tree_hash_root(),
+                &deposit.proof,
+                // Add 1 for the List length mix-in
+                DEPOSIT_CONTRACT_TREE_DEPTH + 1,
+                self.eth1_deposit_index,
+                self.eth1_data.deposit_root,
+            ),
+            "Deposit Merkle branch must be valid"
+        );
+
+        // Deposits must be processed in order
+        self.eth1_deposit_index += 1;
+
+        self.apply_deposit(
+            deposit.data.public_key.clone(),
+            deposit.data.withdrawal_credentials,
+            deposit.data.amount,
+            deposit.data.signature.clone(),
+        )
+    }
+
+    pub fn process_voluntary_exit(
+        &mut self,
+        signed_voluntary_exit: &SignedVoluntaryExit,
+    ) -> anyhow::Result<()> {
+        let voluntary_exit = &signed_voluntary_exit.message;
+        let validator = self
+            .validators
+            .get(voluntary_exit.validator_index as usize)
+            .ok_or_else(|| anyhow!("Invalid validator index"))?;
+        let current_epoch = self.get_current_epoch();
+
+        // Verify the validator is active
+        ensure!(
+            validator.is_active_validator(current_epoch),
+            "Validator is not active"
+        );
+        // Verify exit has not been initiated
+        ensure!(
+            validator.exit_epoch == FAR_FUTURE_EPOCH,
+            "Exit has already been initiated"
+        );
+        // Exits must specify an epoch when they become valid; they are not valid before then
+        ensure!(
+            current_epoch >= voluntary_exit.epoch,
+            "Exit is not yet valid"
+        );
+        // Verify the validator has been active long enough
+        ensure!(
+            current_epoch
+                >= validator
+                    .activation_epoch
+                    .saturating_add(beacon_network_spec().shard_committee_period),
+            "Validator has not been active long enough"
+        );
+
+        // Verify signature, under the Capella domain which exits keep since EIP-7044
+        let domain = compute_domain(
+            DOMAIN_VOLUNTARY_EXIT,
+            Some(CAPELLA_FORK_VERSION),
+            Some(self.genesis_validators_root),
+        );
+        let signing_root = compute_signing_root(voluntary_exit, domain);
+        ensure!(
+            signed_voluntary_exit
+                .signature
+                .verify(&validator.public_key, signing_root.as_ref())?,
+            "BLS Signature verification failed!"
+        );
+
+        // Initiate exit
+        self.initiate_validator_exit(voluntary_exit.validator_index)
+    }
+
+    pub fn process_bls_to_execution_change(
+        &mut self,
+        signed_bls_to_execution_change: &SignedBLSToExecutionChange,
+    ) -> anyhow::Result<()> {
+        let bls_to_execution_change = &signed_bls_to_execution_change.message;
+        let validator = self
+            .validators
+            .get_mut(bls_to_execution_change.validator_index as usize)
+            .ok_or_else(|| anyhow!("Invalid validator index"))?;
+        helpers::validate_bls_to_execution_change(
+            validator,
+            signed_bls_to_execution_change,
+            self.genesis_validators_root,
+        )?;
+
+        validator.withdrawal_credentials =
+            get_eth1_withdrawal_credentials(bls_to_execution_change.to_execution_address);
+
+        Ok(())
+    }
+
+    pub fn process_sync_aggregate(&mut self, sync_aggregate: &SyncAggregate) -> anyhow::Result<()> {
+        // Verify sync committee aggregate signature signing over the previous slot block root
+        let participant_public_keys = self
+            .current_sync_committee
+            .public_keys
+            .iter()
+            .zip(sync_aggregate.sync_committee_bits.iter())
+            .filter_map(|(public_key, bit)| bit.then_some(public_key))
+            .collect::<Vec<_>>();
+        let previous_slot = max(self.slot, 1) - 1;
+        let domain = self.get_domain(
+            DOMAIN_SYNC_COMMITTEE,
+            Some(compute_epoch_at_slot(previous_slot)),
+        );
+        let signing_root =
+            compute_signing_root(self.get_block_root_at_slot(previous_slot)?, domain);
+        ensure!(
+            eth_fast_aggregate_verify(
+                &participant_public_keys,
+                signing_root,
+                &sync_aggregate.sync_committee_signature,
+            )?,
+            "Sync aggregate signature verification failed."
+        );
+
+        // Compute participant and proposer rewards
+        let (participant_reward, proposer_reward) =
+            helpers::get_proposer_and_participant_rewards(self.get_total_active_balance());
+
+        // Apply participant and proposer rewards
+        let validator_indices = self
+            .validators
+            .iter()
+            .enumerate()
+            .map(|(index, validator)| (&validator.public_key, index as u64))
+            .collect::<HashMap<_, _>>();
+        let committee_indices = self
+            .current_sync_committee
+            .public_keys
+            .iter()
+            .map(|public_key| {
+                validator_indices
+                    .get(public_key)
+                    .copied()
+                    .ok_or_else(|| anyhow!("Pubkey not found in validator set."))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let proposer_index = self.get_beacon_proposer_index()?;
+        for (participant_index, participation_bit) in committee_indices
+            .into_iter()
+            .zip(sync_aggregate.sync_committee_bits.iter())
+        {
+            if participation_bit {
+                self.increase_balance(participant_index, participant_reward)?;
+                self.increase_balance(proposer_index, proposer_reward)?;
+            } else {
+                self.decrease_balance(participant_index, participant_reward)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Upgrade the state to the Electra fork at the Electra fork epoch.
+    ///
+    /// Validators which are not yet active have their balance queued as a pending deposit, as do
+    /// the excess balances of validators which already have compounding withdrawal credentials.
+    pub fn upgrade_to_electra(self) -> anyhow::Result<ElectraBeaconState> {
+        let epoch = self.get_current_epoch();
+        let earliest_exit_epoch = self
+            .validators
+            .iter()
+            .map(|validator| validator.exit_epoch)
+            .filter(|&exit_epoch| exit_epoch != FAR_FUTURE_EPOCH)
+            .fold(compute_activation_exit_epoch(epoch), max)
+            + 1;
+
+        let mut post = ElectraBeaconState {
+            genesis_time: self.genesis_time,
+            genesis_validators_root: self.genesis_validators_root,
+            slot: self.slot,
+            fork: Fork {
+                previous_version: self.fork.current_version,
+                current_version: beacon_network_spec().electra_fork_version,
+                epoch,
+            },
+            latest_block_header: self.latest_block_header,
+            block_roots: self.block_roots,
+            state_roots: self.state_roots,
+            historical_roots: self.historical_roots,
+            eth1_data: self.eth1_data,
+            eth1_data_votes: self.eth1_data_votes.into(),
+            eth1_deposit_index: self.eth1_deposit_index,
+            validators: self.validators.into(),
+            balances: self.balances.into(),
+            randao_mixes: self.randao_mixes,
+            slashings: self.slashings,
+            previous_epoch_participation: self.previous_epoch_participation.into(),
+            current_epoch_participation: self.current_epoch_participation.into(),
+            justification_bits: self.justification_bits,
+            previous_justified_checkpoint: self.previous_justified_checkpoint,
+            current_justified_checkpoint: self.current_justified_checkpoint,
+            finalized_checkpoint: self.finalized_checkpoint,
+            inactivity_scores: self.inactivity_scores.into(),
+            current_sync_committee: self.current_sync_committee,
+            next_sync_committee: self.next_sync_committee,
+            latest_execution_payload_header: self.latest_execution_payload_header,
+            next_withdrawal_index: self.next_withdrawal_index,
+            next_withdrawal_validator_index: self.next_withdrawal_validator_index,
+            historical_summaries: self.historical_summaries.into(),
+            deposit_requests_start_index: UNSET_DEPOSIT_REQUESTS_START_INDEX,
+            deposit_balance_to_consume: 0,
+            exit_balance_to_consume: 0,
+            earliest_exit_epoch,
+            consolidation_balance_to_consume: 0,
+            earliest_consolidation_epoch: compute_activation_exit_epoch(epoch),
+            pending_deposits: Default::default(),
+            pending_partial_withdrawals: Default::default(),
+            pending_consolidations: Default::default(),
+        };
+        post.exit_balance_to_consume = post.get_activation_exit_churn_limit();
+        post.consolidation_balance_to_consume = post.get_consolidation_churn_limit();
+
+        // Add validators that are not yet active to pending deposits
+        let pre_activation_indices = post
+            .validators
+            .iter()
+            .enumerate()
+            .filter(|(_, validator)| validator.activation_epoch == FAR_FUTURE_EPOCH)
+            .sorted_by_key(|(index, validator)| (validator.activation_eligibility_epoch, *index))
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        for index in pre_activation_indices {
+            let balance = post
+                .balances
+                .get(index)
+                .copied()
+                .ok_or_else(|| anyhow!("Balance index out of bounds"))?;
+            post.set_balance(index as u64, 0)?;
+            let validator = post
+                .validators
+                .get_mut(index)
+                .ok_or_else(|| anyhow!("Validator index out of bounds"))?;
+            validator.effective_balance = 0;
+            validator.activation_eligibility_epoch = FAR_FUTURE_EPOCH;
+
+            // Use bls.G2_POINT_AT_INFINITY as a signature field placeholder
+            // and GENESIS_SLOT to distinguish from a pending deposit request
+            let pending_deposit = PendingDeposit {
+                public_key: validator.public_key.clone(),
+                withdrawal_credentials: validator.withdrawal_credentials,
+                amount: balance,
+                signature: BLSSignature::infinity(),
+                slot: GENESIS_SLOT,
+            };
+            post.pending_deposits
+                .push(pending_deposit)
+                .map_err(|err| anyhow!("Failed to push pending deposit: {err:?}"))?;
+        }
+
+        // Ensure early adopters of compounding credentials go through the activation churn
+        for index in 0..post.validators.len() {
+            if post.validators[index].has_compounding_withdrawal_credential() {
+                post.queue_excess_active_balance(index as u64)?;
+            }
+        }
+
+        Ok(post)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ream_bls::traits::Signable;
+    use ream_consensus_misc::constants::{
+        BYTES_PER_COMMITMENT, COMPOUNDING_WITHDRAWAL_PREFIX, MIN_ACTIVATION_BALANCE,
+        SYNC_COMMITTEE_SIZE,
+    };
+    use ream_network_spec::networks::initialize_test_network_spec;
+    use ssz_types::BitList;
+
+    use super::*;
+    use crate::{
+        polynomial_commitments::kzg_commitment::KZGCommitment,
+        test_utils::{self, VALIDATOR_BALANCE, generator_private_key, to_deneb_state},
+    };
+
+    /// Returns a Deneb state at genesis whose only validator has the secret key 1.
+    fn single_validator_state() -> anyhow::Result<BeaconState> {
+        Ok(to_deneb_state(test_utils::single_validator_state()?))
+    }
+
+    /// Returns the Deneb state of [`single_validator_state`] at slot 1, whose only validator
+    /// holds every seat of the current sync committee.
+    fn state_at_first_slot() -> anyhow::Result<BeaconState> {
+        let mut state = single_validator_state()?;
+        let public_key = state.validators[0].public_key.clone();
+        state.current_sync_committee = Arc::new(SyncCommittee {
+            public_keys: vec![public_key.clone(); SYNC_COMMITTEE_SIZE as usize].into(),
+            aggregate_public_key: public_key,
+        });
+        state.slot = 1;
+        Ok(state)
+    }
+
+    /// Returns the block of the proposer of the slot of `state` without operations, whose
+    /// execution payload matches the state.
+    fn empty_block(state: &BeaconState) -> anyhow::Result<BeaconBlock> {
+        let epoch = state.get_current_epoch();
+        let randao_signing_root =
+            compute_signing_root(epoch, state.get_domain(DOMAIN_RANDAO, Some(epoch)));
+        Ok(BeaconBlock {
+            slot: state.slot,
+            proposer_index: state.get_beacon_proposer_index()?,
+            parent_root: state.latest_block_header.tree_hash_root(),
+            state_root: B256::ZERO,
+            body: BeaconBlockBody {
+                randao_reveal: generator_private_key().sign(randao_signing_root.as_ref())?,
+                eth1_data: state.eth1_data.clone(),
+                graffiti: B256::ZERO,
+                proposer_slashings: Default::default(),
+                attester_slashings: Default::default(),
+                attestations: Default::default(),
+                deposits: Default::default(),
+                voluntary_exits: Default::default(),
+                sync_aggregate: SyncAggregate {
+                    sync_committee_bits: Default::default(),
+                    sync_committee_signature: BLSSignature::infinity(),
+                },
+                execution_payload: ExecutionPayload {
+                    parent_hash: state.latest_execution_payload_header.block_hash,
+                    prev_randao: state.get_randao_mix(epoch),
+                    timestamp: state.compute_timestamp_at_slot(state.slot),
+                    block_hash: B256::repeat_byte(1),
+                    ..Default::default()
+                },
+                bls_to_execution_changes: Default::default(),
+                blob_kzg_commitments: Default::default(),
+            },
+        })
+    }
+
+    #[test]
+    fn test_process_block_applies_a_block_without_operations() -> anyhow::Result<()> {
+        initialize_test_network_spec();
+        let mut state = state_at_first_slot()?;
+        let block = empty_block(&state)?;
+
+        state.process_block(&block)?;
+        assert_eq!(state.latest_block_header, block.block_header());
+        assert_eq!(
+            state.latest_execution_payload_header,
+            block.body.execution_payload.to_execution_payload_header()
+        );
+        assert_eq!(
+            state.eth1_data_votes.to_vec(),
+            vec![block.body.eth1_data.clone()]
+        );
+        assert_eq!(
+            state.get_randao_mix(0),
+            xor(
+                B256::ZERO.as_slice(),
+                hash(block.body.randao_reveal.to_slice()).as_slice()
+            )
+        );
+        // The only validator missed every seat of the sync committee
+        let (participant_reward, _) =
+            helpers::get_proposer_and_participant_rewards(VALIDATOR_BALANCE);
+        assert!(participant_reward > 0);
+        assert_eq!(
+            state.balances[0],
+            VALIDATOR_BALANCE - participant_reward * SYNC_COMMITTEE_SIZE
+        );
+
+        // The block cannot be applied twice
+        assert!(state.process_block(&block).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_execution_payload_limits_blob_commitments() -> anyhow::Result<()> {
+        initialize_test_network_spec();
+        let state = state_at_first_slot()?;
+        let mut body = empty_block(&state)?.body;
+        let commitment = KZGCommitment([0; BYTES_PER_COMMITMENT]);
+
+        body.blob_kzg_commitments = vec![commitment; MAX_BLOBS_PER_BLOCK_DENEB as usize].into();
+        state.clone().process_execution_payload(&body)?;
+
+        body.blob_kzg_commitments = vec![commitment; MAX_BLOBS_PER_BLOCK_DENEB as usize + 1].into();
+        let err = state
+            .clone()
+            .process_execution_payload(&body)
+            .expect_err("A block with too many blob commitments must be rejected");
+        assert_eq!(
+            err.to_string(),
+            format!("Block must have at most {MAX_BLOBS_PER_BLOCK_DENEB} blob commitments")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_slash_validator_applies_the_bellatrix_penalty() -> anyhow::Result<()> {
+        initialize_test_network_spec();
+        let mut state = single_validator_state()?;
+
+        // The only validator is also the proposer and the whistleblower
+        state.slash_validator(0, None)?;
+        let validator = &state.validators[0];
+        assert!(validator.slashed);
+        assert_eq!(validator.exit_epoch, compute_activation_exit_epoch(0));
+        assert_eq!(
+            validator.withdrawable_epoch,
+            max(
+                validator.exit_epoch + beacon_network_spec().min_validator_withdrawability_delay,
+                EPOCHS_PER_SLASHINGS_VECTOR
+            )
+        );
+        assert_eq!(state.slashings[0], VALIDATOR_BALANCE);
+        assert_eq!(
+            state.balances[0],
+            VALIDATOR_BALANCE - VALIDATOR_BALANCE / MIN_SLASHING_PENALTY_QUOTIENT_BELLATRIX
+                + VALIDATOR_BALANCE / WHISTLEBLOWER_REWARD_QUOTIENT
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_attestation_sets_flags_and_rewards_proposer() -> anyhow::Result<()> {
+        let mut state = single_validator_state()?;
+        let slot = (0..SLOTS_PER_EPOCH)
+            .find(|&slot| {
+                state
+                    .get_beacon_committee(slot, 0)
+                    .is_ok_and(|committee| !committee.is_empty())
+            })
+            .ok_or_else(|| anyhow!("The validator has no attestation duty in the first epoch"))?;
+        let data = AttestationData {
+            slot,
+            index: 0,
+            beacon_block_root: B256::ZERO,
+            source: Checkpoint::default(),
+            target: Checkpoint::default(),
+        };
+        let signing_root = compute_signing_root(
+            data.clone(),
+            state.get_domain(DOMAIN_BEACON_ATTESTER, Some(0)),
+        );
+        let mut aggregation_bits = BitList::with_capacity(1)
+            .map_err(|err| anyhow!("Failed to create aggregation bits: {err:?}"))?;
+        aggregation_bits
+            .set(0, true)
+            .map_err(|err| anyhow!("Failed to set aggregation bit: {err:?}"))?;
+        let attestation = Attestation {
+            aggregation_bits,
+            data,
+            signature: generator_private_key().sign(signing_root.as_ref())?,
+        };
+        state.slot = slot + MIN_ATTESTATION_INCLUSION_DELAY;
+
+        state.process_attestation(&attestation)?;
+        let epoch_participation = if state.get_current_epoch() == 0 {
+            &state.current_epoch_participation
+        } else {
+            &state.previous_epoch_participation
+        };
+        assert_eq!(epoch_participation[0], 0b111);
+        let base_reward = compute_base_reward(
+            VALIDATOR_BALANCE,
+            compute_base_reward_per_increment(VALIDATOR_BALANCE),
+        );
+        let proposer_reward =
+            compute_proposer_reward(base_reward * PARTICIPATION_FLAG_WEIGHTS.iter().sum::<u64>());
+        assert!(proposer_reward > 0);
+        assert_eq!(state.balances[0], VALIDATOR_BALANCE + proposer_reward);
+
+        // Flags which are already set are not rewarded again
+        state.process_attestation(&attestation)?;
+        assert_eq!(state.balances[0], VALIDATOR_BALANCE + proposer_reward);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_balance_mutations_are_checked() -> anyhow::Result<()> {
+        let mut state = single_validator_state()?;
+
+        state.set_balance(0, u64::MAX - 1)?;
+        state.increase_balance(0, 1)?;
+        assert_eq!(state.balances[0], u64::MAX);
+        let err = state
+            .increase_balance(0, 1)
+            .expect_err("Increasing the balance past u64::MAX must fail");
+        assert_eq!(err.to_string(), "Balance of validator 0 overflows");
+        assert_eq!(state.balances[0], u64::MAX);
+
+        state.set_balance(0, VALIDATOR_BALANCE)?;
+        state.decrease_balance(0, VALIDATOR_BALANCE + 1)?;
+        assert_eq!(state.balances[0], 0);
+
+        assert!(state.increase_balance(1, 1).is_err());
+        assert!(state.decrease_balance(1, 1).is_err());
+        assert!(state.set_balance(1, 1).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_upgrade_to_electra_queues_pending_deposits_and_sets_churn() -> anyhow::Result<()> {
+        initialize_test_network_spec();
+        let mut state = single_validator_state()?;
+        let active_validator = state.validators[0].clone();
+        let pending_validator = |activation_eligibility_epoch| Validator {
+            activation_eligibility_epoch,
+            activation_epoch: FAR_FUTURE_EPOCH,
+            ..active_validator.clone()
+        };
+        let mut compounding_withdrawal_credentials = B256::ZERO;
+        compounding_withdrawal_credentials[0] = COMPOUNDING_WITHDRAWAL_PREFIX[0];
+        for (validator, balance) in [
+            (pending_validator(2), VALIDATOR_BALANCE),
+            (pending_validator(1), VALIDATOR_BALANCE / 2),
+            (
+                Validator {
+                    exit_epoch: 10,
+                    ..active_validator.clone()
+                },
+                VALIDATOR_BALANCE,
+            ),
+            (
+                Validator {
+                    withdrawal_credentials: compounding_withdrawal_credentials,
+                    ..active_validator.clone()
+                },
+                VALIDATOR_BALANCE + 8_000_000_000,
+            ),
+        ] {
+            state
+                .validators
+                .push(validator)
+                .map_err(|err| anyhow!("Failed to push validator: {err:?}"))?;
+            state
+                .balances
+                .push(balance)
+                .map_err(|err| anyhow!("Failed to push balance: {err:?}"))?;
+        }
+        let deneb_fork_version = state.fork.current_version;
+
+        let post = state.upgrade_to_electra()?;
+        assert_eq!(post.fork.previous_version, deneb_fork_version);
+        assert_eq!(
+            post.fork.current_version,
+            beacon_network_spec().electra_fork_version
+        );
+        assert_eq!(
+            post.deposit_requests_start_index,
+            UNSET_DEPOSIT_REQUESTS_START_INDEX
+        );
+        assert_eq!(post.deposit_balance_to_consume, 0);
+        // The exit epoch of validator 3 is later than the activation exit epoch of epoch 0
+        assert_eq!(post.earliest_exit_epoch, 11);
+        assert_eq!(
+            post.earliest_consolidation_epoch,
+            compute_activation_exit_epoch(0)
+        );
+        assert_eq!(
+            post.exit_balance_to_consume,
+            post.get_activation_exit_churn_limit()
+        );
+        assert_eq!(
+            post.consolidation_balance_to_consume,
+            post.get_consolidation_churn_limit()
+        );
+
+        // Validators which are not yet active are queued by eligibility epoch, then the excess
+        // balance of the compounding validator
+        assert_eq!(
+            post.pending_deposits
+                .iter()
+                .map(|deposit| (deposit.amount, deposit.slot))
+                .collect::<Vec<_>>(),
+            vec![
+                (VALIDATOR_BALANCE / 2, GENESIS_SLOT),
+                (VALIDATOR_BALANCE, GENESIS_SLOT),
+                (8_000_000_000, GENESIS_SLOT),
+            ]
+        );
+        assert!(
+            post.pending_deposits
+                .iter()
+                .all(|deposit| deposit.signature == BLSSignature::infinity())
+        );
+        for index in [1, 2] {
+            assert_eq!(post.balances[index], 0);
+            assert_eq!(post.validators[index].effective_balance, 0);
+            assert_eq!(
+                post.validators[index].activation_eligibility_epoch,
+                FAR_FUTURE_EPOCH
+            );
+        }
+        assert_eq!(post.balances[4], MIN_ACTIVATION_BALANCE);
+
+        Ok(())
+    }
+}
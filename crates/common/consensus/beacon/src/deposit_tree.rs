@@ -0,0 +1,328 @@
+use std::sync::LazyLock;
+
+use alloy_primitives::B256;
+use anyhow::{bail, ensure};
+use ream_consensus_misc::{
+    constants::DEPOSIT_CONTRACT_TREE_DEPTH, eth_1_data::Eth1Data, hashing::hash32_concat,
+};
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{VariableList, typenum::U32};
+use tree_hash_derive::TreeHash;
+
+/// The roots of the empty subtrees of each depth of the deposit tree.
+static ZERO_HASHES: LazyLock<Vec<B256>> = LazyLock::new(|| {
+    let mut zero_hashes = vec![B256::ZERO];
+    for depth in 0..DEPOSIT_CONTRACT_TREE_DEPTH as usize {
+        zero_hashes.push(B256::from(hash32_concat(
+            zero_hashes[depth].as_slice(),
+            zero_hashes[depth].as_slice(),
+        )));
+    }
+    zero_hashes
+});
+
+/// The finalized part of the deposit tree, as specified by EIP-4881, from which the tree can be
+/// restored without replaying the finalized deposits.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct DepositTreeSnapshot {
+    /// The roots of the finalized subtrees, from the leftmost and largest one.
+    pub finalized: VariableList<B256, U32>,
+    pub deposit_root: B256,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub deposit_count: u64,
+    pub execution_block_hash: B256,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub execution_block_height: u64,
+}
+
+impl DepositTreeSnapshot {
+    /// Return the deposit root of the ``deposit_count`` deposits summarized by ``finalized``.
+    pub fn calculate_root(&self) -> B256 {
+        let mut size = self.deposit_count;
+        let mut index = self.finalized.len();
+        let mut root = ZERO_HASHES[0];
+        for zero_hash in ZERO_HASHES
+            .iter()
+            .take(DEPOSIT_CONTRACT_TREE_DEPTH as usize)
+        {
+            root = B256::from(if size % 2 == 1 && index > 0 {
+                index -= 1;
+                hash32_concat(self.finalized[index].as_slice(), root.as_slice())
+            } else {
+                hash32_concat(root.as_slice(), zero_hash.as_slice())
+            });
+            size /= 2;
+        }
+        mix_in_deposit_count(root, self.deposit_count)
+    }
+}
+
+/// The deposit tree of the deposit contract, which keeps the roots of the subtrees finalized by
+/// [`DepositTree::finalize`] and the leaves of the deposits after them, so that it can prove the
+/// deposits which are not finalized yet for block production.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DepositTree {
+    /// The roots of the finalized subtrees, from the leftmost and largest one.
+    finalized: Vec<B256>,
+    finalized_count: u64,
+    /// The hash and height of the execution block the tree was last finalized at.
+    finalized_execution_block: Option<(B256, u64)>,
+    /// The roots of the deposit data of the deposits after the finalized ones.
+    leaves: Vec<B256>,
+}
+
+impl DepositTree {
+    pub fn from_snapshot(snapshot: &DepositTreeSnapshot) -> anyhow::Result<Self> {
+        ensure!(
+            snapshot.finalized.len() == snapshot.deposit_count.count_ones() as usize,
+            "Snapshot of {} deposits must have {} finalized roots, got {}",
+            snapshot.deposit_count,
+            snapshot.deposit_count.count_ones(),
+            snapshot.finalized.len()
+        );
+        ensure!(
+            snapshot.calculate_root() == snapshot.deposit_root,
+            "Snapshot finalized roots do not match deposit root {}",
+            snapshot.deposit_root
+        );
+
+        Ok(Self {
+            finalized: snapshot.finalized.to_vec(),
+            finalized_count: snapshot.deposit_count,
+            finalized_execution_block: Some((
+                snapshot.execution_block_hash,
+                snapshot.execution_block_height,
+            )),
+            leaves: vec![],
+        })
+    }
+
+    /// Returns the snapshot of the tree as of its last finalization.
+    pub fn snapshot(&self) -> anyhow::Result<DepositTreeSnapshot> {
+        let Some((execution_block_hash, execution_block_height)) = self.finalized_execution_block
+        else {
+            bail!("Deposit tree was never finalized");
+        };
+
+        Ok(DepositTreeSnapshot {
+            finalized: self.finalized.clone().into(),
+            deposit_root: self.root_at(self.finalized_count)?,
+            deposit_count: self.finalized_count,
+            execution_block_hash,
+            execution_block_height,
+        })
+    }
+
+    pub fn deposit_count(&self) -> u64 {
+        self.finalized_count + self.leaves.len() as u64
+    }
+
+    /// Appends the root of the deposit data of the next deposit.
+    pub fn push_leaf(&mut self, leaf: B256) -> anyhow::Result<()> {
+        ensure!(
+            self.deposit_count() < 1 << DEPOSIT_CONTRACT_TREE_DEPTH,
+            "Deposit tree is full"
+        );
+        self.leaves.push(leaf);
+        Ok(())
+    }
+
+    pub fn root(&self) -> anyhow::Result<B256> {
+        self.root_at(self.deposit_count())
+    }
+
+    /// Returns the deposit root of the first `deposit_count` deposits, as an `Eth1Data` of that
+    /// deposit count commits to.
+    pub fn root_at(&self, deposit_count: u64) -> anyhow::Result<B256> {
+        self.ensure_deposit_count(deposit_count)?;
+        Ok(mix_in_deposit_count(
+            self.node(DEPOSIT_CONTRACT_TREE_DEPTH, 0, deposit_count)?,
+            deposit_count,
+        ))
+    }
+
+    /// Returns the proof of the deposit at `index` against the deposit root of the first
+    /// `deposit_count` deposits, including the deposit count mix-in, as `Deposit::proof` expects.
+    pub fn proof(&self, index: u64, deposit_count: u64) -> anyhow::Result<Vec<B256>> {
+        self.ensure_deposit_count(deposit_count)?;
+        ensure!(
+            index >= self.finalized_count,
+            "Deposit {index} is finalized and can no longer be proven"
+        );
+        ensure!(
+            index < deposit_count,
+            "Deposit {index} is not among the first {deposit_count} deposits"
+        );
+
+        let mut proof = (0..DEPOSIT_CONTRACT_TREE_DEPTH)
+            .map(|depth| self.node(depth, ((index >> depth) ^ 1) << depth, deposit_count))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        proof.push(deposit_count_leaf(deposit_count));
+        Ok(proof)
+    }
+
+    /// Finalizes the deposits up to the `eth1_data` of the execution block at
+    /// `execution_block_height`, pruning their leaves.
+    pub fn finalize(
+        &mut self,
+        eth1_data: &Eth1Data,
+        execution_block_height: u64,
+    ) -> anyhow::Result<()> {
+        let deposit_count = eth1_data.deposit_count;
+        ensure!(
+            self.root_at(deposit_count)? == eth1_data.deposit_root,
+            "Deposit root {} does not match the first {deposit_count} deposits",
+            eth1_data.deposit_root
+        );
+
+        let mut finalized = vec![];
+        let mut start = 0;
+        for depth in (0..=DEPOSIT_CONTRACT_TREE_DEPTH).rev() {
+            if deposit_count & (1 << depth) != 0 {
+                finalized.push(self.node(depth, start, deposit_count)?);
+                start += 1 << depth;
+            }
+        }
+        self.leaves
+            .drain(..(deposit_count - self.finalized_count) as usize);
+        self.finalized = finalized;
+        self.finalized_count = deposit_count;
+        self.finalized_execution_block = Some((eth1_data.block_hash, execution_block_height));
+        Ok(())
+    }
+
+    fn ensure_deposit_count(&self, deposit_count: u64) -> anyhow::Result<()> {
+        ensure!(
+            deposit_count >= self.finalized_count && deposit_count <= self.deposit_count(),
+            "Deposit count {deposit_count} must be between the {} finalized deposits and the {} \
+             deposits of the tree",
+            self.finalized_count,
+            self.deposit_count()
+        );
+        Ok(())
+    }
+
+    /// Returns the root of the subtree of `depth` whose leftmost leaf is the deposit at `start`,
+    /// in the tree of the first `deposit_count` deposits.
+    fn node(&self, depth: u64, start: u64, deposit_count: u64) -> anyhow::Result<B256> {
+        if start >= deposit_count {
+            return Ok(ZERO_HASHES[depth as usize]);
+        }
+        if start + (1 << depth) <= self.finalized_count {
+            return self.finalized_node(depth, start);
+        }
+        if depth == 0 {
+            return Ok(self.leaves[(start - self.finalized_count) as usize]);
+        }
+
+        let left = self.node(depth - 1, start, deposit_count)?;
+        let right = self.node(depth - 1, start + (1 << (depth - 1)), deposit_count)?;
+        Ok(B256::from(hash32_concat(left.as_slice(), right.as_slice())))
+    }
+
+    /// Finalized subtrees are only kept as a whole, so a subtree within one was pruned.
+    fn finalized_node(&self, depth: u64, start: u64) -> anyhow::Result<B256> {
+        let mut finalized_start = 0;
+        let mut finalized_roots = self.finalized.iter();
+        for finalized_depth in (0..=DEPOSIT_CONTRACT_TREE_DEPTH).rev() {
+            if self.finalized_count & (1 << finalized_depth) == 0 {
+                continue;
+            }
+            let Some(root) = finalized_roots.next() else {
+                break;
+            };
+            if finalized_depth == depth && finalized_start == start {
+                return Ok(*root);
+            }
+            finalized_start += 1 << finalized_depth;
+        }
+        bail!("Deposit tree node of depth {depth} at deposit {start} was pruned by finalization")
+    }
+}
+
+/// Returns the leaf of the deposit count which the deposit root mixes in.
+fn deposit_count_leaf(deposit_count: u64) -> B256 {
+    let mut leaf = B256::ZERO;
+    leaf[..8].copy_from_slice(&deposit_count.to_le_bytes());
+    leaf
+}
+
+fn mix_in_deposit_count(root: B256, deposit_count: u64) -> B256 {
+    B256::from(hash32_concat(
+        root.as_slice(),
+        deposit_count_leaf(deposit_count).as_slice(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use ream_merkle::is_valid_merkle_branch;
+    use ssz::{Decode, Encode};
+
+    use super::*;
+
+    fn leaf(index: u64) -> B256 {
+        B256::from(hash32_concat(&index.to_le_bytes(), &[]))
+    }
+
+    fn deposit_tree(deposit_count: u64) -> anyhow::Result<DepositTree> {
+        let mut deposit_tree = DepositTree::default();
+        for index in 0..deposit_count {
+            deposit_tree.push_leaf(leaf(index))?;
+        }
+        Ok(deposit_tree)
+    }
+
+    #[test]
+    fn test_proof() -> anyhow::Result<()> {
+        let deposit_tree = deposit_tree(11)?;
+        for deposit_count in [1, 6, 11] {
+            let root = deposit_tree.root_at(deposit_count)?;
+            for index in 0..deposit_count {
+                assert!(is_valid_merkle_branch(
+                    leaf(index),
+                    &deposit_tree.proof(index, deposit_count)?,
+                    DEPOSIT_CONTRACT_TREE_DEPTH + 1,
+                    index,
+                    root,
+                ));
+            }
+        }
+        assert_eq!(deposit_tree.root()?, deposit_tree.root_at(11)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_restore_from_snapshot() -> anyhow::Result<()> {
+        let full_tree = deposit_tree(11)?;
+        let mut deposit_tree = deposit_tree(7)?;
+        deposit_tree.finalize(
+            &Eth1Data {
+                deposit_root: deposit_tree.root_at(6)?,
+                deposit_count: 6,
+                block_hash: B256::repeat_byte(1),
+            },
+            100,
+        )?;
+        assert!(deposit_tree.proof(5, 7).is_err());
+
+        let snapshot = deposit_tree.snapshot()?;
+        assert_eq!(snapshot.calculate_root(), full_tree.root_at(6)?);
+        assert_eq!(
+            DepositTreeSnapshot::from_ssz_bytes(&snapshot.as_ssz_bytes())
+                .map_err(|err| anyhow::anyhow!("{err:?}"))?,
+            snapshot
+        );
+
+        let mut restored_tree = DepositTree::from_snapshot(&snapshot)?;
+        for index in 6..11 {
+            restored_tree.push_leaf(leaf(index))?;
+        }
+        assert_eq!(restored_tree.root()?, full_tree.root()?);
+        for index in 6..11 {
+            assert_eq!(restored_tree.proof(index, 11)?, full_tree.proof(index, 11)?);
+        }
+        Ok(())
+    }
+}
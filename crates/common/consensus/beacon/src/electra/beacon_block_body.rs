@@ -1,4 +1,5 @@
 use alloy_primitives::B256;
+use anyhow::anyhow;
 use ream_bls::BLSSignature;
 use ream_consensus_misc::{
     constants::{
@@ -6,18 +7,21 @@ use ream_consensus_misc::{
         KZG_COMMITMENTS_MERKLE_DEPTH,
     },
     eth_1_data::Eth1Data,
+    preset::MaxBlobCommitmentsPerBlock,
 };
-use ream_merkle::{generate_proof, merkle_tree};
+use ream_merkle::{generate_proof, merkle_tree, multiproof::Multiproof};
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
 use ssz_types::{
     VariableList,
-    typenum::{U1, U8, U16, U4096},
+    typenum::{U1, U8, U16},
 };
 use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
 
-use super::execution_payload::ExecutionPayload;
+use super::{
+    blinded_beacon_block_body::BlindedBeaconBlockBody, execution_payload::ExecutionPayload,
+};
 use crate::{
     attestation::Attestation, attester_slashing::AttesterSlashing,
     bls_to_execution_change::SignedBLSToExecutionChange, deposit::Deposit,
@@ -49,11 +53,31 @@ pub struct BeaconBlockBody {
     // Execution
     pub execution_payload: ExecutionPayload,
     pub bls_to_execution_changes: VariableList<SignedBLSToExecutionChange, U16>,
-    pub blob_kzg_commitments: VariableList<KZGCommitment, U4096>,
+    pub blob_kzg_commitments: VariableList<KZGCommitment, MaxBlobCommitmentsPerBlock>,
     pub execution_requests: ExecutionRequests,
 }
 
 impl BeaconBlockBody {
+    /// Returns the body with its execution payload replaced by the header of the payload. Both
+    /// bodies have the same root.
+    pub fn as_blinded_beacon_block_body(&self) -> BlindedBeaconBlockBody {
+        BlindedBeaconBlockBody {
+            randao_reveal: self.randao_reveal.clone(),
+            eth1_data: self.eth1_data.clone(),
+            graffiti: self.graffiti,
+            proposer_slashings: self.proposer_slashings.clone(),
+            attester_slashings: self.attester_slashings.clone(),
+            attestations: self.attestations.clone(),
+            deposits: self.deposits.clone(),
+            voluntary_exits: self.voluntary_exits.clone(),
+            sync_aggregate: self.sync_aggregate.clone(),
+            execution_payload_header: self.execution_payload.to_execution_payload_header(),
+            bls_to_execution_changes: self.bls_to_execution_changes.clone(),
+            blob_kzg_commitments: self.blob_kzg_commitments.clone(),
+            execution_requests: self.execution_requests.clone(),
+        }
+    }
+
     pub fn merkle_leaves(&self) -> Vec<B256> {
         vec![
             self.randao_reveal.tree_hash_root(),
@@ -113,4 +137,17 @@ impl BeaconBlockBody {
     pub fn execution_payload_inclusion_proof(&self) -> anyhow::Result<Vec<B256>> {
         self.data_inclusion_proof(EXECUTION_PAYLOAD_INDEX)
     }
+
+    /// Return the SSZ multiproof of the nodes at ``generalized_indices`` of the tree of the body
+    /// fields against the body root.
+    pub fn compute_multiproof(&self, generalized_indices: &[u64]) -> anyhow::Result<Multiproof> {
+        let tree = merkle_tree(&self.merkle_leaves(), BLOCK_BODY_MERKLE_DEPTH)?;
+        Multiproof::generate_for_generalized_indices(generalized_indices, |generalized_index| {
+            tree.get(generalized_index as usize)
+                .copied()
+                .ok_or_else(|| {
+                    anyhow!("Generalized index {generalized_index} is outside of the body fields")
+                })
+        })
+    }
 }
@@ -3,12 +3,13 @@ use std::{
     collections::{HashMap, HashSet},
     mem::take,
     ops::Deref,
+    pin::pin,
     sync::Arc,
+    task::{Context, Poll, Waker},
 };
 
 use alloy_primitives::{Address, B256, aliases::B32};
 use anyhow::{anyhow, bail, ensure};
-use ethereum_hashing::{hash, hash_fixed};
 use itertools::Itertools;
 use ream_bls::{
     BLSSignature, PublicKey,
@@ -19,52 +20,53 @@ use ream_consensus_misc::{
     beacon_block_header::{BeaconBlockHeader, SignedBeaconBlockHeader},
     checkpoint::Checkpoint,
     constants::{
-        BASE_REWARD_FACTOR, BEACON_STATE_MERKLE_DEPTH, BLS_WITHDRAWAL_PREFIX, CAPELLA_FORK_VERSION,
-        CHURN_LIMIT_QUOTIENT, COMPOUNDING_WITHDRAWAL_PREFIX, CURRENT_SYNC_COMMITTEE_INDEX,
-        DEPOSIT_CONTRACT_TREE_DEPTH, DOMAIN_BEACON_ATTESTER, DOMAIN_BEACON_PROPOSER,
-        DOMAIN_BLS_TO_EXECUTION_CHANGE, DOMAIN_DEPOSIT, DOMAIN_RANDAO, DOMAIN_SYNC_COMMITTEE,
-        DOMAIN_VOLUNTARY_EXIT, EFFECTIVE_BALANCE_INCREMENT, EJECTION_BALANCE,
+        BASE_REWARD_FACTOR, BEACON_STATE_MERKLE_DEPTH, CAPELLA_FORK_VERSION,
+        COMPOUNDING_WITHDRAWAL_PREFIX, CURRENT_SYNC_COMMITTEE_INDEX, DEPOSIT_CONTRACT_TREE_DEPTH,
+        DOMAIN_BEACON_ATTESTER, DOMAIN_BEACON_PROPOSER, DOMAIN_DEPOSIT, DOMAIN_RANDAO,
+        DOMAIN_SYNC_COMMITTEE, DOMAIN_VOLUNTARY_EXIT, EFFECTIVE_BALANCE_INCREMENT,
         EPOCHS_PER_ETH1_VOTING_PERIOD, EPOCHS_PER_HISTORICAL_VECTOR, EPOCHS_PER_SLASHINGS_VECTOR,
-        EPOCHS_PER_SYNC_COMMITTEE_PERIOD, ETH1_ADDRESS_WITHDRAWAL_PREFIX, FAR_FUTURE_EPOCH,
-        FINALIZED_CHECKPOINT_INDEX, FULL_EXIT_REQUEST_AMOUNT, GENESIS_EPOCH, GENESIS_SLOT,
-        HYSTERESIS_DOWNWARD_MULTIPLIER, HYSTERESIS_QUOTIENT, HYSTERESIS_UPWARD_MULTIPLIER,
-        INACTIVITY_PENALTY_QUOTIENT_BELLATRIX, INACTIVITY_SCORE_BIAS,
-        INACTIVITY_SCORE_RECOVERY_RATE, JUSTIFICATION_BITS_LENGTH, MAX_BLOBS_PER_BLOCK_ELECTRA,
-        MAX_COMMITTEES_PER_SLOT, MAX_DEPOSITS, MAX_EFFECTIVE_BALANCE_ELECTRA,
+        EPOCHS_PER_SYNC_COMMITTEE_PERIOD, FAR_FUTURE_EPOCH, FINALIZED_CHECKPOINT_INDEX,
+        FULL_EXIT_REQUEST_AMOUNT, GENESIS_EPOCH, GENESIS_SLOT, HYSTERESIS_DOWNWARD_MULTIPLIER,
+        HYSTERESIS_QUOTIENT, HYSTERESIS_UPWARD_MULTIPLIER, INACTIVITY_PENALTY_QUOTIENT_BELLATRIX,
+        JUSTIFICATION_BITS_LENGTH, MAX_DEPOSITS, MAX_EFFECTIVE_BALANCE_ELECTRA,
         MAX_PENDING_DEPOSITS_PER_EPOCH, MAX_PENDING_PARTIALS_PER_WITHDRAWALS_SWEEP,
-        MAX_PER_EPOCH_ACTIVATION_CHURN_LIMIT, MAX_PER_EPOCH_ACTIVATION_EXIT_CHURN_LIMIT,
         MAX_RANDOM_VALUE, MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP, MAX_WITHDRAWALS_PER_PAYLOAD,
         MIN_ACTIVATION_BALANCE, MIN_ATTESTATION_INCLUSION_DELAY, MIN_EPOCHS_TO_INACTIVITY_PENALTY,
-        MIN_GENESIS_ACTIVE_VALIDATOR_COUNT, MIN_GENESIS_TIME, MIN_PER_EPOCH_CHURN_LIMIT,
-        MIN_PER_EPOCH_CHURN_LIMIT_ELECTRA, MIN_SEED_LOOKAHEAD,
-        MIN_SLASHING_PENALTY_QUOTIENT_ELECTRA, MIN_VALIDATOR_WITHDRAWABILITY_DELAY,
-        NEXT_SYNC_COMMITTEE_INDEX, PARTICIPATION_FLAG_WEIGHTS, PENDING_CONSOLIDATIONS_LIMIT,
+        MIN_SLASHING_PENALTY_QUOTIENT_ELECTRA, NEXT_SYNC_COMMITTEE_INDEX,
+        PARTICIPATION_FLAG_WEIGHTS, PENDING_CONSOLIDATIONS_LIMIT,
         PENDING_PARTIAL_WITHDRAWALS_LIMIT, PROPORTIONAL_SLASHING_MULTIPLIER_BELLATRIX,
-        PROPOSER_REWARD_QUOTIENT, PROPOSER_WEIGHT, SAFETY_DECAY, SHARD_COMMITTEE_PERIOD,
-        SLOTS_PER_EPOCH, SLOTS_PER_HISTORICAL_ROOT, SYNC_COMMITTEE_SIZE, SYNC_REWARD_WEIGHT,
-        TARGET_COMMITTEE_SIZE, TIMELY_HEAD_FLAG_INDEX, TIMELY_SOURCE_FLAG_INDEX,
+        PROPOSER_REWARD_QUOTIENT, PROPOSER_WEIGHT, SAFETY_DECAY, SLOTS_PER_EPOCH,
+        SLOTS_PER_HISTORICAL_ROOT, SYNC_COMMITTEE_SIZE, TIMELY_HEAD_FLAG_INDEX,
         TIMELY_TARGET_FLAG_INDEX, UINT64_MAX, UINT64_MAX_SQRT, UNSET_DEPOSIT_REQUESTS_START_INDEX,
         WEIGHT_DENOMINATOR, WHISTLEBLOWER_REWARD_QUOTIENT_ELECTRA,
     },
     deposit_message::DepositMessage,
     eth_1_data::Eth1Data,
     fork::Fork,
+    hashing::{hash, hash_fixed, hash32_concat},
     indexed_attestation::IndexedAttestation,
     misc::{
         bytes_to_int64, compute_activation_exit_epoch, compute_committee, compute_domain,
         compute_epoch_at_slot, compute_shuffled_index, compute_signing_root,
-        compute_start_slot_at_epoch, get_committee_indices, is_sorted_and_unique,
+        compute_start_slot_at_epoch, get_committee_indices,
+    },
+    preset::{
+        EpochsPerHistoricalVector, EpochsPerSlashingsVector, PendingConsolidationsLimit,
+        PendingPartialWithdrawalsLimit, SlotsPerEth1VotingPeriod, SlotsPerHistoricalRoot,
     },
     validator::Validator,
 };
-use ream_merkle::{generate_proof, is_valid_merkle_branch, merkle_tree};
+use ream_merkle::{
+    generate_proof, generate_proof_for_generalized_index, get_generalized_index_length,
+    is_valid_merkle_branch, merkle_tree, multiproof::Multiproof,
+};
 use ream_network_spec::networks::beacon_network_spec;
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
 use ssz_types::{
     BitVector, FixedVector, VariableList,
-    serde_utils::{quoted_u64_fixed_vec, quoted_u64_var_list},
-    typenum::{U4, U2048, U8192, U65536, U262144, U16777216, U134217728},
+    serde_utils::quoted_u64_fixed_vec,
+    typenum::{U4, U16777216, U134217728},
 };
 use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
@@ -74,62 +76,44 @@ use super::{
     beacon_block_body::BeaconBlockBody,
     execution_payload::ExecutionPayload,
     execution_payload_header::ExecutionPayloadHeader,
-    zkvm_types::ValidatorRegistryLimit,
+    zkvm_types::{ValidatorRegistryLimit, ValidatorRegistryTreeHashLimit},
 };
 use crate::{
     attestation::Attestation,
     attester_slashing::AttesterSlashing,
+    block_signature_verifier::BlockSignatureVerifier,
     bls_to_execution_change::SignedBLSToExecutionChange,
+    committee_cache::CommitteeCache,
     consolidation_request::ConsolidationRequest,
     deposit::Deposit,
     deposit_request::DepositRequest,
     eth_1_block::Eth1Block,
-    execution_engine::{engine_trait::ExecutionApi, new_payload_request::NewPayloadRequest},
-    helpers::xor,
+    execution_engine::{
+        engine_trait::ExecutionApi, mock_engine::MockExecutionEngine,
+        new_payload_request::NewPayloadRequest,
+    },
+    helpers::{
+        self, add_participation_flags, compute_base_reward, compute_proposer_reward,
+        get_eth1_withdrawal_credentials, xor,
+    },
     historical_summary::HistoricalSummary,
+    participation_cache::ParticipationCache,
     pending_consolidation::PendingConsolidation,
     pending_deposit::PendingDeposit,
     pending_partial_withdrawal::PendingPartialWithdrawal,
+    persistent_list::PersistentList,
     predicates::is_slashable_attestation_data,
     proposer_slashing::ProposerSlashing,
+    pubkey_cache::PubkeyCache,
+    quoted::{quoted_u8_persistent_list, quoted_u64_persistent_list},
     sync_aggregate::SyncAggregate,
     sync_committee::SyncCommittee,
+    tree_hash_cache::BeaconStateTreeHashCache,
     voluntary_exit::SignedVoluntaryExit,
     withdrawal::Withdrawal,
     withdrawal_request::WithdrawalRequest,
 };
 
-pub mod quoted_u8_var_list {
-    use super::*;
-
-    pub fn serialize<S>(
-        value: &VariableList<u8, ValidatorRegistryLimit>,
-        serializer: S,
-    ) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let string_vec: Vec<String> = value.iter().map(|v| v.to_string()).collect();
-        string_vec.serialize(serializer)
-    }
-
-    pub fn deserialize<'de, D>(
-        deserializer: D,
-    ) -> Result<VariableList<u8, ValidatorRegistryLimit>, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let string_vec: Vec<String> = Vec::deserialize(deserializer)?;
-        let bytes = string_vec
-            .into_iter()
-            .map(|s| s.parse::<u8>().map_err(serde::de::Error::custom))
-            .collect::<Result<Vec<_>, _>>()?;
-        VariableList::new(bytes).map_err(|err| {
-            serde::de::Error::custom(format!("Cannot create VariableList from bytes: {err:?}"))
-        })
-    }
-}
-
 /// The BeaconState contains some "zkvm" features that addresses where 32-bit zkVMs would fail
 /// on constructing a VariableList larger than 2^32 size (i.e. 2^40). When "zkvm" feature
 /// is enabled, it would construct the BeaconState with 2^29 list instead.
@@ -150,34 +134,37 @@ pub struct BeaconState {
 
     // History
     pub latest_block_header: BeaconBlockHeader,
-    pub block_roots: FixedVector<B256, U8192>,
-    pub state_roots: FixedVector<B256, U8192>,
+    pub block_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
+    pub state_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
     /// Frozen in Capella, replaced by historical_summaries
     pub historical_roots: VariableList<B256, U16777216>,
 
     // Eth1
     pub eth1_data: Eth1Data,
-    pub eth1_data_votes: VariableList<Eth1Data, U2048>,
+    pub eth1_data_votes: PersistentList<Eth1Data, SlotsPerEth1VotingPeriod>,
     #[serde(with = "serde_utils::quoted_u64")]
     pub eth1_deposit_index: u64,
 
-    // Registry
-    pub validators: VariableList<Validator, ValidatorRegistryLimit>,
-    #[serde(with = "quoted_u64_var_list")]
-    pub balances: VariableList<u64, ValidatorRegistryLimit>,
+    // Registry, shared in chunks between the clones of a state until one of them mutates them
+    pub validators:
+        PersistentList<Validator, ValidatorRegistryLimit, ValidatorRegistryTreeHashLimit>,
+    #[serde(with = "quoted_u64_persistent_list")]
+    pub balances: PersistentList<u64, ValidatorRegistryLimit, ValidatorRegistryTreeHashLimit>,
 
     // Randomness
-    pub randao_mixes: FixedVector<B256, U65536>,
+    pub randao_mixes: FixedVector<B256, EpochsPerHistoricalVector>,
 
     // Slashings
     #[serde(with = "quoted_u64_fixed_vec")]
-    pub slashings: FixedVector<u64, U8192>,
+    pub slashings: FixedVector<u64, EpochsPerSlashingsVector>,
 
     // Participation
-    #[serde(with = "quoted_u8_var_list")]
-    pub previous_epoch_participation: VariableList<u8, ValidatorRegistryLimit>,
-    #[serde(with = "quoted_u8_var_list")]
-    pub current_epoch_participation: VariableList<u8, ValidatorRegistryLimit>,
+    #[serde(with = "quoted_u8_persistent_list")]
+    pub previous_epoch_participation:
+        PersistentList<u8, ValidatorRegistryLimit, ValidatorRegistryTreeHashLimit>,
+    #[serde(with = "quoted_u8_persistent_list")]
+    pub current_epoch_participation:
+        PersistentList<u8, ValidatorRegistryLimit, ValidatorRegistryTreeHashLimit>,
 
     // Finality
     pub justification_bits: BitVector<U4>,
@@ -186,8 +173,9 @@ pub struct BeaconState {
     pub finalized_checkpoint: Checkpoint,
 
     // Inactivity
-    #[serde(with = "quoted_u64_var_list")]
-    pub inactivity_scores: VariableList<u64, ValidatorRegistryLimit>,
+    #[serde(with = "quoted_u64_persistent_list")]
+    pub inactivity_scores:
+        PersistentList<u64, ValidatorRegistryLimit, ValidatorRegistryTreeHashLimit>,
 
     // Sync
     pub current_sync_committee: Arc<SyncCommittee>,
@@ -203,7 +191,7 @@ pub struct BeaconState {
     pub next_withdrawal_validator_index: u64,
 
     // Deep history valid from Capella onwards.
-    pub historical_summaries: VariableList<HistoricalSummary, U16777216>,
+    pub historical_summaries: PersistentList<HistoricalSummary, U16777216>,
 
     // Electra
     #[serde(with = "serde_utils::quoted_u64")]
@@ -218,9 +206,10 @@ pub struct BeaconState {
     pub consolidation_balance_to_consume: u64,
     #[serde(with = "serde_utils::quoted_u64")]
     pub earliest_consolidation_epoch: u64,
-    pub pending_deposits: VariableList<PendingDeposit, U134217728>,
-    pub pending_partial_withdrawals: VariableList<PendingPartialWithdrawal, U134217728>,
-    pub pending_consolidations: VariableList<PendingConsolidation, U262144>,
+    pub pending_deposits: PersistentList<PendingDeposit, U134217728>,
+    pub pending_partial_withdrawals:
+        PersistentList<PendingPartialWithdrawal, PendingPartialWithdrawalsLimit>,
+    pub pending_consolidations: PersistentList<PendingConsolidation, PendingConsolidationsLimit>,
 }
 
 impl BeaconState {
@@ -287,12 +276,7 @@ impl BeaconState {
 
     /// Return the previous epoch (unless the current epoch is ``GENESIS_EPOCH``).
     pub fn get_previous_epoch(&self) -> u64 {
-        let current_epoch = self.get_current_epoch();
-        if current_epoch == GENESIS_EPOCH {
-            GENESIS_EPOCH
-        } else {
-            current_epoch - 1
-        }
+        helpers::get_previous_epoch(self.get_current_epoch())
     }
 
     /// Return the block root at the start of a recent ``epoch``.
@@ -302,11 +286,7 @@ impl BeaconState {
 
     /// Return the block root at a recent ``slot``.
     pub fn get_block_root_at_slot(&self, slot: u64) -> anyhow::Result<B256> {
-        ensure!(
-            slot < self.slot && self.slot <= slot + SLOTS_PER_HISTORICAL_ROOT,
-            "slot given was outside of block_roots range"
-        );
-        Ok(self.block_roots[(slot % SLOTS_PER_HISTORICAL_ROOT) as usize])
+        helpers::get_block_root_at_slot(&self.block_roots, self.slot, slot)
     }
 
     /// Return the randao mix at a recent ``epoch``.
@@ -316,43 +296,27 @@ impl BeaconState {
 
     /// Return the sequence of active validator indices at ``epoch``.
     pub fn get_active_validator_indices(&self, epoch: u64) -> Vec<u64> {
-        self.validators
-            .iter()
-            .enumerate()
-            .filter_map(|(i, validator)| {
-                if validator.is_active_validator(epoch) {
-                    Some(i as u64)
-                } else {
-                    None
-                }
-            })
-            .collect()
+        helpers::get_active_validator_indices(&self.validators, epoch)
     }
 
     /// Return the validator churn limit for the current epoch.
     pub fn get_validator_churn_limit(&self) -> u64 {
+        let network_spec = beacon_network_spec();
         let active_validator_indices = self.get_active_validator_indices(self.get_current_epoch());
         max(
-            MIN_PER_EPOCH_CHURN_LIMIT,
-            active_validator_indices.len() as u64 / CHURN_LIMIT_QUOTIENT,
+            network_spec.min_per_epoch_churn_limit,
+            active_validator_indices.len() as u64 / network_spec.churn_limit_quotient,
         )
     }
 
     /// Return the seed at ``epoch``.
     pub fn get_seed(&self, epoch: u64, domain_type: B32) -> B256 {
-        let mix =
-            self.get_randao_mix(epoch + EPOCHS_PER_HISTORICAL_VECTOR - MIN_SEED_LOOKAHEAD - 1);
-        let epoch_with_index =
-            [domain_type.as_slice(), &epoch.to_le_bytes(), mix.as_slice()].concat();
-        B256::from(hash_fixed(&epoch_with_index))
+        helpers::get_seed(&self.randao_mixes, epoch, domain_type)
     }
 
     /// Return the number of committees in each slot for the given ``epoch``.
     pub fn get_committee_count_per_slot(&self, epoch: u64) -> u64 {
-        (self.get_active_validator_indices(epoch).len() as u64
-            / SLOTS_PER_EPOCH
-            / TARGET_COMMITTEE_SIZE)
-            .clamp(1, MAX_COMMITTEES_PER_SLOT)
+        helpers::get_committee_count_per_slot(self.get_active_validator_indices(epoch).len())
     }
 
     /// Return from ``indices`` a random index sampled by effective balance
@@ -364,16 +328,7 @@ impl BeaconState {
 
         loop {
             let candidate_index = indices[compute_shuffled_index(i % total, total, seed)?];
-
-            let random_bytes = hash(&[seed.as_slice(), &((i / 16) as u64).to_le_bytes()].concat());
-            let offset = i % 16 * 2;
-            let random_value = bytes_to_int64(&random_bytes[offset..offset + 2]);
-
-            let effective_balance = self.validators[candidate_index as usize].effective_balance;
-
-            if (effective_balance * MAX_RANDOM_VALUE)
-                >= (MAX_EFFECTIVE_BALANCE_ELECTRA * random_value as u64)
-            {
+            if self.is_accepted_by_effective_balance(candidate_index, seed, i as u64) {
                 return Ok(candidate_index);
             }
 
@@ -381,6 +336,19 @@ impl BeaconState {
         }
     }
 
+    /// Returns whether the candidate drawn at iteration `i` of a sampling loop is accepted. The
+    /// candidate is accepted with a probability proportional to its effective balance, drawn
+    /// from 16-bit random values so that validators up to `MAX_EFFECTIVE_BALANCE_ELECTRA` are
+    /// weighted precisely.
+    fn is_accepted_by_effective_balance(&self, candidate_index: u64, seed: B256, i: u64) -> bool {
+        let random_bytes = hash(&[seed.as_slice(), &(i / 16).to_le_bytes()].concat());
+        let offset = (i % 16 * 2) as usize;
+        let random_value = bytes_to_int64(&random_bytes[offset..offset + 2]);
+        let effective_balance = self.validators[candidate_index as usize].effective_balance;
+
+        effective_balance * MAX_RANDOM_VALUE >= MAX_EFFECTIVE_BALANCE_ELECTRA * random_value
+    }
+
     /// Return the beacon proposer index at the current slot or a given slot.
     ///
     /// Use `None` when requesting for current slot and `Some(slot)` when requesting for a given
@@ -390,17 +358,27 @@ impl BeaconState {
             Some(slot) => (compute_epoch_at_slot(slot), slot),
             None => (self.get_current_epoch(), self.slot),
         };
-        let seed = B256::from(hash_fixed(
-            &[
-                self.get_seed(epoch, DOMAIN_BEACON_PROPOSER).as_slice(),
-                &slot.to_le_bytes(),
-            ]
-            .concat(),
-        ));
+        let seed = compute_proposer_seed(self.get_seed(epoch, DOMAIN_BEACON_PROPOSER), slot);
         let indices = self.get_active_validator_indices(epoch);
         self.compute_proposer_index(&indices, seed)
     }
 
+    /// Return the beacon proposer indices of every slot of ``epoch``, computing the active
+    /// validator indices and the seed of the epoch once.
+    ///
+    /// Like `get_beacon_proposer_index`, the proposers are sampled by the effective balances of
+    /// this state, so they are only final once the state is in ``epoch``.
+    pub fn get_beacon_proposer_indices_for_epoch(&self, epoch: u64) -> anyhow::Result<Vec<u64>> {
+        let epoch_seed = self.get_seed(epoch, DOMAIN_BEACON_PROPOSER);
+        let indices = self.get_active_validator_indices(epoch);
+        let start_slot = compute_start_slot_at_epoch(epoch);
+        (start_slot..start_slot + SLOTS_PER_EPOCH)
+            .map(|slot| {
+                self.compute_proposer_index(&indices, compute_proposer_seed(epoch_seed, slot))
+            })
+            .collect()
+    }
+
     /// Return the combined effective balance of the ``indices``.
     /// ``EFFECTIVE_BALANCE_INCREMENT`` Gwei minimum to avoid divisions by zero.
     /// Math safe up to ~10B ETH, after which this overflows uint64.
@@ -427,19 +405,11 @@ impl BeaconState {
 
     /// Return the signature domain (fork version concatenated with domain type) of a message.
     pub fn get_domain(&self, domain_type: B32, epoch: Option<u64>) -> B256 {
-        let epoch = match epoch {
-            Some(epoch) => epoch,
-            None => self.get_current_epoch(),
-        };
-        let fork_version = if epoch < self.fork.epoch {
-            self.fork.previous_version
-        } else {
-            self.fork.current_version
-        };
-        compute_domain(
+        helpers::get_domain(
+            &self.fork,
+            self.genesis_validators_root,
             domain_type,
-            Some(fork_version),
-            Some(self.genesis_validators_root),
+            epoch.unwrap_or_else(|| self.get_current_epoch()),
         )
     }
 
@@ -472,12 +442,12 @@ impl BeaconState {
             "Requested epoch {epoch} is beyond the allowed maximum (next epoch: {next_epoch})",
         );
         let start_slot = compute_start_slot_at_epoch(epoch);
-        let committee_count_per_slot = self.get_committee_count_per_slot(epoch);
+        let committee_cache = CommitteeCache::new(self, epoch)?;
         for slot in start_slot..start_slot + SLOTS_PER_EPOCH {
-            for index in 0..committee_count_per_slot {
-                let committee = self.get_beacon_committee(slot, index)?;
+            for index in 0..committee_cache.committees_per_slot() {
+                let committee = committee_cache.get_beacon_committee(slot, index)?;
                 if committee.contains(&validator_index) {
-                    return Ok(Some((committee, index, slot)));
+                    return Ok(Some((committee.to_vec(), index, slot)));
                 }
             }
         }
@@ -490,46 +460,44 @@ impl BeaconState {
         &self,
         indexed_attestation: &IndexedAttestation,
     ) -> anyhow::Result<bool> {
-        let indices: Vec<usize> = indexed_attestation
-            .attesting_indices
-            .iter()
-            .map(|&i| i as usize)
-            .collect();
-        // Verify indices are sorted and unique
-        if indices.is_empty() || !is_sorted_and_unique(&indices) {
-            return Ok(false);
-        }
-
-        let domain = self.get_domain(
-            DOMAIN_BEACON_ATTESTER,
-            Some(indexed_attestation.data.target.epoch),
-        );
-        let signing_root = compute_signing_root(&indexed_attestation.data, domain);
-
-        indexed_attestation
-            .signature
-            .fast_aggregate_verify(
-                indices
-                    .iter()
-                    .map(|&index| {
-                        self.validators
-                            .get(index)
-                            .map(|validator| &validator.public_key)
-                            .ok_or(anyhow!("Invalid index"))
-                    })
-                    .collect::<anyhow::Result<Vec<_>>>()?,
-                signing_root.as_ref(),
-            )
-            .map_err(|err| anyhow!("Invalid indexed attestation: {err}"))
+        helpers::is_valid_indexed_attestation(
+            indexed_attestation,
+            &self.fork,
+            self.genesis_validators_root,
+            |index| self.validators.get(index),
+        )
     }
 
     /// Return the set of attesting indices corresponding to ``aggregation_bits`` and
     /// ``committee_bits``.
     pub fn get_attesting_indices(&self, attestation: &Attestation) -> anyhow::Result<HashSet<u64>> {
+        Self::attesting_indices(attestation, |committee_index| {
+            self.get_beacon_committee(attestation.data.slot, committee_index)
+        })
+    }
+
+    /// Like `get_attesting_indices`, with the committees of the attestation's epoch looked up in
+    /// `committee_cache`.
+    pub fn get_attesting_indices_with_cache(
+        &self,
+        attestation: &Attestation,
+        committee_cache: &CommitteeCache,
+    ) -> anyhow::Result<HashSet<u64>> {
+        Self::attesting_indices(attestation, |committee_index| {
+            Ok(committee_cache
+                .get_beacon_committee(attestation.data.slot, committee_index)?
+                .to_vec())
+        })
+    }
+
+    fn attesting_indices(
+        attestation: &Attestation,
+        get_beacon_committee: impl Fn(u64) -> anyhow::Result<Vec<u64>>,
+    ) -> anyhow::Result<HashSet<u64>> {
         let mut output = HashSet::new();
         let mut committee_offset = 0;
         for committee_index in get_committee_indices(&attestation.committee_bits) {
-            let committee = self.get_beacon_committee(attestation.data.slot, committee_index)?;
+            let committee = get_beacon_committee(committee_index)?;
 
             let mut committee_attesters = HashSet::new();
             for (i, attester_index) in committee.iter().enumerate() {
@@ -553,22 +521,43 @@ impl BeaconState {
         &self,
         attestation: &Attestation,
     ) -> anyhow::Result<IndexedAttestation> {
-        let attesting_indices = self
-            .get_attesting_indices(attestation)?
-            .into_iter()
-            .sorted()
-            .collect::<Vec<_>>();
-        Ok(IndexedAttestation {
+        Ok(Self::indexed_attestation(
+            attestation,
+            self.get_attesting_indices(attestation)?,
+        ))
+    }
+
+    /// Like `get_indexed_attestation`, with the committees of the attestation's epoch looked up
+    /// in `committee_cache`.
+    pub fn get_indexed_attestation_with_cache(
+        &self,
+        attestation: &Attestation,
+        committee_cache: &CommitteeCache,
+    ) -> anyhow::Result<IndexedAttestation> {
+        Ok(Self::indexed_attestation(
+            attestation,
+            self.get_attesting_indices_with_cache(attestation, committee_cache)?,
+        ))
+    }
+
+    fn indexed_attestation(
+        attestation: &Attestation,
+        attesting_indices: HashSet<u64>,
+    ) -> IndexedAttestation {
+        let attesting_indices = attesting_indices.into_iter().sorted().collect::<Vec<_>>();
+        IndexedAttestation {
             attesting_indices: attesting_indices.into(),
             data: attestation.data.clone(),
             signature: attestation.signature.clone(),
-        })
+        }
     }
 
     /// Increase the validator balance at index ``index`` by ``delta``.
     pub fn increase_balance(&mut self, index: u64, delta: u64) -> anyhow::Result<()> {
         if let Some(balance) = self.balances.get_mut(index as usize) {
-            *balance += delta;
+            *balance = balance
+                .checked_add(delta)
+                .ok_or_else(|| anyhow!("Balance of validator {index} overflows"))?;
             Ok(())
         } else {
             Err(anyhow!("failed to increase balance"))
@@ -585,6 +574,16 @@ impl BeaconState {
         }
     }
 
+    /// Set the validator balance at index ``index`` to ``balance``.
+    pub fn set_balance(&mut self, index: u64, balance: u64) -> anyhow::Result<()> {
+        if let Some(current_balance) = self.balances.get_mut(index as usize) {
+            *current_balance = balance;
+            Ok(())
+        } else {
+            Err(anyhow!("failed to set balance"))
+        }
+    }
+
     /// Initiate the exit of the validator with index ``index``.
     pub fn initiate_validator_exit(&mut self, index: u64) -> anyhow::Result<()> {
         // Return if validator already initiated exit
@@ -607,7 +606,7 @@ impl BeaconState {
         validator.exit_epoch = exit_queue_epoch;
         validator.withdrawable_epoch = validator
             .exit_epoch
-            .checked_add(MIN_VALIDATOR_WITHDRAWABILITY_DELAY)
+            .checked_add(beacon_network_spec().min_validator_withdrawability_delay)
             .ok_or(anyhow!("Failed to set withdrawable epoch"))?;
 
         Ok(())
@@ -657,12 +656,154 @@ impl BeaconState {
         Ok(())
     }
 
+    /// Return the genesis state of a chain starting from the eth1 block ``eth1_block_hash``
+    /// with ``deposits``, as a testnet or a test would initialize it.
+    pub fn initialize_beacon_state_from_eth1(
+        eth1_block_hash: B256,
+        eth1_timestamp: u64,
+        deposits: &[Deposit],
+        execution_payload_header: ExecutionPayloadHeader,
+    ) -> anyhow::Result<Self> {
+        let network_spec = beacon_network_spec();
+        let sync_committee = Arc::new(SyncCommittee {
+            public_keys: Default::default(),
+            aggregate_public_key: Default::default(),
+        });
+        let mut state = Self {
+            genesis_time: eth1_timestamp + network_spec.genesis_delay,
+            genesis_validators_root: B256::ZERO,
+            slot: GENESIS_SLOT,
+            fork: Fork {
+                previous_version: network_spec.electra_fork_version,
+                current_version: network_spec.electra_fork_version,
+                epoch: GENESIS_EPOCH,
+            },
+            latest_block_header: BeaconBlockHeader {
+                body_root: BeaconBlockBody::default().tree_hash_root(),
+                ..Default::default()
+            },
+            block_roots: Default::default(),
+            state_roots: Default::default(),
+            historical_roots: Default::default(),
+            eth1_data: Eth1Data {
+                deposit_root: B256::ZERO,
+                deposit_count: deposits.len() as u64,
+                block_hash: eth1_block_hash,
+            },
+            eth1_data_votes: Default::default(),
+            eth1_deposit_index: 0,
+            validators: Default::default(),
+            balances: Default::default(),
+            randao_mixes: FixedVector::from_elem(eth1_block_hash),
+            slashings: Default::default(),
+            previous_epoch_participation: Default::default(),
+            current_epoch_participation: Default::default(),
+            justification_bits: Default::default(),
+            previous_justified_checkpoint: Default::default(),
+            current_justified_checkpoint: Default::default(),
+            finalized_checkpoint: Default::default(),
+            inactivity_scores: Default::default(),
+            current_sync_committee: sync_committee.clone(),
+            next_sync_committee: sync_committee,
+            latest_execution_payload_header: Default::default(),
+            next_withdrawal_index: 0,
+            next_withdrawal_validator_index: 0,
+            historical_summaries: Default::default(),
+            deposit_requests_start_index: UNSET_DEPOSIT_REQUESTS_START_INDEX,
+            deposit_balance_to_consume: 0,
+            exit_balance_to_consume: 0,
+            earliest_exit_epoch: 0,
+            consolidation_balance_to_consume: 0,
+            earliest_consolidation_epoch: 0,
+            pending_deposits: Default::default(),
+            pending_partial_withdrawals: Default::default(),
+            pending_consolidations: Default::default(),
+        };
+
+        // Process deposits, each against the root of the deposits up to it, which the deposit
+        // contract's incremental tree gives without rehashing the earlier deposits
+        let depth = DEPOSIT_CONTRACT_TREE_DEPTH as usize;
+        let mut zero_hashes = vec![B256::ZERO; depth];
+        for height in 1..depth {
+            zero_hashes[height] = B256::from(hash32_concat(
+                zero_hashes[height - 1].as_slice(),
+                zero_hashes[height - 1].as_slice(),
+            ));
+        }
+        let mut branch = vec![B256::ZERO; depth];
+        let mut pubkey_cache = PubkeyCache::default();
+        for (index, deposit) in deposits.iter().enumerate() {
+            let deposit_count = index + 1;
+            let mut node = deposit.data.tree_hash_root();
+            let mut size = deposit_count;
+            for branch_node in branch.iter_mut() {
+                if size % 2 == 1 {
+                    *branch_node = node;
+                    break;
+                }
+                node = B256::from(hash32_concat(branch_node.as_slice(), node.as_slice()));
+                size /= 2;
+            }
+
+            let mut root = B256::ZERO;
+            let mut size = deposit_count;
+            for (branch_node, zero_hash) in branch.iter().zip(&zero_hashes) {
+                root = B256::from(if size % 2 == 1 {
+                    hash32_concat(branch_node.as_slice(), root.as_slice())
+                } else {
+                    hash32_concat(root.as_slice(), zero_hash.as_slice())
+                });
+                size /= 2;
+            }
+            // Mix in the length of the deposit data list
+            let mut length = [0; 32];
+            length[..8].copy_from_slice(&(deposit_count as u64).to_le_bytes());
+            state.eth1_data.deposit_root = B256::from(hash32_concat(root.as_slice(), &length));
+
+            state.process_deposit_with_cache(deposit, &mut pubkey_cache)?;
+        }
+
+        // Process deposit balance updates
+        for deposit in take(&mut state.pending_deposits).iter() {
+            let validator_index = pubkey_cache
+                .get_validator_index(&state, &deposit.public_key)
+                .ok_or(anyhow!("No validator for a genesis deposit"))?;
+            state.increase_balance(validator_index, deposit.amount)?;
+        }
+
+        // Process activations
+        let balances = state.balances.clone();
+        for (validator, &balance) in state.validators.iter_mut().zip(balances.iter()) {
+            validator.effective_balance = min(
+                balance - balance % EFFECTIVE_BALANCE_INCREMENT,
+                validator.get_max_effective_balance(),
+            );
+            if validator.effective_balance >= MIN_ACTIVATION_BALANCE {
+                validator.activation_eligibility_epoch = GENESIS_EPOCH;
+                validator.activation_epoch = GENESIS_EPOCH;
+            }
+        }
+
+        // Set genesis validators root for domain separation and chain versioning
+        state.genesis_validators_root = state.validators.tree_hash_root();
+
+        // Fill in sync committees
+        state.current_sync_committee = Arc::new(state.get_next_sync_committee()?);
+        state.next_sync_committee = Arc::new(state.get_next_sync_committee()?);
+
+        // Initialize the execution payload header
+        state.latest_execution_payload_header = execution_payload_header;
+
+        Ok(state)
+    }
+
     pub fn is_valid_genesis_state(&self) -> bool {
-        if self.genesis_time < MIN_GENESIS_TIME {
+        let network_spec = beacon_network_spec();
+        if self.genesis_time < network_spec.min_genesis_time {
             return false;
         }
         if self.get_active_validator_indices(GENESIS_EPOCH).len()
-            < MIN_GENESIS_ACTIVE_VALIDATOR_COUNT as usize
+            < network_spec.min_genesis_active_validator_count as usize
         {
             return false;
         }
@@ -712,27 +853,38 @@ impl BeaconState {
     }
 
     pub fn process_inactivity_updates(&mut self) -> anyhow::Result<()> {
+        let participation_cache = ParticipationCache::new(self);
+        self.process_inactivity_updates_with_cache(&participation_cache)
+    }
+
+    fn process_inactivity_updates_with_cache(
+        &mut self,
+        participation_cache: &ParticipationCache,
+    ) -> anyhow::Result<()> {
         // Skip the genesis epoch as score updates are based on the previous epoch participation
         if self.get_current_epoch() == GENESIS_EPOCH {
             return Ok(());
         }
-        let unslashed_participating_indices = self.get_unslashed_participating_indices(
-            TIMELY_TARGET_FLAG_INDEX,
-            self.get_previous_epoch(),
-        )?;
-        for index in self.get_eligible_validator_indices()? {
+        let previous_epoch = self.get_previous_epoch();
+        let is_in_inactivity_leak = self.is_in_inactivity_leak();
+        let network_spec = beacon_network_spec();
+        for &index in participation_cache.eligible_validator_indices() {
             // Increase the inactivity score of inactive validators
-            if unslashed_participating_indices.contains(&index) {
+            if participation_cache.is_unslashed_participating(
+                index,
+                TIMELY_TARGET_FLAG_INDEX,
+                previous_epoch,
+            )? {
                 self.inactivity_scores[index as usize] -=
                     min(1, self.inactivity_scores[index as usize])
             } else {
-                self.inactivity_scores[index as usize] += INACTIVITY_SCORE_BIAS
+                self.inactivity_scores[index as usize] += network_spec.inactivity_score_bias
             }
 
             // Decrease the inactivity score of all eligible validators during a leak-free epoch
-            if !self.is_in_inactivity_leak() {
+            if !is_in_inactivity_leak {
                 self.inactivity_scores[index as usize] -= min(
-                    INACTIVITY_SCORE_RECOVERY_RATE,
+                    network_spec.inactivity_score_recovery_rate,
                     self.inactivity_scores[index as usize],
                 )
             }
@@ -742,16 +894,16 @@ impl BeaconState {
     }
 
     pub fn get_base_reward_per_increment(&self) -> u64 {
-        EFFECTIVE_BALANCE_INCREMENT * BASE_REWARD_FACTOR
-            / integer_squareroot(self.get_total_active_balance())
+        compute_base_reward_per_increment(self.get_total_active_balance())
     }
 
     /// Return the base reward for the validator defined by ``index`` with respect to the current
     /// ``state``.
     pub fn get_base_reward(&self, index: u64, base_reward_per_increment: u64) -> u64 {
-        let increments =
-            self.validators[index as usize].effective_balance / EFFECTIVE_BALANCE_INCREMENT;
-        increments * base_reward_per_increment
+        compute_base_reward(
+            self.validators[index as usize].effective_balance,
+            base_reward_per_increment,
+        )
     }
 
     pub fn get_proposer_reward(&self, attesting_index: u64) -> u64 {
@@ -791,46 +943,42 @@ impl BeaconState {
         inclusion_delay: u64,
     ) -> anyhow::Result<Vec<u8>> {
         let justified_checkpoint = if data.target.epoch == self.get_current_epoch() {
-            self.current_justified_checkpoint
+            &self.current_justified_checkpoint
         } else {
-            self.previous_justified_checkpoint
+            &self.previous_justified_checkpoint
         };
-        let is_matching_source = data.source == justified_checkpoint;
-        let is_matching_target =
-            is_matching_source && data.target.root == self.get_block_root(data.target.epoch)?;
-        let is_matching_head = is_matching_target
-            && data.beacon_block_root == self.get_block_root_at_slot(data.slot)?;
-        ensure!(is_matching_source);
-
-        let mut participation_flag_indices = vec![];
-
-        if is_matching_source && inclusion_delay <= integer_squareroot(SLOTS_PER_EPOCH) {
-            participation_flag_indices.push(TIMELY_SOURCE_FLAG_INDEX);
-        }
-        if is_matching_target {
-            participation_flag_indices.push(TIMELY_TARGET_FLAG_INDEX);
-        }
-        if is_matching_head && inclusion_delay == MIN_ATTESTATION_INCLUSION_DELAY {
-            participation_flag_indices.push(TIMELY_HEAD_FLAG_INDEX);
-        }
-
-        Ok(participation_flag_indices)
+        helpers::get_attestation_participation_flag_indices(
+            data,
+            inclusion_delay,
+            justified_checkpoint,
+            |slot| self.get_block_root_at_slot(slot),
+        )
     }
 
     /// Return the inactivity penalty deltas by considering timely target participation flags and
     /// inactivity scores.
     pub fn get_inactivity_penalty_deltas(&self) -> anyhow::Result<(Vec<u64>, Vec<u64>)> {
+        self.get_inactivity_penalty_deltas_with_cache(&ParticipationCache::new(self))
+    }
+
+    fn get_inactivity_penalty_deltas_with_cache(
+        &self,
+        participation_cache: &ParticipationCache,
+    ) -> anyhow::Result<(Vec<u64>, Vec<u64>)> {
         let rewards = vec![0; self.validators.len()];
         let mut penalties = vec![0; self.validators.len()];
         let previous_epoch = self.get_previous_epoch();
-        let matching_target_indices =
-            self.get_unslashed_participating_indices(TIMELY_TARGET_FLAG_INDEX, previous_epoch)?;
-        for index in self.get_eligible_validator_indices()? {
-            if !matching_target_indices.contains(&index) {
+        let inactivity_score_bias = beacon_network_spec().inactivity_score_bias;
+        for &index in participation_cache.eligible_validator_indices() {
+            if !participation_cache.is_unslashed_participating(
+                index,
+                TIMELY_TARGET_FLAG_INDEX,
+                previous_epoch,
+            )? {
                 let penalty_numerator = self.validators[index as usize].effective_balance
                     * self.inactivity_scores[index as usize];
                 let penalty_denominator =
-                    INACTIVITY_SCORE_BIAS * INACTIVITY_PENALTY_QUOTIENT_BELLATRIX;
+                    inactivity_score_bias * INACTIVITY_PENALTY_QUOTIENT_BELLATRIX;
                 penalties[index as usize] += penalty_numerator / penalty_denominator;
             }
         }
@@ -1056,11 +1204,53 @@ impl BeaconState {
         amount: u64,
         signature: BLSSignature,
     ) -> anyhow::Result<()> {
-        if !self
+        let is_new_validator = !self
             .validators
             .iter()
-            .any(|validator| validator.public_key == public_key)
-        {
+            .any(|validator| validator.public_key == public_key);
+        self.apply_deposit_to(
+            public_key,
+            withdrawal_credentials,
+            amount,
+            signature,
+            is_new_validator,
+        )
+    }
+
+    /// Like `apply_deposit`, with the validator looked up in `pubkey_cache`, which is updated
+    /// with the validator the deposit adds.
+    pub fn apply_deposit_with_cache(
+        &mut self,
+        public_key: PublicKey,
+        withdrawal_credentials: B256,
+        amount: u64,
+        signature: BLSSignature,
+        pubkey_cache: &mut PubkeyCache,
+    ) -> anyhow::Result<()> {
+        pubkey_cache.update(self);
+        let is_new_validator = pubkey_cache
+            .get_validator_index(self, &public_key)
+            .is_none();
+        self.apply_deposit_to(
+            public_key,
+            withdrawal_credentials,
+            amount,
+            signature,
+            is_new_validator,
+        )?;
+        pubkey_cache.update(self);
+        Ok(())
+    }
+
+    fn apply_deposit_to(
+        &mut self,
+        public_key: PublicKey,
+        withdrawal_credentials: B256,
+        amount: u64,
+        signature: BLSSignature,
+        is_new_validator: bool,
+    ) -> anyhow::Result<()> {
+        if is_new_validator {
             // Verify the deposit signature (proof of possession) which is not checked by the
             // deposit contract
             match is_valid_deposit_signature(
@@ -1092,6 +1282,32 @@ impl BeaconState {
     }
 
     pub fn process_deposit(&mut self, deposit: &Deposit) -> anyhow::Result<()> {
+        self.verify_and_count_deposit(deposit)?;
+        self.apply_deposit(
+            deposit.data.public_key.clone(),
+            deposit.data.withdrawal_credentials,
+            deposit.data.amount,
+            deposit.data.signature.clone(),
+        )
+    }
+
+    /// Like `process_deposit`, with the validator looked up in `pubkey_cache`.
+    pub fn process_deposit_with_cache(
+        &mut self,
+        deposit: &Deposit,
+        pubkey_cache: &mut PubkeyCache,
+    ) -> anyhow::Result<()> {
+        self.verify_and_count_deposit(deposit)?;
+        self.apply_deposit_with_cache(
+            deposit.data.public_key.clone(),
+            deposit.data.withdrawal_credentials,
+            deposit.data.amount,
+            deposit.data.signature.clone(),
+            pubkey_cache,
+        )
+    }
+
+    fn verify_and_count_deposit(&mut self, deposit: &Deposit) -> anyhow::Result<()> {
         // Verify the Merkle branch
         ensure!(is_valid_merkle_branch(
             deposit.data.tree_hash_root(),
@@ -1105,48 +1321,22 @@ impl BeaconState {
         // Deposits must be processed in order
         self.eth1_deposit_index += 1;
 
-        self.apply_deposit(
-            deposit.data.public_key.clone(),
-            deposit.data.withdrawal_credentials,
-            deposit.data.amount,
-            deposit.data.signature.clone(),
-        )
+        Ok(())
     }
 
     pub fn validate_bls_to_execution_change(
         &self,
         signed_bls_to_execution_change: &SignedBLSToExecutionChange,
     ) -> anyhow::Result<()> {
-        let bls_to_execution_change = &signed_bls_to_execution_change.message;
-
-        ensure!(bls_to_execution_change.validator_index < self.validators.len() as u64);
-
-        let validator: &Validator =
-            &self.validators[bls_to_execution_change.validator_index as usize];
-
-        ensure!(&validator.withdrawal_credentials[..1] == BLS_WITHDRAWAL_PREFIX);
-        ensure!(
-            validator.withdrawal_credentials[1..]
-                == hash(bls_to_execution_change.from_bls_public_key.to_bytes())[1..]
-        );
-
-        // Fork-agnostic domain since address changes are valid across forks
-        let domain = compute_domain(
-            DOMAIN_BLS_TO_EXECUTION_CHANGE,
-            None,
-            Some(self.genesis_validators_root),
-        );
-
-        let signing_root = compute_signing_root(bls_to_execution_change, domain);
-        ensure!(
-            signed_bls_to_execution_change.signature.verify(
-                &bls_to_execution_change.from_bls_public_key,
-                signing_root.as_ref()
-            )?,
-            "BLS Signature verification failed!"
-        );
-
-        Ok(())
+        let validator = self
+            .validators
+            .get(signed_bls_to_execution_change.message.validator_index as usize)
+            .ok_or_else(|| anyhow!("Invalid validator index"))?;
+        helpers::validate_bls_to_execution_change(
+            validator,
+            signed_bls_to_execution_change,
+            self.genesis_validators_root,
+        )
     }
 
     pub fn process_bls_to_execution_change(
@@ -1157,14 +1347,8 @@ impl BeaconState {
 
         let bls_to_execution_change = &signed_bls_to_execution_change.message;
 
-        let withdrawal_credentials = [
-            ETH1_ADDRESS_WITHDRAWAL_PREFIX,
-            vec![0x00; 11].as_slice(),
-            bls_to_execution_change.to_execution_address.as_slice(),
-        ]
-        .concat();
         self.validators[bls_to_execution_change.validator_index as usize].withdrawal_credentials =
-            B256::from_slice(&withdrawal_credentials);
+            get_eth1_withdrawal_credentials(bls_to_execution_change.to_execution_address);
 
         Ok(())
     }
@@ -1177,6 +1361,16 @@ impl BeaconState {
     pub fn validate_voluntary_exit(
         &self,
         signed_voluntary_exit: &SignedVoluntaryExit,
+    ) -> anyhow::Result<()> {
+        self.validate_voluntary_exit_with_verification(signed_voluntary_exit, true)
+    }
+
+    /// Runs ``validate_voluntary_exit``, skipping the signature check unless ``verify_signature``,
+    /// for exits whose signature was verified in a batch.
+    fn validate_voluntary_exit_with_verification(
+        &self,
+        signed_voluntary_exit: &SignedVoluntaryExit,
+        verify_signature: bool,
     ) -> anyhow::Result<()> {
         let voluntary_exit = &signed_voluntary_exit.message;
         let validator_index = voluntary_exit.validator_index as usize;
@@ -1213,7 +1407,7 @@ impl BeaconState {
         // Verify the validator has been active long enough
         let earlist_exit_epoch = validator
             .activation_epoch
-            .checked_add(SHARD_COMMITTEE_PERIOD)
+            .checked_add(beacon_network_spec().shard_committee_period)
             .ok_or(anyhow!("Failed to calculate earliest exit epoch"))?;
         ensure!(
             self.get_current_epoch() >= earlist_exit_epoch,
@@ -1229,9 +1423,10 @@ impl BeaconState {
         let signing_root = compute_signing_root(voluntary_exit, domain);
 
         ensure!(
-            signed_voluntary_exit
-                .signature
-                .verify(&validator.public_key, signing_root.as_ref())?,
+            !verify_signature
+                || signed_voluntary_exit
+                    .signature
+                    .verify(&validator.public_key, signing_root.as_ref())?,
             "BLS Signature verification failed!"
         );
 
@@ -1242,7 +1437,15 @@ impl BeaconState {
         &mut self,
         signed_voluntary_exit: &SignedVoluntaryExit,
     ) -> anyhow::Result<()> {
-        self.validate_voluntary_exit(signed_voluntary_exit)?;
+        self.process_voluntary_exit_with_verification(signed_voluntary_exit, true)
+    }
+
+    fn process_voluntary_exit_with_verification(
+        &mut self,
+        signed_voluntary_exit: &SignedVoluntaryExit,
+        verify_signature: bool,
+    ) -> anyhow::Result<()> {
+        self.validate_voluntary_exit_with_verification(signed_voluntary_exit, verify_signature)?;
 
         // Initiate exit
         self.initiate_validator_exit(signed_voluntary_exit.message.validator_index)?;
@@ -1293,7 +1496,9 @@ impl BeaconState {
         }
 
         // Verify the validator has been active long enough
-        if self.get_current_epoch() < validator.activation_epoch + SHARD_COMMITTEE_PERIOD {
+        if self.get_current_epoch()
+            < validator.activation_epoch + beacon_network_spec().shard_committee_period
+        {
             return Ok(());
         }
 
@@ -1325,7 +1530,8 @@ impl BeaconState {
                 amount,
             );
             let exit_queue_epoch = self.compute_exit_epoch_and_update_churn(to_withdraw);
-            let withdrawable_epoch = exit_queue_epoch + MIN_VALIDATOR_WITHDRAWABILITY_DELAY;
+            let withdrawable_epoch =
+                exit_queue_epoch + beacon_network_spec().min_validator_withdrawability_delay;
             self.pending_partial_withdrawals
                 .push(PendingPartialWithdrawal {
                     validator_index: index as u64,
@@ -1475,7 +1681,9 @@ impl BeaconState {
         }
 
         // Verify the source has been active long enough
-        if current_epoch < source_validator.activation_epoch + SHARD_COMMITTEE_PERIOD {
+        if current_epoch
+            < source_validator.activation_epoch + beacon_network_spec().shard_committee_period
+        {
             return Ok(());
         }
 
@@ -1492,7 +1700,7 @@ impl BeaconState {
         };
         source_validator.exit_epoch = exit_epoch;
         source_validator.withdrawable_epoch =
-            source_validator.exit_epoch + MIN_VALIDATOR_WITHDRAWABILITY_DELAY;
+            source_validator.exit_epoch + beacon_network_spec().min_validator_withdrawability_delay;
 
         self.pending_consolidations
             .push(PendingConsolidation {
@@ -1530,16 +1738,7 @@ impl BeaconState {
     }
 
     pub fn get_proposer_and_participant_rewards(&self) -> (u64, u64) {
-        let total_active_balance = self.get_total_active_balance();
-        let total_active_increments = total_active_balance / EFFECTIVE_BALANCE_INCREMENT;
-        let total_base_rewards = self.get_base_reward_per_increment() * total_active_increments;
-        let max_participant_rewards =
-            total_base_rewards * SYNC_REWARD_WEIGHT / WEIGHT_DENOMINATOR / SLOTS_PER_EPOCH;
-        let participant_reward = max_participant_rewards / SYNC_COMMITTEE_SIZE;
-        let proposer_reward =
-            participant_reward * PROPOSER_WEIGHT / (WEIGHT_DENOMINATOR - PROPOSER_WEIGHT);
-
-        (participant_reward, proposer_reward)
+        helpers::get_proposer_and_participant_rewards(self.get_total_active_balance())
     }
 
     /// Return the sync committee indices, with possible duplicates, for the next sync committee.
@@ -1554,14 +1753,7 @@ impl BeaconState {
             let shuffled_index =
                 compute_shuffled_index(i % active_validator_count, active_validator_count, seed)?;
             let candidate_index = active_validator_indices[shuffled_index];
-
-            let random_bytes = hash(&[seed.as_slice(), &(i / 16).to_le_bytes()].concat());
-            let offset = i % 16 * 2;
-            let random_value = bytes_to_int64(&random_bytes[offset..offset + 2]);
-            let effective_balance = self.validators[candidate_index as usize].effective_balance;
-            if effective_balance * MAX_RANDOM_VALUE
-                >= MAX_EFFECTIVE_BALANCE_ELECTRA * random_value as u64
-            {
+            if self.is_accepted_by_effective_balance(candidate_index, seed, i as u64) {
                 sync_committee_indices.push(candidate_index)
             }
             i += 1
@@ -1570,59 +1762,24 @@ impl BeaconState {
         Ok(sync_committee_indices)
     }
 
+    /// Slash the proposer of the two conflicting headers of ``proposer_slashing``, returning the
+    /// number of validators slashed.
     pub fn process_proposer_slashing(
         &mut self,
         proposer_slashing: &ProposerSlashing,
-    ) -> anyhow::Result<()> {
-        let header_1 = &proposer_slashing.signed_header_1.message;
-        let header_2 = &proposer_slashing.signed_header_2.message;
-
-        // Verify header slots match
-        ensure!(header_1.slot == header_2.slot, "Header slots must match");
-
-        // Verify header proposer indices match
-        ensure!(
-            header_1.proposer_index == header_2.proposer_index,
-            "Proposer indices must match"
-        );
-
-        // Verify the headers are different
-        ensure!(header_1 != header_2, "Headers must be different");
-
-        // Get the proposer and verify they are slashable
-        let proposer_index = header_1.proposer_index;
-        let proposer = self
-            .validators
-            .get(proposer_index as usize)
-            .ok_or_else(|| anyhow::anyhow!("Invalid proposer index"))?;
-
-        ensure!(
-            proposer.is_slashable_validator(self.get_current_epoch()),
-            "Proposer is not slashable"
-        );
-
-        // Verify signatures
-        for signed_header in [
-            &proposer_slashing.signed_header_1,
-            &proposer_slashing.signed_header_2,
-        ] {
-            let domain = self.get_domain(
-                DOMAIN_BEACON_PROPOSER,
-                Some(compute_epoch_at_slot(signed_header.message.slot)),
-            );
-
-            let signing_root = compute_signing_root(&signed_header.message, domain);
-
-            ensure!(
-                signed_header
-                    .signature
-                    .verify(&proposer.public_key, signing_root.as_ref())?,
-                "BLS Signature verification failed!"
-            );
-        }
+    ) -> anyhow::Result<u64> {
+        let proposer_index = helpers::validate_proposer_slashing(
+            proposer_slashing,
+            self.get_current_epoch(),
+            &self.fork,
+            self.genesis_validators_root,
+            |index| self.validators.get(index),
+        )?;
 
         // Slash the validator
-        self.slash_validator(proposer_index, None)
+        self.slash_validator(proposer_index, None)?;
+
+        Ok(1)
     }
 
     pub fn process_historical_summaries_update(&mut self) -> anyhow::Result<()> {
@@ -1678,25 +1835,27 @@ impl BeaconState {
         Ok((attestation_indices_1, attestation_indices_2))
     }
 
+    /// Slash the slashable validators attesting to both attestations of ``attester_slashing``,
+    /// returning the number of validators slashed.
     pub fn process_attester_slashing(
         &mut self,
         attester_slashing: &AttesterSlashing,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<u64> {
         let (indices_1, indices_2) = self.get_slashable_attester_indices(attester_slashing)?;
         let current_epoch = self.get_current_epoch();
-        let mut slashed_any = false;
+        let mut slashed_count = 0;
 
         // Find common attesting indices and process slashing
         for &index in indices_1.intersection(&indices_2).sorted() {
             if self.validators[index as usize].is_slashable_validator(current_epoch) {
                 self.slash_validator(index, None)?;
-                slashed_any = true;
+                slashed_count += 1;
             }
         }
 
-        ensure!(slashed_any, "No validator was slashed");
+        ensure!(slashed_count > 0, "No validator was slashed");
 
-        Ok(())
+        Ok(slashed_count)
     }
 
     fn calculate_sync_committee_balance_change(
@@ -1721,6 +1880,14 @@ impl BeaconState {
     }
 
     pub fn process_sync_aggregate(&mut self, sync_aggregate: &SyncAggregate) -> anyhow::Result<()> {
+        self.process_sync_aggregate_with_verification(sync_aggregate, true)
+    }
+
+    fn process_sync_aggregate_with_verification(
+        &mut self,
+        sync_aggregate: &SyncAggregate,
+        verify_signature: bool,
+    ) -> anyhow::Result<()> {
         let committee_public_keys = &self.current_sync_committee.public_keys;
         let mut participant_public_keys = vec![];
 
@@ -1742,11 +1909,12 @@ impl BeaconState {
             compute_signing_root(self.get_block_root_at_slot(previous_slot)?, domain);
 
         ensure!(
-            eth_fast_aggregate_verify(
-                &participant_public_keys,
-                signing_root,
-                &sync_aggregate.sync_committee_signature,
-            )?,
+            !verify_signature
+                || eth_fast_aggregate_verify(
+                    &participant_public_keys,
+                    signing_root,
+                    &sync_aggregate.sync_committee_signature,
+                )?,
             "Sync aggregate signature verification failed."
         );
 
@@ -1850,6 +2018,14 @@ impl BeaconState {
     }
 
     pub fn process_justification_and_finalization(&mut self) -> anyhow::Result<()> {
+        let participation_cache = ParticipationCache::new(self);
+        self.process_justification_and_finalization_with_cache(&participation_cache)
+    }
+
+    fn process_justification_and_finalization_with_cache(
+        &mut self,
+        participation_cache: &ParticipationCache,
+    ) -> anyhow::Result<()> {
         // Initial FFG checkpoint values have a `0x00` stub for `root`.
         // Skip FFG updates in the first two epochs to avoid corner cases that might result in
         // modifying this stub.
@@ -1857,21 +2033,13 @@ impl BeaconState {
             return Ok(());
         }
 
-        let previous_indices = self.get_unslashed_participating_indices(
-            TIMELY_TARGET_FLAG_INDEX,
-            self.get_previous_epoch(),
-        )?;
-        let current_indices = self.get_unslashed_participating_indices(
-            TIMELY_TARGET_FLAG_INDEX,
-            self.get_current_epoch(),
-        )?;
-
-        let total_active_balance = self.get_total_active_balance();
-        let previous_target_balance = self.get_total_balance(previous_indices);
-        let current_target_balance = self.get_total_balance(current_indices);
+        let previous_target_balance = participation_cache
+            .unslashed_participating_balance(TIMELY_TARGET_FLAG_INDEX, self.get_previous_epoch())?;
+        let current_target_balance = participation_cache
+            .unslashed_participating_balance(TIMELY_TARGET_FLAG_INDEX, self.get_current_epoch())?;
 
         self.weigh_justification_and_finalization(
-            total_active_balance,
+            participation_cache.total_active_balance(),
             previous_target_balance,
             current_target_balance,
         )?;
@@ -1965,16 +2133,18 @@ impl BeaconState {
 
         // Reset eth1 data votes
         if next_epoch.is_multiple_of(EPOCHS_PER_ETH1_VOTING_PERIOD) {
-            self.eth1_data_votes = VariableList::default();
+            self.eth1_data_votes = PersistentList::default();
         }
 
         Ok(())
     }
 
     pub fn process_effective_balance_updates(&mut self) -> anyhow::Result<()> {
-        // Update effective balances with hysteresis
-        for (index, validator) in self.validators.iter_mut().enumerate() {
+        // Update effective balances with hysteresis. Only the validators whose effective balance
+        // changes are written, so that only their chunks of the registry are copied.
+        for index in 0..self.validators.len() {
             let balance = self.balances[index];
+            let validator = &self.validators[index];
             let hysteresis_increment = EFFECTIVE_BALANCE_INCREMENT / HYSTERESIS_QUOTIENT;
             let downward_threshold = hysteresis_increment * HYSTERESIS_DOWNWARD_MULTIPLIER;
             let upward_threshold = hysteresis_increment * HYSTERESIS_UPWARD_MULTIPLIER;
@@ -1982,8 +2152,9 @@ impl BeaconState {
             if balance + downward_threshold < validator.effective_balance
                 || validator.effective_balance + upward_threshold < balance
             {
-                validator.effective_balance = (balance - balance % EFFECTIVE_BALANCE_INCREMENT)
+                let effective_balance = (balance - balance % EFFECTIVE_BALANCE_INCREMENT)
                     .min(validator.get_max_effective_balance());
+                self.validators[index].effective_balance = effective_balance;
             }
         }
 
@@ -1991,6 +2162,14 @@ impl BeaconState {
     }
 
     pub fn process_randao(&mut self, body: &BeaconBlockBody) -> anyhow::Result<()> {
+        self.process_randao_with_verification(body, true)
+    }
+
+    fn process_randao_with_verification(
+        &mut self,
+        body: &BeaconBlockBody,
+        verify_signature: bool,
+    ) -> anyhow::Result<()> {
         let epoch = self.get_current_epoch();
 
         // Verify RANDAO reveal
@@ -2001,8 +2180,10 @@ impl BeaconState {
             let signing_root =
                 compute_signing_root(epoch, self.get_domain(DOMAIN_RANDAO, Some(epoch)));
             ensure!(
-                body.randao_reveal
-                    .verify(&proposer.public_key, signing_root.as_ref())?,
+                !verify_signature
+                    || body
+                        .randao_reveal
+                        .verify(&proposer.public_key, signing_root.as_ref())?,
                 "BLS Signature verification failed!"
             );
 
@@ -2036,6 +2217,35 @@ impl BeaconState {
     }
 
     pub fn process_attestation(&mut self, attestation: &Attestation) -> anyhow::Result<()> {
+        let committee_cache = CommitteeCache::new(self, attestation.data.target.epoch)?;
+        self.process_attestation_with_cache(attestation, &committee_cache)
+    }
+
+    /// Like `process_attestation`, with the committees of the target epoch looked up in
+    /// `committee_cache`, so that the attestations of a block share one shuffle per epoch.
+    pub fn process_attestation_with_cache(
+        &mut self,
+        attestation: &Attestation,
+        committee_cache: &CommitteeCache,
+    ) -> anyhow::Result<()> {
+        let mut participation_cache = ParticipationCache::new(self);
+        self.process_attestation_with_verification(
+            attestation,
+            committee_cache,
+            &mut participation_cache,
+            true,
+        )
+    }
+
+    /// The total active balance is read from `participation_cache`, which is updated with the
+    /// flags set by the attestation so that the attestations of a block share it.
+    fn process_attestation_with_verification(
+        &mut self,
+        attestation: &Attestation,
+        committee_cache: &CommitteeCache,
+        participation_cache: &mut ParticipationCache,
+        verify_signature: bool,
+    ) -> anyhow::Result<()> {
         let data = &attestation.data;
         ensure!(
             data.target.epoch == self.get_previous_epoch()
@@ -2048,13 +2258,18 @@ impl BeaconState {
             "Target epoch must match the computed epoch at slot"
         );
 
+        ensure!(
+            committee_cache.epoch() == data.target.epoch,
+            "Committee cache must be of the target epoch"
+        );
+
         ensure!(
             data.slot + MIN_ATTESTATION_INCLUSION_DELAY <= self.slot,
             "Attestation must be included after the minimum delay"
         );
 
         ensure!(
-            data.index < self.get_committee_count_per_slot(data.target.epoch),
+            data.index < committee_cache.committees_per_slot(),
             "Committee index must be within bounds"
         );
 
@@ -2062,8 +2277,8 @@ impl BeaconState {
         let committee_indices = get_committee_indices(&attestation.committee_bits);
         let mut committee_offset = 0;
         for committee_index in committee_indices {
-            ensure!(committee_index < self.get_committee_count_per_slot(data.target.epoch));
-            let committee = self.get_beacon_committee(data.slot, committee_index)?;
+            ensure!(committee_index < committee_cache.committees_per_slot());
+            let committee = committee_cache.get_beacon_committee(data.slot, committee_index)?;
             let mut committee_attesters = HashSet::new();
             for (i, &attester_index) in committee.iter().enumerate() {
                 if attestation
@@ -2093,40 +2308,45 @@ impl BeaconState {
             self.get_attestation_participation_flag_indices(data, self.slot - data.slot)?;
         // Verify signature
         ensure!(
-            self.is_valid_indexed_attestation(&self.get_indexed_attestation(attestation)?)?,
+            !verify_signature
+                || self.is_valid_indexed_attestation(
+                    &self.get_indexed_attestation_with_cache(attestation, committee_cache)?
+                )?,
             "Attestation signature must be valid"
         );
 
-        let base_reward_per_increment = self.get_base_reward_per_increment();
+        let base_reward_per_increment =
+            compute_base_reward_per_increment(participation_cache.total_active_balance());
         let mut proposer_reward_numerator = 0;
-        for index in self.get_attesting_indices(attestation)? {
+        let is_current_epoch = data.target.epoch == self.get_current_epoch();
+        for index in self.get_attesting_indices_with_cache(attestation, committee_cache)? {
             let index = index as usize;
-            for (flag_index, &weight) in PARTICIPATION_FLAG_WEIGHTS.iter().enumerate() {
-                let flag_index = flag_index as u8;
-                let epoch_participation = if data.target.epoch == self.get_current_epoch() {
-                    &mut self.current_epoch_participation
-                } else {
-                    &mut self.previous_epoch_participation
-                };
-
-                let epoch_part = epoch_participation.get_mut(index).ok_or_else(|| {
-                    anyhow!("Validator index {index} out of bounds in epoch_participation",)
-                })?;
-
-                if participation_flag_indices.contains(&flag_index)
-                    && !Self::has_flag(*epoch_part, flag_index)
-                {
-                    *epoch_part = Self::add_flag(*epoch_part, flag_index);
-                    proposer_reward_numerator +=
-                        self.get_base_reward(index as u64, base_reward_per_increment) * weight;
-                }
+            let epoch_participation = if is_current_epoch {
+                &mut self.current_epoch_participation
+            } else {
+                &mut self.previous_epoch_participation
+            };
+            let flags = epoch_participation.get_mut(index).ok_or_else(|| {
+                anyhow!("Validator index {index} out of bounds in epoch_participation")
+            })?;
+            let validator = &self.validators[index];
+            for flag_index in add_participation_flags(flags, &participation_flag_indices) {
+                participation_cache.add_flag(
+                    validator,
+                    index as u64,
+                    flag_index,
+                    data.target.epoch,
+                )?;
+                proposer_reward_numerator +=
+                    compute_base_reward(validator.effective_balance, base_reward_per_increment)
+                        * PARTICIPATION_FLAG_WEIGHTS[flag_index as usize];
             }
         }
         // Reward proposer
-        let proposer_reward_denominator =
-            (WEIGHT_DENOMINATOR - PROPOSER_WEIGHT) * WEIGHT_DENOMINATOR / PROPOSER_WEIGHT;
-        let proposer_reward = proposer_reward_numerator / proposer_reward_denominator;
-        self.increase_balance(self.get_beacon_proposer_index(None)?, proposer_reward)?;
+        self.increase_balance(
+            self.get_beacon_proposer_index(None)?,
+            compute_proposer_reward(proposer_reward_numerator),
+        )?;
         Ok(())
     }
 
@@ -2149,6 +2369,16 @@ impl BeaconState {
     }
 
     pub fn process_slashings(&mut self) -> anyhow::Result<()> {
+        for (index, penalty) in self.get_slashing_penalties()? {
+            self.decrease_balance(index, penalty)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the proportional slashing penalties `process_slashings` applies at the end of the
+    /// current epoch, by validator index.
+    pub fn get_slashing_penalties(&self) -> anyhow::Result<Vec<(u64, u64)>> {
         let epoch = self.get_current_epoch();
         let total_balance = self.get_total_active_balance();
         let adjusted_total_slashing_balance = (self.slashings.iter().sum::<u64>()
@@ -2159,6 +2389,7 @@ impl BeaconState {
         let increment = EFFECTIVE_BALANCE_INCREMENT;
         let penalty_per_effective_balance_increment =
             adjusted_total_slashing_balance / (total_balance / increment);
+        let mut penalties = vec![];
         for index in 0..self.validators.len() {
             let validator = &self
                 .validators
@@ -2171,22 +2402,42 @@ impl BeaconState {
                 let penalty =
                     penalty_per_effective_balance_increment * effective_balance_increments;
 
-                self.decrease_balance(index as u64, penalty)?;
+                penalties.push((index as u64, penalty));
             }
         }
 
-        Ok(())
+        Ok(penalties)
     }
 
     /// Applies ``deposit`` to the ``state``.
     pub fn apply_pending_deposit(&mut self, deposit: &PendingDeposit) -> anyhow::Result<()> {
-        if let Some((index, _validator)) = self
+        let validator_index = self
             .validators
             .iter()
-            .enumerate()
-            .find(|(_, validator)| validator.public_key == deposit.public_key)
-        {
-            self.increase_balance(index as u64, deposit.amount)?;
+            .position(|validator| validator.public_key == deposit.public_key);
+        self.apply_pending_deposit_to(deposit, validator_index.map(|index| index as u64))
+    }
+
+    /// Like `apply_pending_deposit`, with the validator looked up in `pubkey_cache`, which is
+    /// updated with the validator the deposit adds.
+    pub fn apply_pending_deposit_with_cache(
+        &mut self,
+        deposit: &PendingDeposit,
+        pubkey_cache: &mut PubkeyCache,
+    ) -> anyhow::Result<()> {
+        let validator_index = pubkey_cache.get_validator_index(self, &deposit.public_key);
+        self.apply_pending_deposit_to(deposit, validator_index)?;
+        pubkey_cache.update(self);
+        Ok(())
+    }
+
+    fn apply_pending_deposit_to(
+        &mut self,
+        deposit: &PendingDeposit,
+        validator_index: Option<u64>,
+    ) -> anyhow::Result<()> {
+        if let Some(index) = validator_index {
+            self.increase_balance(index, deposit.amount)?;
         } else {
             // Verify the deposit signature (proof of possession) which is not checked by the
             // deposit contract
@@ -2210,6 +2461,14 @@ impl BeaconState {
     }
 
     pub fn process_pending_deposits(&mut self) -> anyhow::Result<()> {
+        self.process_pending_deposits_with_cache(&mut PubkeyCache::default())
+    }
+
+    /// Like `process_pending_deposits`, with the validators looked up in `pubkey_cache`.
+    pub fn process_pending_deposits_with_cache(
+        &mut self,
+        pubkey_cache: &mut PubkeyCache,
+    ) -> anyhow::Result<()> {
         let next_epoch = self.get_current_epoch() + 1;
         let available_for_processing =
             self.deposit_balance_to_consume + self.get_activation_exit_churn_limit();
@@ -2218,6 +2477,8 @@ impl BeaconState {
         let mut deposits_to_postpone = vec![];
         let mut is_churn_limit_reached = false;
         let finalized_slot = compute_start_slot_at_epoch(self.finalized_checkpoint.epoch);
+        // Updated on the first deposit processed, as most epochs have none
+        let mut is_pubkey_cache_updated = false;
 
         for index in 0..self.pending_deposits.len() {
             let Some(deposit) = self.pending_deposits.get(index).cloned() else {
@@ -2242,10 +2503,14 @@ impl BeaconState {
             }
 
             // Read validator state
-            let (is_validator_exited, is_validator_withdrawn) = if let Some(validator) = self
-                .validators
-                .iter()
-                .find(|validator| validator.public_key == deposit.public_key)
+            if !is_pubkey_cache_updated {
+                pubkey_cache.update(self);
+                is_pubkey_cache_updated = true;
+            }
+            let (is_validator_exited, is_validator_withdrawn) = if let Some(validator) =
+                pubkey_cache
+                    .get_validator_index(self, &deposit.public_key)
+                    .and_then(|index| self.validators.get(index as usize))
             {
                 (
                     validator.exit_epoch < FAR_FUTURE_EPOCH,
@@ -2258,7 +2523,7 @@ impl BeaconState {
             if is_validator_withdrawn {
                 // Deposited balance will never become active. Increase balance but do not consume
                 // churn
-                self.apply_pending_deposit(&deposit)?;
+                self.apply_pending_deposit_with_cache(&deposit, pubkey_cache)?;
             } else if is_validator_exited {
                 // Validator is exiting, postpone the deposit until after withdrawable epoch
                 deposits_to_postpone.push(deposit.clone());
@@ -2273,7 +2538,7 @@ impl BeaconState {
 
                 // Consume churn and apply deposit.
                 processed_amount += deposit.amount;
-                self.apply_pending_deposit(&deposit)?;
+                self.apply_pending_deposit_with_cache(&deposit, pubkey_cache)?;
             }
 
             // Regardless of how the deposit was handled, we move on in the queue.
@@ -2353,6 +2618,15 @@ impl BeaconState {
     }
 
     pub fn process_operations(&mut self, body: &BeaconBlockBody) -> anyhow::Result<()> {
+        self.process_operations_with_verification(body, true, &mut PubkeyCache::default())
+    }
+
+    fn process_operations_with_verification(
+        &mut self,
+        body: &BeaconBlockBody,
+        verify_signatures: bool,
+        pubkey_cache: &mut PubkeyCache,
+    ) -> anyhow::Result<()> {
         // Disable former deposit mechanism once all prior deposits are processed
         let eth1_deposit_index_limit = min(
             self.eth1_data.deposit_count,
@@ -2373,14 +2647,34 @@ impl BeaconState {
         for attester_slashing in body.attester_slashings.iter() {
             self.process_attester_slashing(attester_slashing)?;
         }
+        // The attestations of a block share the shuffle of each target epoch and the
+        // participation, which is updated as they set participation flags
+        let mut committee_caches: Vec<CommitteeCache> = vec![];
+        let mut participation_cache = ParticipationCache::new(self);
         for attestation in body.attestations.iter() {
-            self.process_attestation(attestation)?;
+            let target_epoch = attestation.data.target.epoch;
+            let position = match committee_caches
+                .iter()
+                .position(|committee_cache| committee_cache.epoch() == target_epoch)
+            {
+                Some(position) => position,
+                None => {
+                    committee_caches.push(CommitteeCache::new(self, target_epoch)?);
+                    committee_caches.len() - 1
+                }
+            };
+            self.process_attestation_with_verification(
+                attestation,
+                &committee_caches[position],
+                &mut participation_cache,
+                verify_signatures,
+            )?;
         }
         for deposit in body.deposits.iter() {
-            self.process_deposit(deposit)?;
+            self.process_deposit_with_cache(deposit, pubkey_cache)?;
         }
         for voluntary_exit in body.voluntary_exits.iter() {
-            self.process_voluntary_exit(voluntary_exit)?;
+            self.process_voluntary_exit_with_verification(voluntary_exit, verify_signatures)?;
         }
         for bls_to_execution_change in body.bls_to_execution_changes.iter() {
             self.process_bls_to_execution_change(bls_to_execution_change)?;
@@ -2430,7 +2724,7 @@ impl BeaconState {
     /// Return the validator activation churn limit for the current epoch.
     pub fn get_validator_activation_churn_limit(&self) -> u64 {
         min(
-            MAX_PER_EPOCH_ACTIVATION_CHURN_LIMIT,
+            beacon_network_spec().max_per_epoch_activation_churn_limit,
             self.get_validator_churn_limit(),
         )
     }
@@ -2442,18 +2736,20 @@ impl BeaconState {
         // Process activation eligibility, ejections, and activations
         let mut initiate_validator = vec![];
         let finalized_checkpoint_epoch = self.finalized_checkpoint.epoch;
-        for (index, validator) in self.validators.iter_mut().enumerate() {
+        let ejection_balance = beacon_network_spec().ejection_balance;
+        for index in 0..self.validators.len() {
+            let validator = &self.validators[index];
             if validator.is_eligible_for_activation_queue() {
-                validator.activation_eligibility_epoch =
+                self.validators[index].activation_eligibility_epoch =
                     current_epoch.checked_add(1).ok_or_else(|| {
                         anyhow::anyhow!("Epoch overflow when setting activation eligibility epoch")
                     })?;
             } else if validator.is_active_validator(current_epoch)
-                && validator.effective_balance <= EJECTION_BALANCE
+                && validator.effective_balance <= ejection_balance
             {
                 initiate_validator.push(index as u64);
             } else if Self::is_eligible_for_activation(finalized_checkpoint_epoch, validator) {
-                validator.activation_epoch = activation_epoch;
+                self.validators[index].activation_epoch = activation_epoch;
             }
         }
 
@@ -2466,25 +2762,34 @@ impl BeaconState {
 
     /// Return the deltas for a given ``flag_index`` by scanning through the participation flags.
     pub fn get_flag_index_deltas(&self, flag_index: u8) -> anyhow::Result<(Vec<u64>, Vec<u64>)> {
+        self.get_flag_index_deltas_with_cache(flag_index, &ParticipationCache::new(self))
+    }
+
+    fn get_flag_index_deltas_with_cache(
+        &self,
+        flag_index: u8,
+        participation_cache: &ParticipationCache,
+    ) -> anyhow::Result<(Vec<u64>, Vec<u64>)> {
         let mut rewards = vec![0; self.validators.len()];
         let mut penalties = vec![0; self.validators.len()];
 
         let previous_epoch = self.get_previous_epoch();
-        let unslashed_participating_indices =
-            self.get_unslashed_participating_indices(flag_index, previous_epoch)?;
         let weight = PARTICIPATION_FLAG_WEIGHTS[flag_index as usize];
         let unslashed_participating_balance =
-            self.get_total_balance(unslashed_participating_indices.clone());
+            participation_cache.unslashed_participating_balance(flag_index, previous_epoch)?;
         let unslashed_participating_increments =
             unslashed_participating_balance / EFFECTIVE_BALANCE_INCREMENT;
-        let active_increments = self.get_total_active_balance() / EFFECTIVE_BALANCE_INCREMENT;
+        let total_active_balance = participation_cache.total_active_balance();
+        let active_increments = total_active_balance / EFFECTIVE_BALANCE_INCREMENT;
 
-        let base_reward_per_increment = self.get_base_reward_per_increment();
-        for index in self.get_eligible_validator_indices()? {
+        let base_reward_per_increment = EFFECTIVE_BALANCE_INCREMENT * BASE_REWARD_FACTOR
+            / integer_squareroot(total_active_balance);
+        let is_in_inactivity_leak = self.is_in_inactivity_leak();
+        for &index in participation_cache.eligible_validator_indices() {
             let base_reward = self.get_base_reward(index, base_reward_per_increment);
 
-            if unslashed_participating_indices.contains(&index) {
-                if !self.is_in_inactivity_leak() {
+            if participation_cache.is_unslashed_participating(index, flag_index, previous_epoch)? {
+                if !is_in_inactivity_leak {
                     let reward_numerator =
                         base_reward * weight * unslashed_participating_increments;
                     rewards[index as usize] +=
@@ -2499,6 +2804,14 @@ impl BeaconState {
     }
 
     pub fn process_rewards_and_penalties(&mut self) -> anyhow::Result<()> {
+        let participation_cache = ParticipationCache::new(self);
+        self.process_rewards_and_penalties_with_cache(&participation_cache)
+    }
+
+    fn process_rewards_and_penalties_with_cache(
+        &mut self,
+        participation_cache: &ParticipationCache,
+    ) -> anyhow::Result<()> {
         // No rewards are applied at the end of `GENESIS_EPOCH` because rewards are for work done in
         // the previous epoch
         if self.get_current_epoch() == GENESIS_EPOCH {
@@ -2510,11 +2823,13 @@ impl BeaconState {
 
         // Collect the flag deltas for each participation flag index
         for flag_index in 0..PARTICIPATION_FLAG_WEIGHTS.len() {
-            deltas.push(self.get_flag_index_deltas(flag_index as u8)?);
+            deltas.push(
+                self.get_flag_index_deltas_with_cache(flag_index as u8, participation_cache)?,
+            );
         }
 
         // Add the inactivity penalties
-        deltas.push(self.get_inactivity_penalty_deltas()?);
+        deltas.push(self.get_inactivity_penalty_deltas_with_cache(participation_cache)?);
 
         // Iterate over rewards and penalties for each delta
         for (rewards, penalties) in deltas {
@@ -2563,13 +2878,24 @@ impl BeaconState {
     }
 
     pub fn process_epoch(&mut self) -> anyhow::Result<()> {
-        self.process_justification_and_finalization()?;
-        self.process_inactivity_updates()?;
-        self.process_rewards_and_penalties()?;
+        self.process_epoch_with_cache(&mut PubkeyCache::default())
+    }
+
+    /// Like `process_epoch`, with the pending deposits looked up in `pubkey_cache`.
+    pub fn process_epoch_with_cache(
+        &mut self,
+        pubkey_cache: &mut PubkeyCache,
+    ) -> anyhow::Result<()> {
+        // Participation, slashings and effective balances do not change until the rewards are
+        // applied, so the three share one cache
+        let participation_cache = ParticipationCache::new(self);
+        self.process_justification_and_finalization_with_cache(&participation_cache)?;
+        self.process_inactivity_updates_with_cache(&participation_cache)?;
+        self.process_rewards_and_penalties_with_cache(&participation_cache)?;
         self.process_registry_updates()?;
         self.process_slashings()?;
         self.process_eth1_data_reset()?;
-        self.process_pending_deposits()?;
+        self.process_pending_deposits_with_cache(pubkey_cache)?;
         self.process_pending_consolidations()?;
         self.process_effective_balance_updates()?;
         self.process_slashings_reset()?;
@@ -2582,13 +2908,29 @@ impl BeaconState {
     }
 
     pub fn process_slots(&mut self, slot: u64) -> anyhow::Result<()> {
+        self.process_slots_with_cache(
+            slot,
+            &mut BeaconStateTreeHashCache::default(),
+            &mut PubkeyCache::default(),
+        )
+    }
+
+    /// Like `process_slots`, with the state root of each slot computed through
+    /// `tree_hash_cache`, so that only the fields changed since the previous slot are rehashed,
+    /// and the pending deposits of each epoch looked up in `pubkey_cache`.
+    pub fn process_slots_with_cache(
+        &mut self,
+        slot: u64,
+        tree_hash_cache: &mut BeaconStateTreeHashCache,
+        pubkey_cache: &mut PubkeyCache,
+    ) -> anyhow::Result<()> {
         ensure!(self.slot < slot);
 
         while self.slot < slot {
-            self.process_slot()?;
+            self.process_slot_with_cache(tree_hash_cache)?;
             // Process epoch on the start slot of the next epoch
             if (self.slot + 1).is_multiple_of(SLOTS_PER_EPOCH) {
-                self.process_epoch()?;
+                self.process_epoch_with_cache(pubkey_cache)?;
             }
 
             self.slot += 1
@@ -2598,8 +2940,20 @@ impl BeaconState {
     }
 
     pub fn process_slot(&mut self) -> anyhow::Result<()> {
-        // Cache state root
         let previous_state_root = self.tree_hash_root();
+        self.cache_slot_roots(previous_state_root)
+    }
+
+    pub fn process_slot_with_cache(
+        &mut self,
+        tree_hash_cache: &mut BeaconStateTreeHashCache,
+    ) -> anyhow::Result<()> {
+        let previous_state_root = tree_hash_cache.tree_hash_root(self);
+        self.cache_slot_roots(previous_state_root)
+    }
+
+    fn cache_slot_roots(&mut self, previous_state_root: B256) -> anyhow::Result<()> {
+        // Cache state root
         self.state_roots[(self.slot % SLOTS_PER_HISTORICAL_ROOT) as usize] = previous_state_root;
 
         // Cache latest block header state root
@@ -2629,7 +2983,10 @@ impl BeaconState {
         // Verify timestamp
         ensure!(payload.timestamp == self.compute_timestamp_at_slot(self.slot));
         // Verify commitments are under limit
-        ensure!(body.blob_kzg_commitments.len() <= MAX_BLOBS_PER_BLOCK_ELECTRA as usize);
+        ensure!(
+            body.blob_kzg_commitments.len()
+                <= beacon_network_spec().max_blobs_per_block_electra as usize
+        );
 
         // Verify the execution payload is valid
         let mut versioned_hashes = vec![];
@@ -2660,15 +3017,37 @@ impl BeaconState {
         &mut self,
         block: &BeaconBlock,
         execution_engine: &Option<impl ExecutionApi>,
+    ) -> anyhow::Result<()> {
+        self.process_block_with_verification(
+            block,
+            execution_engine,
+            true,
+            &mut PubkeyCache::default(),
+        )
+        .await
+    }
+
+    /// Runs ``process_block``, skipping the checks of the proposer, attestation, exit and sync
+    /// aggregate signatures unless ``verify_signatures``, for blocks whose signatures were
+    /// verified in a batch by [`BlockSignatureVerifier`].
+    async fn process_block_with_verification(
+        &mut self,
+        block: &BeaconBlock,
+        execution_engine: &Option<impl ExecutionApi>,
+        verify_signatures: bool,
+        pubkey_cache: &mut PubkeyCache,
     ) -> anyhow::Result<()> {
         self.process_block_header(block)?;
         self.process_withdrawals(&block.body.execution_payload)?;
         self.process_execution_payload(&block.body, execution_engine)
             .await?;
-        self.process_randao(&block.body)?;
+        self.process_randao_with_verification(&block.body, verify_signatures)?;
         self.process_eth1_data(&block.body)?;
-        self.process_operations(&block.body)?;
-        self.process_sync_aggregate(&block.body.sync_aggregate)?;
+        self.process_operations_with_verification(&block.body, verify_signatures, pubkey_cache)?;
+        self.process_sync_aggregate_with_verification(
+            &block.body.sync_aggregate,
+            verify_signatures,
+        )?;
 
         Ok(())
     }
@@ -2678,29 +3057,77 @@ impl BeaconState {
         signed_block: &SignedBeaconBlock,
         validate_result: bool,
         execution_engine: &Option<impl ExecutionApi>,
+    ) -> anyhow::Result<()> {
+        self.state_transition_with_cache(
+            signed_block,
+            validate_result,
+            execution_engine,
+            &mut BeaconStateTreeHashCache::default(),
+            &mut PubkeyCache::default(),
+        )
+        .await
+    }
+
+    /// Like `state_transition`, with the state roots computed through `tree_hash_cache`, which
+    /// saves rehashing the fields unchanged since the last state it hashed, and the depositing
+    /// validators looked up in `pubkey_cache`.
+    pub async fn state_transition_with_cache(
+        &mut self,
+        signed_block: &SignedBeaconBlock,
+        validate_result: bool,
+        execution_engine: &Option<impl ExecutionApi>,
+        tree_hash_cache: &mut BeaconStateTreeHashCache,
+        pubkey_cache: &mut PubkeyCache,
     ) -> anyhow::Result<()> {
         let block = &signed_block.message;
         // Process slots (including those with no blocks) since block
-        self.process_slots(block.slot)?;
+        self.process_slots_with_cache(block.slot, tree_hash_cache, pubkey_cache)?;
 
-        // Verify signature
+        // Verify signatures, as a batch
         if validate_result {
-            ensure!(self.verify_block_header_signature(&signed_block.signed_header())?)
+            BlockSignatureVerifier::verify_entire_block(self, signed_block)?;
         }
         // Process block
-        self.process_block(block, execution_engine).await?;
+        self.process_block_with_verification(
+            block,
+            execution_engine,
+            !validate_result,
+            pubkey_cache,
+        )
+        .await?;
         // Verify state root
         if validate_result {
-            ensure!(block.state_root == self.tree_hash_root())
+            ensure!(block.state_root == tree_hash_cache.tree_hash_root(self))
         }
         Ok(())
     }
 
+    /// Runs ``state_transition`` without an execution engine or an async runtime, for provers
+    /// such as zkVMs which verify the execution payload separately.
+    pub fn state_transition_without_execution_engine(
+        &mut self,
+        signed_block: &SignedBeaconBlock,
+        validate_result: bool,
+    ) -> anyhow::Result<()> {
+        // Without an execution engine nothing is awaited, so the transition completes on its
+        // first poll
+        let transition = pin!(self.state_transition(
+            signed_block,
+            validate_result,
+            &None::<MockExecutionEngine>
+        ));
+        match transition.poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(result) => result,
+            Poll::Pending => bail!("State transition awaited without an execution engine"),
+        }
+    }
+
     /// Return the churn limit for the current epoch.
     pub fn get_balance_churn_limit(&self) -> u64 {
+        let network_spec = beacon_network_spec();
         let churn = max(
-            MIN_PER_EPOCH_CHURN_LIMIT_ELECTRA,
-            self.get_total_active_balance() / CHURN_LIMIT_QUOTIENT,
+            network_spec.min_per_epoch_churn_limit_electra,
+            self.get_total_active_balance() / network_spec.churn_limit_quotient,
         );
         churn - churn % EFFECTIVE_BALANCE_INCREMENT
     }
@@ -2708,7 +3135,7 @@ impl BeaconState {
     /// Return the churn limit for the current epoch dedicated to activations and exits.
     pub fn get_activation_exit_churn_limit(&self) -> u64 {
         min(
-            MAX_PER_EPOCH_ACTIVATION_EXIT_CHURN_LIMIT,
+            beacon_network_spec().max_per_epoch_activation_exit_churn_limit,
             self.get_balance_churn_limit(),
         )
     }
@@ -2749,10 +3176,7 @@ impl BeaconState {
 
         if balance > &MIN_ACTIVATION_BALANCE {
             let excess_balance = balance - MIN_ACTIVATION_BALANCE;
-            *self
-                .balances
-                .get_mut(index as usize)
-                .ok_or(anyhow!("Balance index out of bounds"))? = MIN_ACTIVATION_BALANCE;
+            self.set_balance(index, MIN_ACTIVATION_BALANCE)?;
 
             let Some(validator) = self.validators.get(index as usize) else {
                 return Err(anyhow!("Validator index out of bounds"));
@@ -2846,7 +3270,7 @@ impl BeaconState {
         let active_balance_eth = self.get_total_active_balance();
         let delta = self.get_balance_churn_limit();
         let epochs_for_validator_set_churn = SAFETY_DECAY * active_balance_eth / (2 * delta * 100);
-        MIN_VALIDATOR_WITHDRAWABILITY_DELAY + epochs_for_validator_set_churn
+        beacon_network_spec().min_validator_withdrawability_delay + epochs_for_validator_set_churn
     }
 
     pub fn merkle_leaves(&self) -> Vec<B256> {
@@ -2930,9 +3354,181 @@ impl BeaconState {
         .concat())
     }
 
+    /// Return the leaves of the field of the state at ``field_index`` when it is a container,
+    /// which proofs can reach into.
+    fn container_field_merkle_leaves(&self, field_index: u64) -> Option<Vec<B256>> {
+        let checkpoint_leaves = |checkpoint: &Checkpoint| {
+            vec![
+                checkpoint.epoch.tree_hash_root(),
+                checkpoint.root.tree_hash_root(),
+            ]
+        };
+        let sync_committee_leaves = |sync_committee: &SyncCommittee| {
+            vec![
+                sync_committee.public_keys.tree_hash_root(),
+                sync_committee.aggregate_public_key.tree_hash_root(),
+            ]
+        };
+        match field_index {
+            3 => Some(vec![
+                self.fork.previous_version.tree_hash_root(),
+                self.fork.current_version.tree_hash_root(),
+                self.fork.epoch.tree_hash_root(),
+            ]),
+            4 => Some(vec![
+                self.latest_block_header.slot.tree_hash_root(),
+                self.latest_block_header.proposer_index.tree_hash_root(),
+                self.latest_block_header.parent_root.tree_hash_root(),
+                self.latest_block_header.state_root.tree_hash_root(),
+                self.latest_block_header.body_root.tree_hash_root(),
+            ]),
+            8 => Some(vec![
+                self.eth1_data.deposit_root.tree_hash_root(),
+                self.eth1_data.deposit_count.tree_hash_root(),
+                self.eth1_data.block_hash.tree_hash_root(),
+            ]),
+            18 => Some(checkpoint_leaves(&self.previous_justified_checkpoint)),
+            19 => Some(checkpoint_leaves(&self.current_justified_checkpoint)),
+            20 => Some(checkpoint_leaves(&self.finalized_checkpoint)),
+            22 => Some(sync_committee_leaves(&self.current_sync_committee)),
+            23 => Some(sync_committee_leaves(&self.next_sync_committee)),
+            24 => Some(self.latest_execution_payload_header.merkle_leaves()),
+            _ => None,
+        }
+    }
+
+    /// Return the tree of the container field of the state holding the node at
+    /// ``generalized_index``, with the generalized indices of the field in the state tree and of
+    /// the node in the field tree.
+    fn container_field_tree(
+        &self,
+        generalized_index: u64,
+    ) -> anyhow::Result<(Vec<B256>, u64, u64)> {
+        let field_length =
+            get_generalized_index_length(generalized_index) - BEACON_STATE_MERKLE_DEPTH;
+        let field_generalized_index = generalized_index >> field_length;
+        let field_index = field_generalized_index - (1 << BEACON_STATE_MERKLE_DEPTH);
+        let Some(field_leaves) = self.container_field_merkle_leaves(field_index) else {
+            bail!("Proofs into field {field_index} of the state are not supported");
+        };
+        let field_depth = (field_leaves.len() as u64).next_power_of_two().ilog2() as u64;
+        ensure!(
+            field_length <= field_depth,
+            "Generalized index {generalized_index} is deeper than field {field_index}"
+        );
+        let node_generalized_index =
+            (1 << field_length) | (generalized_index & ((1 << field_length) - 1));
+
+        Ok((
+            merkle_tree(&field_leaves, field_depth)?,
+            field_generalized_index,
+            node_generalized_index,
+        ))
+    }
+
+    /// Return the SSZ Merkle proof of the node at ``generalized_index`` against the state root.
+    ///
+    /// The node may be any node of the tree of the state fields, or a node inside one of the
+    /// fields which are containers, such as ``finalized_checkpoint.root``, ``next_sync_committee``
+    /// or the fields of ``latest_execution_payload_header``.
+    pub fn compute_merkle_proof(&self, generalized_index: u64) -> anyhow::Result<Vec<B256>> {
+        ensure!(
+            generalized_index >= 1,
+            "Generalized index must be at least 1"
+        );
+        let state_tree = merkle_tree(&self.merkle_leaves(), BEACON_STATE_MERKLE_DEPTH)?;
+        if get_generalized_index_length(generalized_index) <= BEACON_STATE_MERKLE_DEPTH {
+            return generate_proof_for_generalized_index(&state_tree, generalized_index);
+        }
+
+        let (field_tree, field_generalized_index, node_generalized_index) =
+            self.container_field_tree(generalized_index)?;
+        Ok([
+            generate_proof_for_generalized_index(&field_tree, node_generalized_index)?,
+            generate_proof_for_generalized_index(&state_tree, field_generalized_index)?,
+        ]
+        .concat())
+    }
+
+    /// Return the SSZ multiproof of the nodes at ``generalized_indices`` against the state root,
+    /// which may reach into the same fields as [Self::compute_merkle_proof].
+    pub fn compute_multiproof(&self, generalized_indices: &[u64]) -> anyhow::Result<Multiproof> {
+        let state_tree = merkle_tree(&self.merkle_leaves(), BEACON_STATE_MERKLE_DEPTH)?;
+        Multiproof::generate_for_generalized_indices(generalized_indices, |generalized_index| {
+            if get_generalized_index_length(generalized_index) <= BEACON_STATE_MERKLE_DEPTH {
+                return Ok(state_tree[generalized_index as usize]);
+            }
+            let (field_tree, _, node_generalized_index) =
+                self.container_field_tree(generalized_index)?;
+            Ok(field_tree[node_generalized_index as usize])
+        })
+    }
+
     pub fn state_root(&self) -> B256 {
         self.tree_hash_root()
     }
+
+    /// Verify that the state is the post-state of the block ``root`` of a trusted checkpoint at
+    /// ``epoch``, by recomputing the state root and the root of the latest block header. Returns
+    /// the state root.
+    pub fn verify_against_checkpoint(
+        &self,
+        root: B256,
+        epoch: u64,
+    ) -> Result<B256, CheckpointStateError> {
+        let state_root = self.state_root();
+        let mut latest_block_header = self.latest_block_header.clone();
+        if latest_block_header.state_root == B256::ZERO {
+            latest_block_header.state_root = state_root;
+        } else if latest_block_header.slot == self.slot
+            && latest_block_header.state_root != state_root
+        {
+            return Err(CheckpointStateError::StateRootMismatch {
+                header_state_root: latest_block_header.state_root,
+                state_root,
+            });
+        }
+
+        let block_root = latest_block_header.tree_hash_root();
+        if block_root != root {
+            return Err(CheckpointStateError::BlockRootMismatch {
+                block_root,
+                checkpoint_root: root,
+            });
+        }
+
+        let state_epoch = compute_epoch_at_slot(self.slot);
+        if state_epoch != epoch {
+            return Err(CheckpointStateError::EpochMismatch {
+                state_epoch,
+                checkpoint_epoch: epoch,
+            });
+        }
+
+        Ok(state_root)
+    }
+}
+
+/// The reasons for a state not to match a trusted checkpoint.
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum CheckpointStateError {
+    #[error(
+        "Latest block header state root {header_state_root} does not match the state root {state_root}"
+    )]
+    StateRootMismatch {
+        header_state_root: B256,
+        state_root: B256,
+    },
+    #[error("Latest block root {block_root} does not match the checkpoint root {checkpoint_root}")]
+    BlockRootMismatch {
+        block_root: B256,
+        checkpoint_root: B256,
+    },
+    #[error("State epoch {state_epoch} does not match the checkpoint epoch {checkpoint_epoch}")]
+    EpochMismatch {
+        state_epoch: u64,
+        checkpoint_epoch: u64,
+    },
 }
 
 pub fn get_validator_from_deposit(
@@ -2959,6 +3555,20 @@ pub fn get_validator_from_deposit(
     validator
 }
 
+/// Return the base reward per increment for a ``total_active_balance``, as
+/// ``get_base_reward_per_increment`` computes it from the state.
+pub fn compute_base_reward_per_increment(total_active_balance: u64) -> u64 {
+    EFFECTIVE_BALANCE_INCREMENT * BASE_REWARD_FACTOR / integer_squareroot(total_active_balance)
+}
+
+/// Return the seed from which the proposer of ``slot`` is sampled, given the proposer seed of its
+/// epoch.
+fn compute_proposer_seed(epoch_seed: B256, slot: u64) -> B256 {
+    B256::from(hash_fixed(
+        &[epoch_seed.as_slice(), &slot.to_le_bytes()].concat(),
+    ))
+}
+
 /// Wrapper to ``bls.FastAggregateVerify`` accepting the ``G2_POINT_AT_INFINITY`` signature when
 /// ``public_keys`` is empty.
 pub fn eth_fast_aggregate_verify(
@@ -3019,3 +3629,110 @@ pub fn is_valid_deposit_signature(
         .verify(public_key, signing_root.as_ref())
         .map_err(|err| anyhow!("Invalid deposit signature: {err:?}"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::single_validator_state;
+
+    /// Returns a state at the second slot of epoch 3 and the block it is the post-state of.
+    fn anchor() -> anyhow::Result<(BeaconState, BeaconBlock)> {
+        let mut state = single_validator_state()?;
+        state.slot = 3 * SLOTS_PER_EPOCH + 1;
+        let mut block = BeaconBlock {
+            slot: state.slot,
+            parent_root: B256::repeat_byte(1),
+            ..Default::default()
+        };
+        state.latest_block_header = BeaconBlockHeader {
+            slot: block.slot,
+            proposer_index: block.proposer_index,
+            parent_root: block.parent_root,
+            state_root: B256::ZERO,
+            body_root: block.body.tree_hash_root(),
+        };
+        block.state_root = state.state_root();
+        Ok((state, block))
+    }
+
+    #[test]
+    fn test_balance_mutations_are_checked() -> anyhow::Result<()> {
+        let mut state = single_validator_state()?;
+
+        state.set_balance(0, u64::MAX - 1)?;
+        state.increase_balance(0, 1)?;
+        assert_eq!(state.balances[0], u64::MAX);
+        let err = state
+            .increase_balance(0, 1)
+            .expect_err("Increasing the balance past u64::MAX must fail");
+        assert_eq!(err.to_string(), "Balance of validator 0 overflows");
+        assert_eq!(state.balances[0], u64::MAX);
+
+        state.set_balance(0, MIN_ACTIVATION_BALANCE)?;
+        state.decrease_balance(0, MIN_ACTIVATION_BALANCE + 1)?;
+        assert_eq!(state.balances[0], 0);
+
+        assert!(state.increase_balance(1, 1).is_err());
+        assert!(state.decrease_balance(1, 1).is_err());
+        assert!(state.set_balance(1, 1).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_against_checkpoint_accepts_the_anchor_of_the_checkpoint() -> anyhow::Result<()> {
+        let (state, block) = anchor()?;
+        assert_eq!(
+            state.verify_against_checkpoint(block.block_root(), 3),
+            Ok(block.state_root)
+        );
+
+        // The state root is filled into the latest block header at the next slot
+        let mut next_state = state.clone();
+        next_state.latest_block_header.state_root = block.state_root;
+        next_state.slot += 1;
+        assert_eq!(
+            next_state.verify_against_checkpoint(block.block_root(), 3),
+            Ok(next_state.state_root())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_against_checkpoint_rejects_another_root() -> anyhow::Result<()> {
+        let (state, block) = anchor()?;
+        assert_eq!(
+            state.verify_against_checkpoint(B256::repeat_byte(2), 3),
+            Err(CheckpointStateError::BlockRootMismatch {
+                block_root: block.block_root(),
+                checkpoint_root: B256::repeat_byte(2),
+            })
+        );
+
+        let mut state = state;
+        state.latest_block_header.state_root = B256::repeat_byte(3);
+        assert_eq!(
+            state.verify_against_checkpoint(block.block_root(), 3),
+            Err(CheckpointStateError::StateRootMismatch {
+                header_state_root: B256::repeat_byte(3),
+                state_root: state.state_root(),
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_against_checkpoint_rejects_another_epoch() -> anyhow::Result<()> {
+        let (state, block) = anchor()?;
+        for epoch in [2, 4] {
+            assert_eq!(
+                state.verify_against_checkpoint(block.block_root(), epoch),
+                Err(CheckpointStateError::EpochMismatch {
+                    state_epoch: 3,
+                    checkpoint_epoch: epoch,
+                })
+            );
+        }
+        Ok(())
+    }
+}
@@ -5,7 +5,11 @@ use ssz_derive::{Decode, Encode};
 use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
 
-use crate::electra::blinded_beacon_block_body::BlindedBeaconBlockBody;
+use crate::electra::{
+    beacon_block::{BeaconBlock, SignedBeaconBlock},
+    blinded_beacon_block_body::BlindedBeaconBlockBody,
+    execution_payload::ExecutionPayload,
+};
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
 pub struct BlindedBeaconBlock {
@@ -22,6 +26,19 @@ impl BlindedBeaconBlock {
     pub fn block_root(&self) -> B256 {
         self.tree_hash_root()
     }
+
+    pub fn to_beacon_block(
+        &self,
+        execution_payload: ExecutionPayload,
+    ) -> anyhow::Result<BeaconBlock> {
+        Ok(BeaconBlock {
+            slot: self.slot,
+            proposer_index: self.proposer_index,
+            parent_root: self.parent_root,
+            state_root: self.state_root,
+            body: self.body.to_beacon_block_body(execution_payload)?,
+        })
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
@@ -29,3 +46,17 @@ pub struct SignedBlindedBeaconBlock {
     pub message: BlindedBeaconBlock,
     pub signature: BLSSignature,
 }
+
+impl SignedBlindedBeaconBlock {
+    /// Returns the full block, given the execution payload whose header is committed to by the
+    /// block. The signature stays valid since both blocks have the same root.
+    pub fn to_signed_beacon_block(
+        &self,
+        execution_payload: ExecutionPayload,
+    ) -> anyhow::Result<SignedBeaconBlock> {
+        Ok(SignedBeaconBlock {
+            message: self.message.to_beacon_block(execution_payload)?,
+            signature: self.signature.clone(),
+        })
+    }
+}
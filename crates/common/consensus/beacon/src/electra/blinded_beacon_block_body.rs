@@ -1,20 +1,28 @@
 use alloy_primitives::B256;
+use anyhow::ensure;
 use ream_bls::BLSSignature;
-use ream_consensus_misc::eth_1_data::Eth1Data;
+use ream_consensus_misc::{eth_1_data::Eth1Data, preset::MaxBlobCommitmentsPerBlock};
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
 use ssz_types::{
     VariableList,
-    typenum::{U1, U8, U16, U4096},
+    typenum::{U1, U8, U16},
 };
 use tree_hash_derive::TreeHash;
 
 use crate::{
-    attestation::Attestation, attester_slashing::AttesterSlashing,
-    bls_to_execution_change::SignedBLSToExecutionChange, deposit::Deposit,
-    electra::execution_payload_header::ExecutionPayloadHeader,
-    execution_requests::ExecutionRequests, polynomial_commitments::kzg_commitment::KZGCommitment,
-    proposer_slashing::ProposerSlashing, sync_aggregate::SyncAggregate,
+    attestation::Attestation,
+    attester_slashing::AttesterSlashing,
+    bls_to_execution_change::SignedBLSToExecutionChange,
+    deposit::Deposit,
+    electra::{
+        beacon_block_body::BeaconBlockBody, execution_payload::ExecutionPayload,
+        execution_payload_header::ExecutionPayloadHeader,
+    },
+    execution_requests::ExecutionRequests,
+    polynomial_commitments::kzg_commitment::KZGCommitment,
+    proposer_slashing::ProposerSlashing,
+    sync_aggregate::SyncAggregate,
     voluntary_exit::SignedVoluntaryExit,
 };
 
@@ -39,6 +47,37 @@ pub struct BlindedBeaconBlockBody {
     // Execution
     pub execution_payload_header: ExecutionPayloadHeader,
     pub bls_to_execution_changes: VariableList<SignedBLSToExecutionChange, U16>,
-    pub blob_kzg_commitments: VariableList<KZGCommitment, U4096>,
+    pub blob_kzg_commitments: VariableList<KZGCommitment, MaxBlobCommitmentsPerBlock>,
     pub execution_requests: ExecutionRequests,
 }
+
+impl BlindedBeaconBlockBody {
+    /// Returns the full body, given the execution payload whose header is committed to by this
+    /// body.
+    pub fn to_beacon_block_body(
+        &self,
+        execution_payload: ExecutionPayload,
+    ) -> anyhow::Result<BeaconBlockBody> {
+        ensure!(
+            execution_payload.to_execution_payload_header() == self.execution_payload_header,
+            "Execution payload {} does not match the header of the blinded body",
+            execution_payload.block_hash
+        );
+
+        Ok(BeaconBlockBody {
+            randao_reveal: self.randao_reveal.clone(),
+            eth1_data: self.eth1_data.clone(),
+            graffiti: self.graffiti,
+            proposer_slashings: self.proposer_slashings.clone(),
+            attester_slashings: self.attester_slashings.clone(),
+            attestations: self.attestations.clone(),
+            deposits: self.deposits.clone(),
+            voluntary_exits: self.voluntary_exits.clone(),
+            sync_aggregate: self.sync_aggregate.clone(),
+            execution_payload,
+            bls_to_execution_changes: self.bls_to_execution_changes.clone(),
+            blob_kzg_commitments: self.blob_kzg_commitments.clone(),
+            execution_requests: self.execution_requests.clone(),
+        })
+    }
+}
@@ -4,14 +4,14 @@ use alloy_consensus::{
 };
 use alloy_primitives::{Address, B64, B256, Bloom, Bytes, U256, b256};
 use alloy_rlp::Encodable;
-use ream_consensus_misc::misc::checksummed_address;
+use ream_consensus_misc::{misc::checksummed_address, preset::MaxWithdrawalsPerPayload};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use ssz_derive::{Decode, Encode};
 use ssz_types::{
     FixedVector, VariableList,
     serde_utils::{hex_fixed_vec, hex_var_list, list_of_hex_var_list},
-    typenum::{self, U16, U32, U1048576, U1073741824},
+    typenum::{self, U32, U1048576, U1073741824},
 };
 use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
@@ -54,7 +54,7 @@ pub struct ExecutionPayload {
     pub block_hash: B256,
     #[serde(with = "list_of_hex_var_list")]
     pub transactions: Transactions,
-    pub withdrawals: VariableList<Withdrawal, U16>,
+    pub withdrawals: VariableList<Withdrawal, MaxWithdrawalsPerPayload>,
     #[serde(with = "serde_utils::quoted_u64")]
     pub blob_gas_used: u64,
     #[serde(with = "serde_utils::quoted_u64")]
@@ -123,6 +123,12 @@ impl ExecutionPayload {
     }
 }
 
+impl From<&ExecutionPayload> for ExecutionPayloadHeader {
+    fn from(execution_payload: &ExecutionPayload) -> Self {
+        execution_payload.to_execution_payload_header()
+    }
+}
+
 fn compute_requests_hash(block_requests: &[Bytes]) -> B256 {
     let mut hasher = Sha256::new();
 
@@ -7,6 +7,7 @@ use ssz_types::{
     serde_utils::{hex_fixed_vec, hex_var_list},
     typenum::{self, U32},
 };
+use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
 
 #[derive(
@@ -44,3 +45,27 @@ pub struct ExecutionPayloadHeader {
     #[serde(with = "serde_utils::quoted_u64")]
     pub excess_blob_gas: u64,
 }
+
+impl ExecutionPayloadHeader {
+    pub fn merkle_leaves(&self) -> Vec<B256> {
+        vec![
+            self.parent_hash.tree_hash_root(),
+            self.fee_recipient.tree_hash_root(),
+            self.state_root.tree_hash_root(),
+            self.receipts_root.tree_hash_root(),
+            self.logs_bloom.tree_hash_root(),
+            self.prev_randao.tree_hash_root(),
+            self.block_number.tree_hash_root(),
+            self.gas_limit.tree_hash_root(),
+            self.gas_used.tree_hash_root(),
+            self.timestamp.tree_hash_root(),
+            self.extra_data.tree_hash_root(),
+            self.base_fee_per_gas.tree_hash_root(),
+            self.block_hash.tree_hash_root(),
+            self.transactions_root.tree_hash_root(),
+            self.withdrawals_root.tree_hash_root(),
+            self.blob_gas_used.tree_hash_root(),
+            self.excess_blob_gas.tree_hash_root(),
+        ]
+    }
+}
@@ -1,6 +1,5 @@
 #[cfg(feature = "zkvm")]
 use ssz_types::typenum::U536870912;
-#[cfg(not(feature = "zkvm"))]
 use ssz_types::typenum::U1099511627776;
 
 // VALIDATOR_REGISTRY_LIMIT
@@ -8,3 +7,7 @@ use ssz_types::typenum::U1099511627776;
 pub type ValidatorRegistryLimit = U1099511627776;
 #[cfg(feature = "zkvm")]
 pub type ValidatorRegistryLimit = U536870912;
+
+// The VALIDATOR_REGISTRY_LIMIT of the spec, which the registry lists are hashed with even when
+// the "zkvm" feature lowers their length limit
+pub type ValidatorRegistryTreeHashLimit = U1099511627776;
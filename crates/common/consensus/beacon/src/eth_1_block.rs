@@ -8,7 +8,9 @@ use ssz_derive::{Decode, Encode};
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct Eth1Block {
+    #[serde(with = "serde_utils::quoted_u64")]
     pub number: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
     pub timestamp: u64,
     pub deposit_root: B256,
     #[serde(with = "serde_utils::quoted_u64")]
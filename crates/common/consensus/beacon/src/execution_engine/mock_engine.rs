@@ -1,3 +1,4 @@
+#[cfg(feature = "std")]
 use std::path::Path;
 
 use alloy_primitives::B256;
@@ -22,6 +23,7 @@ impl MockExecutionEngine {
         }
     }
 
+    #[cfg(feature = "std")]
     pub fn from_file(execution_yaml_path: &Path) -> anyhow::Result<MockExecutionEngine> {
         let file = std::fs::File::open(execution_yaml_path)?;
         Ok(serde_yaml::from_reader(file)?)
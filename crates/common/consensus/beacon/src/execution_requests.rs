@@ -1,9 +1,7 @@
+use ream_consensus_misc::preset::{MaxDepositRequestsPerPayload, MaxWithdrawalRequestsPerPayload};
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use ssz_types::{
-    VariableList,
-    typenum::{U2, U16, U8192},
-};
+use ssz_types::{VariableList, typenum::U2};
 use tree_hash_derive::TreeHash;
 
 use crate::{
@@ -15,7 +13,7 @@ use crate::{
     Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash, Default,
 )]
 pub struct ExecutionRequests {
-    pub deposits: VariableList<DepositRequest, U8192>,
-    pub withdrawals: VariableList<WithdrawalRequest, U16>,
+    pub deposits: VariableList<DepositRequest, MaxDepositRequestsPerPayload>,
+    pub withdrawals: VariableList<WithdrawalRequest, MaxWithdrawalRequestsPerPayload>,
     pub consolidations: VariableList<ConsolidationRequest, U2>,
 }
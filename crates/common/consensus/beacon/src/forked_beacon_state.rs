@@ -0,0 +1,210 @@
+use alloy_primitives::B256;
+use itertools::Either;
+use ream_consensus_misc::{
+    checkpoint::Checkpoint,
+    fork::{Fork, ForkName},
+    misc::compute_epoch_at_slot,
+    validator::Validator,
+};
+use ream_network_spec::networks::beacon_network_spec;
+use serde::{Deserialize, Serialize};
+use ssz::{Decode, DecodeError, Encode};
+use tree_hash::TreeHash;
+
+use crate::{altair, bellatrix, capella, deneb, electra, phase0};
+
+/// The offset of the slot in the SSZ encoding of every beacon state, after the genesis time and
+/// the genesis validators root.
+const SLOT_OFFSET: usize = 8 + 32;
+
+/// A beacon state of any fork.
+///
+/// Serializes like the beacon API, as the name of its fork under "version" and the state under
+/// "data".
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(tag = "version", content = "data", rename_all = "lowercase")]
+pub enum ForkedBeaconState {
+    Phase0(Box<phase0::beacon_state::BeaconState>),
+    Altair(Box<altair::beacon_state::BeaconState>),
+    Bellatrix(Box<bellatrix::beacon_state::BeaconState>),
+    Capella(Box<capella::beacon_state::BeaconState>),
+    Deneb(Box<deneb::beacon_state::BeaconState>),
+    Electra(Box<electra::beacon_state::BeaconState>),
+}
+
+macro_rules! map_state {
+    ($value:expr, $state:ident => $body:expr) => {
+        match $value {
+            ForkedBeaconState::Phase0($state) => $body,
+            ForkedBeaconState::Altair($state) => $body,
+            ForkedBeaconState::Bellatrix($state) => $body,
+            ForkedBeaconState::Capella($state) => $body,
+            ForkedBeaconState::Deneb($state) => $body,
+            ForkedBeaconState::Electra($state) => $body,
+        }
+    };
+}
+
+/// Like `map_state!` for the registry lists, which Electra keeps in a persistent list.
+macro_rules! map_registry {
+    ($value:expr, $state:ident => $body:expr) => {
+        match $value {
+            ForkedBeaconState::Phase0($state) => Either::Left($body),
+            ForkedBeaconState::Altair($state) => Either::Left($body),
+            ForkedBeaconState::Bellatrix($state) => Either::Left($body),
+            ForkedBeaconState::Capella($state) => Either::Left($body),
+            ForkedBeaconState::Deneb($state) => Either::Left($body),
+            ForkedBeaconState::Electra($state) => Either::Right($body),
+        }
+    };
+}
+
+macro_rules! impl_from_state {
+    ($($fork:ident => $variant:ident),*) => {
+        $(
+            impl From<$fork::beacon_state::BeaconState> for ForkedBeaconState {
+                fn from(state: $fork::beacon_state::BeaconState) -> Self {
+                    ForkedBeaconState::$variant(Box::new(state))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_state!(
+    phase0 => Phase0,
+    altair => Altair,
+    bellatrix => Bellatrix,
+    capella => Capella,
+    deneb => Deneb,
+    electra => Electra
+);
+
+impl ForkedBeaconState {
+    pub fn fork_name(&self) -> ForkName {
+        match self {
+            ForkedBeaconState::Phase0(_) => ForkName::Phase0,
+            ForkedBeaconState::Altair(_) => ForkName::Altair,
+            ForkedBeaconState::Bellatrix(_) => ForkName::Bellatrix,
+            ForkedBeaconState::Capella(_) => ForkName::Capella,
+            ForkedBeaconState::Deneb(_) => ForkName::Deneb,
+            ForkedBeaconState::Electra(_) => ForkName::Electra,
+        }
+    }
+
+    pub fn genesis_time(&self) -> u64 {
+        map_state!(self, state => state.genesis_time)
+    }
+
+    pub fn genesis_validators_root(&self) -> B256 {
+        map_state!(self, state => state.genesis_validators_root)
+    }
+
+    pub fn slot(&self) -> u64 {
+        map_state!(self, state => state.slot)
+    }
+
+    pub fn fork(&self) -> Fork {
+        map_state!(self, state => state.fork)
+    }
+
+    pub fn validators(&self) -> impl ExactSizeIterator<Item = &Validator> {
+        map_registry!(self, state => state.validators.iter())
+    }
+
+    pub fn balances(&self) -> impl ExactSizeIterator<Item = &u64> {
+        map_registry!(self, state => state.balances.iter())
+    }
+
+    pub fn current_justified_checkpoint(&self) -> Checkpoint {
+        map_state!(self, state => state.current_justified_checkpoint)
+    }
+
+    pub fn finalized_checkpoint(&self) -> Checkpoint {
+        map_state!(self, state => state.finalized_checkpoint)
+    }
+
+    pub fn tree_hash_root(&self) -> B256 {
+        map_state!(self, state => state.tree_hash_root())
+    }
+
+    pub fn as_electra(&self) -> Option<&electra::beacon_state::BeaconState> {
+        match self {
+            ForkedBeaconState::Electra(state) => Some(state),
+            _ => None,
+        }
+    }
+
+    /// Decodes `bytes` as the beacon state of `fork_name`.
+    pub fn from_ssz_bytes_for_fork(bytes: &[u8], fork_name: ForkName) -> Result<Self, DecodeError> {
+        let state = match fork_name {
+            ForkName::Phase0 => {
+                Self::from(phase0::beacon_state::BeaconState::from_ssz_bytes(bytes)?)
+            }
+            ForkName::Altair => {
+                Self::from(altair::beacon_state::BeaconState::from_ssz_bytes(bytes)?)
+            }
+            ForkName::Bellatrix => {
+                Self::from(bellatrix::beacon_state::BeaconState::from_ssz_bytes(bytes)?)
+            }
+            ForkName::Capella => {
+                Self::from(capella::beacon_state::BeaconState::from_ssz_bytes(bytes)?)
+            }
+            ForkName::Deneb => Self::from(deneb::beacon_state::BeaconState::from_ssz_bytes(bytes)?),
+            ForkName::Electra => {
+                Self::from(electra::beacon_state::BeaconState::from_ssz_bytes(bytes)?)
+            }
+            ForkName::Fulu => {
+                return Err(DecodeError::BytesInvalid(format!(
+                    "Unsupported beacon state fork {fork_name}"
+                )));
+            }
+        };
+        Ok(state)
+    }
+
+    /// Decodes `bytes` as the beacon state of the fork active at its slot in the network spec.
+    pub fn from_ssz_bytes_by_slot(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let slot_bytes =
+            bytes
+                .get(SLOT_OFFSET..SLOT_OFFSET + 8)
+                .ok_or(DecodeError::InvalidByteLength {
+                    len: bytes.len(),
+                    expected: SLOT_OFFSET + 8,
+                })?;
+        let slot = u64::from_ssz_bytes(slot_bytes)?;
+        let fork_name = beacon_network_spec()
+            .fork_schedule()
+            .fork_name_at_epoch(compute_epoch_at_slot(slot));
+        Self::from_ssz_bytes_for_fork(bytes, fork_name)
+    }
+}
+
+impl Encode for ForkedBeaconState {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        map_state!(self, state => state.ssz_append(buf))
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        map_state!(self, state => state.ssz_bytes_len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_ssz_bytes_for_fork_rejects_unsupported_fork() {
+        assert!(ForkedBeaconState::from_ssz_bytes_for_fork(&[], ForkName::Fulu).is_err());
+    }
+
+    #[test]
+    fn test_from_ssz_bytes_by_slot_rejects_truncated_state() {
+        assert!(ForkedBeaconState::from_ssz_bytes_by_slot(&[0; SLOT_OFFSET]).is_err());
+    }
+}
@@ -0,0 +1,89 @@
+use alloy_primitives::B256;
+use ream_consensus_misc::{
+    beacon_block_header::SignedBeaconBlockHeader,
+    constants::{
+        BLOB_KZG_COMMITMENTS_INDEX, KZG_COMMITMENTS_INCLUSION_PROOF_DEPTH, NUMBER_OF_COLUMNS,
+    },
+    preset::MaxBlobCommitmentsPerBlock,
+};
+use ream_merkle::is_valid_merkle_branch;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{
+    FixedVector, VariableList,
+    serde_utils::hex_fixed_vec,
+    typenum::{U4, U128, U2048},
+};
+use tree_hash::TreeHash;
+use tree_hash_derive::TreeHash;
+
+use crate::polynomial_commitments::{kzg_commitment::KZGCommitment, kzg_proof::KZGProof};
+
+/// A cell of an extended blob, the unit a column of the data matrix is made of.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Decode, Encode, TreeHash)]
+#[serde(transparent)]
+pub struct Cell {
+    #[serde(with = "hex_fixed_vec")]
+    pub inner: FixedVector<u8, U2048>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Encode, Decode, TreeHash)]
+pub struct DataColumnSidecar {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub index: u64,
+    pub column: VariableList<Cell, MaxBlobCommitmentsPerBlock>,
+    pub kzg_commitments: VariableList<KZGCommitment, MaxBlobCommitmentsPerBlock>,
+    pub kzg_proofs: VariableList<KZGProof, MaxBlobCommitmentsPerBlock>,
+    pub signed_block_header: SignedBeaconBlockHeader,
+    pub kzg_commitments_inclusion_proof: FixedVector<B256, U4>,
+}
+
+impl DataColumnSidecar {
+    /// Checks that the sidecar is well formed: a valid column index and one cell and proof for
+    /// each commitment.
+    pub fn verify_data_column_sidecar(&self) -> bool {
+        self.index < NUMBER_OF_COLUMNS
+            && !self.kzg_commitments.is_empty()
+            && self.column.len() == self.kzg_commitments.len()
+            && self.column.len() == self.kzg_proofs.len()
+    }
+
+    pub fn verify_data_column_sidecar_inclusion_proof(&self) -> bool {
+        is_valid_merkle_branch(
+            self.kzg_commitments.tree_hash_root(),
+            &self.kzg_commitments_inclusion_proof,
+            KZG_COMMITMENTS_INCLUSION_PROOF_DEPTH,
+            BLOB_KZG_COMMITMENTS_INDEX,
+            self.signed_block_header.message.body_root,
+        )
+    }
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize, Encode, Decode, TreeHash, Default,
+)]
+pub struct DataColumnIdentifier {
+    pub block_root: B256,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub index: u64,
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize, Encode, Decode, TreeHash, Default,
+)]
+pub struct DataColumnsByRootIdentifier {
+    pub block_root: B256,
+    #[serde(with = "ssz_types::serde_utils::quoted_u64_var_list")]
+    pub columns: VariableList<u64, U128>,
+}
+
+/// A cell of the data matrix with its proof, addressed by its blob (row) and column.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Encode, Decode, TreeHash)]
+pub struct MatrixEntry {
+    pub cell: Cell,
+    pub kzg_proof: KZGProof,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub column_index: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub row_index: u64,
+}
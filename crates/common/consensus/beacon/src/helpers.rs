@@ -1,9 +1,35 @@
 use std::cmp;
 
-use alloy_primitives::B256;
-use ream_consensus_misc::constants::{EFFECTIVE_BALANCE_INCREMENT, SLOTS_PER_EPOCH};
+use alloy_primitives::{Address, B256, aliases::B32};
+use anyhow::{anyhow, ensure};
+use ream_bls::{PublicKey, traits::Verifiable};
+use ream_consensus_misc::{
+    attestation_data::AttestationData,
+    checkpoint::Checkpoint,
+    constants::{
+        BLS_WITHDRAWAL_PREFIX, DOMAIN_BEACON_ATTESTER, DOMAIN_BEACON_PROPOSER,
+        DOMAIN_BLS_TO_EXECUTION_CHANGE, EFFECTIVE_BALANCE_INCREMENT, EPOCHS_PER_HISTORICAL_VECTOR,
+        ETH1_ADDRESS_WITHDRAWAL_PREFIX, GENESIS_EPOCH, MAX_COMMITTEES_PER_SLOT,
+        MIN_ATTESTATION_INCLUSION_DELAY, MIN_SEED_LOOKAHEAD, PARTICIPATION_FLAG_WEIGHTS,
+        PROPOSER_WEIGHT, SLOTS_PER_EPOCH, SLOTS_PER_HISTORICAL_ROOT, SYNC_COMMITTEE_SIZE,
+        SYNC_REWARD_WEIGHT, TARGET_COMMITTEE_SIZE, TIMELY_HEAD_FLAG_INDEX,
+        TIMELY_SOURCE_FLAG_INDEX, TIMELY_TARGET_FLAG_INDEX, WEIGHT_DENOMINATOR,
+    },
+    fork::Fork,
+    hashing::{hash, hash_fixed},
+    indexed_attestation::IndexedAttestation,
+    misc::{
+        compute_domain, compute_epoch_at_slot, compute_signing_root, compute_start_slot_at_epoch,
+        is_sorted_and_unique,
+    },
+    validator::Validator,
+};
 
-use crate::electra::beacon_state::BeaconState;
+use crate::{
+    bls_to_execution_change::SignedBLSToExecutionChange,
+    electra::beacon_state::{BeaconState, compute_base_reward_per_increment, integer_squareroot},
+    proposer_slashing::ProposerSlashing,
+};
 
 pub fn get_total_balance(state: &BeaconState, indices: Vec<u64>) -> u64 {
     let sum = indices
@@ -38,3 +64,296 @@ pub fn xor<T: AsRef<[u8]>>(bytes_1: T, bytes_2: T) -> B256 {
     }
     result
 }
+
+// The accessors below are shared by the beacon states of the forks since Altair. They take the
+// fields of the state they read, like `capella::withdrawals`.
+
+/// Return the previous epoch of ``current_epoch`` (unless it is ``GENESIS_EPOCH``).
+pub fn get_previous_epoch(current_epoch: u64) -> u64 {
+    if current_epoch == GENESIS_EPOCH {
+        GENESIS_EPOCH
+    } else {
+        current_epoch - 1
+    }
+}
+
+/// Return the block root at a recent ``slot`` of a state at ``state_slot``.
+pub fn get_block_root_at_slot(
+    block_roots: &[B256],
+    state_slot: u64,
+    slot: u64,
+) -> anyhow::Result<B256> {
+    ensure!(
+        slot < state_slot && state_slot <= slot + SLOTS_PER_HISTORICAL_ROOT,
+        "slot given was outside of block_roots range"
+    );
+    Ok(block_roots[(slot % SLOTS_PER_HISTORICAL_ROOT) as usize])
+}
+
+/// Return the sequence of active validator indices at ``epoch``.
+pub fn get_active_validator_indices<'a>(
+    validators: impl IntoIterator<Item = &'a Validator>,
+    epoch: u64,
+) -> Vec<u64> {
+    validators
+        .into_iter()
+        .enumerate()
+        .filter(|(_, validator)| validator.is_active_validator(epoch))
+        .map(|(index, _)| index as u64)
+        .collect()
+}
+
+/// Return the seed at ``epoch``.
+pub fn get_seed(randao_mixes: &[B256], epoch: u64, domain_type: B32) -> B256 {
+    let mix = randao_mixes[((epoch + EPOCHS_PER_HISTORICAL_VECTOR - MIN_SEED_LOOKAHEAD - 1)
+        % EPOCHS_PER_HISTORICAL_VECTOR) as usize];
+    B256::from(hash_fixed(
+        &[domain_type.as_slice(), &epoch.to_le_bytes(), mix.as_slice()].concat(),
+    ))
+}
+
+/// Return the number of committees in each slot of an epoch with ``active_validator_count``
+/// active validators.
+pub fn get_committee_count_per_slot(active_validator_count: usize) -> u64 {
+    (active_validator_count as u64 / SLOTS_PER_EPOCH / TARGET_COMMITTEE_SIZE)
+        .clamp(1, MAX_COMMITTEES_PER_SLOT)
+}
+
+/// Return the signature domain (fork version concatenated with domain type) of a message signed
+/// at ``epoch``.
+pub fn get_domain(
+    fork: &Fork,
+    genesis_validators_root: B256,
+    domain_type: B32,
+    epoch: u64,
+) -> B256 {
+    let fork_version = if epoch < fork.epoch {
+        fork.previous_version
+    } else {
+        fork.current_version
+    };
+    compute_domain(
+        domain_type,
+        Some(fork_version),
+        Some(genesis_validators_root),
+    )
+}
+
+/// Check if ``indexed_attestation`` is not empty, has sorted and unique indices and has a valid
+/// aggregate signature, given the ``fork`` and the validators of the state.
+pub fn is_valid_indexed_attestation<'a>(
+    indexed_attestation: &IndexedAttestation,
+    fork: &Fork,
+    genesis_validators_root: B256,
+    validator: impl Fn(usize) -> Option<&'a Validator>,
+) -> anyhow::Result<bool> {
+    let indices = indexed_attestation
+        .attesting_indices
+        .iter()
+        .map(|&index| index as usize)
+        .collect::<Vec<_>>();
+    // Verify indices are sorted and unique
+    if indices.is_empty() || !is_sorted_and_unique(&indices) {
+        return Ok(false);
+    }
+
+    let domain = get_domain(
+        fork,
+        genesis_validators_root,
+        DOMAIN_BEACON_ATTESTER,
+        indexed_attestation.data.target.epoch,
+    );
+    let signing_root = compute_signing_root(&indexed_attestation.data, domain);
+    indexed_attestation
+        .signature
+        .fast_aggregate_verify(
+            indices
+                .iter()
+                .map(|&index| {
+                    validator(index)
+                        .map(|validator| &validator.public_key)
+                        .ok_or(anyhow!("Invalid index"))
+                })
+                .collect::<anyhow::Result<Vec<&PublicKey>>>()?,
+            signing_root.as_ref(),
+        )
+        .map_err(|err| anyhow!("Invalid indexed attestation: {err}"))
+}
+
+/// Return the flag indices that are satisfied by an attestation with ``data`` included after
+/// ``inclusion_delay`` slots, given the ``justified_checkpoint`` of its target epoch and the block
+/// roots of the state.
+pub fn get_attestation_participation_flag_indices(
+    data: &AttestationData,
+    inclusion_delay: u64,
+    justified_checkpoint: &Checkpoint,
+    block_root_at_slot: impl Fn(u64) -> anyhow::Result<B256>,
+) -> anyhow::Result<Vec<u8>> {
+    let is_matching_source = data.source == *justified_checkpoint;
+    ensure!(is_matching_source, "Attestation source must be justified");
+    let is_matching_target =
+        data.target.root == block_root_at_slot(compute_start_slot_at_epoch(data.target.epoch))?;
+    let is_matching_head =
+        is_matching_target && data.beacon_block_root == block_root_at_slot(data.slot)?;
+
+    let mut participation_flag_indices = vec![];
+    if inclusion_delay <= integer_squareroot(SLOTS_PER_EPOCH) {
+        participation_flag_indices.push(TIMELY_SOURCE_FLAG_INDEX);
+    }
+    if is_matching_target {
+        participation_flag_indices.push(TIMELY_TARGET_FLAG_INDEX);
+    }
+    if is_matching_head && inclusion_delay == MIN_ATTESTATION_INCLUSION_DELAY {
+        participation_flag_indices.push(TIMELY_HEAD_FLAG_INDEX);
+    }
+
+    Ok(participation_flag_indices)
+}
+
+/// Sets the flags of ``participation_flag_indices`` in the participation ``flags`` of a validator
+/// and returns the flag indices which were not set yet.
+pub fn add_participation_flags(
+    flags: &mut u8,
+    participation_flag_indices: &[u8],
+) -> impl Iterator<Item = u8> {
+    let previous_flags = *flags;
+    for &flag_index in participation_flag_indices {
+        *flags = BeaconState::add_flag(*flags, flag_index);
+    }
+    let added_flags = *flags & !previous_flags;
+    (0..PARTICIPATION_FLAG_WEIGHTS.len() as u8)
+        .filter(move |&flag_index| BeaconState::has_flag(added_flags, flag_index))
+}
+
+/// Return the base reward of a validator with ``effective_balance``.
+pub fn compute_base_reward(effective_balance: u64, base_reward_per_increment: u64) -> u64 {
+    effective_balance / EFFECTIVE_BALANCE_INCREMENT * base_reward_per_increment
+}
+
+/// Return the reward of the proposer including attestations whose participation flags add up to
+/// ``proposer_reward_numerator``.
+pub fn compute_proposer_reward(proposer_reward_numerator: u64) -> u64 {
+    let proposer_reward_denominator =
+        (WEIGHT_DENOMINATOR - PROPOSER_WEIGHT) * WEIGHT_DENOMINATOR / PROPOSER_WEIGHT;
+    proposer_reward_numerator / proposer_reward_denominator
+}
+
+/// Return the reward of a sync committee participant and the reward of the proposer for each
+/// participant, given the ``total_active_balance`` of the state.
+pub fn get_proposer_and_participant_rewards(total_active_balance: u64) -> (u64, u64) {
+    let total_active_increments = total_active_balance / EFFECTIVE_BALANCE_INCREMENT;
+    let total_base_rewards =
+        compute_base_reward_per_increment(total_active_balance) * total_active_increments;
+    let max_participant_rewards =
+        total_base_rewards * SYNC_REWARD_WEIGHT / WEIGHT_DENOMINATOR / SLOTS_PER_EPOCH;
+    let participant_reward = max_participant_rewards / SYNC_COMMITTEE_SIZE;
+    let proposer_reward =
+        participant_reward * PROPOSER_WEIGHT / (WEIGHT_DENOMINATOR - PROPOSER_WEIGHT);
+
+    (participant_reward, proposer_reward)
+}
+
+/// Check that the two headers of ``proposer_slashing`` conflict and are signed by their proposer,
+/// who must be slashable at ``current_epoch``, and return the index of the proposer.
+pub fn validate_proposer_slashing<'a>(
+    proposer_slashing: &ProposerSlashing,
+    current_epoch: u64,
+    fork: &Fork,
+    genesis_validators_root: B256,
+    validator: impl Fn(usize) -> Option<&'a Validator>,
+) -> anyhow::Result<u64> {
+    let header_1 = &proposer_slashing.signed_header_1.message;
+    let header_2 = &proposer_slashing.signed_header_2.message;
+
+    // Verify header slots match
+    ensure!(header_1.slot == header_2.slot, "Header slots must match");
+
+    // Verify header proposer indices match
+    ensure!(
+        header_1.proposer_index == header_2.proposer_index,
+        "Proposer indices must match"
+    );
+
+    // Verify the headers are different
+    ensure!(header_1 != header_2, "Headers must be different");
+
+    // Get the proposer and verify they are slashable
+    let proposer_index = header_1.proposer_index;
+    let proposer =
+        validator(proposer_index as usize).ok_or_else(|| anyhow!("Invalid proposer index"))?;
+
+    ensure!(
+        proposer.is_slashable_validator(current_epoch),
+        "Proposer is not slashable"
+    );
+
+    // Verify signatures
+    for signed_header in [
+        &proposer_slashing.signed_header_1,
+        &proposer_slashing.signed_header_2,
+    ] {
+        let domain = get_domain(
+            fork,
+            genesis_validators_root,
+            DOMAIN_BEACON_PROPOSER,
+            compute_epoch_at_slot(signed_header.message.slot),
+        );
+
+        let signing_root = compute_signing_root(&signed_header.message, domain);
+
+        ensure!(
+            signed_header
+                .signature
+                .verify(&proposer.public_key, signing_root.as_ref())?,
+            "BLS Signature verification failed!"
+        );
+    }
+
+    Ok(proposer_index)
+}
+
+/// Check that ``signed_bls_to_execution_change`` is signed with the BLS withdrawal key of
+/// ``validator``.
+pub fn validate_bls_to_execution_change(
+    validator: &Validator,
+    signed_bls_to_execution_change: &SignedBLSToExecutionChange,
+    genesis_validators_root: B256,
+) -> anyhow::Result<()> {
+    let bls_to_execution_change = &signed_bls_to_execution_change.message;
+
+    ensure!(&validator.withdrawal_credentials[..1] == BLS_WITHDRAWAL_PREFIX);
+    ensure!(
+        validator.withdrawal_credentials[1..]
+            == hash(bls_to_execution_change.from_bls_public_key.to_bytes())[1..]
+    );
+
+    // Fork-agnostic domain since address changes are valid across forks
+    let domain = compute_domain(
+        DOMAIN_BLS_TO_EXECUTION_CHANGE,
+        None,
+        Some(genesis_validators_root),
+    );
+
+    let signing_root = compute_signing_root(bls_to_execution_change, domain);
+    ensure!(
+        signed_bls_to_execution_change.signature.verify(
+            &bls_to_execution_change.from_bls_public_key,
+            signing_root.as_ref()
+        )?,
+        "BLS Signature verification failed!"
+    );
+
+    Ok(())
+}
+
+/// Return the withdrawal credentials which withdraw to ``execution_address``.
+pub fn get_eth1_withdrawal_credentials(execution_address: Address) -> B256 {
+    B256::from_slice(
+        &[
+            ETH1_ADDRESS_WITHDRAWAL_PREFIX,
+            vec![0x00; 11].as_slice(),
+            execution_address.as_slice(),
+        ]
+        .concat(),
+    )
+}
@@ -0,0 +1,177 @@
+use alloy_primitives::B256;
+use anyhow::{anyhow, ensure};
+use ream_consensus_misc::{
+    constants::{BEACON_STATE_MERKLE_DEPTH, SLOTS_PER_HISTORICAL_ROOT},
+    historical_batch::HistoricalBatch,
+};
+use ream_merkle::{
+    concat_generalized_indices, generalized_index_from_leaf_index, generate_proof,
+    generate_sparse_proof, merkle_tree, verify_merkle_branch,
+};
+use serde::{Deserialize, Serialize};
+use tree_hash::TreeHash;
+
+use crate::{electra::beacon_state::BeaconState, historical_summary::HistoricalSummary};
+
+/// Index of `historical_roots` in the fields of `BeaconState`.
+pub const HISTORICAL_ROOTS_INDEX: u64 = 7;
+
+/// Index of `historical_summaries` in the fields of `BeaconState`.
+pub const HISTORICAL_SUMMARIES_INDEX: u64 = 27;
+
+/// Depth of the Merkle trees of `historical_roots` and `historical_summaries`, without the length
+/// mix-in.
+pub const HISTORICAL_LIST_MERKLE_DEPTH: u64 = 24;
+
+/// Depth of the Merkle tree of `block_roots` and `state_roots`.
+pub const HISTORICAL_ROOTS_VECTOR_MERKLE_DEPTH: u64 = SLOTS_PER_HISTORICAL_ROOT.ilog2() as u64;
+
+/// A proof that the block with `block_root` was the canonical block at `slot`, against the root
+/// of a `BeaconState` whose `historical_summaries`, or legacy `historical_roots`, summarize the
+/// period of `slot`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoricalBlockProof {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub slot: u64,
+    pub block_root: B256,
+    pub state_root: B256,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub generalized_index: u64,
+    pub branch: Vec<B256>,
+}
+
+impl HistoricalBlockProof {
+    pub fn verify(&self) -> bool {
+        verify_merkle_branch(
+            self.block_root,
+            &self.branch,
+            self.generalized_index,
+            self.state_root,
+        )
+    }
+}
+
+impl BeaconState {
+    /// Returns the index into `historical_summaries` of the summary of the period of `slot`, if
+    /// the period was summarized.
+    pub fn historical_summary_index(&self, slot: u64) -> Option<u64> {
+        let period = slot / SLOTS_PER_HISTORICAL_ROOT;
+        let first_summarized_period = (self.slot / SLOTS_PER_HISTORICAL_ROOT)
+            .checked_sub(self.historical_summaries.len() as u64)?;
+        let index = period.checked_sub(first_summarized_period)?;
+        (index < self.historical_summaries.len() as u64).then_some(index)
+    }
+
+    /// Proves the block root at `slot` against `historical_summaries`, given the `block_roots` of
+    /// the period of `slot` as they were when the period was summarized.
+    pub fn historical_summary_proof(
+        &self,
+        slot: u64,
+        block_roots: &[B256],
+    ) -> anyhow::Result<HistoricalBlockProof> {
+        let index = self
+            .historical_summary_index(slot)
+            .ok_or_else(|| anyhow!("The period of slot {slot} is not summarized"))?;
+        let leaves = self
+            .historical_summaries
+            .iter()
+            .map(|historical_summary| historical_summary.tree_hash_root())
+            .collect::<Vec<_>>();
+
+        self.historical_block_proof(
+            slot,
+            block_roots,
+            &self.historical_summaries[index as usize],
+            HISTORICAL_SUMMARIES_INDEX,
+            &leaves,
+            index,
+        )
+    }
+
+    /// Proves the block root at `slot` against the legacy `historical_roots`, which summarize the
+    /// periods before Capella.
+    pub fn historical_roots_proof(
+        &self,
+        slot: u64,
+        historical_batch: &HistoricalBatch,
+    ) -> anyhow::Result<HistoricalBlockProof> {
+        let index = slot / SLOTS_PER_HISTORICAL_ROOT;
+        let historical_root = self
+            .historical_roots
+            .get(index as usize)
+            .ok_or_else(|| anyhow!("The period of slot {slot} is not in historical_roots"))?;
+        ensure!(
+            historical_batch.tree_hash_root() == *historical_root,
+            "Historical batch does not match historical root {historical_root}"
+        );
+
+        self.historical_block_proof(
+            slot,
+            &historical_batch.block_roots,
+            &HistoricalSummary {
+                block_summary_root: historical_batch.block_roots.tree_hash_root(),
+                state_summary_root: historical_batch.state_roots.tree_hash_root(),
+            },
+            HISTORICAL_ROOTS_INDEX,
+            &self.historical_roots,
+            index,
+        )
+    }
+
+    /// Both accumulators are lists whose elements are merkleized like a `HistoricalSummary`, so a
+    /// block root is proven through its `block_roots` vector, the accumulator element, the list
+    /// and finally the state.
+    fn historical_block_proof(
+        &self,
+        slot: u64,
+        block_roots: &[B256],
+        historical_summary: &HistoricalSummary,
+        field_index: u64,
+        list_leaves: &[B256],
+        list_index: u64,
+    ) -> anyhow::Result<HistoricalBlockProof> {
+        ensure!(
+            block_roots.len() as u64 == SLOTS_PER_HISTORICAL_ROOT,
+            "Expected {SLOTS_PER_HISTORICAL_ROOT} block roots, got {}",
+            block_roots.len()
+        );
+        let block_roots_tree = merkle_tree(block_roots, HISTORICAL_ROOTS_VECTOR_MERKLE_DEPTH)?;
+        ensure!(
+            block_roots_tree[1] == historical_summary.block_summary_root,
+            "Block roots do not match the summary of the period of slot {slot}"
+        );
+
+        let block_index = slot % SLOTS_PER_HISTORICAL_ROOT;
+        let branch = [
+            generate_proof(
+                &block_roots_tree,
+                block_index,
+                HISTORICAL_ROOTS_VECTOR_MERKLE_DEPTH,
+            )?,
+            vec![historical_summary.state_summary_root],
+            generate_sparse_proof(list_leaves, list_index, HISTORICAL_LIST_MERKLE_DEPTH)?,
+            vec![list_leaves.len().to_le_bytes().tree_hash_root()],
+            self.data_inclusion_proof(field_index)?,
+        ]
+        .concat();
+
+        Ok(HistoricalBlockProof {
+            slot,
+            block_root: block_roots[block_index as usize],
+            state_root: self.tree_hash_root(),
+            generalized_index: concat_generalized_indices(&[
+                generalized_index_from_leaf_index(field_index, BEACON_STATE_MERKLE_DEPTH),
+                // The data of a list is the left child of its root
+                2,
+                generalized_index_from_leaf_index(list_index, HISTORICAL_LIST_MERKLE_DEPTH),
+                // `block_summary_root` is the first field of a `HistoricalSummary`
+                2,
+                generalized_index_from_leaf_index(
+                    block_index,
+                    HISTORICAL_ROOTS_VECTOR_MERKLE_DEPTH,
+                ),
+            ]),
+            branch,
+        })
+    }
+}
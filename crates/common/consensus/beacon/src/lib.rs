@@ -1,29 +1,48 @@
 #![warn(clippy::unwrap_used)]
 
+pub mod altair;
 pub mod attestation;
 pub mod attester_slashing;
+pub mod bellatrix;
 pub mod blob_sidecar;
+pub mod block_signature_verifier;
 pub mod bls_to_execution_change;
+pub mod capella;
+pub mod committee_cache;
 pub mod consolidation_request;
+pub mod deneb;
 pub mod deposit;
 pub mod deposit_request;
+pub mod deposit_tree;
 pub mod electra;
 pub mod eth_1_block;
 pub mod execution_engine;
 pub mod execution_requests;
 pub mod fork_choice;
+pub mod forked_beacon_state;
+pub mod fulu;
 pub mod genesis;
 pub mod helpers;
+pub mod historical_proof;
 pub mod historical_summary;
+pub mod participation_cache;
 pub mod pending_consolidation;
 pub mod pending_deposit;
 pub mod pending_partial_withdrawal;
+pub mod persistent_list;
+pub mod phase0;
 pub mod polynomial_commitments;
 pub mod predicates;
 pub mod proposer_slashing;
+pub mod pubkey_cache;
+pub mod quoted;
 pub mod single_attestation;
 pub mod sync_aggregate;
 pub mod sync_committee;
+#[cfg(any(test, feature = "test_utils"))]
+pub mod test_utils;
+pub mod tree_hash_cache;
+pub mod validator_income;
 pub mod voluntary_exit;
 pub mod withdrawal;
 pub mod withdrawal_request;
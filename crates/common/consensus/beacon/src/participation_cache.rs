@@ -0,0 +1,189 @@
+use std::cmp::max;
+
+use anyhow::{anyhow, ensure};
+use ream_consensus_misc::{
+    constants::{EFFECTIVE_BALANCE_INCREMENT, NUM_FLAG_INDICES},
+    validator::Validator,
+};
+
+use crate::electra::beacon_state::BeaconState;
+
+/// The participation of the previous and current epochs, precomputed in one pass over the
+/// validators so that justification, inactivity updates and rewards do not each rebuild the
+/// unslashed participating indices.
+///
+/// The cache only depends on the participation flags, the slashings and the effective balances,
+/// so it stays valid until one of them changes during epoch processing. Flags set by attestations
+/// are recorded with [`ParticipationCache::add_flag`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParticipationCache {
+    previous_epoch: u64,
+    current_epoch: u64,
+    /// The participation flags of each validator, cleared for the validators which are slashed or
+    /// not active in the epoch.
+    previous_epoch_flags: Vec<u8>,
+    current_epoch_flags: Vec<u8>,
+    previous_epoch_flag_balances: [u64; NUM_FLAG_INDICES],
+    current_epoch_flag_balances: [u64; NUM_FLAG_INDICES],
+    eligible_validator_indices: Vec<u64>,
+    total_active_balance: u64,
+}
+
+impl ParticipationCache {
+    pub fn new(state: &BeaconState) -> Self {
+        let previous_epoch = state.get_previous_epoch();
+        let current_epoch = state.get_current_epoch();
+        let validator_count = state.validators.len();
+        let mut cache = Self {
+            previous_epoch,
+            current_epoch,
+            previous_epoch_flags: vec![0; validator_count],
+            current_epoch_flags: vec![0; validator_count],
+            previous_epoch_flag_balances: [0; NUM_FLAG_INDICES],
+            current_epoch_flag_balances: [0; NUM_FLAG_INDICES],
+            eligible_validator_indices: vec![],
+            total_active_balance: 0,
+        };
+
+        for (index, validator) in state.validators.iter().enumerate() {
+            let is_active_in_previous_epoch = validator.is_active_validator(previous_epoch);
+            let is_active_in_current_epoch = validator.is_active_validator(current_epoch);
+
+            if is_active_in_current_epoch {
+                cache.total_active_balance += validator.effective_balance;
+            }
+            if is_active_in_previous_epoch
+                || (validator.slashed && previous_epoch + 1 < validator.withdrawable_epoch)
+            {
+                cache.eligible_validator_indices.push(index as u64);
+            }
+            if validator.slashed {
+                continue;
+            }
+
+            if is_active_in_previous_epoch {
+                let flags = state.previous_epoch_participation[index];
+                cache.previous_epoch_flags[index] = flags;
+                add_flag_balances(
+                    &mut cache.previous_epoch_flag_balances,
+                    flags,
+                    validator.effective_balance,
+                );
+            }
+            if is_active_in_current_epoch {
+                let flags = state.current_epoch_participation[index];
+                cache.current_epoch_flags[index] = flags;
+                add_flag_balances(
+                    &mut cache.current_epoch_flag_balances,
+                    flags,
+                    validator.effective_balance,
+                );
+            }
+        }
+        cache.total_active_balance = max(EFFECTIVE_BALANCE_INCREMENT, cache.total_active_balance);
+
+        cache
+    }
+
+    fn epoch_flags(&self, epoch: u64) -> anyhow::Result<(&[u8], &[u64; NUM_FLAG_INDICES])> {
+        ensure!(
+            epoch == self.previous_epoch || epoch == self.current_epoch,
+            "Epoch must be either the previous or current epoch"
+        );
+        Ok(if epoch == self.current_epoch {
+            (&self.current_epoch_flags, &self.current_epoch_flag_balances)
+        } else {
+            (
+                &self.previous_epoch_flags,
+                &self.previous_epoch_flag_balances,
+            )
+        })
+    }
+
+    /// Returns whether the validator is active, unslashed and has `flag_index` set in `epoch`.
+    pub fn is_unslashed_participating(
+        &self,
+        index: u64,
+        flag_index: u8,
+        epoch: u64,
+    ) -> anyhow::Result<bool> {
+        let (flags, _) = self.epoch_flags(epoch)?;
+        Ok(flags
+            .get(index as usize)
+            .is_some_and(|&flags| BeaconState::has_flag(flags, flag_index)))
+    }
+
+    /// Returns the total effective balance of the unslashed participating validators for
+    /// `flag_index` in `epoch`, with the same `EFFECTIVE_BALANCE_INCREMENT` minimum as
+    /// `get_total_balance`.
+    pub fn unslashed_participating_balance(
+        &self,
+        flag_index: u8,
+        epoch: u64,
+    ) -> anyhow::Result<u64> {
+        let (_, balances) = self.epoch_flags(epoch)?;
+        Ok(max(
+            EFFECTIVE_BALANCE_INCREMENT,
+            balances[flag_index as usize],
+        ))
+    }
+
+    /// Records `flag_index` as set in `epoch` for the validator at `index`, so that the
+    /// participating balances follow the flags set by the attestations of a block instead of
+    /// being rebuilt from the state.
+    pub fn add_flag(
+        &mut self,
+        validator: &Validator,
+        index: u64,
+        flag_index: u8,
+        epoch: u64,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            epoch == self.previous_epoch || epoch == self.current_epoch,
+            "Epoch must be either the previous or current epoch"
+        );
+        if validator.slashed || !validator.is_active_validator(epoch) {
+            return Ok(());
+        }
+
+        let (flags, balances) = if epoch == self.current_epoch {
+            (
+                &mut self.current_epoch_flags,
+                &mut self.current_epoch_flag_balances,
+            )
+        } else {
+            (
+                &mut self.previous_epoch_flags,
+                &mut self.previous_epoch_flag_balances,
+            )
+        };
+        let flags = flags.get_mut(index as usize).ok_or_else(|| {
+            anyhow!("Validator index {index} out of bounds in participation cache")
+        })?;
+        if !BeaconState::has_flag(*flags, flag_index) {
+            *flags = BeaconState::add_flag(*flags, flag_index);
+            balances[flag_index as usize] += validator.effective_balance;
+        }
+        Ok(())
+    }
+
+    pub fn eligible_validator_indices(&self) -> &[u64] {
+        &self.eligible_validator_indices
+    }
+
+    pub fn total_active_balance(&self) -> u64 {
+        self.total_active_balance
+    }
+}
+
+fn add_flag_balances(
+    flag_balances: &mut [u64; NUM_FLAG_INDICES],
+    flags: u8,
+    effective_balance: u64,
+) {
+    for (flag_index, balance) in flag_balances.iter_mut().enumerate() {
+        if BeaconState::has_flag(flags, flag_index as u8) {
+            *balance += effective_balance;
+        }
+    }
+}
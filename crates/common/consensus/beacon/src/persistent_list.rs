@@ -0,0 +1,637 @@
+//! An SSZ list whose clones share its values in chunks, for the validator registry of the beacon
+//! state.
+
+use std::{
+    collections::BTreeSet,
+    iter::FlatMap,
+    marker::PhantomData,
+    mem::take,
+    ops::{Index, IndexMut},
+    slice,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use alloy_primitives::B256;
+use ream_consensus_misc::hashing::hash32_concat;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use ssz::{BYTES_PER_LENGTH_OFFSET, Decode, DecodeError, Encode, SszEncoder};
+use ssz_types::{Error, VariableList, typenum::Unsigned};
+use tree_hash::{PackedEncoding, TreeHash, TreeHashType, mix_in_length};
+
+/// The number of values of a chunk.
+pub const CHUNK_LENGTH: usize = 1024;
+
+/// A list of at most `N` values stored in chunks of [`CHUNK_LENGTH`] values, which its clones
+/// share until they mutate them.
+///
+/// Cloning a list is O(1). The first mutation of a value after a clone copies the table of the
+/// chunks and the chunk of the value, not the whole list, so the states cloned for fork choice
+/// and block production only copy the chunks of the validators and balances their blocks touch.
+///
+/// Each chunk keeps the leaves and the root of its subtree, so hashing the list again only
+/// rehashes the leaves of the values mutated since, and the roots of their chunks.
+///
+/// The list is encoded and serialized like a `VariableList<T, N>`, and hashed like a
+/// `VariableList<T, L>`. `L` is `N` unless the length of the list is limited below the limit it
+/// is hashed with, like the validator registry with the "zkvm" feature.
+pub struct PersistentList<T, N, L = N> {
+    chunks: Arc<Vec<Arc<Chunk<T>>>>,
+    len: usize,
+    _phantom: PhantomData<(N, L)>,
+}
+
+/// The values of a chunk and the hashes of its subtree. A chunk is not mutated while the clones
+/// of a list share it, so its hashes hold for all of them.
+struct Chunk<T> {
+    values: Vec<T>,
+    hashes: Mutex<ChunkHashes>,
+}
+
+#[derive(Clone, Default)]
+struct ChunkHashes {
+    /// The leaves of the subtree of the chunk, as last computed.
+    leaves: Vec<B256>,
+    /// The indices in the chunk of the values mutated or pushed since the leaves were computed.
+    dirty_values: BTreeSet<usize>,
+    /// The root of the subtree of the chunk, `None` if a value changed since it was computed.
+    root: Option<B256>,
+}
+
+impl<T> Chunk<T> {
+    fn new(values: Vec<T>) -> Self {
+        Self {
+            values,
+            hashes: Mutex::default(),
+        }
+    }
+
+    fn hashes_mut(&mut self) -> &mut ChunkHashes {
+        self.hashes
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn mark_dirty(&mut self, index: usize) {
+        let hashes = self.hashes_mut();
+        hashes.dirty_values.insert(index);
+        hashes.root = None;
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.values.len() {
+            return None;
+        }
+        self.mark_dirty(index);
+        self.values.get_mut(index)
+    }
+
+    fn push(&mut self, value: T) {
+        let index = self.values.len();
+        self.mark_dirty(index);
+        self.values.push(value);
+    }
+}
+
+impl<T: Clone> Clone for Chunk<T> {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            hashes: Mutex::new(
+                self.hashes
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .clone(),
+            ),
+        }
+    }
+}
+
+impl<T: TreeHash> Chunk<T> {
+    /// Returns the root of the subtree of `depth` whose first leaves are those of the chunk,
+    /// recomputing only the leaves of the values mutated since the last call.
+    fn root(&self, depth: u32) -> B256 {
+        let mut hashes = self.hashes.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(root) = hashes.root {
+            return root;
+        }
+
+        let packing_factor = packing_factor::<T>();
+        let leaf_count = self.values.len().div_ceil(packing_factor);
+        let computed_count = hashes.leaves.len();
+        let dirty_leaves: BTreeSet<usize> = take(&mut hashes.dirty_values)
+            .into_iter()
+            .map(|index| index / packing_factor)
+            .chain(computed_count..leaf_count)
+            .collect();
+        hashes.leaves.resize(leaf_count, B256::ZERO);
+        for leaf_index in dirty_leaves {
+            hashes.leaves[leaf_index] = self.leaf(leaf_index, packing_factor);
+        }
+
+        let root = merkleize(&hashes.leaves, 0, depth);
+        hashes.root = Some(root);
+        root
+    }
+
+    fn leaf(&self, leaf_index: usize, packing_factor: usize) -> B256 {
+        match T::tree_hash_type() {
+            TreeHashType::Basic => {
+                let start = leaf_index * packing_factor;
+                let end = (start + packing_factor).min(self.values.len());
+                let bytes: Vec<u8> = self.values[start..end]
+                    .iter()
+                    .flat_map(|value| value.tree_hash_packed_encoding())
+                    .collect();
+                let mut leaf = B256::ZERO;
+                leaf[..bytes.len()].copy_from_slice(&bytes);
+                leaf
+            }
+            TreeHashType::Container | TreeHashType::List | TreeHashType::Vector => {
+                self.values[leaf_index].tree_hash_root()
+            }
+        }
+    }
+}
+
+/// Returns the number of values packed in a leaf of the tree of a list of `T`.
+fn packing_factor<T: TreeHash>() -> usize {
+    match T::tree_hash_type() {
+        TreeHashType::Basic => T::tree_hash_packing_factor(),
+        TreeHashType::Container | TreeHashType::List | TreeHashType::Vector => 1,
+    }
+}
+
+type ChunkIter<'a, T> = FlatMap<
+    slice::Iter<'a, Arc<Chunk<T>>>,
+    slice::Iter<'a, T>,
+    fn(&'a Arc<Chunk<T>>) -> slice::Iter<'a, T>,
+>;
+
+/// An iterator over the values of a [`PersistentList`].
+pub struct Iter<'a, T> {
+    values: ChunkIter<'a, T>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.values.next()?;
+        self.remaining -= 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let value = self.values.next_back()?;
+        self.remaining -= 1;
+        Some(value)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T, N: Unsigned, L> PersistentList<T, N, L> {
+    /// Returns a list of `values`, or an error if there are more than `N` of them.
+    pub fn new(values: Vec<T>) -> Result<Self, Error> {
+        if values.len() > N::to_usize() {
+            return Err(Error::OutOfBounds {
+                i: values.len(),
+                len: N::to_usize(),
+            });
+        }
+        let len = values.len();
+        let mut values = values.into_iter();
+        let chunks = (0..len.div_ceil(CHUNK_LENGTH))
+            .map(|_| Arc::new(Chunk::new(values.by_ref().take(CHUNK_LENGTH).collect())))
+            .collect();
+        Ok(Self {
+            chunks: Arc::new(chunks),
+            len,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.chunks
+            .get(index / CHUNK_LENGTH)?
+            .values
+            .get(index % CHUNK_LENGTH)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            values: self.chunks.iter().flat_map(
+                (|chunk| chunk.values.iter()) as fn(&Arc<Chunk<T>>) -> slice::Iter<'_, T>,
+            ),
+            remaining: self.len,
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: Clone, N: Unsigned, L> PersistentList<T, N, L> {
+    /// Returns the value at `index` for mutation, copying its chunk if it is shared.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+        let chunk = Arc::make_mut(&mut self.chunks).get_mut(index / CHUNK_LENGTH)?;
+        Arc::make_mut(chunk).get_mut(index % CHUNK_LENGTH)
+    }
+
+    /// Appends `value`, or returns an error if the list already has `N` values.
+    pub fn push(&mut self, value: T) -> Result<(), Error> {
+        if self.len >= N::to_usize() {
+            return Err(Error::OutOfBounds {
+                i: self.len + 1,
+                len: N::to_usize(),
+            });
+        }
+        let chunks = Arc::make_mut(&mut self.chunks);
+        match chunks.last_mut() {
+            Some(chunk) if chunk.values.len() < CHUNK_LENGTH => Arc::make_mut(chunk).push(value),
+            _ => {
+                let mut values = Vec::with_capacity(CHUNK_LENGTH);
+                values.push(value);
+                chunks.push(Arc::new(Chunk::new(values)));
+            }
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Iterates over the values for mutation. This copies every shared chunk and rehashes every
+    /// value, so mutations of a few values should go through [`Self::get_mut`] instead.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        Arc::make_mut(&mut self.chunks)
+            .iter_mut()
+            .flat_map(|chunk| {
+                let chunk = Arc::make_mut(chunk);
+                *chunk.hashes_mut() = ChunkHashes::default();
+                chunk.values.iter_mut()
+            })
+    }
+}
+
+impl<T, N, L> Clone for PersistentList<T, N, L> {
+    fn clone(&self) -> Self {
+        Self {
+            chunks: self.chunks.clone(),
+            len: self.len,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, N, L> Default for PersistentList<T, N, L> {
+    fn default() -> Self {
+        Self {
+            chunks: Arc::default(),
+            len: 0,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: std::fmt::Debug, N: Unsigned, L> std::fmt::Debug for PersistentList<T, N, L> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, N, L> PartialEq for PersistentList<T, N, L> {
+    fn eq(&self, other: &Self) -> bool {
+        // The chunks shared with a clone are equal without comparing their values
+        self.len == other.len
+            && self
+                .chunks
+                .iter()
+                .zip(other.chunks.iter())
+                .all(|(chunk, other)| Arc::ptr_eq(chunk, other) || chunk.values == other.values)
+    }
+}
+
+impl<T: Eq, N, L> Eq for PersistentList<T, N, L> {}
+
+impl<T, N: Unsigned, L> Index<usize> for PersistentList<T, N, L> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.get(index) {
+            Some(value) => value,
+            None => panic!(
+                "index out of bounds: the len is {} but the index is {index}",
+                self.len
+            ),
+        }
+    }
+}
+
+impl<T: Clone, N: Unsigned, L> IndexMut<usize> for PersistentList<T, N, L> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let len = self.len;
+        match self.get_mut(index) {
+            Some(value) => value,
+            None => panic!("index out of bounds: the len is {len} but the index is {index}"),
+        }
+    }
+}
+
+impl<'a, T, N: Unsigned, L> IntoIterator for &'a PersistentList<T, N, L> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, N: Unsigned, L> From<VariableList<T, N>> for PersistentList<T, N, L> {
+    fn from(values: VariableList<T, N>) -> Self {
+        let values = Vec::from(values);
+        let len = values.len();
+        Self::new(values).unwrap_or_else(|_| unreachable!("a list of {len} values fits its limit"))
+    }
+}
+
+/// Truncates `values` to `N` values, like `VariableList::from`.
+impl<T, N: Unsigned, L> From<Vec<T>> for PersistentList<T, N, L> {
+    fn from(values: Vec<T>) -> Self {
+        Self::from(VariableList::from(values))
+    }
+}
+
+impl<T: Clone, N: Unsigned, L> From<PersistentList<T, N, L>> for VariableList<T, N> {
+    fn from(values: PersistentList<T, N, L>) -> Self {
+        VariableList::from(values.to_vec())
+    }
+}
+
+impl<T: Clone, N: Unsigned, L> From<PersistentList<T, N, L>> for Vec<T> {
+    fn from(values: PersistentList<T, N, L>) -> Self {
+        values.to_vec()
+    }
+}
+
+impl<T: Encode, N: Unsigned, L> Encode for PersistentList<T, N, L> {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        if T::is_ssz_fixed_len() {
+            T::ssz_fixed_len() * self.len
+        } else {
+            self.iter()
+                .map(|value| value.ssz_bytes_len())
+                .sum::<usize>()
+                + BYTES_PER_LENGTH_OFFSET * self.len
+        }
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        if T::is_ssz_fixed_len() {
+            buf.reserve(T::ssz_fixed_len() * self.len);
+            for value in self {
+                value.ssz_append(buf);
+            }
+        } else {
+            let mut encoder = SszEncoder::container(buf, self.len * BYTES_PER_LENGTH_OFFSET);
+            for value in self {
+                encoder.append(value);
+            }
+            encoder.finalize();
+        }
+    }
+}
+
+impl<T: Decode, N: Unsigned, L> Decode for PersistentList<T, N, L> {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        VariableList::<T, N>::from_ssz_bytes(bytes).map(Self::from)
+    }
+}
+
+/// Returns the root of the tree of `depth` whose first nodes at `height` are `nodes` and the
+/// others empty subtrees.
+fn merkleize(nodes: &[B256], height: u32, depth: u32) -> B256 {
+    let mut zero_hash = B256::ZERO;
+    for _ in 0..height {
+        zero_hash = B256::from(hash32_concat(zero_hash.as_slice(), zero_hash.as_slice()));
+    }
+    let mut nodes = nodes.to_vec();
+    for _ in height..depth {
+        nodes = nodes
+            .chunks(2)
+            .map(|pair| {
+                let right = pair.get(1).unwrap_or(&zero_hash);
+                B256::from(hash32_concat(pair[0].as_slice(), right.as_slice()))
+            })
+            .collect();
+        zero_hash = B256::from(hash32_concat(zero_hash.as_slice(), zero_hash.as_slice()));
+    }
+    nodes.first().copied().unwrap_or(zero_hash)
+}
+
+impl<T: TreeHash, N: Unsigned, L: Unsigned> TreeHash for PersistentList<T, N, L> {
+    fn tree_hash_type() -> TreeHashType {
+        TreeHashType::List
+    }
+
+    fn tree_hash_packed_encoding(&self) -> PackedEncoding {
+        unreachable!("List should never be packed.")
+    }
+
+    fn tree_hash_packing_factor() -> usize {
+        unreachable!("List should never be packed.")
+    }
+
+    fn tree_hash_root(&self) -> B256 {
+        let packing_factor = packing_factor::<T>();
+        let depth = L::to_u64()
+            .div_ceil(packing_factor as u64)
+            .next_power_of_two()
+            .ilog2();
+        let chunk_depth = (CHUNK_LENGTH / packing_factor).ilog2();
+        let root = if depth >= chunk_depth {
+            let chunk_roots: Vec<B256> = self
+                .chunks
+                .iter()
+                .map(|chunk| chunk.root(chunk_depth))
+                .collect();
+            merkleize(&chunk_roots, chunk_depth, depth)
+        } else {
+            // A list limited to less than a chunk has a single chunk, whose tree is the list's
+            self.chunks
+                .first()
+                .map_or_else(|| merkleize(&[], 0, depth), |chunk| chunk.root(depth))
+        };
+        mix_in_length(&root, self.len)
+    }
+}
+
+impl<T: Serialize, N: Unsigned, L> Serialize for PersistentList<T, N, L> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, T: Deserialize<'de>, N: Unsigned, L> Deserialize<'de> for PersistentList<T, N, L> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        VariableList::<T, N>::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ream_consensus_misc::validator::Validator;
+    use ssz_types::typenum::U8;
+
+    use super::*;
+    use crate::electra::zkvm_types::{ValidatorRegistryLimit, ValidatorRegistryTreeHashLimit};
+
+    type Validators =
+        PersistentList<Validator, ValidatorRegistryLimit, ValidatorRegistryTreeHashLimit>;
+    type Balances = PersistentList<u64, ValidatorRegistryLimit, ValidatorRegistryTreeHashLimit>;
+
+    fn validator(index: u64) -> Validator {
+        Validator {
+            public_key: Default::default(),
+            withdrawal_credentials: B256::repeat_byte(index as u8),
+            effective_balance: 32_000_000_000,
+            slashed: false,
+            activation_eligibility_epoch: index,
+            activation_epoch: 0,
+            exit_epoch: u64::MAX,
+            withdrawable_epoch: u64::MAX,
+        }
+    }
+
+    #[test]
+    fn test_matches_variable_list() -> anyhow::Result<()> {
+        for len in [0, 1, 5, CHUNK_LENGTH, 2 * CHUNK_LENGTH + 3] {
+            let validators: Vec<_> = (0..len as u64).map(validator).collect();
+            let variable_list =
+                VariableList::<Validator, ValidatorRegistryLimit>::from(validators.clone());
+            let list = Validators::from(validators);
+            assert_eq!(list.len(), len);
+            assert_eq!(list.tree_hash_root(), variable_list.tree_hash_root());
+            assert_eq!(list.as_ssz_bytes(), variable_list.as_ssz_bytes());
+            assert_eq!(
+                Validators::from_ssz_bytes(&list.as_ssz_bytes())
+                    .map_err(|err| anyhow::anyhow!("Failed to decode the list {err:?}"))?,
+                list
+            );
+
+            let balances: Vec<u64> = (0..len as u64).collect();
+            let variable_list = VariableList::<u64, ValidatorRegistryLimit>::from(balances.clone());
+            let list = Balances::from(balances);
+            assert_eq!(list.tree_hash_root(), variable_list.tree_hash_root());
+            assert_eq!(list.as_ssz_bytes(), variable_list.as_ssz_bytes());
+            assert_eq!(
+                serde_json::to_string(&list)?,
+                serde_json::to_string(&variable_list)?
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_clone_only_copies_mutated_chunks() {
+        let list = Balances::from((0..3 * CHUNK_LENGTH as u64).collect::<Vec<_>>());
+        let mut clone = list.clone();
+        assert!(Arc::ptr_eq(&list.chunks, &clone.chunks));
+
+        clone[CHUNK_LENGTH + 1] += 1;
+        assert_eq!(list[CHUNK_LENGTH + 1], CHUNK_LENGTH as u64 + 1);
+        assert_eq!(clone[CHUNK_LENGTH + 1], CHUNK_LENGTH as u64 + 2);
+        assert!(Arc::ptr_eq(&list.chunks[0], &clone.chunks[0]));
+        assert!(!Arc::ptr_eq(&list.chunks[1], &clone.chunks[1]));
+        assert!(Arc::ptr_eq(&list.chunks[2], &clone.chunks[2]));
+        assert_ne!(list, clone);
+    }
+
+    /// Asserts that `list` is hashed like the `VariableList` of its values.
+    fn assert_root_matches_variable_list<T: Clone + TreeHash>(
+        list: &PersistentList<T, ValidatorRegistryLimit, ValidatorRegistryTreeHashLimit>,
+    ) {
+        let values: VariableList<T, ValidatorRegistryLimit> = list.clone().into();
+        assert_eq!(list.tree_hash_root(), values.tree_hash_root());
+    }
+
+    #[test]
+    fn test_tree_hash_root_after_mutations() {
+        let mut validators = Validators::from(
+            (0..2 * CHUNK_LENGTH as u64)
+                .map(validator)
+                .collect::<Vec<_>>(),
+        );
+        let mut balances = Balances::from((0..2 * CHUNK_LENGTH as u64 + 3).collect::<Vec<_>>());
+        let validators_root = validators.tree_hash_root();
+        let balances_root = balances.tree_hash_root();
+
+        // Mutating a clone after its chunks are hashed leaves the roots of the original intact
+        let mut validators_clone = validators.clone();
+        validators_clone[CHUNK_LENGTH + 5].slashed = true;
+        validators_clone
+            .push(validator(2 * CHUNK_LENGTH as u64))
+            .expect("The list is below its limit");
+        let mut balances_clone = balances.clone();
+        balances_clone[3] += 1;
+        balances_clone.push(1).expect("The list is below its limit");
+        assert_root_matches_variable_list(&validators_clone);
+        assert_root_matches_variable_list(&balances_clone);
+        assert_eq!(validators.tree_hash_root(), validators_root);
+        assert_eq!(balances.tree_hash_root(), balances_root);
+
+        validators[0].effective_balance = 0;
+        for balance in balances.iter_mut() {
+            *balance *= 2;
+        }
+        assert_root_matches_variable_list(&validators);
+        assert_root_matches_variable_list(&balances);
+    }
+
+    #[test]
+    fn test_tree_hash_root_below_a_chunk() {
+        for len in [0, 1, 5, 8] {
+            let mut list = PersistentList::<u64, U8>::from((0..len).collect::<Vec<_>>());
+            let values = VariableList::<u64, U8>::from((0..len).collect::<Vec<_>>());
+            assert_eq!(list.tree_hash_root(), values.tree_hash_root());
+
+            if let Some(value) = list.get_mut(0) {
+                *value = 7;
+                let mut values = Vec::from(values);
+                values[0] = 7;
+                assert_eq!(
+                    list.tree_hash_root(),
+                    VariableList::<u64, U8>::from(values).tree_hash_root()
+                );
+            }
+        }
+    }
+}
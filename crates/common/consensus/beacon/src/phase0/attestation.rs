@@ -0,0 +1,15 @@
+use ream_bls::BLSSignature;
+use ream_consensus_misc::attestation_data::AttestationData;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{BitList, typenum::U2048};
+use tree_hash_derive::TreeHash;
+
+/// The attestation of the forks before Electra, whose aggregation bits cover the single
+/// committee at ``data.index``.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct Attestation {
+    pub aggregation_bits: BitList<U2048>,
+    pub data: AttestationData,
+    pub signature: BLSSignature,
+}
@@ -0,0 +1,22 @@
+use ream_bls::BLSSignature;
+use ream_consensus_misc::attestation_data::AttestationData;
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{VariableList, serde_utils::quoted_u64_var_list, typenum::U2048};
+use tree_hash_derive::TreeHash;
+
+/// The indexed attestation of the forks before Electra, whose attesting indices are limited to a
+/// single committee.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct IndexedAttestation {
+    #[serde(with = "quoted_u64_var_list")]
+    pub attesting_indices: VariableList<u64, U2048>,
+    pub data: AttestationData,
+    pub signature: BLSSignature,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct AttesterSlashing {
+    pub attestation_1: IndexedAttestation,
+    pub attestation_2: IndexedAttestation,
+}
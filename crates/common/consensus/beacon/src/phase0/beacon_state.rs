@@ -0,0 +1,69 @@
+use alloy_primitives::B256;
+use ream_consensus_misc::{
+    beacon_block_header::BeaconBlockHeader,
+    checkpoint::Checkpoint,
+    eth_1_data::Eth1Data,
+    fork::Fork,
+    pending_attestation::PendingAttestation,
+    preset::{
+        EpochsPerHistoricalVector, EpochsPerSlashingsVector, MaxPendingAttestations,
+        SlotsPerEth1VotingPeriod, SlotsPerHistoricalRoot,
+    },
+    validator::Validator,
+};
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::{
+    BitVector, FixedVector, VariableList,
+    serde_utils::{quoted_u64_fixed_vec, quoted_u64_var_list},
+    typenum::{U4, U16777216},
+};
+use tree_hash_derive::TreeHash;
+
+use crate::electra::zkvm_types::ValidatorRegistryLimit;
+
+/// The beacon state of the Phase0 fork.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+pub struct BeaconState {
+    // Versioning
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub genesis_time: u64,
+    pub genesis_validators_root: B256,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub slot: u64,
+    pub fork: Fork,
+
+    // History
+    pub latest_block_header: BeaconBlockHeader,
+    pub block_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
+    pub state_roots: FixedVector<B256, SlotsPerHistoricalRoot>,
+    pub historical_roots: VariableList<B256, U16777216>,
+
+    // Eth1
+    pub eth1_data: Eth1Data,
+    pub eth1_data_votes: VariableList<Eth1Data, SlotsPerEth1VotingPeriod>,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub eth1_deposit_index: u64,
+
+    // Registry
+    pub validators: VariableList<Validator, ValidatorRegistryLimit>,
+    #[serde(with = "quoted_u64_var_list")]
+    pub balances: VariableList<u64, ValidatorRegistryLimit>,
+
+    // Randomness
+    pub randao_mixes: FixedVector<B256, EpochsPerHistoricalVector>,
+
+    // Slashings
+    #[serde(with = "quoted_u64_fixed_vec")]
+    pub slashings: FixedVector<u64, EpochsPerSlashingsVector>,
+
+    // Attestations
+    pub previous_epoch_attestations: VariableList<PendingAttestation, MaxPendingAttestations>,
+    pub current_epoch_attestations: VariableList<PendingAttestation, MaxPendingAttestations>,
+
+    // Finality
+    pub justification_bits: BitVector<U4>,
+    pub previous_justified_checkpoint: Checkpoint,
+    pub current_justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+}
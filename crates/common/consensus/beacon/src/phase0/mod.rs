@@ -0,0 +1,3 @@
+pub mod attestation;
+pub mod attester_slashing;
+pub mod beacon_state;
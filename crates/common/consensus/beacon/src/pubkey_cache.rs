@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use ream_bls::PublicKey;
+
+use crate::electra::beacon_state::BeaconState;
+
+/// The index of each validator in the registry, by public key.
+///
+/// Validators are only ever appended to the registry and their public keys never change, so the
+/// cache is kept up to date by indexing the validators appended since the last update. A state
+/// whose registry diverged from the cached one at the last cached index rebuilds the cache, but
+/// the registries of two forks which appended different validators in the same number are not
+/// told apart: a cache shared by the states of several forks must be rebuilt when switching forks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PubkeyCache {
+    indices: HashMap<PublicKey, u64>,
+}
+
+impl PubkeyCache {
+    pub fn new(state: &BeaconState) -> Self {
+        let mut cache = Self::default();
+        cache.update(state);
+        cache
+    }
+
+    /// Indexes the validators of `state` which are not cached yet.
+    pub fn update(&mut self, state: &BeaconState) {
+        if !self.is_consistent_with(state) {
+            self.indices.clear();
+        }
+
+        for (index, validator) in state.validators.iter().enumerate().skip(self.indices.len()) {
+            self.indices
+                .insert(validator.public_key.clone(), index as u64);
+        }
+    }
+
+    /// Returns the index of the validator with `public_key` in `state`.
+    pub fn get_validator_index(&self, state: &BeaconState, public_key: &PublicKey) -> Option<u64> {
+        self.indices.get(public_key).copied().filter(|&index| {
+            state
+                .validators
+                .get(index as usize)
+                .is_some_and(|validator| validator.public_key == *public_key)
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Returns whether the last validator cached, or the last validator of `state` if it has
+    /// fewer, is at the same index in the cache and in `state`.
+    fn is_consistent_with(&self, state: &BeaconState) -> bool {
+        let Some(last_index) = self
+            .indices
+            .len()
+            .min(state.validators.len())
+            .checked_sub(1)
+        else {
+            return true;
+        };
+        state.validators.get(last_index).is_some_and(|validator| {
+            self.indices.get(&validator.public_key) == Some(&(last_index as u64))
+        })
+    }
+}
@@ -0,0 +1,110 @@
+//! Quoted integer serializers for the lists of the beacon state, which the beacon API encodes
+//! as lists of decimal strings.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use ssz_types::{VariableList, serde_utils::quoted_u64_var_list};
+
+use crate::{
+    electra::zkvm_types::{ValidatorRegistryLimit, ValidatorRegistryTreeHashLimit},
+    persistent_list::PersistentList,
+};
+
+/// Lists of integers of each validator, like the balances and participation of the beacon state.
+type ValidatorU8List = PersistentList<u8, ValidatorRegistryLimit, ValidatorRegistryTreeHashLimit>;
+type ValidatorU64List = PersistentList<u64, ValidatorRegistryLimit, ValidatorRegistryTreeHashLimit>;
+
+pub mod quoted_u8_var_list {
+    use super::*;
+
+    pub fn serialize<S>(
+        value: &VariableList<u8, ValidatorRegistryLimit>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let string_vec: Vec<String> = value.iter().map(|v| v.to_string()).collect();
+        string_vec.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> Result<VariableList<u8, ValidatorRegistryLimit>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let string_vec: Vec<String> = Vec::deserialize(deserializer)?;
+        let bytes = string_vec
+            .into_iter()
+            .map(|s| s.parse::<u8>().map_err(serde::de::Error::custom))
+            .collect::<Result<Vec<_>, _>>()?;
+        VariableList::new(bytes).map_err(|err| {
+            serde::de::Error::custom(format!("Cannot create VariableList from bytes: {err:?}"))
+        })
+    }
+}
+
+pub mod quoted_u8_persistent_list {
+    use super::*;
+
+    pub fn serialize<S>(value: &ValidatorU8List, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(value.iter().map(|value| value.to_string()))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ValidatorU8List, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let participation = quoted_u8_var_list::deserialize(deserializer)?;
+        Ok(PersistentList::from(participation))
+    }
+}
+
+pub mod quoted_u64_persistent_list {
+    use super::*;
+
+    pub fn serialize<S>(value: &ValidatorU64List, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(value.iter().map(|value| value.to_string()))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ValidatorU64List, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let balances: VariableList<u64, ValidatorRegistryLimit> =
+            quoted_u64_var_list::deserialize(deserializer)?;
+        Ok(PersistentList::from(balances))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Participation {
+        #[serde(with = "quoted_u8_var_list")]
+        participation: VariableList<u8, ValidatorRegistryLimit>,
+        #[serde(with = "quoted_u8_persistent_list")]
+        current_participation: ValidatorU8List,
+        #[serde(with = "quoted_u64_persistent_list")]
+        balances: ValidatorU64List,
+    }
+
+    #[test]
+    fn test_quoted_lists_round_trip() -> anyhow::Result<()> {
+        let json = r#"{"participation":["0","7"],"current_participation":["3"],"balances":["32000000000","0"]}"#;
+        let participation: Participation = serde_json::from_str(json)?;
+        assert_eq!(participation.participation.to_vec(), vec![0, 7]);
+        assert_eq!(participation.current_participation.to_vec(), vec![3]);
+        assert_eq!(participation.balances.to_vec(), vec![32000000000, 0]);
+        assert_eq!(serde_json::to_string(&participation)?, json);
+        Ok(())
+    }
+}
@@ -1,13 +1,52 @@
+use anyhow::anyhow;
 use ream_bls::BLSSignature;
 use ream_consensus_misc::attestation_data::AttestationData;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
+use ssz_types::{BitList, BitVector};
 use tree_hash_derive::TreeHash;
 
+use crate::attestation::Attestation;
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
 pub struct SingleAttestation {
+    #[serde(with = "serde_utils::quoted_u64")]
     pub committee_index: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
     pub attester_index: u64,
     pub data: AttestationData,
     pub signature: BLSSignature,
 }
+
+impl SingleAttestation {
+    /// Returns the attestation whose only attester is the one of this attestation in
+    /// ``committee``, the beacon committee at ``committee_index``.
+    pub fn to_attestation(&self, committee: &[u64]) -> anyhow::Result<Attestation> {
+        let position = committee
+            .iter()
+            .position(|&index| index == self.attester_index)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Attester {} is not a member of the committee",
+                    self.attester_index
+                )
+            })?;
+
+        let mut aggregation_bits = BitList::with_capacity(committee.len())
+            .map_err(|err| anyhow!("Failed to create aggregation bits: {err:?}"))?;
+        aggregation_bits
+            .set(position, true)
+            .map_err(|err| anyhow!("Failed to set aggregation bit: {err:?}"))?;
+        let mut committee_bits = BitVector::new();
+        committee_bits
+            .set(self.committee_index as usize, true)
+            .map_err(|err| anyhow!("Failed to set committee bit: {err:?}"))?;
+
+        Ok(Attestation {
+            aggregation_bits,
+            data: self.data.clone(),
+            signature: self.signature.clone(),
+            committee_bits,
+        })
+    }
+}
@@ -1,13 +1,14 @@
 use ream_bls::BLSSignature;
+use ream_consensus_misc::preset::SyncCommitteeSize;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use ssz_types::{BitVector, typenum::U512};
+use ssz_types::BitVector;
 use tree_hash_derive::TreeHash;
 
 #[derive(
     Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash, Default,
 )]
 pub struct SyncAggregate {
-    pub sync_committee_bits: BitVector<U512>,
+    pub sync_committee_bits: BitVector<SyncCommitteeSize>,
     pub sync_committee_signature: BLSSignature,
 }
@@ -1,13 +1,16 @@
 use ream_bls::PublicKey;
+use ream_consensus_misc::preset::SyncCommitteeSize;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use ssz_types::{FixedVector, typenum::U512};
+use ssz_types::FixedVector;
 use tree_hash_derive::TreeHash;
 
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+#[derive(
+    Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash,
+)]
 pub struct SyncCommittee {
     #[serde(rename = "pubkeys")]
-    pub public_keys: FixedVector<PublicKey, U512>,
+    pub public_keys: FixedVector<PublicKey, SyncCommitteeSize>,
     #[serde(rename = "aggregate_pubkey")]
     pub aggregate_public_key: PublicKey,
 }
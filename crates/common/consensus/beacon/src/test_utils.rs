@@ -0,0 +1,255 @@
+use std::{str::FromStr, sync::Arc};
+
+use alloy_primitives::B256;
+use anyhow::bail;
+use ream_bls::{PrivateKey, PublicKey, traits::Signable};
+use ream_consensus_misc::{
+    attestation_data::AttestationData,
+    checkpoint::Checkpoint,
+    constants::{
+        DOMAIN_BEACON_ATTESTER, FAR_FUTURE_EPOCH, SLOTS_PER_EPOCH, genesis_validators_root,
+    },
+    fork::Fork,
+    misc::compute_signing_root,
+    validator::Validator,
+};
+use ream_network_spec::networks::beacon_network_spec;
+use tree_hash::TreeHash;
+
+use crate::{
+    attestation::Attestation,
+    deneb::beacon_state::BeaconState as DenebBeaconState,
+    electra::{beacon_block::BeaconBlock, beacon_state::BeaconState},
+    single_attestation::SingleAttestation,
+    sync_committee::SyncCommittee,
+};
+
+/// The public key of the secret key 1, the generator of G1.
+pub const GENERATOR_PUBLIC_KEY: &str = "0x97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+
+/// The balance of the validators added by [`push_validator`].
+pub const VALIDATOR_BALANCE: u64 = 32_000_000_000;
+
+/// Returns the secret key 1, whose public key is [`GENERATOR_PUBLIC_KEY`].
+pub fn generator_private_key() -> PrivateKey {
+    // The zkcrypto backend reads secret keys as little-endian scalars
+    let mut secret_key = B256::ZERO;
+    secret_key[0] = 1;
+    PrivateKey { inner: secret_key }
+}
+
+pub fn generator_public_key() -> anyhow::Result<PublicKey> {
+    Ok(PublicKey::from_str(GENERATOR_PUBLIC_KEY)?)
+}
+
+/// Returns an active validator with `public_key` and the maximum effective balance.
+pub fn active_validator(public_key: PublicKey) -> Validator {
+    Validator {
+        public_key,
+        withdrawal_credentials: B256::ZERO,
+        effective_balance: VALIDATOR_BALANCE,
+        slashed: false,
+        activation_eligibility_epoch: 0,
+        activation_epoch: 0,
+        exit_epoch: FAR_FUTURE_EPOCH,
+        withdrawable_epoch: FAR_FUTURE_EPOCH,
+    }
+}
+
+/// Returns an Electra state at genesis without validators, whose other fields are empty.
+pub fn genesis_state() -> BeaconState {
+    let sync_committee = Arc::new(SyncCommittee {
+        public_keys: Default::default(),
+        aggregate_public_key: Default::default(),
+    });
+    BeaconState {
+        genesis_time: 0,
+        genesis_validators_root: B256::ZERO,
+        slot: 0,
+        fork: Fork {
+            previous_version: Default::default(),
+            current_version: Default::default(),
+            epoch: 0,
+        },
+        latest_block_header: Default::default(),
+        block_roots: Default::default(),
+        state_roots: Default::default(),
+        historical_roots: Default::default(),
+        eth1_data: Default::default(),
+        eth1_data_votes: Default::default(),
+        eth1_deposit_index: 0,
+        validators: Default::default(),
+        balances: Default::default(),
+        randao_mixes: Default::default(),
+        slashings: Default::default(),
+        previous_epoch_participation: Default::default(),
+        current_epoch_participation: Default::default(),
+        justification_bits: Default::default(),
+        previous_justified_checkpoint: Default::default(),
+        current_justified_checkpoint: Default::default(),
+        finalized_checkpoint: Default::default(),
+        inactivity_scores: Default::default(),
+        current_sync_committee: sync_committee.clone(),
+        next_sync_committee: sync_committee,
+        latest_execution_payload_header: Default::default(),
+        next_withdrawal_index: 0,
+        next_withdrawal_validator_index: 0,
+        historical_summaries: Default::default(),
+        deposit_requests_start_index: 0,
+        deposit_balance_to_consume: 0,
+        exit_balance_to_consume: 0,
+        earliest_exit_epoch: 0,
+        consolidation_balance_to_consume: 0,
+        earliest_consolidation_epoch: 0,
+        pending_deposits: Default::default(),
+        pending_partial_withdrawals: Default::default(),
+        pending_consolidations: Default::default(),
+    }
+}
+
+/// Appends `validator` with `balance` to the registry of `state`, along with its participation
+/// flags and inactivity score.
+pub fn push_validator(
+    state: &mut BeaconState,
+    validator: Validator,
+    balance: u64,
+) -> anyhow::Result<()> {
+    let push_error = |err| anyhow::anyhow!("Failed to push to a list {err:?}");
+    state.validators.push(validator).map_err(push_error)?;
+    state.balances.push(balance).map_err(push_error)?;
+    state
+        .previous_epoch_participation
+        .push(0)
+        .map_err(push_error)?;
+    state
+        .current_epoch_participation
+        .push(0)
+        .map_err(push_error)?;
+    state.inactivity_scores.push(0).map_err(push_error)?;
+    Ok(())
+}
+
+/// Returns an Electra state at genesis whose only validator has the secret key 1.
+pub fn single_validator_state() -> anyhow::Result<BeaconState> {
+    let mut state = genesis_state();
+    push_validator(
+        &mut state,
+        active_validator(generator_public_key()?),
+        VALIDATOR_BALANCE,
+    )?;
+    Ok(state)
+}
+
+/// Returns `state` as the genesis state of an Electra chain of the test network, along with the
+/// block it is the post-state of.
+pub fn electra_anchor(mut state: BeaconState) -> (BeaconState, BeaconBlock) {
+    let electra_fork_version = beacon_network_spec().electra_fork_version;
+    state.genesis_validators_root = genesis_validators_root();
+    state.fork = Fork {
+        previous_version: electra_fork_version,
+        current_version: electra_fork_version,
+        epoch: 0,
+    };
+    let block = BeaconBlock {
+        state_root: state.tree_hash_root(),
+        ..Default::default()
+    };
+    (state, block)
+}
+
+/// Returns an Electra anchor at the minimum genesis time of the test network whose only
+/// validator has the secret key 1.
+pub fn single_validator_anchor() -> anyhow::Result<(BeaconState, BeaconBlock)> {
+    let mut state = single_validator_state()?;
+    state.genesis_time = beacon_network_spec().min_genesis_time;
+    Ok(electra_anchor(state))
+}
+
+/// Returns the slot of the first epoch at which the only validator of `state` attests.
+pub fn attester_slot(state: &BeaconState) -> anyhow::Result<u64> {
+    for slot in 0..SLOTS_PER_EPOCH {
+        if !state.get_beacon_committee(slot, 0)?.is_empty() {
+            return Ok(slot);
+        }
+    }
+    bail!("The validator has no attestation duty in the first epoch")
+}
+
+/// Returns the single attestation of the only validator of `state` at `slot` for
+/// `beacon_block_root`, with `target_root` as the target of the first epoch and `index` as the
+/// committee index of its data.
+pub fn single_attestation(
+    state: &BeaconState,
+    slot: u64,
+    beacon_block_root: B256,
+    target_root: B256,
+    index: u64,
+) -> anyhow::Result<SingleAttestation> {
+    let checkpoint = Checkpoint {
+        epoch: 0,
+        root: target_root,
+    };
+    let data = AttestationData {
+        slot,
+        index,
+        beacon_block_root,
+        source: checkpoint,
+        target: checkpoint,
+    };
+    let signing_root = compute_signing_root(
+        data.clone(),
+        state.get_domain(DOMAIN_BEACON_ATTESTER, Some(0)),
+    );
+    Ok(SingleAttestation {
+        committee_index: 0,
+        attester_index: 0,
+        data,
+        signature: generator_private_key().sign(signing_root.as_ref())?,
+    })
+}
+
+/// Returns the attestation of the only validator of `state` at `slot` for `beacon_block_root`,
+/// with `target_root` as the target of the first epoch.
+pub fn attestation(
+    state: &BeaconState,
+    slot: u64,
+    beacon_block_root: B256,
+    target_root: B256,
+) -> anyhow::Result<Attestation> {
+    single_attestation(state, slot, beacon_block_root, target_root, 0)?
+        .to_attestation(&state.get_beacon_committee(slot, 0)?)
+}
+
+/// Returns the Deneb state with the fields of the Electra `state` which Deneb has.
+pub fn to_deneb_state(state: BeaconState) -> DenebBeaconState {
+    DenebBeaconState {
+        genesis_time: state.genesis_time,
+        genesis_validators_root: state.genesis_validators_root,
+        slot: state.slot,
+        fork: state.fork,
+        latest_block_header: state.latest_block_header,
+        block_roots: state.block_roots,
+        state_roots: state.state_roots,
+        historical_roots: state.historical_roots,
+        eth1_data: state.eth1_data,
+        eth1_data_votes: state.eth1_data_votes.into(),
+        eth1_deposit_index: state.eth1_deposit_index,
+        validators: state.validators.into(),
+        balances: state.balances.into(),
+        randao_mixes: state.randao_mixes,
+        slashings: state.slashings,
+        previous_epoch_participation: state.previous_epoch_participation.into(),
+        current_epoch_participation: state.current_epoch_participation.into(),
+        justification_bits: state.justification_bits,
+        previous_justified_checkpoint: state.previous_justified_checkpoint,
+        current_justified_checkpoint: state.current_justified_checkpoint,
+        finalized_checkpoint: state.finalized_checkpoint,
+        inactivity_scores: state.inactivity_scores.into(),
+        current_sync_committee: state.current_sync_committee,
+        next_sync_committee: state.next_sync_committee,
+        latest_execution_payload_header: state.latest_execution_payload_header,
+        next_withdrawal_index: state.next_withdrawal_index,
+        next_withdrawal_validator_index: state.next_withdrawal_validator_index,
+        historical_summaries: state.historical_summaries.into(),
+    }
+}
@@ -0,0 +1,374 @@
+use std::sync::Arc;
+
+use alloy_primitives::B256;
+use ream_consensus_misc::{
+    beacon_block_header::BeaconBlockHeader,
+    eth_1_data::Eth1Data,
+    fork::Fork,
+    hashing::hash32_concat,
+    preset::{EpochsPerHistoricalVector, EpochsPerSlashingsVector, SlotsPerHistoricalRoot},
+};
+use ssz_types::typenum::Unsigned;
+use tree_hash::TreeHash;
+
+use crate::{
+    electra::{beacon_state::BeaconState, execution_payload_header::ExecutionPayloadHeader},
+    sync_committee::SyncCommittee,
+};
+
+/// The number of fields of the beacon state, whose roots are the leaves of its tree.
+const BEACON_STATE_FIELD_COUNT: usize = 37;
+const BEACON_STATE_DEPTH: usize = 6;
+
+// The depths of the trees of the vector fields and the frozen historical roots, from their
+// limits in chunks. The lengths switched by the "minimal" feature are taken from the preset.
+const ROOTS_VECTOR_DEPTH: usize = chunk_depth(SlotsPerHistoricalRoot::USIZE);
+const HISTORICAL_ROOTS_DEPTH: usize = 24;
+const RANDAO_MIXES_DEPTH: usize = chunk_depth(EpochsPerHistoricalVector::USIZE);
+const SLASHINGS_DEPTH: usize = chunk_depth(EpochsPerSlashingsVector::USIZE.div_ceil(4));
+
+/// Returns the depth of the tree which merkleizes `chunk_count` chunks.
+const fn chunk_depth(chunk_count: usize) -> usize {
+    chunk_count.next_power_of_two().ilog2() as usize
+}
+
+/// Returns the roots of the empty trees of each depth up to `depth`.
+fn zero_hashes(depth: usize) -> Vec<B256> {
+    let mut zero_hashes = vec![B256::ZERO];
+    for level in 0..depth {
+        zero_hashes.push(B256::from(hash32_concat(
+            zero_hashes[level].as_slice(),
+            zero_hashes[level].as_slice(),
+        )));
+    }
+    zero_hashes
+}
+
+fn mix_in_length(root: B256, length: usize) -> B256 {
+    let mut length_chunk = [0; 32];
+    length_chunk[..8].copy_from_slice(&(length as u64).to_le_bytes());
+    B256::from(hash32_concat(root.as_slice(), &length_chunk))
+}
+
+/// Packs `values` into chunks of four little-endian values.
+fn pack_u64<'a>(values: impl IntoIterator<Item = &'a u64>) -> impl Iterator<Item = B256> {
+    let mut values = values.into_iter().peekable();
+    std::iter::from_fn(move || {
+        values.peek()?;
+        let mut chunk = B256::ZERO;
+        for (bytes, value) in chunk.chunks_mut(8).zip(values.by_ref()) {
+            bytes.copy_from_slice(&value.to_le_bytes());
+        }
+        Some(chunk)
+    })
+}
+
+/// A merkle tree of a fixed depth which keeps its nodes, so that setting its leaves only
+/// rehashes the branches of the leaves which changed.
+#[derive(Debug, Clone)]
+struct MerkleCache {
+    /// The nodes of each level from the leaves up, without the empty subtrees right of the
+    /// leaves.
+    levels: Vec<Vec<B256>>,
+    zero_hashes: Vec<B256>,
+}
+
+impl MerkleCache {
+    fn new(depth: usize) -> Self {
+        Self {
+            levels: vec![vec![]; depth + 1],
+            zero_hashes: zero_hashes(depth),
+        }
+    }
+
+    fn depth(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    /// Sets the leaves of the tree to `leaves` and returns its root.
+    fn root(&mut self, leaves: impl Iterator<Item = B256>) -> B256 {
+        let mut dirty = vec![];
+        let mut leaf_count = 0;
+        for (index, leaf) in leaves.enumerate() {
+            leaf_count = index + 1;
+            match self.levels[0].get_mut(index) {
+                Some(cached) if *cached == leaf => {}
+                Some(cached) => {
+                    *cached = leaf;
+                    dirty.push(index);
+                }
+                None => {
+                    self.levels[0].push(leaf);
+                    dirty.push(index);
+                }
+            }
+        }
+        // The last remaining leaf now has an empty sibling subtree
+        if leaf_count < self.levels[0].len() {
+            self.levels[0].truncate(leaf_count);
+            if let Some(last_index) = leaf_count.checked_sub(1) {
+                dirty.push(last_index);
+            }
+        }
+
+        for level in 0..self.depth() {
+            let (children, parents) = self.levels.split_at_mut(level + 1);
+            let (children, parents) = (&children[level], &mut parents[0]);
+            let parent_count = children.len().div_ceil(2);
+            parents.resize(parent_count, B256::ZERO);
+
+            dirty.iter_mut().for_each(|index| *index /= 2);
+            dirty.dedup();
+            for &index in &dirty {
+                let left = children[2 * index];
+                let right = children
+                    .get(2 * index + 1)
+                    .copied()
+                    .unwrap_or(self.zero_hashes[level]);
+                parents[index] = B256::from(hash32_concat(left.as_slice(), right.as_slice()));
+            }
+        }
+
+        self.levels[self.depth()]
+            .first()
+            .copied()
+            .unwrap_or(self.zero_hashes[self.depth()])
+    }
+}
+
+/// The root of a field, rehashed when the field differs from the value it was last hashed for.
+#[derive(Debug, Clone)]
+struct FieldCache<T> {
+    value: Option<T>,
+    root: B256,
+}
+
+impl<T> Default for FieldCache<T> {
+    fn default() -> Self {
+        Self {
+            value: None,
+            root: B256::ZERO,
+        }
+    }
+}
+
+impl<T: Clone + PartialEq + TreeHash> FieldCache<T> {
+    fn root(&mut self, value: &T) -> B256 {
+        if self.value.as_ref() != Some(value) {
+            self.root = value.tree_hash_root();
+            self.value = Some(value.clone());
+        }
+        self.root
+    }
+}
+
+/// Memoizes the roots of the fields of a beacon state, so that hashing a state again after a
+/// few of its fields changed, as every slot does, only rehashes what changed.
+///
+/// The lists of the state are `PersistentList`s, which keep the trees of their chunks and track
+/// the values mutated since, so for instance changing a few balances only rehashes their chunks
+/// of the balances tree. The cache keeps a copy of the other small fields, and the leaves of the
+/// vectors, and finds what changed by comparing the state with them, which is far cheaper than
+/// hashing. A cache may be used with any state, but only saves work on states close to the last
+/// one it hashed.
+#[derive(Debug, Clone)]
+pub struct BeaconStateTreeHashCache {
+    fork: FieldCache<Fork>,
+    latest_block_header: FieldCache<BeaconBlockHeader>,
+    block_roots: MerkleCache,
+    state_roots: MerkleCache,
+    historical_roots: MerkleCache,
+    eth1_data: FieldCache<Eth1Data>,
+    randao_mixes: MerkleCache,
+    slashings: MerkleCache,
+    // The sync committees are compared by pointer before their values
+    current_sync_committee: FieldCache<Arc<SyncCommittee>>,
+    next_sync_committee: FieldCache<Arc<SyncCommittee>>,
+    latest_execution_payload_header: FieldCache<ExecutionPayloadHeader>,
+    fields: MerkleCache,
+}
+
+impl Default for BeaconStateTreeHashCache {
+    fn default() -> Self {
+        Self {
+            fork: FieldCache::default(),
+            latest_block_header: FieldCache::default(),
+            block_roots: MerkleCache::new(ROOTS_VECTOR_DEPTH),
+            state_roots: MerkleCache::new(ROOTS_VECTOR_DEPTH),
+            historical_roots: MerkleCache::new(HISTORICAL_ROOTS_DEPTH),
+            eth1_data: FieldCache::default(),
+            randao_mixes: MerkleCache::new(RANDAO_MIXES_DEPTH),
+            slashings: MerkleCache::new(SLASHINGS_DEPTH),
+            current_sync_committee: FieldCache::default(),
+            next_sync_committee: FieldCache::default(),
+            latest_execution_payload_header: FieldCache::default(),
+            fields: MerkleCache::new(BEACON_STATE_DEPTH),
+        }
+    }
+}
+
+impl BeaconStateTreeHashCache {
+    /// Returns the root of `state`, equal to `state.tree_hash_root()`.
+    pub fn tree_hash_root(&mut self, state: &BeaconState) -> B256 {
+        let field_roots: [B256; BEACON_STATE_FIELD_COUNT] = [
+            state.genesis_time.tree_hash_root(),
+            state.genesis_validators_root.tree_hash_root(),
+            state.slot.tree_hash_root(),
+            self.fork.root(&state.fork),
+            self.latest_block_header.root(&state.latest_block_header),
+            self.block_roots.root(state.block_roots.iter().copied()),
+            self.state_roots.root(state.state_roots.iter().copied()),
+            mix_in_length(
+                self.historical_roots
+                    .root(state.historical_roots.iter().copied()),
+                state.historical_roots.len(),
+            ),
+            self.eth1_data.root(&state.eth1_data),
+            state.eth1_data_votes.tree_hash_root(),
+            state.eth1_deposit_index.tree_hash_root(),
+            state.validators.tree_hash_root(),
+            state.balances.tree_hash_root(),
+            self.randao_mixes.root(state.randao_mixes.iter().copied()),
+            self.slashings.root(pack_u64(&state.slashings)),
+            state.previous_epoch_participation.tree_hash_root(),
+            state.current_epoch_participation.tree_hash_root(),
+            state.justification_bits.tree_hash_root(),
+            state.previous_justified_checkpoint.tree_hash_root(),
+            state.current_justified_checkpoint.tree_hash_root(),
+            state.finalized_checkpoint.tree_hash_root(),
+            state.inactivity_scores.tree_hash_root(),
+            self.current_sync_committee
+                .root(&state.current_sync_committee),
+            self.next_sync_committee.root(&state.next_sync_committee),
+            self.latest_execution_payload_header
+                .root(&state.latest_execution_payload_header),
+            state.next_withdrawal_index.tree_hash_root(),
+            state.next_withdrawal_validator_index.tree_hash_root(),
+            state.historical_summaries.tree_hash_root(),
+            state.deposit_requests_start_index.tree_hash_root(),
+            state.deposit_balance_to_consume.tree_hash_root(),
+            state.exit_balance_to_consume.tree_hash_root(),
+            state.earliest_exit_epoch.tree_hash_root(),
+            state.consolidation_balance_to_consume.tree_hash_root(),
+            state.earliest_consolidation_epoch.tree_hash_root(),
+            state.pending_deposits.tree_hash_root(),
+            state.pending_partial_withdrawals.tree_hash_root(),
+            state.pending_consolidations.tree_hash_root(),
+        ];
+        self.fields.root(field_roots.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::aliases::B32;
+    use ream_consensus_misc::{checkpoint::Checkpoint, validator::Validator};
+
+    use super::*;
+    use crate::{
+        pending_deposit::PendingDeposit,
+        test_utils::{VALIDATOR_BALANCE, active_validator, genesis_state, push_validator},
+    };
+
+    fn test_state() -> anyhow::Result<BeaconState> {
+        let mut state = genesis_state();
+        state.genesis_time = 1;
+        state.genesis_validators_root = B256::repeat_byte(1);
+        state.slot = 100;
+        state.fork = Fork {
+            previous_version: B32::repeat_byte(2),
+            current_version: B32::repeat_byte(3),
+            epoch: 4,
+        };
+        state.eth1_deposit_index = 5;
+        state.next_withdrawal_index = 6;
+        state.next_withdrawal_validator_index = 7;
+        state.deposit_requests_start_index = 8;
+        state.deposit_balance_to_consume = 9;
+        state.exit_balance_to_consume = 10;
+        state.earliest_exit_epoch = 11;
+        state.consolidation_balance_to_consume = 12;
+        state.earliest_consolidation_epoch = 13;
+        for index in 0..37 {
+            push_test_validator(&mut state, index)?;
+        }
+        Ok(state)
+    }
+
+    /// Appends a validator whose fields and balance differ from the others by their `index`.
+    fn push_test_validator(state: &mut BeaconState, index: u64) -> anyhow::Result<()> {
+        push_validator(
+            state,
+            Validator {
+                withdrawal_credentials: B256::repeat_byte(index as u8),
+                ..active_validator(Default::default())
+            },
+            VALIDATOR_BALANCE + index,
+        )?;
+        let index = index as usize;
+        state.previous_epoch_participation[index] = index as u8 % 8;
+        state.inactivity_scores[index] = index as u64;
+        Ok(())
+    }
+
+    #[test]
+    fn test_root_matches_tree_hash_root_as_the_state_changes() -> anyhow::Result<()> {
+        let mut state = test_state()?;
+        let mut cache = BeaconStateTreeHashCache::default();
+        assert_eq!(cache.tree_hash_root(&state), state.tree_hash_root());
+
+        // A slot without a block
+        let root_index = state.slot as usize % SlotsPerHistoricalRoot::USIZE;
+        state.state_roots[root_index] = B256::repeat_byte(14);
+        state.block_roots[root_index] = B256::repeat_byte(15);
+        state.slot += 1;
+        assert_eq!(cache.tree_hash_root(&state), state.tree_hash_root());
+
+        // A block changing a few balances, appending validators and rotating participation
+        state.balances[3] += 1;
+        state.validators[5].slashed = true;
+        for index in 37..42 {
+            push_test_validator(&mut state, index)?;
+        }
+        state.previous_epoch_participation = state.current_epoch_participation.clone();
+        state.finalized_checkpoint = Checkpoint {
+            epoch: 2,
+            root: B256::repeat_byte(16),
+        };
+        state.latest_execution_payload_header.block_number = 17;
+        state
+            .eth1_data_votes
+            .push(Eth1Data {
+                deposit_count: 18,
+                ..Default::default()
+            })
+            .map_err(|err| anyhow::anyhow!("Failed to push an eth1 data vote {err:?}"))?;
+        state
+            .pending_deposits
+            .push(PendingDeposit {
+                public_key: Default::default(),
+                withdrawal_credentials: B256::repeat_byte(19),
+                amount: VALIDATOR_BALANCE,
+                signature: Default::default(),
+                slot: 20,
+            })
+            .map_err(|err| anyhow::anyhow!("Failed to push a pending deposit {err:?}"))?;
+        assert_eq!(cache.tree_hash_root(&state), state.tree_hash_root());
+
+        // The original of a state mutated after it was hashed keeps its root
+        let previous_state = state.clone();
+        let previous_root = state.tree_hash_root();
+        state.balances[4] -= 1;
+        state.current_epoch_participation[6] = 7;
+        state.inactivity_scores[8] += 1;
+        assert_eq!(cache.tree_hash_root(&state), state.tree_hash_root());
+        assert_eq!(cache.tree_hash_root(&previous_state), previous_root);
+
+        // Lists which shrink
+        state.eth1_data_votes = Default::default();
+        let inactivity_scores = Vec::from(state.inactivity_scores.clone());
+        state.inactivity_scores = inactivity_scores[..10].to_vec().into();
+        assert_eq!(cache.tree_hash_root(&state), state.tree_hash_root());
+        Ok(())
+    }
+}
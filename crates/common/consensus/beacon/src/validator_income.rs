@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+
+/// The balance changes of a validator during an epoch, by component.
+///
+/// The attestation components are applied at the end of the epoch and reward the participation
+/// of the previous epoch, the sync committee and proposer components sum the blocks of the epoch.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Encode, Decode)]
+pub struct ValidatorIncome {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub source_reward: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub source_penalty: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub target_reward: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub target_penalty: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub head_reward: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub inactivity_penalty: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub sync_committee_reward: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub sync_committee_penalty: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub proposer_reward: u64,
+    /// The initial penalty of a slashing included in a block of the epoch.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub slashing_penalty: u64,
+    /// The penalty proportional to the total slashed balance, applied at the end of the epoch
+    /// halfway through the withdrawability delay of a slashed validator.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub proportional_slashing_penalty: u64,
+}
+
+impl ValidatorIncome {
+    /// Replaces the components applied by epoch processing with those of `epoch_income`.
+    pub fn set_epoch_components(&mut self, epoch_income: &ValidatorIncome) {
+        self.source_reward = epoch_income.source_reward;
+        self.source_penalty = epoch_income.source_penalty;
+        self.target_reward = epoch_income.target_reward;
+        self.target_penalty = epoch_income.target_penalty;
+        self.head_reward = epoch_income.head_reward;
+        self.inactivity_penalty = epoch_income.inactivity_penalty;
+        self.proportional_slashing_penalty = epoch_income.proportional_slashing_penalty;
+    }
+
+    /// Adds the components applied by the processing of a block.
+    pub fn add_block_components(&mut self, block_income: &ValidatorIncome) {
+        self.sync_committee_reward += block_income.sync_committee_reward;
+        self.sync_committee_penalty += block_income.sync_committee_penalty;
+        self.proposer_reward += block_income.proposer_reward;
+        self.slashing_penalty += block_income.slashing_penalty;
+    }
+
+    /// Returns the net balance change of the validator, in Gwei.
+    pub fn total(&self) -> i64 {
+        let rewards = self.source_reward
+            + self.target_reward
+            + self.head_reward
+            + self.sync_committee_reward
+            + self.proposer_reward;
+        let penalties = self.source_penalty
+            + self.target_penalty
+            + self.inactivity_penalty
+            + self.sync_committee_penalty
+            + self.slashing_penalty
+            + self.proportional_slashing_penalty;
+        rewards as i64 - penalties as i64
+    }
+}
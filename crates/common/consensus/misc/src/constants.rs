@@ -9,10 +9,12 @@ pub const BEACON_STATE_MERKLE_DEPTH: u64 = 6;
 pub const BLOB_KZG_COMMITMENTS_INDEX: u64 = 11;
 pub const BLOCK_BODY_MERKLE_DEPTH: u64 = 4;
 pub const BYTES_PER_BLOB: usize = BYTES_PER_FIELD_ELEMENT * FIELD_ELEMENTS_PER_BLOB;
+pub const BYTES_PER_CELL: usize = BYTES_PER_FIELD_ELEMENT * FIELD_ELEMENTS_PER_CELL;
 pub const BYTES_PER_COMMITMENT: usize = 48;
 pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
 pub const BYTES_PER_PROOF: usize = 48;
 pub const CAPELLA_FORK_VERSION: B32 = fixed_bytes!("0x03000000");
+pub const CELLS_PER_EXT_BLOB: u64 = 128;
 pub const CHURN_LIMIT_QUOTIENT: u64 = 65536;
 pub const CURRENT_SYNC_COMMITTEE_INDEX: u64 = 22;
 pub const DEPOSIT_CONTRACT_TREE_DEPTH: u64 = 32;
@@ -26,14 +28,27 @@ pub const DOMAIN_SYNC_COMMITTEE: B32 = fixed_bytes!("0x07000000");
 pub const DOMAIN_VOLUNTARY_EXIT: B32 = fixed_bytes!("0x04000000");
 pub const EFFECTIVE_BALANCE_INCREMENT: u64 = 1_000_000_000;
 pub const EJECTION_BALANCE: u64 = 16000000000;
+#[cfg(not(feature = "minimal"))]
 pub const EPOCHS_PER_ETH1_VOTING_PERIOD: u64 = 64;
+#[cfg(feature = "minimal")]
+pub const EPOCHS_PER_ETH1_VOTING_PERIOD: u64 = 4;
+#[cfg(not(feature = "minimal"))]
 pub const EPOCHS_PER_HISTORICAL_VECTOR: u64 = 65536;
+#[cfg(feature = "minimal")]
+pub const EPOCHS_PER_HISTORICAL_VECTOR: u64 = 64;
+#[cfg(not(feature = "minimal"))]
 pub const EPOCHS_PER_SLASHINGS_VECTOR: u64 = 8192;
+#[cfg(feature = "minimal")]
+pub const EPOCHS_PER_SLASHINGS_VECTOR: u64 = 64;
+#[cfg(not(feature = "minimal"))]
 pub const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 256;
+#[cfg(feature = "minimal")]
+pub const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 8;
 pub const ETH1_FOLLOW_DISTANCE: u64 = 2048;
 pub const EXECUTION_PAYLOAD_INDEX: u64 = 9;
 pub const FAR_FUTURE_EPOCH: u64 = 18446744073709551615;
 pub const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
+pub const FIELD_ELEMENTS_PER_CELL: usize = 64;
 pub const FINALIZED_CHECKPOINT_INDEX: u64 = 20;
 pub const GENESIS_SLOT: u64 = 0;
 pub const GENESIS_EPOCH: u64 = 0;
@@ -46,25 +61,47 @@ pub const INTERVALS_PER_SLOT: u64 = 3;
 pub const INACTIVITY_SCORE_BIAS: u64 = 4;
 pub const INACTIVITY_SCORE_RECOVERY_RATE: u64 = 16;
 pub const JUSTIFICATION_BITS_LENGTH: usize = 4;
+#[cfg(not(feature = "minimal"))]
 pub const KZG_COMMITMENT_INCLUSION_PROOF_DEPTH: u64 = 17;
+#[cfg(feature = "minimal")]
+pub const KZG_COMMITMENT_INCLUSION_PROOF_DEPTH: u64 = 10;
+pub const KZG_COMMITMENTS_INCLUSION_PROOF_DEPTH: u64 = 4;
+#[cfg(not(feature = "minimal"))]
 pub const KZG_COMMITMENTS_MERKLE_DEPTH: u64 = 12;
+#[cfg(feature = "minimal")]
+pub const KZG_COMMITMENTS_MERKLE_DEPTH: u64 = 5;
+#[cfg(not(feature = "minimal"))]
 pub const MAX_BLOBS_PER_BLOCK: usize = 4096;
+#[cfg(feature = "minimal")]
+pub const MAX_BLOBS_PER_BLOCK: usize = 32;
+#[cfg(not(feature = "minimal"))]
 pub const MAX_COMMITTEES_PER_SLOT: u64 = 64;
+#[cfg(feature = "minimal")]
+pub const MAX_COMMITTEES_PER_SLOT: u64 = 4;
 pub const MAX_DEPOSITS: u64 = 16;
+pub const MAX_EFFECTIVE_BALANCE: u64 = 32_000_000_000;
 pub const MAX_SEED_LOOKAHEAD: u64 = 4;
 pub const MAX_PER_EPOCH_ACTIVATION_CHURN_LIMIT: u64 = 8;
 pub const MAX_RANDOM_VALUE: u64 = 65535;
 pub const MAX_VALIDATORS_PER_COMMITTEE: u64 = 2048;
+#[cfg(not(feature = "minimal"))]
 pub const MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP: usize = 16384;
+#[cfg(feature = "minimal")]
+pub const MAX_VALIDATORS_PER_WITHDRAWALS_SWEEP: usize = 16;
+#[cfg(not(feature = "minimal"))]
 pub const MAX_WITHDRAWALS_PER_PAYLOAD: u64 = 16;
+#[cfg(feature = "minimal")]
+pub const MAX_WITHDRAWALS_PER_PAYLOAD: u64 = 4;
 pub const MIN_ATTESTATION_INCLUSION_DELAY: u64 = 1;
 pub const MIN_EPOCHS_TO_INACTIVITY_PENALTY: u64 = 4;
 pub const MIN_GENESIS_ACTIVE_VALIDATOR_COUNT: u64 = 16384;
 pub const MIN_GENESIS_TIME: u64 = 1606824000;
 pub const MIN_PER_EPOCH_CHURN_LIMIT: u64 = 4;
 pub const MIN_SEED_LOOKAHEAD: u64 = 1;
+pub const MIN_SLASHING_PENALTY_QUOTIENT_BELLATRIX: u64 = 32;
 pub const MIN_VALIDATOR_WITHDRAWABILITY_DELAY: u64 = 256;
 pub const NEXT_SYNC_COMMITTEE_INDEX: u64 = 23;
+pub const NUMBER_OF_COLUMNS: u64 = 128;
 pub const NUM_FLAG_INDICES: usize = 3;
 pub const PROPORTIONAL_SLASHING_MULTIPLIER_BELLATRIX: u64 = 3;
 pub const PROPOSER_REWARD_QUOTIENT: u64 = 8;
@@ -73,12 +110,27 @@ pub const REORG_PARENT_WEIGHT_THRESHOLD: u64 = 160;
 pub const SAFETY_DECAY: u64 = 10;
 pub const SECONDS_PER_ETH1_BLOCK: u64 = 14;
 pub const SHARD_COMMITTEE_PERIOD: u64 = 256;
+#[cfg(not(feature = "minimal"))]
 pub const SHUFFLE_ROUND_COUNT: u8 = 90;
+#[cfg(feature = "minimal")]
+pub const SHUFFLE_ROUND_COUNT: u8 = 10;
+#[cfg(not(feature = "minimal"))]
 pub const SLOTS_PER_EPOCH: u64 = 32;
+#[cfg(feature = "minimal")]
+pub const SLOTS_PER_EPOCH: u64 = 8;
+#[cfg(not(feature = "minimal"))]
 pub const SLOTS_PER_HISTORICAL_ROOT: u64 = 8192;
+#[cfg(feature = "minimal")]
+pub const SLOTS_PER_HISTORICAL_ROOT: u64 = 64;
+#[cfg(not(feature = "minimal"))]
 pub const SYNC_COMMITTEE_SIZE: u64 = 512;
+#[cfg(feature = "minimal")]
+pub const SYNC_COMMITTEE_SIZE: u64 = 32;
 pub const SYNC_REWARD_WEIGHT: u64 = 2;
+#[cfg(not(feature = "minimal"))]
 pub const TARGET_COMMITTEE_SIZE: u64 = 128;
+#[cfg(feature = "minimal")]
+pub const TARGET_COMMITTEE_SIZE: u64 = 4;
 pub const TIMELY_HEAD_FLAG_INDEX: u8 = 2;
 pub const TIMELY_SOURCE_FLAG_INDEX: u8 = 0;
 pub const TIMELY_TARGET_FLAG_INDEX: u8 = 1;
@@ -105,15 +157,24 @@ pub const MIN_SLASHING_PENALTY_QUOTIENT_ELECTRA: u64 = 4096;
 pub const WHISTLEBLOWER_REWARD_QUOTIENT_ELECTRA: u64 = 4096;
 
 // Withdrawals processing
+#[cfg(not(feature = "minimal"))]
 pub const MAX_PENDING_PARTIALS_PER_WITHDRAWALS_SWEEP: u64 = 8;
+#[cfg(feature = "minimal")]
+pub const MAX_PENDING_PARTIALS_PER_WITHDRAWALS_SWEEP: u64 = 2;
 
 // Misc
 pub const FULL_EXIT_REQUEST_AMOUNT: u64 = 0;
 pub const UNSET_DEPOSIT_REQUESTS_START_INDEX: u64 = u64::MAX;
 
 // State list lengths
+#[cfg(not(feature = "minimal"))]
 pub const PENDING_CONSOLIDATIONS_LIMIT: u64 = 262_144;
+#[cfg(feature = "minimal")]
+pub const PENDING_CONSOLIDATIONS_LIMIT: u64 = 64;
+#[cfg(not(feature = "minimal"))]
 pub const PENDING_PARTIAL_WITHDRAWALS_LIMIT: u64 = 134_217_728;
+#[cfg(feature = "minimal")]
+pub const PENDING_PARTIAL_WITHDRAWALS_LIMIT: u64 = 64;
 
 // Gwei values
 pub const MAX_EFFECTIVE_BALANCE_ELECTRA: u64 = 2_048_000_000_000;
@@ -123,6 +184,7 @@ pub const MIN_ACTIVATION_BALANCE: u64 = 32_000_000_000;
 pub const MAX_PENDING_DEPOSITS_PER_EPOCH: u64 = 16;
 
 // Execution
+pub const MAX_BLOBS_PER_BLOCK_DENEB: u64 = 6;
 pub const MAX_BLOBS_PER_BLOCK_ELECTRA: u64 = 9;
 
 // Validator cycle
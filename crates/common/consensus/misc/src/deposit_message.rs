@@ -9,5 +9,6 @@ pub struct DepositMessage {
     #[serde(rename = "pubkey")]
     pub public_key: PublicKey,
     pub withdrawal_credentials: B256,
+    #[serde(with = "serde_utils::quoted_u64")]
     pub amount: u64,
 }
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use alloy_primitives::aliases::B32;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
@@ -14,3 +16,43 @@ pub struct Fork {
 impl Fork {
     pub const UNSCHEDULED_EPOCH: u64 = u64::MAX;
 }
+
+/// The forks of the beacon chain, in activation order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForkName {
+    Phase0,
+    Altair,
+    Bellatrix,
+    Capella,
+    Deneb,
+    Electra,
+    Fulu,
+}
+
+impl ForkName {
+    pub const ALL: [ForkName; 7] = [
+        ForkName::Phase0,
+        ForkName::Altair,
+        ForkName::Bellatrix,
+        ForkName::Capella,
+        ForkName::Deneb,
+        ForkName::Electra,
+        ForkName::Fulu,
+    ];
+}
+
+impl fmt::Display for ForkName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ForkName::Phase0 => "phase0",
+            ForkName::Altair => "altair",
+            ForkName::Bellatrix => "bellatrix",
+            ForkName::Capella => "capella",
+            ForkName::Deneb => "deneb",
+            ForkName::Electra => "electra",
+            ForkName::Fulu => "fulu",
+        };
+        write!(f, "{name}")
+    }
+}
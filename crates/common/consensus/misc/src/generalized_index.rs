@@ -0,0 +1,97 @@
+//! Generalized indices of the fields which are commonly proven against a block or state root.
+//!
+//! https://ethereum.github.io/consensus-specs/ssz/merkle-proofs/#generalized-merkle-tree-index
+
+use ream_merkle::{concat_generalized_indices, generalized_index_from_leaf_index};
+
+use crate::constants::{
+    BEACON_STATE_MERKLE_DEPTH, BLOB_KZG_COMMITMENTS_INDEX, BLOCK_BODY_MERKLE_DEPTH,
+    CURRENT_SYNC_COMMITTEE_INDEX, EXECUTION_PAYLOAD_INDEX, FINALIZED_CHECKPOINT_INDEX,
+    NEXT_SYNC_COMMITTEE_INDEX,
+};
+
+/// Depth of the Merkle tree of the fields of `BeaconBlock`.
+pub const BEACON_BLOCK_MERKLE_DEPTH: u64 = 3;
+
+/// Index of `state_root` in the fields of `BeaconBlock`.
+pub const BLOCK_STATE_ROOT_INDEX: u64 = 3;
+
+/// Index of `body_root` in the fields of `BeaconBlock`.
+pub const BLOCK_BODY_ROOT_INDEX: u64 = 4;
+
+/// Depth of the Merkle tree of the fields of `ExecutionPayload` and `ExecutionPayloadHeader`.
+pub const EXECUTION_PAYLOAD_MERKLE_DEPTH: u64 = 5;
+
+/// Index of `block_hash` in the fields of `ExecutionPayload` and `ExecutionPayloadHeader`.
+pub const EXECUTION_BLOCK_HASH_INDEX: u64 = 12;
+
+/// Index of `latest_execution_payload_header` in the fields of `BeaconState`.
+pub const LATEST_EXECUTION_PAYLOAD_HEADER_INDEX: u64 = 24;
+
+/// Generalized index of `root` in `Checkpoint`.
+const CHECKPOINT_ROOT_GINDEX: u64 = 3;
+
+/// `BeaconBlock.state_root`
+pub const BLOCK_STATE_ROOT_GINDEX: u64 =
+    generalized_index_from_leaf_index(BLOCK_STATE_ROOT_INDEX, BEACON_BLOCK_MERKLE_DEPTH);
+
+/// `BeaconBlock.body`
+pub const BLOCK_BODY_ROOT_GINDEX: u64 =
+    generalized_index_from_leaf_index(BLOCK_BODY_ROOT_INDEX, BEACON_BLOCK_MERKLE_DEPTH);
+
+/// `BeaconBlockBody.execution_payload`
+pub const EXECUTION_PAYLOAD_GINDEX: u64 =
+    generalized_index_from_leaf_index(EXECUTION_PAYLOAD_INDEX, BLOCK_BODY_MERKLE_DEPTH);
+
+/// `BeaconBlockBody.execution_payload.block_hash`
+pub const EXECUTION_PAYLOAD_BLOCK_HASH_GINDEX: u64 = concat_generalized_indices(&[
+    EXECUTION_PAYLOAD_GINDEX,
+    generalized_index_from_leaf_index(EXECUTION_BLOCK_HASH_INDEX, EXECUTION_PAYLOAD_MERKLE_DEPTH),
+]);
+
+/// `BeaconBlockBody.blob_kzg_commitments`
+pub const BLOB_KZG_COMMITMENTS_GINDEX: u64 =
+    generalized_index_from_leaf_index(BLOB_KZG_COMMITMENTS_INDEX, BLOCK_BODY_MERKLE_DEPTH);
+
+/// `BeaconState.finalized_checkpoint.root`
+pub const FINALIZED_ROOT_GINDEX: u64 = concat_generalized_indices(&[
+    generalized_index_from_leaf_index(FINALIZED_CHECKPOINT_INDEX, BEACON_STATE_MERKLE_DEPTH),
+    CHECKPOINT_ROOT_GINDEX,
+]);
+
+/// `BeaconState.current_sync_committee`
+pub const CURRENT_SYNC_COMMITTEE_GINDEX: u64 =
+    generalized_index_from_leaf_index(CURRENT_SYNC_COMMITTEE_INDEX, BEACON_STATE_MERKLE_DEPTH);
+
+/// `BeaconState.next_sync_committee`
+pub const NEXT_SYNC_COMMITTEE_GINDEX: u64 =
+    generalized_index_from_leaf_index(NEXT_SYNC_COMMITTEE_INDEX, BEACON_STATE_MERKLE_DEPTH);
+
+/// `BeaconState.latest_execution_payload_header`
+pub const LATEST_EXECUTION_PAYLOAD_HEADER_GINDEX: u64 = generalized_index_from_leaf_index(
+    LATEST_EXECUTION_PAYLOAD_HEADER_INDEX,
+    BEACON_STATE_MERKLE_DEPTH,
+);
+
+/// `BeaconState.latest_execution_payload_header.block_hash`
+pub const LATEST_EXECUTION_PAYLOAD_HEADER_BLOCK_HASH_GINDEX: u64 = concat_generalized_indices(&[
+    LATEST_EXECUTION_PAYLOAD_HEADER_GINDEX,
+    generalized_index_from_leaf_index(EXECUTION_BLOCK_HASH_INDEX, EXECUTION_PAYLOAD_MERKLE_DEPTH),
+]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generalized_indices_match_the_spec() {
+        assert_eq!(FINALIZED_ROOT_GINDEX, 169);
+        assert_eq!(CURRENT_SYNC_COMMITTEE_GINDEX, 86);
+        assert_eq!(NEXT_SYNC_COMMITTEE_GINDEX, 87);
+        assert_eq!(EXECUTION_PAYLOAD_GINDEX, 25);
+        assert_eq!(BLOB_KZG_COMMITMENTS_GINDEX, 27);
+        assert_eq!(EXECUTION_PAYLOAD_BLOCK_HASH_GINDEX, 812);
+        assert_eq!(LATEST_EXECUTION_PAYLOAD_HEADER_BLOCK_HASH_GINDEX, 2828);
+        assert_eq!(BLOCK_BODY_ROOT_GINDEX, 12);
+    }
+}
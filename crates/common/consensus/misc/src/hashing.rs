@@ -0,0 +1,30 @@
+//! The SHA-256 functions used by the state transition.
+//!
+//! By default they are backed by `ethereum_hashing`, which picks an accelerated implementation at
+//! runtime. The "sha2-hashing" feature backs them with the portable `sha2` crate instead, which
+//! builds for any target and is the crate zkVMs patch to use their SHA-256 precompiles.
+
+#[cfg(not(feature = "sha2-hashing"))]
+pub use ethereum_hashing::{hash, hash_fixed, hash32_concat};
+#[cfg(feature = "sha2-hashing")]
+pub use sha2_backend::{hash, hash_fixed, hash32_concat};
+
+#[cfg(feature = "sha2-hashing")]
+mod sha2_backend {
+    use sha2::{Digest, Sha256};
+
+    pub fn hash(input: &[u8]) -> Vec<u8> {
+        hash_fixed(input).to_vec()
+    }
+
+    pub fn hash_fixed(input: &[u8]) -> [u8; 32] {
+        Sha256::digest(input).into()
+    }
+
+    pub fn hash32_concat(h1: &[u8], h2: &[u8]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(h1);
+        hasher.update(h2);
+        hasher.finalize().into()
+    }
+}
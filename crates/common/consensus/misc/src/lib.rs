@@ -9,9 +9,13 @@ pub mod deposit_message;
 pub mod eth_1_data;
 pub mod fork;
 pub mod fork_data;
+pub mod generalized_index;
+pub mod hashing;
 pub mod historical_batch;
 pub mod indexed_attestation;
 pub mod misc;
 pub mod pending_attestation;
+pub mod preset;
 pub mod signing_data;
+pub mod stable_container;
 pub mod validator;
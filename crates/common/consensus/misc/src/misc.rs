@@ -1,8 +1,12 @@
-use std::cmp::max;
+use std::{
+    cmp::max,
+    num::NonZeroUsize,
+    sync::{Arc, LazyLock, Mutex, PoisonError},
+};
 
 use alloy_primitives::{B256, aliases::B32};
 use anyhow::ensure;
-use ethereum_hashing::hash;
+use lru::LruCache;
 use ssz_types::{BitVector, typenum::U64};
 use tree_hash::TreeHash;
 
@@ -12,6 +16,7 @@ use crate::{
         MAX_SEED_LOOKAHEAD, SHUFFLE_ROUND_COUNT, SLOTS_PER_EPOCH,
     },
     fork_data::ForkData,
+    hashing::hash,
     signing_data::SigningData,
 };
 
@@ -70,6 +75,83 @@ pub fn compute_shuffled_index(
     Ok(index)
 }
 
+/// Return ``indices`` permuted so that position ``i`` holds
+/// ``indices[compute_shuffled_index(i, len(indices), seed)]``.
+///
+/// Shuffles the whole list at once, computing the pivot of each round once and hashing each 256
+/// positions once per round instead of twice per index and round.
+pub fn shuffle_list(indices: &[u64], seed: B256) -> Vec<u64> {
+    let mut shuffled = indices.to_vec();
+    let index_count = shuffled.len();
+    if index_count <= 1 {
+        return shuffled;
+    }
+
+    // Each round swaps pairs of positions, so applying the rounds to the list in reverse order
+    // composes them in the order `compute_shuffled_index` applies them to an index
+    for round in (0..SHUFFLE_ROUND_COUNT).rev() {
+        let seed_with_round = [seed.as_slice(), &round.to_le_bytes()].concat();
+        let pivot = (bytes_to_int64(&hash(&seed_with_round)[..]) % index_count as u64) as usize;
+
+        let mut source_position = None;
+        let mut source = vec![];
+        for index in 0..index_count {
+            let flip = (pivot + index_count - index) % index_count;
+            // Visit each pair once, from its lower position
+            if flip <= index {
+                continue;
+            }
+            let position = flip;
+            if source_position != Some(position / 256) {
+                source = hash(
+                    &[
+                        seed_with_round.as_slice(),
+                        &(position / 256).to_le_bytes()[0..4],
+                    ]
+                    .concat(),
+                );
+                source_position = Some(position / 256);
+            }
+            let byte = source[(position % 256) / 8];
+            if (byte >> (position % 8)) % 2 == 1 {
+                shuffled.swap(index, flip);
+            }
+        }
+    }
+    shuffled
+}
+
+/// The number of shufflings kept by [`cached_shuffle_list`], enough for the previous, current
+/// and next epochs of a few forks.
+const SHUFFLING_CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(16).expect("16 is not zero");
+
+/// The recent shufflings by seed, along with the indices they shuffle.
+type ShufflingCache = LruCache<B256, (Arc<[u64]>, Arc<[u64]>)>;
+
+static SHUFFLING_CACHE: LazyLock<Mutex<ShufflingCache>> =
+    LazyLock::new(|| Mutex::new(LruCache::new(SHUFFLING_CACHE_SIZE)));
+
+/// Return [`shuffle_list`] of ``indices`` with ``seed``, reusing the shuffling of a recent call
+/// with the same arguments.
+pub fn cached_shuffle_list(indices: &[u64], seed: B256) -> Arc<[u64]> {
+    if let Some((cached_indices, shuffling)) = SHUFFLING_CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&seed)
+        && **cached_indices == *indices
+    {
+        return shuffling.clone();
+    }
+
+    // Shuffle without holding the lock, a concurrent call for the same seed shuffles it too
+    let shuffling = Arc::<[u64]>::from(shuffle_list(indices, seed));
+    SHUFFLING_CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .put(seed, (indices.into(), shuffling.clone()));
+    shuffling
+}
+
 // Return the integer deserialization of ``data`` interpreted as ``ENDIANNESS``-endian.
 pub fn bytes_to_int64(slice: &[u8]) -> u64 {
     let mut bytes = [0u8; 8];
@@ -85,17 +167,16 @@ pub fn compute_committee(
     index: u64,
     count: u64,
 ) -> anyhow::Result<Vec<u64>> {
+    ensure!(
+        index < count,
+        "Committee index {index} must be less than {count}"
+    );
+    // Every committee of an epoch is a slice of the same shuffling, so the whole list is shuffled
+    // once and reused by the lookups of the other committees
+    let shuffling = cached_shuffle_list(indices, seed);
     let start = (indices.len() as u64 * index) / count;
     let end = (indices.len() as u64 * (index + 1)) / count;
-    (start..end)
-        .map(|i| {
-            let shuffled_index = compute_shuffled_index(i as usize, indices.len(), seed)?;
-            indices
-                .get(shuffled_index)
-                .copied()
-                .ok_or_else(|| anyhow::anyhow!("Index out of bounds: {}", shuffled_index))
-        })
-        .collect::<anyhow::Result<Vec<u64>>>()
+    Ok(shuffling[start as usize..end as usize].to_vec())
 }
 
 pub fn is_shuffling_stable(slot: u64) -> bool {
@@ -157,3 +238,24 @@ pub fn compute_sync_committee_period(epoch: u64) -> u64 {
 pub fn compute_sync_committee_period_at_slot(slot: u64) -> u64 {
     compute_sync_committee_period(compute_epoch_at_slot(slot))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shuffle_list_matches_compute_shuffled_index() -> anyhow::Result<()> {
+        let seed = B256::repeat_byte(7);
+        for index_count in [0, 1, 2, 3, 100, 300, 1000] {
+            let indices = (0..index_count)
+                .map(|index| index * 3)
+                .collect::<Vec<u64>>();
+            let expected = (0..indices.len())
+                .map(|i| Ok(indices[compute_shuffled_index(i, indices.len(), seed)?]))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            assert_eq!(shuffle_list(&indices, seed), expected);
+            assert_eq!(*cached_shuffle_list(&indices, seed), *expected);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+//! SSZ lengths of the preset, which the `minimal` feature switches from the mainnet preset to
+//! the minimal preset.
+
+#[cfg(feature = "minimal")]
+use ssz_types::typenum::{U2, U4, U8, U10, U32, U64, U1024};
+#[cfg(not(feature = "minimal"))]
+use ssz_types::typenum::{U16, U17, U128, U512, U2048, U4096, U8192, U65536, U262144, U134217728};
+
+// SLOTS_PER_HISTORICAL_ROOT
+#[cfg(not(feature = "minimal"))]
+pub type SlotsPerHistoricalRoot = U8192;
+#[cfg(feature = "minimal")]
+pub type SlotsPerHistoricalRoot = U64;
+
+// EPOCHS_PER_HISTORICAL_VECTOR
+#[cfg(not(feature = "minimal"))]
+pub type EpochsPerHistoricalVector = U65536;
+#[cfg(feature = "minimal")]
+pub type EpochsPerHistoricalVector = U64;
+
+// EPOCHS_PER_SLASHINGS_VECTOR
+#[cfg(not(feature = "minimal"))]
+pub type EpochsPerSlashingsVector = U8192;
+#[cfg(feature = "minimal")]
+pub type EpochsPerSlashingsVector = U64;
+
+// EPOCHS_PER_ETH1_VOTING_PERIOD * SLOTS_PER_EPOCH
+#[cfg(not(feature = "minimal"))]
+pub type SlotsPerEth1VotingPeriod = U2048;
+#[cfg(feature = "minimal")]
+pub type SlotsPerEth1VotingPeriod = U32;
+
+// MAX_ATTESTATIONS * SLOTS_PER_EPOCH
+#[cfg(not(feature = "minimal"))]
+pub type MaxPendingAttestations = U4096;
+#[cfg(feature = "minimal")]
+pub type MaxPendingAttestations = U1024;
+
+// SYNC_COMMITTEE_SIZE
+#[cfg(not(feature = "minimal"))]
+pub type SyncCommitteeSize = U512;
+#[cfg(feature = "minimal")]
+pub type SyncCommitteeSize = U32;
+
+// SYNC_COMMITTEE_SIZE // SYNC_COMMITTEE_SUBNET_COUNT
+#[cfg(not(feature = "minimal"))]
+pub type SyncSubcommitteeSize = U128;
+#[cfg(feature = "minimal")]
+pub type SyncSubcommitteeSize = U8;
+
+// MAX_WITHDRAWALS_PER_PAYLOAD
+#[cfg(not(feature = "minimal"))]
+pub type MaxWithdrawalsPerPayload = U16;
+#[cfg(feature = "minimal")]
+pub type MaxWithdrawalsPerPayload = U4;
+
+// MAX_BLOB_COMMITMENTS_PER_BLOCK
+#[cfg(not(feature = "minimal"))]
+pub type MaxBlobCommitmentsPerBlock = U4096;
+#[cfg(feature = "minimal")]
+pub type MaxBlobCommitmentsPerBlock = U32;
+
+// KZG_COMMITMENT_INCLUSION_PROOF_DEPTH
+#[cfg(not(feature = "minimal"))]
+pub type KzgCommitmentInclusionProofDepth = U17;
+#[cfg(feature = "minimal")]
+pub type KzgCommitmentInclusionProofDepth = U10;
+
+// MAX_DEPOSIT_REQUESTS_PER_PAYLOAD
+#[cfg(not(feature = "minimal"))]
+pub type MaxDepositRequestsPerPayload = U8192;
+#[cfg(feature = "minimal")]
+pub type MaxDepositRequestsPerPayload = U4;
+
+// MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD
+#[cfg(not(feature = "minimal"))]
+pub type MaxWithdrawalRequestsPerPayload = U16;
+#[cfg(feature = "minimal")]
+pub type MaxWithdrawalRequestsPerPayload = U2;
+
+// PENDING_PARTIAL_WITHDRAWALS_LIMIT
+#[cfg(not(feature = "minimal"))]
+pub type PendingPartialWithdrawalsLimit = U134217728;
+#[cfg(feature = "minimal")]
+pub type PendingPartialWithdrawalsLimit = U64;
+
+// PENDING_CONSOLIDATIONS_LIMIT
+#[cfg(not(feature = "minimal"))]
+pub type PendingConsolidationsLimit = U262144;
+#[cfg(feature = "minimal")]
+pub type PendingConsolidationsLimit = U64;
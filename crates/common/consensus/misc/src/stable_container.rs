@@ -0,0 +1,600 @@
+//! SSZ `StableContainer` and `Profile` types, which keep the generalized indices of their fields
+//! stable across forks.
+//!
+//! https://eips.ethereum.org/EIPS/eip-7495
+//!
+//! A stable container is declared with [`stable_container!`] and has optional fields only. A
+//! profile is declared with [`profile!`] against a stable container, it keeps a subset of the
+//! fields of the stable container, each of them either required or optional, and shares its hash
+//! tree root with the stable container holding the same values.
+
+use alloy_primitives::B256;
+use ssz::{BYTES_PER_LENGTH_OFFSET, DecodeError, Encode};
+use tree_hash::merkle_root;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use anyhow;
+    pub use ssz;
+    pub use tree_hash;
+}
+
+/// Returns the length of the serialization of a `Bitvector[length]`.
+pub const fn bitvector_len(length: usize) -> usize {
+    length.div_ceil(8)
+}
+
+/// Serializes `bits` as a `Bitvector[length]`, the bits past the end of `bits` are unset.
+pub fn encode_bitvector(bits: &[bool], length: usize) -> Vec<u8> {
+    let mut bytes = vec![0; bitvector_len(length)];
+    for (index, _) in bits.iter().enumerate().filter(|(_, bit)| **bit) {
+        bytes[index / 8] |= 1 << (index % 8);
+    }
+    bytes
+}
+
+/// Splits a `Bitvector[length]` off the front of `bytes`, and returns its first `known_bits` bits
+/// with the remaining bytes. The bits past `known_bits` must be unset, as they belong to fields the
+/// type does not know about.
+pub fn decode_bitvector(
+    bytes: &[u8],
+    length: usize,
+    known_bits: usize,
+) -> Result<(Vec<bool>, &[u8]), DecodeError> {
+    let bitvector_len = bitvector_len(length);
+    if bytes.len() < bitvector_len {
+        return Err(DecodeError::InvalidByteLength {
+            len: bytes.len(),
+            expected: bitvector_len,
+        });
+    }
+    let (bitvector, bytes) = bytes.split_at(bitvector_len);
+    let bit = |index: usize| bitvector[index / 8] & (1 << (index % 8)) != 0;
+    if (known_bits..bitvector_len * 8).any(bit) {
+        return Err(DecodeError::BytesInvalid(format!(
+            "Bitvector has bits set past its {known_bits} known fields"
+        )));
+    }
+    Ok(((0..known_bits).map(bit).collect(), bytes))
+}
+
+/// Returns the number of bytes `T` takes in the fixed part of a container.
+pub fn fixed_part_len<T: Encode>() -> usize {
+    if T::is_ssz_fixed_len() {
+        T::ssz_fixed_len()
+    } else {
+        BYTES_PER_LENGTH_OFFSET
+    }
+}
+
+/// Returns the number of bytes `value` takes in a container, in both the fixed and variable parts.
+pub fn field_len<T: Encode>(value: &T) -> usize {
+    if T::is_ssz_fixed_len() {
+        T::ssz_fixed_len()
+    } else {
+        BYTES_PER_LENGTH_OFFSET + value.ssz_bytes_len()
+    }
+}
+
+/// Returns the hash tree root of a `StableContainer[capacity]` whose fields have `field_roots`,
+/// `None` for the inactive fields.
+///
+/// The field roots are merkleized as if the container had `capacity` fields, so that adding fields
+/// in a later fork does not move the existing ones, and the active fields are mixed in.
+pub fn stable_container_root(capacity: usize, field_roots: &[Option<B256>]) -> B256 {
+    let leaves = field_roots
+        .iter()
+        .flat_map(|root| root.unwrap_or_default().0)
+        .collect::<Vec<_>>();
+    let active_fields = field_roots.iter().map(Option::is_some).collect::<Vec<_>>();
+    let active_fields_root = merkle_root(
+        &encode_bitvector(&active_fields, capacity),
+        capacity.div_ceil(256),
+    );
+    B256::from(crate::hashing::hash32_concat(
+        merkle_root(&leaves, capacity).as_slice(),
+        active_fields_root.as_slice(),
+    ))
+}
+
+/// Returns whether the field indices of a profile are in the order of the stable container.
+pub const fn is_strictly_increasing(indices: &[usize]) -> bool {
+    let mut i = 1;
+    while i < indices.len() {
+        if indices[i - 1] >= indices[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Declares a `StableContainer[N]`: a struct with optional fields, encoded as a `Bitvector[N]` of
+/// its active fields followed by the active fields as a container.
+///
+/// ```ignore
+/// stable_container! {
+///     pub struct Shape[4] {
+///         pub side: u16,
+///         pub color: u8,
+///         pub radius: u16,
+///     }
+/// }
+/// ```
+///
+/// Fields can only be appended to the end of a stable container, and at most `N` of them.
+#[macro_export]
+macro_rules! stable_container {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident[$capacity:expr] {
+            $($(#[$field_attr:meta])* $field_vis:vis $field:ident: $ty:ty),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Default, PartialEq, Eq)]
+        $vis struct $name {
+            $($(#[$field_attr])* $field_vis $field: Option<$ty>,)*
+        }
+
+        const _: () = assert!(
+            [$(stringify!($field)),*].len() <= $capacity,
+            "Stable container has more fields than its capacity"
+        );
+
+        impl $name {
+            pub const MAX_FIELDS: usize = $capacity;
+
+            const FIELD_COUNT: usize = [$(stringify!($field)),*].len();
+
+            pub fn active_fields(&self) -> Vec<bool> {
+                vec![$(self.$field.is_some()),*]
+            }
+        }
+
+        impl $crate::stable_container::__private::ssz::Encode for $name {
+            fn is_ssz_fixed_len() -> bool {
+                false
+            }
+
+            fn ssz_append(&self, buf: &mut Vec<u8>) {
+                buf.extend($crate::stable_container::encode_bitvector(
+                    &self.active_fields(),
+                    Self::MAX_FIELDS,
+                ));
+                let fixed_len = 0 $(+ self.$field.as_ref().map_or(0, |_| {
+                    $crate::stable_container::fixed_part_len::<$ty>()
+                }))*;
+                let mut encoder =
+                    $crate::stable_container::__private::ssz::SszEncoder::container(buf, fixed_len);
+                $(if let Some(value) = &self.$field {
+                    encoder.append(value);
+                })*
+                encoder.finalize();
+            }
+
+            fn ssz_bytes_len(&self) -> usize {
+                $crate::stable_container::bitvector_len(Self::MAX_FIELDS)
+                    $(+ self.$field.as_ref().map_or(0, $crate::stable_container::field_len))*
+            }
+        }
+
+        impl $crate::stable_container::__private::ssz::Decode for $name {
+            fn is_ssz_fixed_len() -> bool {
+                false
+            }
+
+            fn from_ssz_bytes(
+                bytes: &[u8],
+            ) -> Result<Self, $crate::stable_container::__private::ssz::DecodeError> {
+                let (active_fields, bytes) = $crate::stable_container::decode_bitvector(
+                    bytes,
+                    Self::MAX_FIELDS,
+                    Self::FIELD_COUNT,
+                )?;
+                let mut builder =
+                    $crate::stable_container::__private::ssz::SszDecoderBuilder::new(bytes);
+                let mut active = active_fields.iter();
+                $(if active.next() == Some(&true) {
+                    builder.register_type::<$ty>()?;
+                })*
+                let mut decoder = builder.build()?;
+                let mut active = active_fields.iter();
+                Ok(Self {
+                    $($field: match active.next() {
+                        Some(true) => Some(decoder.decode_next::<$ty>()?),
+                        _ => None,
+                    },)*
+                })
+            }
+        }
+
+        impl $crate::stable_container::__private::tree_hash::TreeHash for $name {
+            fn tree_hash_type() -> $crate::stable_container::__private::tree_hash::TreeHashType {
+                $crate::stable_container::__private::tree_hash::TreeHashType::Container
+            }
+
+            fn tree_hash_packed_encoding(
+                &self,
+            ) -> $crate::stable_container::__private::tree_hash::PackedEncoding {
+                unreachable!("StableContainer should never be packed")
+            }
+
+            fn tree_hash_packing_factor() -> usize {
+                unreachable!("StableContainer should never be packed")
+            }
+
+            fn tree_hash_root(&self) -> $crate::stable_container::__private::tree_hash::Hash256 {
+                $crate::stable_container::stable_container_root(
+                    Self::MAX_FIELDS,
+                    &[$(self.$field.as_ref().map(
+                        $crate::stable_container::__private::tree_hash::TreeHash::tree_hash_root,
+                    )),*],
+                )
+            }
+        }
+    };
+}
+
+/// Declares a `Profile[B]` of the stable container `B`: a struct with a subset of the fields of
+/// `B`, in the same order and with the same names, each either `required` or `optional`.
+///
+/// ```ignore
+/// profile! {
+///     pub struct Circle: Shape {
+///         pub color: required u8 = 1,
+///         pub radius: optional u16 = 2,
+///     }
+/// }
+/// ```
+///
+/// The number after each field is its index in `B`. A profile is encoded as a `Bitvector` of its
+/// optional fields which are present, omitted if it has no optional fields, followed by the
+/// present fields as a container. Its hash tree root is the one of the matching value of `B`.
+#[macro_export]
+macro_rules! profile {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident: $base:ty {
+            $($(#[$field_attr:meta])* $field_vis:vis $field:ident: $kind:ident $ty:ty = $index:literal),*
+            $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        $vis struct $name {
+            $($(#[$field_attr])* $field_vis $field: $crate::__profile_field!(type $kind $ty),)*
+        }
+
+        const _: () = {
+            assert!(
+                $crate::stable_container::is_strictly_increasing(&[$($index),*]),
+                "Profile fields must be in the order of the stable container"
+            );
+            $(assert!(
+                $index < <$base>::MAX_FIELDS,
+                "Profile field index is out of the stable container"
+            );)*
+        };
+
+        impl $name {
+            const OPTIONAL_FIELD_COUNT: usize =
+                0 $(+ $crate::__profile_field!(is_optional $kind) as usize)*;
+
+            fn optional_fields(&self) -> Vec<bool> {
+                let mut optional_fields = vec![];
+                $(if $crate::__profile_field!(is_optional $kind) {
+                    optional_fields.push($crate::__profile_field!(as_option $kind self.$field).is_some());
+                })*
+                optional_fields
+            }
+        }
+
+        impl $crate::stable_container::__private::ssz::Encode for $name {
+            fn is_ssz_fixed_len() -> bool {
+                false
+            }
+
+            fn ssz_append(&self, buf: &mut Vec<u8>) {
+                if Self::OPTIONAL_FIELD_COUNT > 0 {
+                    buf.extend($crate::stable_container::encode_bitvector(
+                        &self.optional_fields(),
+                        Self::OPTIONAL_FIELD_COUNT,
+                    ));
+                }
+                let fixed_len = 0 $(+ $crate::__profile_field!(as_option $kind self.$field)
+                    .map_or(0, |_| $crate::stable_container::fixed_part_len::<$ty>()))*;
+                let mut encoder =
+                    $crate::stable_container::__private::ssz::SszEncoder::container(buf, fixed_len);
+                $(if let Some(value) = $crate::__profile_field!(as_option $kind self.$field) {
+                    encoder.append(value);
+                })*
+                encoder.finalize();
+            }
+
+            fn ssz_bytes_len(&self) -> usize {
+                $crate::stable_container::bitvector_len(Self::OPTIONAL_FIELD_COUNT)
+                    $(+ $crate::__profile_field!(as_option $kind self.$field)
+                        .map_or(0, $crate::stable_container::field_len))*
+            }
+        }
+
+        impl $crate::stable_container::__private::ssz::Decode for $name {
+            fn is_ssz_fixed_len() -> bool {
+                false
+            }
+
+            fn from_ssz_bytes(
+                bytes: &[u8],
+            ) -> Result<Self, $crate::stable_container::__private::ssz::DecodeError> {
+                let (optional_fields, bytes) = if Self::OPTIONAL_FIELD_COUNT > 0 {
+                    $crate::stable_container::decode_bitvector(
+                        bytes,
+                        Self::OPTIONAL_FIELD_COUNT,
+                        Self::OPTIONAL_FIELD_COUNT,
+                    )?
+                } else {
+                    (vec![], bytes)
+                };
+                let mut optional = optional_fields.iter();
+                let present = [$(
+                    !$crate::__profile_field!(is_optional $kind) || optional.next() == Some(&true)
+                ),*];
+
+                let mut builder =
+                    $crate::stable_container::__private::ssz::SszDecoderBuilder::new(bytes);
+                let mut field_present = present.iter();
+                $(if field_present.next() == Some(&true) {
+                    builder.register_type::<$ty>()?;
+                })*
+                let mut decoder = builder.build()?;
+                let mut field_present = present.iter();
+                Ok(Self {
+                    $($field: $crate::__profile_field!(
+                        from_option $kind match field_present.next() {
+                            Some(true) => Some(decoder.decode_next::<$ty>()?),
+                            _ => None,
+                        }
+                    ),)*
+                })
+            }
+        }
+
+        impl $crate::stable_container::__private::tree_hash::TreeHash for $name {
+            fn tree_hash_type() -> $crate::stable_container::__private::tree_hash::TreeHashType {
+                $crate::stable_container::__private::tree_hash::TreeHashType::Container
+            }
+
+            fn tree_hash_packed_encoding(
+                &self,
+            ) -> $crate::stable_container::__private::tree_hash::PackedEncoding {
+                unreachable!("Profile should never be packed")
+            }
+
+            fn tree_hash_packing_factor() -> usize {
+                unreachable!("Profile should never be packed")
+            }
+
+            fn tree_hash_root(&self) -> $crate::stable_container::__private::tree_hash::Hash256 {
+                let mut field_roots = vec![None; <$base>::MAX_FIELDS];
+                $(field_roots[$index] = $crate::__profile_field!(as_option $kind self.$field).map(
+                    $crate::stable_container::__private::tree_hash::TreeHash::tree_hash_root,
+                );)*
+                $crate::stable_container::stable_container_root(<$base>::MAX_FIELDS, &field_roots)
+            }
+        }
+
+        impl From<$name> for $base {
+            #[allow(clippy::needless_update)]
+            fn from(profile: $name) -> Self {
+                Self {
+                    $($field: $crate::__profile_field!(into_option $kind profile.$field),)*
+                    ..Default::default()
+                }
+            }
+        }
+
+        impl TryFrom<$base> for $name {
+            type Error = $crate::stable_container::__private::anyhow::Error;
+
+            fn try_from(base: $base) -> Result<Self, Self::Error> {
+                let profile_fields = [$($index),*];
+                if let Some((index, _)) = base
+                    .active_fields()
+                    .iter()
+                    .enumerate()
+                    .find(|(index, active)| **active && !profile_fields.contains(index))
+                {
+                    $crate::stable_container::__private::anyhow::bail!(
+                        "Field {index} is not part of {}",
+                        stringify!($name)
+                    );
+                }
+                Ok(Self {
+                    $($field: $crate::__profile_field!(from_base $kind $field base.$field),)*
+                })
+            }
+        }
+    };
+}
+
+/// Expands the parts of [`profile!`] which depend on whether a field is required or optional.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __profile_field {
+    (type required $ty:ty) => { $ty };
+    (type optional $ty:ty) => { Option<$ty> };
+    (is_optional required) => { false };
+    (is_optional optional) => { true };
+    (as_option required $value:expr) => { Some(&$value) };
+    (as_option optional $value:expr) => { $value.as_ref() };
+    (into_option required $value:expr) => { Some($value) };
+    (into_option optional $value:expr) => { $value };
+    (from_option required $value:expr) => {
+        match $value {
+            Some(value) => value,
+            None => unreachable!("Required profile fields are always present"),
+        }
+    };
+    (from_option optional $value:expr) => { $value };
+    (from_base required $field:ident $value:expr) => {
+        $value.ok_or_else(|| {
+            $crate::stable_container::__private::anyhow::anyhow!(
+                "Required field {} is missing",
+                stringify!($field)
+            )
+        })?
+    };
+    (from_base optional $field:ident $value:expr) => { $value };
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::hex;
+    use ssz::{Decode, Encode};
+    use ssz_types::{BitVector, typenum::U4};
+    use tree_hash::TreeHash;
+    use tree_hash_derive::TreeHash;
+
+    use super::*;
+
+    stable_container! {
+        struct Shape[4] {
+            side: u16,
+            color: u8,
+            radius: u16,
+        }
+    }
+
+    profile! {
+        struct Square: Shape {
+            side: required u16 = 0,
+            color: required u8 = 1,
+        }
+    }
+
+    profile! {
+        struct Circle: Shape {
+            color: required u8 = 1,
+            radius: optional u16 = 2,
+        }
+    }
+
+    /// The fields of `Shape` padded to its capacity, which merkleize like the stable container.
+    #[derive(TreeHash)]
+    struct ShapeLeaves {
+        side: u16,
+        color: u8,
+        radius: u16,
+        padding: B256,
+    }
+
+    fn expected_root(leaves: ShapeLeaves, active_fields: [bool; 4]) -> B256 {
+        let mut bits = BitVector::<U4>::new();
+        for (index, active) in active_fields.into_iter().enumerate() {
+            bits.set(index, active)
+                .expect("Index is within the bitvector");
+        }
+        B256::from(crate::hashing::hash32_concat(
+            leaves.tree_hash_root().as_slice(),
+            bits.tree_hash_root().as_slice(),
+        ))
+    }
+
+    #[test]
+    fn test_stable_container_round_trip() {
+        let shape = Shape {
+            side: None,
+            color: Some(1),
+            radius: Some(0x42),
+        };
+        let bytes = shape.as_ssz_bytes();
+        assert_eq!(bytes, hex!("06014200"));
+        assert_eq!(bytes.len(), shape.ssz_bytes_len());
+        assert_eq!(Shape::from_ssz_bytes(&bytes).expect("Valid shape"), shape);
+
+        assert_eq!(
+            shape.tree_hash_root(),
+            expected_root(
+                ShapeLeaves {
+                    side: 0,
+                    color: 1,
+                    radius: 0x42,
+                    padding: B256::ZERO,
+                },
+                [false, true, true, false]
+            )
+        );
+    }
+
+    #[test]
+    fn test_stable_container_rejects_unknown_fields() {
+        // The fourth field is within the capacity but unknown to `Shape`
+        assert!(Shape::from_ssz_bytes(&hex!("0a01")).is_err());
+    }
+
+    #[test]
+    fn test_profile_without_optional_fields() {
+        let square = Square {
+            side: 0x42,
+            color: 1,
+        };
+        let bytes = square.as_ssz_bytes();
+        assert_eq!(bytes, hex!("420001"));
+        assert_eq!(
+            Square::from_ssz_bytes(&bytes).expect("Valid square"),
+            square
+        );
+
+        let shape = Shape::from(square.clone());
+        assert_eq!(shape.as_ssz_bytes(), hex!("03420001"));
+        assert_eq!(square.tree_hash_root(), shape.tree_hash_root());
+        assert_eq!(Square::try_from(shape).expect("Shape is a square"), square);
+    }
+
+    #[test]
+    fn test_profile_with_optional_fields() {
+        let circle = Circle {
+            color: 1,
+            radius: Some(0x42),
+        };
+        let bytes = circle.as_ssz_bytes();
+        assert_eq!(bytes, hex!("01014200"));
+        assert_eq!(bytes.len(), circle.ssz_bytes_len());
+        assert_eq!(
+            Circle::from_ssz_bytes(&bytes).expect("Valid circle"),
+            circle
+        );
+        assert_eq!(
+            circle.tree_hash_root(),
+            Shape::from(circle.clone()).tree_hash_root()
+        );
+
+        let circle = Circle {
+            color: 1,
+            radius: None,
+        };
+        assert_eq!(circle.as_ssz_bytes(), hex!("0001"));
+        assert_eq!(
+            Circle::from_ssz_bytes(&circle.as_ssz_bytes()).expect("Valid circle"),
+            circle
+        );
+    }
+
+    #[test]
+    fn test_profile_conversion_checks_fields() {
+        let square = Shape {
+            side: Some(0x42),
+            color: Some(1),
+            radius: None,
+        };
+        assert!(Circle::try_from(square).is_err());
+
+        let colorless = Shape {
+            side: None,
+            color: None,
+            radius: Some(0x42),
+        };
+        assert!(Circle::try_from(colorless).is_err());
+    }
+}
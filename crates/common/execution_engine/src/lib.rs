@@ -1,15 +1,24 @@
+pub mod metrics;
 pub mod rpc_types;
 pub mod utils;
 
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc, time::Instant};
 
 use alloy_primitives::{Address, B64, B256, Bytes, U64, hex};
-use alloy_rpc_types_eth::{Block, BlockId, BlockNumberOrTag, Filter, Log, TransactionRequest};
-use anyhow::anyhow;
+use alloy_rpc_types_eth::{
+    Block, BlockId, BlockNumberOrTag, Filter, Log, TransactionInput, TransactionRequest,
+};
+use anyhow::{anyhow, ensure};
 use async_trait::async_trait;
 use jsonwebtoken::{EncodingKey, Header, encode, get_current_timestamp};
+use metrics::{EngineMetrics, RequestOutcome, request_timeout};
+use parking_lot::Mutex;
 use ream_consensus_beacon::{
-    electra::execution_payload::ExecutionPayload,
+    electra::{
+        beacon_block::SignedBeaconBlock, blinded_beacon_block::SignedBlindedBeaconBlock,
+        execution_payload::ExecutionPayload,
+    },
+    eth_1_block::Eth1Block,
     execution_engine::{
         engine_trait::ExecutionApi, new_payload_request::NewPayloadRequest,
         rpc_types::get_blobs::BlobAndProofV1,
@@ -25,18 +34,33 @@ use rpc_types::{
     execution_payload::ExecutionPayloadV3,
     forkchoice_update::{ForkchoiceStateV1, ForkchoiceUpdateResult, PayloadAttributesV3},
     get_payload::PayloadV4,
+    payload_bodies::ExecutionPayloadBodyV1,
     payload_status::{PayloadStatus, PayloadStatusV1},
 };
+use serde::de::DeserializeOwned;
 use serde_json::json;
 use ssz::Encode;
 use ssz_types::VariableList;
+use tokio::time::timeout;
+use tracing::{debug, warn};
 use utils::{Claims, JsonRpcRequest, JsonRpcResponse, blob_versioned_hashes, strip_prefix};
 
+/// The maximum number of payload bodies requested at once, which every execution engine must
+/// serve.
+const MAX_PAYLOAD_BODIES_PER_REQUEST: usize = 32;
+
+/// Selector of `get_deposit_root()` of the deposit contract.
+const GET_DEPOSIT_ROOT_SELECTOR: [u8; 4] = [0xc5, 0xf2, 0x89, 0x2f];
+
+/// Selector of `get_deposit_count()` of the deposit contract.
+const GET_DEPOSIT_COUNT_SELECTOR: [u8; 4] = [0x62, 0x1f, 0xd1, 0x30];
+
 #[derive(Clone)]
 pub struct ExecutionEngine {
     http_client: Client,
     jwt_encoding_key: EncodingKey,
     engine_api_url: Url,
+    metrics: Arc<Mutex<EngineMetrics>>,
 }
 
 impl ExecutionEngine {
@@ -47,6 +71,7 @@ impl ExecutionEngine {
             http_client: Client::new(),
             jwt_encoding_key: EncodingKey::from_secret(jwt_private_key.as_slice()),
             engine_api_url,
+            metrics: Arc::new(Mutex::new(EngineMetrics::default())),
         })
     }
 
@@ -105,6 +130,51 @@ impl ExecutionEngine {
             .build()?)
     }
 
+    /// Sends a request to the engine within the timeout budget of its method, and records the
+    /// latency and outcome of the call.
+    pub async fn send_request<T: DeserializeOwned>(
+        &self,
+        rpc_request: JsonRpcRequest,
+    ) -> anyhow::Result<T> {
+        let method = rpc_request.method.clone();
+        let budget = request_timeout(&method);
+        let http_post_request = self.build_request(rpc_request)?;
+
+        let start = Instant::now();
+        let result = timeout(budget, async {
+            self.http_client
+                .execute(http_post_request)
+                .await?
+                .json::<JsonRpcResponse<T>>()
+                .await?
+                .to_result()
+        })
+        .await;
+        let latency = start.elapsed();
+
+        let (outcome, result) = match result {
+            Ok(Ok(response)) => (RequestOutcome::Success, Ok(response)),
+            Ok(Err(err)) => {
+                debug!("Engine call {method} failed after {latency:?}: {err}");
+                (RequestOutcome::Error, Err(err))
+            }
+            Err(_) => {
+                warn!("Engine call {method} overran its budget of {budget:?}");
+                (
+                    RequestOutcome::Timeout,
+                    Err(anyhow!("Engine call {method} timed out after {budget:?}")),
+                )
+            }
+        };
+        self.metrics.lock().observe(&method, latency, outcome);
+        result
+    }
+
+    /// Returns a snapshot of the metrics of the engine calls.
+    pub fn metrics(&self) -> EngineMetrics {
+        self.metrics.lock().clone()
+    }
+
     pub async fn eth_syncing(&self) -> anyhow::Result<EthSyncing> {
         let request_body = JsonRpcRequest {
             id: 1,
@@ -113,14 +183,7 @@ impl ExecutionEngine {
             params: vec![],
         };
 
-        let http_post_request = self.build_request(request_body)?;
-
-        self.http_client
-            .execute(http_post_request)
-            .await?
-            .json::<JsonRpcResponse<EthSyncing>>()
-            .await?
-            .to_result()
+        self.send_request(request_body).await
     }
 
     pub async fn eth_block_number(&self) -> anyhow::Result<B64> {
@@ -131,14 +194,7 @@ impl ExecutionEngine {
             params: vec![],
         };
 
-        let http_post_request = self.build_request(request_body)?;
-
-        self.http_client
-            .execute(http_post_request)
-            .await?
-            .json::<JsonRpcResponse<B64>>()
-            .await?
-            .to_result()
+        self.send_request(request_body).await
     }
 
     pub async fn eth_chain_id(&self) -> anyhow::Result<U64> {
@@ -149,14 +205,7 @@ impl ExecutionEngine {
             params: vec![],
         };
 
-        let http_post_request = self.build_request(request_body)?;
-
-        self.http_client
-            .execute(http_post_request)
-            .await?
-            .json::<JsonRpcResponse<U64>>()
-            .await?
-            .to_result()
+        self.send_request(request_body).await
     }
 
     pub async fn eth_get_block_by_number(
@@ -171,14 +220,68 @@ impl ExecutionEngine {
             params: vec![json!(block_number_or_tag), json!(hydrated)],
         };
 
-        let http_post_request = self.build_request(request_body)?;
+        self.send_request(request_body).await
+    }
 
-        self.http_client
-            .execute(http_post_request)
-            .await?
-            .json::<JsonRpcResponse<Block>>()
+    /// Returns the eth1 block with `block_number` together with the state of the deposit contract
+    /// at that block.
+    pub async fn get_eth1_block(
+        &self,
+        deposit_contract_address: Address,
+        block_number: u64,
+    ) -> anyhow::Result<Eth1Block> {
+        let block_id = BlockId::Number(BlockNumberOrTag::Number(block_number));
+        let block = self
+            .eth_get_block_by_number(BlockNumberOrTag::Number(block_number), false)
+            .await?;
+
+        let deposit_root = self
+            .eth_call(
+                TransactionRequest::default()
+                    .to(deposit_contract_address)
+                    .input(TransactionInput::new(Bytes::from_static(
+                        &GET_DEPOSIT_ROOT_SELECTOR,
+                    ))),
+                Some(block_id),
+            )
+            .await?;
+        ensure!(
+            deposit_root.len() == 32,
+            "Invalid deposit root length: {}",
+            deposit_root.len()
+        );
+
+        // The deposit count is returned as ABI encoded bytes: an offset, a length and the little
+        // endian count
+        let deposit_count = self
+            .eth_call(
+                TransactionRequest::default()
+                    .to(deposit_contract_address)
+                    .input(TransactionInput::new(Bytes::from_static(
+                        &GET_DEPOSIT_COUNT_SELECTOR,
+                    ))),
+                Some(block_id),
+            )
+            .await?;
+        let deposit_count = deposit_count
+            .get(64..72)
+            .ok_or_else(|| anyhow!("Invalid deposit count length: {}", deposit_count.len()))?;
+
+        Ok(Eth1Block {
+            number: block.header.number,
+            timestamp: block.header.timestamp,
+            deposit_root: B256::from_slice(&deposit_root),
+            deposit_count: u64::from_le_bytes(deposit_count.try_into()?),
+            block_hash: block.header.hash,
+        })
+    }
+
+    pub async fn get_latest_block_number(&self) -> anyhow::Result<u64> {
+        Ok(self
+            .eth_get_block_by_number(BlockNumberOrTag::Latest, false)
             .await?
-            .to_result()
+            .header
+            .number)
     }
 
     pub async fn eth_get_block_by_hash(
@@ -193,14 +296,7 @@ impl ExecutionEngine {
             params: vec![json!(block_hash), json!(hydrated)],
         };
 
-        let http_post_request = self.build_request(request_body)?;
-
-        self.http_client
-            .execute(http_post_request)
-            .await?
-            .json::<JsonRpcResponse<Block>>()
-            .await?
-            .to_result()
+        self.send_request(request_body).await
     }
 
     pub async fn eth_get_logs(&self, filter: Filter) -> anyhow::Result<Vec<Log>> {
@@ -211,14 +307,7 @@ impl ExecutionEngine {
             params: vec![json!(filter)],
         };
 
-        let http_post_request = self.build_request(request_body)?;
-
-        self.http_client
-            .execute(http_post_request)
-            .await?
-            .json::<JsonRpcResponse<Vec<Log>>>()
-            .await?
-            .to_result()
+        self.send_request(request_body).await
     }
 
     pub async fn eth_call(
@@ -238,14 +327,7 @@ impl ExecutionEngine {
             params,
         };
 
-        let http_post_request = self.build_request(request_body)?;
-
-        self.http_client
-            .execute(http_post_request)
-            .await?
-            .json::<JsonRpcResponse<Bytes>>()
-            .await?
-            .to_result()
+        self.send_request(request_body).await
     }
 
     pub async fn eth_send_raw_transaction(&self, transaction: Bytes) -> anyhow::Result<B256> {
@@ -256,14 +338,7 @@ impl ExecutionEngine {
             params: vec![json!(transaction)],
         };
 
-        let http_post_request = self.build_request(request_body)?;
-
-        self.http_client
-            .execute(http_post_request)
-            .await?
-            .json::<JsonRpcResponse<B256>>()
-            .await?
-            .to_result()
+        self.send_request(request_body).await
     }
 
     pub async fn eth_get_code(&self, address: Address, block_id: BlockId) -> anyhow::Result<Bytes> {
@@ -274,20 +349,15 @@ impl ExecutionEngine {
             params: vec![json!(address), json!(block_id)],
         };
 
-        let http_post_request = self.build_request(request_body)?;
-
-        self.http_client
-            .execute(http_post_request)
-            .await?
-            .json::<JsonRpcResponse<Bytes>>()
-            .await?
-            .to_result()
+        self.send_request(request_body).await
     }
 
     pub async fn engine_exchange_capabilities(&self) -> anyhow::Result<Vec<String>> {
         let capabilities: Vec<String> = vec![
             "engine_forkchoiceUpdatedV3".to_string(),
             "engine_getBlobsV1".to_string(),
+            "engine_getPayloadBodiesByHashV1".to_string(),
+            "engine_getPayloadBodiesByRangeV1".to_string(),
             "engine_getPayloadV4".to_string(),
             "engine_newPayloadV4".to_string(),
         ];
@@ -298,14 +368,7 @@ impl ExecutionEngine {
             params: vec![json!(capabilities)],
         };
 
-        let http_post_request = self.build_request(request_body)?;
-
-        self.http_client
-            .execute(http_post_request)
-            .await?
-            .json::<JsonRpcResponse<Vec<String>>>()
-            .await?
-            .to_result()
+        self.send_request(request_body).await
     }
 
     pub async fn engine_get_payload_v4(&self, payload_id: B64) -> anyhow::Result<PayloadV4> {
@@ -316,14 +379,79 @@ impl ExecutionEngine {
             params: vec![json!(payload_id)],
         };
 
-        let http_post_request = self.build_request(request_body)?;
+        self.send_request(request_body).await
+    }
 
-        self.http_client
-            .execute(http_post_request)
-            .await?
-            .json::<JsonRpcResponse<PayloadV4>>()
-            .await?
-            .to_result()
+    /// Returns the bodies of the payloads with `block_hashes`, in the same order. The body of an
+    /// unknown payload is `None`.
+    pub async fn engine_get_payload_bodies_by_hash_v1(
+        &self,
+        block_hashes: Vec<B256>,
+    ) -> anyhow::Result<Vec<Option<ExecutionPayloadBodyV1>>> {
+        let request_body = JsonRpcRequest {
+            id: 1,
+            jsonrpc: "2.0".to_string(),
+            method: "engine_getPayloadBodiesByHashV1".to_string(),
+            params: vec![json!(block_hashes)],
+        };
+
+        self.send_request(request_body).await
+    }
+
+    /// Returns the bodies of the canonical payloads from block number `start`. The response stops
+    /// early at the latest known block.
+    pub async fn engine_get_payload_bodies_by_range_v1(
+        &self,
+        start: u64,
+        count: u64,
+    ) -> anyhow::Result<Vec<Option<ExecutionPayloadBodyV1>>> {
+        let request_body = JsonRpcRequest {
+            id: 1,
+            jsonrpc: "2.0".to_string(),
+            method: "engine_getPayloadBodiesByRangeV1".to_string(),
+            params: vec![json!(U64::from(start)), json!(U64::from(count))],
+        };
+
+        self.send_request(request_body).await
+    }
+
+    /// Rebuilds full blocks from blinded blocks with the payload bodies known to the execution
+    /// engine, failing if a payload is unknown or does not match the header of its block.
+    pub async fn reconstruct_blocks(
+        &self,
+        blinded_blocks: Vec<SignedBlindedBeaconBlock>,
+    ) -> anyhow::Result<Vec<SignedBeaconBlock>> {
+        let mut blocks = Vec::with_capacity(blinded_blocks.len());
+        for chunk in blinded_blocks.chunks(MAX_PAYLOAD_BODIES_PER_REQUEST) {
+            let block_hashes = chunk
+                .iter()
+                .map(|block| block.message.body.execution_payload_header.block_hash)
+                .collect::<Vec<_>>();
+            let payload_bodies = self
+                .engine_get_payload_bodies_by_hash_v1(block_hashes)
+                .await?;
+            ensure!(
+                payload_bodies.len() == chunk.len(),
+                "Expected {} payload bodies, got {}",
+                chunk.len(),
+                payload_bodies.len()
+            );
+
+            for (blinded_block, payload_body) in chunk.iter().zip(payload_bodies) {
+                let header = &blinded_block.message.body.execution_payload_header;
+                let payload_body = payload_body.ok_or_else(|| {
+                    anyhow!(
+                        "Execution engine does not know payload {}",
+                        header.block_hash
+                    )
+                })?;
+                blocks.push(
+                    blinded_block
+                        .to_signed_beacon_block(payload_body.to_execution_payload(header))?,
+                );
+            }
+        }
+        Ok(blocks)
     }
 
     pub async fn engine_new_payload_v4(
@@ -345,14 +473,7 @@ impl ExecutionEngine {
             ],
         };
 
-        let http_post_request = self.build_request(request_body)?;
-
-        self.http_client
-            .execute(http_post_request)
-            .await?
-            .json::<JsonRpcResponse<PayloadStatusV1>>()
-            .await?
-            .to_result()
+        self.send_request(request_body).await
     }
 
     pub async fn engine_forkchoice_updated_v3(
@@ -367,14 +488,7 @@ impl ExecutionEngine {
             params: vec![json!(forkchoice_state), json!(payload_attributes)],
         };
 
-        let http_post_request = self.build_request(request_body)?;
-
-        self.http_client
-            .execute(http_post_request)
-            .await?
-            .json::<JsonRpcResponse<ForkchoiceUpdateResult>>()
-            .await?
-            .to_result()
+        self.send_request(request_body).await
     }
 }
 
@@ -461,13 +575,6 @@ impl ExecutionApi for ExecutionEngine {
             params: vec![json!(blob_version_hashes)],
         };
 
-        let http_post_request = self.build_request(request_body)?;
-
-        self.http_client
-            .execute(http_post_request)
-            .await?
-            .json::<JsonRpcResponse<Vec<Option<BlobAndProofV1>>>>()
-            .await?
-            .to_result()
+        self.send_request(request_body).await
     }
 }
@@ -0,0 +1,129 @@
+use std::{collections::HashMap, time::Duration};
+
+use ream_consensus_misc::constants::INTERVALS_PER_SLOT;
+use ream_network_spec::networks::beacon_network_spec;
+
+/// Upper bounds of the latency buckets in milliseconds, a call slower than the last bound is
+/// counted in an extra overflow bucket.
+pub const ENGINE_LATENCY_BUCKETS_MS: [u64; 8] = [5, 10, 25, 50, 100, 250, 1000, 4000];
+
+/// Returns the time an engine call may take before it is abandoned.
+///
+/// `engine_getPayload` must return before the attestation deadline of the slot leaves no time to
+/// broadcast the block, so it gets a quarter of the first interval of the slot, one second on
+/// mainnet. Importing and fork choice updates may take until the end of the second interval.
+pub fn request_timeout(method: &str) -> Duration {
+    let seconds_per_slot = beacon_network_spec().seconds_per_slot;
+    let interval = Duration::from_secs(seconds_per_slot) / INTERVALS_PER_SLOT as u32;
+    match method {
+        method if method.starts_with("engine_getPayloadV") => interval / 4,
+        method if method.starts_with("engine_newPayload") => interval * 2,
+        method if method.starts_with("engine_forkchoiceUpdated") => interval * 2,
+        "engine_getBlobsV1" | "engine_exchangeCapabilities" => interval / 4,
+        _ => Duration::from_secs(seconds_per_slot),
+    }
+}
+
+/// The outcome of an engine call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestOutcome {
+    Success,
+    Error,
+    Timeout,
+}
+
+/// Latency and outcomes of the calls to one engine method.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MethodMetrics {
+    latency_counts: [u64; ENGINE_LATENCY_BUCKETS_MS.len() + 1],
+    pub successes: u64,
+    pub errors: u64,
+    pub timeouts: u64,
+}
+
+impl MethodMetrics {
+    pub fn observe(&mut self, latency: Duration, outcome: RequestOutcome) {
+        let latency_ms = latency.as_millis() as u64;
+        let bucket = ENGINE_LATENCY_BUCKETS_MS
+            .iter()
+            .position(|&bound| latency_ms <= bound)
+            .unwrap_or(ENGINE_LATENCY_BUCKETS_MS.len());
+        self.latency_counts[bucket] += 1;
+        match outcome {
+            RequestOutcome::Success => self.successes += 1,
+            RequestOutcome::Error => self.errors += 1,
+            RequestOutcome::Timeout => self.timeouts += 1,
+        }
+    }
+
+    pub fn latency_counts(&self) -> &[u64] {
+        &self.latency_counts
+    }
+
+    pub fn total(&self) -> u64 {
+        self.latency_counts.iter().sum()
+    }
+}
+
+/// Metrics of the engine calls, by method.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EngineMetrics {
+    methods: HashMap<String, MethodMetrics>,
+}
+
+impl EngineMetrics {
+    pub fn observe(&mut self, method: &str, latency: Duration, outcome: RequestOutcome) {
+        self.methods
+            .entry(method.to_string())
+            .or_default()
+            .observe(latency, outcome);
+    }
+
+    pub fn method(&self, method: &str) -> Option<&MethodMetrics> {
+        self.methods.get(method)
+    }
+
+    pub fn methods(&self) -> impl Iterator<Item = (&String, &MethodMetrics)> {
+        self.methods.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine_metrics_buckets() {
+        let mut engine_metrics = EngineMetrics::default();
+        for (latency_ms, outcome) in [
+            (1, RequestOutcome::Success),
+            (10, RequestOutcome::Success),
+            (11, RequestOutcome::Error),
+            (999, RequestOutcome::Success),
+            (1001, RequestOutcome::Timeout),
+            (5000, RequestOutcome::Timeout),
+        ] {
+            engine_metrics.observe(
+                "engine_getPayloadV4",
+                Duration::from_millis(latency_ms),
+                outcome,
+            );
+        }
+
+        let method_metrics = engine_metrics.method("engine_getPayloadV4").unwrap();
+        assert_eq!(
+            method_metrics.latency_counts(),
+            &[1, 1, 1, 0, 0, 0, 1, 1, 1]
+        );
+        assert_eq!(method_metrics.total(), 6);
+        assert_eq!(
+            (
+                method_metrics.successes,
+                method_metrics.errors,
+                method_metrics.timeouts
+            ),
+            (3, 1, 2)
+        );
+        assert!(engine_metrics.method("engine_newPayloadV4").is_none());
+    }
+}
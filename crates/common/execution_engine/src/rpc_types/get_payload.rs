@@ -1,12 +1,12 @@
 use alloy_primitives::{B256, Bytes};
-use ream_consensus_beacon::polynomial_commitments::kzg_commitment::KZGCommitment;
+use ream_consensus_beacon::{
+    execution_engine::rpc_types::get_blobs::Blob,
+    polynomial_commitments::{kzg_commitment::KZGCommitment, kzg_proof::KZGProof},
+};
+use ream_consensus_misc::preset::MaxBlobCommitmentsPerBlock;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use ssz_types::{
-    VariableList,
-    serde_utils::list_of_hex_var_list,
-    typenum::{U96, U1024, U1048576},
-};
+use ssz_types::VariableList;
 use tree_hash_derive::TreeHash;
 
 use super::execution_payload::ExecutionPayloadV3;
@@ -14,11 +14,9 @@ use super::execution_payload::ExecutionPayloadV3;
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
 #[serde(rename_all = "camelCase")]
 pub struct BlobsBundleV1 {
-    pub blobs: VariableList<KZGCommitment, U1048576>,
-    #[serde(with = "list_of_hex_var_list")]
-    pub commitments: VariableList<VariableList<u8, U96>, U1024>,
-    #[serde(with = "list_of_hex_var_list")]
-    pub proofs: VariableList<VariableList<u8, U96>, U1024>,
+    pub commitments: VariableList<KZGCommitment, MaxBlobCommitmentsPerBlock>,
+    pub proofs: VariableList<KZGProof, MaxBlobCommitmentsPerBlock>,
+    pub blobs: VariableList<Blob, MaxBlobCommitmentsPerBlock>,
 }
 
 #[derive(Deserialize, Debug)]
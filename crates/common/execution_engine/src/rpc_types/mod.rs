@@ -2,4 +2,5 @@ pub mod eth_syncing;
 pub mod execution_payload;
 pub mod forkchoice_update;
 pub mod get_payload;
+pub mod payload_bodies;
 pub mod payload_status;
@@ -0,0 +1,47 @@
+use ream_consensus_beacon::{
+    electra::{
+        execution_payload::{ExecutionPayload, Transactions},
+        execution_payload_header::ExecutionPayloadHeader,
+    },
+    withdrawal::Withdrawal,
+};
+use ream_consensus_misc::preset::MaxWithdrawalsPerPayload;
+use serde::{Deserialize, Serialize};
+use ssz_types::{VariableList, serde_utils::list_of_hex_var_list};
+
+/// The parts of an execution payload which are not committed to in full by its header, as returned
+/// by `engine_getPayloadBodiesByHashV1` and `engine_getPayloadBodiesByRangeV1`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionPayloadBodyV1 {
+    #[serde(with = "list_of_hex_var_list")]
+    pub transactions: Transactions,
+    pub withdrawals: Option<VariableList<Withdrawal, MaxWithdrawalsPerPayload>>,
+}
+
+impl ExecutionPayloadBodyV1 {
+    /// Rebuilds the execution payload of `header`. The payload is only the one committed to by the
+    /// header if the roots of its transactions and withdrawals match the header, which the caller
+    /// has to check.
+    pub fn to_execution_payload(self, header: &ExecutionPayloadHeader) -> ExecutionPayload {
+        ExecutionPayload {
+            parent_hash: header.parent_hash,
+            fee_recipient: header.fee_recipient,
+            state_root: header.state_root,
+            receipts_root: header.receipts_root,
+            logs_bloom: header.logs_bloom.clone(),
+            prev_randao: header.prev_randao,
+            block_number: header.block_number,
+            gas_limit: header.gas_limit,
+            gas_used: header.gas_used,
+            timestamp: header.timestamp,
+            extra_data: header.extra_data.clone(),
+            base_fee_per_gas: header.base_fee_per_gas,
+            block_hash: header.block_hash,
+            transactions: self.transactions,
+            withdrawals: self.withdrawals.unwrap_or_default(),
+            blob_gas_used: header.blob_gas_used,
+            excess_blob_gas: header.excess_blob_gas,
+        }
+    }
+}
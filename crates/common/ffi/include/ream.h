@@ -0,0 +1,66 @@
+/*
+ * C ABI of ream's state transition, SSZ hashing and fork choice, implemented by the ream-ffi
+ * crate. Values are passed SSZ encoded.
+ *
+ * Functions return REAM_OK on success and REAM_ERROR on failure, in which case ream_last_error
+ * describes the failure. ream_initialize_network must be called first.
+ */
+
+#ifndef REAM_H
+#define REAM_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#define REAM_OK 0
+#define REAM_ERROR -1
+
+typedef enum {
+    REAM_SSZ_BEACON_STATE = 0,
+    REAM_SSZ_BEACON_BLOCK = 1,
+    REAM_SSZ_BEACON_BLOCK_BODY = 2,
+    REAM_SSZ_ATTESTATION = 3,
+    REAM_SSZ_CHECKPOINT = 4,
+} ReamSszType;
+
+/* Bytes allocated by ream, released with ream_buffer_free. */
+typedef struct {
+    uint8_t *data;
+    size_t len;
+} ReamBuffer;
+
+typedef struct ForkChoice ReamForkChoice;
+
+/* The error of the last failed call on this thread, valid until the next failed call. */
+const char *ream_last_error(void);
+
+void ream_buffer_free(ReamBuffer buffer);
+
+/* network is "mainnet", "holesky", "sepolia", "hoodi", "dev" or the path to a config file. */
+int32_t ream_initialize_network(const char *network);
+
+int32_t ream_state_transition(const uint8_t *state, size_t state_len,
+                              const uint8_t *signed_block, size_t signed_block_len,
+                              bool validate_result, ReamBuffer *post_state);
+
+int32_t ream_hash_tree_root(uint32_t ssz_type, const uint8_t *data, size_t len,
+                            uint8_t root[32]);
+
+int32_t ream_fork_choice_new(const uint8_t *anchor_state, size_t anchor_state_len,
+                             const uint8_t *anchor_block, size_t anchor_block_len,
+                             ReamForkChoice **fork_choice);
+
+void ream_fork_choice_free(ReamForkChoice *fork_choice);
+
+int32_t ream_fork_choice_on_tick(ReamForkChoice *fork_choice, uint64_t time);
+
+int32_t ream_fork_choice_on_block(ReamForkChoice *fork_choice, const uint8_t *signed_block,
+                                  size_t len);
+
+int32_t ream_fork_choice_on_attestation(ReamForkChoice *fork_choice, const uint8_t *attestation,
+                                        size_t len, bool is_from_block);
+
+int32_t ream_fork_choice_get_head(ReamForkChoice *fork_choice, uint8_t root[32]);
+
+#endif /* REAM_H */
@@ -0,0 +1,337 @@
+//! The C ABI of the bindings, declared in `include/ream.h`.
+//!
+//! Functions return `REAM_OK` on success and `REAM_ERROR` on failure, in which case
+//! `ream_last_error` describes the failure. Buffers returned by ream must be released with
+//! `ream_buffer_free`, and fork choice stores with `ream_fork_choice_free`.
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    ffi::{CStr, CString, c_char},
+    panic::{AssertUnwindSafe, catch_unwind},
+    ptr, slice,
+};
+
+use anyhow::anyhow;
+
+use crate::{
+    fork_choice::ForkChoice,
+    network::initialize_network,
+    ssz_type::{SszType, hash_tree_root},
+    state_transition::state_transition,
+};
+
+pub const REAM_OK: i32 = 0;
+pub const REAM_ERROR: i32 = -1;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Bytes allocated by ream.
+#[repr(C)]
+pub struct ReamBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+impl ReamBuffer {
+    fn new(bytes: Vec<u8>) -> Self {
+        let len = bytes.len();
+        let data = Box::into_raw(bytes.into_boxed_slice()) as *mut u8;
+        Self { data, len }
+    }
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic")
+}
+
+/// Runs `f`, turning its errors and panics into `REAM_ERROR` and the last error.
+fn run(f: impl FnOnce() -> anyhow::Result<()>) -> i32 {
+    let result = catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|panic| Err(anyhow!("Panicked: {}", panic_message(&*panic))));
+    let error = match result {
+        Ok(()) => return REAM_OK,
+        Err(err) => format!("{err:#}"),
+    };
+    LAST_ERROR.with(|last_error| {
+        *last_error.borrow_mut() =
+            Some(CString::new(error.replace('\0', " ")).expect("Interior nul bytes were replaced"))
+    });
+    REAM_ERROR
+}
+
+/// # Safety
+///
+/// `data` must be valid for reads of `len` bytes, or `len` must be 0.
+unsafe fn bytes<'a>(data: *const u8, len: usize) -> anyhow::Result<&'a [u8]> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    if data.is_null() {
+        return Err(anyhow!("Null pointer for {len} bytes"));
+    }
+    // SAFETY: guaranteed by the caller
+    Ok(unsafe { slice::from_raw_parts(data, len) })
+}
+
+/// # Safety
+///
+/// `pointer` must be null or valid for writes.
+unsafe fn write<T>(pointer: *mut T, value: T) -> anyhow::Result<()> {
+    if pointer.is_null() {
+        return Err(anyhow!("Null output pointer"));
+    }
+    // SAFETY: guaranteed by the caller
+    unsafe { pointer.write(value) };
+    Ok(())
+}
+
+/// # Safety
+///
+/// `fork_choice` must be null or returned by `ream_fork_choice_new` and not freed.
+unsafe fn fork_choice_mut<'a>(fork_choice: *mut ForkChoice) -> anyhow::Result<&'a mut ForkChoice> {
+    // SAFETY: guaranteed by the caller
+    unsafe { fork_choice.as_mut() }.ok_or_else(|| anyhow!("Null fork choice"))
+}
+
+/// Returns the error of the last failed call on this thread, or null. The string is valid until
+/// the next failed call on this thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn ream_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| {
+        last_error
+            .borrow()
+            .as_ref()
+            .map_or(ptr::null(), |error| error.as_ptr())
+    })
+}
+
+/// # Safety
+///
+/// `buffer` must be returned by ream and not freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ream_buffer_free(buffer: ReamBuffer) {
+    if !buffer.data.is_null() {
+        // SAFETY: the buffer was allocated by `ReamBuffer::new` with this length
+        drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(buffer.data, buffer.len)) });
+    }
+}
+
+/// # Safety
+///
+/// `network` must be a nul-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ream_initialize_network(network: *const c_char) -> i32 {
+    run(|| {
+        if network.is_null() {
+            return Err(anyhow!("Null network"));
+        }
+        // SAFETY: guaranteed by the caller
+        let network = unsafe { CStr::from_ptr(network) }.to_str()?;
+        initialize_network(network)
+    })
+}
+
+/// # Safety
+///
+/// The inputs must be valid for reads of their lengths, and `post_state` for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ream_state_transition(
+    state: *const u8,
+    state_len: usize,
+    signed_block: *const u8,
+    signed_block_len: usize,
+    validate_result: bool,
+    post_state: *mut ReamBuffer,
+) -> i32 {
+    run(|| {
+        // SAFETY: guaranteed by the caller
+        unsafe {
+            let result = state_transition(
+                bytes(state, state_len)?,
+                bytes(signed_block, signed_block_len)?,
+                validate_result,
+            )?;
+            write(post_state, ReamBuffer::new(result))
+        }
+    })
+}
+
+/// # Safety
+///
+/// `data` must be valid for reads of `len` bytes, and `root` for writes of 32 bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ream_hash_tree_root(
+    ssz_type: u32,
+    data: *const u8,
+    len: usize,
+    root: *mut [u8; 32],
+) -> i32 {
+    run(|| {
+        let ssz_type = SszType::try_from(ssz_type)?;
+        // SAFETY: guaranteed by the caller
+        unsafe { write(root, hash_tree_root(ssz_type, bytes(data, len)?)?.0) }
+    })
+}
+
+/// # Safety
+///
+/// The anchors must be valid for reads of their lengths, and `fork_choice` for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ream_fork_choice_new(
+    anchor_state: *const u8,
+    anchor_state_len: usize,
+    anchor_block: *const u8,
+    anchor_block_len: usize,
+    fork_choice: *mut *mut ForkChoice,
+) -> i32 {
+    run(|| {
+        // SAFETY: guaranteed by the caller
+        unsafe {
+            let store = ForkChoice::new(
+                bytes(anchor_state, anchor_state_len)?,
+                bytes(anchor_block, anchor_block_len)?,
+            )?;
+            write(fork_choice, Box::into_raw(Box::new(store)))
+        }
+    })
+}
+
+/// # Safety
+///
+/// `fork_choice` must be null or returned by `ream_fork_choice_new` and not freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ream_fork_choice_free(fork_choice: *mut ForkChoice) {
+    if !fork_choice.is_null() {
+        // SAFETY: guaranteed by the caller
+        drop(unsafe { Box::from_raw(fork_choice) });
+    }
+}
+
+/// # Safety
+///
+/// `fork_choice` must be returned by `ream_fork_choice_new` and not freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ream_fork_choice_on_tick(fork_choice: *mut ForkChoice, time: u64) -> i32 {
+    // SAFETY: guaranteed by the caller
+    run(|| unsafe { fork_choice_mut(fork_choice) }?.on_tick(time))
+}
+
+/// # Safety
+///
+/// `fork_choice` must be returned by `ream_fork_choice_new` and not freed, and `signed_block`
+/// valid for reads of `len` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ream_fork_choice_on_block(
+    fork_choice: *mut ForkChoice,
+    signed_block: *const u8,
+    len: usize,
+) -> i32 {
+    run(|| {
+        // SAFETY: guaranteed by the caller
+        unsafe { fork_choice_mut(fork_choice)?.on_block(bytes(signed_block, len)?) }
+    })
+}
+
+/// # Safety
+///
+/// `fork_choice` must be returned by `ream_fork_choice_new` and not freed, and `attestation`
+/// valid for reads of `len` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ream_fork_choice_on_attestation(
+    fork_choice: *mut ForkChoice,
+    attestation: *const u8,
+    len: usize,
+    is_from_block: bool,
+) -> i32 {
+    run(|| {
+        // SAFETY: guaranteed by the caller
+        unsafe {
+            fork_choice_mut(fork_choice)?.on_attestation(bytes(attestation, len)?, is_from_block)
+        }
+    })
+}
+
+/// # Safety
+///
+/// `fork_choice` must be returned by `ream_fork_choice_new` and not freed, and `root` valid for
+/// writes of 32 bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ream_fork_choice_get_head(
+    fork_choice: *mut ForkChoice,
+    root: *mut [u8; 32],
+) -> i32 {
+    run(|| {
+        // SAFETY: guaranteed by the caller
+        unsafe {
+            let head = fork_choice_mut(fork_choice)?.get_head()?;
+            write(root, head.0)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::B256;
+    use ream_consensus_misc::checkpoint::Checkpoint;
+    use ssz::Encode;
+    use tree_hash::TreeHash;
+
+    use super::*;
+
+    #[test]
+    fn test_hash_tree_root() {
+        let checkpoint = Checkpoint {
+            epoch: 1,
+            root: B256::repeat_byte(2),
+        }
+        .as_ssz_bytes();
+        let mut root = [0; 32];
+
+        let result = unsafe {
+            ream_hash_tree_root(
+                SszType::Checkpoint as u32,
+                checkpoint.as_ptr(),
+                checkpoint.len(),
+                &mut root,
+            )
+        };
+
+        assert_eq!(result, REAM_OK);
+        assert_eq!(
+            B256::from(root),
+            Checkpoint {
+                epoch: 1,
+                root: B256::repeat_byte(2),
+            }
+            .tree_hash_root()
+        );
+    }
+
+    #[test]
+    fn test_failure_sets_last_error() {
+        let mut root = [0; 32];
+
+        let result = unsafe { ream_hash_tree_root(42, ptr::null(), 0, &mut root) };
+
+        assert_eq!(result, REAM_ERROR);
+        let error = unsafe { CStr::from_ptr(ream_last_error()) };
+        assert_eq!(error.to_str().unwrap(), "Unknown SSZ type 42");
+    }
+
+    #[test]
+    fn test_fork_choice_rejects_null() {
+        let mut root = [0; 32];
+
+        assert_eq!(
+            unsafe { ream_fork_choice_get_head(ptr::null_mut(), &mut root) },
+            REAM_ERROR
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use std::{
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use alloy_primitives::B256;
+use anyhow::bail;
+use ream_consensus_beacon::{
+    attestation::Attestation,
+    electra::{
+        beacon_block::{BeaconBlock, SignedBeaconBlock},
+        beacon_state::BeaconState,
+    },
+    execution_engine::mock_engine::MockExecutionEngine,
+};
+use ream_fork_choice::{
+    handlers::{on_attestation, on_block, on_tick},
+    store::{Store, get_forkchoice_store},
+};
+use ream_storage::db::ReamDB;
+use tempdir::TempDir;
+
+use crate::{network::ensure_network_initialized, ssz_type::decode};
+
+/// A fork choice store in a temporary database, driven by the fork choice handlers.
+///
+/// Like in the consensus spec tests, `on_block` does not process the attestations and slashings
+/// of the block, they are passed to `on_attestation` separately.
+pub struct ForkChoice {
+    store: Store,
+    _data_dir: TempDir,
+}
+
+impl ForkChoice {
+    pub fn new(anchor_state: &[u8], anchor_block: &[u8]) -> anyhow::Result<Self> {
+        ensure_network_initialized()?;
+        let anchor_state = decode::<BeaconState>(anchor_state)?;
+        let anchor_block = decode::<BeaconBlock>(anchor_block)?;
+
+        let data_dir = TempDir::new("ream_ffi_fork_choice")?;
+        let store = get_forkchoice_store(
+            anchor_state,
+            anchor_block,
+            ReamDB::new(data_dir.path().to_path_buf())?,
+        )?;
+
+        Ok(Self {
+            store,
+            _data_dir: data_dir,
+        })
+    }
+
+    pub fn on_tick(&mut self, time: u64) -> anyhow::Result<()> {
+        on_tick(&mut self.store, time)
+    }
+
+    /// Imports `signed_block` without checking the availability of its blobs.
+    pub fn on_block(&mut self, signed_block: &[u8]) -> anyhow::Result<()> {
+        let signed_block = decode::<SignedBeaconBlock>(signed_block)?;
+        complete(on_block(
+            &mut self.store,
+            &signed_block,
+            &None::<MockExecutionEngine>,
+            false,
+        ))?
+    }
+
+    pub fn on_attestation(
+        &mut self,
+        attestation: &[u8],
+        is_from_block: bool,
+    ) -> anyhow::Result<()> {
+        on_attestation(
+            &mut self.store,
+            decode::<Attestation>(attestation)?,
+            is_from_block,
+        )
+    }
+
+    pub fn get_head(&self) -> anyhow::Result<B256> {
+        self.store.get_head()
+    }
+}
+
+/// Runs a handler which awaits nothing without an execution engine, so it completes on its first
+/// poll.
+fn complete<F: Future>(future: F) -> anyhow::Result<F::Output> {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => Ok(output),
+        Poll::Pending => bail!("Handler awaited without an execution engine"),
+    }
+}
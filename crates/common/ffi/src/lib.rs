@@ -0,0 +1,13 @@
+//! Bindings around ream's state transition, SSZ hashing and fork choice, so that external test
+//! frameworks can drive them for differential testing.
+//!
+//! The C ABI is in [c_api] and declared in `include/ream.h`. Python bindings are built with the
+//! "python" feature. Every value crosses the boundary SSZ encoded.
+
+pub mod c_api;
+pub mod fork_choice;
+pub mod network;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod ssz_type;
+pub mod state_transition;
@@ -0,0 +1,26 @@
+use anyhow::{anyhow, ensure};
+use ream_network_spec::{
+    cli::beacon_network_parser,
+    networks::{HAS_NETWORK_SPEC_BEEN_INITIALIZED, beacon_network_spec, set_beacon_network_spec},
+};
+
+/// Sets the network the bindings run on, either the name of a known network such as "mainnet" or
+/// the path to a config file. It must be called before the other bindings, and a process can only
+/// run on one network.
+pub fn initialize_network(network: &str) -> anyhow::Result<()> {
+    let network_spec = beacon_network_parser(network).map_err(|err| anyhow!(err))?;
+    HAS_NETWORK_SPEC_BEEN_INITIALIZED.call_once(|| set_beacon_network_spec(network_spec.clone()));
+    ensure!(
+        beacon_network_spec() == network_spec,
+        "Already initialized with another network than {network}"
+    );
+    Ok(())
+}
+
+pub(crate) fn ensure_network_initialized() -> anyhow::Result<()> {
+    ensure!(
+        HAS_NETWORK_SPEC_BEEN_INITIALIZED.is_completed(),
+        "The network must be initialized first"
+    );
+    Ok(())
+}
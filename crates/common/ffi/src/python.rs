@@ -0,0 +1,82 @@
+//! Python bindings, importable as `ream_ffi` from the library built with the "python" feature.
+
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyBytes};
+
+use crate::{fork_choice::ForkChoice, ssz_type::SszType};
+
+fn to_py_err(err: anyhow::Error) -> PyErr {
+    PyValueError::new_err(format!("{err:#}"))
+}
+
+#[pyfunction]
+fn initialize_network(network: &str) -> PyResult<()> {
+    crate::network::initialize_network(network).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(signature = (state, signed_block, validate_result = true))]
+fn state_transition<'py>(
+    py: Python<'py>,
+    state: &[u8],
+    signed_block: &[u8],
+    validate_result: bool,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let post_state =
+        crate::state_transition::state_transition(state, signed_block, validate_result)
+            .map_err(to_py_err)?;
+    Ok(PyBytes::new(py, &post_state))
+}
+
+/// `ssz_type` is the name of the container, e.g. "BeaconState".
+#[pyfunction]
+fn hash_tree_root<'py>(
+    py: Python<'py>,
+    ssz_type: &str,
+    data: &[u8],
+) -> PyResult<Bound<'py, PyBytes>> {
+    let ssz_type = ssz_type.parse::<SszType>().map_err(to_py_err)?;
+    let root = crate::ssz_type::hash_tree_root(ssz_type, data).map_err(to_py_err)?;
+    Ok(PyBytes::new(py, root.as_slice()))
+}
+
+#[pyclass(name = "ForkChoice", unsendable)]
+struct PyForkChoice(ForkChoice);
+
+#[pymethods]
+impl PyForkChoice {
+    #[new]
+    fn new(anchor_state: &[u8], anchor_block: &[u8]) -> PyResult<Self> {
+        Ok(Self(
+            ForkChoice::new(anchor_state, anchor_block).map_err(to_py_err)?,
+        ))
+    }
+
+    fn on_tick(&mut self, time: u64) -> PyResult<()> {
+        self.0.on_tick(time).map_err(to_py_err)
+    }
+
+    fn on_block(&mut self, signed_block: &[u8]) -> PyResult<()> {
+        self.0.on_block(signed_block).map_err(to_py_err)
+    }
+
+    #[pyo3(signature = (attestation, is_from_block = false))]
+    fn on_attestation(&mut self, attestation: &[u8], is_from_block: bool) -> PyResult<()> {
+        self.0
+            .on_attestation(attestation, is_from_block)
+            .map_err(to_py_err)
+    }
+
+    fn get_head<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        let head = self.0.get_head().map_err(to_py_err)?;
+        Ok(PyBytes::new(py, head.as_slice()))
+    }
+}
+
+#[pymodule]
+fn ream_ffi(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(initialize_network, module)?)?;
+    module.add_function(wrap_pyfunction!(state_transition, module)?)?;
+    module.add_function(wrap_pyfunction!(hash_tree_root, module)?)?;
+    module.add_class::<PyForkChoice>()?;
+    Ok(())
+}
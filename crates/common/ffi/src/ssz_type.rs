@@ -0,0 +1,113 @@
+use std::{any::type_name, str::FromStr};
+
+use alloy_primitives::B256;
+use anyhow::{anyhow, bail};
+use ream_consensus_beacon::{
+    attestation::Attestation,
+    electra::{
+        beacon_block::BeaconBlock, beacon_block_body::BeaconBlockBody, beacon_state::BeaconState,
+    },
+};
+use ream_consensus_misc::checkpoint::Checkpoint;
+use ssz::Decode;
+use tree_hash::TreeHash;
+
+/// The containers `hash_tree_root` accepts. The discriminants are part of the C ABI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum SszType {
+    BeaconState = 0,
+    BeaconBlock = 1,
+    BeaconBlockBody = 2,
+    Attestation = 3,
+    Checkpoint = 4,
+}
+
+impl TryFrom<u32> for SszType {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => SszType::BeaconState,
+            1 => SszType::BeaconBlock,
+            2 => SszType::BeaconBlockBody,
+            3 => SszType::Attestation,
+            4 => SszType::Checkpoint,
+            _ => bail!("Unknown SSZ type {value}"),
+        })
+    }
+}
+
+impl FromStr for SszType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "BeaconState" => SszType::BeaconState,
+            "BeaconBlock" => SszType::BeaconBlock,
+            "BeaconBlockBody" => SszType::BeaconBlockBody,
+            "Attestation" => SszType::Attestation,
+            "Checkpoint" => SszType::Checkpoint,
+            _ => bail!("Unknown SSZ type {s}"),
+        })
+    }
+}
+
+pub(crate) fn decode<T: Decode>(bytes: &[u8]) -> anyhow::Result<T> {
+    T::from_ssz_bytes(bytes)
+        .map_err(|err| anyhow!("Failed to decode {}: {err:?}", type_name::<T>()))
+}
+
+/// Decodes `bytes` as `ssz_type` and returns its root.
+pub fn hash_tree_root(ssz_type: SszType, bytes: &[u8]) -> anyhow::Result<B256> {
+    Ok(match ssz_type {
+        SszType::BeaconState => decode::<BeaconState>(bytes)?.tree_hash_root(),
+        SszType::BeaconBlock => decode::<BeaconBlock>(bytes)?.tree_hash_root(),
+        SszType::BeaconBlockBody => decode::<BeaconBlockBody>(bytes)?.tree_hash_root(),
+        SszType::Attestation => decode::<Attestation>(bytes)?.tree_hash_root(),
+        SszType::Checkpoint => decode::<Checkpoint>(bytes)?.tree_hash_root(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use ssz::Encode;
+
+    use super::*;
+
+    #[test]
+    fn test_hash_tree_root() {
+        let checkpoint = Checkpoint {
+            epoch: 7,
+            root: B256::repeat_byte(1),
+        };
+
+        assert_eq!(
+            hash_tree_root(SszType::Checkpoint, &checkpoint.as_ssz_bytes()).unwrap(),
+            checkpoint.tree_hash_root()
+        );
+    }
+
+    #[test]
+    fn test_hash_tree_root_rejects_invalid_ssz() {
+        assert!(hash_tree_root(SszType::Checkpoint, &[0; 3]).is_err());
+    }
+
+    #[test]
+    fn test_ssz_type_discriminants() {
+        for ssz_type in [
+            SszType::BeaconState,
+            SszType::BeaconBlock,
+            SszType::BeaconBlockBody,
+            SszType::Attestation,
+            SszType::Checkpoint,
+        ] {
+            assert_eq!(SszType::try_from(ssz_type as u32).unwrap(), ssz_type);
+            assert_eq!(
+                format!("{ssz_type:?}").parse::<SszType>().unwrap(),
+                ssz_type
+            );
+        }
+        assert!(SszType::try_from(5).is_err());
+    }
+}
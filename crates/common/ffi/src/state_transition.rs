@@ -0,0 +1,19 @@
+use ream_consensus_beacon::electra::{beacon_block::SignedBeaconBlock, beacon_state::BeaconState};
+use ssz::Encode;
+
+use crate::{network::ensure_network_initialized, ssz_type::decode};
+
+/// Applies `signed_block` to `state` and returns the post-state. The execution payload is not
+/// sent to an execution engine.
+pub fn state_transition(
+    state: &[u8],
+    signed_block: &[u8],
+    validate_result: bool,
+) -> anyhow::Result<Vec<u8>> {
+    ensure_network_initialized()?;
+    let mut state = decode::<BeaconState>(state)?;
+    let signed_block = decode::<SignedBeaconBlock>(signed_block)?;
+
+    state.state_transition_without_execution_engine(&signed_block, validate_result)?;
+    Ok(state.as_ssz_bytes())
+}
@@ -3,7 +3,8 @@ use anyhow::{anyhow, ensure};
 use ream_consensus_beacon::{
     attestation::Attestation, attester_slashing::AttesterSlashing,
     electra::beacon_block::SignedBeaconBlock, execution_engine::engine_trait::ExecutionApi,
-    predicates::is_slashable_attestation_data,
+    predicates::is_slashable_attestation_data, pubkey_cache::PubkeyCache,
+    tree_hash_cache::BeaconStateTreeHashCache,
 };
 use ream_consensus_misc::{constants::INTERVALS_PER_SLOT, misc::compute_start_slot_at_epoch};
 use ream_network_spec::networks::beacon_network_spec;
@@ -21,6 +22,27 @@ pub async fn on_block(
     signed_block: &SignedBeaconBlock,
     execution_engine: &Option<impl ExecutionApi>,
     verify_blob_availability: bool,
+) -> anyhow::Result<()> {
+    on_block_with_cache(
+        store,
+        signed_block,
+        execution_engine,
+        verify_blob_availability,
+        &mut BeaconStateTreeHashCache::default(),
+        &mut PubkeyCache::default(),
+    )
+    .await
+}
+
+/// Like `on_block`, with the state transition hashing the states through `tree_hash_cache` and
+/// looking the depositing validators up in `pubkey_cache`.
+pub async fn on_block_with_cache(
+    store: &mut Store,
+    signed_block: &SignedBeaconBlock,
+    execution_engine: &Option<impl ExecutionApi>,
+    verify_blob_availability: bool,
+    tree_hash_cache: &mut BeaconStateTreeHashCache,
+    pubkey_cache: &mut PubkeyCache,
 ) -> anyhow::Result<()> {
     let block = &signed_block.message;
 
@@ -83,7 +105,13 @@ pub async fn on_block(
         .clone();
     let block_root = block.tree_hash_root();
     state
-        .state_transition(signed_block, true, execution_engine)
+        .state_transition_with_cache(
+            signed_block,
+            true,
+            execution_engine,
+            tree_hash_cache,
+            pubkey_cache,
+        )
         .await?;
 
     // Add new block to the store
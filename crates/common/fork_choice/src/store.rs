@@ -23,7 +23,7 @@ use ream_consensus_misc::{
 };
 use ream_network_spec::networks::beacon_network_spec;
 use ream_operation_pool::OperationPool;
-use ream_polynomial_commitments::handlers::verify_blob_kzg_proof_batch;
+use ream_polynomial_commitments::handlers::validate_blobs_and_kzg_commitments;
 use ream_storage::{
     db::ReamDB,
     tables::{Field, MultimapTable, Table},
@@ -763,10 +763,7 @@ impl Store {
             .map(|blob_and_proof| (blob_and_proof.blob, blob_and_proof.proof))
             .unzip();
 
-        ensure!(
-            verify_blob_kzg_proof_batch(&blobs, blob_kzg_commitments, &proofs)?,
-            "Blob KZG proof batch verification failed (from store)"
-        );
+        validate_blobs_and_kzg_commitments(&blobs, blob_kzg_commitments, &proofs)?;
 
         Ok(true)
     }
@@ -864,6 +861,8 @@ pub fn get_forkchoice_store(
         .insert(finalized_checkpoint)?;
     db.proposer_boost_root_provider()
         .insert(proposer_boost_root)?;
+    db.equivocating_indices_provider()
+        .insert(Default::default())?;
     db.beacon_block_provider()
         .insert(anchor_root, signed_anchor_block)?;
     db.beacon_state_provider()
@@ -5,7 +5,7 @@ use ream_consensus_beacon::{
     sync_committee::SyncCommittee,
 };
 use serde::Serialize;
-use ssz_types::{FixedVector, typenum::U5};
+use ssz_types::{FixedVector, typenum::U6};
 use tree_hash::TreeHash;
 
 use crate::header::LightClientHeader;
@@ -14,7 +14,7 @@ use crate::header::LightClientHeader;
 pub struct LightClientBootstrap {
     pub header: LightClientHeader,
     pub current_sync_committee: SyncCommittee,
-    pub current_sync_committee_branch: FixedVector<B256, U5>,
+    pub current_sync_committee_branch: FixedVector<B256, U6>,
 }
 
 impl LightClientBootstrap {
@@ -2,20 +2,45 @@ use alloy_primitives::B256;
 use ream_consensus_beacon::sync_aggregate::SyncAggregate;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use ssz_types::{FixedVector, typenum::U6};
+use ssz_types::{FixedVector, typenum::U7};
 use tree_hash_derive::TreeHash;
 
-use crate::header::LightClientHeader;
+use crate::{header::LightClientHeader, update::LightClientUpdate};
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
 pub struct LightClientFinalityUpdate {
     pub attested_header: LightClientHeader,
     /// Finalized header corresponding to `attested_header.beacon.state_root`
     pub finalized_header: LightClientHeader,
-    pub finality_branch: FixedVector<B256, U6>,
+    pub finality_branch: FixedVector<B256, U7>,
     /// Sync committee aggregate signature
     pub sync_aggregate: SyncAggregate,
     /// Slot at which the aggregate signature was created (untrusted)
     #[serde(with = "serde_utils::quoted_u64")]
     pub signature_slot: u64,
 }
+
+impl From<LightClientUpdate> for LightClientFinalityUpdate {
+    fn from(update: LightClientUpdate) -> Self {
+        Self {
+            attested_header: update.attested_header,
+            finalized_header: update.finalized_header,
+            finality_branch: update.finality_branch,
+            sync_aggregate: update.sync_aggregate,
+            signature_slot: update.signature_slot,
+        }
+    }
+}
+
+impl From<LightClientFinalityUpdate> for LightClientUpdate {
+    fn from(finality_update: LightClientFinalityUpdate) -> Self {
+        Self {
+            attested_header: finality_update.attested_header,
+            finalized_header: finality_update.finalized_header,
+            finality_branch: finality_update.finality_branch,
+            sync_aggregate: finality_update.sync_aggregate,
+            signature_slot: finality_update.signature_slot,
+            ..Default::default()
+        }
+    }
+}
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
 use tree_hash_derive::TreeHash;
 
-use crate::header::LightClientHeader;
+use crate::{header::LightClientHeader, update::LightClientUpdate};
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
 pub struct LightClientOptimisticUpdate {
@@ -15,3 +15,24 @@ pub struct LightClientOptimisticUpdate {
     #[serde(with = "serde_utils::quoted_u64")]
     pub signature_slot: u64,
 }
+
+impl From<LightClientUpdate> for LightClientOptimisticUpdate {
+    fn from(update: LightClientUpdate) -> Self {
+        Self {
+            attested_header: update.attested_header,
+            sync_aggregate: update.sync_aggregate,
+            signature_slot: update.signature_slot,
+        }
+    }
+}
+
+impl From<LightClientOptimisticUpdate> for LightClientUpdate {
+    fn from(optimistic_update: LightClientOptimisticUpdate) -> Self {
+        Self {
+            attested_header: optimistic_update.attested_header,
+            sync_aggregate: optimistic_update.sync_aggregate,
+            signature_slot: optimistic_update.signature_slot,
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,543 @@
+use std::{cmp::max, mem};
+
+use alloy_primitives::B256;
+use anyhow::ensure;
+use ream_consensus_beacon::{
+    electra::{
+        beacon_state::eth_fast_aggregate_verify, execution_payload_header::ExecutionPayloadHeader,
+    },
+    sync_committee::SyncCommittee,
+};
+use ream_consensus_misc::{
+    constants::{
+        DOMAIN_SYNC_COMMITTEE, EPOCHS_PER_SYNC_COMMITTEE_PERIOD, GENESIS_SLOT, SLOTS_PER_EPOCH,
+    },
+    generalized_index::{
+        CURRENT_SYNC_COMMITTEE_GINDEX, EXECUTION_PAYLOAD_GINDEX, FINALIZED_ROOT_GINDEX,
+        NEXT_SYNC_COMMITTEE_GINDEX,
+    },
+    misc::{
+        compute_domain, compute_epoch_at_slot, compute_signing_root,
+        compute_sync_committee_period_at_slot,
+    },
+};
+use ream_merkle::{is_valid_normalized_merkle_branch, verify_merkle_branch};
+use ream_network_spec::networks::beacon_network_spec;
+use tree_hash::TreeHash;
+
+use crate::{
+    bootstrap::LightClientBootstrap,
+    finality_update::LightClientFinalityUpdate,
+    header::LightClientHeader,
+    optimistic_update::LightClientOptimisticUpdate,
+    update::{LightClientUpdate, MIN_SYNC_COMMITTEE_PARTICIPANTS},
+};
+
+/// Number of slots after which the best valid update is force applied when finality stalls.
+pub const UPDATE_TIMEOUT: u64 = SLOTS_PER_EPOCH * EPOCHS_PER_SYNC_COMMITTEE_PERIOD;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LightClientStore {
+    /// Header that is finalized
+    pub finalized_header: LightClientHeader,
+    /// Sync committees corresponding to the finalized header
+    pub current_sync_committee: SyncCommittee,
+    pub next_sync_committee: SyncCommittee,
+    /// Best available header to switch finalized head to if we see nothing else
+    pub best_valid_update: Option<LightClientUpdate>,
+    /// Most recent available reasonably-safe header
+    pub optimistic_header: LightClientHeader,
+    /// Max number of active participants in a sync committee (used to calculate safety threshold)
+    pub previous_max_active_participants: u64,
+    pub current_max_active_participants: u64,
+}
+
+impl LightClientStore {
+    /// Initialize the store from a bootstrap of a trusted block root.
+    pub fn new(trusted_block_root: B256, bootstrap: LightClientBootstrap) -> anyhow::Result<Self> {
+        ensure!(
+            is_valid_light_client_header(&bootstrap.header),
+            "Invalid bootstrap header"
+        );
+        ensure!(
+            bootstrap.header.beacon.tree_hash_root() == trusted_block_root,
+            "Bootstrap header root must be equal to the trusted block root"
+        );
+        ensure!(
+            is_valid_normalized_merkle_branch(
+                bootstrap.current_sync_committee.tree_hash_root(),
+                &bootstrap.current_sync_committee_branch,
+                CURRENT_SYNC_COMMITTEE_GINDEX,
+                bootstrap.header.beacon.state_root,
+            ),
+            "Invalid current sync committee branch"
+        );
+
+        Ok(Self {
+            finalized_header: bootstrap.header.clone(),
+            current_sync_committee: bootstrap.current_sync_committee,
+            next_sync_committee: SyncCommittee::default(),
+            best_valid_update: None,
+            optimistic_header: bootstrap.header,
+            previous_max_active_participants: 0,
+            current_max_active_participants: 0,
+        })
+    }
+
+    pub fn is_next_sync_committee_known(&self) -> bool {
+        self.next_sync_committee != SyncCommittee::default()
+    }
+
+    pub fn get_safety_threshold(&self) -> u64 {
+        max(
+            self.previous_max_active_participants,
+            self.current_max_active_participants,
+        ) / 2
+    }
+
+    pub fn validate_light_client_update(
+        &self,
+        update: &LightClientUpdate,
+        current_slot: u64,
+        genesis_validators_root: B256,
+    ) -> anyhow::Result<()> {
+        // Verify sync committee has sufficient participants
+        let sync_aggregate = &update.sync_aggregate;
+        ensure!(
+            update.num_active_participants() >= MIN_SYNC_COMMITTEE_PARTICIPANTS,
+            "Not enough sync committee participants"
+        );
+
+        // Verify update does not skip a sync committee period
+        ensure!(
+            is_valid_light_client_header(&update.attested_header),
+            "Invalid attested header"
+        );
+        let update_attested_slot = update.attested_header.beacon.slot;
+        let update_finalized_slot = update.finalized_header.beacon.slot;
+        ensure!(
+            current_slot >= update.signature_slot
+                && update.signature_slot > update_attested_slot
+                && update_attested_slot >= update_finalized_slot,
+            "Update slots must satisfy current_slot >= signature_slot > attested_slot >= \
+             finalized_slot"
+        );
+        let store_period = compute_sync_committee_period_at_slot(self.finalized_header.beacon.slot);
+        let update_signature_period = compute_sync_committee_period_at_slot(update.signature_slot);
+        if self.is_next_sync_committee_known() {
+            ensure!(
+                update_signature_period == store_period
+                    || update_signature_period == store_period + 1,
+                "Signature period must be the store period or the one after it"
+            );
+        } else {
+            ensure!(
+                update_signature_period == store_period,
+                "Signature period must be the store period"
+            );
+        }
+
+        // Verify update is relevant
+        let update_attested_period = compute_sync_committee_period_at_slot(update_attested_slot);
+        let update_has_next_sync_committee = !self.is_next_sync_committee_known()
+            && update.is_sync_committee_update()
+            && update_attested_period == store_period;
+        ensure!(
+            update_attested_slot > self.finalized_header.beacon.slot
+                || update_has_next_sync_committee,
+            "Update is not relevant"
+        );
+
+        // Verify that the `finality_branch`, if present, confirms `finalized_header` to match the
+        // finalized checkpoint root saved in the state of `attested_header`. Note that the
+        // genesis finalized checkpoint root is represented as a zero hash.
+        if !update.is_finality_update() {
+            ensure!(
+                update.finalized_header == LightClientHeader::default(),
+                "Finalized header must be empty without a finality branch"
+            );
+        } else {
+            let finalized_root = if update_finalized_slot == GENESIS_SLOT {
+                ensure!(
+                    update.finalized_header == LightClientHeader::default(),
+                    "Finalized header must be empty at genesis"
+                );
+                B256::ZERO
+            } else {
+                ensure!(
+                    is_valid_light_client_header(&update.finalized_header),
+                    "Invalid finalized header"
+                );
+                update.finalized_header.beacon.tree_hash_root()
+            };
+            ensure!(
+                is_valid_normalized_merkle_branch(
+                    finalized_root,
+                    &update.finality_branch,
+                    FINALIZED_ROOT_GINDEX,
+                    update.attested_header.beacon.state_root,
+                ),
+                "Invalid finality branch"
+            );
+        }
+
+        // Verify that the `next_sync_committee`, if present, actually is the next sync committee
+        // saved in the state of the `attested_header`
+        if !update.is_sync_committee_update() {
+            ensure!(
+                update.next_sync_committee == SyncCommittee::default(),
+                "Next sync committee must be empty without a next sync committee branch"
+            );
+        } else {
+            if update_attested_period == store_period && self.is_next_sync_committee_known() {
+                ensure!(
+                    update.next_sync_committee == self.next_sync_committee,
+                    "Next sync committee does not match the known next sync committee"
+                );
+            }
+            ensure!(
+                is_valid_normalized_merkle_branch(
+                    update.next_sync_committee.tree_hash_root(),
+                    &update.next_sync_committee_branch,
+                    NEXT_SYNC_COMMITTEE_GINDEX,
+                    update.attested_header.beacon.state_root,
+                ),
+                "Invalid next sync committee branch"
+            );
+        }
+
+        // Verify sync committee aggregate signature
+        let sync_committee = if update_signature_period == store_period {
+            &self.current_sync_committee
+        } else {
+            &self.next_sync_committee
+        };
+        let participant_public_keys = sync_committee
+            .public_keys
+            .iter()
+            .zip(sync_aggregate.sync_committee_bits.iter())
+            .filter_map(|(public_key, bit)| bit.then_some(public_key))
+            .collect::<Vec<_>>();
+        let fork_version_slot = max(update.signature_slot, 1) - 1;
+        let fork_version =
+            beacon_network_spec().fork_version_at_epoch(compute_epoch_at_slot(fork_version_slot));
+        let domain = compute_domain(
+            DOMAIN_SYNC_COMMITTEE,
+            Some(fork_version),
+            Some(genesis_validators_root),
+        );
+        let signing_root = compute_signing_root(&update.attested_header.beacon, domain);
+        ensure!(
+            eth_fast_aggregate_verify(
+                &participant_public_keys,
+                signing_root,
+                &sync_aggregate.sync_committee_signature,
+            )?,
+            "Sync committee signature verification failed"
+        );
+
+        Ok(())
+    }
+
+    pub fn apply_light_client_update(&mut self, update: LightClientUpdate) -> anyhow::Result<()> {
+        let store_period = compute_sync_committee_period_at_slot(self.finalized_header.beacon.slot);
+        let update_finalized_period =
+            compute_sync_committee_period_at_slot(update.finalized_header.beacon.slot);
+        if !self.is_next_sync_committee_known() {
+            ensure!(
+                update_finalized_period == store_period,
+                "Finalized period must be the store period while the next sync committee is \
+                 unknown"
+            );
+            self.next_sync_committee = update.next_sync_committee;
+        } else if update_finalized_period == store_period + 1 {
+            self.current_sync_committee =
+                mem::replace(&mut self.next_sync_committee, update.next_sync_committee);
+            self.previous_max_active_participants = self.current_max_active_participants;
+            self.current_max_active_participants = 0;
+        }
+        if update.finalized_header.beacon.slot > self.finalized_header.beacon.slot {
+            self.finalized_header = update.finalized_header;
+            if self.finalized_header.beacon.slot > self.optimistic_header.beacon.slot {
+                self.optimistic_header = self.finalized_header.clone();
+            }
+        }
+        Ok(())
+    }
+
+    pub fn process_light_client_store_force_update(
+        &mut self,
+        current_slot: u64,
+    ) -> anyhow::Result<()> {
+        if current_slot <= self.finalized_header.beacon.slot + UPDATE_TIMEOUT {
+            return Ok(());
+        }
+        let Some(mut best_valid_update) = self.best_valid_update.take() else {
+            return Ok(());
+        };
+
+        // Forced best update when the update timeout has elapsed. Because the apply logic waits
+        // for `finalized_header.beacon.slot` to indicate sync committee finality, the
+        // `attested_header` may be treated as `finalized_header` in extended periods of
+        // non-finality to guarantee progression into later sync committee periods according to
+        // `is_better_update`.
+        if best_valid_update.finalized_header.beacon.slot <= self.finalized_header.beacon.slot {
+            best_valid_update.finalized_header = best_valid_update.attested_header.clone();
+        }
+        self.apply_light_client_update(best_valid_update)
+    }
+
+    pub fn process_light_client_update(
+        &mut self,
+        update: LightClientUpdate,
+        current_slot: u64,
+        genesis_validators_root: B256,
+    ) -> anyhow::Result<()> {
+        self.validate_light_client_update(&update, current_slot, genesis_validators_root)?;
+
+        let num_active_participants = update.num_active_participants();
+        let sync_committee_size = update.sync_aggregate.sync_committee_bits.len() as u64;
+
+        // Update the best update in case we have to force-update to it if the timeout elapses
+        if self
+            .best_valid_update
+            .as_ref()
+            .is_none_or(|best_valid_update| is_better_update(&update, best_valid_update))
+        {
+            self.best_valid_update = Some(update.clone());
+        }
+
+        // Track the maximum number of active participants in the committee signatures
+        self.current_max_active_participants = max(
+            self.current_max_active_participants,
+            num_active_participants,
+        );
+
+        // Update the optimistic header
+        if num_active_participants > self.get_safety_threshold()
+            && update.attested_header.beacon.slot > self.optimistic_header.beacon.slot
+        {
+            self.optimistic_header = update.attested_header.clone();
+        }
+
+        // Update finalized header
+        let update_has_finalized_next_sync_committee = !self.is_next_sync_committee_known()
+            && update.is_sync_committee_update()
+            && update.is_finality_update()
+            && compute_sync_committee_period_at_slot(update.finalized_header.beacon.slot)
+                == compute_sync_committee_period_at_slot(update.attested_header.beacon.slot);
+        if num_active_participants * 3 >= sync_committee_size * 2
+            && (update.finalized_header.beacon.slot > self.finalized_header.beacon.slot
+                || update_has_finalized_next_sync_committee)
+        {
+            // Normal update through 2/3 threshold
+            self.apply_light_client_update(update)?;
+            self.best_valid_update = None;
+        }
+
+        Ok(())
+    }
+
+    pub fn process_light_client_finality_update(
+        &mut self,
+        finality_update: LightClientFinalityUpdate,
+        current_slot: u64,
+        genesis_validators_root: B256,
+    ) -> anyhow::Result<()> {
+        self.process_light_client_update(
+            finality_update.into(),
+            current_slot,
+            genesis_validators_root,
+        )
+    }
+
+    pub fn process_light_client_optimistic_update(
+        &mut self,
+        optimistic_update: LightClientOptimisticUpdate,
+        current_slot: u64,
+        genesis_validators_root: B256,
+    ) -> anyhow::Result<()> {
+        self.process_light_client_update(
+            optimistic_update.into(),
+            current_slot,
+            genesis_validators_root,
+        )
+    }
+}
+
+impl LightClientUpdate {
+    pub fn num_active_participants(&self) -> u64 {
+        self.sync_aggregate.sync_committee_bits.num_set_bits() as u64
+    }
+
+    pub fn is_sync_committee_update(&self) -> bool {
+        self.next_sync_committee_branch
+            .iter()
+            .any(|node| *node != B256::ZERO)
+    }
+
+    pub fn is_finality_update(&self) -> bool {
+        self.finality_branch.iter().any(|node| *node != B256::ZERO)
+    }
+
+    fn has_relevant_sync_committee(&self) -> bool {
+        self.is_sync_committee_update()
+            && compute_sync_committee_period_at_slot(self.attested_header.beacon.slot)
+                == compute_sync_committee_period_at_slot(self.signature_slot)
+    }
+
+    fn has_sync_committee_finality(&self) -> bool {
+        compute_sync_committee_period_at_slot(self.finalized_header.beacon.slot)
+            == compute_sync_committee_period_at_slot(self.attested_header.beacon.slot)
+    }
+}
+
+pub fn is_valid_light_client_header(header: &LightClientHeader) -> bool {
+    let epoch = compute_epoch_at_slot(header.beacon.slot);
+    let network_spec = beacon_network_spec();
+
+    if epoch < network_spec.deneb_fork_epoch
+        && (header.execution.blob_gas_used != 0 || header.execution.excess_blob_gas != 0)
+    {
+        return false;
+    }
+
+    if epoch < network_spec.capella_fork_epoch {
+        return header.execution == ExecutionPayloadHeader::default()
+            && header
+                .execution_branch
+                .iter()
+                .all(|node| *node == B256::ZERO);
+    }
+
+    verify_merkle_branch(
+        header.execution.tree_hash_root(),
+        &header.execution_branch,
+        EXECUTION_PAYLOAD_GINDEX,
+        header.beacon.body_root,
+    )
+}
+
+/// Return whether `new_update` is preferable to `old_update` as the best valid update.
+pub fn is_better_update(new_update: &LightClientUpdate, old_update: &LightClientUpdate) -> bool {
+    // Compare supermajority (> 2/3) sync committee participation
+    let max_active_participants = new_update.sync_aggregate.sync_committee_bits.len() as u64;
+    let new_num_active_participants = new_update.num_active_participants();
+    let old_num_active_participants = old_update.num_active_participants();
+    let new_has_supermajority = new_num_active_participants * 3 >= max_active_participants * 2;
+    let old_has_supermajority = old_num_active_participants * 3 >= max_active_participants * 2;
+    if new_has_supermajority != old_has_supermajority {
+        return new_has_supermajority;
+    }
+    if !new_has_supermajority && new_num_active_participants != old_num_active_participants {
+        return new_num_active_participants > old_num_active_participants;
+    }
+
+    // Compare presence of relevant sync committee
+    let new_has_relevant_sync_committee = new_update.has_relevant_sync_committee();
+    if new_has_relevant_sync_committee != old_update.has_relevant_sync_committee() {
+        return new_has_relevant_sync_committee;
+    }
+
+    // Compare indication of any finality
+    let new_has_finality = new_update.is_finality_update();
+    if new_has_finality != old_update.is_finality_update() {
+        return new_has_finality;
+    }
+
+    // Compare sync committee finality
+    if new_has_finality {
+        let new_has_sync_committee_finality = new_update.has_sync_committee_finality();
+        if new_has_sync_committee_finality != old_update.has_sync_committee_finality() {
+            return new_has_sync_committee_finality;
+        }
+    }
+
+    // Tiebreaker 1: Sync committee participation beyond supermajority
+    if new_num_active_participants != old_num_active_participants {
+        return new_num_active_participants > old_num_active_participants;
+    }
+
+    // Tiebreaker 2: Prefer older data (fewer changes to best)
+    if new_update.attested_header.beacon.slot != old_update.attested_header.beacon.slot {
+        return new_update.attested_header.beacon.slot < old_update.attested_header.beacon.slot;
+    }
+
+    // Tiebreaker 3: Prefer updates with earlier signature slots
+    new_update.signature_slot < old_update.signature_slot
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::*;
+
+    fn update_with_participants(
+        num_active_participants: usize,
+    ) -> anyhow::Result<LightClientUpdate> {
+        let mut update = LightClientUpdate::default();
+        for index in 0..num_active_participants {
+            update
+                .sync_aggregate
+                .sync_committee_bits
+                .set(index, true)
+                .map_err(|err| anyhow!("Failed to set sync committee bit: {err:?}"))?;
+        }
+        Ok(update)
+    }
+
+    #[test]
+    fn test_is_better_update() -> anyhow::Result<()> {
+        let sync_committee_size = LightClientUpdate::default()
+            .sync_aggregate
+            .sync_committee_bits
+            .len();
+        let supermajority = update_with_participants(sync_committee_size)?;
+        let minority = update_with_participants(1)?;
+        assert!(is_better_update(&supermajority, &minority));
+        assert!(!is_better_update(&minority, &supermajority));
+
+        // Finality is preferred when participation is equal
+        let mut finality = update_with_participants(sync_committee_size)?;
+        finality.finality_branch[0] = B256::repeat_byte(1);
+        assert!(is_better_update(&finality, &supermajority));
+        assert!(!is_better_update(&supermajority, &finality));
+
+        // Older data is preferred when everything else is equal
+        let mut older = supermajority.clone();
+        older.signature_slot = 1;
+        let mut newer = supermajority;
+        newer.signature_slot = 2;
+        assert!(is_better_update(&older, &newer));
+        assert!(!is_better_update(&newer, &older));
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_light_client_store_force_update() -> anyhow::Result<()> {
+        let mut best_valid_update = update_with_participants(1)?;
+        best_valid_update.attested_header.beacon.slot = 10;
+        let mut store = LightClientStore {
+            finalized_header: LightClientHeader::default(),
+            current_sync_committee: SyncCommittee::default(),
+            next_sync_committee: SyncCommittee::default(),
+            best_valid_update: Some(best_valid_update.clone()),
+            optimistic_header: LightClientHeader::default(),
+            previous_max_active_participants: 0,
+            current_max_active_participants: 0,
+        };
+
+        // Nothing happens before the update timeout has elapsed
+        store.process_light_client_store_force_update(UPDATE_TIMEOUT)?;
+        assert_eq!(store.best_valid_update, Some(best_valid_update.clone()));
+        assert_eq!(store.finalized_header, LightClientHeader::default());
+
+        // The attested header of the best valid update is treated as finalized afterwards
+        store.process_light_client_store_force_update(UPDATE_TIMEOUT + 1)?;
+        assert_eq!(store.best_valid_update, None);
+        assert_eq!(store.finalized_header, best_valid_update.attested_header);
+        assert_eq!(store.optimistic_header, best_valid_update.attested_header);
+        Ok(())
+    }
+}
@@ -8,7 +8,10 @@ use ream_consensus_beacon::{
 use ream_consensus_misc::{constants::GENESIS_SLOT, misc::compute_sync_committee_period_at_slot};
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use ssz_types::{FixedVector, typenum::U6};
+use ssz_types::{
+    FixedVector,
+    typenum::{U6, U7},
+};
 use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
 
@@ -16,7 +19,9 @@ use crate::header::LightClientHeader;
 
 pub const MIN_SYNC_COMMITTEE_PARTICIPANTS: u64 = 1;
 
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
+#[derive(
+    Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash,
+)]
 pub struct LightClientUpdate {
     /// Header attested to by the sync committee
     pub attested_header: LightClientHeader,
@@ -25,7 +30,7 @@ pub struct LightClientUpdate {
     pub next_sync_committee_branch: FixedVector<B256, U6>,
     /// Finalized header corresponding to `attested_header.beacon.state_root`
     pub finalized_header: LightClientHeader,
-    pub finality_branch: FixedVector<B256, U6>,
+    pub finality_branch: FixedVector<B256, U7>,
     /// Sync committee aggregate signature
     pub sync_aggregate: SyncAggregate,
     /// Slot at which the aggregate signature was created (untrusted)
@@ -1,13 +1,13 @@
 use std::slice::Iter;
 
-use ream_consensus_misc::fork::Fork;
+use ream_consensus_misc::fork::{Fork, ForkName};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ForkSchedule(pub [Fork; ForkSchedule::TOTAL]);
 
 impl ForkSchedule {
-    pub const TOTAL: usize = 6;
+    pub const TOTAL: usize = ForkName::ALL.len();
 
     pub const fn new(forks: [Fork; ForkSchedule::TOTAL]) -> Self {
         Self(forks)
@@ -21,4 +21,64 @@ impl ForkSchedule {
         self.iter()
             .filter(|fork| fork.epoch != Fork::UNSCHEDULED_EPOCH)
     }
+
+    /// Returns the name of the latest fork activated at `epoch`.
+    pub fn fork_name_at_epoch(&self, epoch: u64) -> ForkName {
+        self.iter()
+            .zip(ForkName::ALL)
+            .filter(|(fork, _)| fork.epoch <= epoch && fork.epoch != Fork::UNSCHEDULED_EPOCH)
+            .map(|(_, fork_name)| fork_name)
+            .next_back()
+            .unwrap_or(ForkName::Phase0)
+    }
+
+    /// Returns the latest fork activated at `epoch`.
+    pub fn fork_at_epoch(&self, epoch: u64) -> &Fork {
+        &self.0[self.fork_name_at_epoch(epoch) as usize]
+    }
+
+    /// Returns the first fork scheduled to activate after `epoch`, if any.
+    pub fn next_fork_at_epoch(&self, epoch: u64) -> Option<&Fork> {
+        self.scheduled().find(|fork| fork.epoch > epoch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::aliases::B32;
+
+    use super::*;
+
+    fn fork(version: u8, epoch: u64) -> Fork {
+        Fork {
+            previous_version: B32::from([version.saturating_sub(1), 0, 0, 0]),
+            current_version: B32::from([version, 0, 0, 0]),
+            epoch,
+        }
+    }
+
+    #[test]
+    fn test_fork_at_epoch() {
+        let fork_schedule = ForkSchedule::new([
+            fork(0, 0),
+            fork(1, 0),
+            fork(2, 10),
+            fork(3, 20),
+            fork(4, 20),
+            fork(5, 30),
+            fork(6, Fork::UNSCHEDULED_EPOCH),
+        ]);
+
+        assert_eq!(fork_schedule.fork_name_at_epoch(0), ForkName::Altair);
+        assert_eq!(fork_schedule.fork_name_at_epoch(19), ForkName::Bellatrix);
+        assert_eq!(fork_schedule.fork_name_at_epoch(20), ForkName::Deneb);
+        assert_eq!(
+            fork_schedule.fork_name_at_epoch(u64::MAX - 1),
+            ForkName::Electra
+        );
+
+        assert_eq!(fork_schedule.next_fork_at_epoch(0), Some(&fork(2, 10)));
+        assert_eq!(fork_schedule.next_fork_at_epoch(10), Some(&fork(3, 20)));
+        assert_eq!(fork_schedule.next_fork_at_epoch(30), None);
+    }
 }
@@ -111,6 +111,10 @@ pub struct BeaconNetworkSpec {
     #[serde(with = "crate::b32_hex")]
     pub electra_fork_version: B32,
     pub electra_fork_epoch: u64,
+    #[serde(with = "crate::b32_hex", default)]
+    pub fulu_fork_version: B32,
+    #[serde(default = "unscheduled_fork_epoch")]
+    pub fulu_fork_epoch: u64,
 
     // Time parameters
     pub seconds_per_slot: u64,
@@ -169,6 +173,60 @@ pub struct BeaconNetworkSpec {
     pub blob_sidecar_subnet_count_electra: u64,
     pub max_blobs_per_block_electra: u64,
     pub max_request_blob_sidecars_electra: u64,
+
+    // Fulu, defaulted for the configurations which predate it
+    #[serde(default = "default_number_of_custody_groups")]
+    pub number_of_custody_groups: u64,
+    #[serde(default = "default_data_column_sidecar_subnet_count")]
+    pub data_column_sidecar_subnet_count: u64,
+    #[serde(default = "default_custody_requirement")]
+    pub custody_requirement: u64,
+    #[serde(default = "default_samples_per_slot")]
+    pub samples_per_slot: u64,
+    #[serde(default = "default_validator_custody_requirement")]
+    pub validator_custody_requirement: u64,
+    #[serde(default = "default_balance_per_additional_custody_group")]
+    pub balance_per_additional_custody_group: u64,
+    #[serde(default = "default_max_request_data_column_sidecars")]
+    pub max_request_data_column_sidecars: u64,
+    #[serde(default = "default_min_epochs_for_data_column_sidecars_requests")]
+    pub min_epochs_for_data_column_sidecars_requests: u64,
+}
+
+fn unscheduled_fork_epoch() -> u64 {
+    Fork::UNSCHEDULED_EPOCH
+}
+
+fn default_number_of_custody_groups() -> u64 {
+    128
+}
+
+fn default_data_column_sidecar_subnet_count() -> u64 {
+    128
+}
+
+fn default_custody_requirement() -> u64 {
+    4
+}
+
+fn default_samples_per_slot() -> u64 {
+    8
+}
+
+fn default_validator_custody_requirement() -> u64 {
+    8
+}
+
+fn default_balance_per_additional_custody_group() -> u64 {
+    32000000000
+}
+
+fn default_max_request_data_column_sidecars() -> u64 {
+    16384
+}
+
+fn default_min_epochs_for_data_column_sidecars_requests() -> u64 {
+    4096
 }
 
 impl BeaconNetworkSpec {
@@ -212,9 +270,28 @@ impl BeaconNetworkSpec {
                 current_version: self.electra_fork_version,
                 epoch: self.electra_fork_epoch,
             },
+            Fork {
+                previous_version: self.electra_fork_version,
+                current_version: self.fulu_fork_version,
+                epoch: self.fulu_fork_epoch,
+            },
         ])
     }
 
+    /// Returns the fork version active at `epoch` according to the fork schedule.
+    pub fn fork_version_at_epoch(&self, epoch: u64) -> B32 {
+        self.fork_schedule().fork_at_epoch(epoch).current_version
+    }
+
+    /// Returns the fork digest active at `epoch` according to the fork schedule.
+    pub fn fork_digest_at_epoch(&self, epoch: u64, genesis_validators_root: B256) -> B32 {
+        ForkData {
+            current_version: self.fork_version_at_epoch(epoch),
+            genesis_validators_root,
+        }
+        .compute_fork_digest()
+    }
+
     /// Returns the slot number for `n_days_ago` days ago.
     ///
     /// if n_days_ago is larger then the current slot, it returns 0.
@@ -252,6 +329,9 @@ pub static MAINNET: LazyLock<Arc<BeaconNetworkSpec>> = LazyLock::new(|| {
         deneb_fork_epoch: 269568,
         electra_fork_version: fixed_bytes!("0x05000000"),
         electra_fork_epoch: 364032,
+        // Fulu is not supported yet, it can be scheduled through a custom network config
+        fulu_fork_version: fixed_bytes!("0x06000000"),
+        fulu_fork_epoch: Fork::UNSCHEDULED_EPOCH,
         seconds_per_slot: 12,
         seconds_per_eth1_block: 14,
         min_validator_withdrawability_delay: 256,
@@ -293,6 +373,14 @@ pub static MAINNET: LazyLock<Arc<BeaconNetworkSpec>> = LazyLock::new(|| {
         blob_sidecar_subnet_count_electra: 9,
         max_blobs_per_block_electra: 9,
         max_request_blob_sidecars_electra: 1152,
+        number_of_custody_groups: 128,
+        data_column_sidecar_subnet_count: 128,
+        custody_requirement: 4,
+        samples_per_slot: 8,
+        validator_custody_requirement: 8,
+        balance_per_additional_custody_group: 32000000000,
+        max_request_data_column_sidecars: 16384,
+        min_epochs_for_data_column_sidecars_requests: 4096,
     }
     .into()
 });
@@ -321,6 +409,9 @@ pub static HOLESKY: LazyLock<Arc<BeaconNetworkSpec>> = LazyLock::new(|| {
         deneb_fork_epoch: 29696,
         electra_fork_version: fixed_bytes!("0x06017000"),
         electra_fork_epoch: 115968,
+        // Fulu is not supported yet, it can be scheduled through a custom network config
+        fulu_fork_version: fixed_bytes!("0x07017000"),
+        fulu_fork_epoch: Fork::UNSCHEDULED_EPOCH,
         seconds_per_slot: 12,
         seconds_per_eth1_block: 14,
         min_validator_withdrawability_delay: 256,
@@ -362,6 +453,14 @@ pub static HOLESKY: LazyLock<Arc<BeaconNetworkSpec>> = LazyLock::new(|| {
         blob_sidecar_subnet_count_electra: 9,
         max_blobs_per_block_electra: 9,
         max_request_blob_sidecars_electra: 1152,
+        number_of_custody_groups: 128,
+        data_column_sidecar_subnet_count: 128,
+        custody_requirement: 4,
+        samples_per_slot: 8,
+        validator_custody_requirement: 8,
+        balance_per_additional_custody_group: 32000000000,
+        max_request_data_column_sidecars: 16384,
+        min_epochs_for_data_column_sidecars_requests: 4096,
     }
     .into()
 });
@@ -390,6 +489,9 @@ pub static SEPOLIA: LazyLock<Arc<BeaconNetworkSpec>> = LazyLock::new(|| {
         deneb_fork_epoch: 132608,
         electra_fork_version: fixed_bytes!("0x90000074"),
         electra_fork_epoch: 222464,
+        // Fulu is not supported yet, it can be scheduled through a custom network config
+        fulu_fork_version: fixed_bytes!("0x90000075"),
+        fulu_fork_epoch: Fork::UNSCHEDULED_EPOCH,
         seconds_per_slot: 12,
         seconds_per_eth1_block: 14,
         min_validator_withdrawability_delay: 256,
@@ -431,6 +533,14 @@ pub static SEPOLIA: LazyLock<Arc<BeaconNetworkSpec>> = LazyLock::new(|| {
         blob_sidecar_subnet_count_electra: 9,
         max_blobs_per_block_electra: 9,
         max_request_blob_sidecars_electra: 1152,
+        number_of_custody_groups: 128,
+        data_column_sidecar_subnet_count: 128,
+        custody_requirement: 4,
+        samples_per_slot: 8,
+        validator_custody_requirement: 8,
+        balance_per_additional_custody_group: 32000000000,
+        max_request_data_column_sidecars: 16384,
+        min_epochs_for_data_column_sidecars_requests: 4096,
     }
     .into()
 });
@@ -459,6 +569,9 @@ pub static HOODI: LazyLock<Arc<BeaconNetworkSpec>> = LazyLock::new(|| {
         deneb_fork_epoch: 0,
         electra_fork_version: fixed_bytes!("0x60000910"),
         electra_fork_epoch: 2048,
+        // Fulu is not supported yet, it can be scheduled through a custom network config
+        fulu_fork_version: fixed_bytes!("0x70000910"),
+        fulu_fork_epoch: Fork::UNSCHEDULED_EPOCH,
         seconds_per_slot: 12,
         seconds_per_eth1_block: 14,
         min_validator_withdrawability_delay: 256,
@@ -500,6 +613,14 @@ pub static HOODI: LazyLock<Arc<BeaconNetworkSpec>> = LazyLock::new(|| {
         blob_sidecar_subnet_count_electra: 9,
         max_blobs_per_block_electra: 9,
         max_request_blob_sidecars_electra: 1152,
+        number_of_custody_groups: 128,
+        data_column_sidecar_subnet_count: 128,
+        custody_requirement: 4,
+        samples_per_slot: 8,
+        validator_custody_requirement: 8,
+        balance_per_additional_custody_group: 32000000000,
+        max_request_data_column_sidecars: 16384,
+        min_epochs_for_data_column_sidecars_requests: 4096,
     }
     .into()
 });
@@ -528,6 +649,9 @@ pub static DEV: LazyLock<Arc<BeaconNetworkSpec>> = LazyLock::new(|| {
         deneb_fork_epoch: 269568,
         electra_fork_version: fixed_bytes!("0x05000000"),
         electra_fork_epoch: 364032,
+        // Fulu is not supported yet, it can be scheduled through a custom network config
+        fulu_fork_version: fixed_bytes!("0x06000000"),
+        fulu_fork_epoch: Fork::UNSCHEDULED_EPOCH,
         seconds_per_slot: 12,
         seconds_per_eth1_block: 14,
         min_validator_withdrawability_delay: 256,
@@ -569,6 +693,14 @@ pub static DEV: LazyLock<Arc<BeaconNetworkSpec>> = LazyLock::new(|| {
         blob_sidecar_subnet_count_electra: 9,
         max_blobs_per_block_electra: 9,
         max_request_blob_sidecars_electra: 1152,
+        number_of_custody_groups: 128,
+        data_column_sidecar_subnet_count: 128,
+        custody_requirement: 4,
+        samples_per_slot: 8,
+        validator_custody_requirement: 8,
+        balance_per_additional_custody_group: 32000000000,
+        max_request_data_column_sidecars: 16384,
+        min_epochs_for_data_column_sidecars_requests: 4096,
     }
     .into()
 });
@@ -2,9 +2,13 @@ use std::collections::HashMap;
 
 use alloy_primitives::{Address, B256};
 use parking_lot::RwLock;
+use ream_bls::BLSSignature;
 use ream_consensus_beacon::{
     bls_to_execution_change::SignedBLSToExecutionChange, electra::beacon_state::BeaconState,
-    voluntary_exit::SignedVoluntaryExit,
+    proposer_slashing::ProposerSlashing, voluntary_exit::SignedVoluntaryExit,
+};
+use ream_validator_beacon::{
+    contribution_and_proof::SyncCommitteeContribution, sync_committee::SyncCommitteeMessage,
 };
 use tree_hash::TreeHash;
 
@@ -14,11 +18,30 @@ pub struct ProposerPreparation {
     pub submission_epoch: u64,
 }
 
+/// Identifies the sync committee messages which can be aggregated into one contribution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SyncContributionKey {
+    pub slot: u64,
+    pub beacon_block_root: B256,
+    pub subcommittee_index: u64,
+}
+
+/// A contribution aggregated from the first `message_count` pooled messages of its key.
+#[derive(Debug, Clone)]
+struct CachedSyncContribution {
+    message_count: usize,
+    contribution: SyncCommitteeContribution,
+}
+
 #[derive(Debug, Default)]
 pub struct OperationPool {
     signed_voluntary_exits: RwLock<HashMap<u64, SignedVoluntaryExit>>,
     signed_bls_to_execution_changes: RwLock<HashMap<B256, SignedBLSToExecutionChange>>,
     proposer_preparations: RwLock<HashMap<u64, ProposerPreparation>>,
+    proposer_slashings: RwLock<HashMap<u64, ProposerSlashing>>,
+    /// Signatures of sync committee messages, keyed by validator index.
+    sync_committee_messages: RwLock<HashMap<SyncContributionKey, HashMap<u64, BLSSignature>>>,
+    sync_contributions: RwLock<HashMap<SyncContributionKey, CachedSyncContribution>>,
 }
 
 impl OperationPool {
@@ -68,6 +91,96 @@ impl OperationPool {
         self.signed_bls_to_execution_changes.write().remove(&root);
     }
 
+    pub fn insert_proposer_slashing(&self, proposer_slashing: ProposerSlashing) {
+        self.proposer_slashings.write().insert(
+            proposer_slashing.signed_header_1.message.proposer_index,
+            proposer_slashing,
+        );
+    }
+
+    pub fn get_proposer_slashings(&self) -> Vec<ProposerSlashing> {
+        self.proposer_slashings.read().values().cloned().collect()
+    }
+
+    pub fn insert_sync_committee_message(
+        &self,
+        subcommittee_index: u64,
+        sync_committee_message: &SyncCommitteeMessage,
+    ) {
+        self.sync_committee_messages
+            .write()
+            .entry(SyncContributionKey {
+                slot: sync_committee_message.slot,
+                beacon_block_root: sync_committee_message.beacon_block_root,
+                subcommittee_index,
+            })
+            .or_default()
+            .insert(
+                sync_committee_message.validator_index,
+                sync_committee_message.signature.clone(),
+            );
+    }
+
+    /// Returns the validator indices and signatures of the pooled messages of `key`.
+    pub fn get_sync_committee_messages(
+        &self,
+        key: &SyncContributionKey,
+    ) -> Vec<(u64, BLSSignature)> {
+        self.sync_committee_messages
+            .read()
+            .get(key)
+            .map(|messages| {
+                messages
+                    .iter()
+                    .map(|(validator_index, signature)| (*validator_index, signature.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the cached contribution of `key` if no message was pooled since it was aggregated.
+    pub fn get_cached_sync_contribution(
+        &self,
+        key: &SyncContributionKey,
+    ) -> Option<SyncCommitteeContribution> {
+        let message_count = self
+            .sync_committee_messages
+            .read()
+            .get(key)
+            .map_or(0, HashMap::len);
+        self.sync_contributions
+            .read()
+            .get(key)
+            .filter(|cached| cached.message_count == message_count)
+            .map(|cached| cached.contribution.clone())
+    }
+
+    pub fn cache_sync_contribution(
+        &self,
+        key: SyncContributionKey,
+        message_count: usize,
+        contribution: SyncCommitteeContribution,
+    ) {
+        self.sync_contributions.write().insert(
+            key,
+            CachedSyncContribution {
+                message_count,
+                contribution,
+            },
+        );
+    }
+
+    /// Drops the sync committee messages and contributions which are too old to be included in a
+    /// block proposed at `current_slot`.
+    pub fn clean_sync_committee_messages(&self, current_slot: u64) {
+        self.sync_committee_messages
+            .write()
+            .retain(|key, _| key.slot + 1 >= current_slot);
+        self.sync_contributions
+            .write()
+            .retain(|key, _| key.slot + 1 >= current_slot);
+    }
+
     pub fn insert_proposer_preparation(
         &self,
         validator_index: u64,
@@ -111,6 +224,43 @@ impl OperationPool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sync_contribution_cache_is_invalidated_by_new_messages() {
+        let operation_pool = OperationPool::default();
+        let message = |validator_index| SyncCommitteeMessage {
+            slot: 10,
+            beacon_block_root: B256::repeat_byte(1),
+            validator_index,
+            signature: BLSSignature::default(),
+        };
+        let key = SyncContributionKey {
+            slot: 10,
+            beacon_block_root: B256::repeat_byte(1),
+            subcommittee_index: 2,
+        };
+        let contribution = SyncCommitteeContribution {
+            slot: 10,
+            beacon_block_root: B256::repeat_byte(1),
+            subcommittee_index: 2,
+            aggregation_bits: Default::default(),
+            signature: BLSSignature::default(),
+        };
+
+        operation_pool.insert_sync_committee_message(2, &message(1));
+        operation_pool.cache_sync_contribution(key, 1, contribution.clone());
+        assert_eq!(
+            operation_pool.get_cached_sync_contribution(&key),
+            Some(contribution)
+        );
+
+        operation_pool.insert_sync_committee_message(2, &message(2));
+        assert_eq!(operation_pool.get_sync_committee_messages(&key).len(), 2);
+        assert_eq!(operation_pool.get_cached_sync_contribution(&key), None);
+
+        operation_pool.clean_sync_committee_messages(12);
+        assert!(operation_pool.get_sync_committee_messages(&key).is_empty());
+    }
+
     #[test]
     fn test_proposer_preparation_operations() {
         let operation_pool = OperationPool::default();
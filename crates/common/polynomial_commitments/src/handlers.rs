@@ -1,8 +1,11 @@
-use kzg::eip_4844::verify_blob_kzg_proof_batch_raw;
+use anyhow::{anyhow, ensure};
+use kzg::{DAS, eip_4844::verify_blob_kzg_proof_batch_raw};
 use ream_consensus_beacon::{
     execution_engine::rpc_types::get_blobs::Blob,
+    fulu::data_column_sidecar::{Cell, DataColumnSidecar},
     polynomial_commitments::{kzg_commitment::KZGCommitment, kzg_proof::KZGProof},
 };
+use ream_consensus_misc::constants::BYTES_PER_CELL;
 
 use super::{error::KzgError, trusted_setup};
 
@@ -35,3 +38,74 @@ pub fn verify_blob_kzg_proof_batch(
 
     result.map_err(KzgError::KzgError).map_err(Into::into)
 }
+
+/// Check that the blobs and blob KZG proofs of a block match its ``blob_kzg_commitments``.
+pub fn validate_blobs_and_kzg_commitments(
+    blobs: &[Blob],
+    blob_kzg_commitments: &[KZGCommitment],
+    blob_kzg_proofs: &[KZGProof],
+) -> anyhow::Result<()> {
+    ensure!(
+        blobs.len() == blob_kzg_commitments.len() && blob_kzg_proofs.len() == blobs.len(),
+        "Got {} blobs and {} blob KZG proofs for {} blob KZG commitments",
+        blobs.len(),
+        blob_kzg_proofs.len(),
+        blob_kzg_commitments.len()
+    );
+    ensure!(
+        verify_blob_kzg_proof_batch(blobs, blob_kzg_commitments, blob_kzg_proofs)?,
+        "Blob KZG proof batch verification failed"
+    );
+    Ok(())
+}
+
+/// Given a list of cells and their cell KZG proofs, verify that each cell at the matching index of
+/// ``cell_indices`` is a cell of the blob of the matching commitment.
+pub fn verify_cell_kzg_proof_batch(
+    commitments_bytes: &[KZGCommitment],
+    cell_indices: &[u64],
+    cells: &[Cell],
+    proofs_bytes: &[KZGProof],
+) -> anyhow::Result<bool> {
+    let raw_commitments = commitments_bytes
+        .iter()
+        .map(|commitment| commitment.0)
+        .collect::<Vec<_>>();
+
+    let raw_cell_indices = cell_indices
+        .iter()
+        .map(|&cell_index| cell_index as usize)
+        .collect::<Vec<_>>();
+
+    let raw_cells = cells
+        .iter()
+        .map(|cell| {
+            <[u8; BYTES_PER_CELL]>::try_from(&cell.inner[..])
+                .map_err(|err| anyhow!("Invalid cell length: {err}"))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let raw_proofs = proofs_bytes.iter().map(|proof| proof.0).collect::<Vec<_>>();
+
+    let result = trusted_setup::blst_settings().verify_cell_kzg_proof_batch_raw(
+        &raw_commitments,
+        &raw_cell_indices,
+        &raw_cells,
+        &raw_proofs,
+    );
+
+    result.map_err(KzgError::KzgError).map_err(Into::into)
+}
+
+/// Verify that the cells of the column of ``sidecar`` match its KZG commitments and proofs.
+pub fn verify_data_column_sidecar_kzg_proofs(sidecar: &DataColumnSidecar) -> anyhow::Result<bool> {
+    // The column index also represents the cell index
+    let cell_indices = vec![sidecar.index; sidecar.column.len()];
+
+    verify_cell_kzg_proof_batch(
+        &sidecar.kzg_commitments,
+        &cell_indices,
+        &sidecar.column,
+        &sidecar.kzg_proofs,
+    )
+}
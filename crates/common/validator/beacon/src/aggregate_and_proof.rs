@@ -1,4 +1,7 @@
-use ream_bls::{BLSSignature, PrivateKey, traits::Signable};
+use ream_bls::{
+    BLSSignature, PrivateKey, PublicKey,
+    traits::{Signable, Verifiable},
+};
 use ream_consensus_beacon::{attestation::Attestation, electra::beacon_state::BeaconState};
 use ream_consensus_misc::{
     constants::DOMAIN_AGGREGATE_AND_PROOF,
@@ -9,7 +12,7 @@ use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
 use tree_hash_derive::TreeHash;
 
-use crate::attestation::get_slot_signature;
+use crate::attestation::{get_selection_proof, get_slot_signature};
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
 pub struct AggregateAndProof {
@@ -18,7 +21,7 @@ pub struct AggregateAndProof {
     pub selection_proof: BLSSignature,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
 pub struct SignedAggregateAndProof {
     pub message: AggregateAndProof,
     pub signature: BLSSignature,
@@ -64,3 +67,39 @@ pub fn sign_aggregate_and_proof(
     let signing_root = compute_signing_root(aggregate_and_proof, domain);
     Ok(private_key.sign(signing_root.as_ref())?)
 }
+
+/// Builds the aggregate and proof of `aggregator_index` for `aggregate` and signs it.
+pub fn get_signed_aggregate_and_proof(
+    aggregator_index: u64,
+    aggregate: Attestation,
+    private_key: &PrivateKey,
+) -> anyhow::Result<SignedAggregateAndProof> {
+    let aggregate_and_proof = AggregateAndProof {
+        aggregator_index,
+        selection_proof: get_selection_proof(aggregate.data.slot, private_key)?,
+        aggregate,
+    };
+    Ok(SignedAggregateAndProof {
+        signature: sign_aggregate_and_proof(&aggregate_and_proof, private_key)?,
+        message: aggregate_and_proof,
+    })
+}
+
+/// Verifies that the aggregate and proof is signed by the aggregator with `public_key`.
+pub fn verify_aggregate_and_proof_signature(
+    state: &BeaconState,
+    signed_aggregate_and_proof: &SignedAggregateAndProof,
+    public_key: &PublicKey,
+) -> anyhow::Result<bool> {
+    let aggregate_and_proof = &signed_aggregate_and_proof.message;
+    let domain = state.get_domain(
+        DOMAIN_AGGREGATE_AND_PROOF,
+        Some(compute_epoch_at_slot(
+            aggregate_and_proof.aggregate.data.slot,
+        )),
+    );
+    let signing_root = compute_signing_root(aggregate_and_proof, domain);
+    Ok(signed_aggregate_and_proof
+        .signature
+        .verify(public_key, signing_root.as_ref())?)
+}
@@ -2,9 +2,9 @@ use std::{cmp::max, collections::HashSet};
 
 use anyhow::{anyhow, ensure};
 use ream_bls::{
-    PrivateKey,
+    PrivateKey, PublicKey,
     signature::BLSSignature,
-    traits::{Aggregatable, Signable},
+    traits::{Aggregatable, Signable, Verifiable},
 };
 use ream_consensus_beacon::{attestation::Attestation, electra::beacon_state::BeaconState};
 use ream_consensus_misc::{
@@ -32,13 +32,18 @@ pub fn is_aggregator(
     committee_index: u64,
     slot_signature: BLSSignature,
 ) -> anyhow::Result<bool> {
-    Ok(
-        (hash_signature_prefix_to_u64(&slot_signature) as usize).is_multiple_of(max(
-            1,
-            state.get_beacon_committee(slot, committee_index)?.len()
-                / TARGET_AGGREGATORS_PER_COMMITTEE as usize,
-        )),
-    )
+    Ok(is_aggregator_in_committee(
+        state.get_beacon_committee(slot, committee_index)?.len(),
+        &slot_signature,
+    ))
+}
+
+/// Same as [`is_aggregator`] for a committee of known length, for callers without a state.
+pub fn is_aggregator_in_committee(committee_length: usize, slot_signature: &BLSSignature) -> bool {
+    (hash_signature_prefix_to_u64(slot_signature) as usize).is_multiple_of(max(
+        1,
+        committee_length / TARGET_AGGREGATORS_PER_COMMITTEE as usize,
+    ))
 }
 
 /// Compute the correct subnet for an attestation for Phase 0.
@@ -117,6 +122,19 @@ pub fn get_slot_signature(
     Ok(private_key.sign(signing_root.as_ref())?)
 }
 
+/// Verifies that `slot_signature` is the selection proof of the validator with `public_key` for
+/// `slot`.
+pub fn verify_slot_signature(
+    state: &BeaconState,
+    slot: u64,
+    public_key: &PublicKey,
+    slot_signature: &BLSSignature,
+) -> anyhow::Result<bool> {
+    let domain = state.get_domain(DOMAIN_SELECTION_PROOF, Some(compute_epoch_at_slot(slot)));
+    let signing_root = compute_signing_root(slot, domain);
+    Ok(slot_signature.verify(public_key, signing_root.as_ref())?)
+}
+
 pub fn get_aggregate_signature(attestations: Vec<Attestation>) -> anyhow::Result<BLSSignature> {
     let signatures: Vec<&BLSSignature> = attestations
         .iter()
@@ -10,7 +10,10 @@ use eventsource_client::{Client, ClientBuilder, SSE};
 use futures::{Stream, StreamExt};
 use http_client::{ClientWithBaseUrl, ContentType};
 use ream_beacon_api_types::{
-    block::{BroadcastValidation, FullBlockData, ProduceBlockData, ProduceBlockResponse},
+    block::{
+        BroadcastValidation, FullBlockData, ProduceBlockData, ProduceBlockResponse,
+        SignedBlockContents,
+    },
     committee::BeaconCommitteeSubscription,
     duties::{AttesterDuty, ProposerDuty, SyncCommitteeDuty},
     error::ValidatorError,
@@ -26,10 +29,7 @@ use ream_beacon_api_types::{
 use ream_bls::BLSSignature;
 use ream_consensus_beacon::{
     attestation::Attestation,
-    electra::{
-        beacon_block::SignedBeaconBlock,
-        blinded_beacon_block::{BlindedBeaconBlock, SignedBlindedBeaconBlock},
-    },
+    electra::blinded_beacon_block::{BlindedBeaconBlock, SignedBlindedBeaconBlock},
     genesis::Genesis,
     single_attestation::SingleAttestation,
     voluntary_exit::SignedVoluntaryExit,
@@ -647,19 +647,16 @@ impl BeaconApiClient {
     pub async fn publish_block(
         &self,
         broadcast_validation: BroadcastValidation,
-        signed_beacon_block: SignedBeaconBlock,
+        signed_block_contents: SignedBlockContents,
     ) -> anyhow::Result<(), ValidatorError> {
         let response = self
             .http_client
             .execute(
                 self.http_client
                     .post("/eth/v2/beacon/blocks".to_string(), ContentType::Ssz)?
-                    .query(&[(
-                        "broadcast_validation",
-                        serde_json::to_string(&broadcast_validation)?,
-                    )])
+                    .query(&[("broadcast_validation", broadcast_validation)])
                     .header(ETH_CONSENSUS_VERSION_HEADER, VERSION)
-                    .body(signed_beacon_block.as_ssz_bytes())
+                    .body(signed_block_contents.as_ssz_bytes())
                     .build()?,
             )
             .await?;
@@ -686,10 +683,7 @@ impl BeaconApiClient {
                         "/eth/v2/beacon/blinded_blocks".to_string(),
                         ContentType::Ssz,
                     )?
-                    .query(&[(
-                        "broadcast_validation",
-                        serde_json::to_string(&broadcast_validation)?,
-                    )])
+                    .query(&[("broadcast_validation", broadcast_validation)])
                     .header(ETH_CONSENSUS_VERSION_HEADER, VERSION)
                     .body(signed_blinded_beacon_block.as_ssz_bytes())
                     .build()?,
@@ -1,5 +1,54 @@
+use anyhow::ensure;
+use ream_consensus_beacon::{
+    electra::beacon_block_body::BeaconBlockBody,
+    execution_engine::rpc_types::get_blobs::Blob,
+    polynomial_commitments::{kzg_commitment::KZGCommitment, kzg_proof::KZGProof},
+};
+use ream_consensus_misc::constants::MAX_BLOBS_PER_BLOCK_ELECTRA;
+use ream_execution_engine::{rpc_types::get_payload::BlobsBundleV1, utils::blob_versioned_hashes};
 use ream_network_spec::networks::beacon_network_spec;
 
 pub fn compute_subnet_for_blob_sidecar(blob_index: u64) -> u64 {
     blob_index % beacon_network_spec().blob_sidecar_subnet_count_electra
 }
+
+/// Inserts the commitments of the blobs bundle returned along with the execution payload of
+/// `body` into the body, once checked against the blob versioned hashes of the payload
+/// transactions.
+///
+/// Returns the blobs and their proofs, which are needed to build the blob sidecars of the block
+/// once it is signed.
+pub fn insert_blobs_bundle(
+    body: &mut BeaconBlockBody,
+    blobs_bundle: BlobsBundleV1,
+) -> anyhow::Result<(Vec<Blob>, Vec<KZGProof>)> {
+    let BlobsBundleV1 {
+        commitments,
+        proofs,
+        blobs,
+    } = blobs_bundle;
+    ensure!(
+        commitments.len() as u64 <= MAX_BLOBS_PER_BLOCK_ELECTRA,
+        "Blobs bundle has {} commitments, more than the maximum of {MAX_BLOBS_PER_BLOCK_ELECTRA}",
+        commitments.len()
+    );
+    ensure!(
+        commitments.len() == proofs.len() && commitments.len() == blobs.len(),
+        "Blobs bundle has {} commitments, {} proofs and {} blobs",
+        commitments.len(),
+        proofs.len(),
+        blobs.len()
+    );
+
+    let versioned_hashes = blob_versioned_hashes(&body.execution_payload.transactions)?;
+    ensure!(
+        commitments
+            .iter()
+            .map(KZGCommitment::calculate_versioned_hash)
+            .eq(versioned_hashes),
+        "Blobs bundle commitments do not match the blob versioned hashes of the payload"
+    );
+
+    body.blob_kzg_commitments = commitments;
+    Ok((blobs.into(), proofs.into()))
+}
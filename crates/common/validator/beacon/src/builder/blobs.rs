@@ -1,20 +1,23 @@
+use anyhow::ensure;
+use ream_beacon_api_types::block::SignedBlockContents;
 use ream_consensus_beacon::{
-    electra::execution_payload::ExecutionPayload,
-    polynomial_commitments::kzg_commitment::KZGCommitment,
+    electra::{
+        blinded_beacon_block::SignedBlindedBeaconBlock, execution_payload::ExecutionPayload,
+    },
+    execution_engine::rpc_types::get_blobs::Blob,
+    polynomial_commitments::{kzg_commitment::KZGCommitment, kzg_proof::KZGProof},
 };
+use ream_consensus_misc::preset::MaxBlobCommitmentsPerBlock;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use ssz_types::{
-    VariableList,
-    typenum::{U96, U1024, U1048576},
-};
+use ssz_types::VariableList;
 use tree_hash_derive::TreeHash;
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
 pub struct BlobsBundle {
-    pub blobs: VariableList<KZGCommitment, U1048576>,
-    pub commitments: VariableList<VariableList<u8, U96>, U1024>,
-    pub proofs: VariableList<VariableList<u8, U96>, U1024>,
+    pub commitments: VariableList<KZGCommitment, MaxBlobCommitmentsPerBlock>,
+    pub proofs: VariableList<KZGProof, MaxBlobCommitmentsPerBlock>,
+    pub blobs: VariableList<Blob, MaxBlobCommitmentsPerBlock>,
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash)]
@@ -22,3 +25,35 @@ pub struct ExecutionPayloadAndBlobsBundle {
     pub execution_payload: ExecutionPayload,
     pub blobs_bundle: BlobsBundle,
 }
+
+impl ExecutionPayloadAndBlobsBundle {
+    /// Reveals the full contents of a blinded block submitted to the builder, checking that the
+    /// revealed blobs are the ones committed to by the block.
+    pub fn unblind(
+        self,
+        signed_blinded_block: &SignedBlindedBeaconBlock,
+    ) -> anyhow::Result<SignedBlockContents> {
+        let BlobsBundle {
+            commitments,
+            proofs,
+            blobs,
+        } = self.blobs_bundle;
+        ensure!(
+            commitments == signed_blinded_block.message.body.blob_kzg_commitments,
+            "Builder blobs bundle does not match the blob commitments of the blinded block"
+        );
+        ensure!(
+            proofs.len() == commitments.len() && blobs.len() == commitments.len(),
+            "Builder blobs bundle has {} commitments, {} proofs and {} blobs",
+            commitments.len(),
+            proofs.len(),
+            blobs.len()
+        );
+
+        Ok(SignedBlockContents {
+            signed_block: signed_blinded_block.to_signed_beacon_block(self.execution_payload)?,
+            kzg_proofs: proofs.into(),
+            blobs: blobs.into(),
+        })
+    }
+}
@@ -4,14 +4,15 @@ use ream_consensus_beacon::{
     electra::execution_payload_header::ExecutionPayloadHeader,
     execution_requests::ExecutionRequests, polynomial_commitments::kzg_commitment::KZGCommitment,
 };
+use ream_consensus_misc::preset::MaxBlobCommitmentsPerBlock;
 use serde::{Deserialize, Serialize};
-use ssz_types::{VariableList, typenum::U4096};
+use ssz_types::VariableList;
 use tree_hash_derive::TreeHash;
 
 #[derive(Debug, PartialEq, Eq, Clone, TreeHash, Serialize, Deserialize)]
 pub struct BuilderBid {
     pub header: ExecutionPayloadHeader,
-    pub blob_kzg_commitments: VariableList<KZGCommitment, U4096>,
+    pub blob_kzg_commitments: VariableList<KZGCommitment, MaxBlobCommitmentsPerBlock>,
     pub execution_requests: ExecutionRequests,
     pub value: U256,
     #[serde(rename = "pubkey")]
@@ -6,10 +6,11 @@ use ream_beacon_api_types::responses::{ETH_CONSENSUS_VERSION_HEADER, VERSION};
 use ream_bls::PublicKey;
 use ream_consensus_beacon::electra::blinded_beacon_block::SignedBlindedBeaconBlock;
 use reqwest::StatusCode;
+use serde::Deserialize;
 use url::Url;
 
 use super::{
-    blobs::ExecutionPayloadAndBlobsBundle, builder_bid::SignedBuilderBid,
+    blobs::ExecutionPayloadAndBlobsBundle, builder_bid::SignedBuilderBid, relay::RelayConfig,
     validator_registration::SignedValidatorRegistrationV1,
 };
 use crate::beacon_api_client::http_client::{ClientWithBaseUrl, ContentType};
@@ -17,40 +18,52 @@ use crate::beacon_api_client::http_client::{ClientWithBaseUrl, ContentType};
 #[derive(Debug, Clone)]
 pub struct BuilderConfig {
     pub builder_enabled: bool,
-    pub mev_relay_url: Url,
+    pub relays: Vec<RelayConfig>,
+    /// The gas limit the validators register with the relays.
+    pub gas_limit: u64,
 }
 
+#[derive(Debug, Deserialize)]
+struct BuilderBidResponse {
+    data: SignedBuilderBid,
+}
+
+#[derive(Debug)]
 pub struct BuilderClient {
     client: ClientWithBaseUrl,
 }
 
 impl BuilderClient {
     pub fn new(
-        config: BuilderConfig,
+        relay_url: Url,
         request_timeout: Duration,
         content_type: ContentType,
     ) -> anyhow::Result<Self> {
         Ok(Self {
-            client: ClientWithBaseUrl::new(config.mev_relay_url, request_timeout, content_type)?,
+            client: ClientWithBaseUrl::new(relay_url, request_timeout, content_type)?,
         })
     }
 
-    /// Get an execution payload header.
+    /// Get an execution payload header, or `None` if the builder has no bid for the slot.
     pub async fn get_builder_header(
         &self,
         parent_hash: B256,
         public_key: &PublicKey,
         slot: u64,
-    ) -> anyhow::Result<SignedBuilderBid> {
-        Ok(self
+    ) -> anyhow::Result<Option<SignedBuilderBid>> {
+        let response = self
             .client
             .get(format!(
                 "/eth/v1/builder/header/{slot}/{parent_hash:?}/{public_key:?}"
             ))?
             .send()
-            .await?
-            .json::<SignedBuilderBid>()
-            .await?)
+            .await?;
+
+        match response.status() {
+            StatusCode::OK => Ok(Some(response.json::<BuilderBidResponse>().await?.data)),
+            StatusCode::NO_CONTENT => Ok(None),
+            status => Err(anyhow!("failed to get builder header: {status:?}")),
+        }
     }
 
     /// Submit a signed blinded block and get unblinded execution payload.
@@ -84,19 +97,16 @@ impl BuilderClient {
         }
     }
 
-    /// Registers a validator's preferred fee recipient and gas limit.
-    pub async fn resgister_validator(
+    /// Registers the preferred fee recipients and gas limits of the validators.
+    pub async fn register_validators(
         &self,
-        signed_registration: SignedValidatorRegistrationV1,
+        signed_registrations: &[SignedValidatorRegistrationV1],
     ) -> anyhow::Result<()> {
         let response = self
             .client
-            .post(
-                "/eth/v1/builder/register_validator".to_string(),
-                ContentType::Json,
-            )?
+            .post("/eth/v1/builder/validators".to_string(), ContentType::Json)?
             .header(ETH_CONSENSUS_VERSION_HEADER, VERSION)
-            .json(&signed_registration)
+            .json(signed_registrations)
             .send()
             .await?;
 
@@ -0,0 +1,120 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use alloy_primitives::Address;
+use anyhow::{anyhow, ensure};
+use ream_bls::{PrivateKey, PublicKey};
+
+use super::validator_registration::{SignedValidatorRegistrationV1, ValidatorRegistrationV1};
+
+pub const VALIDATOR_REGISTRATIONS_FILE_NAME: &str = "validator_registrations.json";
+
+/// Relays reject registrations whose timestamp is further than this in the future, in seconds.
+pub const MAX_REGISTRATION_CLOCK_DISPARITY: u64 = 10;
+
+/// The signed registrations of the validators, persisted so that a restart sends the same
+/// registrations to the relays instead of re-signing them with a newer timestamp.
+#[derive(Debug)]
+pub struct ValidatorRegistrations {
+    path: PathBuf,
+    registrations: HashMap<PublicKey, SignedValidatorRegistrationV1>,
+}
+
+impl ValidatorRegistrations {
+    /// Loads the registrations persisted at `path`, or starts without registrations if the file
+    /// does not exist yet.
+    pub fn load(path: PathBuf) -> anyhow::Result<Self> {
+        let registrations = if path.exists() {
+            serde_json::from_slice::<Vec<SignedValidatorRegistrationV1>>(&fs::read(&path)?)
+                .map_err(|err| anyhow!("Failed to parse validator registrations {path:?}: {err}"))?
+                .into_iter()
+                .map(|registration| (registration.message.public_key.clone(), registration))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Self {
+            path,
+            registrations,
+        })
+    }
+
+    pub fn registrations(&self) -> Vec<SignedValidatorRegistrationV1> {
+        self.registrations.values().cloned().collect()
+    }
+
+    /// Keeps the registration of the validator if its fee recipient and gas limit are unchanged,
+    /// otherwise signs a new registration at `timestamp`.
+    ///
+    /// Returns `true` if a new registration was signed.
+    pub fn update(
+        &mut self,
+        public_key: &PublicKey,
+        private_key: &PrivateKey,
+        fee_recipient: Address,
+        gas_limit: u64,
+        timestamp: u64,
+    ) -> anyhow::Result<bool> {
+        let previous = self
+            .registrations
+            .get(public_key)
+            .map(|registration| &registration.message);
+        if previous.is_some_and(|previous| {
+            previous.fee_recipient == fee_recipient && previous.gas_limit == gas_limit
+        }) {
+            return Ok(false);
+        }
+
+        let registration = ValidatorRegistrationV1 {
+            fee_recipient,
+            gas_limit,
+            timestamp,
+            public_key: public_key.clone(),
+        };
+        validate_registration_update(previous, &registration, timestamp)?;
+
+        self.registrations.insert(
+            public_key.clone(),
+            registration.create_signed_registration(private_key)?,
+        );
+        Ok(true)
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let temp_path = self.path.with_extension("json.tmp");
+        fs::write(
+            &temp_path,
+            serde_json::to_vec_pretty(&self.registrations())?,
+        )?;
+        fs::rename(&temp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Checks the rules relays apply before replacing the registration of a validator: the
+/// registration must not be from the future, must be newer than the one it replaces and must not
+/// send the fees to the zero address.
+pub fn validate_registration_update(
+    previous: Option<&ValidatorRegistrationV1>,
+    registration: &ValidatorRegistrationV1,
+    current_time: u64,
+) -> anyhow::Result<()> {
+    ensure!(
+        registration.timestamp <= current_time + MAX_REGISTRATION_CLOCK_DISPARITY,
+        "Registration timestamp {} is ahead of the current time {current_time}",
+        registration.timestamp
+    );
+    ensure!(
+        registration.fee_recipient != Address::ZERO,
+        "Registration fee recipient must not be the zero address"
+    );
+    if let Some(previous) = previous {
+        ensure!(
+            registration.timestamp > previous.timestamp,
+            "Registration timestamp {} is not newer than the previous registration timestamp {}",
+            registration.timestamp,
+            previous.timestamp
+        );
+    }
+    Ok(())
+}
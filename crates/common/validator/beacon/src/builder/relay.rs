@@ -0,0 +1,232 @@
+use std::{fs, path::Path, str::FromStr, time::Duration};
+
+use alloy_primitives::{B256, U256};
+use anyhow::{anyhow, bail, ensure};
+use futures::future::join_all;
+use ream_bls::PublicKey;
+use ream_consensus_beacon::electra::blinded_beacon_block::SignedBlindedBeaconBlock;
+use serde::Deserialize;
+use tokio::time::timeout;
+use tracing::warn;
+use url::Url;
+
+use super::{
+    blobs::ExecutionPayloadAndBlobsBundle, builder_bid::SignedBuilderBid,
+    builder_client::BuilderClient, validator_registration::SignedValidatorRegistrationV1,
+    verify::verify_bid_signature,
+};
+use crate::beacon_api_client::http_client::ContentType;
+
+/// The time given to a relay to answer a header request when none is configured, in
+/// milliseconds.
+pub const DEFAULT_GET_HEADER_TIMEOUT_MS: u64 = 1000;
+
+fn default_get_header_timeout_ms() -> u64 {
+    DEFAULT_GET_HEADER_TIMEOUT_MS
+}
+
+/// A relay and the policy applied to its bids.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RelayConfig {
+    /// The URL of the relay. The public key of the relay can be given as the user of the URL, in
+    /// which case only the bids signed by that key are accepted.
+    pub url: Url,
+    #[serde(default = "default_get_header_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Bids below this value, in wei, are ignored.
+    #[serde(default)]
+    pub min_bid: U256,
+    /// The validators allowed to use the relay, all of them if unset.
+    #[serde(default)]
+    pub validators: Option<Vec<PublicKey>>,
+}
+
+impl RelayConfig {
+    /// A relay with the default policy.
+    pub fn new(url: Url) -> Self {
+        Self {
+            url,
+            timeout_ms: DEFAULT_GET_HEADER_TIMEOUT_MS,
+            min_bid: U256::ZERO,
+            validators: None,
+        }
+    }
+
+    /// Loads a list of relays from a YAML file.
+    pub fn load_from_file(path: impl AsRef<Path>) -> anyhow::Result<Vec<Self>> {
+        let path = path.as_ref();
+        serde_yaml::from_slice(&fs::read(path)?)
+            .map_err(|err| anyhow!("Failed to parse relay configuration {path:?}: {err}"))
+    }
+
+    pub fn is_enabled_for(&self, public_key: &PublicKey) -> bool {
+        self.validators
+            .as_ref()
+            .is_none_or(|validators| validators.contains(public_key))
+    }
+}
+
+#[derive(Debug)]
+struct Relay {
+    config: RelayConfig,
+    public_key: Option<PublicKey>,
+    client: BuilderClient,
+}
+
+impl Relay {
+    /// Checks a bid of the relay against the policy of the relay and the expected parent.
+    fn verify_bid(&self, bid: &SignedBuilderBid, parent_hash: B256) -> anyhow::Result<()> {
+        ensure!(
+            bid.message.header.parent_hash == parent_hash,
+            "Bid is built on {} instead of {parent_hash}",
+            bid.message.header.parent_hash
+        );
+        if let Some(public_key) = &self.public_key {
+            ensure!(
+                bid.message.public_key == *public_key,
+                "Bid is not signed by the relay public key"
+            );
+        }
+        ensure!(
+            bid.message.value >= self.config.min_bid,
+            "Bid value {} is below the minimum bid {}",
+            bid.message.value,
+            self.config.min_bid
+        );
+        ensure!(verify_bid_signature(bid)?, "Bid signature is invalid");
+        Ok(())
+    }
+}
+
+/// The relays the validators can get blocks from.
+#[derive(Debug, Default)]
+pub struct RelaySet {
+    relays: Vec<Relay>,
+}
+
+impl RelaySet {
+    pub fn new(configs: Vec<RelayConfig>, request_timeout: Duration) -> anyhow::Result<Self> {
+        let relays = configs
+            .into_iter()
+            .map(|config| {
+                let public_key = match config.url.username() {
+                    "" => None,
+                    username => Some(PublicKey::from_str(username).map_err(|err| {
+                        anyhow!("Invalid public key in relay URL {}: {err:?}", config.url)
+                    })?),
+                };
+                let mut url = config.url.clone();
+                url.set_username("")
+                    .map_err(|_| anyhow!("Invalid relay URL {}", config.url))?;
+                Ok::<_, anyhow::Error>(Relay {
+                    client: BuilderClient::new(url, request_timeout, ContentType::Json)?,
+                    public_key,
+                    config,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Self { relays })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.relays.is_empty()
+    }
+
+    /// Requests a header from every relay enabled for the proposer at once, and returns the
+    /// highest bid which passes the policy of its relay together with the index of the relay.
+    /// A relay which does not answer within its timeout is skipped.
+    pub async fn get_best_bid(
+        &self,
+        parent_hash: B256,
+        public_key: &PublicKey,
+        slot: u64,
+    ) -> Option<(usize, SignedBuilderBid)> {
+        let requests = self
+            .relays
+            .iter()
+            .enumerate()
+            .filter(|(_, relay)| relay.config.is_enabled_for(public_key))
+            .map(|(index, relay)| async move {
+                let result = async {
+                    let bid = timeout(
+                        Duration::from_millis(relay.config.timeout_ms),
+                        relay
+                            .client
+                            .get_builder_header(parent_hash, public_key, slot),
+                    )
+                    .await
+                    .map_err(|_| anyhow!("Timed out"))??
+                    .ok_or_else(|| anyhow!("No bid"))?;
+                    relay.verify_bid(&bid, parent_hash)?;
+                    Ok::<_, anyhow::Error>((index, bid))
+                }
+                .await;
+                result
+                    .inspect_err(|err| {
+                        warn!(
+                            "Ignoring header of relay {} at slot {slot}: {err}",
+                            relay.config.url
+                        )
+                    })
+                    .ok()
+            });
+
+        join_all(requests)
+            .await
+            .into_iter()
+            .flatten()
+            .max_by_key(|(_, bid)| bid.message.value)
+    }
+
+    /// Sends each relay the registrations of the validators enabled for it.
+    pub async fn register_validators(
+        &self,
+        registrations: &[SignedValidatorRegistrationV1],
+    ) -> anyhow::Result<()> {
+        let requests = self.relays.iter().map(|relay| async move {
+            let registrations = registrations
+                .iter()
+                .filter(|registration| {
+                    relay
+                        .config
+                        .is_enabled_for(&registration.message.public_key)
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            if registrations.is_empty() {
+                return Ok(());
+            }
+            relay
+                .client
+                .register_validators(&registrations)
+                .await
+                .map_err(|err| anyhow!("Relay {}: {err}", relay.config.url))
+        });
+
+        let errors = join_all(requests)
+            .await
+            .into_iter()
+            .filter_map(Result::err)
+            .map(|err| err.to_string())
+            .collect::<Vec<_>>();
+        if !errors.is_empty() {
+            bail!("Failed to register validators: {}", errors.join(", "));
+        }
+        Ok(())
+    }
+
+    /// Submits a blinded block to the relay which provided its bid, and returns the revealed
+    /// payload.
+    pub async fn submit_blinded_block(
+        &self,
+        relay_index: usize,
+        signed_blinded_block: SignedBlindedBeaconBlock,
+    ) -> anyhow::Result<ExecutionPayloadAndBlobsBundle> {
+        self.relays
+            .get(relay_index)
+            .ok_or_else(|| anyhow!("Unknown relay {relay_index}"))?
+            .client
+            .get_blinded_blocks(signed_blinded_block)
+            .await
+    }
+}
@@ -1,6 +1,8 @@
 use alloy_primitives::{aliases::B32, fixed_bytes};
 
 pub const ATTESTATION_SUBNET_COUNT: u64 = 64;
+/// The gas limit registered with relays when none is configured.
+pub const DEFAULT_GAS_LIMIT: u64 = 36_000_000;
 pub const DOMAIN_CONTRIBUTION_AND_PROOF: B32 = fixed_bytes!("0x09000000");
 pub const DOMAIN_SELECTION_PROOF: B32 = fixed_bytes!("0x05000000");
 pub const DOMAIN_SYNC_COMMITTEE_SELECTION_PROOF: B32 = fixed_bytes!("0x08000000");
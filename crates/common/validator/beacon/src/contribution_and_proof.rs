@@ -1,10 +1,17 @@
 use alloy_primitives::B256;
-use ream_bls::{BLSSignature, PrivateKey, traits::Signable};
-use ream_consensus_misc::misc::{compute_domain, compute_signing_root};
+use ream_bls::{
+    BLSSignature, PrivateKey, PublicKey,
+    traits::{Signable, Verifiable},
+};
+use ream_consensus_beacon::electra::beacon_state::BeaconState;
+use ream_consensus_misc::{
+    misc::{compute_domain, compute_epoch_at_slot, compute_signing_root},
+    preset::SyncSubcommitteeSize,
+};
 use ream_network_spec::networks::beacon_network_spec;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use ssz_types::{BitVector, typenum::U128};
+use ssz_types::BitVector;
 use tree_hash_derive::TreeHash;
 
 use crate::{
@@ -18,7 +25,7 @@ pub struct SyncCommitteeContribution {
     pub beacon_block_root: B256,
     #[serde(with = "serde_utils::quoted_u64")]
     pub subcommittee_index: u64,
-    pub aggregation_bits: BitVector<U128>,
+    pub aggregation_bits: BitVector<SyncSubcommitteeSize>,
     pub signature: BLSSignature,
 }
 
@@ -64,3 +71,36 @@ pub fn get_contribution_and_proof_signature(
     let signing_root = compute_signing_root(contribution_and_proof, domain);
     Ok(private_key.sign(signing_root.as_ref())?)
 }
+
+/// Builds the contribution and proof of `aggregator_index` for `contribution` and signs it.
+pub fn get_signed_contribution_and_proof(
+    contribution: SyncCommitteeContribution,
+    aggregator_index: u64,
+    private_key: &PrivateKey,
+) -> anyhow::Result<SignedContributionAndProof> {
+    let contribution_and_proof =
+        get_contribution_and_proof(contribution, aggregator_index, private_key)?;
+    Ok(SignedContributionAndProof {
+        signature: get_contribution_and_proof_signature(&contribution_and_proof, private_key)?,
+        message: contribution_and_proof,
+    })
+}
+
+/// Verifies that the contribution and proof is signed by the aggregator with `public_key`.
+pub fn verify_contribution_and_proof_signature(
+    state: &BeaconState,
+    signed_contribution_and_proof: &SignedContributionAndProof,
+    public_key: &PublicKey,
+) -> anyhow::Result<bool> {
+    let contribution_and_proof = &signed_contribution_and_proof.message;
+    let domain = state.get_domain(
+        DOMAIN_CONTRIBUTION_AND_PROOF,
+        Some(compute_epoch_at_slot(
+            contribution_and_proof.contribution.slot,
+        )),
+    );
+    let signing_root = compute_signing_root(contribution_and_proof, domain);
+    Ok(signed_contribution_and_proof
+        .signature
+        .verify(public_key, signing_root.as_ref())?)
+}
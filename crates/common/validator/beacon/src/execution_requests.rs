@@ -5,8 +5,9 @@ use ream_consensus_beacon::{
     electra::beacon_state::BeaconState, execution_requests::ExecutionRequests,
     withdrawal_request::WithdrawalRequest,
 };
-use ream_consensus_misc::constants::{
-    CONSOLIDATION_REQUEST_TYPE, DEPOSIT_REQUEST_TYPE, WITHDRAWAL_REQUEST_TYPE,
+use ream_consensus_misc::{
+    constants::{CONSOLIDATION_REQUEST_TYPE, DEPOSIT_REQUEST_TYPE, WITHDRAWAL_REQUEST_TYPE},
+    preset::{MaxDepositRequestsPerPayload, MaxWithdrawalRequestsPerPayload},
 };
 use ream_execution_engine::{
     ExecutionEngine,
@@ -15,10 +16,7 @@ use ream_execution_engine::{
     },
 };
 use ssz::Decode;
-use ssz_types::{
-    VariableList,
-    typenum::{U2, U16, U8192},
-};
+use ssz_types::{VariableList, typenum::U2};
 use tree_hash::TreeHash;
 
 pub fn get_execution_requests(
@@ -43,8 +41,10 @@ pub fn get_execution_requests(
                     "Multiple deposit requests found in execution requests"
                 );
                 deposits = Some(
-                    VariableList::<DepositRequest, U8192>::from_ssz_bytes(&request_bytes[1..])
-                        .map_err(|err| anyhow!("Failed to deserialize DepositRequest: {err:?}"))?,
+                    VariableList::<DepositRequest, MaxDepositRequestsPerPayload>::from_ssz_bytes(
+                        &request_bytes[1..],
+                    )
+                    .map_err(|err| anyhow!("Failed to deserialize DepositRequest: {err:?}"))?,
                 );
             }
             WITHDRAWAL_REQUEST_TYPE => {
@@ -53,7 +53,7 @@ pub fn get_execution_requests(
                     "Multiple withdrawal requests found in execution requests"
                 );
                 withdrawals = Some(
-                    VariableList::<WithdrawalRequest, U16>::from_ssz_bytes(&request_bytes[1..])
+                    VariableList::<WithdrawalRequest, MaxWithdrawalRequestsPerPayload>::from_ssz_bytes(&request_bytes[1..])
                         .map_err(|err| {
                             anyhow!("Failed to deserialize WithdrawalRequest: {err:?}")
                         })?,
@@ -3,8 +3,8 @@ use std::{cmp::max, collections::HashSet};
 use alloy_primitives::B256;
 use anyhow::{anyhow, bail, ensure};
 use ream_bls::{
-    BLSSignature, PrivateKey,
-    traits::{Aggregatable, Signable},
+    BLSSignature, PrivateKey, PublicKey,
+    traits::{Aggregatable, Signable, Verifiable},
 };
 use ream_consensus_beacon::{
     electra::{beacon_block::BeaconBlock, beacon_state::BeaconState},
@@ -13,15 +13,19 @@ use ream_consensus_beacon::{
 use ream_consensus_misc::{
     constants::{DOMAIN_SYNC_COMMITTEE, EPOCHS_PER_SYNC_COMMITTEE_PERIOD, SYNC_COMMITTEE_SIZE},
     misc::{compute_domain, compute_epoch_at_slot, compute_signing_root},
+    preset::{SyncCommitteeSize, SyncSubcommitteeSize},
 };
 use ream_network_spec::networks::beacon_network_spec;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use ssz_types::{BitVector, typenum::U512};
+use ssz_types::BitVector;
 use tree_hash_derive::TreeHash;
 
 use crate::{
-    constants::{SYNC_COMMITTEE_SUBNET_COUNT, TARGET_AGGREGATORS_PER_COMMITTEE},
+    constants::{
+        DOMAIN_SYNC_COMMITTEE_SELECTION_PROOF, SYNC_COMMITTEE_SUBNET_COUNT,
+        TARGET_AGGREGATORS_PER_COMMITTEE,
+    },
     contribution_and_proof::SyncCommitteeContribution,
     hash_signature_prefix_to_u64,
 };
@@ -103,7 +107,7 @@ pub fn compute_subnets_for_sync_committee(
 
     Ok(sync_committee_indices
         .into_iter()
-        .map(|index| index as u64 / (SYNC_COMMITTEE_SIZE / SYNC_COMMITTEE_SUBNET_COUNT))
+        .map(|index| compute_subcommittee_index(index as u64))
         .collect())
 }
 
@@ -111,7 +115,7 @@ pub fn process_sync_committee_contributions(
     block: &mut BeaconBlock,
     contributions: HashSet<SyncCommitteeContribution>,
 ) -> anyhow::Result<()> {
-    let mut sync_committee_bits = BitVector::<U512>::new();
+    let mut sync_committee_bits = BitVector::<SyncCommitteeSize>::new();
     let mut signatures = vec![];
     let sync_subcommittee_size = SYNC_COMMITTEE_SIZE / SYNC_COMMITTEE_SUBNET_COUNT;
 
@@ -137,13 +141,78 @@ pub fn process_sync_committee_contributions(
     Ok(())
 }
 
+/// Aggregates the signatures of the sync committee messages of the members of a subcommittee
+/// into a contribution. A validator which appears several times in the subcommittee sets all of
+/// its bits, and its signature is aggregated once per bit.
+///
+/// Returns `None` if none of the messages is from a member of the subcommittee.
+pub fn aggregate_sync_committee_contribution(
+    state: &BeaconState,
+    slot: u64,
+    beacon_block_root: B256,
+    subcommittee_index: u64,
+    messages: &[(u64, BLSSignature)],
+) -> anyhow::Result<Option<SyncCommitteeContribution>> {
+    let sync_committee_period = compute_sync_committee_period(compute_epoch_at_slot(slot));
+    let current_sync_committee_period = compute_sync_committee_period(state.get_current_epoch());
+    let sync_committee = if sync_committee_period == current_sync_committee_period {
+        &state.current_sync_committee
+    } else if sync_committee_period == current_sync_committee_period + 1 {
+        &state.next_sync_committee
+    } else {
+        bail!(
+            "Sync committee of period {sync_committee_period} is unknown at period {current_sync_committee_period}"
+        );
+    };
+
+    let sync_subcommittee_size = SYNC_COMMITTEE_SIZE / SYNC_COMMITTEE_SUBNET_COUNT;
+    let start = (subcommittee_index * sync_subcommittee_size) as usize;
+    let subcommittee_public_keys = sync_committee
+        .public_keys
+        .get(start..start + sync_subcommittee_size as usize)
+        .ok_or_else(|| anyhow!("Invalid subcommittee index: {subcommittee_index}"))?;
+
+    let mut aggregation_bits = BitVector::<SyncSubcommitteeSize>::new();
+    let mut signatures = vec![];
+    for (validator_index, signature) in messages {
+        let Some(validator) = state.validators.get(*validator_index as usize) else {
+            continue;
+        };
+        for (position, public_key) in subcommittee_public_keys.iter().enumerate() {
+            if *public_key == validator.public_key {
+                aggregation_bits
+                    .set(position, true)
+                    .map_err(|err| anyhow!("Failed to set aggregation bit: {err:?}"))?;
+                signatures.push(signature);
+            }
+        }
+    }
+
+    if signatures.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(SyncCommitteeContribution {
+        slot,
+        beacon_block_root,
+        subcommittee_index,
+        aggregation_bits,
+        signature: BLSSignature::aggregate(&signatures)?,
+    }))
+}
+
+/// Returns the subcommittee of the member at `sync_committee_index` in the sync committee.
+pub fn compute_subcommittee_index(sync_committee_index: u64) -> u64 {
+    sync_committee_index / (SYNC_COMMITTEE_SIZE / SYNC_COMMITTEE_SUBNET_COUNT)
+}
+
 pub fn get_sync_committee_selection_proof(
     slot: u64,
     subcommittee_index: u64,
     private_key: &PrivateKey,
 ) -> anyhow::Result<BLSSignature> {
     let domain = compute_domain(
-        DOMAIN_SYNC_COMMITTEE,
+        DOMAIN_SYNC_COMMITTEE_SELECTION_PROOF,
         Some(beacon_network_spec().electra_fork_version),
         None,
     );
@@ -157,6 +226,29 @@ pub fn get_sync_committee_selection_proof(
     Ok(private_key.sign(signing_root.as_ref())?)
 }
 
+/// Verifies that `selection_proof` is the sync committee selection proof of the validator with
+/// `public_key` for `subcommittee_index` at `slot`.
+pub fn verify_sync_committee_selection_proof(
+    state: &BeaconState,
+    slot: u64,
+    subcommittee_index: u64,
+    public_key: &PublicKey,
+    selection_proof: &BLSSignature,
+) -> anyhow::Result<bool> {
+    let domain = state.get_domain(
+        DOMAIN_SYNC_COMMITTEE_SELECTION_PROOF,
+        Some(compute_epoch_at_slot(slot)),
+    );
+    let signing_root = compute_signing_root(
+        SyncAggregatorSelectionData {
+            slot,
+            subcommittee_index,
+        },
+        domain,
+    );
+    Ok(selection_proof.verify(public_key, signing_root.as_ref())?)
+}
+
 pub fn is_sync_committee_aggregator(signature: &BLSSignature) -> bool {
     hash_signature_prefix_to_u64(signature).is_multiple_of(max(
         1,
@@ -7,10 +7,10 @@ use std::{
 };
 
 use alloy_primitives::Address;
-use anyhow::{anyhow, bail};
+use anyhow::{anyhow, bail, ensure};
 use futures::future::try_join_all;
 use ream_beacon_api_types::{
-    block::{BroadcastValidation, ProduceBlockData},
+    block::{BroadcastValidation, ProduceBlockData, SignedBlockContents},
     duties::{AttesterDuty, ProposerDuty, SyncCommitteeDuty},
     id::{ID, ValidatorID},
     request::SyncCommitteeRequestItem,
@@ -21,7 +21,7 @@ use ream_consensus_beacon::{
 };
 use ream_consensus_misc::{
     attestation_data::AttestationData,
-    constants::{DOMAIN_SYNC_COMMITTEE, INTERVALS_PER_SLOT, SLOTS_PER_EPOCH, SYNC_COMMITTEE_SIZE},
+    constants::{DOMAIN_SYNC_COMMITTEE, INTERVALS_PER_SLOT, SLOTS_PER_EPOCH},
     misc::{compute_domain, compute_epoch_at_slot, compute_signing_root},
 };
 use ream_executor::ReamExecutor;
@@ -33,16 +33,22 @@ use tracing::{error, info, warn};
 use tree_hash::TreeHash;
 
 use crate::{
-    aggregate_and_proof::{AggregateAndProof, SignedAggregateAndProof, sign_aggregate_and_proof},
-    attestation::{get_selection_proof, sign_attestation_data},
+    aggregate_and_proof::get_signed_aggregate_and_proof,
+    attestation::sign_attestation_data,
     beacon_api_client::BeaconApiClient,
     block::{sign_beacon_block, sign_blinded_beacon_block},
-    constants::SYNC_COMMITTEE_SUBNET_COUNT,
+    builder::{
+        builder_client::BuilderConfig, registration::ValidatorRegistrations, relay::RelaySet,
+    },
+    constants::DEFAULT_GAS_LIMIT,
     contribution_and_proof::{
         ContributionAndProof, SignedContributionAndProof, get_contribution_and_proof_signature,
     },
     randao::sign_randao_reveal,
-    sync_committee::{get_sync_committee_selection_proof, is_sync_committee_aggregator},
+    sync_committee::{
+        compute_subcommittee_index, get_sync_committee_selection_proof,
+        is_sync_committee_aggregator,
+    },
     voluntary_exit::sign_voluntary_exit,
 };
 
@@ -80,6 +86,9 @@ pub struct ValidatorService {
     pub sync_committee_duties: Vec<SyncCommitteeDuty>,
     pub sync_aggregator_infos: Vec<SyncTaskInfo>,
     pub sync_normal_infos: Vec<SyncTaskInfo>,
+    pub relays: RelaySet,
+    pub builder_gas_limit: u64,
+    pub validator_registrations: ValidatorRegistrations,
 }
 
 impl ValidatorService {
@@ -89,8 +98,19 @@ impl ValidatorService {
         beacon_api_endpoint: Url,
         request_timeout: Duration,
         executor: ReamExecutor,
+        builder_config: Option<BuilderConfig>,
+        validator_registrations: ValidatorRegistrations,
     ) -> anyhow::Result<Self> {
         let validators = keystores.into_iter().map(Arc::new).collect::<Vec<_>>();
+        let builder_gas_limit = builder_config
+            .as_ref()
+            .map_or(DEFAULT_GAS_LIMIT, |builder_config| builder_config.gas_limit);
+        let relays = match builder_config {
+            Some(builder_config) if builder_config.builder_enabled => {
+                RelaySet::new(builder_config.relays, request_timeout)?
+            }
+            _ => RelaySet::default(),
+        };
 
         Ok(Self {
             beacon_api_client: Arc::new(BeaconApiClient::new(
@@ -108,15 +128,19 @@ impl ValidatorService {
             sync_committee_duties: Vec::new(),
             sync_aggregator_infos: Vec::new(),
             sync_normal_infos: Vec::new(),
+            relays,
+            builder_gas_limit,
+            validator_registrations,
         })
     }
 
-    pub async fn start(mut self) {
+    /// Performs the duties of the validators every interval, starting from the current one.
+    /// `genesis_time` must be in the past, it is the genesis time reported by the beacon node.
+    pub async fn start(mut self, genesis_time: u64) {
         let seconds_per_slot = beacon_network_spec().seconds_per_slot;
         let seconds_per_interval = seconds_per_slot / INTERVALS_PER_SLOT;
 
-        let genesis_instant =
-            UNIX_EPOCH + Duration::from_secs(beacon_network_spec().min_genesis_time);
+        let genesis_instant = UNIX_EPOCH + Duration::from_secs(genesis_time);
         let elapsed = SystemTime::now()
             .duration_since(genesis_instant)
             .expect("System Time is before the genesis time");
@@ -132,6 +156,11 @@ impl ValidatorService {
         };
         interval.set_missed_tick_behavior(MissedTickBehavior::Burst);
 
+        // Started at genesis, the duties of the first epoch are fetched before its first slot
+        if intervals == 0 {
+            self.on_epoch(epoch).await;
+        }
+
         loop {
             tokio::select! {
                 _ = interval.tick() => {
@@ -166,8 +195,8 @@ impl ValidatorService {
             .map(|aggregator_info| {
                 let client = client.clone();
                 tokio::spawn(async move {
-                    let subcommittee_index = aggregator_info.committee_index
-                        / (SYNC_COMMITTEE_SIZE / SYNC_COMMITTEE_SUBNET_COUNT);
+                    let subcommittee_index =
+                        compute_subcommittee_index(aggregator_info.committee_index);
 
                     let contribution = client
                         .get_sync_committee_contribution(slot, subcommittee_index, block_root)
@@ -231,6 +260,48 @@ impl ValidatorService {
         if let Some(proposer_duties) = self.fetch_proposer_duties(epoch, &validator_indices).await {
             self.proposer_duties = proposer_duties;
         }
+
+        if let Err(err) = self.register_with_builder().await {
+            warn!("Failed to register validators with the builder: {err:?}");
+        }
+    }
+
+    /// Sends the registrations of the known validators to the relays, signing new registrations
+    /// for the validators whose fee recipient or gas limit changed. Relays expect the
+    /// registrations to be sent again every epoch.
+    pub async fn register_with_builder(&mut self) -> anyhow::Result<()> {
+        if self.relays.is_empty() {
+            return Ok(());
+        }
+
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let mut changed = false;
+        for keystore in self.validator_index_to_keystore.values() {
+            changed |= self.validator_registrations.update(
+                &keystore.public_key,
+                &keystore.private_key,
+                *self.suggested_fee_recipient,
+                self.builder_gas_limit,
+                timestamp,
+            )?;
+        }
+        if changed {
+            self.validator_registrations.save()?;
+        }
+
+        let registrations = self
+            .validator_registrations
+            .registrations()
+            .into_iter()
+            .filter(|registration| {
+                self.public_key_to_index
+                    .contains_key(&registration.message.public_key)
+            })
+            .collect::<Vec<_>>();
+        if registrations.is_empty() {
+            return Ok(());
+        }
+        self.relays.register_validators(&registrations).await
     }
 
     // Runs on the end of every epoch after the per-slot code(exactly 4 seconds prior to the next
@@ -401,11 +472,26 @@ impl ValidatorService {
 
         match block_response.data {
             ProduceBlockData::Full(full_block) => {
-                let signed_beacon_block =
+                let signed_block =
                     sign_beacon_block(slot, full_block.block, &keystore.private_key)?;
+                ensure!(
+                    full_block.blobs.len() == signed_block.message.body.blob_kzg_commitments.len()
+                        && full_block.kzg_proofs.len() == full_block.blobs.len(),
+                    "Produced block at slot {slot} has {} commitments, {} blobs and {} proofs",
+                    signed_block.message.body.blob_kzg_commitments.len(),
+                    full_block.blobs.len(),
+                    full_block.kzg_proofs.len()
+                );
 
                 self.beacon_api_client
-                    .publish_block(BroadcastValidation::Gossip, signed_beacon_block)
+                    .publish_block(
+                        BroadcastValidation::Gossip,
+                        SignedBlockContents {
+                            signed_block,
+                            kzg_proofs: full_block.kzg_proofs,
+                            blobs: full_block.blobs,
+                        },
+                    )
                     .await?;
             }
             ProduceBlockData::Blinded(blinded_block) => {
@@ -432,7 +518,7 @@ impl ValidatorService {
             for &committee_index in &duty.validator_sync_committee_indices {
                 let selection_proof = get_sync_committee_selection_proof(
                     slot,
-                    committee_index,
+                    compute_subcommittee_index(committee_index),
                     &keystore.private_key,
                 )
                 .map_err(|err| anyhow!("Could not get selection proof: {err:?}"))?;
@@ -543,26 +629,19 @@ impl ValidatorService {
             .cloned()
             .ok_or_else(|| anyhow!("Keystore not found for validator: {aggregator_index}"))?;
 
-        let aggregate_and_proof = AggregateAndProof {
-            aggregator_index,
-            aggregate: self
-                .beacon_api_client
-                .get_aggregated_attestation(
-                    attestation_data.tree_hash_root(),
-                    slot,
-                    committee_index,
-                )
-                .await?
-                .data,
-            selection_proof: get_selection_proof(slot, &keystore.private_key)?,
-        };
+        let aggregate = self
+            .beacon_api_client
+            .get_aggregated_attestation(attestation_data.tree_hash_root(), slot, committee_index)
+            .await?
+            .data;
 
         Ok(self
             .beacon_api_client
-            .publish_aggregate_and_proofs(vec![SignedAggregateAndProof {
-                signature: sign_aggregate_and_proof(&aggregate_and_proof, &keystore.private_key)?,
-                message: aggregate_and_proof,
-            }])
+            .publish_aggregate_and_proofs(vec![get_signed_aggregate_and_proof(
+                aggregator_index,
+                aggregate,
+                &keystore.private_key,
+            )?])
             .await?)
     }
 
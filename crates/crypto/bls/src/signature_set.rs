@@ -0,0 +1,30 @@
+use alloy_primitives::B256;
+
+use crate::{BLSSignature, PublicKey};
+
+/// A signature over `message` by the aggregate of `public_keys`, to be verified along with the
+/// other signature sets of a batch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureSet<'a> {
+    pub signature: &'a BLSSignature,
+    pub public_keys: Vec<&'a PublicKey>,
+    pub message: B256,
+}
+
+impl<'a> SignatureSet<'a> {
+    pub fn new(
+        signature: &'a BLSSignature,
+        public_keys: Vec<&'a PublicKey>,
+        message: B256,
+    ) -> Self {
+        Self {
+            signature,
+            public_keys,
+            message,
+        }
+    }
+
+    pub fn single(signature: &'a BLSSignature, public_key: &'a PublicKey, message: B256) -> Self {
+        Self::new(signature, vec![public_key], message)
+    }
+}
@@ -2,3 +2,4 @@ pub mod errors;
 pub mod private_key;
 pub mod public_key;
 pub mod signature;
+pub mod signature_set;
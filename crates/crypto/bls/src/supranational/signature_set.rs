@@ -0,0 +1,77 @@
+use blst::{
+    BLST_ERROR, blst_scalar,
+    min_pk::{AggregatePublicKey as BlstAggregatePublicKey, Signature as BlstSignature},
+};
+use rand::Rng;
+
+use crate::{
+    constants::DST,
+    errors::BLSError,
+    signature_set::SignatureSet,
+    traits::{BatchVerifiable, SupranationalBatchVerifiable},
+};
+
+/// The number of random bits each signature set is weighted by, so that invalid signatures
+/// cannot cancel each other out in the batch.
+const RANDOM_BITS: usize = 64;
+
+impl BatchVerifiable for SignatureSet<'_> {
+    type Error = BLSError;
+
+    fn verify_batch(signature_sets: &[SignatureSet]) -> Result<bool, BLSError> {
+        if signature_sets.is_empty() {
+            return Ok(true);
+        }
+        if signature_sets
+            .iter()
+            .any(|signature_set| signature_set.public_keys.is_empty())
+        {
+            return Ok(false);
+        }
+
+        let signatures = signature_sets
+            .iter()
+            .map(|signature_set| signature_set.signature.to_blst_signature())
+            .collect::<Result<Vec<_>, _>>()?;
+        let public_keys = signature_sets
+            .iter()
+            .map(|signature_set| {
+                let public_keys = signature_set
+                    .public_keys
+                    .iter()
+                    .map(|public_key| public_key.to_blst_public_key())
+                    .collect::<Result<Vec<_>, _>>()?;
+                BlstAggregatePublicKey::aggregate(&public_keys.iter().collect::<Vec<_>>(), true)
+                    .map(|aggregate_public_key| aggregate_public_key.to_public_key())
+                    .map_err(|err| BLSError::BlstError(err.into()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let messages = signature_sets
+            .iter()
+            .map(|signature_set| signature_set.message.as_slice())
+            .collect::<Vec<_>>();
+
+        let mut rng = rand::thread_rng();
+        let randoms = signature_sets
+            .iter()
+            .map(|_| {
+                let mut random = blst_scalar::default();
+                random.b[..8].copy_from_slice(&rng.gen_range(1..=u64::MAX).to_le_bytes());
+                random
+            })
+            .collect::<Vec<_>>();
+
+        Ok(BlstSignature::verify_multiple_aggregate_signatures(
+            &messages,
+            DST,
+            &public_keys.iter().collect::<Vec<_>>(),
+            false,
+            &signatures.iter().collect::<Vec<_>>(),
+            true,
+            &randoms,
+            RANDOM_BITS,
+        ) == BLST_ERROR::BLST_SUCCESS)
+    }
+}
+
+impl SupranationalBatchVerifiable for SignatureSet<'_> {}
@@ -1,4 +1,4 @@
-use crate::{BLSSignature, PublicKey, errors::BLSError};
+use crate::{BLSSignature, PublicKey, SignatureSet, errors::BLSError};
 
 /// Trait for aggregating BLS public keys.
 ///
@@ -89,3 +89,29 @@ pub trait ZkcryptoVerifiable: Verifiable<Error = BLSError> {}
 
 /// Marker trait for supranational/blst BLS signature verification implementation
 pub trait SupranationalVerifiable: Verifiable<Error = BLSError> {}
+
+/// Trait for verifying many BLS signatures at once.
+///
+/// This trait provides functionality to verify a batch of signature sets, each a signature over
+/// a message by the aggregate of some public keys, with fewer pairings than verifying the sets one
+/// by one.
+pub trait BatchVerifiable {
+    type Error;
+
+    /// Verifies every signature set of a batch.
+    ///
+    /// # Arguments
+    /// * `signature_sets` - The signature sets to verify
+    ///
+    /// # Returns
+    /// * `Result<bool, BLSError>` - Ok(true) if every signature set is valid, Ok(false) if any of
+    ///   them is invalid, without telling which, or Err if there are issues with signature or
+    ///   public key bytes
+    fn verify_batch(signature_sets: &[SignatureSet]) -> Result<bool, Self::Error>;
+}
+
+/// Marker trait for zkcrypto/bls12_381 BLS batch verification implementation
+pub trait ZkcryptoBatchVerifiable: BatchVerifiable<Error = BLSError> {}
+
+/// Marker trait for supranational/blst BLS batch verification implementation
+pub trait SupranationalBatchVerifiable: BatchVerifiable<Error = BLSError> {}
@@ -0,0 +1,27 @@
+use crate::{
+    errors::BLSError,
+    signature_set::SignatureSet,
+    traits::{BatchVerifiable, Verifiable, ZkcryptoBatchVerifiable},
+};
+
+impl BatchVerifiable for SignatureSet<'_> {
+    type Error = BLSError;
+
+    /// Verifies the signature sets one by one, as weighting them by random scalars to share the
+    /// pairings would need a source of randomness inside the zkVM.
+    fn verify_batch(signature_sets: &[SignatureSet]) -> Result<bool, BLSError> {
+        for signature_set in signature_sets {
+            if signature_set.public_keys.is_empty()
+                || !signature_set.signature.fast_aggregate_verify(
+                    &signature_set.public_keys,
+                    signature_set.message.as_ref(),
+                )?
+            {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl ZkcryptoBatchVerifiable for SignatureSet<'_> {}
@@ -21,10 +21,29 @@ pub fn get_subtree_index(generalized_index: u64) -> u64 {
 }
 
 /// Return the generalized index of the leaf index with ``depth``.
-pub fn generalized_index_from_leaf_index(leaf_index: u64, depth: u64) -> u64 {
+pub const fn generalized_index_from_leaf_index(leaf_index: u64, depth: u64) -> u64 {
     leaf_index + (1 << depth)
 }
 
+/// Return the length of a path represented by a generalized index, which is also the length of
+/// its Merkle branch.
+pub const fn get_generalized_index_length(generalized_index: u64) -> u64 {
+    (u64::BITS - 1 - generalized_index.leading_zeros()) as u64
+}
+
+/// Given generalized indices i1 for A -> B, i2 for B -> C .... i_n for Y -> Z, returns
+/// the generalized index for A -> Z.
+pub const fn concat_generalized_indices(generalized_indices: &[u64]) -> u64 {
+    let mut result = 1;
+    let mut i = 0;
+    while i < generalized_indices.len() {
+        let power_of_two = 1 << get_generalized_index_length(generalized_indices[i]);
+        result = result * power_of_two + (generalized_indices[i] - power_of_two);
+        i += 1;
+    }
+    result
+}
+
 /// Get the generalized indices of the sister chunks along the
 /// path from the chunk with the given tree index to the root.
 fn get_branch_indices(generalized_index: u64) -> Vec<u64> {
@@ -95,4 +114,14 @@ mod tests {
         assert_eq!(helper_indices[2], 6);
         assert_eq!(helper_indices[3], 4);
     }
+
+    #[test]
+    fn test_concat_generalized_indices() {
+        assert_eq!(get_generalized_index_length(1), 0);
+        assert_eq!(get_generalized_index_length(84), 6);
+        assert_eq!(concat_generalized_indices(&[]), 1);
+        assert_eq!(concat_generalized_indices(&[84]), 84);
+        // `finalized_checkpoint.root` in the Electra `BeaconState`
+        assert_eq!(concat_generalized_indices(&[84, 3]), 169);
+    }
 }
@@ -9,7 +9,13 @@ mod hash;
 mod index;
 
 use hash::hash_concat;
-use index::{generalized_index_child, get_generalized_index_bit, get_subtree_index};
+pub use index::{
+    concat_generalized_indices, generalized_index_from_leaf_index, get_generalized_index_length,
+};
+use index::{
+    generalized_index_child, generalized_index_parent, generalized_index_sibling,
+    get_generalized_index_bit, get_subtree_index,
+};
 
 pub fn merkle_tree(leaves: &[B256], depth: u64) -> anyhow::Result<Vec<B256>> {
     let num_of_leaves = leaves.len();
@@ -62,6 +68,59 @@ pub fn generate_proof(tree: &[B256], index: u64, depth: u64) -> anyhow::Result<V
     Ok(proof)
 }
 
+/// Generates the branch of the node at `generalized_index` in a tree built by [merkle_tree],
+/// which may be a leaf or an inner node.
+pub fn generate_proof_for_generalized_index(
+    tree: &[B256],
+    generalized_index: u64,
+) -> anyhow::Result<Vec<B256>> {
+    ensure!(
+        generalized_index >= 1 && generalized_index < tree.len() as u64,
+        "Generalized index out of bounds"
+    );
+
+    let mut proof = vec![];
+    let mut current_index = generalized_index;
+    while current_index > 1 {
+        proof.push(tree[generalized_index_sibling(current_index) as usize]);
+        current_index = generalized_index_parent(current_index);
+    }
+
+    Ok(proof)
+}
+
+/// Generates the proof of a leaf in a tree of `depth` whose leaves after `leaves` are all zero,
+/// without allocating the whole tree. This is used for the proofs of SSZ lists, whose trees are
+/// padded to their maximum length.
+pub fn generate_sparse_proof(leaves: &[B256], index: u64, depth: u64) -> anyhow::Result<Vec<B256>> {
+    ensure!(index < 1 << depth, "Index out of bounds");
+    ensure!(
+        leaves.len() as u64 <= 1 << depth,
+        "Number of leaves is greater than the bottom length (depth too small)"
+    );
+
+    let mut proof = Vec::with_capacity(depth as usize);
+    let mut layer = leaves.to_vec();
+    let mut index = index as usize;
+    let mut zero_hash = B256::ZERO;
+    for _ in 0..depth {
+        proof.push(layer.get(index ^ 1).copied().unwrap_or(zero_hash));
+        layer = layer
+            .chunks(2)
+            .map(|pair| {
+                hash_concat(
+                    pair[0].as_slice(),
+                    pair.get(1).unwrap_or(&zero_hash).as_slice(),
+                )
+            })
+            .collect();
+        zero_hash = hash_concat(zero_hash.as_slice(), zero_hash.as_slice());
+        index /= 2;
+    }
+
+    Ok(proof)
+}
+
 pub fn is_valid_merkle_branch(
     leaf: B256,
     branch: &[B256],
@@ -84,6 +143,25 @@ pub fn get_root_from_merkle_branch(leaf: B256, branch: &[B256], depth: u64, inde
     value
 }
 
+/// Check a Merkle branch proving that `leaf` is the node at `generalized_index` in the tree with
+/// `root`. The length of the branch must match the depth of the generalized index.
+pub fn verify_merkle_branch(
+    leaf: B256,
+    branch: &[B256],
+    generalized_index: u64,
+    root: B256,
+) -> bool {
+    let depth = get_generalized_index_length(generalized_index);
+    branch.len() as u64 == depth
+        && is_valid_merkle_branch(
+            leaf,
+            branch,
+            depth,
+            get_subtree_index(generalized_index),
+            root,
+        )
+}
+
 pub fn is_valid_normalized_merkle_branch(
     leaf: B256,
     branch: &[B256],
@@ -160,4 +238,59 @@ mod tests {
             root
         ));
     }
+
+    #[test]
+    fn test_verify_merkle_branch() {
+        let leaves = (0..8).map(B256::repeat_byte).collect::<Vec<_>>();
+        let depth = 3;
+        let tree = merkle_tree(&leaves, depth).unwrap();
+        let root = tree[1];
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let branch = generate_proof(&tree, index as u64, depth).unwrap();
+            let generalized_index = generalized_index_from_leaf_index(index as u64, depth);
+            assert!(verify_merkle_branch(
+                *leaf,
+                &branch,
+                generalized_index,
+                root
+            ));
+            assert!(!verify_merkle_branch(
+                *leaf,
+                &branch,
+                generalized_index ^ 1,
+                root
+            ));
+            assert!(!verify_merkle_branch(
+                *leaf,
+                &branch[1..],
+                generalized_index,
+                root
+            ));
+        }
+
+        // A sparse proof matches the proof generated from the whole tree
+        assert_eq!(
+            generate_sparse_proof(&leaves[..5], 3, depth).unwrap(),
+            generate_proof(&merkle_tree(&leaves[..5], depth).unwrap(), 3, depth).unwrap()
+        );
+
+        // An inner node is proven with the upper part of the branch of its leaves
+        let branch = generate_proof(&tree, 0, depth).unwrap();
+        assert!(verify_merkle_branch(tree[4], &branch[1..], 4, root));
+        assert_eq!(
+            generate_proof_for_generalized_index(&tree, 4).unwrap(),
+            branch[1..]
+        );
+        assert_eq!(
+            generate_proof_for_generalized_index(
+                &tree,
+                generalized_index_from_leaf_index(5, depth)
+            )
+            .unwrap(),
+            generate_proof(&tree, 5, depth).unwrap()
+        );
+        assert!(generate_proof_for_generalized_index(&tree, 0).is_err());
+        assert!(generate_proof_for_generalized_index(&tree, 16).is_err());
+    }
 }
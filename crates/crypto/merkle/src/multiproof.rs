@@ -56,6 +56,34 @@ impl Multiproof {
         Ok(Self { leaves, proofs })
     }
 
+    /// Generate a multiproof of the nodes at the given generalized indices, which may be leaves
+    /// or inner nodes. The nodes are read through ``node_at``, so the tree does not need to be
+    /// built as a whole.
+    pub fn generate_for_generalized_indices(
+        generalized_indices: &[u64],
+        node_at: impl Fn(u64) -> anyhow::Result<B256>,
+    ) -> anyhow::Result<Self> {
+        ensure!(!generalized_indices.is_empty(), "Indices cannot be empty");
+        ensure!(
+            generalized_indices
+                .iter()
+                .all(|&generalized_index| generalized_index >= 1),
+            "Generalized index must be at least 1"
+        );
+        let helper_indices = get_helper_indices(generalized_indices);
+
+        let leaves = generalized_indices
+            .iter()
+            .map(|&generalized_index| Ok((generalized_index, node_at(generalized_index)?)))
+            .collect::<anyhow::Result<HashMap<u64, B256>>>()?;
+        let proofs = helper_indices
+            .iter()
+            .map(|&generalized_index| Ok((generalized_index, node_at(generalized_index)?)))
+            .collect::<anyhow::Result<BTreeMap<u64, B256>>>()?;
+
+        Ok(Self { leaves, proofs })
+    }
+
     /// Return the root of the multiproof.
     pub fn calculate_root(&self) -> anyhow::Result<B256> {
         let leaf_indices = self.leaves.keys().cloned().collect::<Vec<_>>();
@@ -157,5 +185,25 @@ mod tests {
 
         // Should succeed to verify the multiproof.
         multiproof.verify(root).unwrap();
+
+        // The same multiproof is generated from the generalized indices of the leaves
+        let generalized_indices = target_indices
+            .iter()
+            .map(|&index| generalized_index_from_leaf_index(index, DEPTH))
+            .collect::<Vec<_>>();
+        let node_at = |generalized_index: u64| {
+            tree.get(generalized_index as usize)
+                .copied()
+                .ok_or_else(|| anyhow!("Generalized index out of bounds"))
+        };
+        let generalized_multiproof =
+            Multiproof::generate_for_generalized_indices(&generalized_indices, node_at).unwrap();
+        assert_eq!(generalized_multiproof.leaves, multiproof.leaves);
+        assert_eq!(generalized_multiproof.proofs, multiproof.proofs);
+
+        // An inner node can be proven along with a leaf outside of its subtree
+        let multiproof = Multiproof::generate_for_generalized_indices(&[5, 14], node_at).unwrap();
+        multiproof.verify(root).unwrap();
+        assert!(multiproof.verify(tree[2]).is_err());
     }
 }
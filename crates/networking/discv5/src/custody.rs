@@ -0,0 +1,127 @@
+use alloy_primitives::U256;
+use anyhow::ensure;
+use discv5::enr::NodeId;
+use ethereum_hashing::hash_fixed;
+use ream_consensus_misc::constants::NUMBER_OF_COLUMNS;
+use ream_network_spec::networks::beacon_network_spec;
+
+pub const CUSTODY_GROUP_COUNT_ENR_KEY: &str = "cgc";
+
+/// Computes the custody groups a node must custody, sorted.
+///
+/// Spec: https://github.com/ethereum/consensus-specs/blob/dev/specs/fulu/das-core.md#get_custody_groups
+pub fn get_custody_groups(node_id: NodeId, custody_group_count: u64) -> anyhow::Result<Vec<u64>> {
+    let number_of_custody_groups = beacon_network_spec().number_of_custody_groups;
+    ensure!(
+        custody_group_count <= number_of_custody_groups,
+        "Custody group count {custody_group_count} exceeds the {number_of_custody_groups} custody \
+         groups",
+    );
+
+    // Skip computation if all groups are custodied
+    if custody_group_count == number_of_custody_groups {
+        return Ok((0..number_of_custody_groups).collect());
+    }
+
+    let mut current_id = U256::from_be_bytes(node_id.raw());
+    let mut custody_groups = vec![];
+    while (custody_groups.len() as u64) < custody_group_count {
+        let hash = hash_fixed(&current_id.to_le_bytes::<32>());
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&hash[..8]);
+        let custody_group = u64::from_le_bytes(bytes) % number_of_custody_groups;
+        if !custody_groups.contains(&custody_group) {
+            custody_groups.push(custody_group);
+        }
+        current_id = current_id.wrapping_add(U256::from(1));
+    }
+
+    custody_groups.sort_unstable();
+    Ok(custody_groups)
+}
+
+/// Computes the data columns of a custody group.
+///
+/// Spec: https://github.com/ethereum/consensus-specs/blob/dev/specs/fulu/das-core.md#compute_columns_for_custody_group
+pub fn compute_columns_for_custody_group(custody_group: u64) -> anyhow::Result<Vec<u64>> {
+    let number_of_custody_groups = beacon_network_spec().number_of_custody_groups;
+    ensure!(
+        custody_group < number_of_custody_groups,
+        "Custody group {custody_group} exceeds the {number_of_custody_groups} custody groups",
+    );
+
+    let columns_per_group = NUMBER_OF_COLUMNS / number_of_custody_groups;
+    Ok((0..columns_per_group)
+        .map(|index| number_of_custody_groups * index + custody_group)
+        .collect())
+}
+
+/// Computes the data columns a node must custody, sorted.
+pub fn compute_custody_columns(
+    node_id: NodeId,
+    custody_group_count: u64,
+) -> anyhow::Result<Vec<u64>> {
+    let mut columns = get_custody_groups(node_id, custody_group_count)?
+        .into_iter()
+        .map(compute_columns_for_custody_group)
+        .collect::<anyhow::Result<Vec<_>>>()?
+        .concat();
+    columns.sort_unstable();
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use ream_network_spec::networks::initialize_test_network_spec;
+
+    use super::*;
+
+    #[test]
+    fn test_get_custody_groups() -> anyhow::Result<()> {
+        initialize_test_network_spec();
+        let number_of_custody_groups = beacon_network_spec().number_of_custody_groups;
+
+        // The node id wraps around to zero after the largest one
+        let node_id = NodeId::new(&[0xff; 32]);
+        for custody_group_count in [0, 1, 4, number_of_custody_groups / 2] {
+            let custody_groups = get_custody_groups(node_id, custody_group_count)?;
+            assert_eq!(custody_groups.len() as u64, custody_group_count);
+            assert!(custody_groups.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(
+                custody_groups
+                    .iter()
+                    .all(|&custody_group| custody_group < number_of_custody_groups)
+            );
+        }
+        assert_eq!(
+            get_custody_groups(node_id, number_of_custody_groups)?,
+            (0..number_of_custody_groups).collect::<Vec<_>>()
+        );
+        assert!(get_custody_groups(node_id, number_of_custody_groups + 1).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compute_custody_columns() -> anyhow::Result<()> {
+        initialize_test_network_spec();
+        let number_of_custody_groups = beacon_network_spec().number_of_custody_groups;
+
+        let node_id = NodeId::new(&[7; 32]);
+        let custody_groups = get_custody_groups(node_id, 4)?;
+        let columns = compute_custody_columns(node_id, 4)?;
+        assert_eq!(
+            columns.len() as u64,
+            4 * NUMBER_OF_COLUMNS / number_of_custody_groups
+        );
+        assert!(
+            columns
+                .iter()
+                .all(|column| custody_groups.contains(&(column % number_of_custody_groups)))
+        );
+        assert_eq!(
+            compute_custody_columns(node_id, number_of_custody_groups)?,
+            (0..NUMBER_OF_COLUMNS).collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+}
@@ -29,7 +29,7 @@ use crate::{
     config::DiscoveryConfig,
     eth2::{ENR_ETH2_KEY, EnrForkId},
     subnet::{
-        ATTESTATION_BITFIELD_ENR_KEY, SYNC_COMMITTEE_BITFIELD_ENR_KEY,
+        ATTESTATION_BITFIELD_ENR_KEY, AttestationSubnets, SYNC_COMMITTEE_BITFIELD_ENR_KEY,
         attestation_subnet_predicate, sync_committee_subnet_predicate,
     },
 };
@@ -259,6 +259,17 @@ impl Discovery {
     pub fn local_enr(&self) -> Enr {
         self.discv5.local_enr()
     }
+
+    /// Updates the `attnets` field of the local ENR, which increments the ENR sequence number.
+    pub fn update_attestation_subnets(
+        &mut self,
+        attestation_subnets: &AttestationSubnets,
+    ) -> anyhow::Result<()> {
+        self.discv5
+            .enr_insert(ATTESTATION_BITFIELD_ENR_KEY, attestation_subnets)
+            .map_err(|err| anyhow!("Failed to update attnets in local ENR: {err:?}"))?;
+        Ok(())
+    }
 }
 
 impl NetworkBehaviour for Discovery {
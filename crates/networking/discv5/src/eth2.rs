@@ -16,12 +16,24 @@ pub struct EnrForkId {
 
 impl EnrForkId {
     pub fn electra(genesis_validators_root: B256) -> Self {
-        let current_fork_version = beacon_network_spec().electra_fork_version;
-        let next_fork_version = current_fork_version;
-        let next_fork_epoch = FAR_FUTURE_EPOCH;
+        Self::at_epoch(
+            beacon_network_spec().electra_fork_epoch,
+            genesis_validators_root,
+        )
+    }
+
+    /// Builds the `eth2` ENR field for `epoch` from the fork schedule. If no fork is scheduled
+    /// after `epoch`, the next fork is the current fork at `FAR_FUTURE_EPOCH`.
+    pub fn at_epoch(epoch: u64, genesis_validators_root: B256) -> Self {
+        let fork_schedule = beacon_network_spec().fork_schedule();
+        let current_fork = fork_schedule.fork_at_epoch(epoch);
+        let (next_fork_version, next_fork_epoch) = match fork_schedule.next_fork_at_epoch(epoch) {
+            Some(next_fork) => (next_fork.current_version, next_fork.epoch),
+            None => (current_fork.current_version, FAR_FUTURE_EPOCH),
+        };
 
         let fork_digest = ForkData {
-            current_version: current_fork_version,
+            current_version: current_fork.current_version,
             genesis_validators_root,
         }
         .compute_fork_digest();
@@ -1,4 +1,5 @@
 pub mod config;
+pub mod custody;
 pub mod discovery;
 pub mod eth2;
 pub mod subnet;
@@ -1,6 +1,10 @@
+use alloy_primitives::{B256, U256};
 use alloy_rlp::{BufMut, Decodable, Encodable, bytes::Bytes};
 use anyhow::{anyhow, ensure};
-use discv5::Enr;
+use discv5::{Enr, enr::NodeId};
+use ethereum_hashing::hash_fixed;
+use ream_consensus_misc::misc::compute_shuffled_index;
+use ream_network_spec::networks::beacon_network_spec;
 use ssz::Encode;
 use ssz_types::{
     BitVector,
@@ -10,6 +14,7 @@ use tracing::{error, trace};
 
 pub const ATTESTATION_BITFIELD_ENR_KEY: &str = "attnets";
 pub const ATTESTATION_SUBNET_COUNT: usize = 64;
+pub const NODE_ID_BITS: usize = 256;
 pub const SYNC_COMMITTEE_BITFIELD_ENR_KEY: &str = "syncnets";
 pub const SYNC_COMMITTEE_SUBNET_COUNT: usize = 4;
 
@@ -54,6 +59,16 @@ impl AttestationSubnets {
             .get(subnet_id as usize)
             .map_err(|err| anyhow!("Subnet ID out of bounds: {err:?}"))
     }
+
+    /// Builds the attestation subnet bitfield for the long-lived subnets the node is subscribed
+    /// to at the given epoch.
+    pub fn from_subscribed_subnets(node_id: NodeId, epoch: u64) -> anyhow::Result<Self> {
+        let mut attestation_subnets = Self::new();
+        for subnet_id in compute_subscribed_subnets(node_id, epoch)? {
+            attestation_subnets.enable_attestation_subnet(subnet_id)?;
+        }
+        Ok(attestation_subnets)
+    }
 }
 
 impl Encodable for AttestationSubnets {
@@ -137,6 +152,43 @@ impl Decodable for SyncCommitteeSubnets {
     }
 }
 
+/// Computes the `index`-th long-lived attestation subnet for a node at a given epoch.
+///
+/// Spec: https://github.com/ethereum/consensus-specs/blob/dev/specs/phase0/p2p-interface.md#attestation-subnet-subscription
+pub fn compute_subscribed_subnet(node_id: NodeId, epoch: u64, index: u64) -> anyhow::Result<u8> {
+    let network_spec = beacon_network_spec();
+    let prefix_bits = network_spec.attestation_subnet_prefix_bits;
+    ensure!(
+        prefix_bits as usize <= NODE_ID_BITS,
+        "Attestation subnet prefix bits {prefix_bits} exceeds node id bits {NODE_ID_BITS}",
+    );
+
+    let node_id = U256::from_be_bytes(node_id.raw());
+    let node_id_prefix = node_id >> (NODE_ID_BITS - prefix_bits as usize);
+    let node_offset = node_id % U256::from(network_spec.epochs_per_subnet_subscription);
+    let node_id_prefix = usize::try_from(node_id_prefix)
+        .map_err(|err| anyhow!("Failed to convert node id prefix: {err:?}"))?;
+    let node_offset = u64::try_from(node_offset)
+        .map_err(|err| anyhow!("Failed to convert node offset: {err:?}"))?;
+
+    let permutation_seed = B256::from(hash_fixed(
+        &((epoch + node_offset) / network_spec.epochs_per_subnet_subscription).to_le_bytes(),
+    ));
+    let permutated_prefix =
+        compute_shuffled_index(node_id_prefix, 1 << prefix_bits, permutation_seed)?;
+
+    Ok(((permutated_prefix as u64 + index) % network_spec.attestation_subnet_count) as u8)
+}
+
+/// Computes the long-lived attestation subnets a node must subscribe to at a given epoch.
+///
+/// Spec: https://github.com/ethereum/consensus-specs/blob/dev/specs/phase0/p2p-interface.md#attestation-subnet-subscription
+pub fn compute_subscribed_subnets(node_id: NodeId, epoch: u64) -> anyhow::Result<Vec<u8>> {
+    (0..beacon_network_spec().subnets_per_node)
+        .map(|index| compute_subscribed_subnet(node_id, epoch, index))
+        .collect()
+}
+
 pub fn attestation_subnet_predicate(subnets: Vec<u8>) -> impl Fn(&Enr) -> bool + Send + Sync {
     move |enr: &Enr| {
         if subnets.is_empty() {
@@ -231,6 +283,7 @@ mod tests {
         Enr,
         enr::{CombinedKey, k256::ecdsa::SigningKey},
     };
+    use ream_network_spec::networks::initialize_test_network_spec;
 
     use super::*;
 
@@ -490,4 +543,43 @@ mod tests {
         };
         assert!(!combined_subnet_predicate_fn(&enr));
     }
+
+    #[test]
+    fn test_compute_subscribed_subnets() {
+        initialize_test_network_spec();
+        let node_id = NodeId::new(&[0xab; 32]);
+
+        let subnets = compute_subscribed_subnets(node_id, 100).unwrap();
+        assert_eq!(
+            subnets.len(),
+            beacon_network_spec().subnets_per_node as usize
+        );
+        assert_eq!(subnets, compute_subscribed_subnets(node_id, 100).unwrap());
+
+        // Subnets of a node are consecutive, wrapping around the subnet count
+        for (index, subnet_id) in subnets.iter().enumerate() {
+            assert!((*subnet_id as usize) < ATTESTATION_SUBNET_COUNT);
+            assert_eq!(
+                *subnet_id as usize,
+                (subnets[0] as usize + index) % ATTESTATION_SUBNET_COUNT
+            );
+        }
+
+        // The node offset is 0xab, so the subscription is stable for epochs 85..=340
+        assert_eq!(
+            compute_subscribed_subnets(node_id, 85).unwrap(),
+            compute_subscribed_subnets(node_id, 340).unwrap()
+        );
+
+        let attestation_subnets =
+            AttestationSubnets::from_subscribed_subnets(node_id, 100).unwrap();
+        for subnet_id in 0..ATTESTATION_SUBNET_COUNT as u8 {
+            assert_eq!(
+                attestation_subnets
+                    .is_attestation_subnet_enabled(subnet_id)
+                    .unwrap(),
+                subnets.contains(&subnet_id)
+            );
+        }
+    }
 }
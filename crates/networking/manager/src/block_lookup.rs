@@ -0,0 +1,232 @@
+use alloy_primitives::B256;
+use anyhow::{anyhow, ensure};
+use libp2p::PeerId;
+use ream_beacon_chain::beacon_chain::BeaconChain;
+use ream_consensus_beacon::{
+    blob_sidecar::{BlobIdentifier, BlobSidecar},
+    electra::beacon_block::SignedBeaconBlock,
+};
+use ream_consensus_misc::{
+    beacon_block_header::SignedBeaconBlockHeader, misc::compute_start_slot_at_epoch,
+};
+use ream_executor::ReamExecutor;
+use ream_p2p::{channel::P2PMessage, network_state::NetworkState};
+use ream_storage::tables::{Field, Table};
+use ream_syncer::block_range::peer_range_downloader::{
+    PeerBlobIdentifierDownloader, PeerRootsDownloader,
+};
+use tokio::sync::mpsc::{self, UnboundedSender};
+use tracing::{info, warn};
+use tree_hash::TreeHash;
+
+use crate::{
+    p2p_sender::P2PSender,
+    quarantine::{BlockQuarantine, MAX_QUARANTINED_FUTURE_SLOTS, QuarantinedBlock},
+    reprocess::ReprocessQueue,
+};
+
+/// The outcome of looking up an unknown ancestor by its root.
+pub struct BlockLookupResult {
+    pub root: B256,
+    pub result: anyhow::Result<QuarantinedBlock>,
+}
+
+pub async fn is_block_known(beacon_chain: &BeaconChain, block_root: B256) -> anyhow::Result<bool> {
+    Ok(beacon_chain
+        .store
+        .lock()
+        .await
+        .db
+        .beacon_block_provider()
+        .get(block_root)?
+        .is_some())
+}
+
+/// Returns whether a block with an unknown parent, or a blob sidecar of it, may be quarantined:
+/// its header must be from a slot the quarantine keeps and signed by its proposer.
+pub async fn is_quarantinable(
+    beacon_chain: &BeaconChain,
+    signed_header: &SignedBeaconBlockHeader,
+) -> anyhow::Result<bool> {
+    let store = beacon_chain.store.lock().await;
+    let slot = signed_header.message.slot;
+    let finalized_slot =
+        compute_start_slot_at_epoch(store.db.finalized_checkpoint_provider().get()?.epoch);
+    if slot <= finalized_slot || slot > store.get_current_slot()? + MAX_QUARANTINED_FUTURE_SLOTS {
+        return Ok(false);
+    }
+
+    // The parent state is unknown, the proposer is looked up in the latest state instead
+    let latest_state = store.db.get_latest_state()?;
+    drop(store);
+    Ok(latest_state
+        .verify_block_header_signature(signed_header)
+        .unwrap_or(false))
+}
+
+/// Requests the unknown ancestors queued by the quarantine from the connected peer with the
+/// highest head slot. The blocks and their blob sidecars are sent back through `lookup_sender`.
+pub fn request_missing_blocks(
+    block_quarantine: &mut BlockQuarantine,
+    network_state: &NetworkState,
+    p2p_sender: &P2PSender,
+    executor: &ReamExecutor,
+    lookup_sender: &mpsc::UnboundedSender<BlockLookupResult>,
+) {
+    let missing_roots = block_quarantine.take_missing_roots();
+    if missing_roots.is_empty() {
+        return;
+    }
+
+    let Some(peer_id) = network_state
+        .connected_peers()
+        .into_iter()
+        .max_by_key(|peer| peer.status.as_ref().map(|status| status.head_slot))
+        .map(|peer| peer.peer_id)
+    else {
+        warn!("No connected peers to look up unknown ancestors of quarantined blocks");
+        for root in missing_roots {
+            block_quarantine.retry_lookup(root);
+        }
+        return;
+    };
+
+    for root in missing_roots {
+        info!("Looking up unknown block {root} from peer {peer_id}");
+        let p2p_sender = p2p_sender.0.clone();
+        let lookup_sender = lookup_sender.clone();
+        let lookup_executor = executor.clone();
+        executor.spawn(async move {
+            let result = lookup_block(peer_id, p2p_sender, lookup_executor, root).await;
+            if let Err(err) = lookup_sender.send(BlockLookupResult { root, result }) {
+                warn!("Failed to send block lookup result: {err}");
+            }
+        });
+    }
+}
+
+async fn lookup_block(
+    peer_id: PeerId,
+    p2p_sender: UnboundedSender<P2PMessage>,
+    executor: ReamExecutor,
+    root: B256,
+) -> anyhow::Result<QuarantinedBlock> {
+    let block =
+        PeerRootsDownloader::start(peer_id, p2p_sender.clone(), executor.clone(), vec![root])
+            .await???
+            .into_iter()
+            .find(|block| block.message.block_root() == root)
+            .ok_or_else(|| anyhow!("Peer {peer_id} did not return block {root}"))?;
+
+    let blob_identifiers = (0..block.message.body.blob_kzg_commitments.len() as u64)
+        .map(|index| BlobIdentifier::new(root, index))
+        .collect::<Vec<_>>();
+    let blob_sidecars = if blob_identifiers.is_empty() {
+        vec![]
+    } else {
+        PeerBlobIdentifierDownloader::start(peer_id, p2p_sender, executor, blob_identifiers)
+            .await???
+    };
+
+    Ok(QuarantinedBlock {
+        block,
+        blob_sidecars,
+    })
+}
+
+/// Imports a block whose parent is known, or quarantines it if its parent is unknown. Once
+/// imported, the quarantined descendants of the block are re-processed.
+pub async fn import_or_quarantine_block(
+    beacon_chain: &BeaconChain,
+    block_quarantine: &mut BlockQuarantine,
+    reprocess_queue: &mut ReprocessQueue,
+    quarantined_block: QuarantinedBlock,
+) -> anyhow::Result<()> {
+    let QuarantinedBlock {
+        block,
+        blob_sidecars,
+    } = quarantined_block;
+    let block_root = block.message.block_root();
+
+    if !is_block_known(beacon_chain, block.message.parent_root).await? {
+        ensure!(
+            is_quarantinable(beacon_chain, &block.signed_header()).await?,
+            "Block {block_root} is outside of the quarantined slots or has an invalid signature"
+        );
+        for blob_sidecar in blob_sidecars {
+            block_quarantine.insert_blob_sidecar(blob_sidecar);
+        }
+        if !block_quarantine.insert_block(block) {
+            warn!("Failed to quarantine block {block_root}, the block is already quarantined");
+        }
+        return Ok(());
+    }
+
+    import_block(beacon_chain, block, blob_sidecars).await?;
+    reprocess_queue.on_block_imported(block_root);
+    import_quarantined_descendants(beacon_chain, block_quarantine, reprocess_queue, block_root)
+        .await;
+    Ok(())
+}
+
+/// Re-processes the quarantined descendants of a block which has just been imported, dropping the
+/// descendants of any block which fails to be imported.
+pub async fn import_quarantined_descendants(
+    beacon_chain: &BeaconChain,
+    block_quarantine: &mut BlockQuarantine,
+    reprocess_queue: &mut ReprocessQueue,
+    block_root: B256,
+) {
+    let mut pending_blocks = block_quarantine.take_children(block_root);
+    while let Some(QuarantinedBlock {
+        block,
+        blob_sidecars,
+    }) = pending_blocks.pop()
+    {
+        let block_root = block.message.block_root();
+        info!(
+            "Processing quarantined block: slot: {}, root: {block_root}",
+            block.message.slot
+        );
+
+        if let Err(err) = import_block(beacon_chain, block, blob_sidecars).await {
+            let dropped = block_quarantine.remove_descendants(block_root);
+            warn!(
+                "Failed to process quarantined block {block_root}, dropped {dropped} descendants: {err}"
+            );
+            continue;
+        }
+
+        reprocess_queue.on_block_imported(block_root);
+        pending_blocks.extend(block_quarantine.take_children(block_root));
+    }
+}
+
+async fn import_block(
+    beacon_chain: &BeaconChain,
+    block: SignedBeaconBlock,
+    blob_sidecars: Vec<BlobSidecar>,
+) -> anyhow::Result<()> {
+    let block_root = block.message.block_root();
+    {
+        let store = beacon_chain.store.lock().await;
+        for blob_sidecar in blob_sidecars {
+            ensure!(
+                blob_sidecar.signed_block_header.message.tree_hash_root() == block_root,
+                "Blob sidecar {} does not belong to block {block_root}",
+                blob_sidecar.index
+            );
+            ensure!(
+                blob_sidecar.verify_blob_sidecar_inclusion_proof(),
+                "Invalid inclusion proof for blob sidecar {} of block {block_root}",
+                blob_sidecar.index
+            );
+            store.db.blobs_and_proofs_provider().insert(
+                BlobIdentifier::new(block_root, blob_sidecar.index),
+                blob_sidecar.into(),
+            )?;
+        }
+    }
+
+    beacon_chain.process_block(block).await
+}
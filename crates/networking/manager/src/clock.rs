@@ -0,0 +1,61 @@
+use std::{
+    future::pending,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use ream_network_spec::networks::beacon_network_spec;
+use tokio::{
+    sync::watch,
+    time::{Instant, Interval, interval_at},
+};
+
+/// The clock driving the per-slot processing of the manager.
+#[derive(Debug)]
+pub enum SlotClock {
+    /// Ticks every slot with the system time.
+    System(Interval),
+    /// Ticks whenever the time, in seconds since the UNIX epoch, is set through the sender of the
+    /// channel, which lets tests control the time of several nodes at once.
+    Manual(watch::Receiver<u64>),
+}
+
+impl SlotClock {
+    /// A clock ticking every slot with the system time. It first ticks right away, or at
+    /// `genesis_time` when created before genesis, so no time before genesis is processed.
+    pub fn system(genesis_time: u64) -> Self {
+        let until_genesis = (UNIX_EPOCH + Duration::from_secs(genesis_time))
+            .duration_since(SystemTime::now())
+            .unwrap_or_default();
+        Self::System(interval_at(
+            Instant::now() + until_genesis,
+            Duration::from_secs(beacon_network_spec().seconds_per_slot),
+        ))
+    }
+
+    /// A clock following the time set through the sender of `receiver`. Like the system clock, it
+    /// ticks once right away.
+    pub fn manual(mut receiver: watch::Receiver<u64>) -> Self {
+        receiver.mark_changed();
+        Self::Manual(receiver)
+    }
+
+    /// Waits for the next tick and returns the time in seconds since the UNIX epoch.
+    pub async fn tick(&mut self) -> u64 {
+        match self {
+            SlotClock::System(interval) => {
+                interval.tick().await;
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("correct time")
+                    .as_secs()
+            }
+            SlotClock::Manual(receiver) => {
+                // The clock stops once its sender is dropped
+                if receiver.changed().await.is_err() {
+                    pending::<()>().await;
+                }
+                *receiver.borrow_and_update()
+            }
+        }
+    }
+}
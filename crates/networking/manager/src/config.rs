@@ -1,6 +1,6 @@
-use std::{net::IpAddr, path::PathBuf};
+use std::{net::IpAddr, num::NonZeroUsize, path::PathBuf};
 
-use ream_p2p::bootnodes::Bootnodes;
+use ream_p2p::{bootnodes::Bootnodes, config::TransportKind};
 use url::Url;
 
 pub struct ManagerConfig {
@@ -18,4 +18,9 @@ pub struct ManagerConfig {
     pub purge_db: bool,
     pub execution_endpoint: Option<Url>,
     pub execution_jwt_secret: Option<PathBuf>,
+    pub monitored_validators: Vec<u64>,
+    pub validator_income_history: bool,
+    pub blinded_block_storage: bool,
+    pub seen_message_cache_capacity: NonZeroUsize,
+    pub transport: TransportKind,
 }
@@ -1,7 +1,11 @@
-use libp2p::gossipsub::Message;
+use libp2p::{
+    PeerId,
+    gossipsub::{Message, MessageAcceptance, MessageId},
+};
 use ream_beacon_chain::beacon_chain::BeaconChain;
 use ream_consensus_beacon::{
     blob_sidecar::BlobIdentifier, execution_engine::rpc_types::get_blobs::BlobAndProofV1,
+    proposer_slashing::ProposerSlashing,
 };
 use ream_consensus_misc::constants::genesis_validators_root;
 use ream_network_spec::networks::beacon_network_spec;
@@ -20,7 +24,9 @@ use tracing::{error, info, trace, warn};
 use tree_hash::TreeHash;
 
 use crate::{
+    block_lookup::{import_quarantined_descendants, is_block_known, is_quarantinable},
     gossipsub::validate::{
+        aggregate_and_proof::validate_aggregate_and_proof,
         attester_slashing::validate_attester_slashing,
         beacon_attestation::validate_beacon_attestation,
         beacon_block::validate_gossip_beacon_block, blob_sidecar::validate_blob_sidecar,
@@ -29,6 +35,8 @@ use crate::{
         sync_committee::validate_sync_committee, voluntary_exit::validate_voluntary_exit,
     },
     p2p_sender::P2PSender,
+    quarantine::BlockQuarantine,
+    reprocess::ReprocessQueue,
 };
 
 pub fn init_gossipsub_config_with_topics() -> GossipsubConfig {
@@ -93,12 +101,26 @@ pub fn init_gossipsub_config_with_topics() -> GossipsubConfig {
 }
 
 /// Dispatches a gossipsub message to its appropriate handler.
+///
+/// `origin` is the id and propagation source of a message received from the network, and is
+/// `None` for messages which are re-processed.
 pub async fn handle_gossipsub_message(
     message: Message,
+    origin: Option<(MessageId, PeerId)>,
     beacon_chain: &BeaconChain,
     cached_db: &CachedDB,
     p2p_sender: &P2PSender,
+    block_quarantine: &mut BlockQuarantine,
+    reprocess_queue: &mut ReprocessQueue,
 ) {
+    let current_slot = match beacon_chain.store.lock().await.get_current_slot() {
+        Ok(current_slot) => current_slot,
+        Err(err) => {
+            error!("Failed to get current slot: {err}");
+            return;
+        }
+    };
+
     match GossipsubMessage::decode(&message.topic, &message.data) {
         Ok(gossip_message) => match gossip_message {
             GossipsubMessage::BeaconBlock(signed_block) => {
@@ -108,7 +130,69 @@ pub async fn handle_gossipsub_message(
                     signed_block.message.block_root()
                 );
 
-                let validation_result = match validate_gossip_beacon_block(
+                // Blocks from a slot which has not started on the local clock yet are queued until
+                // the slot starts
+                let slot = signed_block.message.slot;
+                if slot > current_slot {
+                    if reprocess_queue.queue_until_slot(slot, message, current_slot) {
+                        info!("Queued gossipsub beacon block from future slot {slot}");
+                    } else {
+                        warn!("Ignoring gossipsub beacon block from future slot {slot}");
+                    }
+                    return;
+                }
+
+                // Blocks which were already accepted are dropped before any of the checks below
+                let block_root = signed_block.message.block_root();
+                if cached_db
+                    .seen_block_roots
+                    .read()
+                    .await
+                    .contains(&block_root)
+                {
+                    trace!("Ignoring gossipsub beacon block {block_root}: already seen");
+                    return;
+                }
+
+                // [IGNORE] The block's parent has been seen, blocks with unknown parents are
+                // quarantined until their parent is imported
+                let parent_root = signed_block.message.parent_root;
+                match is_block_known(beacon_chain, parent_root).await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        match is_quarantinable(beacon_chain, &signed_block.signed_header()).await {
+                            Ok(true) => {}
+                            Ok(false) => {
+                                warn!(
+                                    "Ignoring gossipsub beacon block with unknown parent {parent_root}: slot is not quarantined or signature is invalid"
+                                );
+                                return;
+                            }
+                            Err(err) => {
+                                error!(
+                                    "Failed to check gossipsub beacon block for quarantine: {err}"
+                                );
+                                return;
+                            }
+                        }
+                        if block_quarantine.insert_block(*signed_block) {
+                            info!(
+                                "Quarantined gossipsub beacon block with unknown parent {parent_root}"
+                            );
+                        } else {
+                            warn!(
+                                "Ignoring gossipsub beacon block with unknown parent {parent_root}: block is already quarantined"
+                            );
+                        }
+                        return;
+                    }
+                    Err(err) => {
+                        error!("Failed to look up parent of gossipsub beacon block: {err}");
+                        return;
+                    }
+                }
+
+                let (validation_result, proposer_slashing) = match validate_gossip_beacon_block(
                     beacon_chain,
                     cached_db,
                     &signed_block,
@@ -122,11 +206,46 @@ pub async fn handle_gossipsub_message(
                     }
                 };
 
+                if let Some(proposer_slashing) = proposer_slashing {
+                    warn!(
+                        "Proposer {} equivocated at slot {slot}",
+                        signed_block.message.proposer_index
+                    );
+                    // [IGNORE] Honest peers relay the second block of a proposer too
+                    if let Some((message_id, propagation_source)) = origin {
+                        p2p_sender.report_gossipsub_message(
+                            message_id,
+                            propagation_source,
+                            MessageAcceptance::Ignore,
+                        );
+                    }
+                    publish_proposer_slashing(
+                        proposer_slashing,
+                        beacon_chain,
+                        cached_db,
+                        p2p_sender,
+                    )
+                    .await;
+                }
+
                 match validation_result {
                     ValidationResult::Accept => {
+                        cached_db.seen_block_roots.write().await.put(block_root, ());
                         let signed_block_bytes = signed_block.as_ssz_bytes();
-                        if let Err(err) = beacon_chain.process_block(*signed_block).await {
-                            error!("Failed to process gossipsub beacon block: {err}");
+                        match beacon_chain.process_block(*signed_block).await {
+                            Ok(()) => {
+                                reprocess_queue.on_block_imported(block_root);
+                                import_quarantined_descendants(
+                                    beacon_chain,
+                                    block_quarantine,
+                                    reprocess_queue,
+                                    block_root,
+                                )
+                                .await
+                            }
+                            Err(err) => {
+                                error!("Failed to process gossipsub beacon block: {err}");
+                            }
                         }
                         p2p_sender.send_gossip(GossipMessage {
                             topic: GossipTopic::from_topic_hash(&message.topic)
@@ -148,6 +267,34 @@ pub async fn handle_gossipsub_message(
                     single_attestation.tree_hash_root()
                 );
 
+                // Attestations from a slot which has not started on the local clock yet, or for a
+                // block which has not been imported yet, are queued until the dependency is met
+                let slot = single_attestation.data.slot;
+                if slot > current_slot {
+                    if !reprocess_queue.queue_until_slot(slot, message, current_slot) {
+                        info!("Attestation ignored: from future slot {slot}");
+                    }
+                    return;
+                }
+                let beacon_block_root = single_attestation.data.beacon_block_root;
+                match is_block_known(beacon_chain, beacon_block_root).await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        if !reprocess_queue.queue_until_block(
+                            beacon_block_root,
+                            message,
+                            current_slot,
+                        ) {
+                            info!("Attestation ignored: unknown block {beacon_block_root}");
+                        }
+                        return;
+                    }
+                    Err(err) => {
+                        error!("Failed to look up block of attestation: {err}");
+                        return;
+                    }
+                }
+
                 match validate_beacon_attestation(
                     &single_attestation,
                     beacon_chain,
@@ -156,13 +303,19 @@ pub async fn handle_gossipsub_message(
                 )
                 .await
                 {
-                    Ok(validation_result) => match validation_result {
+                    Ok((validation_result, attestation)) => match validation_result {
                         ValidationResult::Accept => {
                             p2p_sender.send_gossip(GossipMessage {
                                 topic: GossipTopic::from_topic_hash(&message.topic)
                                     .expect("invalid topic hash"),
                                 data: single_attestation.as_ssz_bytes(),
                             });
+                            if let Some(attestation) = attestation
+                                && let Err(err) =
+                                    beacon_chain.process_attestation(attestation, false).await
+                            {
+                                warn!("Failed to apply attestation to fork choice: {err}");
+                            }
                         }
                         ValidationResult::Reject(reason) => {
                             info!("Attestation rejected: {reason}");
@@ -209,11 +362,38 @@ pub async fn handle_gossipsub_message(
                     }
                 }
             }
-            GossipsubMessage::AggregateAndProof(aggregate_and_proof) => {
+            GossipsubMessage::AggregateAndProof(signed_aggregate_and_proof) => {
                 info!(
                     "Aggregate And Proof received over gossipsub: root: {}",
-                    aggregate_and_proof.tree_hash_root()
+                    signed_aggregate_and_proof.tree_hash_root()
                 );
+
+                match validate_aggregate_and_proof(
+                    &signed_aggregate_and_proof,
+                    beacon_chain,
+                    cached_db,
+                )
+                .await
+                {
+                    Ok(validation_result) => match validation_result {
+                        ValidationResult::Accept => {
+                            p2p_sender.send_gossip(GossipMessage {
+                                topic: GossipTopic::from_topic_hash(&message.topic)
+                                    .expect("invalid topic hash"),
+                                data: signed_aggregate_and_proof.as_ssz_bytes(),
+                            });
+                        }
+                        ValidationResult::Reject(reason) => {
+                            info!("Aggregate And Proof rejected: {reason}");
+                        }
+                        ValidationResult::Ignore(reason) => {
+                            info!("Aggregate And Proof ignored: {reason}");
+                        }
+                    },
+                    Err(err) => {
+                        error!("Could not validate Aggregate And Proof: {err}");
+                    }
+                }
             }
             GossipsubMessage::SyncCommittee((sync_committee, subnet_id)) => {
                 info!(
@@ -226,6 +406,12 @@ pub async fn handle_gossipsub_message(
                 {
                     Ok(validation_result) => match validation_result {
                         ValidationResult::Accept => {
+                            beacon_chain
+                                .store
+                                .lock()
+                                .await
+                                .operation_pool
+                                .insert_sync_committee_message(subnet_id, &sync_committee);
                             p2p_sender.send_gossip(GossipMessage {
                                 topic: GossipTopic::from_topic_hash(&message.topic)
                                     .expect("invalid topic hash"),
@@ -291,6 +477,12 @@ pub async fn handle_gossipsub_message(
                 {
                     Ok(validation_result) => match validation_result {
                         ValidationResult::Accept => {
+                            beacon_chain
+                                .store
+                                .lock()
+                                .await
+                                .operation_pool
+                                .insert_proposer_slashing((*proposer_slashing).clone());
                             p2p_sender.send_gossip(GossipMessage {
                                 topic: GossipTopic::from_topic_hash(&message.topic)
                                     .expect("invalid topic hash"),
@@ -314,6 +506,38 @@ pub async fn handle_gossipsub_message(
                     "Blob Sidecar received over gossipsub: root: {}",
                     blob_sidecar.tree_hash_root()
                 );
+
+                // Blob sidecars of blocks with unknown parents are held until the block is
+                // released from the quarantine
+                let parent_root = blob_sidecar.signed_block_header.message.parent_root;
+                match is_block_known(beacon_chain, parent_root).await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        match is_quarantinable(beacon_chain, &blob_sidecar.signed_block_header)
+                            .await
+                        {
+                            Ok(true) => {
+                                if !block_quarantine.insert_blob_sidecar(*blob_sidecar) {
+                                    info!("Blob_sidecar ignored: unknown parent {parent_root}");
+                                }
+                            }
+                            Ok(false) => {
+                                info!(
+                                    "Blob_sidecar ignored: unknown parent {parent_root}, slot is not quarantined or signature is invalid"
+                                );
+                            }
+                            Err(err) => {
+                                error!("Failed to check blob_sidecar for quarantine: {err}");
+                            }
+                        }
+                        return;
+                    }
+                    Err(err) => {
+                        error!("Failed to look up parent of blob_sidecar: {err}");
+                        return;
+                    }
+                }
+
                 match validate_blob_sidecar(
                     beacon_chain,
                     &blob_sidecar,
@@ -408,3 +632,186 @@ pub async fn handle_gossipsub_message(
         }
     };
 }
+
+/// Adds a proposer slashing constructed from an observed equivocation to the operation pool and
+/// gossips it, unless a slashing for the proposer was already seen.
+async fn publish_proposer_slashing(
+    proposer_slashing: ProposerSlashing,
+    beacon_chain: &BeaconChain,
+    cached_db: &CachedDB,
+    p2p_sender: &P2PSender,
+) {
+    match validate_proposer_slashing(&proposer_slashing, beacon_chain, cached_db).await {
+        Ok(ValidationResult::Accept) => {
+            let proposer_slashing_bytes = proposer_slashing.as_ssz_bytes();
+            beacon_chain
+                .store
+                .lock()
+                .await
+                .operation_pool
+                .insert_proposer_slashing(proposer_slashing);
+            p2p_sender.send_gossip(GossipMessage {
+                topic: GossipTopic {
+                    fork: beacon_network_spec().fork_digest(genesis_validators_root()),
+                    kind: GossipTopicKind::ProposerSlashing,
+                },
+                data: proposer_slashing_bytes,
+            });
+        }
+        Ok(ValidationResult::Ignore(reason) | ValidationResult::Reject(reason)) => {
+            info!("Not publishing proposer slashing for equivocation: {reason}");
+        }
+        Err(err) => {
+            error!("Could not validate proposer slashing for equivocation: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use alloy_primitives::B256;
+    use libp2p::gossipsub::TopicHash;
+    use ream_bls::traits::Signable;
+    use ream_consensus_beacon::{
+        electra::{
+            beacon_block::{BeaconBlock, SignedBeaconBlock},
+            beacon_state::BeaconState,
+        },
+        test_utils::{generator_private_key, single_validator_anchor},
+    };
+    use ream_consensus_misc::{constants::DOMAIN_BEACON_PROPOSER, misc::compute_signing_root};
+    use ream_fork_choice::store::get_forkchoice_store;
+    use ream_network_spec::networks::initialize_test_network_spec;
+    use ream_operation_pool::OperationPool;
+    use ream_p2p::channel::P2PMessage;
+    use ream_storage::{cache::AddressSlotIdentifier, db::ReamDB};
+    use tempdir::TempDir;
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    /// Returns the block of the only validator of `state` at slot 1 on top of `parent_root` with
+    /// `graffiti`, signed by the validator.
+    fn signed_block(
+        state: &BeaconState,
+        parent_root: B256,
+        graffiti: B256,
+    ) -> anyhow::Result<SignedBeaconBlock> {
+        let mut block = SignedBeaconBlock {
+            message: BeaconBlock {
+                slot: 1,
+                proposer_index: 0,
+                parent_root,
+                ..Default::default()
+            },
+            signature: Default::default(),
+        };
+        block.message.body.graffiti = graffiti;
+        let signing_root = compute_signing_root(
+            block.signed_header().message,
+            state.get_domain(DOMAIN_BEACON_PROPOSER, None),
+        );
+        block.signature = generator_private_key().sign(signing_root.as_ref())?;
+        Ok(block)
+    }
+
+    #[tokio::test]
+    async fn test_proposer_equivocation_is_ignored_and_slashed() -> anyhow::Result<()> {
+        initialize_test_network_spec();
+        let (state, anchor_block) = single_validator_anchor()?;
+        let anchor_root = anchor_block.tree_hash_root();
+        let data_dir = TempDir::new("ream_gossip_equivocation_test")?;
+        let db = ReamDB::new(data_dir.path().to_path_buf())?;
+        get_forkchoice_store(state.clone(), anchor_block, db.clone())?;
+        let beacon_chain = BeaconChain::new(
+            db,
+            Arc::new(OperationPool::default()),
+            None,
+            vec![],
+            false,
+            false,
+        );
+        beacon_chain
+            .process_tick(state.genesis_time + beacon_network_spec().seconds_per_slot)
+            .await?;
+        let cached_db = CachedDB::default();
+
+        // The first block of the proposer for the slot was accepted
+        let first_block = signed_block(&state, anchor_root, B256::ZERO)?;
+        cached_db.seen_proposer_signature.write().await.put(
+            AddressSlotIdentifier {
+                address: state.validators[0].public_key.clone(),
+                slot: 1,
+            },
+            first_block.signature.clone(),
+        );
+        cached_db
+            .seen_block_headers
+            .write()
+            .await
+            .put((1, 0), first_block.signed_header());
+
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let p2p_sender = P2PSender(sender);
+        let second_block = signed_block(&state, anchor_root, B256::repeat_byte(1))?;
+        let message_id = MessageId::new(&[1]);
+        let propagation_source = PeerId::random();
+        handle_gossipsub_message(
+            Message {
+                source: None,
+                data: second_block.as_ssz_bytes(),
+                sequence_number: None,
+                topic: TopicHash::from(GossipTopic {
+                    fork: beacon_network_spec().fork_digest(genesis_validators_root()),
+                    kind: GossipTopicKind::BeaconBlock,
+                }),
+            },
+            Some((message_id.clone(), propagation_source)),
+            &beacon_chain,
+            &cached_db,
+            &p2p_sender,
+            &mut BlockQuarantine::default(),
+            &mut ReprocessQueue::default(),
+        )
+        .await;
+
+        // [IGNORE] The block is the first block with valid signature received for the proposer
+        // for the slot, so the peer relaying the second block is not penalized
+        let Some(P2PMessage::ReportGossipsubMessage {
+            message_id: reported_message_id,
+            propagation_source: reported_source,
+            acceptance,
+        }) = receiver.try_recv().ok()
+        else {
+            panic!("Expected the second block to be reported");
+        };
+        assert_eq!(reported_message_id, message_id);
+        assert_eq!(reported_source, propagation_source);
+        assert!(matches!(acceptance, MessageAcceptance::Ignore));
+
+        // The proposer is punished through the proposer slashing proven by the two blocks instead
+        let proposer_slashing = ProposerSlashing {
+            signed_header_1: first_block.signed_header(),
+            signed_header_2: second_block.signed_header(),
+        };
+        let Some(P2PMessage::Gossip(gossip_message)) = receiver.try_recv().ok() else {
+            panic!("Expected the proposer slashing to be gossiped");
+        };
+        assert_eq!(gossip_message.topic.kind, GossipTopicKind::ProposerSlashing);
+        assert_eq!(gossip_message.data, proposer_slashing.as_ssz_bytes());
+        assert!(receiver.try_recv().is_err());
+        assert_eq!(
+            beacon_chain
+                .store
+                .lock()
+                .await
+                .operation_pool
+                .get_proposer_slashings(),
+            vec![proposer_slashing]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,231 @@
+use alloy_primitives::{B256, keccak256};
+use anyhow::anyhow;
+use ream_beacon_chain::beacon_chain::BeaconChain;
+use ream_consensus_beacon::{attestation::Attestation, electra::beacon_state::BeaconState};
+use ream_consensus_misc::misc::{compute_epoch_at_slot, get_committee_indices};
+use ream_storage::{
+    cache::CachedDB,
+    tables::{Field, Table},
+};
+use ream_validator_beacon::{
+    aggregate_and_proof::{SignedAggregateAndProof, verify_aggregate_and_proof_signature},
+    attestation::{is_aggregator_in_committee, verify_slot_signature},
+};
+use ssz::Encode;
+use tree_hash::TreeHash;
+
+use super::result::ValidationResult;
+
+/// Identifies an aggregate by its attestation data and its aggregation and committee bits, so
+/// that the same aggregate sent by several aggregators is only verified once.
+pub fn aggregate_digest(aggregate: &Attestation) -> B256 {
+    keccak256(
+        [
+            aggregate.data.tree_hash_root().as_slice(),
+            &aggregate.aggregation_bits.as_ssz_bytes(),
+            &aggregate.committee_bits.as_ssz_bytes(),
+        ]
+        .concat(),
+    )
+}
+
+pub async fn validate_aggregate_and_proof(
+    signed_aggregate_and_proof: &SignedAggregateAndProof,
+    beacon_chain: &BeaconChain,
+    cached_db: &CachedDB,
+) -> anyhow::Result<ValidationResult> {
+    let aggregate_and_proof = &signed_aggregate_and_proof.message;
+    let aggregate = &aggregate_and_proof.aggregate;
+    let aggregate_epoch = compute_epoch_at_slot(aggregate.data.slot);
+
+    // The checks against the seen caches come first, as they are the cheapest way to drop the
+    // duplicates which make up most of an attestation flood
+
+    // [IGNORE] A valid aggregate attestation defined by hash_tree_root(aggregate.data) whose
+    // aggregation_bits is a non-strict superset has not already been seen. Only identical
+    // aggregates are detected.
+    let digest = aggregate_digest(aggregate);
+    if cached_db.seen_aggregates.read().await.contains(&digest) {
+        return Ok(ValidationResult::Ignore(
+            "The aggregate has already been seen".to_string(),
+        ));
+    }
+
+    // [IGNORE] The aggregate is the first valid aggregate received for the aggregator with index
+    // aggregate_and_proof.aggregator_index for the epoch aggregate.data.target.epoch.
+    let aggregator_key = (
+        aggregate_and_proof.aggregator_index,
+        aggregate.data.target.epoch,
+    );
+    if cached_db
+        .seen_aggregators
+        .read()
+        .await
+        .contains(&aggregator_key)
+    {
+        return Ok(ValidationResult::Ignore(
+            "An aggregate of the aggregator has already been seen for the epoch".to_string(),
+        ));
+    }
+
+    let head_root = beacon_chain.head_root().await?;
+    let store = beacon_chain.store.lock().await;
+    let current_slot = store.get_current_slot()?;
+
+    // [IGNORE] aggregate.data.slot is within the last ATTESTATION_PROPAGATION_SLOT_RANGE slots,
+    // which since Deneb means its epoch is the current or the previous epoch
+    let current_epoch = compute_epoch_at_slot(current_slot);
+    if aggregate.data.slot > current_slot || aggregate_epoch + 1 < current_epoch {
+        return Ok(ValidationResult::Ignore(
+            "The aggregate is not from the current or previous epoch".to_string(),
+        ));
+    }
+
+    // [REJECT] The aggregate attestation's epoch matches its target
+    if aggregate.data.target.epoch != aggregate_epoch {
+        return Ok(ValidationResult::Reject(
+            "The aggregate's epoch doesn't match its target".to_string(),
+        ));
+    }
+
+    // [REJECT] aggregate.data.index == 0
+    if aggregate.data.index != 0 {
+        return Ok(ValidationResult::Reject(
+            "The aggregate data index must be 0".to_string(),
+        ));
+    }
+
+    // [IGNORE] The block being voted for (aggregate.data.beacon_block_root) has been seen (via
+    // gossip or non-gossip sources)
+    if store
+        .db
+        .beacon_block_provider()
+        .get(aggregate.data.beacon_block_root)?
+        .is_none()
+    {
+        return Ok(ValidationResult::Ignore(
+            "The block being voted for has not been seen".to_string(),
+        ));
+    }
+
+    let state: BeaconState = store
+        .db
+        .beacon_state_provider()
+        .get(head_root)?
+        .ok_or_else(|| anyhow!("No beacon state found for head root: {head_root}"))?;
+
+    // [REJECT] The committee index is within the expected range, with exactly one committee bit
+    // set
+    let committee_indices = get_committee_indices(&aggregate.committee_bits);
+    let [committee_index] = committee_indices[..] else {
+        return Ok(ValidationResult::Reject(
+            "The aggregate must have exactly one committee bit set".to_string(),
+        ));
+    };
+    let committee_cache = cached_db
+        .get_committee_cache(&state, head_root, aggregate_epoch)
+        .await?;
+    if committee_index >= committee_cache.committees_per_slot() {
+        return Ok(ValidationResult::Reject(
+            "The committee index is not within the expected range".to_string(),
+        ));
+    }
+
+    // [REJECT] The number of aggregation bits matches the committee size, and the attestation has
+    // participants
+    let committee = committee_cache.get_beacon_committee(aggregate.data.slot, committee_index)?;
+    if aggregate.aggregation_bits.len() != committee.len() {
+        return Ok(ValidationResult::Reject(
+            "The aggregation bits do not match the committee size".to_string(),
+        ));
+    }
+    if aggregate.aggregation_bits.is_zero() {
+        return Ok(ValidationResult::Reject(
+            "The aggregate has no participants".to_string(),
+        ));
+    }
+
+    // [REJECT] aggregate_and_proof.selection_proof selects the validator as an aggregator for the
+    // slot
+    if !is_aggregator_in_committee(committee.len(), &aggregate_and_proof.selection_proof) {
+        return Ok(ValidationResult::Reject(
+            "The validator is not selected as an aggregator".to_string(),
+        ));
+    }
+
+    // [REJECT] The aggregator's validator index is within the committee
+    if !committee.contains(&aggregate_and_proof.aggregator_index) {
+        return Ok(ValidationResult::Reject(
+            "The aggregator is not a member of the committee".to_string(),
+        ));
+    }
+
+    let aggregator = state
+        .validators
+        .get(aggregate_and_proof.aggregator_index as usize)
+        .ok_or_else(|| anyhow!("Could not get aggregator"))?;
+
+    // [REJECT] The aggregate_and_proof.selection_proof is a valid signature of the
+    // aggregate.data.slot by the validator with index aggregate_and_proof.aggregator_index.
+    if !verify_slot_signature(
+        &state,
+        aggregate.data.slot,
+        &aggregator.public_key,
+        &aggregate_and_proof.selection_proof,
+    )? {
+        return Ok(ValidationResult::Reject(
+            "Invalid selection proof".to_string(),
+        ));
+    }
+
+    // [REJECT] The aggregator signature, signed_aggregate_and_proof.signature, is valid.
+    if !verify_aggregate_and_proof_signature(
+        &state,
+        signed_aggregate_and_proof,
+        &aggregator.public_key,
+    )? {
+        return Ok(ValidationResult::Reject(
+            "Invalid aggregator signature".to_string(),
+        ));
+    }
+
+    // [REJECT] The signature of aggregate is valid.
+    if !state.is_valid_indexed_attestation(
+        &state.get_indexed_attestation_with_cache(aggregate, &committee_cache)?,
+    )? {
+        return Ok(ValidationResult::Reject(
+            "Invalid aggregate signature".to_string(),
+        ));
+    }
+
+    // [REJECT] The aggregate attestation's target block is an ancestor of the block named in the
+    // LMD vote
+    if store.get_checkpoint_block(
+        aggregate.data.beacon_block_root,
+        aggregate.data.target.epoch,
+    )? != aggregate.data.target.root
+    {
+        return Ok(ValidationResult::Reject(
+            "The target block is not an ancestor of the LMD vote block".to_string(),
+        ));
+    }
+
+    // [IGNORE] The current finalized_checkpoint is an ancestor of the block defined by
+    // aggregate.data.beacon_block_root
+    let finalized_checkpoint = store.db.finalized_checkpoint_provider().get()?;
+    if store.get_checkpoint_block(aggregate.data.beacon_block_root, finalized_checkpoint.epoch)?
+        != finalized_checkpoint.root
+    {
+        return Ok(ValidationResult::Ignore(
+            "Finalized checkpoint is not an ancestor of the block defined by aggregate.data.beacon_block_root".to_string(),
+        ));
+    }
+
+    cached_db.seen_aggregates.write().await.put(digest, ());
+    cached_db
+        .seen_aggregators
+        .write()
+        .await
+        .put(aggregator_key, ());
+    Ok(ValidationResult::Accept)
+}
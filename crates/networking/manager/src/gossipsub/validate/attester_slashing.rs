@@ -14,8 +14,8 @@ pub async fn validate_attester_slashing(
     beacon_chain: &BeaconChain,
     cached_db: &CachedDB,
 ) -> anyhow::Result<ValidationResult> {
+    let head_root = beacon_chain.head_root().await?;
     let store = beacon_chain.store.lock().await;
-    let head_root = store.get_head()?;
     let mut state: BeaconState = store
         .db
         .beacon_state_provider()
@@ -2,7 +2,8 @@ use anyhow::anyhow;
 use ream_beacon_chain::beacon_chain::BeaconChain;
 use ream_bls::traits::Verifiable;
 use ream_consensus_beacon::{
-    electra::beacon_state::BeaconState, single_attestation::SingleAttestation,
+    attestation::Attestation, electra::beacon_state::BeaconState,
+    single_attestation::SingleAttestation,
 };
 use ream_consensus_misc::{
     constants::DOMAIN_BEACON_ATTESTER,
@@ -16,15 +17,37 @@ use ream_validator_beacon::attestation::compute_subnet_for_attestation;
 
 use super::result::ValidationResult;
 
+/// Validates a gossip attestation, and returns it as an `Attestation` of its committee if it is
+/// accepted, so that it can be applied to fork choice.
 pub async fn validate_beacon_attestation(
     attestation: &SingleAttestation,
     beacon_chain: &BeaconChain,
     attestation_subnet_id: u64,
     cached_db: &CachedDB,
-) -> anyhow::Result<ValidationResult> {
+) -> anyhow::Result<(ValidationResult, Option<Attestation>)> {
+    // [IGNORE] There has been no other valid attestation seen on an attestation subnet that has an
+    // identical attestation.data.target.epoch and participating validator index. Checked before
+    // loading the head state, so that duplicates are dropped without any further work.
+    let attestation_key = AtestationKey {
+        attestation_subnet_id,
+        target_epoch: attestation.data.target.epoch,
+        participating_validator_index: attestation.attester_index,
+    };
+    if cached_db
+        .seen_attestations
+        .read()
+        .await
+        .contains(&attestation_key)
+    {
+        return Ok((
+            ValidationResult::Ignore("There has been no other valid attestation seen".to_string()),
+            None,
+        ));
+    }
+
+    let head_root = beacon_chain.head_root().await?;
     let store = beacon_chain.store.lock().await;
 
-    let head_root = store.get_head()?;
     let state: BeaconState = store
         .db
         .beacon_state_provider()
@@ -36,8 +59,11 @@ pub async fn validate_beacon_attestation(
 
     // [REJECT] The committee index is within the expected range
     if index >= committees_per_slot {
-        return Ok(ValidationResult::Reject(
-            "The committee index is not within the expected range".to_string(),
+        return Ok((
+            ValidationResult::Reject(
+                "The committee index is not within the expected range".to_string(),
+            ),
+            None,
         ));
     }
 
@@ -45,8 +71,9 @@ pub async fn validate_beacon_attestation(
     if compute_subnet_for_attestation(committees_per_slot, attestation.data.slot, index)
         != attestation_subnet_id
     {
-        return Ok(ValidationResult::Reject(
-            "The attestation is not for the correct subnet".to_string(),
+        return Ok((
+            ValidationResult::Reject("The attestation is not for the correct subnet".to_string()),
+            None,
         ));
     }
 
@@ -61,8 +88,9 @@ pub async fn validate_beacon_attestation(
     // [IGNORE] attestation.data.slot is equal to or earlier than the current_slot (with a
     // MAXIMUM_GOSSIP_CLOCK_DISPARITY allowance)
     if attestation.data.slot > current_slot {
-        return Ok(ValidationResult::Ignore(
-            "Attestation is from a future slot".to_string(),
+        return Ok((
+            ValidationResult::Ignore("Attestation is from a future slot".to_string()),
+            None,
         ));
     }
 
@@ -73,50 +101,40 @@ pub async fn validate_beacon_attestation(
     let previous_epoch = state.get_previous_epoch();
 
     if attestation_epoch != current_epoch && attestation_epoch != previous_epoch {
-        return Ok(ValidationResult::Ignore(
-            "Attestation is from a epoch too far in the past".to_string(),
+        return Ok((
+            ValidationResult::Ignore("Attestation is from a epoch too far in the past".to_string()),
+            None,
         ));
     }
 
     // [REJECT] The attestation's epoch matches its target
     if attestation.data.target.epoch != attestation_epoch {
-        return Ok(ValidationResult::Reject(
-            "The attestation's epoch doesn't match its target".to_string(),
+        return Ok((
+            ValidationResult::Reject(
+                "The attestation's epoch doesn't match its target".to_string(),
+            ),
+            None,
         ));
     }
 
     // [REJECT] attestation.data.index == 0
-    if index == 0 {
-        return Ok(ValidationResult::Reject(
-            "Committee index must not be 0".to_string(),
+    if attestation.data.index != 0 {
+        return Ok((
+            ValidationResult::Reject("Committee index must be 0".to_string()),
+            None,
         ));
     }
 
     // [REJECT] The attester is a member of the committee
-    if !state
+    let committee = cached_db
+        .get_committee_cache(&state, head_root, attestation_epoch)
+        .await?
         .get_beacon_committee(attestation.data.slot, index)?
-        .contains(&(attestation.attester_index))
-    {
-        return Ok(ValidationResult::Reject(
-            "The attester is not a member of the committee".to_string(),
-        ));
-    }
-
-    // [IGNORE] There has been no other valid attestation seen on an attestation subnet that has an
-    // identical attestation.data.target.epoch and participating validator index.
-    let attestation_key = AtestationKey {
-        attestation_subnet_id,
-        target_epoch: attestation.data.target.epoch,
-        participating_validator_index: attestation.attester_index,
-    };
-    if cached_db
-        .seen_attestations
-        .read()
-        .await
-        .contains(&attestation_key)
-    {
-        return Ok(ValidationResult::Ignore(
-            "There has been no other valid attestation seen".to_string(),
+        .to_vec();
+    if !committee.contains(&attestation.attester_index) {
+        return Ok((
+            ValidationResult::Reject("The attester is not a member of the committee".to_string()),
+            None,
         ));
     }
 
@@ -134,8 +152,9 @@ pub async fn validate_beacon_attestation(
         .verify(&validator.public_key, signing_root.as_slice())?;
 
     if !signature_valid {
-        return Ok(ValidationResult::Reject(
-            "Invalid attestation signature".to_string(),
+        return Ok((
+            ValidationResult::Reject("Invalid attestation signature".to_string()),
+            None,
         ));
     }
 
@@ -148,8 +167,9 @@ pub async fn validate_beacon_attestation(
         .get(attestation.data.beacon_block_root)?
         .is_none()
     {
-        return Ok(ValidationResult::Ignore(
-            "The block being voted for has not been seen".to_string(),
+        return Ok((
+            ValidationResult::Ignore("The block being voted for has not been seen".to_string()),
+            None,
         ));
     }
 
@@ -162,8 +182,11 @@ pub async fn validate_beacon_attestation(
         attestation.data.target.epoch,
     )? != attestation.data.target.root
     {
-        return Ok(ValidationResult::Reject(
-            "The target block is not an ancestor of the LMD vote block".to_string(),
+        return Ok((
+            ValidationResult::Reject(
+                "The target block is not an ancestor of the LMD vote block".to_string(),
+            ),
+            None,
         ));
     }
 
@@ -175,9 +198,9 @@ pub async fn validate_beacon_attestation(
         finalized_checpoint.epoch,
     )? != finalized_checpoint.root
     {
-        return Ok(ValidationResult::Ignore(
+        return Ok((ValidationResult::Ignore(
             "Finalized checkpoint is not an ancestor of the block defined by aggregate.data.beacon_block_root".to_string(),
-        ));
+        ), None));
     }
 
     cached_db
@@ -185,5 +208,8 @@ pub async fn validate_beacon_attestation(
         .write()
         .await
         .put(attestation_key, ());
-    Ok(ValidationResult::Accept)
+    Ok((
+        ValidationResult::Accept,
+        Some(attestation.to_attestation(&committee)?),
+    ))
 }
@@ -3,6 +3,7 @@ use ream_beacon_chain::beacon_chain::BeaconChain;
 use ream_consensus_beacon::{
     electra::{beacon_block::SignedBeaconBlock, beacon_state::BeaconState},
     execution_engine::new_payload_request::NewPayloadRequest,
+    proposer_slashing::ProposerSlashing,
 };
 use ream_consensus_misc::{
     constants::MAX_BLOBS_PER_BLOCK_ELECTRA, misc::compute_start_slot_at_epoch,
@@ -15,21 +16,27 @@ use ream_storage::{
 
 use super::result::ValidationResult;
 
+/// Validates a gossip beacon block, and returns along with the result the proposer slashing
+/// proven by the block if it is a second block validly signed by its proposer for the slot.
 pub async fn validate_gossip_beacon_block(
     beacon_chain: &BeaconChain,
     cached_db: &CachedDB,
     block: &SignedBeaconBlock,
-) -> anyhow::Result<ValidationResult> {
+) -> anyhow::Result<(ValidationResult, Option<ProposerSlashing>)> {
     let latest_state = beacon_chain.store.lock().await.db.get_latest_state()?;
 
     // Validate incoming block
     match validate_beacon_block(beacon_chain, cached_db, block, &latest_state, false).await? {
         ValidationResult::Accept => {}
         ValidationResult::Ignore(reason) => {
-            return Ok(ValidationResult::Ignore(reason));
+            // [IGNORE] A second block of the proposer for the slot is ignored, as honest peers
+            // relay it too, but it proves an equivocation if it is validly signed
+            let proposer_slashing =
+                detect_proposer_equivocation(cached_db, block, &latest_state).await;
+            return Ok((ValidationResult::Ignore(reason), proposer_slashing));
         }
         ValidationResult::Reject(reason) => {
-            return Ok(ValidationResult::Reject(reason));
+            return Ok((ValidationResult::Reject(reason), None));
         }
     }
 
@@ -59,10 +66,10 @@ pub async fn validate_gossip_beacon_block(
     {
         ValidationResult::Accept => {}
         ValidationResult::Ignore(reason) => {
-            return Ok(ValidationResult::Ignore(reason));
+            return Ok((ValidationResult::Ignore(reason), None));
         }
         ValidationResult::Reject(reason) => {
-            return Ok(ValidationResult::Reject(reason));
+            return Ok((ValidationResult::Reject(reason), None));
         }
     };
 
@@ -70,7 +77,10 @@ pub async fn validate_gossip_beacon_block(
         .validators
         .get(block.message.proposer_index as usize)
     else {
-        return Ok(ValidationResult::Reject("Validator not found".to_string()));
+        return Ok((
+            ValidationResult::Reject("Validator not found".to_string()),
+            None,
+        ));
     };
 
     cached_db.seen_proposer_signature.write().await.put(
@@ -80,6 +90,10 @@ pub async fn validate_gossip_beacon_block(
         },
         block.signature.clone(),
     );
+    cached_db.seen_block_headers.write().await.put(
+        (block.message.slot, block.message.proposer_index),
+        block.signed_header(),
+    );
 
     for signed_bls_execution_change in block.message.body.bls_to_execution_changes.iter() {
         let validator =
@@ -94,7 +108,48 @@ pub async fn validate_gossip_beacon_block(
         );
     }
 
-    Ok(ValidationResult::Accept)
+    Ok((ValidationResult::Accept, None))
+}
+
+/// Detects a proposer which signed a second, distinct block header for a slot it already proposed
+/// a block for, and returns the proposer slashing for the two headers.
+///
+/// The signature of `block` is checked before `state` is copied to process the slashing, so that
+/// a block which is not signed by its proposer costs a single signature verification.
+pub async fn detect_proposer_equivocation(
+    cached_db: &CachedDB,
+    block: &SignedBeaconBlock,
+    state: &BeaconState,
+) -> Option<ProposerSlashing> {
+    let seen_header = cached_db
+        .seen_block_headers
+        .read()
+        .await
+        .peek(&(block.message.slot, block.message.proposer_index))
+        .cloned()?;
+
+    let signed_header = block.signed_header();
+    if seen_header.message == signed_header.message
+        || !matches!(
+            state.verify_block_header_signature(&signed_header),
+            Ok(true)
+        )
+    {
+        return None;
+    }
+
+    // Only two headers validly signed by a slashable proposer prove the equivocation, and the
+    // seen header may have been recorded before its signature was checked
+    let proposer_slashing = ProposerSlashing {
+        signed_header_1: seen_header,
+        signed_header_2: signed_header,
+    };
+    state
+        .clone()
+        .process_proposer_slashing(&proposer_slashing)
+        .ok()?;
+
+    Some(proposer_slashing)
 }
 
 pub async fn validate_beacon_block(
@@ -280,3 +335,88 @@ pub async fn validate_beacon_block(
 
     Ok(ValidationResult::Accept)
 }
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::B256;
+    use ream_bls::{BLSSignature, traits::Signable};
+    use ream_consensus_beacon::{
+        electra::beacon_block::BeaconBlock,
+        test_utils::{generator_private_key, single_validator_state},
+    };
+    use ream_consensus_misc::{constants::DOMAIN_BEACON_PROPOSER, misc::compute_signing_root};
+    use ream_network_spec::networks::initialize_test_network_spec;
+
+    use super::*;
+
+    /// Returns the block of the only validator of `state` at slot 1 with `state_root`, signed by
+    /// its proposer.
+    fn signed_block(state: &BeaconState, state_root: B256) -> anyhow::Result<SignedBeaconBlock> {
+        let message = BeaconBlock {
+            slot: 1,
+            state_root,
+            ..Default::default()
+        };
+        let mut block = SignedBeaconBlock {
+            message,
+            signature: BLSSignature::infinity(),
+        };
+        let signing_root = compute_signing_root(
+            block.signed_header().message,
+            state.get_domain(DOMAIN_BEACON_PROPOSER, None),
+        );
+        block.signature = generator_private_key().sign(signing_root.as_ref())?;
+        Ok(block)
+    }
+
+    #[tokio::test]
+    async fn test_detect_proposer_equivocation() -> anyhow::Result<()> {
+        initialize_test_network_spec();
+        let state = single_validator_state()?;
+        let cached_db = CachedDB::default();
+        let seen_block = signed_block(&state, B256::repeat_byte(1))?;
+        assert!(
+            detect_proposer_equivocation(&cached_db, &seen_block, &state)
+                .await
+                .is_none()
+        );
+        cached_db
+            .seen_block_headers
+            .write()
+            .await
+            .put((1, 0), seen_block.signed_header());
+
+        // The block which was seen
+        assert!(
+            detect_proposer_equivocation(&cached_db, &seen_block, &state)
+                .await
+                .is_none()
+        );
+
+        // Another block which is not signed by the proposer
+        let mut unsigned_block = signed_block(&state, B256::repeat_byte(2))?;
+        unsigned_block.signature = seen_block.signature.clone();
+        assert!(
+            detect_proposer_equivocation(&cached_db, &unsigned_block, &state)
+                .await
+                .is_none()
+        );
+
+        // Another block signed by the proposer
+        let equivocating_block = signed_block(&state, B256::repeat_byte(2))?;
+        let proposer_slashing =
+            detect_proposer_equivocation(&cached_db, &equivocating_block, &state)
+                .await
+                .expect("The proposer signed two blocks for the slot");
+        assert_eq!(
+            proposer_slashing.signed_header_1,
+            seen_block.signed_header()
+        );
+        assert_eq!(
+            proposer_slashing.signed_header_2,
+            equivocating_block.signed_header()
+        );
+
+        Ok(())
+    }
+}
@@ -34,6 +34,7 @@ pub async fn validate_blob_sidecar(
     }
 
     let header = &blob_sidecar.signed_block_header.message;
+    let head_root = beacon_chain.head_root().await?;
     let store = beacon_chain.store.lock().await;
 
     // [IGNORE] The sidecar is not from a future slot
@@ -52,7 +53,6 @@ pub async fn validate_blob_sidecar(
         ));
     }
 
-    let head_root = store.get_head()?;
     let state: BeaconState = store
         .db
         .beacon_state_provider()
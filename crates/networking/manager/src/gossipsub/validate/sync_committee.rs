@@ -22,9 +22,9 @@ pub async fn validate_sync_committee(
     subnet_id: u64,
     cached_db: &CachedDB,
 ) -> anyhow::Result<ValidationResult> {
+    let head_root = beacon_chain.head_root().await?;
     let store = beacon_chain.store.lock().await;
 
-    let head_root = store.get_head()?;
     let state: BeaconState = store
         .db
         .beacon_state_provider()
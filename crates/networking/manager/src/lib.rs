@@ -1,5 +1,9 @@
+pub mod block_lookup;
+pub mod clock;
 pub mod config;
 pub mod gossipsub;
 pub mod p2p_sender;
+pub mod quarantine;
+pub mod reprocess;
 pub mod req_resp;
 pub mod service;
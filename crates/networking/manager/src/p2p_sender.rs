@@ -1,5 +1,9 @@
 use anyhow::anyhow;
-use libp2p::{PeerId, swarm::ConnectionId};
+use libp2p::{
+    PeerId,
+    gossipsub::{MessageAcceptance, MessageId},
+    swarm::ConnectionId,
+};
 use ream_p2p::{
     channel::{GossipMessage, P2PMessage, P2PResponse},
     req_resp::{error::ReqRespError, handler::RespMessage, messages::ResponseMessage},
@@ -16,6 +20,27 @@ impl P2PSender {
         }
     }
 
+    pub fn update_attestation_subnets(&self, epoch: u64) {
+        if let Err(err) = self.0.send(P2PMessage::UpdateAttestationSubnets { epoch }) {
+            warn!("Failed to send attestation subnets update: {err}");
+        }
+    }
+
+    pub fn report_gossipsub_message(
+        &self,
+        message_id: MessageId,
+        propagation_source: PeerId,
+        acceptance: MessageAcceptance,
+    ) {
+        if let Err(err) = self.0.send(P2PMessage::ReportGossipsubMessage {
+            message_id,
+            propagation_source,
+            acceptance,
+        }) {
+            warn!("Failed to send gossipsub message report: {err}");
+        }
+    }
+
     pub fn send_response(
         &self,
         peer_id: PeerId,
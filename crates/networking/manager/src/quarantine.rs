@@ -0,0 +1,366 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    mem,
+};
+
+use alloy_primitives::B256;
+use ream_beacon_chain::node_metrics::QuarantinedBlockEntry;
+use ream_consensus_beacon::{blob_sidecar::BlobSidecar, electra::beacon_block::SignedBeaconBlock};
+use ream_consensus_misc::constants::MAX_BLOBS_PER_BLOCK_ELECTRA;
+use tree_hash::TreeHash;
+
+/// The maximum number of blocks with unknown parents held in the quarantine.
+pub const MAX_QUARANTINED_BLOCKS: usize = 64;
+
+/// The maximum number of blob sidecars of quarantined blocks held in the quarantine.
+pub const MAX_QUARANTINED_BLOB_SIDECARS: usize =
+    MAX_QUARANTINED_BLOCKS * MAX_BLOBS_PER_BLOCK_ELECTRA as usize;
+
+/// The number of slots past the current slot up to which blocks are held in the quarantine, to
+/// tolerate the clock disparity between peers.
+pub const MAX_QUARANTINED_FUTURE_SLOTS: u64 = 1;
+
+/// A quarantined block together with the blob sidecars received for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuarantinedBlock {
+    pub block: SignedBeaconBlock,
+    pub blob_sidecars: Vec<BlobSidecar>,
+}
+
+/// Buffers blocks whose parents are unknown, and the blob sidecars of those blocks, until their
+/// ancestors are imported.
+///
+/// The roots of unknown ancestors are queued for lookup. An ancestor is only looked up once at a
+/// time and the blocks which are already quarantined are never looked up. Once full, the
+/// quarantine makes room by evicting the blocks and blob sidecars which were held the longest.
+#[derive(Debug, Default)]
+pub struct BlockQuarantine {
+    blocks: HashMap<B256, SignedBeaconBlock>,
+    /// Roots of the quarantined blocks in the order they were inserted, which may still hold the
+    /// roots of blocks released since.
+    block_order: VecDeque<B256>,
+    /// Roots of the quarantined blocks, keyed by their parent root.
+    children: HashMap<B256, Vec<B256>>,
+    /// Blob sidecars keyed by the root of their block.
+    blob_sidecars: HashMap<B256, Vec<BlobSidecar>>,
+    /// Roots of the blocks of the held blob sidecars in the order they were first inserted, which
+    /// may still hold the roots of blocks whose blob sidecars were released since.
+    blob_sidecar_order: VecDeque<B256>,
+    blob_sidecar_count: usize,
+    /// Roots of unknown ancestors which still have to be requested from a peer.
+    missing_roots: Vec<B256>,
+    /// Roots of unknown ancestors which were requested, or are queued to be requested.
+    requested_roots: HashSet<B256>,
+}
+
+impl BlockQuarantine {
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    pub fn blob_sidecar_count(&self) -> usize {
+        self.blob_sidecar_count
+    }
+
+    /// Returns the number of unknown ancestors which are requested or queued to be requested.
+    pub fn pending_lookup_count(&self) -> usize {
+        self.requested_roots.len()
+    }
+
+    /// Returns the quarantined blocks ordered by slot.
+    pub fn entries(&self) -> Vec<QuarantinedBlockEntry> {
+        let mut entries = self
+            .blocks
+            .iter()
+            .map(|(&block_root, block)| QuarantinedBlockEntry {
+                slot: block.message.slot,
+                block_root,
+                parent_root: block.message.parent_root,
+                blob_sidecars: self.blob_sidecars.get(&block_root).map_or(0, Vec::len),
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|entry| entry.slot);
+        entries
+    }
+
+    /// Returns the roots of the unknown ancestors which are requested or queued to be requested.
+    pub fn pending_lookups(&self) -> Vec<B256> {
+        self.requested_roots.iter().copied().collect()
+    }
+
+    pub fn contains_block(&self, block_root: B256) -> bool {
+        self.blocks.contains_key(&block_root)
+    }
+
+    /// Quarantines a block whose parent is unknown and queues the lookup of its parent if needed,
+    /// evicting the block held the longest if the quarantine is full.
+    ///
+    /// Returns `false` if the block is already quarantined.
+    pub fn insert_block(&mut self, block: SignedBeaconBlock) -> bool {
+        let block_root = block.message.block_root();
+        if self.blocks.contains_key(&block_root) {
+            return false;
+        }
+        if self.blocks.len() >= MAX_QUARANTINED_BLOCKS {
+            self.evict_oldest_block();
+        }
+
+        // The block was looked up, the lookup of its parent continues the chain of lookups
+        self.requested_roots.remove(&block_root);
+        self.missing_roots.retain(|root| *root != block_root);
+
+        let parent_root = block.message.parent_root;
+        if !self.blocks.contains_key(&parent_root) && self.requested_roots.insert(parent_root) {
+            self.missing_roots.push(parent_root);
+        }
+
+        self.children
+            .entry(parent_root)
+            .or_default()
+            .push(block_root);
+        self.blocks.insert(block_root, block);
+        self.block_order.push_back(block_root);
+        true
+    }
+
+    /// Holds on to a blob sidecar of a block which has not been imported yet, evicting the blob
+    /// sidecars of the block held the longest if the quarantine is full.
+    ///
+    /// Returns `false` if the blob sidecar is already held.
+    pub fn insert_blob_sidecar(&mut self, blob_sidecar: BlobSidecar) -> bool {
+        let block_root = blob_sidecar.signed_block_header.message.tree_hash_root();
+        if self
+            .blob_sidecars
+            .get(&block_root)
+            .is_some_and(|blob_sidecars| {
+                blob_sidecars
+                    .iter()
+                    .any(|existing| existing.index == blob_sidecar.index)
+            })
+        {
+            return false;
+        }
+        if self.blob_sidecar_count >= MAX_QUARANTINED_BLOB_SIDECARS {
+            self.evict_oldest_blob_sidecars(block_root);
+        }
+
+        let blob_sidecars = self.blob_sidecars.entry(block_root).or_default();
+        if blob_sidecars.is_empty() {
+            self.blob_sidecar_order.push_back(block_root);
+        }
+        blob_sidecars.push(blob_sidecar);
+        self.blob_sidecar_count += 1;
+        true
+    }
+
+    /// Returns the roots of the unknown ancestors which have to be requested from peers.
+    pub fn take_missing_roots(&mut self) -> Vec<B256> {
+        mem::take(&mut self.missing_roots)
+    }
+
+    /// Queues the lookup of an ancestor again after a failed request, if blocks still depend on it.
+    pub fn retry_lookup(&mut self, root: B256) {
+        if self.children.contains_key(&root) && !self.missing_roots.contains(&root) {
+            self.missing_roots.push(root);
+        } else {
+            self.requested_roots.remove(&root);
+        }
+    }
+
+    /// Releases the quarantined children of a block which has just been imported.
+    pub fn take_children(&mut self, parent_root: B256) -> Vec<QuarantinedBlock> {
+        self.requested_roots.remove(&parent_root);
+        self.missing_roots.retain(|root| *root != parent_root);
+
+        let Some(children) = self.children.remove(&parent_root) else {
+            return vec![];
+        };
+
+        children
+            .into_iter()
+            .filter_map(|block_root| {
+                let block = self.blocks.remove(&block_root)?;
+                Some(QuarantinedBlock {
+                    block,
+                    blob_sidecars: self.take_blob_sidecars(block_root),
+                })
+            })
+            .collect()
+    }
+
+    /// Drops a block which failed to be imported together with all of its quarantined
+    /// descendants, and returns the number of dropped blocks.
+    pub fn remove_descendants(&mut self, block_root: B256) -> usize {
+        self.requested_roots.remove(&block_root);
+        self.missing_roots.retain(|root| *root != block_root);
+        self.take_blob_sidecars(block_root);
+
+        let mut removed = 0;
+        let mut roots = self.children.remove(&block_root).unwrap_or_default();
+        while let Some(root) = roots.pop() {
+            if self.blocks.remove(&root).is_some() {
+                removed += 1;
+            }
+            self.take_blob_sidecars(root);
+            roots.extend(self.children.remove(&root).unwrap_or_default());
+        }
+        removed
+    }
+
+    /// Drops the blocks and blob sidecars which are not newer than the finalized slot, since they
+    /// can no longer become canonical, or which are too far past the current slot to have been
+    /// proposed yet, together with the descendants of the blocks.
+    pub fn prune(&mut self, finalized_slot: u64, current_slot: u64) {
+        let is_stale = |slot: u64| {
+            slot <= finalized_slot || slot > current_slot + MAX_QUARANTINED_FUTURE_SLOTS
+        };
+        let stale_roots = self
+            .blocks
+            .iter()
+            .filter(|(_, block)| is_stale(block.message.slot))
+            .map(|(root, _)| *root)
+            .collect::<Vec<_>>();
+        for root in stale_roots {
+            if self.blocks.remove(&root).is_some() {
+                self.remove_descendants(root);
+            }
+        }
+
+        self.blob_sidecars.retain(|_, blob_sidecars| {
+            blob_sidecars.first().is_some_and(|blob_sidecar| {
+                !is_stale(blob_sidecar.signed_block_header.message.slot)
+            })
+        });
+        self.blob_sidecar_count = self.blob_sidecars.values().map(Vec::len).sum();
+        self.blob_sidecar_order
+            .retain(|root| self.blob_sidecars.contains_key(root));
+        self.remove_orphaned_lookups();
+    }
+
+    /// Evicts the block held the longest together with its quarantined descendants.
+    fn evict_oldest_block(&mut self) {
+        while let Some(root) = self.block_order.pop_front() {
+            if self.blocks.remove(&root).is_some() {
+                self.remove_descendants(root);
+                self.remove_orphaned_lookups();
+                return;
+            }
+        }
+    }
+
+    /// Evicts the blob sidecars of the block whose blob sidecars were held the longest, other than
+    /// the block of `block_root`.
+    fn evict_oldest_blob_sidecars(&mut self, block_root: B256) {
+        while let Some(root) = self.blob_sidecar_order.pop_front() {
+            if root == block_root {
+                self.blob_sidecar_order.push_back(root);
+                if self.blob_sidecar_order.len() == 1 {
+                    return;
+                }
+                continue;
+            }
+            if self.blob_sidecars.contains_key(&root) {
+                self.take_blob_sidecars(root);
+                return;
+            }
+        }
+    }
+
+    /// Drops the roots of the blocks which are no longer quarantined, and the lookups of the
+    /// ancestors which no quarantined block depends on anymore.
+    fn remove_orphaned_lookups(&mut self) {
+        self.block_order
+            .retain(|root| self.blocks.contains_key(root));
+        self.children
+            .retain(|_, children| children.iter().any(|root| self.blocks.contains_key(root)));
+        self.requested_roots
+            .retain(|root| self.children.contains_key(root));
+        self.missing_roots
+            .retain(|root| self.children.contains_key(root));
+    }
+
+    fn take_blob_sidecars(&mut self, block_root: B256) -> Vec<BlobSidecar> {
+        let mut blob_sidecars = self.blob_sidecars.remove(&block_root).unwrap_or_default();
+        self.blob_sidecar_count -= blob_sidecars.len();
+        blob_sidecars.sort_by_key(|blob_sidecar| blob_sidecar.index);
+        blob_sidecars
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ream_bls::BLSSignature;
+    use ream_consensus_beacon::electra::beacon_block::BeaconBlock;
+
+    use super::*;
+
+    fn block(slot: u64, parent_root: B256) -> SignedBeaconBlock {
+        SignedBeaconBlock {
+            message: BeaconBlock {
+                slot,
+                parent_root,
+                ..Default::default()
+            },
+            signature: BLSSignature::default(),
+        }
+    }
+
+    #[test]
+    fn test_quarantine_chain_of_unknown_blocks() {
+        let mut block_quarantine = BlockQuarantine::default();
+        let unknown_root = B256::repeat_byte(1);
+        let first = block(10, unknown_root);
+        let first_root = first.message.block_root();
+        let second = block(11, first_root);
+        let second_root = second.message.block_root();
+
+        assert!(block_quarantine.insert_block(second));
+        assert_eq!(block_quarantine.take_missing_roots(), vec![first_root]);
+
+        // The looked up parent is quarantined as well and its own parent is looked up
+        assert!(block_quarantine.insert_block(first.clone()));
+        assert!(!block_quarantine.insert_block(first));
+        assert_eq!(block_quarantine.take_missing_roots(), vec![unknown_root]);
+        assert_eq!(block_quarantine.len(), 2);
+
+        let released = block_quarantine.take_children(unknown_root);
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].block.message.slot, 10);
+
+        let released = block_quarantine.take_children(first_root);
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].block.message.block_root(), second_root);
+        assert!(block_quarantine.is_empty());
+    }
+
+    #[test]
+    fn test_quarantine_is_bounded_and_pruned() {
+        let mut block_quarantine = BlockQuarantine::default();
+        for slot in 0..MAX_QUARANTINED_BLOCKS as u64 {
+            assert!(block_quarantine.insert_block(block(slot + 1, B256::repeat_byte(1))));
+        }
+        assert_eq!(block_quarantine.take_missing_roots().len(), 1);
+
+        // A full quarantine makes room by evicting the block held the longest
+        let latest = block(1000, B256::repeat_byte(2));
+        let latest_root = latest.message.block_root();
+        assert!(block_quarantine.insert_block(latest));
+        assert_eq!(block_quarantine.len(), MAX_QUARANTINED_BLOCKS);
+        assert!(block_quarantine.contains_block(latest_root));
+        assert_eq!(block_quarantine.entries()[0].slot, 2);
+
+        // Blocks too far past the current slot are pruned along with the finalized ones
+        block_quarantine.prune(32, 100);
+        assert_eq!(block_quarantine.len(), MAX_QUARANTINED_BLOCKS - 32);
+        assert!(!block_quarantine.contains_block(latest_root));
+        assert!(block_quarantine.take_missing_roots().is_empty());
+
+        block_quarantine.prune(MAX_QUARANTINED_BLOCKS as u64, 100);
+        assert!(block_quarantine.is_empty());
+        assert!(block_quarantine.take_missing_roots().is_empty());
+    }
+}
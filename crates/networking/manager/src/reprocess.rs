@@ -0,0 +1,199 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    mem,
+};
+
+use alloy_primitives::B256;
+use libp2p::gossipsub::Message;
+use ream_beacon_chain::node_metrics::ReprocessEntry;
+use ream_consensus_misc::constants::SLOTS_PER_EPOCH;
+
+/// The maximum number of gossipsub messages held in the reprocessing queue.
+pub const MAX_QUEUED_MESSAGES: usize = 16384;
+
+/// Messages from a slot further than this ahead of the local clock are not queued.
+pub const MAX_QUEUED_FUTURE_SLOTS: u64 = 1;
+
+/// Messages waiting for a block are dropped if the block has not been imported within this many
+/// slots.
+pub const QUEUED_MESSAGE_EXPIRY_SLOTS: u64 = SLOTS_PER_EPOCH;
+
+#[derive(Debug)]
+struct QueuedMessage {
+    message: Message,
+    queued_at_slot: u64,
+}
+
+/// Parks gossipsub messages which arrived too early, either because they reference a block which
+/// has not been imported yet or because their slot has not started on the local clock, and
+/// re-queues them once their dependency is satisfied.
+#[derive(Debug, Default)]
+pub struct ReprocessQueue {
+    /// Messages keyed by the root of the block they are waiting for.
+    awaiting_block: HashMap<B256, Vec<QueuedMessage>>,
+    /// Messages keyed by the slot they are waiting for.
+    awaiting_slot: BTreeMap<u64, Vec<Message>>,
+    /// Messages whose dependency is satisfied and which can be processed again.
+    ready: Vec<Message>,
+    len: usize,
+}
+
+impl ReprocessQueue {
+    /// Returns the number of messages which are waiting for a block or a slot.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of messages waiting for each block root.
+    pub fn awaiting_block_entries(&self) -> Vec<ReprocessEntry<B256>> {
+        self.awaiting_block
+            .iter()
+            .map(|(&key, messages)| ReprocessEntry {
+                key,
+                messages: messages.len(),
+            })
+            .collect()
+    }
+
+    /// Returns the number of messages waiting for each slot, in slot order.
+    pub fn awaiting_slot_entries(&self) -> Vec<ReprocessEntry<u64>> {
+        self.awaiting_slot
+            .iter()
+            .map(|(&key, messages)| ReprocessEntry {
+                key,
+                messages: messages.len(),
+            })
+            .collect()
+    }
+
+    /// Parks a message until the block with `block_root` is imported.
+    ///
+    /// Returns `false` if the queue is full.
+    pub fn queue_until_block(
+        &mut self,
+        block_root: B256,
+        message: Message,
+        current_slot: u64,
+    ) -> bool {
+        if self.len >= MAX_QUEUED_MESSAGES {
+            return false;
+        }
+
+        self.awaiting_block
+            .entry(block_root)
+            .or_default()
+            .push(QueuedMessage {
+                message,
+                queued_at_slot: current_slot,
+            });
+        self.len += 1;
+        true
+    }
+
+    /// Parks a message until `slot` starts on the local clock.
+    ///
+    /// Returns `false` if the queue is full or if the slot is too far in the future.
+    pub fn queue_until_slot(&mut self, slot: u64, message: Message, current_slot: u64) -> bool {
+        if self.len >= MAX_QUEUED_MESSAGES || slot > current_slot + MAX_QUEUED_FUTURE_SLOTS {
+            return false;
+        }
+
+        self.awaiting_slot.entry(slot).or_default().push(message);
+        self.len += 1;
+        true
+    }
+
+    /// Re-queues the messages which were waiting for the imported block.
+    pub fn on_block_imported(&mut self, block_root: B256) {
+        if let Some(queued_messages) = self.awaiting_block.remove(&block_root) {
+            self.len -= queued_messages.len();
+            self.ready.extend(
+                queued_messages
+                    .into_iter()
+                    .map(|queued_message| queued_message.message),
+            );
+        }
+    }
+
+    /// Re-queues the messages whose slot has started and drops the messages which waited too long
+    /// for their block.
+    pub fn on_slot(&mut self, current_slot: u64) {
+        let future_messages = self.awaiting_slot.split_off(&(current_slot + 1));
+        for (_, messages) in mem::replace(&mut self.awaiting_slot, future_messages) {
+            self.len -= messages.len();
+            self.ready.extend(messages);
+        }
+
+        let mut expired = 0;
+        self.awaiting_block.retain(|_, queued_messages| {
+            let queued = queued_messages.len();
+            queued_messages.retain(|queued_message| {
+                queued_message.queued_at_slot + QUEUED_MESSAGE_EXPIRY_SLOTS > current_slot
+            });
+            expired += queued - queued_messages.len();
+            !queued_messages.is_empty()
+        });
+        self.len -= expired;
+    }
+
+    /// Returns the messages which can be processed again.
+    pub fn take_ready(&mut self) -> Vec<Message> {
+        mem::take(&mut self.ready)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libp2p::gossipsub::TopicHash;
+
+    use super::*;
+
+    fn message(data: u8) -> Message {
+        Message {
+            source: None,
+            data: vec![data],
+            sequence_number: None,
+            topic: TopicHash::from_raw("test"),
+        }
+    }
+
+    #[test]
+    fn test_messages_are_released_when_block_is_imported() {
+        let mut reprocess_queue = ReprocessQueue::default();
+        let block_root = B256::repeat_byte(1);
+        assert!(reprocess_queue.queue_until_block(block_root, message(1), 10));
+        assert!(reprocess_queue.queue_until_block(block_root, message(2), 10));
+        assert_eq!(reprocess_queue.len(), 2);
+
+        reprocess_queue.on_block_imported(B256::repeat_byte(2));
+        assert!(reprocess_queue.take_ready().is_empty());
+
+        reprocess_queue.on_block_imported(block_root);
+        assert_eq!(reprocess_queue.take_ready().len(), 2);
+        assert!(reprocess_queue.is_empty());
+    }
+
+    #[test]
+    fn test_messages_are_released_when_slot_starts() {
+        let mut reprocess_queue = ReprocessQueue::default();
+        assert!(reprocess_queue.queue_until_slot(11, message(1), 10));
+        assert!(!reprocess_queue.queue_until_slot(12, message(2), 10));
+        assert!(reprocess_queue.queue_until_block(B256::ZERO, message(3), 10));
+
+        reprocess_queue.on_slot(10);
+        assert!(reprocess_queue.take_ready().is_empty());
+
+        reprocess_queue.on_slot(11);
+        assert_eq!(reprocess_queue.take_ready(), vec![message(1)]);
+        assert_eq!(reprocess_queue.len(), 1);
+
+        // Messages waiting for a block expire
+        reprocess_queue.on_slot(10 + QUEUED_MESSAGE_EXPIRY_SLOTS);
+        assert!(reprocess_queue.take_ready().is_empty());
+        assert!(reprocess_queue.is_empty());
+    }
+}
@@ -1,12 +1,16 @@
 use std::sync::Arc;
 
 use libp2p::{PeerId, swarm::ConnectionId};
+use ream_beacon_chain::block_reconstruction::get_beacon_block;
 use ream_consensus_beacon::blob_sidecar::BlobIdentifier;
+use ream_execution_engine::ExecutionEngine;
 use ream_p2p::{
     network_state::NetworkState,
     req_resp::messages::{
         RequestMessage, ResponseMessage,
-        beacon_blocks::{BeaconBlocksByRangeV2Request, BeaconBlocksByRootV2Request},
+        beacon_blocks::{
+            BeaconBlocksByRangeV2Request, BeaconBlocksByRootV2Request, EncodedSignedBeaconBlock,
+        },
         blob_sidecars::{BlobSidecarsByRangeV1Request, BlobSidecarsByRootV1Request},
     },
 };
@@ -15,6 +19,7 @@ use tracing::{info, trace, warn};
 
 use crate::p2p_sender::P2PSender;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_req_resp_message(
     peer_id: PeerId,
     stream_id: u64,
@@ -22,6 +27,7 @@ pub async fn handle_req_resp_message(
     message: RequestMessage,
     p2p_sender: &P2PSender,
     ream_db: &ReamDB,
+    execution_engine: Option<&ExecutionEngine>,
     network_state: Arc<NetworkState>,
 ) {
     match message {
@@ -59,7 +65,21 @@ pub async fn handle_req_resp_message(
                     );
                     return;
                 };
-                let Ok(Some(block)) = ream_db.beacon_block_provider().get(block_root) else {
+
+                // Full blocks are sent as stored, only blinded blocks have to be reconstructed
+                if let Ok(Some(bytes)) = ream_db.beacon_block_provider().get_ssz_bytes(block_root) {
+                    p2p_sender.send_response(
+                        peer_id,
+                        connection_id,
+                        stream_id,
+                        ResponseMessage::EncodedBeaconBlocksByRange(EncodedSignedBeaconBlock::new(
+                            bytes,
+                        )),
+                    );
+                    continue;
+                }
+                let Ok(Some(block)) = get_beacon_block(ream_db, execution_engine, block_root).await
+                else {
                     trace!("No block found for root {block_root}");
                     p2p_sender.send_error_response(
                         peer_id,
@@ -82,7 +102,8 @@ pub async fn handle_req_resp_message(
         }
         RequestMessage::BeaconBlocksByRoot(BeaconBlocksByRootV2Request { inner }) => {
             for block_root in inner {
-                let Ok(Some(block)) = ream_db.beacon_block_provider().get(block_root) else {
+                let Ok(Some(block)) = get_beacon_block(ream_db, execution_engine, block_root).await
+                else {
                     trace!("No block found for root {block_root}");
                     p2p_sender.send_error_response(
                         peer_id,
@@ -115,7 +136,8 @@ pub async fn handle_req_resp_message(
                     );
                     return;
                 };
-                let Ok(Some(block)) = ream_db.beacon_block_provider().get(block_root) else {
+                let Ok(Some(block)) = get_beacon_block(ream_db, execution_engine, block_root).await
+                else {
                     trace!("No block found for root {block_root}");
                     p2p_sender.send_error_response(
                         peer_id,
@@ -185,9 +207,8 @@ pub async fn handle_req_resp_message(
                     return;
                 };
 
-                let Ok(Some(block)) = ream_db
-                    .beacon_block_provider()
-                    .get(blob_identifier.block_root)
+                let Ok(Some(block)) =
+                    get_beacon_block(ream_db, execution_engine, blob_identifier.block_root).await
                 else {
                     trace!("No block found for root {}", blob_identifier.block_root);
                     p2p_sender.send_error_response(
@@ -4,7 +4,11 @@ use std::{
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
-use ream_beacon_chain::beacon_chain::BeaconChain;
+use ream_beacon_chain::{
+    beacon_chain::BeaconChain,
+    node_metrics::{QueueDepths, QueueDump, QueueDumpSender},
+};
+use ream_consensus_misc::misc::{compute_epoch_at_slot, compute_start_slot_at_epoch};
 use ream_discv5::{
     config::DiscoveryConfig,
     subnet::{AttestationSubnets, SyncCommitteeSubnets},
@@ -14,19 +18,31 @@ use ream_executor::ReamExecutor;
 use ream_network_spec::networks::beacon_network_spec;
 use ream_operation_pool::OperationPool;
 use ream_p2p::{
+    channel::P2PMessage,
     config::NetworkConfig,
     network::beacon::{Network, ReamNetworkEvent},
     network_state::NetworkState,
 };
-use ream_storage::{cache::CachedDB, db::ReamDB};
+use ream_storage::{
+    cache::{CachedDB, CachedDBConfig},
+    db::ReamDB,
+    tables::Field,
+};
 use ream_syncer::block_range::BlockRangeSyncer;
-use tokio::{sync::mpsc, time::interval};
-use tracing::{error, info};
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::interval,
+};
+use tracing::{error, info, warn};
 
 use crate::{
+    block_lookup::{BlockLookupResult, import_or_quarantine_block, request_missing_blocks},
+    clock::SlotClock,
     config::ManagerConfig,
     gossipsub::handle::{handle_gossipsub_message, init_gossipsub_config_with_topics},
     p2p_sender::P2PSender,
+    quarantine::BlockQuarantine,
+    reprocess::ReprocessQueue,
     req_resp::handle_req_resp_message,
 };
 
@@ -34,10 +50,14 @@ pub struct NetworkManagerService {
     pub beacon_chain: Arc<BeaconChain>,
     manager_receiver: mpsc::UnboundedReceiver<ReamNetworkEvent>,
     p2p_sender: P2PSender,
+    queue_dump_sender: QueueDumpSender,
+    queue_dump_receiver: mpsc::UnboundedReceiver<oneshot::Sender<QueueDump>>,
     pub network_state: Arc<NetworkState>,
     pub block_range_syncer: BlockRangeSyncer,
     pub ream_db: ReamDB,
-    pub cached_db: CachedDB,
+    pub cached_db: Arc<CachedDB>,
+    executor: ReamExecutor,
+    clock: SlotClock,
 }
 
 /// The `NetworkManagerService` acts as the manager for all networking activities in Ream.
@@ -87,10 +107,12 @@ impl NetworkManagerService {
             discv5_config,
             gossipsub_config,
             data_dir: ream_dir,
+            transport: config.transport,
         };
 
         let (manager_sender, manager_receiver) = mpsc::unbounded_channel();
         let (p2p_sender, p2p_receiver) = mpsc::unbounded_channel();
+        let (queue_dump_sender, queue_dump_receiver) = mpsc::unbounded_channel();
 
         let execution_engine = if let (Some(execution_endpoint), Some(jwt_path)) =
             (config.execution_endpoint, config.execution_jwt_secret)
@@ -103,8 +125,12 @@ impl NetworkManagerService {
             ream_db.clone(),
             operation_pool,
             execution_engine,
+            config.monitored_validators,
+            config.validator_income_history,
+            config.blinded_block_storage,
         ));
         let status = beacon_chain.build_status_request().await?;
+        let genesis_time = ream_db.genesis_time_provider().get()?;
 
         let network = Network::init(executor.clone(), &network_config, status).await?;
 
@@ -121,19 +147,43 @@ impl NetworkManagerService {
             executor.clone(),
         );
 
-        let cached_db = CachedDB::new();
+        let cached_db = Arc::new(CachedDB::with_config(CachedDBConfig {
+            seen_message_capacity: config.seen_message_cache_capacity,
+            slot_duration: Duration::from_secs(beacon_network_spec().seconds_per_slot),
+        }));
 
         Ok(Self {
             beacon_chain,
             manager_receiver,
             p2p_sender: P2PSender(p2p_sender),
+            queue_dump_sender,
+            queue_dump_receiver,
             network_state,
             block_range_syncer,
             ream_db,
             cached_db,
+            executor,
+            clock: SlotClock::system(genesis_time),
         })
     }
 
+    /// Replaces the system clock driving the per-slot processing, e.g. with a manual clock in
+    /// tests.
+    pub fn with_clock(mut self, clock: SlotClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Returns a sender for messages to the network worker, e.g. to publish gossip messages.
+    pub fn p2p_sender(&self) -> mpsc::UnboundedSender<P2PMessage> {
+        self.p2p_sender.0.clone()
+    }
+
+    /// Returns a sender for requests of the contents of the queues of the manager.
+    pub fn queue_dump_sender(&self) -> QueueDumpSender {
+        self.queue_dump_sender.clone()
+    }
+
     /// Starts the manager service, which receives either a Gossipsub message or Req/Resp message
     /// from the network worker, and dispatches them to the appropriate handlers.
     ///
@@ -143,14 +193,40 @@ impl NetworkManagerService {
             beacon_chain,
             mut manager_receiver,
             p2p_sender,
+            mut queue_dump_receiver,
             ream_db,
             cached_db,
             network_state,
             block_range_syncer,
+            executor,
+            mut clock,
             ..
         } = self;
 
-        let mut interval = interval(Duration::from_secs(beacon_network_spec().seconds_per_slot));
+        let mut block_quarantine = BlockQuarantine::default();
+        let mut reprocess_queue = ReprocessQueue::default();
+        let (lookup_sender, mut lookup_receiver) = mpsc::unbounded_channel();
+
+        // The eth1 cache is updated every slot off the manager loop, so requests to a slow
+        // execution client don't hold up the processing of messages
+        if beacon_chain.execution_engine.is_some() {
+            let beacon_chain = beacon_chain.clone();
+            executor.spawn(async move {
+                let mut interval =
+                    interval(Duration::from_secs(beacon_network_spec().seconds_per_slot));
+                loop {
+                    interval.tick().await;
+                    let time = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .expect("correct time")
+                        .as_secs();
+                    if let Err(err) = beacon_chain.update_eth1_cache(time).await {
+                        warn!("Failed to update eth1 cache: {err}");
+                    }
+                }
+            });
+        }
+
         let mut syncer_handle = block_range_syncer.start();
         loop {
             tokio::select! {
@@ -183,24 +259,66 @@ impl NetworkManagerService {
                         syncer_handle = block_range_syncer.start();
                     }
                 }
-                _ = interval.tick() => {
-                    let time = SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .expect("correct time")
-                        .as_secs();
+                time = clock.tick() => {
 
                     if let Err(err) =  beacon_chain.process_tick(time).await {
                         error!("Failed to process gossipsub tick: {err}");
                     }
+
+                    let store = beacon_chain.store.lock().await;
+                    let current_slot = match store.get_current_slot() {
+                        Ok(slot) => {
+                            p2p_sender.update_attestation_subnets(compute_epoch_at_slot(slot));
+                            reprocess_queue.on_slot(slot);
+                            store.operation_pool.clean_sync_committee_messages(slot);
+                            Some(slot)
+                        }
+                        Err(err) => {
+                            error!("Failed to get current slot: {err}");
+                            None
+                        }
+                    };
+                    drop(store);
+
+                    if let (Some(canonical_head), Some(current_slot)) = (beacon_chain.canonical_head(), current_slot) {
+                        block_quarantine.prune(compute_start_slot_at_epoch(canonical_head.finalized_checkpoint.epoch), current_slot);
+                    }
+                }
+                Some(BlockLookupResult { root, result }) = lookup_receiver.recv() => {
+                    let quarantined_block = match result {
+                        Ok(quarantined_block) => quarantined_block,
+                        Err(err) => {
+                            warn!("Failed to look up block {root}: {err}");
+                            // The lookup is requested again along with the next missing ancestors
+                            block_quarantine.retry_lookup(root);
+                            continue;
+                        }
+                    };
+
+                    if let Err(err) = import_or_quarantine_block(&beacon_chain, &mut block_quarantine, &mut reprocess_queue, quarantined_block).await {
+                        warn!("Failed to import looked up block {root}: {err}");
+                        block_quarantine.remove_descendants(root);
+                    }
+                }
+                Some(reply) = queue_dump_receiver.recv() => {
+                    let queue_dump = QueueDump {
+                        depths: *beacon_chain.queue_depths.lock().await,
+                        quarantined_blocks: block_quarantine.entries(),
+                        pending_block_lookups: block_quarantine.pending_lookups(),
+                        awaiting_block: reprocess_queue.awaiting_block_entries(),
+                        awaiting_slot: reprocess_queue.awaiting_slot_entries(),
+                    };
+                    // The requester may have given up waiting, which is fine
+                    let _ = reply.send(queue_dump);
                 }
                 Some(event) = manager_receiver.recv() => {
                     match event {
                         // Handles Gossipsub messages from other peers.
-                        ReamNetworkEvent::GossipsubMessage { message } =>
-                            handle_gossipsub_message(message, &beacon_chain, &cached_db, &p2p_sender).await,
+                        ReamNetworkEvent::GossipsubMessage { message_id, propagation_source, message } =>
+                            handle_gossipsub_message(message, Some((message_id, propagation_source)), &beacon_chain, &cached_db, &p2p_sender, &mut block_quarantine, &mut reprocess_queue).await,
                         // Handles Req/Resp messages from other peers.
                         ReamNetworkEvent::RequestMessage { peer_id, stream_id, connection_id, message } =>
-                            handle_req_resp_message(peer_id, stream_id, connection_id, message, &p2p_sender, &ream_db, network_state.clone()).await,
+                            handle_req_resp_message(peer_id, stream_id, connection_id, message, &p2p_sender, &ream_db, beacon_chain.execution_engine.as_ref(), network_state.clone()).await,
                         // Log and skip unrecognized requests.
                         unhandled_request => {
                             info!("Unhandled request: {unhandled_request:?}");
@@ -208,6 +326,43 @@ impl NetworkManagerService {
                     }
                 }
             }
+
+            // Re-processes the gossipsub messages whose block was imported or whose slot started,
+            // which may in turn release more messages
+            loop {
+                let ready_messages = reprocess_queue.take_ready();
+                if ready_messages.is_empty() {
+                    break;
+                }
+                for message in ready_messages {
+                    handle_gossipsub_message(
+                        message,
+                        None,
+                        &beacon_chain,
+                        &cached_db,
+                        &p2p_sender,
+                        &mut block_quarantine,
+                        &mut reprocess_queue,
+                    )
+                    .await;
+                }
+            }
+
+            request_missing_blocks(
+                &mut block_quarantine,
+                &network_state,
+                &p2p_sender,
+                &executor,
+                &lookup_sender,
+            );
+
+            *beacon_chain.queue_depths.lock().await = QueueDepths {
+                network_events: manager_receiver.len(),
+                quarantined_blocks: block_quarantine.len(),
+                quarantined_blob_sidecars: block_quarantine.blob_sidecar_count(),
+                pending_block_lookups: block_quarantine.pending_lookup_count(),
+                reprocess_queue: reprocess_queue.len(),
+            };
         }
     }
 }
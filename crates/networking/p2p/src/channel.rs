@@ -1,5 +1,9 @@
 use alloy_primitives::B256;
-use libp2p::{PeerId, swarm::ConnectionId};
+use libp2p::{
+    PeerId,
+    gossipsub::{MessageAcceptance, MessageId},
+    swarm::ConnectionId,
+};
 use ream_consensus_beacon::blob_sidecar::BlobIdentifier;
 use tokio::sync::mpsc;
 
@@ -22,6 +26,21 @@ pub enum P2PMessage {
     Request(P2PRequest),
     Response(P2PResponse),
     Gossip(GossipMessage),
+    /// Recompute the long-lived attestation subnets for the given epoch.
+    UpdateAttestationSubnets {
+        epoch: u64,
+    },
+    /// Reports the validation result of a gossipsub message, which scores its propagation source.
+    ReportGossipsubMessage {
+        message_id: MessageId,
+        propagation_source: PeerId,
+        acceptance: MessageAcceptance,
+    },
+    /// Disconnects the peer, and refuses any further connection with it if `ban` is set.
+    DisconnectPeer {
+        peer_id: PeerId,
+        ban: bool,
+    },
 }
 
 pub enum P2PRequest {
@@ -4,10 +4,23 @@ use ream_discv5::config::DiscoveryConfig;
 
 use crate::gossipsub::configurations::GossipsubConfig;
 
+/// The transport the node listens and dials on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransportKind {
+    #[default]
+    Tcp,
+    /// An in-process transport, which lets tests run several nodes without opening sockets. The
+    /// node listens on `/memory/<socket_port>`, and the TCP port of the ENR of a peer is dialed
+    /// as a memory address.
+    Memory,
+}
+
 pub struct NetworkConfig {
     pub discv5_config: DiscoveryConfig,
 
     pub gossipsub_config: GossipsubConfig,
 
     pub data_dir: PathBuf,
+
+    pub transport: TransportKind,
 }
@@ -11,8 +11,8 @@ use ream_light_client::{
 };
 use ream_network_spec::networks::beacon_network_spec;
 use ream_validator_beacon::{
-    aggregate_and_proof::AggregateAndProof, contribution_and_proof::SignedContributionAndProof,
-    sync_committee::SyncCommitteeMessage,
+    aggregate_and_proof::SignedAggregateAndProof,
+    contribution_and_proof::SignedContributionAndProof, sync_committee::SyncCommitteeMessage,
 };
 use ssz::Decode;
 
@@ -26,7 +26,7 @@ pub enum GossipsubMessage {
     BeaconBlock(Box<SignedBeaconBlock>),
     AttesterSlashing(Box<AttesterSlashing>),
     ProposerSlashing(Box<ProposerSlashing>),
-    AggregateAndProof(Box<AggregateAndProof>),
+    AggregateAndProof(Box<SignedAggregateAndProof>),
     BlobSidecar(Box<BlobSidecar>),
     BeaconAttestation((Box<SingleAttestation>, u64)),
     SyncCommittee((Box<SyncCommitteeMessage>, u64)),
@@ -61,7 +61,7 @@ impl GossipsubMessage {
                 )))
             }
             GossipTopicKind::AggregateAndProof => Ok(Self::AggregateAndProof(Box::new(
-                AggregateAndProof::from_ssz_bytes(data)?,
+                SignedAggregateAndProof::from_ssz_bytes(data)?,
             ))),
             GossipTopicKind::BeaconAttestation(subnet_id) => Ok(Self::BeaconAttestation((
                 Box::new(SingleAttestation::from_ssz_bytes(data)?),
@@ -17,12 +17,14 @@ use libp2p::{
     core::{
         ConnectedPoint,
         muxing::StreamMuxerBox,
-        transport::Boxed,
+        transport::{Boxed, MemoryTransport},
         upgrade::{SelectUpgrade, Version},
     },
     dns::Transport as DnsTransport,
     futures::StreamExt,
-    gossipsub::{Event as GossipsubEvent, IdentTopic as Topic, Message, MessageAuthenticity},
+    gossipsub::{
+        Event as GossipsubEvent, IdentTopic as Topic, Message, MessageAuthenticity, MessageId,
+    },
     identify,
     multiaddr::Protocol,
     noise::Config as NoiseConfig,
@@ -34,7 +36,10 @@ use libp2p_identity::{Keypair, PublicKey, secp256k1, secp256k1::PublicKey as Sec
 use libp2p_mplex::{MaxBufferBehaviour, MplexConfig};
 use parking_lot::{Mutex, RwLock};
 use ream_consensus_misc::constants::genesis_validators_root;
-use ream_discv5::discovery::{Discovery, DiscoveryOutEvent, QueryType};
+use ream_discv5::{
+    discovery::{Discovery, DiscoveryOutEvent, QueryType},
+    subnet::AttestationSubnets,
+};
 use ream_executor::ReamExecutor;
 use ream_network_spec::networks::beacon_network_spec;
 use tokio::{
@@ -46,7 +51,7 @@ use yamux::Config as YamuxConfig;
 
 use crate::{
     channel::{P2PCallbackResponse, P2PMessage, P2PRequest, P2PResponse},
-    config::NetworkConfig,
+    config::{NetworkConfig, TransportKind},
     constants::{PING_INTERVAL_DURATION, TARGET_PEER_COUNT},
     gossipsub::{GossipsubBehaviour, snappy::SnappyTransform, topics::GossipTopic},
     network_state::NetworkState,
@@ -97,6 +102,8 @@ pub enum ReamNetworkEvent {
         message: RequestMessage,
     },
     GossipsubMessage {
+        message_id: MessageId,
+        propagation_source: PeerId,
         message: Message,
     },
 }
@@ -117,6 +124,9 @@ pub struct Network {
     request_id: u64,
     network_state: Arc<NetworkState>,
     peers_to_ping: HashSetDelay<PeerId>,
+    /// Peers banned at runtime, which are disconnected as soon as they connect and never dialed.
+    banned_peers: HashSet<PeerId>,
+    transport: TransportKind,
 }
 
 impl Network {
@@ -186,7 +196,7 @@ impl Network {
             }
         };
 
-        let transport = build_transport(Keypair::from(local_key.clone()))
+        let transport = build_transport(Keypair::from(local_key.clone()), config.transport)
             .map_err(|err| anyhow!("Failed to build transport: {err:?}"))?;
 
         let swarm = {
@@ -228,6 +238,8 @@ impl Network {
             request_id: 0,
             network_state,
             peers_to_ping: HashSetDelay::new(PING_INTERVAL_DURATION),
+            banned_peers: HashSet::new(),
+            transport: config.transport,
         };
 
         network.start_network_worker(config).await?;
@@ -238,8 +250,16 @@ impl Network {
     async fn start_network_worker(&mut self, config: &NetworkConfig) -> anyhow::Result<()> {
         info!("Libp2p starting .... ");
 
-        let mut multi_addr: Multiaddr = config.discv5_config.socket_address.into();
-        multi_addr.push(Protocol::Tcp(config.discv5_config.socket_port));
+        let multi_addr = match config.transport {
+            TransportKind::Tcp => {
+                let mut multi_addr: Multiaddr = config.discv5_config.socket_address.into();
+                multi_addr.push(Protocol::Tcp(config.discv5_config.socket_port));
+                multi_addr
+            }
+            TransportKind::Memory => {
+                Multiaddr::empty().with(Protocol::Memory(config.discv5_config.socket_port.into()))
+            }
+        };
 
         match self.swarm.listen_on(multi_addr.clone()) {
             Ok(listener_id) => {
@@ -368,6 +388,25 @@ impl Network {
                                 warn!("Failed to publish gossip message: {err}");
                             }
                         }
+                        P2PMessage::UpdateAttestationSubnets { epoch } => {
+                            if let Err(err) = self.update_attestation_subnets(epoch) {
+                                warn!("Failed to update attestation subnets: {err:?}");
+                            }
+                        }
+                        P2PMessage::ReportGossipsubMessage { message_id, propagation_source, acceptance } => {
+                            if !self.swarm.behaviour_mut().gossipsub.report_message_validation_result(&message_id, &propagation_source, acceptance) {
+                                trace!("Gossipsub message {message_id} is no longer cached, skipping validation report");
+                            }
+                        }
+                        P2PMessage::DisconnectPeer { peer_id, ban } => {
+                            if ban {
+                                info!("Banning peer {peer_id}");
+                                self.banned_peers.insert(peer_id);
+                            }
+                            if self.swarm.disconnect_peer_id(peer_id).is_err() {
+                                trace!("Peer {peer_id} is not connected, nothing to disconnect");
+                            }
+                        }
                     }
                 }
                 Some(Ok(peer_id)) = self.peers_to_ping.next() => {
@@ -426,6 +465,31 @@ impl Network {
         }
     }
 
+    /// Recomputes the long-lived attestation subnets the node is subscribed to at `epoch`. If they
+    /// changed, the `attnets` of the local ENR and MetaData are updated and the MetaData sequence
+    /// number is incremented.
+    fn update_attestation_subnets(&mut self, epoch: u64) -> anyhow::Result<()> {
+        let attestation_subnets =
+            AttestationSubnets::from_subscribed_subnets(self.enr().node_id(), epoch)?;
+        if self.network_state.meta_data.read().attnets == attestation_subnets.0 {
+            return Ok(());
+        }
+
+        let discovery = &mut self.swarm.behaviour_mut().discovery;
+        discovery.update_attestation_subnets(&attestation_subnets)?;
+        *self.network_state.local_enr.write() = discovery.local_enr();
+
+        {
+            let mut meta_data = self.network_state.meta_data.write();
+            meta_data.attnets = attestation_subnets.0;
+            meta_data.seq_number += 1;
+        }
+        self.network_state.write_meta_data_to_disk()?;
+
+        info!("Updated long-lived attestation subnets for epoch {epoch}");
+        Ok(())
+    }
+
     fn send_request(&mut self, peer_id: PeerId, message: RequestMessage) -> Option<u64> {
         if !self.swarm.is_connected(&peer_id) {
             return None;
@@ -462,6 +526,11 @@ impl Network {
             SwarmEvent::ConnectionEstablished {
                 peer_id, endpoint, ..
             } => {
+                if self.banned_peers.contains(&peer_id) {
+                    info!("Disconnecting banned peer {peer_id}");
+                    let _ = self.swarm.disconnect_peer_id(peer_id);
+                    return None;
+                }
                 if let ConnectedPoint::Listener { send_back_addr, .. } = &endpoint {
                     self.network_state.upsert_peer(
                         peer_id,
@@ -515,20 +584,34 @@ impl Network {
     fn handle_discovered_peers(&mut self, peers: HashMap<Enr, Option<Instant>>) {
         trace!("Discovered peers: {peers:?}");
         for (enr, _) in peers {
-            let mut multiaddrs: Vec<Multiaddr> = Vec::new();
-            if let Some(ip) = enr.ip4()
-                && let Some(tcp) = enr.tcp4()
+            if Network::peer_id_from_enr(&enr)
+                .is_some_and(|peer_id| self.banned_peers.contains(&peer_id))
             {
-                let mut multiaddr: Multiaddr = ip.into();
-                multiaddr.push(Protocol::Tcp(tcp));
-                multiaddrs.push(multiaddr);
+                continue;
             }
-            if let Some(ip6) = enr.ip6()
-                && let Some(tcp6) = enr.tcp6()
-            {
-                let mut multiaddr: Multiaddr = ip6.into();
-                multiaddr.push(Protocol::Tcp(tcp6));
-                multiaddrs.push(multiaddr);
+            let mut multiaddrs: Vec<Multiaddr> = Vec::new();
+            match self.transport {
+                TransportKind::Tcp => {
+                    if let Some(ip) = enr.ip4()
+                        && let Some(tcp) = enr.tcp4()
+                    {
+                        let mut multiaddr: Multiaddr = ip.into();
+                        multiaddr.push(Protocol::Tcp(tcp));
+                        multiaddrs.push(multiaddr);
+                    }
+                    if let Some(ip6) = enr.ip6()
+                        && let Some(tcp6) = enr.tcp6()
+                    {
+                        let mut multiaddr: Multiaddr = ip6.into();
+                        multiaddr.push(Protocol::Tcp(tcp6));
+                        multiaddrs.push(multiaddr);
+                    }
+                }
+                TransportKind::Memory => {
+                    if let Some(tcp) = enr.tcp4() {
+                        multiaddrs.push(Multiaddr::empty().with(Protocol::Memory(tcp.into())));
+                    }
+                }
             }
 
             let mut successfully_dialed = false;
@@ -771,10 +854,14 @@ impl Network {
     fn handle_gossipsub_event(&mut self, event: GossipsubEvent) -> Option<ReamNetworkEvent> {
         match event {
             GossipsubEvent::Message {
-                propagation_source: _,
-                message_id: _,
+                propagation_source,
+                message_id,
+                message,
+            } => Some(ReamNetworkEvent::GossipsubMessage {
+                message_id,
+                propagation_source,
                 message,
-            } => Some(ReamNetworkEvent::GossipsubMessage { message }),
+            }),
             GossipsubEvent::Subscribed { peer_id, topic } => {
                 trace!("Peer {peer_id} subscribed to topic: {topic:?}");
                 None
@@ -809,7 +896,19 @@ impl Network {
     }
 }
 
-pub fn build_transport(local_private_key: Keypair) -> io::Result<Boxed<(PeerId, StreamMuxerBox)>> {
+pub fn build_transport(
+    local_private_key: Keypair,
+    transport: TransportKind,
+) -> io::Result<Boxed<(PeerId, StreamMuxerBox)>> {
+    if transport == TransportKind::Memory {
+        return Ok(MemoryTransport::default()
+            .upgrade(Version::V1)
+            .authenticate(NoiseConfig::new(&local_private_key).expect("Noise disabled"))
+            .multiplex(YamuxConfig::default())
+            .timeout(Duration::from_secs(10))
+            .boxed());
+    }
+
     // mplex config
     let mut mplex_config = MplexConfig::new();
     mplex_config.set_max_buffer_size(256);
@@ -883,6 +982,7 @@ mod tests {
                 ..Default::default()
             },
             data_dir: std::env::temp_dir().join("ream_network_test"),
+            transport: TransportKind::Tcp,
         };
 
         Network::init(
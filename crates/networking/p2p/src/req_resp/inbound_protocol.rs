@@ -1,23 +1,14 @@
-use std::{
-    future::Future,
-    io::{Cursor, Read, Write},
-    pin::Pin,
-    time::Duration,
-};
+use std::{future::Future, io::Write, pin::Pin, time::Duration};
 
 use asynchronous_codec::BytesMut;
 use futures::{
     FutureExt, StreamExt,
     prelude::{AsyncRead, AsyncWrite},
 };
-use libp2p::{
-    InboundUpgrade,
-    bytes::{Buf, BufMut},
-    core::UpgradeInfo,
-};
+use libp2p::{InboundUpgrade, bytes::BufMut, core::UpgradeInfo};
 use ream_consensus_misc::constants::genesis_validators_root;
 use ream_network_spec::networks::beacon_network_spec;
-use snap::{read::FrameDecoder, write::FrameEncoder};
+use snap::write::FrameEncoder;
 use ssz::{Decode, Encode};
 use ssz_types::{VariableList, typenum::U256};
 use tokio::time::timeout;
@@ -36,8 +27,10 @@ use super::{
         RequestMessage, goodbye::Goodbye, meta_data::GetMetaDataV2, ping::Ping, status::Status,
     },
     protocol_id::{ProtocolId, SupportedProtocol},
+    snappy::SnappyFrameDecoder,
 };
 use crate::{
+    constants::MAX_PAYLOAD_SIZE,
     req_resp::messages::{
         beacon_blocks::{BeaconBlocksByRangeV2Request, BeaconBlocksByRootV2Request},
         blob_sidecars::{BlobSidecarsByRangeV1Request, BlobSidecarsByRootV1Request},
@@ -72,6 +65,7 @@ where
                 Box::pin(timed_socket),
                 InboundSSZSnappyCodec {
                     protocol: info.clone(),
+                    snappy_decoder: None,
                 },
             );
 
@@ -105,6 +99,7 @@ impl UpgradeInfo for InboundReqRespProtocol {
 #[derive(Debug)]
 pub struct InboundSSZSnappyCodec {
     protocol: ProtocolId,
+    snappy_decoder: Option<SnappyFrameDecoder>,
 }
 
 impl Encoder<RespMessage> for InboundSSZSnappyCodec {
@@ -160,16 +155,22 @@ impl Decoder for InboundSSZSnappyCodec {
             )));
         }
 
-        let length = match Uvi::<usize>::default().decode(src)? {
-            Some(length) => length,
-            None => return Ok(None),
+        let snappy_decoder = match &mut self.snappy_decoder {
+            Some(snappy_decoder) => snappy_decoder,
+            None => {
+                let length = match Uvi::<usize>::default().decode(src)? {
+                    Some(length) => length,
+                    None => return Ok(None),
+                };
+                self.snappy_decoder
+                    .insert(SnappyFrameDecoder::new(length, MAX_PAYLOAD_SIZE as usize)?)
+            }
         };
+        let length = snappy_decoder.expected_len();
 
-        let mut decoder = FrameDecoder::new(Cursor::new(&src));
-        let mut buf: Vec<u8> = vec![0; length];
-        let result = match decoder.read_exact(&mut buf) {
-            Ok(_) => {
-                src.advance(decoder.get_ref().position() as usize);
+        let result = match snappy_decoder.decode(src)? {
+            Some(buf) => {
+                self.snappy_decoder = None;
                 match self.protocol.protocol {
                     SupportedProtocol::GoodbyeV1 => Ok(Some(RequestMessage::Goodbye(
                         Goodbye::from_ssz_bytes(&buf).map_err(ReqRespError::from)?,
@@ -209,7 +210,7 @@ impl Decoder for InboundSSZSnappyCodec {
                     )),
                 }
             }
-            Err(err) => Err(ReqRespError::from(err)),
+            None => Ok(None),
         };
 
         debug!(
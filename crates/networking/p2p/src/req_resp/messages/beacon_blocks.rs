@@ -1,4 +1,5 @@
 use alloy_primitives::B256;
+use ssz::{Decode, DecodeError, Encode};
 use ssz_derive::{Decode, Encode};
 use ssz_types::{VariableList, typenum::U1024};
 
@@ -34,3 +35,42 @@ impl BeaconBlocksByRootV2Request {
         }
     }
 }
+
+/// A `SignedBeaconBlock` already encoded as SSZ, such as read from the database, which is written
+/// to the stream as is instead of being decoded and encoded again.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EncodedSignedBeaconBlock(Vec<u8>);
+
+impl EncodedSignedBeaconBlock {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Encode for EncodedSignedBeaconBlock {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.0);
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl Decode for EncodedSignedBeaconBlock {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self(bytes.to_vec()))
+    }
+}
@@ -7,7 +7,9 @@ pub mod status;
 
 use std::sync::Arc;
 
-use beacon_blocks::{BeaconBlocksByRangeV2Request, BeaconBlocksByRootV2Request};
+use beacon_blocks::{
+    BeaconBlocksByRangeV2Request, BeaconBlocksByRootV2Request, EncodedSignedBeaconBlock,
+};
 use blob_sidecars::{BlobSidecarsByRangeV1Request, BlobSidecarsByRootV1Request};
 use goodbye::Goodbye;
 use meta_data::GetMetaDataV2;
@@ -65,4 +67,7 @@ pub enum ResponseMessage {
     BeaconBlocksByRoot(SignedBeaconBlock),
     BlobSidecarsByRange(BlobSidecar),
     BlobSidecarsByRoot(BlobSidecar),
+    /// A block of a BeaconBlocksByRange response served from its stored encoding. It is only
+    /// sent, responses are always decoded as `BeaconBlocksByRange`.
+    EncodedBeaconBlocksByRange(EncodedSignedBeaconBlock),
 }
@@ -5,6 +5,7 @@ pub mod inbound_protocol;
 pub mod messages;
 pub mod outbound_protocol;
 pub mod protocol_id;
+pub mod snappy;
 
 use std::task::{Context, Poll};
 
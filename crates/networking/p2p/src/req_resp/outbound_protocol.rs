@@ -1,8 +1,4 @@
-use std::{
-    future::Future,
-    io::{Cursor, ErrorKind, Read, Write},
-    pin::Pin,
-};
+use std::{future::Future, io::Write, pin::Pin};
 
 use alloy_primitives::aliases::B32;
 use anyhow::anyhow;
@@ -15,7 +11,7 @@ use libp2p::{OutboundUpgrade, bytes::Buf, core::UpgradeInfo};
 use ream_consensus_beacon::{blob_sidecar::BlobSidecar, electra::beacon_block::SignedBeaconBlock};
 use ream_consensus_misc::constants::genesis_validators_root;
 use ream_network_spec::networks::beacon_network_spec;
-use snap::{read::FrameDecoder, write::FrameEncoder};
+use snap::write::FrameEncoder;
 use ssz::{Decode, Encode};
 use ssz_types::{VariableList, typenum::U256};
 use tokio_util::{
@@ -31,8 +27,14 @@ use super::{
     inbound_protocol::ResponseCode,
     messages::{RequestMessage, meta_data::GetMetaDataV2, ping::Ping, status::Status},
     protocol_id::{ProtocolId, SupportedProtocol},
+    snappy::SnappyFrameDecoder,
+};
+use crate::{
+    constants::MAX_PAYLOAD_SIZE, req_resp::messages::ResponseMessage, utils::max_message_size,
 };
-use crate::{req_resp::messages::ResponseMessage, utils::max_message_size};
+
+/// Error messages are an SSZ `List[byte, 256]`
+const ERROR_MESSAGE_MAX_LENGTH: usize = 256;
 
 #[derive(Debug, Clone)]
 pub struct OutboundReqRespProtocol {
@@ -58,7 +60,7 @@ where
                 protocol,
                 current_response_code: None,
                 context_bytes: None,
-                length: None,
+                snappy_decoder: None,
             },
         );
 
@@ -86,7 +88,7 @@ pub struct OutboundSSZSnappyCodec {
     protocol: ProtocolId,
     current_response_code: Option<ResponseCode>,
     context_bytes: Option<B32>,
-    length: Option<usize>,
+    snappy_decoder: Option<SnappyFrameDecoder>,
 }
 
 impl Encoder<RequestMessage> for OutboundSSZSnappyCodec {
@@ -151,33 +153,27 @@ impl Decoder for OutboundSSZSnappyCodec {
             ))));
         }
 
-        let length = match self.length {
-            Some(cached_length) => cached_length,
+        let snappy_decoder = match &mut self.snappy_decoder {
+            Some(snappy_decoder) => snappy_decoder,
             None => {
-                let decoded_length = match Uvi::<usize>::default().decode(src)? {
-                    Some(decoded_length) => decoded_length,
+                let length = match Uvi::<usize>::default().decode(src)? {
+                    Some(length) => length,
                     None => return Ok(None),
                 };
-                *self.length.get_or_insert(decoded_length)
+                // The length-prefix is within the expected size bounds derived from the payload
+                // SSZ type or MAX_PAYLOAD_SIZE, whichever is smaller.
+                let max_length = match response_code {
+                    ResponseCode::Success => MAX_PAYLOAD_SIZE as usize,
+                    _ => ERROR_MESSAGE_MAX_LENGTH,
+                };
+                self.snappy_decoder
+                    .insert(SnappyFrameDecoder::new(length, max_length)?)
             }
         };
 
-        // The length-prefix is within the expected size bounds derived from the payload SSZ
-        // type or MAX_PAYLOAD_SIZE, whichever is smaller.
-        if length > max_message_size() as usize {
-            return Err(ReqRespError::Anyhow(anyhow::anyhow!(
-                "Message size exceeds maximum: {} > {}",
-                length,
-                max_message_size()
-            )));
-        }
-
-        let mut decoder = FrameDecoder::new(Cursor::new(&src));
-        let mut buf: Vec<u8> = vec![0; length];
-        let result = match decoder.read_exact(&mut buf) {
-            Ok(_) => {
-                src.advance(decoder.get_ref().position() as usize);
-                self.length = None;
+        let result = match snappy_decoder.decode(src)? {
+            Some(buf) => {
+                self.snappy_decoder = None;
                 self.context_bytes = None;
                 if ResponseCode::Success == response_code {
                     match self.protocol.protocol {
@@ -230,20 +226,7 @@ impl Decoder for OutboundSSZSnappyCodec {
                     )))
                 }
             }
-            Err(err) => match err.kind() {
-                ErrorKind::UnexpectedEof => {
-                    if decoder.get_ref().position() < max_message_size() {
-                        Ok(None)
-                    } else {
-                        Err(ReqRespError::InvalidData(format!(
-                            "Message is bigger then max message size: {err:?}"
-                        )))
-                    }
-                }
-                _ => Err(ReqRespError::InvalidData(format!(
-                    "Failed to snappy message {err:?}"
-                ))),
-            },
+            None => Ok(None),
         };
         debug!(
             "OutboundSSZSnappyCodec::decode: protocol: {:?}, response_code: {:?}, result: {:?}",
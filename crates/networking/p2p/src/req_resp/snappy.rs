@@ -0,0 +1,273 @@
+use asynchronous_codec::BytesMut;
+use crc::{CRC_32_ISCSI, Crc};
+use snap::raw::{Decoder as RawDecoder, decompress_len, max_compress_len};
+
+use super::error::ReqRespError;
+
+/// https://github.com/google/snappy/blob/main/framing_format.txt
+const STREAM_IDENTIFIER: &[u8] = b"sNaPpY";
+const CHUNK_TYPE_COMPRESSED_DATA: u8 = 0x00;
+const CHUNK_TYPE_UNCOMPRESSED_DATA: u8 = 0x01;
+const CHUNK_TYPE_PADDING: u8 = 0xfe;
+const CHUNK_TYPE_STREAM_IDENTIFIER: u8 = 0xff;
+const CHUNK_HEADER_LEN: usize = 4;
+const CHECKSUM_LEN: usize = 4;
+
+/// The uncompressed data of a chunk must not exceed 65536 bytes
+const MAX_UNCOMPRESSED_CHUNK_LEN: usize = 1 << 16;
+
+const CASTAGNOLI: Crc<u32> = Crc::<u32>::new(&CRC_32_ISCSI);
+
+/// The largest chunk the framing format allows for the data of a chunk, checksum included
+fn max_chunk_len() -> usize {
+    CHECKSUM_LEN + max_compress_len(MAX_UNCOMPRESSED_CHUNK_LEN)
+}
+
+/// The most bytes an encoder can produce for `len` bytes of data: the stream identifier and the
+/// worst-case compression of every chunk.
+pub fn max_encoded_len(len: usize) -> usize {
+    let chunks = len.div_ceil(MAX_UNCOMPRESSED_CHUNK_LEN).max(1);
+    CHUNK_HEADER_LEN
+        + STREAM_IDENTIFIER.len()
+        + chunks * (CHUNK_HEADER_LEN + CHECKSUM_LEN)
+        + max_compress_len(len)
+}
+
+fn masked_checksum(data: &[u8]) -> u32 {
+    let checksum = CASTAGNOLI.checksum(data);
+    checksum.rotate_right(15).wrapping_add(0xa282ead8)
+}
+
+/// Decodes a snappy frame stream of a known uncompressed length incrementally, one chunk at a
+/// time as the bytes arrive.
+///
+/// The declared length is checked against the limit before anything is allocated, and every
+/// chunk is checked against the remaining length before it is decompressed, so a peer cannot make
+/// us allocate or decompress more than the length it announced.
+#[derive(Debug)]
+pub struct SnappyFrameDecoder {
+    expected_len: usize,
+    max_encoded_len: usize,
+    consumed: usize,
+    seen_stream_identifier: bool,
+    decoder: RawDecoder,
+    output: Vec<u8>,
+}
+
+impl SnappyFrameDecoder {
+    pub fn new(expected_len: usize, max_len: usize) -> Result<Self, ReqRespError> {
+        if expected_len > max_len {
+            return Err(ReqRespError::InvalidData(format!(
+                "Declared length exceeds maximum: {expected_len} > {max_len}"
+            )));
+        }
+
+        Ok(Self {
+            expected_len,
+            max_encoded_len: max_encoded_len(expected_len),
+            consumed: 0,
+            seen_stream_identifier: false,
+            decoder: RawDecoder::new(),
+            output: Vec::with_capacity(expected_len),
+        })
+    }
+
+    pub fn expected_len(&self) -> usize {
+        self.expected_len
+    }
+
+    /// Consumes the complete chunks in `src` and returns the decoded data once `expected_len`
+    /// bytes have been decoded. Incomplete chunks are left in `src` for the next call.
+    pub fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Vec<u8>>, ReqRespError> {
+        while self.output.len() < self.expected_len {
+            if src.len() < CHUNK_HEADER_LEN {
+                return Ok(None);
+            }
+            let chunk_type = src[0];
+            let chunk_len = u32::from_le_bytes([src[1], src[2], src[3], 0]) as usize;
+
+            if chunk_len > max_chunk_len() {
+                return Err(ReqRespError::InvalidData(format!(
+                    "Snappy chunk exceeds maximum: {chunk_len} > {}",
+                    max_chunk_len()
+                )));
+            }
+            if self.consumed + CHUNK_HEADER_LEN + chunk_len > self.max_encoded_len {
+                return Err(ReqRespError::InvalidData(format!(
+                    "Snappy stream exceeds maximum encoded length {} for {} bytes",
+                    self.max_encoded_len, self.expected_len
+                )));
+            }
+            if src.len() < CHUNK_HEADER_LEN + chunk_len {
+                return Ok(None);
+            }
+
+            let chunk = src.split_to(CHUNK_HEADER_LEN + chunk_len);
+            self.consumed += chunk.len();
+            self.decode_chunk(chunk_type, &chunk[CHUNK_HEADER_LEN..])?;
+        }
+
+        Ok(Some(std::mem::take(&mut self.output)))
+    }
+
+    fn decode_chunk(&mut self, chunk_type: u8, data: &[u8]) -> Result<(), ReqRespError> {
+        if chunk_type == CHUNK_TYPE_STREAM_IDENTIFIER {
+            if data != STREAM_IDENTIFIER {
+                return Err(ReqRespError::InvalidData(
+                    "Invalid snappy stream identifier".to_string(),
+                ));
+            }
+            self.seen_stream_identifier = true;
+            return Ok(());
+        }
+        if !self.seen_stream_identifier {
+            return Err(ReqRespError::InvalidData(
+                "Snappy stream does not start with a stream identifier".to_string(),
+            ));
+        }
+
+        match chunk_type {
+            CHUNK_TYPE_COMPRESSED_DATA | CHUNK_TYPE_UNCOMPRESSED_DATA => {
+                if data.len() < CHECKSUM_LEN {
+                    return Err(ReqRespError::InvalidData(
+                        "Snappy chunk is too short for its checksum".to_string(),
+                    ));
+                }
+                let (checksum, data) = data.split_at(CHECKSUM_LEN);
+                let checksum =
+                    u32::from_le_bytes([checksum[0], checksum[1], checksum[2], checksum[3]]);
+
+                let len = match chunk_type {
+                    CHUNK_TYPE_COMPRESSED_DATA => decompress_len(data).map_err(|err| {
+                        ReqRespError::InvalidData(format!("Invalid snappy chunk: {err}"))
+                    })?,
+                    _ => data.len(),
+                };
+                let remaining = self.expected_len - self.output.len();
+                if len > MAX_UNCOMPRESSED_CHUNK_LEN || len > remaining {
+                    return Err(ReqRespError::InvalidData(format!(
+                        "Snappy chunk of {len} bytes exceeds the {remaining} bytes left of the declared length"
+                    )));
+                }
+
+                let start = self.output.len();
+                match chunk_type {
+                    CHUNK_TYPE_COMPRESSED_DATA => {
+                        self.output.resize(start + len, 0);
+                        self.decoder
+                            .decompress(data, &mut self.output[start..])
+                            .map_err(|err| {
+                                ReqRespError::InvalidData(format!("Invalid snappy chunk: {err}"))
+                            })?;
+                    }
+                    _ => self.output.extend_from_slice(data),
+                }
+
+                if masked_checksum(&self.output[start..]) != checksum {
+                    return Err(ReqRespError::InvalidData(
+                        "Snappy chunk checksum mismatch".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+            CHUNK_TYPE_PADDING | 0x80..=0xfd => Ok(()),
+            _ => Err(ReqRespError::InvalidData(format!(
+                "Unskippable snappy chunk type {chunk_type:#04x}"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use snap::write::FrameEncoder;
+
+    use super::*;
+
+    fn stream_identifier() -> BytesMut {
+        let mut src = BytesMut::from(&[CHUNK_TYPE_STREAM_IDENTIFIER, 6, 0, 0][..]);
+        src.extend_from_slice(STREAM_IDENTIFIER);
+        src
+    }
+
+    fn encode(data: &[u8]) -> BytesMut {
+        let mut encoder = FrameEncoder::new(vec![]);
+        encoder.write_all(data).unwrap();
+        encoder.flush().unwrap();
+        BytesMut::from(&encoder.get_ref()[..])
+    }
+
+    #[test]
+    fn test_decode_incrementally() {
+        let data = (0..200_000u32).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        let encoded = encode(&data);
+
+        let mut decoder = SnappyFrameDecoder::new(data.len(), data.len()).unwrap();
+        let mut src = BytesMut::new();
+        let mut result = None;
+        for byte in encoded.iter() {
+            src.extend_from_slice(&[*byte]);
+            if let Some(decoded) = decoder.decode(&mut src).unwrap() {
+                result = Some(decoded);
+            }
+        }
+
+        assert_eq!(result, Some(data));
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn test_leaves_following_bytes() {
+        let mut src = encode(b"first");
+        src.extend_from_slice(&encode(b"second"));
+
+        let mut decoder = SnappyFrameDecoder::new(5, 5).unwrap();
+        assert_eq!(decoder.decode(&mut src).unwrap(), Some(b"first".to_vec()));
+        assert_eq!(src, encode(b"second"));
+    }
+
+    #[test]
+    fn test_rejects_declared_length_over_limit() {
+        assert!(SnappyFrameDecoder::new(1025, 1024).is_err());
+    }
+
+    #[test]
+    fn test_rejects_more_data_than_declared() {
+        let data = vec![0u8; 1000];
+        let mut decoder = SnappyFrameDecoder::new(100, 1000).unwrap();
+        assert!(decoder.decode(&mut encode(&data)).is_err());
+    }
+
+    #[test]
+    fn test_rejects_oversized_chunk_before_it_arrives() {
+        let mut src = stream_identifier();
+        src.extend_from_slice(&[CHUNK_TYPE_COMPRESSED_DATA, 0xff, 0xff, 0xff]);
+
+        let mut decoder = SnappyFrameDecoder::new(100, 100).unwrap();
+        assert!(decoder.decode(&mut src).is_err());
+    }
+
+    #[test]
+    fn test_rejects_padding_beyond_encoded_limit() {
+        let mut src = stream_identifier();
+        for _ in 0..10 {
+            src.extend_from_slice(&[CHUNK_TYPE_PADDING, 100, 0, 0]);
+            src.extend_from_slice(&[0; 100]);
+        }
+
+        let mut decoder = SnappyFrameDecoder::new(10, 10).unwrap();
+        assert!(decoder.decode(&mut src).is_err());
+    }
+
+    #[test]
+    fn test_rejects_corrupted_checksum() {
+        let mut src = encode(b"hello world");
+        let checksum_offset = CHUNK_HEADER_LEN + STREAM_IDENTIFIER.len() + CHUNK_HEADER_LEN;
+        src[checksum_offset] ^= 0xff;
+
+        let mut decoder = SnappyFrameDecoder::new(11, 11).unwrap();
+        assert!(decoder.decode(&mut src).is_err());
+    }
+}
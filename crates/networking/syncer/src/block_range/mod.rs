@@ -1,6 +1,6 @@
 mod block_cache;
 mod peer_manager;
-mod peer_range_downloader;
+pub mod peer_range_downloader;
 
 use std::{
     pin::Pin,
@@ -16,7 +16,10 @@ use futures::task::noop_waker;
 use libp2p::PeerId;
 use peer_manager::PeerManager;
 use peer_range_downloader::{PeerBlobIdentifierDownloader, PeerRootsDownloader};
-use ream_beacon_chain::beacon_chain::BeaconChain;
+use ream_beacon_chain::{
+    beacon_chain::BeaconChain,
+    node_metrics::{SyncBatch, SyncBatchKind},
+};
 use ream_consensus_beacon::{
     blob_sidecar::{BlobIdentifier, BlobSidecar},
     electra::beacon_block::SignedBeaconBlock,
@@ -126,6 +129,7 @@ impl BlockRangeSyncer {
                     block_cache.downloaded_blob_count(),
                     block_cache.blob_count(),
                 );
+                self.publish_sync_metrics(Some(data_to_fetch.to_string()), &task_handles).await;
 
                 match data_to_fetch {
                     DataToFetch::BlockRange(range) => {
@@ -228,10 +232,25 @@ impl BlockRangeSyncer {
             }
 
             info!("All blocks processed successfully.");
+            self.publish_sync_metrics(None, &[]).await;
 
             Ok(self)
         })
     }
+
+    /// Publishes the stage, the batches in flight and the peer contributions of the sync for the
+    /// monitoring API.
+    async fn publish_sync_metrics(&self, stage: Option<String>, tasks: &[DownloadTask]) {
+        let mut sync_metrics = self.beacon_chain.sync_metrics.lock().await;
+        sync_metrics.stage = stage;
+        sync_metrics.batches = tasks.iter().map(DownloadTask::sync_batch).collect();
+        sync_metrics.peer_contributions = self
+            .peer_manager
+            .contributions()
+            .iter()
+            .map(|(peer_id, contribution)| (peer_id.to_string(), *contribution))
+            .collect();
+    }
 }
 
 pub enum DownloadTask {
@@ -277,6 +296,33 @@ impl DownloadTask {
         }
     }
 
+    pub fn sync_batch(&self) -> SyncBatch {
+        match self {
+            DownloadTask::BlockRange { range, peer_id, .. } => SyncBatch {
+                kind: SyncBatchKind::BlockRange,
+                peer_id: peer_id.to_string(),
+                start_slot: Some(range.start_slot),
+                count: range.count,
+            },
+            DownloadTask::BlockRoots { roots, peer_id, .. } => SyncBatch {
+                kind: SyncBatchKind::BlockRoots,
+                peer_id: peer_id.to_string(),
+                start_slot: None,
+                count: roots.len() as u64,
+            },
+            DownloadTask::BlobIdentifiers {
+                blob_identifiers,
+                peer_id,
+                ..
+            } => SyncBatch {
+                kind: SyncBatchKind::BlobIdentifiers,
+                peer_id: peer_id.to_string(),
+                start_slot: None,
+                count: blob_identifiers.len() as u64,
+            },
+        }
+    }
+
     pub fn new_blob_identifiers(
         handle: JoinHandle<anyhow::Result<anyhow::Result<Vec<BlobSidecar>>>>,
         blob_identifiers: Vec<BlobIdentifier>,
@@ -329,6 +375,7 @@ fn poll_ready_tasks(
                             Ok(blocks) => blocks,
                             Err(err) => {
                                 block_cache.push_retry_range(*range);
+                                peer_manager.record_failure(peer_id);
                                 peer_manager
                                     .ban_peer(peer_id, format!("Failed to fetch blocks: {err:?}"));
                                 continue;
@@ -338,10 +385,12 @@ fn poll_ready_tasks(
                         if blocks.is_empty() {
                             warn!("Received empty block range from peer: {peer_id}");
                             block_cache.push_retry_range(*range);
+                            peer_manager.record_failure(peer_id);
                             peer_manager
                                 .ban_peer(peer_id, "Received empty block range".to_string());
                             continue;
                         }
+                        peer_manager.record_download(peer_id, blocks.len() as u64, 0);
 
                         if let Err(err) = block_cache.add_blocks(blocks, true) {
                             warn!("Failed to add downloaded blocks to cache: {err:?}");
@@ -379,6 +428,7 @@ fn poll_ready_tasks(
                             Ok(blocks) => blocks,
                             Err(err) => {
                                 warn!("Failed to fetch blocks from roots: {err:?}");
+                                peer_manager.record_failure(peer_id);
                                 peer_manager.ban_peer(
                                     peer_id,
                                     format!("Failed to fetch blocks from receipts: {err:?}"),
@@ -389,10 +439,12 @@ fn poll_ready_tasks(
 
                         if blocks.is_empty() {
                             warn!("Received empty block roots from peer: {peer_id}");
+                            peer_manager.record_failure(peer_id);
                             peer_manager
                                 .ban_peer(peer_id, "Received empty block roots".to_string());
                             continue;
                         }
+                        peer_manager.record_download(peer_id, blocks.len() as u64, 0);
 
                         if let Err(err) = block_cache.add_blocks(blocks, false) {
                             warn!("Failed to add downloaded blocks to cache: {err:?}");
@@ -429,6 +481,7 @@ fn poll_ready_tasks(
                             Ok(blob_sidecars) => blob_sidecars,
                             Err(err) => {
                                 warn!("Failed to fetch blobs from identifiers: {err:?}");
+                                peer_manager.record_failure(peer_id);
                                 peer_manager.ban_peer(
                                     peer_id,
                                     format!("Failed to fetch blobs from identifiers: {err:?}"),
@@ -439,10 +492,12 @@ fn poll_ready_tasks(
 
                         if blob_sidecars.is_empty() {
                             warn!("Received empty blob identifiers from peer: {peer_id}");
+                            peer_manager.record_failure(peer_id);
                             peer_manager
                                 .ban_peer(peer_id, "Received empty blob identifiers".to_string());
                             continue;
                         }
+                        peer_manager.record_download(peer_id, 0, blob_sidecars.len() as u64);
 
                         if let Err(err) = block_cache.add_blobs(blob_sidecars) {
                             warn!("Failed to add downloaded blobs to cache: {err:?}");
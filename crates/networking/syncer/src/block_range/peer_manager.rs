@@ -1,6 +1,7 @@
 use std::{collections::HashMap, sync::Arc, time::Instant};
 
 use libp2p::PeerId;
+use ream_beacon_chain::node_metrics::PeerSyncContribution;
 use ream_consensus_misc::constants::SLOTS_PER_EPOCH;
 use ream_p2p::{network_state::NetworkState, peer::CachedPeer};
 use tracing::warn;
@@ -22,6 +23,7 @@ pub struct PeerManager {
     peers: HashMap<PeerId, PeerInfo>,
     banned_peers: HashMap<PeerId, Instant>,
     ban_reasons: HashMap<PeerId, String>,
+    contributions: HashMap<PeerId, PeerSyncContribution>,
 }
 
 impl PeerManager {
@@ -31,6 +33,7 @@ impl PeerManager {
             peers: HashMap::new(),
             banned_peers: HashMap::new(),
             ban_reasons: HashMap::new(),
+            contributions: HashMap::new(),
         }
     }
 
@@ -98,6 +101,25 @@ impl PeerManager {
         }
     }
 
+    /// Records the blocks and blob sidecars a peer served for a request.
+    pub fn record_download(&mut self, peer_id: &PeerId, blocks: u64, blob_sidecars: u64) {
+        let contribution = self.contributions.entry(*peer_id).or_default();
+        contribution.blocks += blocks;
+        contribution.blob_sidecars += blob_sidecars;
+    }
+
+    /// Records a request the peer failed to serve.
+    pub fn record_failure(&mut self, peer_id: &PeerId) {
+        self.contributions
+            .entry(*peer_id)
+            .or_default()
+            .failed_requests += 1;
+    }
+
+    pub fn contributions(&self) -> &HashMap<PeerId, PeerSyncContribution> {
+        &self.contributions
+    }
+
     pub fn finalized_slot(&self) -> Option<u64> {
         let mut frequencies = HashMap::new();
 
@@ -1,9 +1,47 @@
-use std::net::{IpAddr, SocketAddr};
+use std::{
+    fmt,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use ream_beacon_chain::node_metrics::QueueDumpSender;
+
+type ReloadLogFilter = dyn Fn(&str) -> Result<(), String> + Send + Sync;
+
+/// Replaces the log filter of the node with the given directives, e.g. `info,ream_p2p=debug`.
+#[derive(Clone)]
+pub struct LogFilterHandle(Arc<ReloadLogFilter>);
+
+impl LogFilterHandle {
+    pub fn new(reload: impl Fn(&str) -> Result<(), String> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(reload))
+    }
+
+    pub fn reload(&self, filter: &str) -> Result<(), String> {
+        (self.0)(filter)
+    }
+}
+
+impl fmt::Debug for LogFilterHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogFilterHandle").finish_non_exhaustive()
+    }
+}
+
+/// The admin endpoints are only served when this is configured, and only to the requests which
+/// carry `token` as a bearer token.
+#[derive(Debug, Clone)]
+pub struct AdminConfig {
+    pub token: String,
+    pub log_filter: LogFilterHandle,
+    pub queue_dump_sender: QueueDumpSender,
+}
 
 #[derive(Debug, Clone)]
 pub struct RpcServerConfig {
     pub http_socket_address: SocketAddr,
     pub http_allow_origin: bool,
+    pub admin: Option<AdminConfig>,
 }
 
 impl RpcServerConfig {
@@ -12,6 +50,12 @@ impl RpcServerConfig {
         Self {
             http_socket_address: SocketAddr::new(http_address, http_port),
             http_allow_origin,
+            admin: None,
         }
     }
+
+    pub fn with_admin(mut self, admin: AdminConfig) -> Self {
+        self.admin = Some(admin);
+        self
+    }
 }
@@ -0,0 +1,242 @@
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use actix_web::{
+    HttpRequest, HttpResponse, Responder, get,
+    http::header::AUTHORIZATION,
+    post, put,
+    web::{Data, Json, Path, Query},
+};
+use alloy_primitives::B256;
+use libp2p::PeerId;
+use ream_beacon_api_types::{error::ApiError, responses::DataResponse};
+use ream_beacon_chain::beacon_chain::BeaconChain;
+use ream_p2p::channel::P2PMessage;
+use ream_storage::cache::CachedDB;
+use serde::{Deserialize, Serialize};
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::timeout,
+};
+use tracing::info;
+
+use crate::config::AdminConfig;
+
+/// How long the network manager is given to answer a queue dump request, it answers between two
+/// events so this only elapses when it is stuck.
+const QUEUE_DUMP_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Deserialize)]
+pub struct LogFilterRequest {
+    pub filter: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DisconnectPeerQuery {
+    #[serde(default)]
+    pub ban: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HeadResponse {
+    pub head_root: B256,
+}
+
+/// Checks that the admin endpoints are enabled and that the request carries the admin token.
+fn authorize(
+    request: &HttpRequest,
+    admin_config: Option<&Data<AdminConfig>>,
+) -> Result<Data<AdminConfig>, ApiError> {
+    let admin_config = admin_config
+        .ok_or_else(|| ApiError::NotFound("Admin endpoints are disabled".to_string()))?;
+    let token = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(ApiError::Unauthorized)?;
+    if !constant_time_eq(token.as_bytes(), admin_config.token.as_bytes()) {
+        return Err(ApiError::Unauthorized);
+    }
+    Ok(admin_config.clone())
+}
+
+/// Compares the tokens without returning early on the first differing byte, so that the response
+/// time does not leak how much of the token was guessed.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Called by `/ream/v1/admin/log_filter` to replace the log filter of the node, using the
+/// `RUST_LOG` directive syntax.
+#[put("/admin/log_filter")]
+pub async fn put_log_filter(
+    request: HttpRequest,
+    admin_config: Option<Data<AdminConfig>>,
+    log_filter_request: Json<LogFilterRequest>,
+) -> Result<impl Responder, ApiError> {
+    let admin_config = authorize(&request, admin_config.as_ref())?;
+    admin_config
+        .log_filter
+        .reload(&log_filter_request.filter)
+        .map_err(|err| ApiError::BadRequest(format!("Invalid log filter: {err}")))?;
+    info!("Log filter set to {}", log_filter_request.filter);
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Called by `/ream/v1/admin/peers/{peer_id}/disconnect` to disconnect a peer. With `ban=true`
+/// the peer is also refused any further connection until the node restarts.
+#[post("/admin/peers/{peer_id}/disconnect")]
+pub async fn post_disconnect_peer(
+    request: HttpRequest,
+    admin_config: Option<Data<AdminConfig>>,
+    p2p_sender: Data<mpsc::UnboundedSender<P2PMessage>>,
+    peer_id: Path<String>,
+    query: Query<DisconnectPeerQuery>,
+) -> Result<impl Responder, ApiError> {
+    authorize(&request, admin_config.as_ref())?;
+    let peer_id = peer_id.into_inner();
+    let peer_id = PeerId::from_str(&peer_id).map_err(|err| {
+        ApiError::BadRequest(format!("Invalid PeerId format: {peer_id}, {err:?}"))
+    })?;
+
+    p2p_sender
+        .send(P2PMessage::DisconnectPeer {
+            peer_id,
+            ban: query.ban,
+        })
+        .map_err(|err| {
+            ApiError::InternalError(format!("Failed to send disconnect request: {err}"))
+        })?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Called by `/ream/v1/admin/caches/clear` to empty the caches of the messages already seen.
+#[post("/admin/caches/clear")]
+pub async fn post_clear_caches(
+    request: HttpRequest,
+    admin_config: Option<Data<AdminConfig>>,
+    cached_db: Data<Arc<CachedDB>>,
+) -> Result<impl Responder, ApiError> {
+    authorize(&request, admin_config.as_ref())?;
+    cached_db.clear().await;
+    info!("Caches cleared");
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Called by `/ream/v1/admin/fork_choice/recompute_head` to run fork choice immediately rather
+/// than at the next tick or block.
+#[post("/admin/fork_choice/recompute_head")]
+pub async fn post_recompute_head(
+    request: HttpRequest,
+    admin_config: Option<Data<AdminConfig>>,
+    beacon_chain: Data<Arc<BeaconChain>>,
+) -> Result<impl Responder, ApiError> {
+    authorize(&request, admin_config.as_ref())?;
+    let head_root = beacon_chain
+        .recompute_head()
+        .await
+        .map_err(|err| ApiError::InternalError(format!("Failed to recompute head: {err:?}")))?;
+
+    Ok(HttpResponse::Ok().json(DataResponse::new(HeadResponse { head_root })))
+}
+
+/// Called by `/ream/v1/admin/queues` to get the contents of the queues of the network manager.
+#[get("/admin/queues")]
+pub async fn get_queue_dump(
+    request: HttpRequest,
+    admin_config: Option<Data<AdminConfig>>,
+) -> Result<impl Responder, ApiError> {
+    let admin_config = authorize(&request, admin_config.as_ref())?;
+    let (reply_sender, reply_receiver) = oneshot::channel();
+    admin_config
+        .queue_dump_sender
+        .send(reply_sender)
+        .map_err(|_| ApiError::InternalError("Network manager is not running".to_string()))?;
+    let queue_dump = timeout(QUEUE_DUMP_TIMEOUT, reply_receiver)
+        .await
+        .map_err(|_| ApiError::InternalError("Timed out waiting for the queues".to_string()))?
+        .map_err(|_| ApiError::InternalError("Network manager dropped the request".to_string()))?;
+
+    Ok(HttpResponse::Ok().json(DataResponse::new(queue_dump)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use actix_web::{App, http::StatusCode, test};
+
+    use super::*;
+    use crate::config::LogFilterHandle;
+
+    const TOKEN: &str = "secret";
+
+    /// Returns an admin config whose log filter reloads record the filter they were given.
+    fn admin_config() -> (AdminConfig, Arc<Mutex<Option<String>>>) {
+        let reloaded_filter = Arc::new(Mutex::new(None));
+        let log_filter = LogFilterHandle::new({
+            let reloaded_filter = reloaded_filter.clone();
+            move |filter| {
+                *reloaded_filter.lock().expect("Lock is not poisoned") = Some(filter.to_string());
+                Ok(())
+            }
+        });
+        let admin_config = AdminConfig {
+            token: TOKEN.to_string(),
+            log_filter,
+            queue_dump_sender: mpsc::unbounded_channel().0,
+        };
+        (admin_config, reloaded_filter)
+    }
+
+    async fn put_log_filter_status(authorization: Option<&str>) -> (StatusCode, Option<String>) {
+        let (admin_config, reloaded_filter) = admin_config();
+        let app = test::init_service(
+            App::new()
+                .app_data(Data::new(admin_config))
+                .service(put_log_filter),
+        )
+        .await;
+        let mut request = test::TestRequest::put()
+            .uri("/admin/log_filter")
+            .set_json(serde_json::json!({ "filter": "debug" }));
+        if let Some(authorization) = authorization {
+            request = request.insert_header((AUTHORIZATION, authorization));
+        }
+        let status = test::call_service(&app, request.to_request())
+            .await
+            .status();
+        let reloaded_filter = reloaded_filter
+            .lock()
+            .expect("Lock is not poisoned")
+            .clone();
+        (status, reloaded_filter)
+    }
+
+    #[actix_web::test]
+    async fn test_put_log_filter_rejects_missing_token() {
+        assert_eq!(
+            put_log_filter_status(None).await,
+            (StatusCode::UNAUTHORIZED, None)
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_put_log_filter_rejects_wrong_token() {
+        assert_eq!(
+            put_log_filter_status(Some("Bearer wrong")).await,
+            (StatusCode::UNAUTHORIZED, None)
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_put_log_filter_reloads_filter() {
+        assert_eq!(
+            put_log_filter_status(Some(&format!("Bearer {TOKEN}"))).await,
+            (StatusCode::OK, Some("debug".to_string()))
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use actix_web::{
     HttpResponse, Responder, get,
     web::{Data, Path},
@@ -6,7 +8,9 @@ use actix_web_lab::extract::Query;
 use ream_beacon_api_types::{
     error::ApiError, id::ID, query::BlobSidecarQuery, responses::BeaconVersionedResponse,
 };
+use ream_beacon_chain::beacon_chain::BeaconChain;
 use ream_consensus_beacon::blob_sidecar::BlobIdentifier;
+use ream_execution_engine::ExecutionEngine;
 use ream_storage::{db::ReamDB, tables::Table};
 use tree_hash::TreeHash;
 
@@ -15,10 +19,14 @@ use crate::handlers::block::get_beacon_block_from_id;
 #[get("/beacon/blob_sidecars/{block_id}")]
 pub async fn get_blob_sidecars(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
+    execution_engine: Data<Option<ExecutionEngine>>,
     block_id: Path<ID>,
     query: Query<BlobSidecarQuery>,
 ) -> Result<impl Responder, ApiError> {
-    let beacon_block = get_beacon_block_from_id(block_id.into_inner(), &db).await?;
+    let beacon_block =
+        get_beacon_block_from_id(block_id.into_inner(), &db, &beacon_chain, &execution_engine)
+            .await?;
     let block_root = beacon_block.message.tree_hash_root();
 
     let indices = if let Some(indices) = &query.indices {
@@ -1,30 +1,53 @@
+use std::sync::Arc;
+
 use actix_web::{
-    HttpRequest, HttpResponse, Responder, get, post,
-    web::{Data, Json, Path},
+    HttpRequest, HttpResponse, Responder, get,
+    http::header,
+    post,
+    web::{Bytes, Data, Json, Path, Query},
 };
 use alloy_primitives::B256;
 use ream_beacon_api_types::{
+    block::{BroadcastValidation, SignedBlockContents},
     error::ApiError,
     id::{ID, ValidatorID},
+    query::BroadcastValidationQuery,
     responses::{
         BeaconResponse, BeaconVersionedResponse, DataResponse, RootResponse, SSZ_CONTENT_TYPE,
     },
 };
+use ream_beacon_chain::{beacon_chain::BeaconChain, block_reconstruction::get_beacon_block};
 use ream_consensus_beacon::{
-    electra::{beacon_block::SignedBeaconBlock, beacon_state::BeaconState},
+    blob_sidecar::{BlobIdentifier, BlobSidecar},
+    electra::{
+        beacon_block::SignedBeaconBlock, beacon_state::BeaconState,
+        blinded_beacon_block::SignedBlindedBeaconBlock,
+    },
     genesis::Genesis,
 };
-use ream_consensus_misc::constants::{WHISTLEBLOWER_REWARD_QUOTIENT, genesis_validators_root};
+use ream_consensus_misc::{
+    constants::{WHISTLEBLOWER_REWARD_QUOTIENT, genesis_validators_root},
+    misc::compute_start_slot_at_epoch,
+};
+use ream_execution_engine::ExecutionEngine;
 use ream_network_spec::networks::beacon_network_spec;
+use ream_p2p::{
+    channel::{GossipMessage, P2PMessage},
+    gossipsub::topics::{GossipTopic, GossipTopicKind},
+};
 use ream_storage::{
+    cache::CachedDB,
     db::ReamDB,
     tables::{Field, Table},
 };
+use ream_validator_beacon::blob_sidecars::compute_subnet_for_blob_sidecar;
 use serde::{Deserialize, Serialize};
-use ssz::Encode;
-use tracing::error;
+use ssz::{Decode, Encode};
+use tokio::sync::mpsc;
+use tracing::{error, warn};
+use tree_hash::TreeHash;
 
-use crate::handlers::state::get_state_from_id;
+use crate::handlers::state::{get_canonical_head, get_state_from_id};
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct BlockRewards {
@@ -50,8 +73,13 @@ pub struct ValidatorSyncCommitteeReward {
     pub reward: u64,
 }
 
-pub async fn get_block_root_from_id(block_id: ID, db: &ReamDB) -> Result<B256, ApiError> {
+pub async fn get_block_root_from_id(
+    block_id: ID,
+    db: &ReamDB,
+    beacon_chain: &BeaconChain,
+) -> Result<B256, ApiError> {
     let block_root = match block_id {
+        ID::Head => Ok(Some(get_canonical_head(beacon_chain)?.head_block_root)),
         ID::Finalized => {
             let finalized_checkpoint = db.finalized_checkpoint_provider().get().map_err(|err| {
                 ApiError::InternalError(format!(
@@ -70,7 +98,7 @@ pub async fn get_block_root_from_id(block_id: ID, db: &ReamDB) -> Result<B256, A
 
             Ok(Some(justified_checkpoint.root))
         }
-        ID::Head | ID::Genesis => {
+        ID::Genesis => {
             return Err(ApiError::NotFound(format!(
                 "This ID type is currently not supported: {block_id:?}"
             )));
@@ -142,11 +170,13 @@ fn get_attester_slashing_rewards(
 pub async fn get_beacon_block_from_id(
     block_id: ID,
     db: &ReamDB,
+    beacon_chain: &BeaconChain,
+    execution_engine: &Option<ExecutionEngine>,
 ) -> Result<SignedBeaconBlock, ApiError> {
-    let block_root = get_block_root_from_id(block_id, db).await?;
+    let block_root = get_block_root_from_id(block_id, db, beacon_chain).await?;
 
-    db.beacon_block_provider()
-        .get(block_root)
+    get_beacon_block(db, execution_engine.as_ref(), block_root)
+        .await
         .map_err(|err| {
             ApiError::InternalError(format!("Failed to get block by block_root, error: {err:?}"))
         })?
@@ -155,11 +185,41 @@ pub async fn get_beacon_block_from_id(
         })
 }
 
+/// Returns the blinded form of a block, which does not need the execution payload of a block
+/// stored blinded to be fetched back.
+pub async fn get_blinded_beacon_block_from_id(
+    block_id: ID,
+    db: &ReamDB,
+    beacon_chain: &BeaconChain,
+) -> Result<SignedBlindedBeaconBlock, ApiError> {
+    let block_root = get_block_root_from_id(block_id, db, beacon_chain).await?;
+    let map_err =
+        |err| ApiError::InternalError(format!("Failed to get block by block_root, error: {err:?}"));
+
+    if let Some(block) = db
+        .beacon_block_provider()
+        .get(block_root)
+        .map_err(map_err)?
+    {
+        return Ok(block.as_signed_blinded_beacon_block());
+    }
+    db.blinded_beacon_block_provider()
+        .get(block_root)
+        .map_err(map_err)?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!("Failed to find `beacon block` from {block_root:?}"))
+        })
+}
+
 /// Called by `/genesis` to get the Genesis Config of Beacon Chain.
 #[get("/beacon/genesis")]
-pub async fn get_genesis() -> Result<impl Responder, ApiError> {
+pub async fn get_genesis(db: Data<ReamDB>) -> Result<impl Responder, ApiError> {
+    let genesis_time = db.genesis_time_provider().get().map_err(|err| {
+        ApiError::InternalError(format!("Failed to get genesis time, error: {err:?}"))
+    })?;
+
     Ok(HttpResponse::Ok().json(DataResponse::new(Genesis {
-        genesis_time: beacon_network_spec().min_genesis_time,
+        genesis_time,
         genesis_validators_root: genesis_validators_root(),
         genesis_fork_version: beacon_network_spec().genesis_fork_version,
     })))
@@ -169,9 +229,13 @@ pub async fn get_genesis() -> Result<impl Responder, ApiError> {
 #[get("/beacon/blocks/{block_id}/attestations")]
 pub async fn get_block_attestations(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
+    execution_engine: Data<Option<ExecutionEngine>>,
     block_id: Path<ID>,
 ) -> Result<impl Responder, ApiError> {
-    let beacon_block = get_beacon_block_from_id(block_id.into_inner(), &db).await?;
+    let beacon_block =
+        get_beacon_block_from_id(block_id.into_inner(), &db, &beacon_chain, &execution_engine)
+            .await?;
 
     Ok(HttpResponse::Ok().json(BeaconVersionedResponse::new(
         beacon_block.message.body.attestations,
@@ -182,9 +246,10 @@ pub async fn get_block_attestations(
 #[get("/beacon/blocks/{block_id}/root")]
 pub async fn get_block_root(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
     block_id: Path<ID>,
 ) -> Result<impl Responder, ApiError> {
-    let block_root = get_block_root_from_id(block_id.into_inner(), &db).await?;
+    let block_root = get_block_root_from_id(block_id.into_inner(), &db, &beacon_chain).await?;
 
     Ok(HttpResponse::Ok().json(BeaconResponse::new(RootResponse::new(block_root))))
 }
@@ -193,11 +258,19 @@ pub async fn get_block_root(
 #[get("/beacon/blocks/{block_id}/rewards")]
 pub async fn get_block_rewards(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
+    execution_engine: Data<Option<ExecutionEngine>>,
     block_id: Path<ID>,
 ) -> Result<impl Responder, ApiError> {
     let block_id_value = block_id.into_inner();
-    let beacon_block = get_beacon_block_from_id(block_id_value.clone(), &db).await?;
-    let beacon_state = get_state_from_id(block_id_value.clone(), &db).await?;
+    let beacon_block = get_beacon_block_from_id(
+        block_id_value.clone(),
+        &db,
+        &beacon_chain,
+        &execution_engine,
+    )
+    .await?;
+    let beacon_state = get_state_from_id(block_id_value.clone(), &db, &beacon_chain).await?;
 
     let attestation_reward = get_attestations_rewards(&beacon_state, &beacon_block);
     let attester_slashing_reward = get_attester_slashing_rewards(&beacon_state, &beacon_block);
@@ -233,9 +306,13 @@ pub async fn get_block_rewards(
 #[get("/beacon/blocks/{block_id}")]
 pub async fn get_block_from_id(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
+    execution_engine: Data<Option<ExecutionEngine>>,
     block_id: Path<ID>,
 ) -> Result<impl Responder, ApiError> {
-    let beacon_block = get_beacon_block_from_id(block_id.into_inner(), &db).await?;
+    let beacon_block =
+        get_beacon_block_from_id(block_id.into_inner(), &db, &beacon_chain, &execution_engine)
+            .await?;
 
     Ok(HttpResponse::Ok().json(BeaconVersionedResponse::new(beacon_block)))
 }
@@ -243,12 +320,20 @@ pub async fn get_block_from_id(
 #[post("/beacon/rewards/sync_committee/{block_id}")]
 pub async fn post_sync_committee_rewards(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
+    execution_engine: Data<Option<ExecutionEngine>>,
     block_id: Path<ID>,
     validators: Json<Vec<ValidatorID>>,
 ) -> Result<impl Responder, ApiError> {
     let block_id_value = block_id.into_inner();
-    let beacon_block = get_beacon_block_from_id(block_id_value.clone(), &db).await?;
-    let beacon_state = get_state_from_id(block_id_value.clone(), &db).await?;
+    let beacon_block = get_beacon_block_from_id(
+        block_id_value.clone(),
+        &db,
+        &beacon_chain,
+        &execution_engine,
+    )
+    .await?;
+    let beacon_state = get_state_from_id(block_id_value.clone(), &db, &beacon_chain).await?;
 
     let sync_committee_rewards_map =
         match beacon_state.compute_sync_committee_rewards(&beacon_block) {
@@ -298,10 +383,11 @@ pub async fn post_sync_committee_rewards(
 pub async fn get_blind_block(
     http_request: HttpRequest,
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
     block_id: Path<ID>,
 ) -> Result<impl Responder, ApiError> {
-    let beacon_block = get_beacon_block_from_id(block_id.into_inner(), &db).await?;
-    let blinded_beacon_block = beacon_block.as_signed_blinded_beacon_block();
+    let blinded_beacon_block =
+        get_blinded_beacon_block_from_id(block_id.into_inner(), &db, &beacon_chain).await?;
     match http_request
         .headers()
         .get(SSZ_CONTENT_TYPE)
@@ -313,3 +399,229 @@ pub async fn get_blind_block(
         _ => Ok(HttpResponse::Ok().json(BeaconVersionedResponse::new(blinded_beacon_block))),
     }
 }
+
+/// POST /eth/v2/beacon/blocks
+///
+/// Broadcasts a signed block, followed by the blob sidecars built from its blobs, once it passed
+/// the checks of the requested `broadcast_validation` level, then imports it. Responds with 202 if
+/// the block was broadcast but failed to be imported.
+#[post("/beacon/blocks")]
+pub async fn post_block(
+    http_request: HttpRequest,
+    body: Bytes,
+    query: Query<BroadcastValidationQuery>,
+    beacon_chain: Data<Arc<BeaconChain>>,
+    cached_db: Data<Arc<CachedDB>>,
+    p2p_sender: Data<mpsc::UnboundedSender<P2PMessage>>,
+) -> Result<impl Responder, ApiError> {
+    let SignedBlockContents {
+        signed_block,
+        kzg_proofs,
+        blobs,
+    } = match http_request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|header| header.to_str().ok())
+    {
+        Some(SSZ_CONTENT_TYPE) => SignedBlockContents::from_ssz_bytes(&body)
+            .map_err(|err| ApiError::BadRequest(format!("Failed to decode block: {err:?}")))?,
+        _ => serde_json::from_slice(&body)
+            .map_err(|err| ApiError::BadRequest(format!("Failed to decode block: {err}")))?,
+    };
+    let slot = signed_block.message.slot;
+    let proposer_index = signed_block.message.proposer_index;
+
+    verify_block_for_gossip(&beacon_chain, &signed_block).await?;
+
+    let commitment_count = signed_block.message.body.blob_kzg_commitments.len();
+    if blobs.len() != commitment_count || kzg_proofs.len() != commitment_count {
+        return Err(ApiError::BadRequest(format!(
+            "Block has {commitment_count} blob commitments but {} blobs and {} proofs were provided",
+            blobs.len(),
+            kzg_proofs.len()
+        )));
+    }
+    let blob_sidecars = signed_block
+        .get_blob_sidecars(blobs, kzg_proofs)
+        .map_err(|err| ApiError::BadRequest(format!("Failed to build blob sidecars: {err}")))?;
+
+    if !matches!(query.broadcast_validation, BroadcastValidation::Gossip) {
+        beacon_chain
+            .verify_block_consensus(&signed_block)
+            .await
+            .map_err(|err| {
+                ApiError::BadRequest(format!("Block failed consensus validation: {err}"))
+            })?;
+    }
+
+    {
+        // The equivocation check and the recording of the header must not be interleaved with
+        // another publication from the same proposer
+        let mut seen_block_headers = cached_db.seen_block_headers.write().await;
+        let signed_header = signed_block.signed_header();
+        if matches!(
+            query.broadcast_validation,
+            BroadcastValidation::ConsensusAndEquivocation
+        ) {
+            let seen_equivocation = seen_block_headers
+                .peek(&(slot, proposer_index))
+                .is_some_and(|seen_header| seen_header.message != signed_header.message);
+            if seen_equivocation || is_stored_equivocation(&beacon_chain, &signed_block).await? {
+                return Err(ApiError::Conflict(format!(
+                    "Proposer {proposer_index} already proposed another block at slot {slot}"
+                )));
+            }
+        }
+
+        if !seen_block_headers.contains(&(slot, proposer_index)) {
+            seen_block_headers.put((slot, proposer_index), signed_header);
+        }
+    }
+
+    p2p_sender
+        .send(P2PMessage::Gossip(GossipMessage {
+            topic: GossipTopic {
+                fork: beacon_network_spec().fork_digest(genesis_validators_root()),
+                kind: GossipTopicKind::BeaconBlock,
+            },
+            data: signed_block.as_ssz_bytes(),
+        }))
+        .map_err(|err| ApiError::InternalError(format!("Failed to publish block: {err}")))?;
+    publish_blob_sidecars(&beacon_chain, &cached_db, &p2p_sender, blob_sidecars).await?;
+
+    if let Err(err) = beacon_chain.process_block(signed_block).await {
+        warn!("Published block at slot {slot} failed to be imported: {err}");
+        return Ok(HttpResponse::Accepted().finish());
+    }
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Gossips the blob sidecars of a published block right after the block, and stores them so that
+/// the import of the block finds its blobs available. The sidecars are marked as seen so that
+/// they are ignored when they come back over gossip.
+async fn publish_blob_sidecars(
+    beacon_chain: &BeaconChain,
+    cached_db: &CachedDB,
+    p2p_sender: &mpsc::UnboundedSender<P2PMessage>,
+    blob_sidecars: Vec<BlobSidecar>,
+) -> Result<(), ApiError> {
+    let store = beacon_chain.store.lock().await;
+    let mut seen_blob_sidecars = cached_db.seen_blob_sidecars.write().await;
+    for blob_sidecar in blob_sidecars {
+        let header = &blob_sidecar.signed_block_header.message;
+        seen_blob_sidecars.put((header.slot, header.proposer_index, blob_sidecar.index), ());
+        p2p_sender
+            .send(P2PMessage::Gossip(GossipMessage {
+                topic: GossipTopic {
+                    fork: beacon_network_spec().fork_digest(genesis_validators_root()),
+                    kind: GossipTopicKind::BlobSidecar(compute_subnet_for_blob_sidecar(
+                        blob_sidecar.index,
+                    )),
+                },
+                data: blob_sidecar.as_ssz_bytes(),
+            }))
+            .map_err(|err| {
+                ApiError::InternalError(format!("Failed to publish blob sidecar: {err}"))
+            })?;
+        store
+            .db
+            .blobs_and_proofs_provider()
+            .insert(
+                BlobIdentifier::new(header.tree_hash_root(), blob_sidecar.index),
+                blob_sidecar.into(),
+            )
+            .map_err(|err| {
+                ApiError::InternalError(format!("Failed to store blob sidecar: {err:?}"))
+            })?;
+    }
+    Ok(())
+}
+
+/// The lightweight checks applied to a block before it is broadcast, whatever the requested
+/// validation level.
+async fn verify_block_for_gossip(
+    beacon_chain: &BeaconChain,
+    signed_block: &SignedBeaconBlock,
+) -> Result<(), ApiError> {
+    let store = beacon_chain.store.lock().await;
+    let current_slot = store.get_current_slot().map_err(|err| {
+        ApiError::InternalError(format!("Failed to get current slot, error: {err:?}"))
+    })?;
+    if signed_block.message.slot > current_slot {
+        return Err(ApiError::BadRequest(format!(
+            "Block slot {} is ahead of the current slot {current_slot}",
+            signed_block.message.slot
+        )));
+    }
+
+    let finalized_checkpoint = store
+        .db
+        .finalized_checkpoint_provider()
+        .get()
+        .map_err(|err| {
+            ApiError::InternalError(format!(
+                "Failed to get finalized checkpoint, error: {err:?}"
+            ))
+        })?;
+    if signed_block.message.slot <= compute_start_slot_at_epoch(finalized_checkpoint.epoch) {
+        return Err(ApiError::BadRequest(format!(
+            "Block slot {} is not later than the finalized slot",
+            signed_block.message.slot
+        )));
+    }
+
+    let Some(parent_state) = store
+        .db
+        .beacon_state_provider()
+        .get(signed_block.message.parent_root)
+        .map_err(|err| ApiError::InternalError(format!("Failed to get parent state: {err:?}")))?
+    else {
+        return Err(ApiError::BadRequest(format!(
+            "Parent block {} is unknown",
+            signed_block.message.parent_root
+        )));
+    };
+
+    // The header of the block is recorded for equivocation detection, so it must be signed by
+    // its proposer
+    if !parent_state
+        .verify_block_header_signature(&signed_block.signed_header())
+        .map_err(|err| ApiError::BadRequest(format!("Invalid proposer signature: {err}")))?
+    {
+        return Err(ApiError::BadRequest(
+            "Invalid proposer signature".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Returns whether a different block from the same proposer was already imported at the slot of
+/// `signed_block`.
+async fn is_stored_equivocation(
+    beacon_chain: &BeaconChain,
+    signed_block: &SignedBeaconBlock,
+) -> Result<bool, ApiError> {
+    let store = beacon_chain.store.lock().await;
+    let Some(block_root) = store
+        .db
+        .slot_index_provider()
+        .get(signed_block.message.slot)
+        .map_err(|err| ApiError::InternalError(format!("Failed to get block root: {err:?}")))?
+    else {
+        return Ok(false);
+    };
+    if block_root == signed_block.message.block_root() {
+        return Ok(false);
+    }
+
+    Ok(get_beacon_block(
+        &store.db,
+        beacon_chain.execution_engine.as_ref(),
+        block_root,
+    )
+    .await
+    .map_err(|err| ApiError::InternalError(format!("Failed to get block: {err:?}")))?
+    .is_some_and(|block| block.message.proposer_index == signed_block.message.proposer_index))
+}
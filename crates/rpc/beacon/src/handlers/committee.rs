@@ -1,4 +1,4 @@
-use std::vec;
+use std::{sync::Arc, vec};
 
 use actix_web::{
     HttpResponse, Responder, get,
@@ -10,6 +10,7 @@ use ream_beacon_api_types::{
     query::{EpochQuery, IndexQuery, SlotQuery},
     responses::BeaconResponse,
 };
+use ream_beacon_chain::beacon_chain::BeaconChain;
 use ream_consensus_misc::{constants::SLOTS_PER_EPOCH, misc::compute_start_slot_at_epoch};
 use ream_storage::db::ReamDB;
 use serde::Serialize;
@@ -45,8 +46,9 @@ pub async fn get_committees(
     index: Query<IndexQuery>,
     slot: Query<SlotQuery>,
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
 ) -> Result<impl Responder, ApiError> {
-    let state = get_state_from_id(state_id.into_inner(), &db).await?;
+    let state = get_state_from_id(state_id.into_inner(), &db, &beacon_chain).await?;
     let epoch = epoch.epoch.unwrap_or(state.get_current_epoch());
     let committees_per_slot = state.get_committee_count_per_slot(epoch);
 
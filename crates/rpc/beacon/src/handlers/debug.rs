@@ -13,6 +13,7 @@ use ream_beacon_api_types::{
         ForkChoiceValidity,
     },
 };
+use ream_beacon_chain::beacon_chain::BeaconChain;
 use ream_fork_choice::store::{BlockWithEpochInfo, Store};
 use ream_operation_pool::OperationPool;
 use ream_storage::{db::ReamDB, tables::Field};
@@ -23,10 +24,11 @@ use crate::handlers::state::get_state_from_id;
 #[get("/debug/beacon/states/{state_id}")]
 pub async fn get_debug_beacon_state(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
     state_id: Path<ID>,
 ) -> Result<impl Responder, ApiError> {
     Ok(HttpResponse::Ok().json(BeaconResponse::new(
-        get_state_from_id(state_id.into_inner(), &db).await?,
+        get_state_from_id(state_id.into_inner(), &db, &beacon_chain).await?,
     )))
 }
 
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use actix_web::{
     HttpResponse, Responder, get, post,
     web::{Data, Json, Path},
@@ -8,29 +10,45 @@ use ream_beacon_api_types::{
     id::ID,
     responses::DutiesResponse,
 };
-use ream_consensus_misc::{constants::SLOTS_PER_EPOCH, misc::compute_start_slot_at_epoch};
-use ream_storage::db::ReamDB;
+use ream_beacon_chain::beacon_chain::BeaconChain;
+use ream_consensus_misc::misc::compute_start_slot_at_epoch;
+use ream_storage::{cache::CachedDB, db::ReamDB};
 
-use crate::handlers::state::get_state_from_id;
+use crate::handlers::{block::get_block_root_from_id, state::get_state_from_id};
 
 #[get("/validator/duties/proposer/{epoch}")]
 pub async fn get_proposer_duties(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
+    cached_db: Data<Arc<CachedDB>>,
     epoch: Path<u64>,
 ) -> Result<impl Responder, ApiError> {
     let epoch = epoch.into_inner();
-    let state = get_state_from_id(ID::Slot(compute_start_slot_at_epoch(epoch)), &db).await?;
+    let state = get_state_from_id(
+        ID::Slot(compute_start_slot_at_epoch(epoch)),
+        &db,
+        &beacon_chain,
+    )
+    .await?;
+    // The state is the post-state of the block at the start slot
+    let block_root = get_block_root_from_id(
+        ID::Slot(compute_start_slot_at_epoch(epoch)),
+        &db,
+        &beacon_chain,
+    )
+    .await?;
     let dependent_root = state
         .get_block_root_at_slot(compute_start_slot_at_epoch(epoch) - 1)
         .map_err(|err| ApiError::BadRequest(format!("Failed to get dependent root {err:?}")))?;
 
-    let start_slot = compute_start_slot_at_epoch(epoch);
-    let end_slot = start_slot + SLOTS_PER_EPOCH;
+    let proposer_indices = cached_db
+        .get_proposer_indices(&state, block_root, epoch)
+        .await
+        .map_err(|err| ApiError::BadRequest(err.to_string()))?;
     let mut duties = vec![];
-    for slot in start_slot..end_slot {
-        let validator_index = state
-            .get_beacon_proposer_index(Some(slot))
-            .map_err(|err| ApiError::BadRequest(err.to_string()))?;
+    for (slot, &validator_index) in
+        (compute_start_slot_at_epoch(epoch)..).zip(proposer_indices.iter())
+    {
         let Some(validator) = state.validators.get(validator_index as usize) else {
             return Err(ApiError::ValidatorNotFound(format!("{validator_index}")));
         };
@@ -46,11 +64,17 @@ pub async fn get_proposer_duties(
 #[post("/validator/duties/attester/{epoch}")]
 pub async fn get_attester_duties(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
     epoch: Path<u64>,
     validator_indices: Json<Vec<u64>>,
 ) -> Result<impl Responder, ApiError> {
     let epoch = epoch.into_inner();
-    let state = get_state_from_id(ID::Slot(compute_start_slot_at_epoch(epoch)), &db).await?;
+    let state = get_state_from_id(
+        ID::Slot(compute_start_slot_at_epoch(epoch)),
+        &db,
+        &beacon_chain,
+    )
+    .await?;
     let dependent_root = state
         .get_block_root_at_slot(compute_start_slot_at_epoch(epoch) - 1)
         .map_err(|err| ApiError::BadRequest(format!("Failed to get dependent root {err:?}")))?;
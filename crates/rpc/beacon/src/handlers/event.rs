@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use actix_web::{Responder, get, rt, web::Data};
+use actix_web_lab::{extract::Query, sse};
+use ream_beacon_api_types::{
+    error::ApiError,
+    event::{BeaconEvent, CHAIN_REORG_EVENT_TOPIC},
+    query::EventTopicQuery,
+};
+use tokio::sync::{broadcast, mpsc};
+use tracing::warn;
+
+/// The topics which can be subscribed to on the event stream.
+pub const SUPPORTED_EVENT_TOPICS: [&str; 1] = [CHAIN_REORG_EVENT_TOPIC];
+
+/// The number of events buffered for a single event stream.
+const EVENT_STREAM_BUFFER_SIZE: usize = 16;
+
+/// The interval at which keep-alive comments are sent on an idle event stream.
+const EVENT_STREAM_KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// Called by `/eth/v1/events` to subscribe to beacon node events as server-sent events.
+#[get("/events")]
+pub async fn get_events(
+    event_sender: Data<broadcast::Sender<BeaconEvent>>,
+    query: Query<EventTopicQuery>,
+) -> Result<impl Responder, ApiError> {
+    let topics = query.into_inner().topics;
+    if topics.is_empty() {
+        return Err(ApiError::BadRequest(
+            "At least one event topic is required".to_string(),
+        ));
+    }
+    if let Some(topic) = topics
+        .iter()
+        .find(|topic| !SUPPORTED_EVENT_TOPICS.contains(&topic.as_str()))
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Unsupported event topic: {topic}"
+        )));
+    }
+
+    let mut event_receiver = event_sender.subscribe();
+    let (stream_sender, stream_receiver) = mpsc::channel(EVENT_STREAM_BUFFER_SIZE);
+    rt::spawn(async move {
+        loop {
+            let event = match event_receiver.recv().await {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Event stream lagged, skipped {skipped} events");
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+
+            if !topics.iter().any(|topic| topic == event.topic()) {
+                continue;
+            }
+
+            let data = match event.data() {
+                Ok(data) => data,
+                Err(err) => {
+                    warn!("Failed to serialize {} event: {err}", event.topic());
+                    continue;
+                }
+            };
+
+            // The client disconnected
+            if stream_sender
+                .send(sse::Data::new(data).event(event.topic()).into())
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+    });
+
+    Ok(
+        sse::Sse::from_infallible_receiver(stream_receiver)
+            .with_keep_alive(EVENT_STREAM_KEEP_ALIVE),
+    )
+}
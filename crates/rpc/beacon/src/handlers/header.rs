@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use actix_web::{
     HttpResponse, Responder, get,
     web::{Data, Path, Query},
@@ -9,8 +11,10 @@ use ream_beacon_api_types::{
     query::{ParentRootQuery, SlotQuery},
     responses::BeaconResponse,
 };
+use ream_beacon_chain::{beacon_chain::BeaconChain, block_reconstruction::get_beacon_block};
 use ream_consensus_misc::beacon_block_header::SignedBeaconBlockHeader;
-use ream_storage::{db::ReamDB, tables::Table};
+use ream_execution_engine::ExecutionEngine;
+use ream_storage::db::ReamDB;
 use serde::{Deserialize, Serialize};
 use tree_hash::TreeHash;
 
@@ -38,24 +42,30 @@ impl HeaderData {
 #[get("/beacon/headers")]
 pub async fn get_headers(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
+    execution_engine: Data<Option<ExecutionEngine>>,
     slot: Query<SlotQuery>,
     parent_root: Query<ParentRootQuery>,
 ) -> Result<impl Responder, ApiError> {
     let (header, root) = match (slot.slot, parent_root.parent_root) {
-        (None, None) => get_header_from_slot(None, &db).await?,
+        (None, None) => get_header_from_slot(None, &db, &beacon_chain, &execution_engine).await?,
         (None, Some(parent_root)) => {
             // get parent block to have access to `slot`
-            let parent_block = db
-                .beacon_block_provider()
-                .get(parent_root)
+            let parent_block = get_beacon_block(&db, execution_engine.as_ref(), parent_root)
+                .await
                 .map_err(|err| {
                     ApiError::InternalError(format!("Failed to get headers, error: {err:?}"))
                 })?
                 .ok_or_else(|| ApiError::NotFound(String::from("Unable to fetch parent block")))?;
 
             // fetch block header at `slot+1`
-            let (child_header, child_block_root) =
-                get_header_from_slot(Some(parent_block.message.slot + 1), &db).await?;
+            let (child_header, child_block_root) = get_header_from_slot(
+                Some(parent_block.message.slot + 1),
+                &db,
+                &beacon_chain,
+                &execution_engine,
+            )
+            .await?;
 
             if child_header.message.parent_root != parent_root {
                 return Err(ApiError::NotFound(format!(
@@ -65,9 +75,12 @@ pub async fn get_headers(
 
             (child_header, child_block_root)
         }
-        (Some(slot), None) => get_header_from_slot(Some(slot), &db).await?,
+        (Some(slot), None) => {
+            get_header_from_slot(Some(slot), &db, &beacon_chain, &execution_engine).await?
+        }
         (Some(slot), Some(parent_root)) => {
-            let (header, root) = get_header_from_slot(Some(slot), &db).await?;
+            let (header, root) =
+                get_header_from_slot(Some(slot), &db, &beacon_chain, &execution_engine).await?;
             if header.message.parent_root == parent_root {
                 (header, root)
             } else {
@@ -86,8 +99,12 @@ pub async fn get_headers(
 pub async fn get_headers_from_block(
     block_id: Path<ID>,
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
+    execution_engine: Data<Option<ExecutionEngine>>,
 ) -> Result<impl Responder, ApiError> {
-    let block = get_beacon_block_from_id(block_id.into_inner(), &db).await?;
+    let block =
+        get_beacon_block_from_id(block_id.into_inner(), &db, &beacon_chain, &execution_engine)
+            .await?;
     let header = block.signed_header();
 
     Ok(HttpResponse::Ok().json(BeaconResponse::new(HeaderData::new(
@@ -100,19 +117,16 @@ pub async fn get_headers_from_block(
 pub async fn get_header_from_slot(
     slot: Option<u64>,
     db: &ReamDB,
+    beacon_chain: &BeaconChain,
+    execution_engine: &Option<ExecutionEngine>,
 ) -> Result<(SignedBeaconBlockHeader, B256), ApiError> {
-    let slot = match slot {
-        Some(slot) => slot,
-        None => db
-            .slot_index_provider()
-            .get_highest_slot()
-            .map_err(|err| {
-                ApiError::InternalError(format!("Failed to get headers, error: {err:?}"))
-            })?
-            .ok_or_else(|| ApiError::NotFound(String::from("Unable to fetch latest slot")))?,
+    // Without a slot, the header of the canonical head is returned
+    let block_id = match slot {
+        Some(slot) => ID::Slot(slot),
+        None => ID::Head,
     };
-
-    let beacon_block = get_beacon_block_from_id(ID::Slot(slot), db).await?;
+    let beacon_block =
+        get_beacon_block_from_id(block_id, db, beacon_chain, execution_engine).await?;
 
     let header = beacon_block.signed_header();
     let root = header.tree_hash_root();
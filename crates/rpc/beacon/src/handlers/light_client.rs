@@ -10,7 +10,9 @@ use ream_beacon_api_types::{
         VERSION,
     },
 };
+use ream_beacon_chain::block_reconstruction::get_beacon_block;
 use ream_consensus_misc::constants::{EPOCHS_PER_SYNC_COMMITTEE_PERIOD, SLOTS_PER_EPOCH};
+use ream_execution_engine::ExecutionEngine;
 use ream_light_client::{
     bootstrap::LightClientBootstrap, finality_update::LightClientFinalityUpdate,
     header::LightClientHeader, update::LightClientUpdate,
@@ -27,12 +29,12 @@ pub const MAX_REQUEST_LIGHT_CLIENT_UPDATES: u64 = 128;
 #[get("/beacon/light_client/bootstrap/{block_root}")]
 pub async fn get_light_client_bootstrap(
     db: Data<ReamDB>,
+    execution_engine: Data<Option<ExecutionEngine>>,
     block_root: Path<B256>,
 ) -> Result<impl Responder, ApiError> {
     let block_root = block_root.into_inner();
-    let beacon_block = db
-        .beacon_block_provider()
-        .get(block_root)
+    let beacon_block = get_beacon_block(&db, execution_engine.as_ref(), block_root)
+        .await
         .map_err(|err| {
             ApiError::InternalError(format!("Failed to get block by block_root, error: {err:?}"))
         })?
@@ -65,6 +67,7 @@ pub async fn get_light_client_bootstrap(
 #[get("/beacon/light_client/updates")]
 pub async fn get_light_client_updates(
     db: Data<ReamDB>,
+    execution_engine: Data<Option<ExecutionEngine>>,
     start_period: Query<u64>,
     count: Query<u64>,
 ) -> Result<impl Responder, ApiError> {
@@ -89,9 +92,8 @@ pub async fn get_light_client_updates(
                 ))
             })?;
 
-        let block = db
-            .beacon_block_provider()
-            .get(block_root)
+        let block = get_beacon_block(&db, execution_engine.as_ref(), block_root)
+            .await
             .map_err(|err| {
                 ApiError::InternalError(format!(
                     "Failed to get beacon_block from block_root, error: {err:?}"
@@ -113,18 +115,21 @@ pub async fn get_light_client_updates(
                 "Failed to find beacon_state from {block_root:?}"
             )))?;
 
-        let attested_block = db
-            .beacon_block_provider()
-            .get(block.message.parent_root)
-            .map_err(|err| {
-                ApiError::InternalError(format!(
-                    "Failed to get attested_block from block.message.parent_root, error: {err:?}"
-                ))
-            })?
-            .ok_or(ApiError::NotFound(format!(
-                "Failed to find attested_block from {:?}",
-                block.message.parent_root
-            )))?;
+        let attested_block = get_beacon_block(
+            &db,
+            execution_engine.as_ref(),
+            block.message.parent_root,
+        )
+        .await
+        .map_err(|err| {
+            ApiError::InternalError(format!(
+                "Failed to get attested_block from block.message.parent_root, error: {err:?}"
+            ))
+        })?
+        .ok_or(ApiError::NotFound(format!(
+            "Failed to find attested_block from {:?}",
+            block.message.parent_root
+        )))?;
 
         let attested_block_root = attested_block.message.tree_hash_root();
         let attested_state = db
@@ -139,16 +144,16 @@ pub async fn get_light_client_updates(
                 "Failed to find attested_state from {attested_block_root:?}"
             )))?;
 
-        let finalized_block = db
-            .beacon_block_provider()
-            .get(attested_state.finalized_checkpoint.root)
+        let finalized_root = attested_state.finalized_checkpoint.root;
+        let finalized_block = get_beacon_block(&db, execution_engine.as_ref(), finalized_root)
+            .await
             .map_err(|err| {
                 ApiError::InternalError(format!(
                     "Failed to get finalized_block from attested_state.finalized_checkpoint.root, error: {err:?}"
                 ))
             })?
             .ok_or(ApiError::NotFound(format!(
-                "Failed to find finalized_block from {:?}",attested_state.finalized_checkpoint.root
+                "Failed to find finalized_block from {finalized_root:?}"
             )))?;
 
         updates.push(
@@ -177,6 +182,7 @@ pub async fn get_light_client_updates(
 #[get("/beacon/light_client/finality_update")]
 pub async fn get_light_client_finality_update(
     db: Data<ReamDB>,
+    execution_engine: Data<Option<ExecutionEngine>>,
     http_request: HttpRequest,
 ) -> Result<impl Responder, ApiError> {
     // Get the latest finalized checkpoint
@@ -206,22 +212,24 @@ pub async fn get_light_client_finality_update(
         .ok_or_else(|| ApiError::NotFound("Light client finality update unavailable".into()))?;
 
     // Get the head block and state
-    let head_block = db
-        .beacon_block_provider()
-        .get(head_block_root)
+    let head_block = get_beacon_block(&db, execution_engine.as_ref(), head_block_root)
+        .await
         .map_err(|err| {
             ApiError::InternalError(format!("Failed to get head block, error: {err:?}"))
         })?
         .ok_or_else(|| ApiError::NotFound("Light client finality update unavailable".into()))?;
 
     // Get the attested block (parent of head block) and its state
-    let attested_block = db
-        .beacon_block_provider()
-        .get(head_block.message.parent_root)
-        .map_err(|err| {
-            ApiError::InternalError(format!("Failed to get attested block, error: {err:?}"))
-        })?
-        .ok_or_else(|| ApiError::NotFound("Light client finality update unavailable".into()))?;
+    let attested_block = get_beacon_block(
+        &db,
+        execution_engine.as_ref(),
+        head_block.message.parent_root,
+    )
+    .await
+    .map_err(|err| {
+        ApiError::InternalError(format!("Failed to get attested block, error: {err:?}"))
+    })?
+    .ok_or_else(|| ApiError::NotFound("Light client finality update unavailable".into()))?;
 
     let attested_block_root = attested_block.message.tree_hash_root();
     let attested_state = db
@@ -233,13 +241,13 @@ pub async fn get_light_client_finality_update(
         .ok_or_else(|| ApiError::NotFound("Light client finality update unavailable".into()))?;
 
     // Get the finalized block
-    let finalized_block = db
-        .beacon_block_provider()
-        .get(finalized_checkpoint.root)
-        .map_err(|err| {
-            ApiError::InternalError(format!("Failed to get finalized block, error: {err:?}"))
-        })?
-        .ok_or_else(|| ApiError::NotFound("Light client finality update unavailable".into()))?;
+    let finalized_block =
+        get_beacon_block(&db, execution_engine.as_ref(), finalized_checkpoint.root)
+            .await
+            .map_err(|err| {
+                ApiError::InternalError(format!("Failed to get finalized block, error: {err:?}"))
+            })?
+            .ok_or_else(|| ApiError::NotFound("Light client finality update unavailable".into()))?;
 
     // Create the finality update
     let attested_header = LightClientHeader::new(&attested_block).map_err(|err| {
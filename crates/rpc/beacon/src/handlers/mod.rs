@@ -1,16 +1,21 @@
+pub mod admin;
 pub mod blob_sidecar;
 pub mod block;
 pub mod committee;
 pub mod config;
 pub mod debug;
 pub mod duties;
+pub mod event;
 pub mod header;
 pub mod identity;
 pub mod light_client;
+pub mod monitoring;
 pub mod peers;
 pub mod pool;
 pub mod prepare_beacon_proposer;
+pub mod proof;
 pub mod state;
 pub mod syncing;
 pub mod validator;
+pub mod validator_income;
 pub mod version;
@@ -0,0 +1,126 @@
+use std::{cmp::Reverse, collections::BTreeMap, sync::Arc};
+
+use actix_web::{
+    HttpResponse, Responder, get,
+    web::{Data, Query},
+};
+use alloy_primitives::B256;
+use ream_beacon_api_types::{error::ApiError, responses::DataResponse};
+use ream_beacon_chain::{beacon_chain::BeaconChain, block_reconstruction::get_beacon_block};
+use ream_consensus_misc::constants::SLOTS_PER_EPOCH;
+use ream_execution_engine::ExecutionEngine;
+use ream_storage::cache::CachedDB;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize)]
+pub struct CacheStatsResponse {
+    pub name: &'static str,
+    pub hits: u64,
+    pub misses: u64,
+    pub hit_rate: Option<f64>,
+    pub len: usize,
+    pub capacity: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HeadVotesQuery {
+    pub slots: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HeadVote {
+    pub root: B256,
+    pub validators: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SlotHeadVotes {
+    pub slot: u64,
+    pub votes: Vec<HeadVote>,
+}
+
+/// Called by `/ream/v1/node/caches` to get the lookup hit rates and occupancy of the caches.
+#[get("/node/caches")]
+pub async fn get_cache_stats(cached_db: Data<Arc<CachedDB>>) -> Result<impl Responder, ApiError> {
+    let cache_stats = cached_db
+        .cache_stats()
+        .await
+        .into_iter()
+        .map(|(name, stats)| CacheStatsResponse {
+            name,
+            hits: stats.hits,
+            misses: stats.misses,
+            hit_rate: stats.hit_rate(),
+            len: stats.len,
+            capacity: stats.capacity,
+        })
+        .collect::<Vec<_>>();
+
+    Ok(HttpResponse::Ok().json(DataResponse::new(cache_stats)))
+}
+
+/// Called by `/ream/v1/node/queues` to get the depths of the queues of the network manager.
+#[get("/node/queues")]
+pub async fn get_queue_depths(
+    beacon_chain: Data<Arc<BeaconChain>>,
+) -> Result<impl Responder, ApiError> {
+    let queue_depths = *beacon_chain.queue_depths.lock().await;
+    Ok(HttpResponse::Ok().json(DataResponse::new(queue_depths)))
+}
+
+/// Called by `/ream/v1/sync` to get the stage of the range sync, its batches in flight and what
+/// each peer contributed to it.
+#[get("/sync")]
+pub async fn get_sync_metrics(
+    beacon_chain: Data<Arc<BeaconChain>>,
+) -> Result<impl Responder, ApiError> {
+    let sync_metrics = beacon_chain.sync_metrics.lock().await.clone();
+    Ok(HttpResponse::Ok().json(DataResponse::new(sync_metrics)))
+}
+
+/// Called by `/ream/v1/fork_choice/head_votes` to get how the latest messages of the validators
+/// are distributed over the blocks of the recent slots. Optional `slots` sets how many slots back
+/// from the current slot are included, one epoch by default.
+#[get("/fork_choice/head_votes")]
+pub async fn get_head_votes(
+    beacon_chain: Data<Arc<BeaconChain>>,
+    execution_engine: Data<Option<ExecutionEngine>>,
+    query: Query<HeadVotesQuery>,
+) -> Result<impl Responder, ApiError> {
+    let store = beacon_chain.store.lock().await;
+    let current_slot = store
+        .get_current_slot()
+        .map_err(|err| ApiError::InternalError(format!("Failed to get current slot: {err:?}")))?;
+    let first_slot = current_slot.saturating_sub(query.slots.unwrap_or(SLOTS_PER_EPOCH));
+    let votes = store
+        .db
+        .latest_messages_provider()
+        .count_votes()
+        .map_err(|err| ApiError::InternalError(format!("Failed to count votes: {err:?}")))?;
+
+    let mut slot_head_votes = BTreeMap::<u64, Vec<HeadVote>>::new();
+    for (root, validators) in votes {
+        let block = get_beacon_block(&store.db, execution_engine.as_ref(), root)
+            .await
+            .map_err(|err| ApiError::InternalError(format!("Failed to get block: {err:?}")))?;
+        if let Some(block) = block
+            && block.message.slot >= first_slot
+        {
+            slot_head_votes
+                .entry(block.message.slot)
+                .or_default()
+                .push(HeadVote { root, validators });
+        }
+    }
+
+    let slot_head_votes = slot_head_votes
+        .into_iter()
+        .rev()
+        .map(|(slot, mut votes)| {
+            votes.sort_by_key(|vote| Reverse(vote.validators));
+            SlotHeadVotes { slot, votes }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(HttpResponse::Ok().json(DataResponse::new(slot_head_votes)))
+}
@@ -5,6 +5,7 @@ use actix_web::{
     web::{Data, Json},
 };
 use ream_beacon_api_types::{error::ApiError, id::ID, responses::DataResponse};
+use ream_beacon_chain::beacon_chain::BeaconChain;
 use ream_consensus_beacon::{
     bls_to_execution_change::SignedBLSToExecutionChange, voluntary_exit::SignedVoluntaryExit,
 };
@@ -26,19 +27,11 @@ pub async fn get_bls_to_execution_changes(
 #[post("/beacon/pool/bls_to_execution_changes")]
 pub async fn post_bls_to_execution_changes(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
     operation_pool: Data<Arc<OperationPool>>,
     signed_bls_to_execution_change: Json<SignedBLSToExecutionChange>,
 ) -> Result<impl Responder, ApiError> {
-    let highest_slot = db
-        .slot_index_provider()
-        .get_highest_slot()
-        .map_err(|err| {
-            ApiError::InternalError(format!("Failed to get_highest_slot, error: {err:?}"))
-        })?
-        .ok_or(ApiError::NotFound(
-            "Failed to find highest slot".to_string(),
-        ))?;
-    let beacon_state = get_state_from_id(ID::Slot(highest_slot), &db).await?;
+    let beacon_state = get_state_from_id(ID::Head, &db, &beacon_chain).await?;
 
     let signed_bls_to_execution_change = signed_bls_to_execution_change.into_inner();
 
@@ -69,19 +62,11 @@ pub async fn get_voluntary_exits(
 #[post("/beacon/pool/voluntary_exits")]
 pub async fn post_voluntary_exits(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
     operation_pool: Data<Arc<OperationPool>>,
     signed_voluntary_exit: Json<SignedVoluntaryExit>,
 ) -> Result<impl Responder, ApiError> {
-    let highest_slot = db
-        .slot_index_provider()
-        .get_highest_slot()
-        .map_err(|err| {
-            ApiError::InternalError(format!("Failed to get_highest_slot, error: {err:?}"))
-        })?
-        .ok_or(ApiError::NotFound(
-            "Failed to find highest slot".to_string(),
-        ))?;
-    let beacon_state = get_state_from_id(ID::Slot(highest_slot), &db).await?;
+    let beacon_state = get_state_from_id(ID::Head, &db, &beacon_chain).await?;
 
     let signed_voluntary_exit = signed_voluntary_exit.into_inner();
 
@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use actix_web::{
+    HttpResponse, Responder, get,
+    web::{Data, Path},
+};
+use alloy_primitives::B256;
+use ream_beacon_api_types::{error::ApiError, id::ID, responses::BeaconResponse};
+use ream_beacon_chain::{beacon_chain::BeaconChain, block_reconstruction::get_beacon_block};
+use ream_consensus_misc::constants::SLOTS_PER_HISTORICAL_ROOT;
+use ream_execution_engine::ExecutionEngine;
+use ream_storage::{db::ReamDB, tables::Table};
+
+use super::state::get_state_from_id;
+
+/// Called by `/eth/v1/beacon/states/{state_id}/historical_block_proof/{slot}` to prove that the
+/// canonical block at `slot` is part of the chain summarized by the state, through its
+/// `historical_summaries`.
+#[get("/beacon/states/{state_id}/historical_block_proof/{slot}")]
+pub async fn get_historical_block_proof(
+    db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
+    execution_engine: Data<Option<ExecutionEngine>>,
+    param: Path<(ID, u64)>,
+) -> Result<impl Responder, ApiError> {
+    let (state_id, slot) = param.into_inner();
+    let state = get_state_from_id(state_id, &db, &beacon_chain).await?;
+
+    if state.historical_summary_index(slot).is_none() {
+        return Err(
+            if slot / SLOTS_PER_HISTORICAL_ROOT < state.historical_roots.len() as u64 {
+                ApiError::NotFound(format!(
+                    "Slot {slot} is summarized by historical_roots, whose historical batches are not stored"
+                ))
+            } else {
+                ApiError::BadRequest(format!(
+                    "The period of slot {slot} is not summarized by the state at slot {}",
+                    state.slot
+                ))
+            },
+        );
+    }
+
+    let block_roots = get_period_block_roots(
+        &db,
+        execution_engine.as_ref(),
+        slot / SLOTS_PER_HISTORICAL_ROOT,
+        state.slot,
+    )
+    .await?;
+    let proof = state
+        .historical_summary_proof(slot, &block_roots)
+        .map_err(|err| {
+            ApiError::NotFound(format!(
+                "Failed to prove block at slot {slot}, the stored blocks of its period may be incomplete: {err:?}"
+            ))
+        })?;
+
+    Ok(HttpResponse::Ok().json(BeaconResponse::new(proof)))
+}
+
+/// Rebuilds the `block_roots` vector of a period from the stored canonical blocks. The entry of a
+/// slot without a block is the root of the latest block before it.
+async fn get_period_block_roots(
+    db: &ReamDB,
+    execution_engine: Option<&ExecutionEngine>,
+    period: u64,
+    head_slot: u64,
+) -> Result<Vec<B256>, ApiError> {
+    let start_slot = period * SLOTS_PER_HISTORICAL_ROOT;
+    let end_slot = start_slot + SLOTS_PER_HISTORICAL_ROOT;
+    let get_block_root = |slot: u64| {
+        db.slot_index_provider().get(slot).map_err(|err| {
+            ApiError::InternalError(format!("Failed to get block root at slot {slot}: {err:?}"))
+        })
+    };
+
+    // The slots before the first block of the period hold the root of its parent
+    let (first_slot, first_root) = (start_slot..=head_slot)
+        .find_map(|slot| get_block_root(slot).transpose().map(|root| (slot, root)))
+        .ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "No stored block after the start of period {period}"
+            ))
+        })?;
+    let first_root = first_root?;
+    let mut latest_root = get_beacon_block(db, execution_engine, first_root)
+        .await
+        .map_err(|err| ApiError::InternalError(format!("Failed to get block: {err:?}")))?
+        .ok_or_else(|| ApiError::NotFound(format!("Failed to find block {first_root}")))?
+        .message
+        .parent_root;
+
+    let mut block_roots = Vec::with_capacity(SLOTS_PER_HISTORICAL_ROOT as usize);
+    for slot in start_slot..end_slot {
+        if slot >= first_slot
+            && let Some(block_root) = get_block_root(slot)?
+        {
+            latest_root = block_root;
+        }
+        block_roots.push(latest_root);
+    }
+
+    Ok(block_roots)
+}
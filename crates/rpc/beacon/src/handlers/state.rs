@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use actix_web::{
     HttpResponse, Responder, get,
     web::{Data, Path, Query},
@@ -9,6 +11,7 @@ use ream_beacon_api_types::{
     query::EpochQuery,
     responses::{BeaconResponse, BeaconVersionedResponse},
 };
+use ream_beacon_chain::{beacon_chain::BeaconChain, canonical_head::CanonicalHead};
 use ream_consensus_beacon::electra::beacon_state::BeaconState;
 use ream_consensus_misc::{
     checkpoint::Checkpoint, constants::SYNC_COMMITTEE_SIZE, misc::compute_sync_committee_period,
@@ -65,8 +68,21 @@ struct SyncCommitteeResponse {
     pub validator_aggregates: Vec<QuotedU64Vec>,
 }
 
-pub async fn get_state_from_id(state_id: ID, db: &ReamDB) -> Result<BeaconState, ApiError> {
+/// Returns the head snapshot of `beacon_chain`, which is read without locking the fork choice
+/// store.
+pub fn get_canonical_head(beacon_chain: &BeaconChain) -> Result<Arc<CanonicalHead>, ApiError> {
+    beacon_chain
+        .canonical_head()
+        .ok_or_else(|| ApiError::NotFound("The canonical head is not known yet".to_string()))
+}
+
+pub async fn get_state_from_id(
+    state_id: ID,
+    db: &ReamDB,
+    beacon_chain: &BeaconChain,
+) -> Result<BeaconState, ApiError> {
     let block_root = match state_id {
+        ID::Head => Ok(Some(get_canonical_head(beacon_chain)?.head_block_root)),
         ID::Finalized => {
             let finalized_checkpoint = db.finalized_checkpoint_provider().get().map_err(|err| {
                 ApiError::InternalError(format!(
@@ -85,7 +101,7 @@ pub async fn get_state_from_id(state_id: ID, db: &ReamDB) -> Result<BeaconState,
 
             Ok(Some(justified_checkpoint.root))
         }
-        ID::Head | ID::Genesis => {
+        ID::Genesis => {
             return Err(ApiError::NotFound(format!(
                 "This ID type is currently not supported: {state_id:?}"
             )));
@@ -107,9 +123,10 @@ pub async fn get_state_from_id(state_id: ID, db: &ReamDB) -> Result<BeaconState,
 #[get("/beacon/states/{state_id}/root")]
 pub async fn get_state_root(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
     state_id: Path<ID>,
 ) -> Result<impl Responder, ApiError> {
-    let state = get_state_from_id(state_id.into_inner(), &db).await?;
+    let state = get_state_from_id(state_id.into_inner(), &db, &beacon_chain).await?;
 
     let state_root = state.tree_hash_root();
 
@@ -120,9 +137,10 @@ pub async fn get_state_root(
 #[get("/beacon/states/{state_id}/fork")]
 pub async fn get_state_fork(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
     state_id: Path<ID>,
 ) -> Result<impl Responder, ApiError> {
-    let state = get_state_from_id(state_id.into_inner(), &db).await?;
+    let state = get_state_from_id(state_id.into_inner(), &db, &beacon_chain).await?;
 
     Ok(HttpResponse::Ok().json(BeaconResponse::new(state.fork)))
 }
@@ -131,9 +149,10 @@ pub async fn get_state_fork(
 #[get("/beacon/states/{state_id}/finality_checkpoints")]
 pub async fn get_state_finality_checkpoint(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
     state_id: Path<ID>,
 ) -> Result<impl Responder, ApiError> {
-    let state = get_state_from_id(state_id.into_inner(), &db).await?;
+    let state = get_state_from_id(state_id.into_inner(), &db, &beacon_chain).await?;
 
     Ok(
         HttpResponse::Ok().json(BeaconResponse::new(CheckpointData::new(
@@ -150,10 +169,11 @@ pub async fn get_state_finality_checkpoint(
 #[get("/beacon/states/{state_id}/randao")]
 pub async fn get_state_randao(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
     state_id: Path<ID>,
     query: Query<EpochQuery>,
 ) -> Result<impl Responder, ApiError> {
-    let state = get_state_from_id(state_id.into_inner(), &db).await?;
+    let state = get_state_from_id(state_id.into_inner(), &db, &beacon_chain).await?;
 
     let randao_mix = match query.epoch {
         Some(epoch) => state.get_randao_mix(epoch),
@@ -168,9 +188,10 @@ pub async fn get_state_randao(
 #[get("/beacon/states/{state_id}/pending_consolidations")]
 pub async fn get_pending_consolidations(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
     state_id: Path<ID>,
 ) -> Result<impl Responder, ApiError> {
-    let state = get_state_from_id(state_id.into_inner(), &db).await?;
+    let state = get_state_from_id(state_id.into_inner(), &db, &beacon_chain).await?;
 
     Ok(
         HttpResponse::Ok().json(BeaconVersionedResponse::new(Vec::from(
@@ -184,9 +205,10 @@ pub async fn get_pending_consolidations(
 #[get("/beacon/states/{state_id}/pending_deposits")]
 pub async fn get_pending_deposits(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
     state_id: Path<ID>,
 ) -> Result<impl Responder, ApiError> {
-    let state = get_state_from_id(state_id.into_inner(), &db).await?;
+    let state = get_state_from_id(state_id.into_inner(), &db, &beacon_chain).await?;
 
     Ok(
         HttpResponse::Ok().json(BeaconVersionedResponse::new(Vec::from(
@@ -200,9 +222,10 @@ pub async fn get_pending_deposits(
 #[get("/beacon/states/{state_id}/pending_partial_withdrawals")]
 pub async fn get_pending_partial_withdrawals(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
     state_id: Path<ID>,
 ) -> Result<impl Responder, ApiError> {
-    let state = get_state_from_id(state_id.into_inner(), &db).await?;
+    let state = get_state_from_id(state_id.into_inner(), &db, &beacon_chain).await?;
 
     Ok(
         HttpResponse::Ok().json(BeaconVersionedResponse::new(Vec::from(
@@ -217,10 +240,11 @@ pub async fn get_pending_partial_withdrawals(
 #[get("/beacon/states/{state_id}/sync_committees")]
 pub async fn get_sync_committees(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
     state_id: Path<ID>,
     epoch: Query<EpochQuery>,
 ) -> Result<impl Responder, ApiError> {
-    let state = get_state_from_id(state_id.into_inner(), &db).await?;
+    let state = get_state_from_id(state_id.into_inner(), &db, &beacon_chain).await?;
     let current_epoch = state.get_current_epoch();
     let epoch = epoch.epoch.unwrap_or(current_epoch);
     let sync_committee_period = compute_sync_committee_period(epoch);
@@ -6,13 +6,16 @@ use ream_beacon_api_types::{
     responses::{DataResponse, EXECUTION_OPTIMISTIC},
     sync::SyncStatus,
 };
+use ream_beacon_chain::beacon_chain::BeaconChain;
 use ream_execution_engine::ExecutionEngine;
 use ream_fork_choice::store::Store;
 use ream_operation_pool::OperationPool;
-use ream_storage::{db::ReamDB, tables::Table};
+use ream_storage::db::ReamDB;
 use serde::{Deserialize, Serialize};
 use tracing::error;
 
+use super::state::get_canonical_head;
+
 #[derive(Serialize, Deserialize, Default)]
 pub struct Syncing {
     sync_status: SyncStatus,
@@ -36,6 +39,7 @@ impl Syncing {
 #[get("/node/syncing")]
 pub async fn get_syncing_status(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
     operation_pool: Data<Arc<OperationPool>>,
     execution_engine: Data<Option<ExecutionEngine>>,
 ) -> Result<impl Responder, ApiError> {
@@ -45,18 +49,7 @@ pub async fn get_syncing_status(
     };
 
     // get head_slot
-    let head = store.get_head().map_err(|err| {
-        ApiError::InternalError(format!("Failed to get current slot, error: {err:?}"))
-    })?;
-
-    let head_slot = match db.beacon_block_provider().get(head) {
-        Ok(Some(block)) => block.message.slot,
-        err => {
-            return Err(ApiError::InternalError(format!(
-                "Failed to get head slot, error: {err:?}"
-            )));
-        }
-    };
+    let head_slot = get_canonical_head(&beacon_chain)?.head_state.slot;
 
     // calculate sync_distance
     let current_slot = store.get_current_slot().map_err(|err| {
@@ -7,22 +7,36 @@ use actix_web::{
 use ream_beacon_api_types::{
     error::ApiError,
     id::{ID, ValidatorID},
-    query::{AttestationQuery, IdQuery, StatusQuery},
+    query::{
+        AttestationQuery, IdQuery, PaginationQuery, StatusQuery, SyncCommitteeContributionQuery,
+    },
     request::ValidatorsPostRequest,
     responses::{BeaconResponse, DataResponse},
     validator::{ValidatorBalance, ValidatorData, ValidatorStatus},
 };
+use ream_beacon_chain::beacon_chain::BeaconChain;
 use ream_bls::PublicKey;
-use ream_consensus_beacon::electra::beacon_state::BeaconState;
+use ream_consensus_beacon::{
+    electra::{
+        beacon_state::BeaconState,
+        zkvm_types::{ValidatorRegistryLimit, ValidatorRegistryTreeHashLimit},
+    },
+    persistent_list::PersistentList,
+    pubkey_cache::PubkeyCache,
+};
 use ream_consensus_misc::{
-    attestation_data::AttestationData, constants::SLOTS_PER_EPOCH, validator::Validator,
+    attestation_data::AttestationData, constants::SLOTS_PER_EPOCH, misc::compute_epoch_at_slot,
+    validator::Validator,
 };
 use ream_fork_choice::store::Store;
-use ream_operation_pool::OperationPool;
-use ream_storage::{db::ReamDB, tables::Field};
+use ream_operation_pool::{OperationPool, SyncContributionKey};
+use ream_storage::{cache::CachedDB, db::ReamDB, tables::Field};
+use ream_validator_beacon::{
+    constants::SYNC_COMMITTEE_SUBNET_COUNT, sync_committee::aggregate_sync_committee_contribution,
+};
 use serde::Serialize;
 
-use super::state::get_state_from_id;
+use super::state::{get_canonical_head, get_state_from_id};
 
 ///  For slots in Electra and later, this AttestationData must have a committee_index of 0.
 const ELECTRA_COMMITTEE_INDEX: u64 = 0;
@@ -55,10 +69,12 @@ fn build_validator_balances(
 #[get("/beacon/states/{state_id}/validator/{validator_id}")]
 pub async fn get_validator_from_state(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
+    cached_db: Data<Arc<CachedDB>>,
     param: Path<(ID, ValidatorID)>,
 ) -> Result<impl Responder, ApiError> {
     let (state_id, validator_id) = param.into_inner();
-    let state = get_state_from_id(state_id, &db).await?;
+    let state = get_state_from_id(state_id, &db, &beacon_chain).await?;
 
     let (index, validator) = {
         match &validator_id {
@@ -71,13 +87,12 @@ pub async fn get_validator_from_state(
                 }
             },
             ValidatorID::Address(public_key) => {
-                match state
-                    .validators
-                    .iter()
-                    .enumerate()
-                    .find(|(_, validator)| validator.public_key == *public_key)
+                match cached_db
+                    .get_pubkey_cache(&state)
+                    .await
+                    .get_validator_index(&state, public_key)
                 {
-                    Some((i, validator)) => (i, validator.to_owned()),
+                    Some(i) => (i as usize, state.validators[i as usize].to_owned()),
                     None => {
                         return Err(ApiError::NotFound(format!(
                             "Validator not found for public_key: {public_key:?}"
@@ -92,7 +107,7 @@ pub async fn get_validator_from_state(
         "Validator not found for index: {index}"
     )))?;
 
-    let status = validator_status(&validator, &db).await?;
+    let status = validator_status(&validator, &beacon_chain)?;
 
     Ok(
         HttpResponse::Ok().json(BeaconResponse::new(ValidatorData::new(
@@ -104,175 +119,188 @@ pub async fn get_validator_from_state(
     )
 }
 
-pub async fn validator_status(
+pub fn validator_status(
     validator: &Validator,
-    db: &ReamDB,
+    beacon_chain: &BeaconChain,
 ) -> Result<ValidatorStatus, ApiError> {
-    let highest_slot = db
-        .slot_index_provider()
-        .get_highest_slot()
-        .map_err(|err| {
-            ApiError::InternalError(format!("Failed to get_highest_slot, error: {err:?}"))
-        })?
-        .ok_or(ApiError::NotFound(
-            "Failed to find highest slot".to_string(),
-        ))?;
-    let state = get_state_from_id(ID::Slot(highest_slot), db).await?;
+    Ok(validator_status_at_epoch(
+        validator,
+        head_epoch(beacon_chain)?,
+    ))
+}
 
-    if validator.exit_epoch < state.get_current_epoch() {
-        Ok(ValidatorStatus::Offline)
+fn validator_status_at_epoch(validator: &Validator, current_epoch: u64) -> ValidatorStatus {
+    if validator.exit_epoch < current_epoch {
+        ValidatorStatus::Offline
     } else {
-        Ok(ValidatorStatus::ActiveOngoing)
+        ValidatorStatus::ActiveOngoing
     }
 }
 
-#[get("/beacon/states/{state_id}/validators")]
-pub async fn get_validators_from_state(
-    db: Data<ReamDB>,
-    state_id: Path<ID>,
-    id_query: Query<IdQuery>,
-    status_query: Query<StatusQuery>,
-) -> Result<impl Responder, ApiError> {
-    if let Some(validator_ids) = &id_query.id
-        && validator_ids.len() >= MAX_VALIDATOR_COUNT
-    {
-        return Err(ApiError::TooManyValidatorsIds);
-    }
+fn head_epoch(beacon_chain: &BeaconChain) -> Result<u64, ApiError> {
+    Ok(compute_epoch_at_slot(
+        get_canonical_head(beacon_chain)?.head_state.slot,
+    ))
+}
 
-    let state = get_state_from_id(state_id.into_inner(), &db).await?;
-    let mut validators_data = Vec::new();
-    let mut validator_indices_to_process = Vec::new();
-
-    // First, collect all the validator indices we need to process
-    if let Some(validator_ids) = &id_query.id {
-        for validator_id in validator_ids {
-            let (index, _) = {
-                match validator_id {
-                    ValidatorID::Index(i) => match state.validators.get(*i as usize) {
-                        Some(validator) => (*i as usize, validator.to_owned()),
-                        None => {
-                            return Err(ApiError::NotFound(format!(
-                                "Validator not found for index: {i}"
-                            )))?;
-                        }
-                    },
-                    ValidatorID::Address(public_key) => {
-                        match state
-                            .validators
-                            .iter()
-                            .enumerate()
-                            .find(|(_, validator)| validator.public_key == *public_key)
-                        {
-                            Some((i, validator)) => (i, validator.to_owned()),
-                            None => {
-                                return Err(ApiError::NotFound(format!(
-                                    "Validator not found for public_key: {public_key:?}"
-                                )))?;
-                            }
-                        }
-                    }
+/// Resolves validator ids to indices into the registry, in request order and without duplicates.
+fn resolve_validator_indices(
+    state: &BeaconState,
+    pubkey_cache: &PubkeyCache,
+    validator_ids: &[ValidatorID],
+) -> Result<Vec<usize>, ApiError> {
+    let mut seen_indices = HashSet::with_capacity(validator_ids.len());
+    let mut indices = Vec::with_capacity(validator_ids.len());
+
+    for validator_id in validator_ids {
+        let index = match validator_id {
+            ValidatorID::Index(i) => {
+                if *i as usize >= state.validators.len() {
+                    return Err(ApiError::NotFound(format!(
+                        "Validator not found for index: {i}"
+                    )));
                 }
-            };
-            validator_indices_to_process.push(index);
+                *i as usize
+            }
+            ValidatorID::Address(public_key) => pubkey_cache
+                .get_validator_index(state, public_key)
+                .ok_or_else(|| {
+                    ApiError::NotFound(format!(
+                        "Validator not found for public_key: {public_key:?}"
+                    ))
+                })? as usize,
+        };
+
+        if seen_indices.insert(index) {
+            indices.push(index);
         }
-    } else {
-        validator_indices_to_process = (0..state.validators.len()).collect();
     }
 
-    for index in validator_indices_to_process {
-        let validator = &state.validators[index];
-
-        let status = validator_status(validator, &db).await?;
+    Ok(indices)
+}
 
-        if status_query.has_status() && !status_query.contains_status(&status) {
-            continue;
-        }
+/// Returns the validators at `indices` whose status matches `status_query`, where no statuses match
+/// every validator, paginated after the status filter.
+fn select_validators<'a>(
+    validators: &'a PersistentList<
+        Validator,
+        ValidatorRegistryLimit,
+        ValidatorRegistryTreeHashLimit,
+    >,
+    indices: Vec<usize>,
+    current_epoch: u64,
+    status_query: &'a StatusQuery,
+    pagination: &PaginationQuery,
+) -> impl Iterator<Item = (usize, &'a Validator, ValidatorStatus)> {
+    indices
+        .into_iter()
+        .map(move |index| {
+            let validator = &validators[index];
+            (
+                index,
+                validator,
+                validator_status_at_epoch(validator, current_epoch),
+            )
+        })
+        .filter(|(_, _, status)| !status_query.has_status() || status_query.contains_status(status))
+        .skip(pagination.offset.unwrap_or(0))
+        .take(pagination.limit.unwrap_or(usize::MAX))
+}
 
+/// Returns the validators with the given ids, or every validator if no ids are given, whose status
+/// matches `status_query`. The status filter is applied before `pagination`.
+async fn filter_validators(
+    state: &BeaconState,
+    beacon_chain: &BeaconChain,
+    cached_db: &CachedDB,
+    validator_ids: Option<&[ValidatorID]>,
+    status_query: &StatusQuery,
+    pagination: &PaginationQuery,
+) -> Result<Vec<ValidatorData>, ApiError> {
+    let indices = match validator_ids {
+        Some(validator_ids) => resolve_validator_indices(
+            state,
+            &*cached_db.get_pubkey_cache(state).await,
+            validator_ids,
+        )?,
+        None => (0..state.validators.len()).collect(),
+    };
+    let current_epoch = head_epoch(beacon_chain)?;
+
+    select_validators(
+        &state.validators,
+        indices,
+        current_epoch,
+        status_query,
+        pagination,
+    )
+    .map(|(index, validator, status)| {
         let balance = state.balances.get(index).ok_or(ApiError::NotFound(format!(
             "Validator not found for index: {index}"
         )))?;
-
-        validators_data.push(ValidatorData::new(
+        Ok(ValidatorData::new(
             index as u64,
             *balance,
             status,
             validator.clone(),
-        ));
+        ))
+    })
+    .collect()
+}
+
+#[get("/beacon/states/{state_id}/validators")]
+pub async fn get_validators_from_state(
+    db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
+    cached_db: Data<Arc<CachedDB>>,
+    state_id: Path<ID>,
+    id_query: Query<IdQuery>,
+    status_query: Query<StatusQuery>,
+    pagination: Query<PaginationQuery>,
+) -> Result<impl Responder, ApiError> {
+    if let Some(validator_ids) = &id_query.id
+        && validator_ids.len() >= MAX_VALIDATOR_COUNT
+    {
+        return Err(ApiError::TooManyValidatorsIds);
     }
 
+    let state = get_state_from_id(state_id.into_inner(), &db, &beacon_chain).await?;
+    let validators_data = filter_validators(
+        &state,
+        &beacon_chain,
+        &cached_db,
+        id_query.id.as_deref(),
+        &status_query,
+        &pagination,
+    )
+    .await?;
+
     Ok(HttpResponse::Ok().json(BeaconResponse::new(validators_data)))
 }
 
+/// Unlike the GET endpoint, the number of ids is not limited since they are sent in the body.
 #[post("/beacon/states/{state_id}/validators")]
 pub async fn post_validators_from_state(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
+    cached_db: Data<Arc<CachedDB>>,
     state_id: Path<ID>,
     request: Json<ValidatorsPostRequest>,
-    _status_query: Json<StatusQuery>,
+    pagination: Query<PaginationQuery>,
 ) -> Result<impl Responder, ApiError> {
     let ValidatorsPostRequest { ids, statuses, .. } = request.into_inner();
     let status_query = StatusQuery { status: statuses };
 
-    let state = get_state_from_id(state_id.into_inner(), &db).await?;
-    let mut validators_data = Vec::new();
-    let mut validator_indices_to_process = Vec::new();
-
-    // First, collect all the validator indices we need to process
-    if let Some(validator_ids) = &ids {
-        for validator_id in validator_ids {
-            let (index, _) = {
-                match validator_id {
-                    ValidatorID::Index(i) => match state.validators.get(*i as usize) {
-                        Some(validator) => (*i as usize, validator.to_owned()),
-                        None => {
-                            return Err(ApiError::NotFound(format!(
-                                "Validator not found for index: {i}"
-                            )))?;
-                        }
-                    },
-                    ValidatorID::Address(public_key) => {
-                        match state
-                            .validators
-                            .iter()
-                            .enumerate()
-                            .find(|(_, validator)| validator.public_key == *public_key)
-                        {
-                            Some((i, validator)) => (i, validator.to_owned()),
-                            None => {
-                                return Err(ApiError::NotFound(format!(
-                                    "Validator not found for public_key: {public_key:?}"
-                                )))?;
-                            }
-                        }
-                    }
-                }
-            };
-            validator_indices_to_process.push(index);
-        }
-    } else {
-        validator_indices_to_process = (0..state.validators.len()).collect();
-    }
-
-    for index in validator_indices_to_process {
-        let validator = &state.validators[index];
-
-        let status = validator_status(validator, &db).await?;
-
-        if status_query.has_status() && !status_query.contains_status(&status) {
-            continue;
-        }
-
-        let balance = state.balances.get(index).ok_or(ApiError::NotFound(format!(
-            "Validator not found for index: {index}"
-        )))?;
-
-        validators_data.push(ValidatorData::new(
-            index as u64,
-            *balance,
-            status,
-            validator.clone(),
-        ));
-    }
+    let state = get_state_from_id(state_id.into_inner(), &db, &beacon_chain).await?;
+    let validators_data = filter_validators(
+        &state,
+        &beacon_chain,
+        &cached_db,
+        ids.as_deref(),
+        &status_query,
+        &pagination,
+    )
+    .await?;
 
     Ok(HttpResponse::Ok().json(BeaconResponse::new(validators_data)))
 }
@@ -289,10 +317,11 @@ struct ValidatorIdentity {
 #[post("/beacon/states/{state_id}/validator_identities")]
 pub async fn post_validator_identities_from_state(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
     state_id: Path<ID>,
     validator_ids: Json<Vec<ValidatorID>>,
 ) -> Result<impl Responder, ApiError> {
-    let state = get_state_from_id(state_id.into_inner(), &db).await?;
+    let state = get_state_from_id(state_id.into_inner(), &db, &beacon_chain).await?;
 
     let validator_ids_set: HashSet<ValidatorID> = validator_ids.into_inner().into_iter().collect();
 
@@ -323,14 +352,16 @@ pub async fn get_validator_balances_from_state(
     state_id: Path<ID>,
     query: Query<IdQuery>,
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
 ) -> Result<impl Responder, ApiError> {
-    let state = get_state_from_id(state_id.into_inner(), &db).await?;
+    let state = get_state_from_id(state_id.into_inner(), &db, &beacon_chain).await?;
     Ok(
         HttpResponse::Ok().json(BeaconResponse::new(build_validator_balances(
             &state
                 .validators
-                .into_iter()
-                .zip(state.balances.into_iter())
+                .iter()
+                .cloned()
+                .zip(state.balances.iter().copied())
                 .collect::<Vec<_>>(),
             query.id.as_ref(),
         ))),
@@ -342,14 +373,16 @@ pub async fn post_validator_balances_from_state(
     state_id: Path<ID>,
     body: Json<IdQuery>,
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
 ) -> Result<impl Responder, ApiError> {
-    let state = get_state_from_id(state_id.into_inner(), &db).await?;
+    let state = get_state_from_id(state_id.into_inner(), &db, &beacon_chain).await?;
     Ok(
         HttpResponse::Ok().json(BeaconResponse::new(build_validator_balances(
             &state
                 .validators
-                .into_iter()
-                .zip(state.balances.into_iter())
+                .iter()
+                .cloned()
+                .zip(state.balances.iter().copied())
                 .collect::<Vec<_>>(),
             body.id.as_ref(),
         ))),
@@ -372,6 +405,7 @@ impl ValidatorLivenessData {
 #[post("/validator/liveness/{epoch}")]
 pub async fn post_validator_liveness(
     db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
     epoch: Path<u64>,
     validator_indices: Json<Vec<String>>,
 ) -> Result<impl Responder, ApiError> {
@@ -379,7 +413,7 @@ pub async fn post_validator_liveness(
     let validator_indices = validator_indices.into_inner();
 
     let slot = epoch * SLOTS_PER_EPOCH;
-    let state = get_state_from_id(ID::Slot(slot), &db).await?;
+    let state = get_state_from_id(ID::Slot(slot), &db, &beacon_chain).await?;
 
     let mut liveness_data = Vec::new();
 
@@ -485,3 +519,111 @@ pub async fn get_attestation_data(
         target: target_checkpoint,
     })))
 }
+
+/// Called by `/eth/v1/validator/sync_committee_contribution` to aggregate the pooled sync committee
+/// messages of a subcommittee. The contribution is cached until a new message is pooled, so that
+/// every aggregator of the subcommittee is served without aggregating again.
+#[get("/validator/sync_committee_contribution")]
+pub async fn get_sync_committee_contribution(
+    db: Data<ReamDB>,
+    beacon_chain: Data<Arc<BeaconChain>>,
+    operation_pool: Data<Arc<OperationPool>>,
+    query: Query<SyncCommitteeContributionQuery>,
+) -> Result<impl Responder, ApiError> {
+    if query.subcommittee_index >= SYNC_COMMITTEE_SUBNET_COUNT {
+        return Err(ApiError::InvalidParameter(format!(
+            "Subcommittee index {} must be less than {SYNC_COMMITTEE_SUBNET_COUNT}",
+            query.subcommittee_index
+        )));
+    }
+
+    let key = SyncContributionKey {
+        slot: query.slot,
+        beacon_block_root: query.beacon_block_root,
+        subcommittee_index: query.subcommittee_index,
+    };
+    if let Some(contribution) = operation_pool.get_cached_sync_contribution(&key) {
+        return Ok(HttpResponse::Ok().json(DataResponse::new(contribution)));
+    }
+
+    let messages = operation_pool.get_sync_committee_messages(&key);
+    if messages.is_empty() {
+        return Err(ApiError::NotFound(format!(
+            "No sync committee messages for block root {} at slot {}",
+            query.beacon_block_root, query.slot
+        )));
+    }
+
+    let state = get_state_from_id(ID::Head, &db, &beacon_chain).await?;
+    let contribution = aggregate_sync_committee_contribution(
+        &state,
+        query.slot,
+        query.beacon_block_root,
+        query.subcommittee_index,
+        &messages,
+    )
+    .map_err(|err| {
+        ApiError::InternalError(format!(
+            "Failed to aggregate sync committee contribution: {err:?}"
+        ))
+    })?
+    .ok_or_else(|| {
+        ApiError::NotFound(format!(
+            "No sync committee messages of subcommittee {} for block root {} at slot {}",
+            query.subcommittee_index, query.beacon_block_root, query.slot
+        ))
+    })?;
+    operation_pool.cache_sync_contribution(key, messages.len(), contribution.clone());
+
+    Ok(HttpResponse::Ok().json(DataResponse::new(contribution)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validator(exit_epoch: u64) -> Validator {
+        Validator {
+            public_key: PublicKey::default(),
+            withdrawal_credentials: Default::default(),
+            effective_balance: 32_000_000_000,
+            slashed: false,
+            activation_eligibility_epoch: 0,
+            activation_epoch: 0,
+            exit_epoch,
+            withdrawable_epoch: exit_epoch,
+        }
+    }
+
+    fn selected_indices(status: Option<Vec<ValidatorStatus>>) -> Vec<usize> {
+        let validators = vec![validator(u64::MAX), validator(1), validator(u64::MAX)].into();
+        let status_query = StatusQuery { status };
+        select_validators(
+            &validators,
+            vec![0, 1, 2],
+            10,
+            &status_query,
+            &PaginationQuery::default(),
+        )
+        .map(|(index, _, _)| index)
+        .collect()
+    }
+
+    #[test]
+    fn test_select_validators_without_statuses_selects_all() {
+        assert_eq!(selected_indices(None), vec![0, 1, 2]);
+        assert_eq!(selected_indices(Some(vec![])), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_select_validators_by_status() {
+        assert_eq!(
+            selected_indices(Some(vec![ValidatorStatus::ActiveOngoing])),
+            vec![0, 2]
+        );
+        assert_eq!(
+            selected_indices(Some(vec![ValidatorStatus::Offline])),
+            vec![1]
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use actix_web::{
+    HttpResponse, Responder, get,
+    web::{Data, Path, Query},
+};
+use ream_beacon_api_types::{error::ApiError, query::EpochRangeQuery, responses::DataResponse};
+use ream_consensus_beacon::validator_income::ValidatorIncome;
+use ream_storage::db::ReamDB;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct EpochIncome {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub epoch: u64,
+    #[serde(flatten)]
+    pub income: ValidatorIncome,
+    /// The net balance change, which may be negative.
+    pub total: String,
+}
+
+/// Called by `/ream/v1/validator/{validator_index}/income` to get the recorded per-epoch income
+/// of a monitored validator. Optional `start_epoch` and `end_epoch` bound the epochs returned.
+#[get("/validator/{validator_index}/income")]
+pub async fn get_validator_income(
+    db: Data<ReamDB>,
+    validator_index: Path<u64>,
+    query: Query<EpochRangeQuery>,
+) -> Result<impl Responder, ApiError> {
+    let validator_index = validator_index.into_inner();
+    let start_epoch = query.start_epoch.unwrap_or_default();
+    let end_epoch = query.end_epoch.unwrap_or(u64::MAX);
+    if start_epoch > end_epoch {
+        return Err(ApiError::BadRequest(format!(
+            "start_epoch {start_epoch} is after end_epoch {end_epoch}"
+        )));
+    }
+
+    let incomes = db
+        .validator_income_provider()
+        .get_range(validator_index, start_epoch, end_epoch)
+        .map_err(|err| {
+            ApiError::InternalError(format!(
+                "Failed to get income of validator {validator_index}: {err:?}"
+            ))
+        })?
+        .into_iter()
+        .map(|(epoch, income)| EpochIncome {
+            epoch,
+            total: income.total().to_string(),
+            income,
+        })
+        .collect::<Vec<_>>();
+
+    Ok(HttpResponse::Ok().json(DataResponse::new(incomes)))
+}
@@ -2,10 +2,13 @@ use std::sync::Arc;
 
 use actix_web::{App, HttpServer, dev::ServerHandle, middleware, web::Data};
 use config::RpcServerConfig;
+use ream_beacon_api_types::event::BeaconEvent;
+use ream_beacon_chain::beacon_chain::BeaconChain;
 use ream_execution_engine::ExecutionEngine;
 use ream_operation_pool::OperationPool;
-use ream_p2p::network_state::NetworkState;
-use ream_storage::db::ReamDB;
+use ream_p2p::{channel::P2PMessage, network_state::NetworkState};
+use ream_storage::{cache::CachedDB, db::ReamDB};
+use tokio::sync::{broadcast, mpsc};
 use tracing::info;
 
 use crate::routes::register_routers;
@@ -15,12 +18,17 @@ pub mod handlers;
 pub mod routes;
 
 /// Start the Beacon API server.
+#[allow(clippy::too_many_arguments)]
 pub async fn start_server(
     server_config: RpcServerConfig,
     db: ReamDB,
     network_state: Arc<NetworkState>,
     operation_pool: Arc<OperationPool>,
     execution_engine: Option<ExecutionEngine>,
+    event_sender: broadcast::Sender<BeaconEvent>,
+    beacon_chain: Arc<BeaconChain>,
+    cached_db: Arc<CachedDB>,
+    p2p_sender: mpsc::UnboundedSender<P2PMessage>,
 ) -> std::io::Result<()> {
     info!(
         "starting HTTP server on {:?}",
@@ -28,17 +36,25 @@ pub async fn start_server(
     );
     // create the stop handle container
     let stop_handle = Data::new(StopHandle::default());
+    let admin_config = server_config.admin.map(Data::new);
 
     let server = HttpServer::new(move || {
         let stop_handle = stop_handle.clone();
-        App::new()
+        let mut app = App::new()
             .wrap(middleware::Logger::default())
             .app_data(stop_handle)
             .app_data(Data::new(db.clone()))
             .app_data(Data::new(network_state.clone()))
             .app_data(Data::new(operation_pool.clone()))
             .app_data(Data::new(execution_engine.clone()))
-            .configure(register_routers)
+            .app_data(Data::new(event_sender.clone()))
+            .app_data(Data::new(beacon_chain.clone()))
+            .app_data(Data::new(cached_db.clone()))
+            .app_data(Data::new(p2p_sender.clone()));
+        if let Some(admin_config) = &admin_config {
+            app = app.app_data(admin_config.clone());
+        }
+        app.configure(register_routers)
     })
     .bind(server_config.http_socket_address)?
     .run();
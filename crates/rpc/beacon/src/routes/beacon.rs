@@ -4,7 +4,7 @@ use crate::handlers::{
     blob_sidecar::get_blob_sidecars,
     block::{
         get_blind_block, get_block_attestations, get_block_from_id, get_block_rewards,
-        get_block_root, get_genesis, post_sync_committee_rewards,
+        get_block_root, get_genesis, post_block, post_sync_committee_rewards,
     },
     committee::get_committees,
     header::{get_headers, get_headers_from_block},
@@ -15,6 +15,7 @@ use crate::handlers::{
         get_bls_to_execution_changes, get_voluntary_exits, post_bls_to_execution_changes,
         post_voluntary_exits,
     },
+    proof::get_historical_block_proof,
     state::{
         get_pending_consolidations, get_pending_deposits, get_pending_partial_withdrawals,
         get_state_finality_checkpoint, get_state_fork, get_state_randao, get_state_root,
@@ -36,6 +37,7 @@ pub fn register_beacon_routes(cfg: &mut ServiceConfig) {
         .service(get_genesis)
         .service(get_headers)
         .service(get_headers_from_block)
+        .service(get_historical_block_proof)
         .service(get_pending_consolidations)
         .service(get_pending_deposits)
         .service(get_pending_partial_withdrawals)
@@ -64,5 +66,6 @@ pub fn register_beacon_routes(cfg: &mut ServiceConfig) {
 
 pub fn register_beacon_routes_v2(cfg: &mut ServiceConfig) {
     cfg.service(get_block_attestations)
-        .service(get_block_from_id);
+        .service(get_block_from_id)
+        .service(post_block);
 }
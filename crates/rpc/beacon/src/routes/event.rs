@@ -0,0 +1,8 @@
+use actix_web::web::ServiceConfig;
+
+use crate::handlers::event::get_events;
+
+/// Creates and returns all `/events` routes.
+pub fn register_event_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_events);
+}
@@ -3,7 +3,9 @@ use actix_web::web::{ServiceConfig, scope};
 pub mod beacon;
 pub mod config;
 pub mod debug;
+pub mod event;
 pub mod node;
+pub mod ream;
 pub mod validator;
 
 pub fn get_v1_routes(config: &mut ServiceConfig) {
@@ -13,7 +15,8 @@ pub fn get_v1_routes(config: &mut ServiceConfig) {
             .configure(node::register_node_routes)
             .configure(config::register_config_routes)
             .configure(validator::register_validator_routes)
-            .configure(debug::register_debug_routes_v1),
+            .configure(debug::register_debug_routes_v1)
+            .configure(event::register_event_routes),
     );
 }
 
@@ -25,6 +28,13 @@ pub fn get_v2_routes(config: &mut ServiceConfig) {
     );
 }
 
+pub fn get_ream_v1_routes(config: &mut ServiceConfig) {
+    config.service(scope("/ream/v1").configure(ream::register_ream_routes));
+}
+
 pub fn register_routers(config: &mut ServiceConfig) {
-    config.configure(get_v1_routes).configure(get_v2_routes);
+    config
+        .configure(get_v1_routes)
+        .configure(get_v2_routes)
+        .configure(get_ream_v1_routes);
 }
@@ -0,0 +1,24 @@
+use actix_web::web::ServiceConfig;
+
+use crate::handlers::{
+    admin::{
+        get_queue_dump, post_clear_caches, post_disconnect_peer, post_recompute_head,
+        put_log_filter,
+    },
+    monitoring::{get_cache_stats, get_head_votes, get_queue_depths, get_sync_metrics},
+    validator_income::get_validator_income,
+};
+
+/// Registers the node-specific routes, which are not part of the standard beacon API.
+pub fn register_ream_routes(config: &mut ServiceConfig) {
+    config.service(get_cache_stats);
+    config.service(get_queue_depths);
+    config.service(get_sync_metrics);
+    config.service(get_head_votes);
+    config.service(get_validator_income);
+    config.service(put_log_filter);
+    config.service(post_disconnect_peer);
+    config.service(post_clear_caches);
+    config.service(post_recompute_head);
+    config.service(get_queue_dump);
+}
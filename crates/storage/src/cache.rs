@@ -1,12 +1,57 @@
-use std::num::NonZeroUsize;
+use std::{
+    hash::Hash,
+    num::NonZeroUsize,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
 
+use alloy_primitives::B256;
 use lru::LruCache;
 use ream_bls::{BLSSignature, PublicKey};
-use ream_consensus_beacon::bls_to_execution_change::BLSToExecutionChange;
-use tokio::sync::RwLock;
+use ream_consensus_beacon::{
+    bls_to_execution_change::BLSToExecutionChange,
+    committee_cache::{CommitteeCache, ShufflingId},
+    electra::beacon_state::BeaconState,
+    pubkey_cache::PubkeyCache,
+};
+use ream_consensus_misc::{
+    beacon_block_header::SignedBeaconBlockHeader,
+    constants::{MAX_BLOBS_PER_BLOCK_ELECTRA, SLOTS_PER_EPOCH, SYNC_COMMITTEE_SIZE},
+};
+use tokio::sync::{RwLock, RwLockReadGuard};
 
 const LRU_CACHE_SIZE: usize = 64;
 
+/// The default capacity of the caches of attestations and aggregates. The caches are allocated
+/// upfront, so this trades memory for how many attesters of the subscribed subnets are tracked.
+pub const DEFAULT_SEEN_MESSAGE_CAPACITY: usize = 1 << 18;
+
+const DEFAULT_SECONDS_PER_SLOT: u64 = 12;
+
+const SEEN_BLOCK_ROOTS_CAPACITY: usize = 1024;
+
+/// The previous, current and next epochs of a few competing forks.
+const COMMITTEE_CACHES_CAPACITY: usize = 16;
+
+const SEEN_SYNC_MESSAGES_CAPACITY: usize =
+    (SYNC_COMMITTEE_SIZE * SEEN_SYNC_MESSAGE_TTL_SLOTS) as usize;
+
+const SEEN_BLOB_SIDECARS_CAPACITY: usize =
+    2 * SLOTS_PER_EPOCH as usize * MAX_BLOBS_PER_BLOCK_ELECTRA as usize;
+
+/// Blocks and blob sidecars are only gossiped until their slot is finalized, and rarely more than
+/// an epoch late.
+const SEEN_BLOCK_TTL_SLOTS: u64 = SLOTS_PER_EPOCH;
+
+/// Attestations and aggregates are gossiped during the epoch of their slot and the next one.
+const SEEN_ATTESTATION_TTL_SLOTS: u64 = 2 * SLOTS_PER_EPOCH;
+
+/// Sync committee messages are only gossiped during their slot.
+const SEEN_SYNC_MESSAGE_TTL_SLOTS: u64 = 2;
+
 #[derive(Debug, Hash, PartialEq, Eq, Default, Clone)]
 pub struct AddressSlotIdentifier {
     pub address: PublicKey,
@@ -33,62 +78,365 @@ pub struct SyncCommitteeKey {
     pub validator_index: u64,
 }
 
+/// Lookup counters and occupancy of a cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub len: usize,
+    pub capacity: usize,
+}
+
+impl CacheStats {
+    /// Returns the share of the lookups which hit, or `None` before the first lookup.
+    pub fn hit_rate(&self) -> Option<f64> {
+        let lookups = self.hits + self.misses;
+        (lookups > 0).then(|| self.hits as f64 / lookups as f64)
+    }
+}
+
+/// An LRU cache of the messages already seen, which counts how many of its lookups hit.
+///
+/// Entries of a cache with a time to live are treated as absent once it elapsed, and are dropped
+/// from the least recently used end of the cache as new entries are inserted.
+#[derive(Debug)]
+pub struct SeenCache<K: Hash + Eq, V> {
+    cache: LruCache<K, (V, Instant)>,
+    ttl: Option<Duration>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<K: Hash + Eq, V> SeenCache<K, V> {
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            cache: LruCache::new(capacity),
+            ttl: None,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn with_ttl(capacity: NonZeroUsize, ttl: Duration) -> Self {
+        Self {
+            ttl: Some(ttl),
+            ..Self::new(capacity)
+        }
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.record_lookup(self.get_live(key).is_some())
+    }
+
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        let value = self.get_live(key);
+        self.record_lookup(value.is_some());
+        value
+    }
+
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        let now = Instant::now();
+        if let Some(ttl) = self.ttl {
+            while self
+                .cache
+                .peek_lru()
+                .is_some_and(|(_, (_, inserted_at))| now.duration_since(*inserted_at) >= ttl)
+            {
+                self.cache.pop_lru();
+            }
+        }
+        self.cache.put(key, (value, now)).map(|(value, _)| value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.cache
+            .iter()
+            .filter(|(_, (_, inserted_at))| !self.is_expired(inserted_at))
+            .map(|(key, (value, _))| (key, value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Empties the cache and resets its statistics.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        *self.hits.get_mut() = 0;
+        *self.misses.get_mut() = 0;
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            len: self.cache.len(),
+            capacity: self.cache.cap().get(),
+        }
+    }
+
+    fn get_live(&self, key: &K) -> Option<&V> {
+        self.cache
+            .peek(key)
+            .filter(|(_, inserted_at)| !self.is_expired(inserted_at))
+            .map(|(value, _)| value)
+    }
+
+    fn is_expired(&self, inserted_at: &Instant) -> bool {
+        self.ttl.is_some_and(|ttl| inserted_at.elapsed() >= ttl)
+    }
+
+    fn record_lookup(&self, hit: bool) -> bool {
+        let counter = if hit { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        hit
+    }
+}
+
+/// Sizes the caches of the gossip messages which arrive at the rate of the validators rather than
+/// of the blocks: attestations and aggregates.
+#[derive(Debug, Clone, Copy)]
+pub struct CachedDBConfig {
+    pub seen_message_capacity: NonZeroUsize,
+    /// The time to live of the entries is expressed in slots of this duration.
+    pub slot_duration: Duration,
+}
+
+impl Default for CachedDBConfig {
+    fn default() -> Self {
+        Self {
+            seen_message_capacity: NonZeroUsize::new(DEFAULT_SEEN_MESSAGE_CAPACITY)
+                .expect("Invalid cache size"),
+            slot_duration: Duration::from_secs(DEFAULT_SECONDS_PER_SLOT),
+        }
+    }
+}
+
 /// In-memory LRU cache.
 #[derive(Debug)]
 pub struct CachedDB {
-    pub seen_proposer_signature: RwLock<LruCache<AddressSlotIdentifier, BLSSignature>>,
+    pub seen_proposer_signature: RwLock<SeenCache<AddressSlotIdentifier, BLSSignature>>,
+    pub seen_block_headers: RwLock<SeenCache<(u64, u64), SignedBeaconBlockHeader>>,
+    pub seen_block_roots: RwLock<SeenCache<B256, ()>>,
     pub seen_bls_to_execution_signature:
-        RwLock<LruCache<AddressSlotIdentifier, BLSToExecutionChange>>,
-    pub seen_blob_sidecars: RwLock<LruCache<(u64, u64, u64), ()>>,
-    pub seen_attestations: RwLock<LruCache<AtestationKey, ()>>,
-    pub seen_bls_to_execution_change: RwLock<LruCache<AddressValidaterIndexIdentifier, ()>>,
-    pub seen_sync_messages: RwLock<LruCache<SyncCommitteeKey, ()>>,
-    pub seen_voluntary_exit: RwLock<LruCache<u64, ()>>,
-    pub seen_proposer_slashings: RwLock<LruCache<u64, ()>>,
-    pub prior_seen_attester_slashing_indices: RwLock<LruCache<u64, ()>>,
+        RwLock<SeenCache<AddressSlotIdentifier, BLSToExecutionChange>>,
+    pub seen_blob_sidecars: RwLock<SeenCache<(u64, u64, u64), ()>>,
+    pub seen_attestations: RwLock<SeenCache<AtestationKey, ()>>,
+    /// Digests of the attestation data and bits of the aggregates already seen.
+    pub seen_aggregates: RwLock<SeenCache<B256, ()>>,
+    /// The aggregators which already sent a valid aggregate, keyed by aggregator index and
+    /// target epoch.
+    pub seen_aggregators: RwLock<SeenCache<(u64, u64), ()>>,
+    pub seen_bls_to_execution_change: RwLock<SeenCache<AddressValidaterIndexIdentifier, ()>>,
+    pub seen_sync_messages: RwLock<SeenCache<SyncCommitteeKey, ()>>,
+    pub seen_voluntary_exit: RwLock<SeenCache<u64, ()>>,
+    pub seen_proposer_slashings: RwLock<SeenCache<u64, ()>>,
+    pub prior_seen_attester_slashing_indices: RwLock<SeenCache<u64, ()>>,
+    /// The committees of the recent epochs, shared by the states of the same shuffling.
+    pub committee_caches: RwLock<SeenCache<ShufflingId, Arc<CommitteeCache>>>,
+    /// The proposers of each slot of the recent epochs, shared by the states of the same
+    /// proposer shuffling.
+    pub proposer_indices: RwLock<SeenCache<ShufflingId, Arc<Vec<u64>>>>,
+    /// The validator indices by public key, updated from the states looked up in.
+    pub pubkey_cache: RwLock<PubkeyCache>,
 }
 
 impl CachedDB {
     pub fn new() -> Self {
+        Self::with_config(CachedDBConfig::default())
+    }
+
+    pub fn with_config(config: CachedDBConfig) -> Self {
+        let capacity = NonZeroUsize::new(LRU_CACHE_SIZE).expect("Invalid cache size");
+        let block_capacity =
+            NonZeroUsize::new(SEEN_BLOCK_ROOTS_CAPACITY).expect("Invalid cache size");
+        let blob_sidecar_capacity =
+            NonZeroUsize::new(SEEN_BLOB_SIDECARS_CAPACITY).expect("Invalid cache size");
+        let sync_message_capacity =
+            NonZeroUsize::new(SEEN_SYNC_MESSAGES_CAPACITY).expect("Invalid cache size");
+        let committee_capacity =
+            NonZeroUsize::new(COMMITTEE_CACHES_CAPACITY).expect("Invalid cache size");
+        let message_capacity = config.seen_message_capacity;
+        let ttl = |slots: u64| config.slot_duration * slots as u32;
         Self {
-            seen_proposer_signature: LruCache::new(
-                NonZeroUsize::new(LRU_CACHE_SIZE).expect("Invalid cache size"),
-            )
-            .into(),
-            seen_bls_to_execution_signature: LruCache::new(
-                NonZeroUsize::new(LRU_CACHE_SIZE).expect("Invalid cache size"),
-            )
-            .into(),
-            seen_blob_sidecars: LruCache::new(
-                NonZeroUsize::new(LRU_CACHE_SIZE).expect("Invalid cache size"),
-            )
-            .into(),
-            seen_attestations: LruCache::new(
-                NonZeroUsize::new(LRU_CACHE_SIZE).expect("Invalid cache size"),
-            )
-            .into(),
-            seen_bls_to_execution_change: LruCache::new(
-                NonZeroUsize::new(LRU_CACHE_SIZE).expect("Invalid cache size"),
-            )
-            .into(),
-            seen_sync_messages: LruCache::new(
-                NonZeroUsize::new(LRU_CACHE_SIZE).expect("Invalid cache size"),
+            seen_proposer_signature: SeenCache::new(capacity).into(),
+            seen_block_headers: SeenCache::new(capacity).into(),
+            seen_block_roots: SeenCache::with_ttl(block_capacity, ttl(SEEN_BLOCK_TTL_SLOTS)).into(),
+            seen_bls_to_execution_signature: SeenCache::new(capacity).into(),
+            seen_blob_sidecars: SeenCache::with_ttl(
+                blob_sidecar_capacity,
+                ttl(SEEN_BLOCK_TTL_SLOTS),
             )
             .into(),
-            seen_voluntary_exit: LruCache::new(
-                NonZeroUsize::new(LRU_CACHE_SIZE).expect("Invalid cache size"),
+            seen_attestations: SeenCache::with_ttl(
+                message_capacity,
+                ttl(SEEN_ATTESTATION_TTL_SLOTS),
             )
             .into(),
-            seen_proposer_slashings: LruCache::new(
-                NonZeroUsize::new(LRU_CACHE_SIZE).expect("Invalid cache size"),
+            seen_aggregates: SeenCache::with_ttl(message_capacity, ttl(SEEN_ATTESTATION_TTL_SLOTS))
+                .into(),
+            seen_aggregators: SeenCache::with_ttl(
+                message_capacity,
+                ttl(SEEN_ATTESTATION_TTL_SLOTS),
             )
             .into(),
-            prior_seen_attester_slashing_indices: LruCache::new(
-                NonZeroUsize::new(LRU_CACHE_SIZE).expect("Invalid cache size"),
+            seen_bls_to_execution_change: SeenCache::new(capacity).into(),
+            seen_sync_messages: SeenCache::with_ttl(
+                sync_message_capacity,
+                ttl(SEEN_SYNC_MESSAGE_TTL_SLOTS),
             )
             .into(),
+            seen_voluntary_exit: SeenCache::new(capacity).into(),
+            seen_proposer_slashings: SeenCache::new(capacity).into(),
+            prior_seen_attester_slashing_indices: SeenCache::new(capacity).into(),
+            committee_caches: SeenCache::new(committee_capacity).into(),
+            proposer_indices: SeenCache::new(committee_capacity).into(),
+            pubkey_cache: RwLock::default(),
         }
     }
+
+    /// Returns the committees of `epoch` for `state`, whose latest block is `block_root`,
+    /// shuffling them only the first time they are requested for its shuffling.
+    pub async fn get_committee_cache(
+        &self,
+        state: &BeaconState,
+        block_root: B256,
+        epoch: u64,
+    ) -> anyhow::Result<Arc<CommitteeCache>> {
+        let shuffling_id = ShufflingId::new(state, block_root, epoch)?;
+        if let Some(committee_cache) = self.committee_caches.read().await.peek(&shuffling_id) {
+            return Ok(committee_cache.clone());
+        }
+
+        let committee_cache = Arc::new(CommitteeCache::new(state, epoch)?);
+        self.committee_caches
+            .write()
+            .await
+            .put(shuffling_id, committee_cache.clone());
+        Ok(committee_cache)
+    }
+
+    /// Returns the proposers of every slot of `epoch` for `state`, whose latest block is
+    /// `block_root`, computing them only the first time they are requested for its proposer
+    /// shuffling.
+    pub async fn get_proposer_indices(
+        &self,
+        state: &BeaconState,
+        block_root: B256,
+        epoch: u64,
+    ) -> anyhow::Result<Arc<Vec<u64>>> {
+        let shuffling_id = ShufflingId::for_proposers(state, block_root, epoch)?;
+        if let Some(proposer_indices) = self.proposer_indices.read().await.peek(&shuffling_id) {
+            return Ok(proposer_indices.clone());
+        }
+
+        let proposer_indices = Arc::new(state.get_beacon_proposer_indices_for_epoch(epoch)?);
+        self.proposer_indices
+            .write()
+            .await
+            .put(shuffling_id, proposer_indices.clone());
+        Ok(proposer_indices)
+    }
+
+    /// Returns the validator indices by public key, updated with the validators of `state`.
+    pub async fn get_pubkey_cache(&self, state: &BeaconState) -> RwLockReadGuard<'_, PubkeyCache> {
+        let mut pubkey_cache = self.pubkey_cache.write().await;
+        pubkey_cache.update(state);
+        pubkey_cache.downgrade()
+    }
+
+    /// Returns the statistics of each cache, by name.
+    pub async fn cache_stats(&self) -> Vec<(&'static str, CacheStats)> {
+        vec![
+            (
+                "seen_proposer_signature",
+                self.seen_proposer_signature.read().await.stats(),
+            ),
+            (
+                "seen_block_headers",
+                self.seen_block_headers.read().await.stats(),
+            ),
+            (
+                "seen_block_roots",
+                self.seen_block_roots.read().await.stats(),
+            ),
+            (
+                "seen_bls_to_execution_signature",
+                self.seen_bls_to_execution_signature.read().await.stats(),
+            ),
+            (
+                "seen_blob_sidecars",
+                self.seen_blob_sidecars.read().await.stats(),
+            ),
+            (
+                "seen_attestations",
+                self.seen_attestations.read().await.stats(),
+            ),
+            ("seen_aggregates", self.seen_aggregates.read().await.stats()),
+            (
+                "seen_aggregators",
+                self.seen_aggregators.read().await.stats(),
+            ),
+            (
+                "seen_bls_to_execution_change",
+                self.seen_bls_to_execution_change.read().await.stats(),
+            ),
+            (
+                "seen_sync_messages",
+                self.seen_sync_messages.read().await.stats(),
+            ),
+            (
+                "seen_voluntary_exit",
+                self.seen_voluntary_exit.read().await.stats(),
+            ),
+            (
+                "seen_proposer_slashings",
+                self.seen_proposer_slashings.read().await.stats(),
+            ),
+            (
+                "prior_seen_attester_slashing_indices",
+                self.prior_seen_attester_slashing_indices
+                    .read()
+                    .await
+                    .stats(),
+            ),
+            (
+                "committee_caches",
+                self.committee_caches.read().await.stats(),
+            ),
+            (
+                "proposer_indices",
+                self.proposer_indices.read().await.stats(),
+            ),
+        ]
+    }
+
+    /// Empties every cache, so that messages seen before are validated again.
+    pub async fn clear(&self) {
+        self.seen_proposer_signature.write().await.clear();
+        self.seen_block_headers.write().await.clear();
+        self.seen_block_roots.write().await.clear();
+        self.seen_bls_to_execution_signature.write().await.clear();
+        self.seen_blob_sidecars.write().await.clear();
+        self.seen_attestations.write().await.clear();
+        self.seen_aggregates.write().await.clear();
+        self.seen_aggregators.write().await.clear();
+        self.seen_bls_to_execution_change.write().await.clear();
+        self.seen_sync_messages.write().await.clear();
+        self.seen_voluntary_exit.write().await.clear();
+        self.seen_proposer_slashings.write().await.clear();
+        self.prior_seen_attester_slashing_indices
+            .write()
+            .await
+            .clear();
+        self.committee_caches.write().await.clear();
+        self.proposer_indices.write().await.clear();
+        *self.pubkey_cache.write().await = PubkeyCache::default();
+    }
 }
 
 impl Default for CachedDB {
@@ -96,3 +444,42 @@ impl Default for CachedDB {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::thread::sleep;
+
+    use super::*;
+
+    #[test]
+    fn test_seen_cache_expires_entries() {
+        let mut cache = SeenCache::with_ttl(
+            NonZeroUsize::new(4).expect("Invalid cache size"),
+            Duration::from_millis(50),
+        );
+        cache.put(1u64, ());
+        assert!(cache.contains(&1));
+
+        sleep(Duration::from_millis(60));
+        assert!(!cache.contains(&1));
+        assert_eq!(cache.iter().count(), 0);
+
+        // Expired entries are dropped as new ones are inserted
+        cache.put(2, ());
+        assert_eq!(cache.len(), 1);
+        assert!(cache.contains(&2));
+        assert_eq!(cache.stats().hits, 2);
+        assert_eq!(cache.stats().misses, 1);
+    }
+
+    #[test]
+    fn test_seen_cache_without_ttl_evicts_least_recently_used() {
+        let mut cache = SeenCache::new(NonZeroUsize::new(2).expect("Invalid cache size"));
+        cache.put(1u64, ());
+        cache.put(2, ());
+        cache.put(3, ());
+        assert!(!cache.contains(&1));
+        assert!(cache.contains(&2));
+        assert!(cache.contains(&3));
+    }
+}
@@ -11,6 +11,7 @@ use crate::{
         Table,
         beacon_block::{BEACON_BLOCK_TABLE, BeaconBlockTable},
         beacon_state::{BEACON_STATE_TABLE, BeaconStateTable},
+        blinded_beacon_block::{BLINDED_BEACON_BLOCK_TABLE, BlindedBeaconBlockTable},
         blobs_and_proofs::{BLOB_FOLDER_NAME, BlobsAndProofsTable},
         block_timeliness::{BLOCK_TIMELINESS_TABLE, BlockTimelinessTable},
         checkpoint_states::{CHECKPOINT_STATES_TABLE, CheckpointStatesTable},
@@ -19,6 +20,7 @@ use crate::{
         genesis_time::{GENESIS_TIME_FIELD, GenesisTimeField},
         justified_checkpoint::{JUSTIFIED_CHECKPOINT_FIELD, JustifiedCheckpointField},
         latest_messages::{LATEST_MESSAGES_TABLE, LatestMessagesTable},
+        next_slot_to_blind::{NEXT_SLOT_TO_BLIND_FIELD, NextSlotToBlindField},
         parent_root_index::{PARENT_ROOT_INDEX_MULTIMAP_TABLE, ParentRootIndexMultimapTable},
         proposer_boost_root::{PROPOSER_BOOST_ROOT_FIELD, ProposerBoostRootField},
         slot_index::{SLOT_INDEX_TABLE, SlotIndexTable},
@@ -33,6 +35,7 @@ use crate::{
         unrealized_justified_checkpoint::{
             UNREALIZED_JUSTIFED_CHECKPOINT_FIELD, UnrealizedJustifiedCheckpointField,
         },
+        validator_income::{VALIDATOR_INCOME_TABLE, ValidatorIncomeTable},
     },
 };
 
@@ -58,6 +61,7 @@ impl ReamDB {
         let write_txn = db.begin_write()?;
         write_txn.open_table(BEACON_BLOCK_TABLE)?;
         write_txn.open_table(BEACON_STATE_TABLE)?;
+        write_txn.open_table(BLINDED_BEACON_BLOCK_TABLE)?;
         write_txn.open_table(BLOCK_TIMELINESS_TABLE)?;
         write_txn.open_table(CHECKPOINT_STATES_TABLE)?;
         write_txn.open_table(EQUIVOCATING_INDICES_FIELD)?;
@@ -65,6 +69,7 @@ impl ReamDB {
         write_txn.open_table(GENESIS_TIME_FIELD)?;
         write_txn.open_table(JUSTIFIED_CHECKPOINT_FIELD)?;
         write_txn.open_table(LATEST_MESSAGES_TABLE)?;
+        write_txn.open_table(NEXT_SLOT_TO_BLIND_FIELD)?;
         write_txn.open_multimap_table(PARENT_ROOT_INDEX_MULTIMAP_TABLE)?;
         write_txn.open_table(PROPOSER_BOOST_ROOT_FIELD)?;
         write_txn.open_table(SLOT_INDEX_TABLE)?;
@@ -73,6 +78,7 @@ impl ReamDB {
         write_txn.open_table(UNREALIZED_FINALIZED_CHECKPOINT_FIELD)?;
         write_txn.open_table(UNREALIZED_JUSTIFICATIONS_TABLE)?;
         write_txn.open_table(UNREALIZED_JUSTIFED_CHECKPOINT_FIELD)?;
+        write_txn.open_table(VALIDATOR_INCOME_TABLE)?;
         write_txn.commit()?;
 
         fs::create_dir_all(data_dir.join(BLOB_FOLDER_NAME))?;
@@ -95,6 +101,12 @@ impl ReamDB {
         }
     }
 
+    pub fn blinded_beacon_block_provider(&self) -> BlindedBeaconBlockTable {
+        BlindedBeaconBlockTable {
+            db: self.db.clone(),
+        }
+    }
+
     pub fn blobs_and_proofs_provider(&self) -> BlobsAndProofsTable {
         BlobsAndProofsTable {
             data_dir: self.data_dir.clone(),
@@ -167,6 +179,12 @@ impl ReamDB {
         }
     }
 
+    pub fn next_slot_to_blind_provider(&self) -> NextSlotToBlindField {
+        NextSlotToBlindField {
+            db: self.db.clone(),
+        }
+    }
+
     pub fn time_provider(&self) -> TimeField {
         TimeField {
             db: self.db.clone(),
@@ -191,6 +209,12 @@ impl ReamDB {
         }
     }
 
+    pub fn validator_income_provider(&self) -> ValidatorIncomeTable {
+        ValidatorIncomeTable {
+            db: self.db.clone(),
+        }
+    }
+
     pub fn is_initialized(&self) -> bool {
         match self.slot_index_provider().get_highest_slot() {
             Ok(Some(slot)) => slot > 0,
@@ -2,12 +2,13 @@ use std::sync::Arc;
 
 use alloy_primitives::B256;
 use ream_consensus_beacon::electra::beacon_block::SignedBeaconBlock;
-use redb::{Database, Durability, TableDefinition};
+use redb::{Database, Durability, ReadableTable, TableDefinition};
 use tree_hash::TreeHash;
 
 use super::{
-    MultimapTable, SSZEncoding, Table, parent_root_index::ParentRootIndexMultimapTable,
-    slot_index::SlotIndexTable, state_root_index::StateRootIndexTable,
+    MultimapTable, SSZBytes, SSZEncoding, Table, blinded_beacon_block::BLINDED_BEACON_BLOCK_TABLE,
+    parent_root_index::ParentRootIndexMultimapTable, slot_index::SlotIndexTable,
+    state_root_index::StateRootIndexTable,
 };
 use crate::errors::StoreError;
 
@@ -18,10 +19,51 @@ use crate::errors::StoreError;
 pub const BEACON_BLOCK_TABLE: TableDefinition<SSZEncoding<B256>, SSZEncoding<SignedBeaconBlock>> =
     TableDefinition::new("beacon_block");
 
+/// The Beacon Block table read as the stored SSZ bytes of the blocks
+pub const BEACON_BLOCK_BYTES_TABLE: TableDefinition<
+    SSZEncoding<B256>,
+    SSZBytes<SignedBeaconBlock>,
+> = TableDefinition::new("beacon_block");
+
 pub struct BeaconBlockTable {
     pub db: Arc<Database>,
 }
 
+impl BeaconBlockTable {
+    /// Returns the SSZ encoding of the full block stored for `key` as is, without decoding it.
+    /// Blinded blocks are not returned.
+    pub fn get_ssz_bytes(&self, key: B256) -> Result<Option<Vec<u8>>, StoreError> {
+        let read_txn = self.db.begin_read()?;
+
+        let table = read_txn.open_table(BEACON_BLOCK_BYTES_TABLE)?;
+        let result = table.get(key)?;
+        Ok(result.map(|res| res.value().to_vec()))
+    }
+
+    /// Moves the block to the blinded beacon block table, dropping its execution payload. The
+    /// indices of the block are kept. Returns `false` if no full block with an execution payload
+    /// is stored for `key`.
+    pub fn blind(&self, key: B256) -> Result<bool, StoreError> {
+        let mut write_txn = self.db.begin_write()?;
+        write_txn.set_durability(Durability::Immediate);
+        let mut table = write_txn.open_table(BEACON_BLOCK_TABLE)?;
+        let Some(block) = table.get(key)?.map(|block| block.value()) else {
+            return Ok(false);
+        };
+        // The genesis block has no payload for the execution engine to return
+        if block.message.body.execution_payload.block_hash == B256::ZERO {
+            return Ok(false);
+        }
+        table.remove(key)?;
+        let mut blinded_table = write_txn.open_table(BLINDED_BEACON_BLOCK_TABLE)?;
+        blinded_table.insert(key, block.as_signed_blinded_beacon_block())?;
+        drop(table);
+        drop(blinded_table);
+        write_txn.commit()?;
+        Ok(true)
+    }
+}
+
 impl Table for BeaconBlockTable {
     type Key = B256;
 
@@ -62,3 +104,40 @@ impl Table for BeaconBlockTable {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use alloy_primitives::B256;
+    use ream_bls::BLSSignature;
+    use ream_consensus_beacon::electra::beacon_block::{BeaconBlock, SignedBeaconBlock};
+    use redb::Database;
+    use ssz::Encode;
+    use tempdir::TempDir;
+
+    use crate::{
+        errors::StoreError,
+        tables::{Table, beacon_block::BeaconBlockTable},
+    };
+
+    #[test]
+    fn test_get_ssz_bytes() -> Result<(), StoreError> {
+        let tmp_dir = TempDir::new("test_get_ssz_bytes")?;
+        let table = BeaconBlockTable {
+            db: Arc::new(Database::create(tmp_dir.path().join("ream.redb"))?),
+        };
+
+        let block = SignedBeaconBlock {
+            message: BeaconBlock::default(),
+            signature: BLSSignature::default(),
+        };
+        table.insert(B256::ZERO, block.clone())?;
+
+        assert_eq!(table.get_ssz_bytes(B256::ZERO)?, Some(block.as_ssz_bytes()));
+        assert_eq!(table.get(B256::ZERO)?, Some(block));
+        assert_eq!(table.get_ssz_bytes(B256::repeat_byte(1))?, None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use alloy_primitives::B256;
+use ream_consensus_beacon::electra::blinded_beacon_block::SignedBlindedBeaconBlock;
+use redb::{Database, Durability, TableDefinition};
+
+use super::{SSZEncoding, Table};
+use crate::errors::StoreError;
+
+/// Table definition for the Blinded Beacon Block table, which holds the finalized blocks whose
+/// execution payloads were dropped to save disk space
+///
+/// Key: block_id
+/// Value: SignedBlindedBeaconBlock
+pub const BLINDED_BEACON_BLOCK_TABLE: TableDefinition<
+    SSZEncoding<B256>,
+    SSZEncoding<SignedBlindedBeaconBlock>,
+> = TableDefinition::new("blinded_beacon_block");
+
+pub struct BlindedBeaconBlockTable {
+    pub db: Arc<Database>,
+}
+
+impl Table for BlindedBeaconBlockTable {
+    type Key = B256;
+
+    type Value = SignedBlindedBeaconBlock;
+
+    fn get(&self, key: Self::Key) -> Result<Option<Self::Value>, StoreError> {
+        let read_txn = self.db.begin_read()?;
+
+        let table = read_txn.open_table(BLINDED_BEACON_BLOCK_TABLE)?;
+        let result = table.get(key)?;
+        Ok(result.map(|res| res.value()))
+    }
+
+    fn insert(&self, key: Self::Key, value: Self::Value) -> Result<(), StoreError> {
+        let mut write_txn = self.db.begin_write()?;
+        write_txn.set_durability(Durability::Immediate);
+        let mut table = write_txn.open_table(BLINDED_BEACON_BLOCK_TABLE)?;
+        table.insert(key, value)?;
+        drop(table);
+        write_txn.commit()?;
+        Ok(())
+    }
+}
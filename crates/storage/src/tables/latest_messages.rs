@@ -1,7 +1,8 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
+use alloy_primitives::B256;
 use ream_consensus_beacon::fork_choice::latest_message::LatestMessage;
-use redb::{Database, Durability, TableDefinition};
+use redb::{Database, Durability, ReadableTable, TableDefinition};
 
 use super::{SSZEncoding, Table};
 use crate::errors::StoreError;
@@ -40,3 +41,17 @@ impl Table for LatestMessagesTable {
         Ok(())
     }
 }
+
+impl LatestMessagesTable {
+    /// Returns the number of validators whose latest message votes for each block root.
+    pub fn count_votes(&self) -> Result<HashMap<B256, u64>, StoreError> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(LATEST_MESSAGES_TABLE)?;
+        let mut votes = HashMap::new();
+        for result in table.iter()? {
+            let (_, latest_message) = result?;
+            *votes.entry(latest_message.value().root).or_default() += 1;
+        }
+        Ok(votes)
+    }
+}
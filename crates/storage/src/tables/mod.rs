@@ -1,5 +1,6 @@
 pub mod beacon_block;
 pub mod beacon_state;
+pub mod blinded_beacon_block;
 pub mod blobs_and_proofs;
 pub mod block_timeliness;
 pub mod checkpoint_states;
@@ -8,6 +9,7 @@ pub mod finalized_checkpoint;
 pub mod genesis_time;
 pub mod justified_checkpoint;
 pub mod latest_messages;
+pub mod next_slot_to_blind;
 pub mod parent_root_index;
 pub mod proposer_boost_root;
 pub mod slot_index;
@@ -16,8 +18,9 @@ pub mod time;
 pub mod unrealized_finalized_checkpoint;
 pub mod unrealized_justifications;
 pub mod unrealized_justified_checkpoint;
+pub mod validator_income;
 
-use std::{any::type_name, fmt::Debug};
+use std::{any::type_name, fmt::Debug, marker::PhantomData};
 
 use redb::{Key, TypeName, Value};
 use ssz::{Decode, Encode};
@@ -106,3 +109,47 @@ where
         TypeName::new(&format!("SSZEncoding<{}>", type_name::<T>()))
     }
 }
+
+/// Reads the SSZ bytes of a value stored as `SSZEncoding<T>` without decoding them
+///
+/// The type name is the one of `SSZEncoding<T>`, so a table can be opened with either definition.
+#[derive(Debug)]
+pub struct SSZBytes<T>(PhantomData<T>);
+
+impl<T> Value for SSZBytes<T>
+where
+    T: Debug,
+{
+    type SelfType<'a>
+        = &'a [u8]
+    where
+        Self: 'a;
+
+    type AsBytes<'a>
+        = &'a [u8]
+    where
+        Self: 'a;
+
+    fn fixed_width() -> Option<usize> {
+        None
+    }
+
+    fn from_bytes<'a>(data: &'a [u8]) -> Self::SelfType<'a>
+    where
+        Self: 'a,
+    {
+        data
+    }
+
+    fn as_bytes<'a, 'b: 'a>(value: &'a Self::SelfType<'b>) -> Self::AsBytes<'a>
+    where
+        Self: 'a,
+        Self: 'b,
+    {
+        value
+    }
+
+    fn type_name() -> TypeName {
+        TypeName::new(&format!("SSZEncoding<{}>", type_name::<T>()))
+    }
+}
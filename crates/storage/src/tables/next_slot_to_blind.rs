@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use redb::{Database, Durability, TableDefinition};
+
+use super::Field;
+use crate::errors::StoreError;
+
+/// Table definition for the Next_Slot_To_Blind table
+///
+/// Value: u64
+pub const NEXT_SLOT_TO_BLIND_FIELD: TableDefinition<&str, u64> =
+    TableDefinition::new("next_slot_to_blind");
+
+pub const NEXT_SLOT_TO_BLIND_KEY: &str = "next_slot_to_blind_key";
+
+/// The first slot whose block may still be stored with its execution payload, every canonical
+/// block before it has been blinded.
+pub struct NextSlotToBlindField {
+    pub db: Arc<Database>,
+}
+
+impl Field for NextSlotToBlindField {
+    type Value = u64;
+
+    fn get(&self) -> Result<u64, StoreError> {
+        let read_txn = self.db.begin_read()?;
+
+        let table = read_txn.open_table(NEXT_SLOT_TO_BLIND_FIELD)?;
+        let result = table
+            .get(NEXT_SLOT_TO_BLIND_KEY)?
+            .ok_or(StoreError::FieldNotInitilized)?;
+        Ok(result.value())
+    }
+
+    fn insert(&self, value: Self::Value) -> Result<(), StoreError> {
+        let mut write_txn = self.db.begin_write()?;
+        write_txn.set_durability(Durability::Immediate);
+        let mut table = write_txn.open_table(NEXT_SLOT_TO_BLIND_FIELD)?;
+        table.insert(NEXT_SLOT_TO_BLIND_KEY, value)?;
+        drop(table);
+        write_txn.commit()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use ream_consensus_beacon::validator_income::ValidatorIncome;
+use redb::{Database, Durability, ReadableTable, TableDefinition};
+
+use super::{SSZEncoding, Table};
+use crate::errors::StoreError;
+
+/// Table definition for the Validator Income table, which holds the per-epoch balance changes of
+/// the monitored validators
+///
+/// Key: (validator_index, epoch)
+/// Value: ValidatorIncome
+pub const VALIDATOR_INCOME_TABLE: TableDefinition<(u64, u64), SSZEncoding<ValidatorIncome>> =
+    TableDefinition::new("validator_income");
+
+pub struct ValidatorIncomeTable {
+    pub db: Arc<Database>,
+}
+
+impl Table for ValidatorIncomeTable {
+    type Key = (u64, u64);
+
+    type Value = ValidatorIncome;
+
+    fn get(&self, key: Self::Key) -> Result<Option<Self::Value>, StoreError> {
+        let read_txn = self.db.begin_read()?;
+
+        let table = read_txn.open_table(VALIDATOR_INCOME_TABLE)?;
+        let result = table.get(key)?;
+        Ok(result.map(|res| res.value()))
+    }
+
+    fn insert(&self, key: Self::Key, value: Self::Value) -> Result<(), StoreError> {
+        let mut write_txn = self.db.begin_write()?;
+        write_txn.set_durability(Durability::Immediate);
+        let mut table = write_txn.open_table(VALIDATOR_INCOME_TABLE)?;
+        table.insert(key, value)?;
+        drop(table);
+        write_txn.commit()?;
+        Ok(())
+    }
+}
+
+impl ValidatorIncomeTable {
+    /// Merges each income into the stored income of its `(validator_index, epoch)` key with
+    /// `merge`, starting from an empty income for the keys which are not stored yet, in a single
+    /// transaction.
+    pub fn merge(
+        &self,
+        incomes: impl IntoIterator<Item = ((u64, u64), ValidatorIncome)>,
+        merge: impl Fn(&mut ValidatorIncome, &ValidatorIncome),
+    ) -> Result<(), StoreError> {
+        let mut write_txn = self.db.begin_write()?;
+        write_txn.set_durability(Durability::Immediate);
+        let mut table = write_txn.open_table(VALIDATOR_INCOME_TABLE)?;
+        for (key, income) in incomes {
+            let mut stored_income = table.get(key)?.map(|res| res.value()).unwrap_or_default();
+            merge(&mut stored_income, &income);
+            table.insert(key, stored_income)?;
+        }
+        drop(table);
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    /// Returns the stored incomes of a validator from `start_epoch` to `end_epoch` inclusive, by
+    /// epoch.
+    pub fn get_range(
+        &self,
+        validator_index: u64,
+        start_epoch: u64,
+        end_epoch: u64,
+    ) -> Result<Vec<(u64, ValidatorIncome)>, StoreError> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(VALIDATOR_INCOME_TABLE)?;
+        table
+            .range((validator_index, start_epoch)..=(validator_index, end_epoch))?
+            .map(|result| {
+                let (key, value) = result?;
+                Ok((key.value().1, value.value()))
+            })
+            .collect()
+    }
+}
@@ -6,16 +6,19 @@ macro_rules! test_epoch_processing {
             #[allow(non_snake_case)]
             mod [<tests_ $processing_fn>] {
                 use super::*;
+                use ream_network_spec::networks::initialize_test_network_spec;
                 use rstest::rstest;
 
                 #[rstest]
                 fn test_epoch_processing() {
+                    initialize_test_network_spec();
                     let base_path = format!(
                         "mainnet/tests/mainnet/electra/epoch_processing/{}/pyspec_tests",
                         stringify!($operation_name)
                     );
 
-                    for entry in std::fs::read_dir(base_path).unwrap() {
+                    let mut report = utils::CaseReport::new();
+                    for entry in std::fs::read_dir(&base_path).unwrap() {
                         let entry = entry.unwrap();
                         let case_dir = entry.path();
 
@@ -24,7 +27,6 @@ macro_rules! test_epoch_processing {
                         }
 
                         let case_name = case_dir.file_name().unwrap().to_str().unwrap();
-                        println!("Testing case: {}", case_name);
 
                         let mut state: BeaconState =
                             utils::read_ssz_snappy(&case_dir.join("pre.ssz_snappy")).expect("cannot find test asset(pre.ssz_snappy)");
@@ -32,23 +34,9 @@ macro_rules! test_epoch_processing {
                         let expected_post = utils::read_ssz_snappy::<BeaconState>(&case_dir.join("post.ssz_snappy"));
 
                         let result = state.$processing_fn();
-
-                        match (result, expected_post) {
-                            (Ok(_), Ok(expected)) => {
-                                assert_eq!(state, expected, "Post state mismatch in case {case_name}");
-                            }
-                            (Ok(_), Err(_)) => {
-                                panic!("Test case {case_name} should have failed but succeeded");
-                            }
-                            (Err(err), Ok(_)) => {
-                                panic!("Test case {case_name} should have succeeded but failed, err={err:?}");
-                            }
-                            (Err(_), Err(_)) => {
-                                // Test should fail and there should be no post state
-                                // This is the expected outcome for invalid operations
-                            }
-                        }
+                        report.record(case_name, utils::check_post_state(result, &state, expected_post));
                     }
+                    report.finish(&base_path);
                 }
             }
         }
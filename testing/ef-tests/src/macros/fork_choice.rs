@@ -25,8 +25,9 @@ macro_rules! test_fork_choice {
                 use rstest::rstest;
                 use serde::Deserialize;
                 use ssz_derive::{Decode, Encode};
+                use ream_consensus_misc::preset::MaxBlobCommitmentsPerBlock;
                 use ssz_types::{
-                    typenum::{self, U1099511627776, U4096}, FixedVector, VariableList
+                    typenum::{self, U1099511627776}, FixedVector, VariableList
                 };
                 use tree_hash::TreeHash;
 
@@ -150,7 +151,7 @@ macro_rules! test_fork_choice {
 
                                     if let (Some(blobs), Some(proof)) = (blocks.blobs, blocks.proofs) {
                                         let blobs_path = case_dir.join(format!("{}.ssz_snappy", blobs));
-                                        let blobs: VariableList<Blob, U4096> = utils::read_ssz_snappy(&blobs_path).expect("Could not read blob file.");
+                                        let blobs: VariableList<Blob, MaxBlobCommitmentsPerBlock> = utils::read_ssz_snappy(&blobs_path).expect("Could not read blob file.");
                                         let proof: Vec<KZGProof> = proof
                                             .into_iter()
                                             .map(|proof| KZGProof::from_hex(proof).expect("could not get KZGProof"))
@@ -0,0 +1,105 @@
+#[macro_export]
+macro_rules! test_genesis_initialization {
+    () => {
+        #[cfg(test)]
+        #[allow(non_snake_case)]
+        mod tests_genesis_initialization {
+            use std::fs;
+
+            use alloy_primitives::B256;
+            use ream_network_spec::networks::initialize_test_network_spec;
+            use serde::Deserialize;
+
+            use super::*;
+
+            #[derive(Debug, Deserialize)]
+            struct Eth1 {
+                eth1_block_hash: B256,
+                eth1_timestamp: u64,
+            }
+
+            #[derive(Debug, Deserialize)]
+            struct Meta {
+                deposits_count: usize,
+                execution_payload_header: Option<bool>,
+            }
+
+            #[test]
+            fn test_genesis_initialization() {
+                initialize_test_network_spec();
+                let base_path = std::env::current_dir()
+                    .unwrap()
+                    .join("mainnet/tests/mainnet/electra/genesis/initialization/pyspec_tests");
+
+                // The genesis cases are only generated for the presets the spec marks as fast
+                // enough, so a release may not ship them for mainnet
+                if !base_path.is_dir() {
+                    println!("{}: no cases", base_path.display());
+                    return;
+                }
+
+                let mut report = utils::CaseReport::new();
+                for entry in std::fs::read_dir(&base_path).unwrap() {
+                    let entry = entry.unwrap();
+                    let case_dir = entry.path();
+                    if !case_dir.is_dir() {
+                        continue;
+                    }
+
+                    let case_name = case_dir.file_name().unwrap().to_str().unwrap();
+
+                    let eth1: Eth1 = serde_yaml::from_str(
+                        &fs::read_to_string(case_dir.join("eth1.yaml"))
+                            .expect("Failed to read eth1.yaml"),
+                    )
+                    .expect("Failed to parse eth1.yaml");
+                    let meta: Meta = serde_yaml::from_str(
+                        &fs::read_to_string(case_dir.join("meta.yaml"))
+                            .expect("Failed to read meta.yaml"),
+                    )
+                    .expect("Failed to parse meta.yaml");
+
+                    let deposits = (0..meta.deposits_count)
+                        .map(|index| {
+                            utils::read_ssz_snappy::<Deposit>(
+                                &case_dir.join(format!("deposits_{index}.ssz_snappy")),
+                            )
+                            .expect("cannot find test asset (deposits_<index>.ssz_snappy)")
+                        })
+                        .collect::<Vec<_>>();
+                    let execution_payload_header = if meta.execution_payload_header.unwrap_or(false)
+                    {
+                        utils::read_ssz_snappy::<ExecutionPayloadHeader>(
+                            &case_dir.join("execution_payload_header.ssz_snappy"),
+                        )
+                        .expect("cannot find test asset (execution_payload_header.ssz_snappy)")
+                    } else {
+                        ExecutionPayloadHeader::default()
+                    };
+
+                    let expected_state =
+                        utils::read_ssz_snappy::<BeaconState>(&case_dir.join("state.ssz_snappy"));
+
+                    let result = BeaconState::initialize_beacon_state_from_eth1(
+                        eth1.eth1_block_hash,
+                        eth1.eth1_timestamp,
+                        &deposits,
+                        execution_payload_header,
+                    );
+                    let outcome = match (result, expected_state) {
+                        (Ok(state), Ok(expected)) if state == expected => Ok(()),
+                        (Ok(_), Ok(_)) => Err("Genesis state mismatch".to_string()),
+                        (Err(err), _) => {
+                            Err(format!("Should have succeeded but failed, err={err:?}"))
+                        }
+                        (Ok(_), Err(err)) => {
+                            Err(format!("Failed to read the genesis state: {err:?}"))
+                        }
+                    };
+                    report.record(case_name, outcome);
+                }
+                report.finish(&base_path.display().to_string());
+            }
+        }
+    };
+}
@@ -1,10 +1,12 @@
 #[macro_export]
 macro_rules! test_operation_impl {
     ($operation_name:ident, $operation_object:ty, $input_name:literal, $compute_result:expr) => {{
+        ream_network_spec::networks::initialize_test_network_spec();
         let base_path = format!(
             "mainnet/tests/mainnet/electra/operations/{}/pyspec_tests",
             stringify!($operation_name)
         );
+        let mut report = utils::CaseReport::new();
         for entry in std::fs::read_dir(&base_path).unwrap() {
             let entry = entry.unwrap();
             let case_dir = entry.path();
@@ -12,7 +14,6 @@ macro_rules! test_operation_impl {
                 continue;
             }
             let case_name = case_dir.file_name().unwrap().to_str().unwrap();
-            println!("Testing case: {}", case_name);
 
             let mut state: Arc<Mutex<BeaconState>> = Arc::new(Mutex::new(
                 utils::read_ssz_snappy(&case_dir.join("pre.ssz_snappy"))
@@ -27,29 +28,12 @@ macro_rules! test_operation_impl {
             // Call the provided closure to compute the result.
             // The closure is expected to return a Future.
             let result = $compute_result(state.clone(), input, case_dir.to_path_buf()).await;
-            match (result, expected_post) {
-                (Ok(_), Ok(expected)) => {
-                    assert_eq!(
-                        *state.lock().await,
-                        expected,
-                        "Post state mismatch in case {}",
-                        case_name
-                    );
-                }
-                (Ok(_), Err(_)) => {
-                    panic!("Test case {} should have failed but succeeded", case_name);
-                }
-                (Err(err), Ok(_)) => {
-                    panic!(
-                        "Test case {} should have succeeded but failed, err={:?}",
-                        case_name, err
-                    );
-                }
-                (Err(_), Err(_)) => {
-                    // Expected: invalid operations result in an error and no post state.
-                }
-            }
+            report.record(
+                case_name,
+                utils::check_post_state(result, &*state.lock().await, expected_post),
+            );
         }
+        report.finish(&base_path);
     }};
 }
 
@@ -6,6 +6,7 @@ macro_rules! test_rewards {
             #[allow(non_snake_case)]
             mod [<tests_ $operation_name>] {
                 use super::*;
+                use ream_network_spec::networks::initialize_test_network_spec;
                 use rstest::rstest;
                 use ssz_types::{
                     typenum::{U1099511627776},
@@ -22,6 +23,7 @@ macro_rules! test_rewards {
 
                 #[rstest]
                 fn test_rewards() {
+                    initialize_test_network_spec();
                     let base_path = format!(
                         "mainnet/tests/mainnet/electra/rewards/{}/pyspec_tests",
                         stringify!($operation_name)
@@ -28,6 +28,7 @@ macro_rules! test_sanity_blocks {
 
                     let mock_engine = Some(MockExecutionEngine::new());
 
+                    let mut report = utils::CaseReport::new();
                     for entry in std::fs::read_dir(&base_path).unwrap() {
                         let entry = entry.unwrap();
                         let case_dir = entry.path();
@@ -36,7 +37,6 @@ macro_rules! test_sanity_blocks {
                         }
 
                         let case_name = case_dir.file_name().unwrap().to_str().unwrap();
-                        println!("Testing case: {}", case_name);
 
                         let meta: MetaData = {
                             let meta_path = case_dir.join("meta.yaml");
@@ -75,32 +75,12 @@ macro_rules! test_sanity_blocks {
 
                         let expected_post = utils::read_ssz_snappy::<BeaconState>(&case_dir.join("post.ssz_snappy"));
 
-                        match (result, expected_post) {
-                            (Ok(_), Ok(expected)) => {
-                                assert_eq!(
-                                    state, expected,
-                                    "Post state mismatch in case {}",
-                                    case_name
-                                );
-                            }
-                            (Ok(_), Err(_)) => {
-                                panic!("Test case {} should have failed but succeeded", case_name);
-                            }
-                            (Err(err), Ok(_)) => {
-                                panic!(
-                                    "Test case {} should have succeeded but failed, err={:?}",
-                                    case_name, err
-                                );
-                            }
-                            (Err(_), Err(_)) => {
-                                // Expected: invalid operations result in an error and no post state.
-                                println!(
-                                    "Test case {} failed as expected, no post state available.",
-                                    case_name
-                                );
-                            }
-                        }
+                        report.record(
+                            case_name,
+                            utils::check_post_state(result, &state, expected_post),
+                        );
                     }
+                    report.finish(&base_path.display().to_string());
                 }
             }
         }
@@ -11,6 +11,7 @@ macro_rules! test_sanity_slots {
             };
 
             use ream_consensus_beacon::execution_engine::mock_engine::MockExecutionEngine;
+            use ream_network_spec::networks::initialize_test_network_spec;
             use serde_yaml;
             use tokio::sync::Mutex;
 
@@ -18,10 +19,12 @@ macro_rules! test_sanity_slots {
 
             #[tokio::test]
             async fn test_sanity_slots() {
+                initialize_test_network_spec();
                 let base_path = std::env::current_dir()
                     .unwrap()
                     .join("mainnet/tests/mainnet/electra/sanity/slots/pyspec_tests");
 
+                let mut report = utils::CaseReport::new();
                 for entry in std::fs::read_dir(&base_path).unwrap() {
                     let entry = entry.unwrap();
                     let case_dir = entry.path();
@@ -30,7 +33,6 @@ macro_rules! test_sanity_slots {
                     }
 
                     let case_name = case_dir.file_name().unwrap().to_str().unwrap();
-                    println!("Testing case: {}", case_name);
 
                     let slot: u64 = {
                         let slot_path = case_dir.join("slots.yaml");
@@ -49,29 +51,12 @@ macro_rules! test_sanity_slots {
                         utils::read_ssz_snappy::<BeaconState>(&case_dir.join("post.ssz_snappy"));
 
                     let result = state.process_slots(state.slot + slot);
-
-                    match (result, expected_post) {
-                        (Ok(_), Ok(expected)) => {
-                            assert_eq!(
-                                state, expected,
-                                "Post state mismatch in case {}",
-                                case_name
-                            );
-                        }
-                        (Ok(_), Err(_)) => {
-                            panic!("Test case {} should have failed but succeeded", case_name);
-                        }
-                        (Err(err), Ok(_)) => {
-                            panic!(
-                                "Test case {} should have succeeded but failed, err={:?}",
-                                case_name, err
-                            );
-                        }
-                        (Err(_), Err(_)) => {
-                            // Expected: invalid operations result in an error and no post state.
-                        }
-                    }
+                    report.record(
+                        case_name,
+                        utils::check_post_state(result, &state, expected_post),
+                    );
                 }
+                report.finish(&base_path.display().to_string());
             }
         }
     };
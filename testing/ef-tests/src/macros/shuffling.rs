@@ -21,6 +21,7 @@ macro_rules! test_shuffling {
             fn test_shuffling() {
                 let base_path = "mainnet/tests/mainnet/phase0/shuffling/core/shuffle";
 
+                let mut report = utils::CaseReport::new();
                 for entry in std::fs::read_dir(base_path).unwrap() {
                     let entry = entry.unwrap();
                     let case_dir = entry.path();
@@ -30,7 +31,6 @@ macro_rules! test_shuffling {
                     }
 
                     let case_name = case_dir.file_name().unwrap().to_str().unwrap();
-                    println!("Testing case: {}", case_name);
 
                     // Read and parse mapping.yaml
                     let test_data: ShufflingTest = {
@@ -45,15 +45,20 @@ macro_rules! test_shuffling {
                         .expect("Failed to parse seed");
 
                     // Test compute_shuffled_index for each index
-                    for i in 0..test_data.count {
-                        let shuffled = compute_shuffled_index(i, test_data.count, seed)
-                            .expect("shuffling should not fail");
-                        assert_eq!(
-                            shuffled, test_data.mapping[i],
-                            "Mismatch at index {i} in case {case_name}"
-                        );
-                    }
+                    let result =
+                        (0..test_data.count).try_for_each(|i| {
+                            match compute_shuffled_index(i, test_data.count, seed) {
+                                Ok(shuffled) if shuffled == test_data.mapping[i] => Ok(()),
+                                Ok(shuffled) => Err(format!(
+                                    "Mismatch at index {i}: {shuffled} != {}",
+                                    test_data.mapping[i]
+                                )),
+                                Err(err) => Err(format!("Shuffling failed at index {i}: {err:?}")),
+                            }
+                        });
+                    report.record(case_name, result);
                 }
+                report.finish(base_path);
             }
         }
     };
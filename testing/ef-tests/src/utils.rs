@@ -9,3 +9,73 @@ pub fn read_ssz_snappy<T: ssz::Decode>(path: &Path) -> anyhow::Result<T> {
     let ssz = decoder.decompress_vec(&ssz_snappy)?;
     T::from_ssz_bytes(&ssz).map_err(|err| anyhow!("Failed to decode SSZ: {:?}", err))
 }
+
+/// Compares the outcome of a case against its expected post state, where a missing post state
+/// means the case is expected to fail. The value a successful case returns is not checked.
+pub fn check_post_state<T: PartialEq, R, E: std::fmt::Debug>(
+    result: Result<R, E>,
+    state: &T,
+    expected_post: anyhow::Result<T>,
+) -> Result<(), String> {
+    match (result, expected_post) {
+        (Ok(_), Ok(expected)) if *state == expected => Ok(()),
+        (Ok(_), Ok(_)) => Err("Post state mismatch".to_string()),
+        (Ok(_), Err(_)) => Err("Should have failed but succeeded".to_string()),
+        (Err(err), Ok(_)) => Err(format!("Should have succeeded but failed, err={err:?}")),
+        // Invalid cases result in an error and have no post state
+        (Err(_), Err(_)) => Ok(()),
+    }
+}
+
+/// Records the outcome of every case of a suite, so that a failing case does not hide the
+/// outcome of the cases after it.
+#[derive(Debug, Default)]
+pub struct CaseReport {
+    passed: usize,
+    failures: Vec<(String, String)>,
+}
+
+impl CaseReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, case_name: &str, result: Result<(), String>) {
+        match result {
+            Ok(()) => {
+                println!("PASS {case_name}");
+                self.passed += 1;
+            }
+            Err(err) => {
+                println!("FAIL {case_name}: {err}");
+                self.failures.push((case_name.to_string(), err));
+            }
+        }
+    }
+
+    /// Prints a summary of the suite.
+    ///
+    /// # Panics
+    ///
+    /// Panics listing the failed cases if any case failed.
+    pub fn finish(self, suite: &str) {
+        println!(
+            "{suite}: {} passed, {} failed",
+            self.passed,
+            self.failures.len()
+        );
+        if !self.failures.is_empty() {
+            let failures = self
+                .failures
+                .iter()
+                .map(|(case_name, err)| format!("  {case_name}: {err}"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            panic!(
+                "{} of {} cases of {suite} failed:\n{failures}",
+                self.failures.len(),
+                self.passed + self.failures.len()
+            );
+        }
+    }
+}
@@ -1,9 +1,9 @@
 #![cfg(feature = "ef-tests")]
 
 use ef_tests::{
-    test_consensus_type, test_epoch_processing, test_fork_choice, test_merkle_proof,
-    test_merkle_proof_impl, test_operation, test_rewards, test_sanity_blocks, test_sanity_slots,
-    test_shuffling, utils,
+    test_consensus_type, test_epoch_processing, test_fork_choice, test_genesis_initialization,
+    test_merkle_proof, test_merkle_proof_impl, test_operation, test_rewards, test_sanity_blocks,
+    test_sanity_slots, test_shuffling, utils,
 };
 use ream_consensus_beacon::{
     attestation::Attestation,
@@ -178,6 +178,9 @@ test_rewards!(random, get_inactivity_penalty_deltas);
 test_sanity_blocks!(test_sanity_blocks, "sanity/blocks");
 test_sanity_slots!();
 
+// Testing genesis
+test_genesis_initialization!();
+
 // Testing fork_choice
 test_fork_choice!(ex_ante);
 test_fork_choice!(get_head);
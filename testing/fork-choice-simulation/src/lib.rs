@@ -0,0 +1,544 @@
+use std::collections::{BTreeMap, HashMap};
+
+use alloy_primitives::B256;
+use anyhow::{anyhow, ensure};
+use network_simulator::empty_anchor;
+use rand::{Rng, SeedableRng, seq::SliceRandom};
+use rand_chacha::ChaCha8Rng;
+use ream_bls::BLSSignature;
+use ream_consensus_beacon::{
+    attestation::Attestation,
+    electra::{
+        beacon_block::{BeaconBlock, SignedBeaconBlock},
+        beacon_state::BeaconState,
+    },
+};
+use ream_consensus_misc::{
+    attestation_data::AttestationData,
+    checkpoint::Checkpoint,
+    constants::{FAR_FUTURE_EPOCH, MIN_ACTIVATION_BALANCE, SLOTS_PER_EPOCH},
+    misc::{compute_epoch_at_slot, compute_start_slot_at_epoch},
+    validator::Validator,
+};
+use ream_fork_choice::{
+    handlers::on_tick,
+    store::{Store, get_forkchoice_store},
+};
+use ream_network_spec::networks::{beacon_network_spec, initialize_test_network_spec};
+use ream_storage::{
+    db::ReamDB,
+    tables::{Field, Table},
+};
+use ssz_types::{BitList, BitVector};
+use tempdir::TempDir;
+use tree_hash::TreeHash;
+
+/// The parameters of the scenarios generated by `Simulation`.
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    pub validator_count: u64,
+    pub slots: u64,
+    /// The probability that a block is proposed in a slot.
+    pub block_probability: f64,
+    /// The probability that a proposed block is for an earlier slot, as if it arrived late.
+    pub late_block_probability: f64,
+    /// The probability that a proposer outside of a partition builds on a random block instead of
+    /// the head.
+    pub reorg_probability: f64,
+    /// The probability that a validator attests in a slot.
+    pub attestation_probability: f64,
+    /// The probability that a block justifies a new checkpoint on its chain.
+    pub justification_probability: f64,
+    /// The probability that a block received in its slot gets the proposer boost.
+    pub proposer_boost_probability: f64,
+    /// The probability that the validators are split into partitions in a slot.
+    pub partition_probability: f64,
+    /// The probability that the partitions heal in a slot.
+    pub heal_probability: f64,
+    /// The probability that a validator is found equivocating in a slot.
+    pub equivocation_probability: f64,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            validator_count: 16,
+            slots: 4 * SLOTS_PER_EPOCH,
+            block_probability: 0.15,
+            late_block_probability: 0.2,
+            reorg_probability: 0.1,
+            attestation_probability: 0.3,
+            justification_probability: 0.5,
+            proposer_boost_probability: 0.5,
+            partition_probability: 0.05,
+            heal_probability: 0.1,
+            equivocation_probability: 0.01,
+        }
+    }
+}
+
+/// What happened during a scenario, to check that the scenarios exercise the fork choice.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ScenarioReport {
+    pub blocks: usize,
+    pub reorgs: usize,
+    pub partitions: usize,
+    pub justified_epoch: u64,
+    pub finalized_epoch: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BlockInfo {
+    slot: u64,
+    justified: Checkpoint,
+    finalized: Checkpoint,
+    unrealized_justified: Checkpoint,
+    unrealized_finalized: Checkpoint,
+}
+
+#[derive(Debug)]
+struct Partition {
+    validators: Vec<u64>,
+    /// The block the validators of the partition attest to and build on.
+    tip: B256,
+}
+
+/// Drives a fork choice store through a randomized scenario of block trees, attestations,
+/// proposer boosts and partitions, and checks its safety invariants after every slot:
+///
+/// - the justified and finalized checkpoints never go back, and the finalized checkpoint never
+///   conflicts with a previously finalized one
+/// - the justified checkpoint descends from the finalized checkpoint
+/// - the head descends from the justified and finalized checkpoints
+///
+/// Blocks are written to the store directly, with the checkpoints their states would have, since
+/// producing them through the state transition would require signing them. Every checkpoint
+/// justified by the blocks descends from the previously justified ones, as conflicting
+/// justifications require a third of the validators to be slashable.
+pub struct Simulation {
+    config: SimulationConfig,
+    seed: u64,
+    rng: ChaCha8Rng,
+    store: Store,
+    /// The anchor state, from which the states of the blocks and checkpoints are derived.
+    state: BeaconState,
+    blocks: HashMap<B256, BlockInfo>,
+    /// The roots of `blocks` in insertion order, so that random choices are reproducible.
+    block_roots: Vec<B256>,
+    latest_justified: Checkpoint,
+    partitions: Vec<Partition>,
+    slot: u64,
+    head: B256,
+    justified: Checkpoint,
+    finalized: Checkpoint,
+    report: ScenarioReport,
+    _data_dir: TempDir,
+}
+
+impl Simulation {
+    pub fn new(config: SimulationConfig, seed: u64) -> anyhow::Result<Self> {
+        initialize_test_network_spec();
+        let (mut state, _) = empty_anchor(beacon_network_spec().min_genesis_time);
+        for _ in 0..config.validator_count {
+            state
+                .validators
+                .push(Validator {
+                    public_key: Default::default(),
+                    withdrawal_credentials: B256::ZERO,
+                    effective_balance: MIN_ACTIVATION_BALANCE,
+                    slashed: false,
+                    activation_eligibility_epoch: 0,
+                    activation_epoch: 0,
+                    exit_epoch: FAR_FUTURE_EPOCH,
+                    withdrawable_epoch: FAR_FUTURE_EPOCH,
+                })
+                .map_err(|err| anyhow!("Failed to add validator: {err:?}"))?;
+            state
+                .balances
+                .push(MIN_ACTIVATION_BALANCE)
+                .map_err(|err| anyhow!("Failed to add balance: {err:?}"))?;
+        }
+        let anchor_block = BeaconBlock {
+            state_root: state.tree_hash_root(),
+            ..Default::default()
+        };
+        let anchor_root = anchor_block.tree_hash_root();
+
+        let data_dir = TempDir::new("fork_choice_simulation")?;
+        let store = get_forkchoice_store(
+            state.clone(),
+            anchor_block,
+            ReamDB::new(data_dir.path().to_path_buf())?,
+        )?;
+
+        let anchor_checkpoint = Checkpoint {
+            epoch: 0,
+            root: anchor_root,
+        };
+        let blocks = HashMap::from([(
+            anchor_root,
+            BlockInfo {
+                slot: 0,
+                justified: anchor_checkpoint,
+                finalized: anchor_checkpoint,
+                unrealized_justified: anchor_checkpoint,
+                unrealized_finalized: anchor_checkpoint,
+            },
+        )]);
+
+        Ok(Self {
+            config,
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            store,
+            state,
+            blocks,
+            block_roots: vec![anchor_root],
+            latest_justified: anchor_checkpoint,
+            partitions: vec![],
+            slot: 0,
+            head: anchor_root,
+            justified: anchor_checkpoint,
+            finalized: anchor_checkpoint,
+            report: ScenarioReport::default(),
+            _data_dir: data_dir,
+        })
+    }
+
+    /// Runs the scenario to its last slot, failing on the first invariant which does not hold.
+    pub fn run(mut self) -> anyhow::Result<ScenarioReport> {
+        for slot in 1..=self.config.slots {
+            self.process_slot(slot).map_err(|err| {
+                err.context(format!(
+                    "Scenario with seed {} failed at slot {slot}",
+                    self.seed
+                ))
+            })?;
+        }
+
+        self.report.justified_epoch = self.justified.epoch;
+        self.report.finalized_epoch = self.finalized.epoch;
+        Ok(self.report)
+    }
+
+    fn process_slot(&mut self, slot: u64) -> anyhow::Result<()> {
+        self.slot = slot;
+        on_tick(
+            &mut self.store,
+            beacon_network_spec().min_genesis_time + slot * beacon_network_spec().seconds_per_slot,
+        )?;
+        self.update_partitions();
+
+        if self.rng.gen_bool(self.config.block_probability) {
+            self.propose_block()?;
+        }
+        if self.rng.gen_bool(self.config.equivocation_probability) {
+            self.equivocate()?;
+        }
+        self.check_invariants()?;
+
+        self.attest()?;
+        self.check_invariants()
+    }
+
+    fn update_partitions(&mut self) {
+        if !self.partitions.is_empty() {
+            if self.rng.gen_bool(self.config.heal_probability) {
+                self.partitions.clear();
+            }
+            return;
+        }
+        if !self.rng.gen_bool(self.config.partition_probability) {
+            return;
+        }
+
+        let mut validators = (0..self.config.validator_count).collect::<Vec<_>>();
+        validators.shuffle(&mut self.rng);
+        let partition_size = validators.len().div_ceil(self.rng.gen_range(2..=3));
+        self.partitions = validators
+            .chunks(partition_size)
+            .map(|validators| Partition {
+                validators: validators.to_vec(),
+                tip: self.head,
+            })
+            .collect();
+        self.report.partitions += 1;
+    }
+
+    fn partition_of(&self, validator_index: u64) -> Option<usize> {
+        self.partitions
+            .iter()
+            .position(|partition| partition.validators.contains(&validator_index))
+    }
+
+    fn propose_block(&mut self) -> anyhow::Result<()> {
+        let proposer_index = self.rng.gen_range(0..self.config.validator_count);
+        let partition = self.partition_of(proposer_index);
+        let parent_root = match partition {
+            Some(index) => self.partitions[index].tip,
+            None if self.rng.gen_bool(self.config.reorg_probability) => *self
+                .block_roots
+                .choose(&mut self.rng)
+                .expect("The anchor block is always known"),
+            None => self.head,
+        };
+        let parent = self.blocks[&parent_root];
+
+        let slot = if self.rng.gen_bool(self.config.late_block_probability)
+            && parent.slot + 1 < self.slot
+        {
+            self.rng.gen_range(parent.slot + 1..self.slot)
+        } else {
+            self.slot
+        };
+        if slot <= parent.slot {
+            return Ok(());
+        }
+
+        // The store rejects the blocks which do not descend from its finalized checkpoint
+        let store_finalized = self.store.db.finalized_checkpoint_provider().get()?;
+        if slot <= compute_start_slot_at_epoch(store_finalized.epoch)
+            || self
+                .store
+                .get_checkpoint_block(parent_root, store_finalized.epoch)?
+                != store_finalized.root
+        {
+            return Ok(());
+        }
+
+        let block = BeaconBlock {
+            slot,
+            proposer_index,
+            parent_root,
+            // Distinguishes the blocks built on the same parent in the same slot
+            state_root: B256::from(self.rng.r#gen::<[u8; 32]>()),
+            ..Default::default()
+        };
+        let root = block.tree_hash_root();
+
+        // The epoch transitions between the parent and the block realize the justification of
+        // the parent
+        let epoch = compute_epoch_at_slot(slot);
+        let (justified, finalized) = if epoch > compute_epoch_at_slot(parent.slot) {
+            (parent.unrealized_justified, parent.unrealized_finalized)
+        } else {
+            (parent.justified, parent.finalized)
+        };
+        let mut unrealized_justified = parent.unrealized_justified;
+        let mut unrealized_finalized = parent.unrealized_finalized;
+        if epoch > 0 && self.rng.gen_bool(self.config.justification_probability) {
+            let target_epoch = epoch - self.rng.gen_range(0..=1);
+            let target_slot = compute_start_slot_at_epoch(target_epoch);
+            let target = Checkpoint {
+                epoch: target_epoch,
+                root: if slot == target_slot {
+                    root
+                } else {
+                    self.store.get_ancestor(parent_root, target_slot)?
+                },
+            };
+            if target.epoch > unrealized_justified.epoch && self.can_justify(parent_root, target)? {
+                // Justifying two consecutive epochs finalizes the first one
+                if target.epoch == unrealized_justified.epoch + 1 {
+                    unrealized_finalized = unrealized_justified;
+                }
+                unrealized_justified = target;
+                self.justify(target)?;
+            }
+        }
+
+        self.store.db.beacon_block_provider().insert(
+            root,
+            SignedBeaconBlock {
+                message: block,
+                signature: BLSSignature::default(),
+            },
+        )?;
+        let mut state = self.state.clone();
+        state.slot = slot;
+        state.current_justified_checkpoint = justified;
+        state.finalized_checkpoint = finalized;
+        self.store.db.beacon_state_provider().insert(root, state)?;
+        self.store
+            .db
+            .unrealized_justifications_provider()
+            .insert(root, unrealized_justified)?;
+
+        self.store.update_checkpoints(justified, finalized)?;
+        self.store
+            .update_unrealized_checkpoints(unrealized_justified, unrealized_finalized)?;
+        // The justification of blocks from a prior epoch is pulled up right away
+        if epoch < self.store.get_current_store_epoch()? {
+            self.store
+                .update_checkpoints(unrealized_justified, unrealized_finalized)?;
+        }
+
+        if slot == self.slot
+            && self.store.db.proposer_boost_root_provider().get()? == B256::ZERO
+            && self.rng.gen_bool(self.config.proposer_boost_probability)
+        {
+            self.store.db.proposer_boost_root_provider().insert(root)?;
+        }
+
+        self.blocks.insert(
+            root,
+            BlockInfo {
+                slot,
+                justified,
+                finalized,
+                unrealized_justified,
+                unrealized_finalized,
+            },
+        );
+        self.block_roots.push(root);
+        if let Some(index) = partition {
+            self.partitions[index].tip = root;
+        }
+        self.report.blocks += 1;
+
+        Ok(())
+    }
+
+    /// Whether `target`, on the chain of a block built on `parent_root`, can be justified
+    /// without conflicting with the checkpoints justified so far.
+    fn can_justify(&self, parent_root: B256, target: Checkpoint) -> anyhow::Result<bool> {
+        if target.epoch == self.latest_justified.epoch {
+            return Ok(target.root == self.latest_justified.root);
+        }
+
+        let latest_justified_slot = self.blocks[&self.latest_justified.root].slot;
+        Ok(target.epoch > self.latest_justified.epoch
+            && self
+                .store
+                .get_ancestor(parent_root, latest_justified_slot)?
+                == self.latest_justified.root)
+    }
+
+    fn justify(&mut self, checkpoint: Checkpoint) -> anyhow::Result<()> {
+        if checkpoint == self.latest_justified {
+            return Ok(());
+        }
+
+        self.store
+            .db
+            .checkpoint_states_provider()
+            .insert(checkpoint, self.state.clone())?;
+        self.latest_justified = checkpoint;
+        Ok(())
+    }
+
+    /// Records an equivocating validator the way `on_attester_slashing` does, since attester
+    /// slashings are only accepted with valid signatures.
+    fn equivocate(&mut self) -> anyhow::Result<()> {
+        let validator_index = self.rng.gen_range(0..self.config.validator_count);
+        let mut equivocating_indices = self.store.db.equivocating_indices_provider().get()?;
+        equivocating_indices.insert(validator_index);
+        self.store
+            .db
+            .equivocating_indices_provider()
+            .insert(equivocating_indices)?;
+        Ok(())
+    }
+
+    /// Makes a random subset of the validators attest to the head, or to the tip of their
+    /// partition.
+    fn attest(&mut self) -> anyhow::Result<()> {
+        let mut votes = BTreeMap::<B256, Vec<u64>>::new();
+        for validator_index in 0..self.config.validator_count {
+            if !self.rng.gen_bool(self.config.attestation_probability) {
+                continue;
+            }
+            let root = match self.partition_of(validator_index) {
+                Some(index) => self.partitions[index].tip,
+                None => self.head,
+            };
+            votes.entry(root).or_default().push(validator_index);
+        }
+
+        let epoch = compute_epoch_at_slot(self.slot);
+        for (root, attesting_indices) in votes {
+            let attestation = Attestation {
+                aggregation_bits: BitList::with_capacity(attesting_indices.len())
+                    .map_err(|err| anyhow!("Failed to create aggregation bits: {err:?}"))?,
+                data: AttestationData {
+                    slot: self.slot,
+                    index: 0,
+                    beacon_block_root: root,
+                    source: self.store.db.justified_checkpoint_provider().get()?,
+                    target: Checkpoint {
+                        epoch,
+                        root: self.store.get_checkpoint_block(root, epoch)?,
+                    },
+                },
+                signature: BLSSignature::default(),
+                committee_bits: BitVector::new(),
+            };
+            self.store
+                .update_latest_messages(attesting_indices, attestation)?;
+        }
+
+        Ok(())
+    }
+
+    fn check_invariants(&mut self) -> anyhow::Result<()> {
+        let justified = self.store.db.justified_checkpoint_provider().get()?;
+        let finalized = self.store.db.finalized_checkpoint_provider().get()?;
+        ensure!(
+            justified.epoch >= self.justified.epoch,
+            "Justified checkpoint went back from {:?} to {justified:?}",
+            self.justified
+        );
+        ensure!(
+            finalized.epoch >= self.finalized.epoch,
+            "Finalized checkpoint reverted from {:?} to {finalized:?}",
+            self.finalized
+        );
+        ensure!(
+            self.store
+                .get_checkpoint_block(finalized.root, self.finalized.epoch)?
+                == self.finalized.root,
+            "Finalized checkpoint {finalized:?} conflicts with the previously finalized {:?}",
+            self.finalized
+        );
+        ensure!(
+            self.store
+                .get_checkpoint_block(justified.root, finalized.epoch)?
+                == finalized.root,
+            "Justified checkpoint {justified:?} does not descend from the finalized {finalized:?}"
+        );
+
+        let head = self.store.get_head()?;
+        let head_slot = self
+            .blocks
+            .get(&head)
+            .ok_or_else(|| anyhow!("Head {head} is not a known block"))?
+            .slot;
+        ensure!(
+            head_slot <= self.slot,
+            "Head {head} is from the future slot {head_slot}"
+        );
+        ensure!(
+            self.store
+                .get_ancestor(head, self.blocks[&justified.root].slot)?
+                == justified.root,
+            "Head {head} does not descend from the justified {justified:?}"
+        );
+        ensure!(
+            self.store.get_checkpoint_block(head, finalized.epoch)? == finalized.root,
+            "Head {head} does not descend from the finalized {finalized:?}"
+        );
+
+        if self
+            .store
+            .get_ancestor(head, self.blocks[&self.head].slot)?
+            != self.head
+        {
+            self.report.reorgs += 1;
+        }
+        self.head = head;
+        self.justified = justified;
+        self.finalized = finalized;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use std::env;
+
+use fork_choice_simulation::{ScenarioReport, Simulation, SimulationConfig};
+
+/// Overrides the number of scenarios of `test_random_scenarios`, for longer fuzzing runs.
+const SCENARIOS_VAR: &str = "FORK_CHOICE_SIMULATION_SCENARIOS";
+/// Overrides the seed of the first scenario, to reproduce a failure.
+const SEED_VAR: &str = "FORK_CHOICE_SIMULATION_SEED";
+
+fn env_or(name: &str, default: u64) -> u64 {
+    env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+fn run_scenarios(config: SimulationConfig, scenarios: u64) -> anyhow::Result<Vec<ScenarioReport>> {
+    let first_seed = env_or(SEED_VAR, 0);
+    (first_seed..first_seed + scenarios)
+        .map(|seed| Simulation::new(config.clone(), seed)?.run())
+        .collect()
+}
+
+// Each scenario commits every block and state to the database, so the tests only run a few of
+// them and `test_fuzz_random_scenarios` runs the long campaigns
+#[test]
+fn test_random_scenarios() -> anyhow::Result<()> {
+    let reports = run_scenarios(SimulationConfig::default(), env_or(SCENARIOS_VAR, 4))?;
+
+    // The invariants hold trivially unless the scenarios reorg and finalize
+    assert!(reports.iter().any(|report| report.reorgs > 0));
+    assert!(reports.iter().any(|report| report.finalized_epoch > 0));
+    Ok(())
+}
+
+#[test]
+fn test_partitioned_scenarios() -> anyhow::Result<()> {
+    let config = SimulationConfig {
+        partition_probability: 0.2,
+        heal_probability: 0.05,
+        ..Default::default()
+    };
+    let reports = run_scenarios(config, 2)?;
+
+    assert!(reports.iter().all(|report| report.partitions > 0));
+    Ok(())
+}
+
+#[test]
+fn test_scenarios_are_reproducible() -> anyhow::Result<()> {
+    let first = Simulation::new(SimulationConfig::default(), 7)?.run()?;
+    let second = Simulation::new(SimulationConfig::default(), 7)?.run()?;
+
+    assert_eq!(first, second);
+    Ok(())
+}
+
+#[test]
+#[ignore = "runs thousands of scenarios"]
+fn test_fuzz_random_scenarios() -> anyhow::Result<()> {
+    run_scenarios(SimulationConfig::default(), env_or(SCENARIOS_VAR, 2000))?;
+    Ok(())
+}
@@ -0,0 +1,104 @@
+mod tests {
+    use std::sync::Arc;
+
+    use alloy_primitives::B256;
+    use ream_beacon_chain::beacon_chain::BeaconChain;
+    use ream_bls::BLSSignature;
+    use ream_consensus_beacon::{
+        electra::{
+            beacon_block::{BeaconBlock, SignedBeaconBlock},
+            beacon_state::BeaconState,
+        },
+        test_utils::{attester_slot, single_attestation, single_validator_anchor},
+    };
+    use ream_consensus_misc::checkpoint::Checkpoint;
+    use ream_fork_choice::store::get_forkchoice_store;
+    use ream_network_manager::gossipsub::validate::{
+        beacon_attestation::validate_beacon_attestation, result::ValidationResult,
+    };
+    use ream_network_spec::networks::{beacon_network_spec, initialize_test_network_spec};
+    use ream_operation_pool::OperationPool;
+    use ream_storage::{cache::CachedDB, db::ReamDB, tables::Table};
+    use ream_validator_beacon::attestation::compute_subnet_for_attestation;
+    use tempdir::TempDir;
+    use tree_hash::TreeHash;
+
+    /// Returns a beacon chain anchored at an Electra genesis state whose only validator has the
+    /// secret key 1, with a head block at the slot the validator attests at, along with the head
+    /// state, the anchor and head roots and the directory of the database.
+    async fn beacon_chain() -> anyhow::Result<(BeaconChain, BeaconState, B256, B256, TempDir)> {
+        let (state, anchor_block) = single_validator_anchor()?;
+        let anchor_root = anchor_block.tree_hash_root();
+        let slot = attester_slot(&state)?;
+
+        let data_dir = TempDir::new("ream_gossip_attestation_test")?;
+        let db = ReamDB::new(data_dir.path().to_path_buf())?;
+        get_forkchoice_store(state.clone(), anchor_block, db.clone())?;
+
+        let head_block = BeaconBlock {
+            slot,
+            parent_root: anchor_root,
+            state_root: B256::repeat_byte(1),
+            ..Default::default()
+        };
+        let head_root = head_block.tree_hash_root();
+        let head_state = BeaconState { slot, ..state };
+        db.beacon_block_provider().insert(
+            head_root,
+            SignedBeaconBlock {
+                message: head_block,
+                signature: BLSSignature::infinity(),
+            },
+        )?;
+        db.beacon_state_provider()
+            .insert(head_root, head_state.clone())?;
+        db.unrealized_justifications_provider()
+            .insert(head_root, Checkpoint::default())?;
+
+        let beacon_chain = BeaconChain::new(
+            db,
+            Arc::new(OperationPool::default()),
+            None,
+            vec![],
+            false,
+            false,
+        );
+        beacon_chain
+            .process_tick(
+                head_state.genesis_time + (slot + 1) * beacon_network_spec().seconds_per_slot,
+            )
+            .await?;
+        Ok((beacon_chain, head_state, anchor_root, head_root, data_dir))
+    }
+
+    #[tokio::test]
+    pub async fn test_validate_beacon_attestation_data_index() -> anyhow::Result<()> {
+        initialize_test_network_spec();
+        let (beacon_chain, state, anchor_root, head_root, _data_dir) = beacon_chain().await?;
+        assert_eq!(beacon_chain.head_root().await?, head_root);
+        let cached_db = CachedDB::default();
+        let slot = state.slot;
+        let subnet_id = compute_subnet_for_attestation(1, slot, 0);
+
+        // [REJECT] attestation.data.index == 0
+        let attestation = single_attestation(&state, slot, head_root, anchor_root, 1)?;
+        let (result, attestation) =
+            validate_beacon_attestation(&attestation, &beacon_chain, subnet_id, &cached_db).await?;
+        assert_eq!(
+            result,
+            ValidationResult::Reject("Committee index must be 0".to_string())
+        );
+        assert!(attestation.is_none());
+
+        // The attestation of committee 0 is accepted
+        let attestation = single_attestation(&state, slot, head_root, anchor_root, 0)?;
+        let (result, accepted_attestation) =
+            validate_beacon_attestation(&attestation, &beacon_chain, subnet_id, &cached_db).await?;
+        assert_eq!(result, ValidationResult::Accept);
+        let accepted_attestation =
+            accepted_attestation.expect("An accepted attestation is returned");
+        assert_eq!(accepted_attestation.data, attestation.data);
+
+        Ok(())
+    }
+}
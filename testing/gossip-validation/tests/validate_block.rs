@@ -69,7 +69,7 @@ mod tests {
 
         let operation_pool = OperationPool::default();
         let cached_db = CachedDB::default();
-        let beacon_chain = BeaconChain::new(db, operation_pool.into(), None);
+        let beacon_chain = BeaconChain::new(db, operation_pool.into(), None, vec![], false, false);
 
         (beacon_chain, cached_db, block_root)
     }
@@ -152,12 +152,25 @@ mod tests {
                 .unwrap()
         );
 
-        let result =
+        let (result, proposer_slashing) =
             validate_gossip_beacon_block(&beacon_chain, &cached_db, &incoming_beacon_block)
                 .await
                 .unwrap();
 
         assert!(result == ValidationResult::Accept);
+        assert!(proposer_slashing.is_none());
+
+        // A second block of the proposer for the slot is ignored, and only proves an equivocation
+        // if the proposer signed it
+        let mut second_beacon_block = incoming_beacon_block.clone();
+        second_beacon_block.message.body.graffiti = B256::repeat_byte(1);
+        let (result, proposer_slashing) =
+            validate_gossip_beacon_block(&beacon_chain, &cached_db, &second_beacon_block)
+                .await
+                .unwrap();
+
+        assert!(matches!(result, ValidationResult::Ignore(_)));
+        assert!(proposer_slashing.is_none());
     }
 
     fn read_ssz_snappy_file<T: Decode>(path: &str) -> anyhow::Result<T> {
@@ -0,0 +1,262 @@
+use std::{
+    future::Future,
+    net::{IpAddr, Ipv4Addr},
+    num::NonZeroUsize,
+    sync::{
+        Arc,
+        atomic::{AtomicU16, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail, ensure};
+use discv5::Enr;
+use libp2p::PeerId;
+use ream_beacon_chain::beacon_chain::BeaconChain;
+use ream_consensus_beacon::{
+    electra::{beacon_block::BeaconBlock, beacon_state::BeaconState},
+    test_utils::{electra_anchor, genesis_state},
+};
+use ream_executor::ReamExecutor;
+use ream_fork_choice::store::get_forkchoice_store;
+use ream_network_manager::{
+    clock::SlotClock, config::ManagerConfig, service::NetworkManagerService,
+};
+use ream_network_spec::networks::{beacon_network_spec, initialize_test_network_spec};
+use ream_operation_pool::OperationPool;
+use ream_p2p::{
+    bootnodes::Bootnodes,
+    channel::{GossipMessage, P2PMessage},
+    config::TransportKind,
+    gossipsub::topics::GossipTopic,
+    network::beacon::Network,
+    network_state::NetworkState,
+};
+use ream_storage::{cache::CachedDB, db::ReamDB};
+use tempdir::TempDir;
+use tokio::{
+    sync::{mpsc, watch},
+    time::sleep,
+};
+
+/// The memory transport is shared by the whole process, so every node gets its own port even
+/// across simulators running in parallel tests.
+static NEXT_MEMORY_PORT: AtomicU16 = AtomicU16::new(1);
+
+/// How often `NetworkSimulator::wait_until` checks its condition.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Returns an Electra state at genesis without validators and the block it is the post-state of,
+/// an anchor for the scenarios which do not need blocks to be produced.
+pub fn empty_anchor(genesis_time: u64) -> (BeaconState, BeaconBlock) {
+    initialize_test_network_spec();
+    electra_anchor(BeaconState {
+        genesis_time,
+        ..genesis_state()
+    })
+}
+
+/// A beacon node of a `NetworkSimulator`.
+pub struct SimulatedNode {
+    pub beacon_chain: Arc<BeaconChain>,
+    pub network_state: Arc<NetworkState>,
+    pub cached_db: Arc<CachedDB>,
+    pub ream_db: ReamDB,
+    p2p_sender: mpsc::UnboundedSender<P2PMessage>,
+    _data_dir: TempDir,
+}
+
+impl SimulatedNode {
+    pub fn enr(&self) -> Enr {
+        self.network_state.local_enr.read().clone()
+    }
+
+    pub fn peer_id(&self) -> PeerId {
+        Network::peer_id_from_enr(&self.enr()).expect("Node ENR has a secp256k1 key")
+    }
+
+    pub fn connected_peers(&self) -> Vec<PeerId> {
+        self.network_state
+            .connected_peers()
+            .into_iter()
+            .map(|peer| peer.peer_id)
+            .collect()
+    }
+
+    /// Publishes `data`, which must already be SSZ encoded, on `topic`.
+    pub fn publish(&self, topic: GossipTopic, data: Vec<u8>) -> anyhow::Result<()> {
+        self.p2p_sender
+            .send(P2PMessage::Gossip(GossipMessage { topic, data }))
+            .map_err(|err| anyhow!("Failed to publish gossip message: {err}"))
+    }
+
+    /// Returns the current slot of the fork choice store, or `None` while the store is busy, so
+    /// it can be checked from `NetworkSimulator::wait_until`.
+    pub fn current_slot(&self) -> Option<u64> {
+        self.beacon_chain
+            .store
+            .try_lock()
+            .ok()
+            .and_then(|store| store.get_current_slot().ok())
+    }
+}
+
+/// Runs several beacon nodes in one process, connected over the in-memory transport and driven
+/// by a shared manual clock, so multi-node scenarios can be tested without sockets or waiting for
+/// real slots.
+///
+/// Every node starts from the same anchor state and block, and dials all the nodes added before
+/// it.
+pub struct NetworkSimulator {
+    executor: ReamExecutor,
+    clock: watch::Sender<u64>,
+    anchor_state: BeaconState,
+    anchor_block: BeaconBlock,
+    nodes: Vec<SimulatedNode>,
+}
+
+impl NetworkSimulator {
+    /// Creates a simulator without nodes, whose clock is at the slot of `anchor_state`.
+    pub fn new(anchor_state: BeaconState, anchor_block: BeaconBlock) -> anyhow::Result<Self> {
+        initialize_test_network_spec();
+        ensure!(
+            anchor_state.genesis_validators_root == genesis_validators_root(),
+            "The anchor state must have the genesis validators root of the test network"
+        );
+
+        let time =
+            anchor_state.genesis_time + beacon_network_spec().seconds_per_slot * anchor_state.slot;
+        let (clock, _) = watch::channel(time);
+
+        Ok(Self {
+            executor: ReamExecutor::new()?,
+            clock,
+            anchor_state,
+            anchor_block,
+            nodes: vec![],
+        })
+    }
+
+    /// Starts a new node connected to all the existing nodes, and returns its index.
+    pub fn add_node(&mut self) -> anyhow::Result<usize> {
+        let data_dir = TempDir::new("ream_simulated_node")?;
+        let ream_db = ReamDB::new(data_dir.path().to_path_buf())?;
+        get_forkchoice_store(
+            self.anchor_state.clone(),
+            self.anchor_block.clone(),
+            ream_db.clone(),
+        )?;
+
+        let port = NEXT_MEMORY_PORT.fetch_add(1, Ordering::Relaxed);
+        let config = ManagerConfig {
+            http_address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            http_port: 0,
+            http_allow_origin: false,
+            socket_address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            socket_port: port,
+            discovery_port: port,
+            disable_discovery: true,
+            data_dir: Some(data_dir.path().to_path_buf()),
+            ephemeral: true,
+            bootnodes: Bootnodes::Custom(self.nodes.iter().map(SimulatedNode::enr).collect()),
+            checkpoint_sync_url: None,
+            purge_db: false,
+            execution_endpoint: None,
+            execution_jwt_secret: None,
+            monitored_validators: vec![],
+            validator_income_history: false,
+            blinded_block_storage: false,
+            seen_message_cache_capacity: NonZeroUsize::new(1 << 12).expect("Not zero"),
+            transport: TransportKind::Memory,
+        };
+
+        let executor = self.executor.clone();
+        let ream_dir = data_dir.path().to_path_buf();
+        let network_manager = self
+            .block_on({
+                let ream_db = ream_db.clone();
+                async move {
+                    NetworkManagerService::new(
+                        executor,
+                        config,
+                        ream_db,
+                        ream_dir,
+                        Arc::new(OperationPool::default()),
+                    )
+                    .await
+                }
+            })?
+            .with_clock(SlotClock::manual(self.clock.subscribe()));
+
+        let node = SimulatedNode {
+            beacon_chain: network_manager.beacon_chain.clone(),
+            network_state: network_manager.network_state.clone(),
+            cached_db: network_manager.cached_db.clone(),
+            ream_db,
+            p2p_sender: network_manager.p2p_sender(),
+            _data_dir: data_dir,
+        };
+        self.executor.spawn(network_manager.start());
+        self.nodes.push(node);
+
+        Ok(self.nodes.len() - 1)
+    }
+
+    pub fn node(&self, index: usize) -> &SimulatedNode {
+        &self.nodes[index]
+    }
+
+    pub fn nodes(&self) -> &[SimulatedNode] {
+        &self.nodes
+    }
+
+    /// Returns the time of the clock in seconds since the UNIX epoch.
+    pub fn time(&self) -> u64 {
+        *self.clock.borrow()
+    }
+
+    /// Moves the clock of all the nodes forward by `slots`.
+    pub fn advance_slots(&self, slots: u64) {
+        self.clock
+            .send_modify(|time| *time += beacon_network_spec().seconds_per_slot * slots);
+    }
+
+    /// Runs `future` on the runtime of the nodes.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.executor.runtime().block_on(future)
+    }
+
+    /// Waits until `condition` holds for the nodes, or fails after `timeout`.
+    pub fn wait_until(
+        &self,
+        timeout: Duration,
+        condition: impl Fn(&[SimulatedNode]) -> bool,
+    ) -> anyhow::Result<()> {
+        let deadline = Instant::now() + timeout;
+        self.block_on(async {
+            while !condition(&self.nodes) {
+                if Instant::now() >= deadline {
+                    bail!("Condition not reached within {timeout:?}");
+                }
+                sleep(POLL_INTERVAL).await;
+            }
+            Ok(())
+        })
+    }
+
+    /// Waits until every node is connected to all the other nodes.
+    pub fn wait_for_full_mesh(&self, timeout: Duration) -> anyhow::Result<()> {
+        let expected_peers = self.nodes.len().saturating_sub(1);
+        self.wait_until(timeout, |nodes| {
+            nodes
+                .iter()
+                .all(|node| node.connected_peers().len() == expected_peers)
+        })
+    }
+}
+
+impl Drop for NetworkSimulator {
+    fn drop(&mut self) {
+        self.executor.shutdown_signal();
+    }
+}
@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use network_simulator::{NetworkSimulator, empty_anchor};
+use ream_network_spec::networks::{beacon_network_spec, initialize_test_network_spec};
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+fn create_simulator() -> anyhow::Result<NetworkSimulator> {
+    initialize_test_network_spec();
+    let (anchor_state, anchor_block) = empty_anchor(beacon_network_spec().min_genesis_time);
+    NetworkSimulator::new(anchor_state, anchor_block)
+}
+
+#[test]
+fn test_nodes_connect_over_memory_transport() -> anyhow::Result<()> {
+    let mut simulator = create_simulator()?;
+    for _ in 0..3 {
+        simulator.add_node()?;
+    }
+
+    simulator.wait_for_full_mesh(TIMEOUT)?;
+
+    // The last node dialed the others, and learns their status from the handshake
+    let last_node = simulator.node(2);
+    simulator.wait_until(TIMEOUT, |_| {
+        last_node
+            .network_state
+            .connected_peers()
+            .iter()
+            .all(|peer| peer.status.is_some())
+    })?;
+
+    Ok(())
+}
+
+#[test]
+fn test_manual_clock_drives_all_nodes() -> anyhow::Result<()> {
+    let mut simulator = create_simulator()?;
+    for _ in 0..2 {
+        simulator.add_node()?;
+    }
+
+    simulator.wait_until(TIMEOUT, |nodes| {
+        nodes.iter().all(|node| node.current_slot() == Some(0))
+    })?;
+
+    simulator.advance_slots(3);
+    simulator.wait_until(TIMEOUT, |nodes| {
+        nodes.iter().all(|node| node.current_slot() == Some(3))
+    })?;
+
+    Ok(())
+}